@@ -12,6 +12,10 @@ pub use use_cases::{
     AddLiquidityCommand,
     AddLiquidityExecutionResult,
     // Order management
+    AmendError,
+    AmendOrderCommand,
+    AmendOrderResult,
+    AmendOrderUseCase,
     CancelError,
     CancelOrderCommand,
     CancelOrderResult,
@@ -30,11 +34,36 @@ pub use use_cases::{
     GetDepthQuery,
     GetDepthUseCase,
     GetExchangeInfoUseCase,
+    GetMarketByOrderQuery,
+    GetMarketByOrderUseCase,
+    // Spot margin
+    IsolatedMarginAccountsSummary,
+    IsolatedMarginSummary,
+    IsolatedTransferCommand,
+    IsolatedTransferDirection,
     LiquidityAddedEvent,
     LiquidityRemovedEvent,
     LiquidityUseCase,
     LiquidityUseCaseError,
+    MarginAccountSummary,
+    MarginAssetSummary,
+    MarginConfig,
+    MarginError,
+    MarginLiquidationReport,
+    MarginLoanCommand,
+    MarginMaintenanceResult,
+    MarginTransaction,
+    MarginUseCase,
+    MarketByOrderResult,
+    // Option marks
+    OptionMark,
+    OptionMarkConfig,
+    OptionMarkEvent,
+    OptionMarkQuery,
+    OptionMarkRefresh,
+    OptionMarksUseCase,
     OrderError,
+    PortfolioMarginCache,
     ProcessDepositError,
     ProcessDepositUseCase,
     ProcessDepositsResult,
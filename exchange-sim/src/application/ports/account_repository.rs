@@ -16,6 +16,17 @@ pub trait AccountRepository: Send + Sync {
     /// Save an account (insert or update)
     async fn save(&self, account: Account);
 
+    /// Read-modify-write an account atomically with respect to other writes
+    ///
+    /// `f` returns whether it changed the account; the account is written
+    /// back only when it did. Returns `None` if the account does not exist,
+    /// otherwise what `f` returned.
+    async fn update(
+        &self,
+        id: AccountId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Account) -> bool + Send),
+    ) -> Option<bool>;
+
     /// Get or create an account for an owner
     async fn get_or_create(&self, owner_id: &str) -> Account;
 
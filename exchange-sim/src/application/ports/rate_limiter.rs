@@ -16,6 +16,8 @@ pub struct RateLimitConfig {
     pub ws_connections_per_ip: u32,
     /// WebSocket message rate per second
     pub ws_messages_per_second: u32,
+    /// Ban (HTTP 418) for clients that keep sending after a 429
+    pub ip_ban_duration: Duration,
 }
 
 impl Default for RateLimitConfig {
@@ -28,6 +30,7 @@ impl Default for RateLimitConfig {
             request_weight_per_minute: 1200,
             ws_connections_per_ip: 5,
             ws_messages_per_second: 5,
+            ip_ban_duration: Duration::from_secs(120),
         }
     }
 }
@@ -0,0 +1,209 @@
+use crate::application::ports::{
+    EventPublisher, OrderBookReader, OrderBookWriter, RequestRateLimiter,
+};
+use crate::domain::{
+    Clock, DepthUpdateEvent, ExchangeEvent, Order, OrderId, OrderValidator, Quantity, Symbol,
+};
+use std::sync::Arc;
+
+/// Reduce a resting order's quantity, keeping its price and queue priority
+#[derive(Debug, Clone)]
+pub struct AmendOrderCommand {
+    pub symbol: String,
+    pub order_id: Option<OrderId>,
+    pub client_order_id: Option<String>,
+    /// New total quantity; must be below the current quantity and above
+    /// what has already filled
+    pub new_quantity: Quantity,
+    /// Client order ID the order carries after the amend
+    pub new_client_order_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AmendOrderResult {
+    /// The order after the amend
+    pub order: Order,
+    /// Client order ID before the amend
+    pub orig_client_order_id: Option<String>,
+}
+
+pub struct AmendOrderUseCase<C, OB, E, R>
+where
+    C: Clock,
+    OB: OrderBookReader + OrderBookWriter,
+    E: EventPublisher,
+    R: RequestRateLimiter,
+{
+    clock: Arc<C>,
+    order_book_repo: Arc<OB>,
+    event_publisher: Arc<E>,
+    rate_limiter: Arc<R>,
+}
+
+impl<C, OB, E, R> AmendOrderUseCase<C, OB, E, R>
+where
+    C: Clock,
+    OB: OrderBookReader + OrderBookWriter,
+    E: EventPublisher,
+    R: RequestRateLimiter,
+{
+    pub fn new(
+        clock: Arc<C>,
+        order_book_repo: Arc<OB>,
+        event_publisher: Arc<E>,
+        rate_limiter: Arc<R>,
+    ) -> Self {
+        Self {
+            clock,
+            order_book_repo,
+            event_publisher,
+            rate_limiter,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        client_id: &str,
+        command: AmendOrderCommand,
+    ) -> Result<AmendOrderResult, AmendError> {
+        // Check rate limit
+        let rate_result = self.rate_limiter.check_request(client_id, 1).await;
+        if !rate_result.allowed {
+            return Err(AmendError::RateLimited {
+                retry_after_ms: rate_result.retry_after.map(|d| d.as_millis() as u64),
+            });
+        }
+
+        // Parse symbol
+        let symbol =
+            Symbol::new(&command.symbol).map_err(|e| AmendError::InvalidSymbol(e.to_string()))?;
+
+        // Get order book
+        let mut book = self
+            .order_book_repo
+            .get(&symbol)
+            .await
+            .ok_or_else(|| AmendError::SymbolNotFound(command.symbol.clone()))?;
+
+        // Find the order
+        let order_id = if let Some(id) = command.order_id {
+            id
+        } else if let Some(client_order_id) = &command.client_order_id {
+            book.find_by_client_order_id(client_order_id)
+                .ok_or(AmendError::OrderNotFound)?
+                .id
+        } else {
+            return Err(AmendError::MissingOrderId);
+        };
+
+        let order = book
+            .get_order(order_id)
+            .ok_or(AmendError::OrderNotFound)?
+            .clone();
+
+        // An amend is a cancel of part of the order
+        OrderValidator::validate_cancel(&order)
+            .map_err(|e| AmendError::ValidationFailed(e.message))?;
+        if command.new_quantity >= order.quantity || command.new_quantity <= order.filled_quantity {
+            return Err(AmendError::InvalidQuantity {
+                quantity: order.quantity,
+                filled: order.filled_quantity,
+            });
+        }
+
+        // Capture sequence before the reduce for depth update
+        let first_update_id = book.sequence() + 1;
+
+        book.reduce_order(order_id, order.quantity - command.new_quantity)
+            .ok_or(AmendError::OrderNotFound)?;
+        if let Some(new_client_order_id) = command.new_client_order_id {
+            book.set_client_order_id(order_id, new_client_order_id);
+        }
+        let amended = book
+            .get_order(order_id)
+            .ok_or(AmendError::OrderNotFound)?
+            .clone();
+
+        // Capture depth state for depth update
+        let now = self.clock.now();
+        let final_update_id = book.sequence();
+        let deltas = book.take_deltas();
+        let (current_bids, current_asks) = book.depth_update_levels(&deltas, 20);
+
+        // Save book
+        self.order_book_repo.save(book).await;
+
+        // Publish depth update event
+        let depth_update = DepthUpdateEvent::new(
+            &symbol,
+            first_update_id,
+            final_update_id,
+            current_bids,
+            current_asks,
+            self.clock.now_millis(),
+        );
+        self.event_publisher
+            .publish_to_symbol(symbol.as_str(), ExchangeEvent::DepthUpdate(depth_update))
+            .await;
+
+        // Publish order-level (L3) changes
+        for delta in deltas {
+            self.event_publisher
+                .publish_to_symbol(
+                    symbol.as_str(),
+                    ExchangeEvent::MarketByOrder(delta.into_event(&symbol, now)),
+                )
+                .await;
+        }
+
+        Ok(AmendOrderResult {
+            order: amended,
+            orig_client_order_id: order.client_order_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AmendError {
+    RateLimited {
+        retry_after_ms: Option<u64>,
+    },
+    InvalidSymbol(String),
+    SymbolNotFound(String),
+    OrderNotFound,
+    MissingOrderId,
+    ValidationFailed(String),
+    /// The new quantity does not reduce the open quantity
+    InvalidQuantity {
+        quantity: Quantity,
+        filled: Quantity,
+    },
+}
+
+impl std::fmt::Display for AmendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmendError::RateLimited { retry_after_ms } => {
+                write!(f, "Rate limited")?;
+                if let Some(ms) = retry_after_ms {
+                    write!(f, ", retry after {}ms", ms)?;
+                }
+                Ok(())
+            }
+            AmendError::InvalidSymbol(s) => write!(f, "Invalid symbol: {}", s),
+            AmendError::SymbolNotFound(s) => write!(f, "Symbol not found: {}", s),
+            AmendError::OrderNotFound => write!(f, "Order not found"),
+            AmendError::MissingOrderId => {
+                write!(f, "Either orderId or origClientOrderId must be provided")
+            }
+            AmendError::ValidationFailed(s) => write!(f, "Validation failed: {}", s),
+            AmendError::InvalidQuantity { quantity, filled } => write!(
+                f,
+                "New quantity must be below {} and above the filled {}",
+                quantity, filled
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AmendError {}
@@ -3,7 +3,7 @@ use crate::application::ports::{
 };
 use crate::domain::{
     Clock, DepthUpdateEvent, ExchangeEvent, Order, OrderCanceledEvent, OrderId, OrderValidator,
-    PriceLevel, Symbol,
+    Symbol,
 };
 use std::sync::Arc;
 
@@ -80,9 +80,10 @@ where
         // Find the order
         let order_id = if let Some(id) = command.order_id {
             id
-        } else if let Some(_client_id) = &command.client_order_id {
-            // Would need to search by client order ID - not implemented for simplicity
-            return Err(CancelError::OrderNotFound);
+        } else if let Some(client_order_id) = &command.client_order_id {
+            book.find_by_client_order_id(client_order_id)
+                .ok_or(CancelError::OrderNotFound)?
+                .id
         } else {
             return Err(CancelError::MissingOrderId);
         };
@@ -111,8 +112,8 @@ where
 
         // Capture depth state for depth update
         let final_update_id = book.sequence();
-        let current_bids: Vec<PriceLevel> = book.get_bids(20);
-        let current_asks: Vec<PriceLevel> = book.get_asks(20);
+        let deltas = book.take_deltas();
+        let (current_bids, current_asks) = book.depth_update_levels(&deltas, 20);
 
         // Save book
         self.order_book_repo.save(book).await;
@@ -130,6 +131,16 @@ where
             .publish_to_symbol(symbol.as_str(), ExchangeEvent::DepthUpdate(depth_update))
             .await;
 
+        // Publish order-level (L3) changes
+        for delta in deltas {
+            self.event_publisher
+                .publish_to_symbol(
+                    symbol.as_str(),
+                    ExchangeEvent::MarketByOrder(delta.into_event(&symbol, now)),
+                )
+                .await;
+        }
+
         // Publish cancel event
         self.event_publisher
             .publish_to_symbol(
@@ -0,0 +1,71 @@
+use crate::application::ports::{OrderBookReader, RequestRateLimiter};
+use crate::application::use_cases::DepthError;
+use crate::domain::{RestingOrder, Side, Symbol};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct GetMarketByOrderQuery {
+    pub symbol: String,
+}
+
+/// Every resting order in queue order, as of `last_update_id`
+///
+/// Market-by-order events with a sequence above `last_update_id` apply on
+/// top of this snapshot.
+#[derive(Debug, Clone)]
+pub struct MarketByOrderResult {
+    pub last_update_id: u64,
+    pub bids: Vec<RestingOrder>,
+    pub asks: Vec<RestingOrder>,
+}
+
+pub struct GetMarketByOrderUseCase<OB, R>
+where
+    OB: OrderBookReader,
+    R: RequestRateLimiter,
+{
+    order_book_repo: Arc<OB>,
+    rate_limiter: Arc<R>,
+}
+
+impl<OB, R> GetMarketByOrderUseCase<OB, R>
+where
+    OB: OrderBookReader,
+    R: RequestRateLimiter,
+{
+    pub fn new(order_book_repo: Arc<OB>, rate_limiter: Arc<R>) -> Self {
+        Self {
+            order_book_repo,
+            rate_limiter,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        client_id: &str,
+        query: GetMarketByOrderQuery,
+    ) -> Result<MarketByOrderResult, DepthError> {
+        // Full book, weighted like the deepest depth request
+        let rate_result = self.rate_limiter.check_request(client_id, 50).await;
+        if !rate_result.allowed {
+            return Err(DepthError::RateLimited {
+                retry_after_ms: rate_result.retry_after.map(|d| d.as_millis() as u64),
+            });
+        }
+
+        let symbol =
+            Symbol::new(&query.symbol).map_err(|e| DepthError::InvalidSymbol(e.to_string()))?;
+
+        let book = self
+            .order_book_repo
+            .get(&symbol)
+            .await
+            .ok_or_else(|| DepthError::SymbolNotFound(query.symbol.clone()))?;
+
+        Ok(MarketByOrderResult {
+            last_update_id: book.sequence(),
+            bids: book.resting_orders(Side::Buy),
+            asks: book.resting_orders(Side::Sell),
+        })
+    }
+}
@@ -0,0 +1,731 @@
+//! Spot Margin Use Cases
+//!
+//! Borrowing, repayment and isolated-margin transfers for margin accounts,
+//! plus the periodic maintenance pass that accrues hourly interest and
+//! liquidates wallets whose margin level has fallen too far.
+
+use crate::application::ports::{
+    AccountRepository, InstrumentRepository, OrderBookReader, RequestRateLimiter,
+};
+use crate::domain::{
+    Account, AccountError, AccountMarginCalculator, AssetBalance, AssetPrices, Clock,
+    InstrumentType, MARGIN_LEVEL_INITIAL, MARGIN_LEVEL_LIQUIDATION, MarginLiquidation,
+    MarginStatus, MarginTotals, Rate, Symbol, Timestamp, Value,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Interest is charged on the hour
+const INTEREST_PERIOD_MS: i64 = 60 * 60 * 1000;
+
+static NEXT_TRAN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Margin lending configuration
+#[derive(Debug, Clone)]
+pub struct MarginConfig {
+    /// Asset that margin wallets are valued in
+    pub quote_asset: String,
+    /// Annual interest rate for assets without a specific rate
+    pub default_interest_rate: Rate,
+    /// Annual interest rate per asset
+    pub interest_rates: HashMap<String, Rate>,
+    /// Fee charged on collateral seized during liquidation
+    pub liquidation_fee: Rate,
+}
+
+impl Default for MarginConfig {
+    fn default() -> Self {
+        Self {
+            quote_asset: "USDT".to_string(),
+            default_interest_rate: Rate::from_bps(1000), // 10% a year
+            interest_rates: HashMap::new(),
+            liquidation_fee: Rate::from_bps(100), // 1%
+        }
+    }
+}
+
+impl MarginConfig {
+    pub fn interest_rate(&self, asset: &str) -> Rate {
+        self.interest_rates
+            .get(asset)
+            .copied()
+            .unwrap_or(self.default_interest_rate)
+    }
+}
+
+/// Command to borrow or repay on margin
+///
+/// `symbol` selects an isolated margin wallet; `None` means cross margin.
+#[derive(Debug, Clone)]
+pub struct MarginLoanCommand {
+    pub asset: String,
+    pub amount: Value,
+    pub symbol: Option<String>,
+}
+
+/// Direction of an isolated margin transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolatedTransferDirection {
+    /// Main balances into the isolated wallet
+    ToIsolated,
+    /// Isolated wallet back to the main balances
+    ToSpot,
+}
+
+/// Command to move funds in or out of an isolated margin wallet
+#[derive(Debug, Clone)]
+pub struct IsolatedTransferCommand {
+    pub asset: String,
+    pub symbol: String,
+    pub amount: Value,
+    pub direction: IsolatedTransferDirection,
+}
+
+/// Result of a borrow, repay or transfer
+#[derive(Debug, Clone)]
+pub struct MarginTransaction {
+    pub tran_id: u64,
+    pub amount: Value,
+}
+
+/// One asset of a margin wallet
+#[derive(Debug, Clone)]
+pub struct MarginAssetSummary {
+    pub asset: String,
+    pub balance: AssetBalance,
+}
+
+/// Cross margin wallet valuation
+#[derive(Debug, Clone)]
+pub struct MarginAccountSummary {
+    pub assets: Vec<MarginAssetSummary>,
+    pub totals: MarginTotals,
+    pub status: MarginStatus,
+    /// Prices the wallet was valued at
+    pub prices: AssetPrices,
+}
+
+/// Isolated margin wallet valuation
+#[derive(Debug, Clone)]
+pub struct IsolatedMarginSummary {
+    pub symbol: Symbol,
+    pub base: MarginAssetSummary,
+    pub quote: MarginAssetSummary,
+    pub totals: MarginTotals,
+    pub status: MarginStatus,
+}
+
+/// Isolated margin wallets, valued at `prices`
+#[derive(Debug, Clone)]
+pub struct IsolatedMarginAccountsSummary {
+    pub wallets: Vec<IsolatedMarginSummary>,
+    pub prices: AssetPrices,
+}
+
+/// A margin wallet liquidated during maintenance
+#[derive(Debug, Clone)]
+pub struct MarginLiquidationReport {
+    pub owner_id: String,
+    /// Isolated symbol, or `None` for cross margin
+    pub symbol: Option<Symbol>,
+    /// Margin level that triggered the liquidation (scaled by PRICE_SCALE)
+    pub margin_level: i64,
+    pub liquidation: MarginLiquidation,
+}
+
+/// Outcome of one maintenance pass
+#[derive(Debug, Clone, Default)]
+pub struct MarginMaintenanceResult {
+    /// Accounts with margin debt that interest was accrued on
+    pub accounts_accrued: usize,
+    pub liquidations: Vec<MarginLiquidationReport>,
+}
+
+/// Use case for spot margin trading
+pub struct MarginUseCase<C, A, OB, I, R>
+where
+    C: Clock,
+    A: AccountRepository,
+    OB: OrderBookReader,
+    I: InstrumentRepository,
+    R: RequestRateLimiter,
+{
+    clock: Arc<C>,
+    account_repo: Arc<A>,
+    order_book_repo: Arc<OB>,
+    instrument_repo: Arc<I>,
+    rate_limiter: Arc<R>,
+    config: MarginConfig,
+}
+
+impl<C, A, OB, I, R> MarginUseCase<C, A, OB, I, R>
+where
+    C: Clock,
+    A: AccountRepository,
+    OB: OrderBookReader,
+    I: InstrumentRepository,
+    R: RequestRateLimiter,
+{
+    pub fn new(
+        clock: Arc<C>,
+        account_repo: Arc<A>,
+        order_book_repo: Arc<OB>,
+        instrument_repo: Arc<I>,
+        rate_limiter: Arc<R>,
+    ) -> Self {
+        Self {
+            clock,
+            account_repo,
+            order_book_repo,
+            instrument_repo,
+            rate_limiter,
+            config: MarginConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: MarginConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Borrow an asset on cross or isolated margin
+    ///
+    /// The wallet must stay at or above the initial margin level afterwards.
+    pub async fn borrow(
+        &self,
+        client_id: &str,
+        command: MarginLoanCommand,
+    ) -> Result<MarginTransaction, MarginError> {
+        self.check_rate_limit(client_id, 1).await?;
+        if command.amount.raw() <= 0 {
+            return Err(MarginError::InvalidAmount);
+        }
+
+        let now = self.clock.now();
+        let prices = self.asset_prices().await;
+        if prices.price(&command.asset).is_none() {
+            return Err(MarginError::UnsupportedAsset(command.asset));
+        }
+
+        let mut account = self.account_repo.get_or_create(client_id).await;
+        let rate = self.config.interest_rate(&command.asset);
+        let totals = match &command.symbol {
+            None => {
+                account
+                    .margin_borrow(&command.asset, command.amount, rate, now)
+                    .map_err(MarginError::AccountError)?;
+                account.cross_margin_totals(&prices)
+            }
+            Some(symbol) => {
+                let symbol = parse_symbol(symbol)?;
+                account
+                    .isolated_borrow(&symbol, &command.asset, command.amount, rate, now)
+                    .map_err(MarginError::AccountError)?;
+                account
+                    .isolated_margin_totals(&symbol, &prices)
+                    .unwrap_or_default()
+            }
+        };
+
+        if totals.margin_level() < MARGIN_LEVEL_INITIAL {
+            return Err(MarginError::ExceedsBorrowLimit);
+        }
+
+        self.account_repo.save(account).await;
+        Ok(transaction(command.amount))
+    }
+
+    /// Repay a cross or isolated margin loan, interest first
+    pub async fn repay(
+        &self,
+        client_id: &str,
+        command: MarginLoanCommand,
+    ) -> Result<MarginTransaction, MarginError> {
+        self.check_rate_limit(client_id, 1).await?;
+        if command.amount.raw() <= 0 {
+            return Err(MarginError::InvalidAmount);
+        }
+
+        let now = self.clock.now();
+        let mut account = self.account_repo.get_or_create(client_id).await;
+        let repaid = match &command.symbol {
+            None => account.margin_repay(&command.asset, command.amount, now),
+            Some(symbol) => {
+                let symbol = parse_symbol(symbol)?;
+                account.isolated_repay(&symbol, &command.asset, command.amount, now)
+            }
+        }
+        .map_err(MarginError::AccountError)?;
+
+        self.account_repo.save(account).await;
+        Ok(transaction(repaid))
+    }
+
+    /// Move funds between the main balances and an isolated margin wallet
+    pub async fn transfer_isolated(
+        &self,
+        client_id: &str,
+        command: IsolatedTransferCommand,
+    ) -> Result<MarginTransaction, MarginError> {
+        self.check_rate_limit(client_id, 1).await?;
+        if command.amount.raw() <= 0 {
+            return Err(MarginError::InvalidAmount);
+        }
+
+        let symbol = parse_symbol(&command.symbol)?;
+        let pair = self
+            .instrument_repo
+            .get(&symbol)
+            .await
+            .ok_or_else(|| MarginError::SymbolNotFound(command.symbol.clone()))?;
+
+        let now = self.clock.now();
+        let mut account = self.account_repo.get_or_create(client_id).await;
+        match command.direction {
+            IsolatedTransferDirection::ToIsolated => account
+                .transfer_to_isolated(&pair, &command.asset, command.amount, now)
+                .map_err(MarginError::AccountError)?,
+            IsolatedTransferDirection::ToSpot => {
+                account
+                    .transfer_from_isolated(&symbol, &command.asset, command.amount, now)
+                    .map_err(MarginError::AccountError)?;
+                let prices = self.asset_prices().await;
+                let totals = account
+                    .isolated_margin_totals(&symbol, &prices)
+                    .unwrap_or_default();
+                if totals.margin_level() < MARGIN_LEVEL_INITIAL {
+                    return Err(MarginError::ExceedsTransferLimit);
+                }
+            }
+        }
+
+        self.account_repo.save(account).await;
+        Ok(transaction(command.amount))
+    }
+
+    /// Value the cross margin wallet
+    pub async fn account(&self, client_id: &str) -> Result<MarginAccountSummary, MarginError> {
+        self.check_rate_limit(client_id, 10).await?;
+
+        let account = self.account_repo.get_or_create(client_id).await;
+        let prices = self.asset_prices().await;
+        let calc = AccountMarginCalculator::default();
+
+        let mut assets: Vec<MarginAssetSummary> = account
+            .all_balances()
+            .map(|(asset, balance)| MarginAssetSummary {
+                asset: asset.clone(),
+                balance: *balance,
+            })
+            .collect();
+        assets.sort_by(|a, b| a.asset.cmp(&b.asset));
+
+        let totals = account.cross_margin_totals(&prices);
+        Ok(MarginAccountSummary {
+            assets,
+            totals,
+            status: calc.margin_level_status(totals.margin_level()),
+            prices,
+        })
+    }
+
+    /// Value isolated margin wallets, optionally only the given symbols
+    pub async fn isolated_accounts(
+        &self,
+        client_id: &str,
+        symbols: &[String],
+    ) -> Result<IsolatedMarginAccountsSummary, MarginError> {
+        self.check_rate_limit(client_id, 10).await?;
+
+        let filter = symbols
+            .iter()
+            .map(|s| parse_symbol(s))
+            .collect::<Result<Vec<_>, _>>()?;
+        let account = self.account_repo.get_or_create(client_id).await;
+        let prices = self.asset_prices().await;
+        let calc = AccountMarginCalculator::default();
+
+        let mut wallets: Vec<IsolatedMarginSummary> = account
+            .isolated_margins()
+            .filter(|w| filter.is_empty() || filter.contains(&w.symbol))
+            .map(|wallet| {
+                let totals = calc.margin_totals(wallet.all_balances(), &prices);
+                let summary = |asset: &str| MarginAssetSummary {
+                    asset: asset.to_string(),
+                    balance: wallet.balance(asset),
+                };
+                IsolatedMarginSummary {
+                    symbol: wallet.symbol.clone(),
+                    base: summary(&wallet.base_asset),
+                    quote: summary(&wallet.quote_asset),
+                    totals,
+                    status: calc.margin_level_status(totals.margin_level()),
+                }
+            })
+            .collect();
+        wallets.sort_by(|a, b| a.symbol.as_str().cmp(b.symbol.as_str()));
+
+        Ok(IsolatedMarginAccountsSummary { wallets, prices })
+    }
+
+    /// Accrue interest up to the last full hour and liquidate unsafe wallets
+    ///
+    /// Meant to be called periodically; accrual is driven by the clock, so
+    /// calling more than once within the same hour charges nothing extra and
+    /// leaves the account untouched.
+    pub async fn run_maintenance(&self) -> MarginMaintenanceResult {
+        let now = self.clock.now();
+        let accrue_until = last_interest_boundary(now);
+        let prices = self.asset_prices().await;
+        let mut result = MarginMaintenanceResult::default();
+
+        // The listing only picks candidates; each account is re-read and
+        // written under the repository's lock so concurrent order handling
+        // is never overwritten
+        for candidate in self.account_repo.list().await {
+            if !candidate.has_margin_debt() {
+                continue;
+            }
+            let mut accrued = false;
+            let mut liquidations = Vec::new();
+            self.account_repo
+                .update(candidate.id, &mut |account| {
+                    accrued = account.margin_interest_due(accrue_until);
+                    if accrued {
+                        account.accrue_margin_interest(accrue_until);
+                    }
+                    liquidations = self.liquidate_unsafe_wallets(account, &prices, now);
+                    accrued || !liquidations.is_empty()
+                })
+                .await;
+            result.accounts_accrued += usize::from(accrued);
+            result.liquidations.extend(liquidations);
+        }
+
+        result
+    }
+
+    fn liquidate_unsafe_wallets(
+        &self,
+        account: &mut Account,
+        prices: &AssetPrices,
+        now: Timestamp,
+    ) -> Vec<MarginLiquidationReport> {
+        let fee = self.config.liquidation_fee;
+        let mut reports = Vec::new();
+
+        let cross_level = account.cross_margin_totals(prices).margin_level();
+        if cross_level < MARGIN_LEVEL_LIQUIDATION {
+            let liquidation = account.liquidate_cross_margin(prices, fee, now);
+            reports.push(MarginLiquidationReport {
+                owner_id: account.owner_id.clone(),
+                symbol: None,
+                margin_level: cross_level,
+                liquidation,
+            });
+        }
+
+        let symbols: Vec<Symbol> = account
+            .isolated_margins()
+            .filter(|w| w.has_debt())
+            .map(|w| w.symbol.clone())
+            .collect();
+        for symbol in symbols {
+            let level = account
+                .isolated_margin_totals(&symbol, prices)
+                .unwrap_or_default()
+                .margin_level();
+            if level >= MARGIN_LEVEL_LIQUIDATION {
+                continue;
+            }
+            if let Some(liquidation) = account.liquidate_isolated_margin(&symbol, prices, fee, now)
+            {
+                reports.push(MarginLiquidationReport {
+                    owner_id: account.owner_id.clone(),
+                    symbol: Some(symbol),
+                    margin_level: level,
+                    liquidation,
+                });
+            }
+        }
+
+        for report in &reports {
+            tracing::warn!(
+                owner = %report.owner_id,
+                symbol = ?report.symbol,
+                shortfall = %report.liquidation.shortfall,
+                "Liquidated margin wallet"
+            );
+        }
+        reports
+    }
+
+    /// Price every spot asset in the quote asset from its order book
+    async fn asset_prices(&self) -> AssetPrices {
+        let mut prices = AssetPrices::new(&self.config.quote_asset);
+        for pair in self.instrument_repo.get_all().await {
+            if pair.quote_asset != self.config.quote_asset
+                || !matches!(
+                    pair.instrument_type,
+                    InstrumentType::Spot | InstrumentType::Margin
+                )
+            {
+                continue;
+            }
+            let Some(book) = self.order_book_repo.get(&pair.symbol).await else {
+                continue;
+            };
+            if let Some(price) = book.mid_price().or(book.best_bid()).or(book.best_ask()) {
+                prices.set(pair.base_asset.clone(), price);
+            }
+        }
+        prices
+    }
+
+    async fn check_rate_limit(&self, client_id: &str, weight: u32) -> Result<(), MarginError> {
+        let rate_result = self.rate_limiter.check_request(client_id, weight).await;
+        if !rate_result.allowed {
+            return Err(MarginError::RateLimited {
+                retry_after_ms: rate_result.retry_after.map(|d| d.as_millis() as u64),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn parse_symbol(symbol: &str) -> Result<Symbol, MarginError> {
+    Symbol::new(symbol).map_err(|e| MarginError::InvalidSymbol(e.to_string()))
+}
+
+fn transaction(amount: Value) -> MarginTransaction {
+    MarginTransaction {
+        tran_id: NEXT_TRAN_ID.fetch_add(1, Ordering::Relaxed),
+        amount,
+    }
+}
+
+/// Start of the current interest hour
+fn last_interest_boundary(now: Timestamp) -> Timestamp {
+    let millis = now.timestamp_millis();
+    chrono::DateTime::from_timestamp_millis(millis - millis.rem_euclid(INTEREST_PERIOD_MS))
+        .unwrap_or(now)
+}
+
+#[derive(Debug, Clone)]
+pub enum MarginError {
+    RateLimited { retry_after_ms: Option<u64> },
+    InvalidSymbol(String),
+    SymbolNotFound(String),
+    InvalidAmount,
+    UnsupportedAsset(String),
+    ExceedsBorrowLimit,
+    ExceedsTransferLimit,
+    AccountError(AccountError),
+}
+
+impl std::fmt::Display for MarginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarginError::RateLimited { .. } => write!(f, "Rate limited"),
+            MarginError::InvalidSymbol(s) => write!(f, "Invalid symbol: {}", s),
+            MarginError::SymbolNotFound(s) => write!(f, "Symbol not found: {}", s),
+            MarginError::InvalidAmount => write!(f, "Amount must be positive"),
+            MarginError::UnsupportedAsset(a) => write!(f, "Not a valid margin asset: {}", a),
+            MarginError::ExceedsBorrowLimit => {
+                write!(f, "Borrow amount exceeds the maximum borrowable")
+            }
+            MarginError::ExceedsTransferLimit => {
+                write!(f, "Transfer amount exceeds the maximum transferable")
+            }
+            MarginError::AccountError(e) => write!(f, "Account error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MarginError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::OrderBookWriter;
+    use crate::domain::{ControllableClock, Order, Price, Quantity, Side, TimeInForce};
+    use crate::domain::{PRICE_SCALE, TradingPairConfig};
+    use crate::infrastructure::{
+        InMemoryAccountRepository, InMemoryInstrumentRepository, InMemoryOrderBookRepository,
+        SimulationClock, TokenBucketRateLimiter,
+    };
+
+    type TestMarginUseCase = MarginUseCase<
+        SimulationClock,
+        InMemoryAccountRepository,
+        InMemoryOrderBookRepository,
+        InMemoryInstrumentRepository,
+        TokenBucketRateLimiter,
+    >;
+
+    struct Fixture {
+        clock: Arc<SimulationClock>,
+        accounts: Arc<InMemoryAccountRepository>,
+        books: Arc<InMemoryOrderBookRepository>,
+        use_case: TestMarginUseCase,
+    }
+
+    async fn fixture() -> Fixture {
+        let clock = Arc::new(SimulationClock::fixed());
+        let accounts = Arc::new(InMemoryAccountRepository::new());
+        let books = Arc::new(InMemoryOrderBookRepository::new());
+        let instruments = Arc::new(InMemoryInstrumentRepository::new());
+        let symbol = Symbol::new("BTCUSDT").unwrap();
+        instruments.add(TradingPairConfig::new(symbol.clone(), "BTC", "USDT"));
+        set_btc_price(&books, 50000).await;
+
+        let use_case = MarginUseCase::new(
+            Arc::clone(&clock),
+            Arc::clone(&accounts),
+            Arc::clone(&books),
+            instruments,
+            Arc::new(TokenBucketRateLimiter::default()),
+        );
+        Fixture {
+            clock,
+            accounts,
+            books,
+            use_case,
+        }
+    }
+
+    /// Replace the BTCUSDT book with a one-tick market around `price`
+    async fn set_btc_price(books: &InMemoryOrderBookRepository, price: i64) {
+        let symbol = Symbol::new("BTCUSDT").unwrap();
+        let mut book = crate::domain::OrderBook::new(symbol.clone());
+        for (side, px) in [(Side::Buy, price), (Side::Sell, price)] {
+            book.add_order(Order::new_limit(
+                symbol.clone(),
+                side,
+                Quantity::from_int(1),
+                Price::from_int(px),
+                TimeInForce::Gtc,
+            ));
+        }
+        books.save(book).await;
+    }
+
+    fn loan(asset: &str, amount: i64, symbol: Option<&str>) -> MarginLoanCommand {
+        MarginLoanCommand {
+            asset: asset.to_string(),
+            amount: Value::from_int(amount),
+            symbol: symbol.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cross_borrow_respects_initial_margin_level() {
+        let f = fixture().await;
+        let mut account = f.accounts.get_or_create("trader").await;
+        account.deposit("USDT", Value::from_int(50000));
+        f.accounts.save(account).await;
+
+        // 50k + 100k borrowed against 100k of debt is a 1.5 margin level
+        f.use_case
+            .borrow("trader", loan("BTC", 2, None))
+            .await
+            .unwrap();
+        let err = f
+            .use_case
+            .borrow("trader", loan("BTC", 1, None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MarginError::ExceedsBorrowLimit));
+
+        let summary = f.use_case.account("trader").await.unwrap();
+        assert_eq!(summary.totals.total_liability, Value::from_int(100000));
+        assert_eq!(summary.totals.margin_level(), PRICE_SCALE * 3 / 2);
+
+        let err = f
+            .use_case
+            .borrow("trader", loan("DOGE", 1, None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MarginError::UnsupportedAsset(_)));
+    }
+
+    #[tokio::test]
+    async fn test_hourly_interest_accrual() {
+        let f = fixture().await;
+        let mut account = f.accounts.get_or_create("trader").await;
+        account.deposit("USDT", Value::from_int(1_000_000));
+        f.accounts.save(account).await;
+
+        // Borrow on an hour boundary so each pass charges whole hours
+        let start = last_interest_boundary(f.clock.now());
+        f.clock.set_time(start);
+        f.use_case
+            .borrow("trader", loan("USDT", 876_000, None))
+            .await
+            .unwrap();
+
+        // Within the hour nothing is charged
+        f.clock.advance(chrono::Duration::minutes(30));
+        assert_eq!(f.use_case.run_maintenance().await.accounts_accrued, 0);
+        let account = f.accounts.get_by_owner("trader").await.unwrap();
+        assert_eq!(account.balance("USDT").interest, Value::ZERO);
+
+        // 10% a year on 876k is 10 an hour
+        f.clock.advance(chrono::Duration::minutes(90));
+        assert_eq!(f.use_case.run_maintenance().await.accounts_accrued, 1);
+        assert_eq!(f.use_case.run_maintenance().await.accounts_accrued, 0);
+        let account = f.accounts.get_by_owner("trader").await.unwrap();
+        assert_eq!(account.balance("USDT").interest, Value::from_int(20));
+    }
+
+    #[tokio::test]
+    async fn test_isolated_liquidation_leaves_cross_untouched() {
+        let f = fixture().await;
+        let mut account = f.accounts.get_or_create("trader").await;
+        account.deposit("USDT", Value::from_int(100000));
+        f.accounts.save(account).await;
+
+        f.use_case
+            .transfer_isolated(
+                "trader",
+                IsolatedTransferCommand {
+                    asset: "USDT".to_string(),
+                    symbol: "BTCUSDT".to_string(),
+                    amount: Value::from_int(25000),
+                    direction: IsolatedTransferDirection::ToIsolated,
+                },
+            )
+            .await
+            .unwrap();
+        f.use_case
+            .borrow("trader", loan("BTC", 1, Some("BTCUSDT")))
+            .await
+            .unwrap();
+
+        // 25k + 50k against 50k of debt is safe
+        assert!(f.use_case.run_maintenance().await.liquidations.is_empty());
+
+        // Move the borrowed BTC out as if sold short, then let the price run up
+        let mut account = f.accounts.get_by_owner("trader").await.unwrap();
+        let symbol = Symbol::new("BTCUSDT").unwrap();
+        account
+            .transfer_from_isolated(&symbol, "BTC", Value::from_int(1), f.clock.now())
+            .unwrap();
+        f.accounts.save(account).await;
+        set_btc_price(&f.books, 70000).await;
+        let result = f.use_case.run_maintenance().await;
+        assert_eq!(result.liquidations.len(), 1);
+        let report = &result.liquidations[0];
+        assert_eq!(report.symbol, Some(symbol.clone()));
+        assert_eq!(report.liquidation.seized[0].0, "USDT");
+        assert!(report.liquidation.shortfall.raw() > 0);
+
+        let account = f.accounts.get_by_owner("trader").await.unwrap();
+        let wallet = account.isolated_margin(&symbol).unwrap();
+        assert_eq!(wallet.balance("USDT").available, Value::ZERO);
+        // Cross margin funds were never touched
+        assert_eq!(account.balance("USDT").available, Value::from_int(75000));
+        assert_eq!(account.balance("BTC").available, Value::from_int(1));
+    }
+}
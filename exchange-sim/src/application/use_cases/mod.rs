@@ -1,21 +1,39 @@
+mod amend_order;
 mod cancel_order;
 mod get_depth;
 mod get_exchange_info;
+mod get_market_by_order;
 mod liquidity;
+mod margin;
+mod option_marks;
 mod process_deposit;
 mod process_withdrawal;
 mod request_withdrawal;
 mod submit_order;
 mod swap;
 
+pub use amend_order::{AmendError, AmendOrderCommand, AmendOrderResult, AmendOrderUseCase};
 pub use cancel_order::{CancelError, CancelOrderCommand, CancelOrderResult, CancelOrderUseCase};
 pub use get_depth::{DepthError, DepthResult, GetDepthQuery, GetDepthUseCase};
 pub use get_exchange_info::{ExchangeInfo, ExchangeInfoError, GetExchangeInfoUseCase};
+pub use get_market_by_order::{
+    GetMarketByOrderQuery, GetMarketByOrderUseCase, MarketByOrderResult,
+};
 pub use liquidity::{
     AddLiquidityCommand, AddLiquidityExecutionResult, LiquidityAddedEvent, LiquidityRemovedEvent,
     LiquidityUseCase, LiquidityUseCaseError, RemoveLiquidityCommand,
     RemoveLiquidityExecutionResult,
 };
+pub use margin::{
+    IsolatedMarginAccountsSummary, IsolatedMarginSummary, IsolatedTransferCommand,
+    IsolatedTransferDirection, MarginAccountSummary, MarginAssetSummary, MarginConfig, MarginError,
+    MarginLiquidationReport, MarginLoanCommand, MarginMaintenanceResult, MarginTransaction,
+    MarginUseCase,
+};
+pub use option_marks::{
+    OptionMark, OptionMarkConfig, OptionMarkEvent, OptionMarkQuery, OptionMarkRefresh,
+    OptionMarksUseCase, PortfolioMarginCache,
+};
 pub use process_deposit::{
     Deposit, DepositCreditedEvent, DepositId, DepositStatus, ProcessDepositError,
     ProcessDepositUseCase, ProcessDepositsResult, RegisterDepositAddressCommand,
@@ -0,0 +1,770 @@
+//! Option mark prices from a fitted volatility surface.
+//!
+//! Implied vols are solved from each option book, fitted into a smile per
+//! underlying and expiry, and every option is then marked off that surface
+//! rather than off its last trade.
+
+use crate::application::ports::{
+    AccountRepository, EventPublisher, InstrumentRepository, OrderBookReader, RequestRateLimiter,
+};
+use crate::application::use_cases::DepthError;
+use crate::domain::{
+    AccountMarginCalculator, Clock, ExchangeEvent, FutureContract, Instrument, InstrumentType,
+    MarginMode, OptionContract, OptionGreeks, OptionPricer, OptionType, PerpetualContract,
+    PortfolioMarginCalculator, Price, RiskArrayConfig, SpotPair, Symbol, Timestamp,
+    TradingPairConfig, VolSmile, VolSurface,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Configuration for option marking
+#[derive(Debug, Clone)]
+pub struct OptionMarkConfig {
+    /// Continuously compounded risk-free rate
+    pub risk_free_rate: f64,
+    /// Volatility used for underlyings with no quoted options
+    pub default_volatility: f64,
+    /// Binomial steps for American options
+    pub binomial_steps: usize,
+}
+
+impl Default for OptionMarkConfig {
+    fn default() -> Self {
+        Self {
+            risk_free_rate: 0.0,
+            default_volatility: 0.6,
+            binomial_steps: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OptionMarkQuery {
+    /// Only this option; all options when `None`
+    pub symbol: Option<String>,
+}
+
+/// Mark price, implied vols and Greeks of one option
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionMark {
+    pub symbol: Symbol,
+    pub underlying: String,
+    pub expiry: Timestamp,
+    pub strike: Price,
+    pub option_type: OptionType,
+    pub mark_price: Price,
+    /// Underlying spot the option was marked against
+    pub index_price: Price,
+    pub bid_iv: Option<f64>,
+    pub ask_iv: Option<f64>,
+    pub mark_iv: f64,
+    pub greeks: OptionGreeks,
+    pub risk_free_rate: f64,
+}
+
+/// Marks of every option on one underlying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionMarkEvent {
+    pub underlying: String,
+    pub marks: Vec<OptionMark>,
+    pub timestamp: Timestamp,
+}
+
+/// Outcome of a periodic mark refresh
+#[derive(Debug, Clone, Default)]
+pub struct OptionMarkRefresh {
+    pub marks: Vec<OptionMark>,
+    /// Accounts whose option positions moved to a new mark
+    pub accounts_marked: usize,
+}
+
+/// Risk-array calculator from the latest periodic refresh
+///
+/// Shared with the pre-trade check so a submission reuses the refreshed
+/// market state instead of re-marking every instrument.
+#[derive(Debug, Default)]
+pub struct PortfolioMarginCache {
+    calculator: RwLock<Option<Arc<PortfolioMarginCalculator>>>,
+}
+
+impl PortfolioMarginCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest calculator, `None` before the first refresh
+    pub fn get(&self) -> Option<Arc<PortfolioMarginCalculator>> {
+        self.calculator.read().clone()
+    }
+
+    pub fn set(&self, calculator: PortfolioMarginCalculator) {
+        *self.calculator.write() = Some(Arc::new(calculator));
+    }
+}
+
+/// Forward and `(strike, mid IV)` points of one underlying and expiry
+type SmileQuotes = (f64, Vec<(f64, f64)>);
+
+/// An option listed on the exchange with its pricing inputs
+struct ListedOption {
+    contract: OptionContract,
+    symbol: Symbol,
+    index_price: Price,
+    bid: Option<Price>,
+    ask: Option<Price>,
+    mid: Option<Price>,
+}
+
+/// Use case for option mark prices
+pub struct OptionMarksUseCase<C, A, OB, I, E, R>
+where
+    C: Clock,
+    A: AccountRepository,
+    OB: OrderBookReader,
+    I: InstrumentRepository,
+    E: EventPublisher,
+    R: RequestRateLimiter,
+{
+    clock: Arc<C>,
+    account_repo: Arc<A>,
+    order_book_repo: Arc<OB>,
+    instrument_repo: Arc<I>,
+    event_publisher: Arc<E>,
+    rate_limiter: Arc<R>,
+    config: OptionMarkConfig,
+    margin_cache: Option<Arc<PortfolioMarginCache>>,
+}
+
+impl<C, A, OB, I, E, R> OptionMarksUseCase<C, A, OB, I, E, R>
+where
+    C: Clock,
+    A: AccountRepository,
+    OB: OrderBookReader,
+    I: InstrumentRepository,
+    E: EventPublisher,
+    R: RequestRateLimiter,
+{
+    pub fn new(
+        clock: Arc<C>,
+        account_repo: Arc<A>,
+        order_book_repo: Arc<OB>,
+        instrument_repo: Arc<I>,
+        event_publisher: Arc<E>,
+        rate_limiter: Arc<R>,
+    ) -> Self {
+        Self {
+            clock,
+            account_repo,
+            order_book_repo,
+            instrument_repo,
+            event_publisher,
+            rate_limiter,
+            config: OptionMarkConfig::default(),
+            margin_cache: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: OptionMarkConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Publish each refresh's risk-array calculator to `cache`
+    pub fn with_margin_cache(mut self, cache: Arc<PortfolioMarginCache>) -> Self {
+        self.margin_cache = Some(cache);
+        self
+    }
+
+    /// Current marks, for all options or a single symbol
+    pub async fn execute(
+        &self,
+        client_id: &str,
+        query: OptionMarkQuery,
+    ) -> Result<Vec<OptionMark>, DepthError> {
+        let rate_result = self.rate_limiter.check_request(client_id, 5).await;
+        if !rate_result.allowed {
+            return Err(DepthError::RateLimited {
+                retry_after_ms: rate_result.retry_after.map(|d| d.as_millis() as u64),
+            });
+        }
+
+        let filter = query
+            .symbol
+            .map(|s| Symbol::new(&s).map_err(|e| DepthError::InvalidSymbol(e.to_string())))
+            .transpose()?;
+
+        let marks = self.marks().await;
+        match filter {
+            None => Ok(marks),
+            Some(symbol) => {
+                let marks: Vec<OptionMark> =
+                    marks.into_iter().filter(|m| m.symbol == symbol).collect();
+                if marks.is_empty() {
+                    Err(DepthError::SymbolNotFound(symbol.to_string()))
+                } else {
+                    Ok(marks)
+                }
+            }
+        }
+    }
+
+    /// Mark every listed, unexpired option off the fitted surface
+    pub async fn marks(&self) -> Vec<OptionMark> {
+        MarketSnapshot::load(
+            self.order_book_repo.as_ref(),
+            self.instrument_repo.as_ref(),
+            &self.config,
+            self.clock.now(),
+        )
+        .await
+        .marks
+    }
+
+    /// Recompute marks, publish them per underlying and re-mark positions
+    ///
+    /// Meant to be called periodically alongside margin maintenance, so
+    /// option positions are valued off the surface before margin is checked.
+    pub async fn refresh(&self) -> OptionMarkRefresh {
+        let now = self.clock.now();
+        let snapshot = MarketSnapshot::load(
+            self.order_book_repo.as_ref(),
+            self.instrument_repo.as_ref(),
+            &self.config,
+            now,
+        )
+        .await;
+        let calculator = snapshot.portfolio_margin(&self.config, now);
+        if let Some(cache) = &self.margin_cache {
+            cache.set(calculator.clone());
+        }
+        let portfolio_margin = AccountMarginCalculator::new(calculator);
+        let marks = snapshot.marks;
+
+        let mut by_underlying: HashMap<&str, Vec<OptionMark>> = HashMap::new();
+        for mark in &marks {
+            by_underlying
+                .entry(mark.underlying.as_str())
+                .or_default()
+                .push(mark.clone());
+        }
+        for (underlying, marks) in by_underlying {
+            self.event_publisher
+                .publish_to_symbol(
+                    underlying,
+                    ExchangeEvent::OptionMark(OptionMarkEvent {
+                        underlying: underlying.to_string(),
+                        marks,
+                        timestamp: now,
+                    }),
+                )
+                .await;
+        }
+
+        // Portfolio accounts are re-checked even without option positions,
+        // since their status is only ever set here. The listing only picks
+        // candidates; each account is re-read and written under the
+        // repository's lock so concurrent order handling is never overwritten
+        let prices: HashMap<Symbol, Price> = marks
+            .iter()
+            .map(|m| (m.symbol.clone(), m.mark_price))
+            .collect();
+        let mut accounts_marked = 0;
+        for candidate in self.account_repo.list().await {
+            let portfolio = candidate.margin_mode == MarginMode::Portfolio
+                && candidate.positions().next().is_some();
+            if !portfolio
+                && !candidate
+                    .positions()
+                    .any(|p| prices.contains_key(&p.symbol))
+            {
+                continue;
+            }
+            let mut remarked = false;
+            self.account_repo
+                .update(candidate.id, &mut |account| {
+                    remarked = account
+                        .positions()
+                        .any(|p| prices.get(&p.symbol).is_some_and(|m| *m != p.mark_price));
+                    if remarked {
+                        account.update_mark_prices(&prices, now);
+                    }
+                    let status = account.status;
+                    if account.margin_mode == MarginMode::Portfolio
+                        && account.positions().next().is_some()
+                    {
+                        account.refresh_status_with(&portfolio_margin);
+                    }
+                    remarked || account.status != status
+                })
+                .await;
+            accounts_marked += usize::from(remarked);
+        }
+
+        OptionMarkRefresh {
+            marks,
+            accounts_marked,
+        }
+    }
+}
+
+/// Portfolio margin calculator over the instrument registry
+///
+/// Options are valued off the same surface as the published marks, so the
+/// pre-trade check and the periodic status refresh agree.
+pub(crate) async fn portfolio_margin<OB, I>(
+    order_book_repo: &OB,
+    instrument_repo: &I,
+    config: &OptionMarkConfig,
+    now: Timestamp,
+) -> PortfolioMarginCalculator
+where
+    OB: OrderBookReader,
+    I: InstrumentRepository,
+{
+    MarketSnapshot::load(order_book_repo, instrument_repo, config, now)
+        .await
+        .portfolio_margin(config, now)
+}
+
+/// Instrument registry, index prices and option marks at one instant
+struct MarketSnapshot {
+    pairs: Vec<TradingPairConfig>,
+    /// Spot price per (base, quote) pair
+    index_prices: HashMap<(String, String), Price>,
+    marks: Vec<OptionMark>,
+}
+
+impl MarketSnapshot {
+    async fn load<OB, I>(
+        order_book_repo: &OB,
+        instrument_repo: &I,
+        config: &OptionMarkConfig,
+        now: Timestamp,
+    ) -> Self
+    where
+        OB: OrderBookReader,
+        I: InstrumentRepository,
+    {
+        let pairs = instrument_repo.get_all().await;
+
+        let mut index_prices: HashMap<(String, String), Price> = HashMap::new();
+        for pair in &pairs {
+            if !matches!(
+                pair.instrument_type,
+                InstrumentType::Spot | InstrumentType::Margin
+            ) {
+                continue;
+            }
+            let Some(book) = order_book_repo.get(&pair.symbol).await else {
+                continue;
+            };
+            if let Some(price) = book.mid_price().or(book.best_bid()).or(book.best_ask()) {
+                index_prices.insert((pair.base_asset.clone(), pair.quote_asset.clone()), price);
+            }
+        }
+
+        let listed = listed_options(order_book_repo, &pairs, &index_prices, now).await;
+        let marks = mark_options(&listed, config, now);
+        Self {
+            pairs,
+            index_prices,
+            marks,
+        }
+    }
+
+    /// Every spot and derivative pair as a scenario-valued instrument
+    ///
+    /// Each underlying is centred on its index price with the implied vol of
+    /// its nearest-to-the-money option mark, or the default volatility when
+    /// it has no listed options.
+    fn portfolio_margin(
+        &self,
+        config: &OptionMarkConfig,
+        now: Timestamp,
+    ) -> PortfolioMarginCalculator {
+        let mut calc = PortfolioMarginCalculator::new(now).with_config(RiskArrayConfig {
+            risk_free_rate: config.risk_free_rate,
+            ..RiskArrayConfig::default()
+        });
+        for pair in &self.pairs {
+            if let Some(instrument) = instrument(pair) {
+                calc.add_instrument(instrument);
+            }
+        }
+
+        let mut atm: HashMap<&str, &OptionMark> = HashMap::new();
+        for mark in &self.marks {
+            let distance = |m: &OptionMark| (m.strike.to_f64() - m.index_price.to_f64()).abs();
+            let nearest = atm.entry(mark.underlying.as_str()).or_insert(mark);
+            if distance(mark) < distance(nearest) {
+                *nearest = mark;
+            }
+        }
+        for ((base, _), spot) in &self.index_prices {
+            let volatility = atm
+                .get(base.as_str())
+                .map_or(config.default_volatility, |m| m.mark_iv);
+            calc.set_market(base.clone(), *spot, volatility);
+        }
+        calc
+    }
+}
+
+/// Unexpired options whose underlying has a spot price
+async fn listed_options<OB: OrderBookReader>(
+    order_book_repo: &OB,
+    pairs: &[TradingPairConfig],
+    index_prices: &HashMap<(String, String), Price>,
+    now: Timestamp,
+) -> Vec<ListedOption> {
+    let mut listed = Vec::new();
+    for pair in pairs {
+        let Some(contract) = option_contract(pair) else {
+            continue;
+        };
+        if contract.is_expired(now) {
+            continue;
+        }
+        let key = (pair.base_asset.clone(), pair.quote_asset.clone());
+        let Some(&index_price) = index_prices.get(&key) else {
+            continue;
+        };
+        let book = order_book_repo.get(&pair.symbol).await;
+        listed.push(ListedOption {
+            contract,
+            symbol: pair.symbol.clone(),
+            index_price,
+            bid: book.as_ref().and_then(|b| b.best_bid()),
+            ask: book.as_ref().and_then(|b| b.best_ask()),
+            mid: book.as_ref().and_then(|b| b.mid_price()),
+        });
+    }
+    listed.sort_by(|a, b| a.symbol.as_str().cmp(b.symbol.as_str()));
+    listed
+}
+
+/// Solve implied vols from the books, fit the surface and mark off it
+fn mark_options(
+    listed: &[ListedOption],
+    config: &OptionMarkConfig,
+    now: Timestamp,
+) -> Vec<OptionMark> {
+    let pricer = OptionPricer {
+        risk_free_rate: config.risk_free_rate,
+        binomial_steps: config.binomial_steps,
+    };
+
+    // Implied vols from the books, then one smile per underlying and expiry
+    let mut quotes: HashMap<(&str, Timestamp), SmileQuotes> = HashMap::new();
+    let mut book_ivs = Vec::with_capacity(listed.len());
+    for option in listed {
+        let spot = option.index_price.to_f64();
+        let iv = |price: Option<Price>| {
+            price.and_then(|p| pricer.implied_volatility(&option.contract, p.to_f64(), spot, now))
+        };
+        let (bid_iv, ask_iv, mid_iv) = (iv(option.bid), iv(option.ask), iv(option.mid));
+        book_ivs.push((bid_iv, ask_iv));
+
+        if let Some(vol) = mid_iv {
+            let years = option.contract.years_to_expiry(now);
+            let forward = spot * (config.risk_free_rate * years).exp();
+            quotes
+                .entry((option.contract.underlying.as_str(), option.contract.expiry))
+                .or_insert_with(|| (forward, Vec::new()))
+                .1
+                .push((option.contract.strike.to_f64(), vol));
+        }
+    }
+
+    let mut surfaces: HashMap<&str, VolSurface> = HashMap::new();
+    for ((underlying, expiry), (forward, points)) in &quotes {
+        if let Some(smile) = VolSmile::fit(*forward, points) {
+            surfaces
+                .entry(underlying)
+                .or_default()
+                .insert(*expiry, smile);
+        }
+    }
+
+    listed
+        .iter()
+        .zip(book_ivs)
+        .map(|(option, (bid_iv, ask_iv))| {
+            let contract = &option.contract;
+            let spot = option.index_price.to_f64();
+            let mark_iv = surfaces
+                .get(contract.underlying.as_str())
+                .and_then(|s| s.vol(contract.expiry, contract.strike.to_f64(), now))
+                .unwrap_or(config.default_volatility);
+
+            OptionMark {
+                symbol: option.symbol.clone(),
+                underlying: contract.underlying.clone(),
+                expiry: contract.expiry,
+                strike: contract.strike,
+                option_type: contract.option_type,
+                mark_price: Price::from_f64(pricer.price(contract, spot, mark_iv, now)),
+                index_price: option.index_price,
+                bid_iv,
+                ask_iv,
+                mark_iv,
+                greeks: pricer.greeks(contract, spot, mark_iv, now),
+                risk_free_rate: config.risk_free_rate,
+            }
+        })
+        .collect()
+}
+
+/// Pricing view of an option trading pair
+fn option_contract(pair: &TradingPairConfig) -> Option<OptionContract> {
+    if pair.instrument_type != InstrumentType::Option {
+        return None;
+    }
+    let config = pair.option_config.as_ref()?;
+    let expiry = chrono::DateTime::from_timestamp_millis(config.expiration_ms)?;
+    Some(
+        OptionContract::new(
+            pair.base_asset.clone(),
+            expiry,
+            config.strike,
+            config.option_type,
+        )
+        .with_symbol(pair.symbol.to_string())
+        .with_exercise_style(config.exercise_style),
+    )
+}
+
+/// Margin view of a trading pair
+fn instrument(pair: &TradingPairConfig) -> Option<Instrument> {
+    let base = pair.base_asset.clone();
+    let symbol = pair.symbol.to_string();
+    match pair.instrument_type {
+        InstrumentType::Spot | InstrumentType::Margin => {
+            let mut spot = SpotPair::new(base, pair.quote_asset.clone());
+            spot.symbol = symbol;
+            Some(Instrument::Spot(spot))
+        }
+        InstrumentType::PerpetualFutures => {
+            let config = pair.futures_config.clone().unwrap_or_default();
+            let mut perp = if config.settlement_asset == pair.base_asset {
+                PerpetualContract::inverse(base, symbol)
+            } else {
+                PerpetualContract::linear(base, symbol)
+            };
+            perp.multiplier = config.contract_multiplier;
+            perp.maintenance_margin_bps = config.maintenance_margin_bps;
+            perp.initial_margin_bps = config.initial_margin_bps;
+            Some(Instrument::Perpetual(perp))
+        }
+        InstrumentType::Futures => {
+            let config = pair.futures_config.clone()?;
+            let expiry = chrono::DateTime::from_timestamp_millis(config.expiration_ms?)?;
+            let mut future = if config.settlement_asset == pair.base_asset {
+                FutureContract::inverse(base, symbol, expiry)
+            } else {
+                FutureContract::linear(base, symbol, expiry)
+            };
+            future.multiplier = config.contract_multiplier;
+            future.initial_margin_bps = config.initial_margin_bps;
+            Some(Instrument::Future(future))
+        }
+        InstrumentType::Option => option_contract(pair).map(Instrument::Option),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{AccountRepository, OrderBookWriter};
+    use crate::domain::{
+        ExerciseStyle, OptionConfig, Order, PositionSide, Quantity, Side, TimeInForce, Value,
+    };
+    use crate::infrastructure::{
+        BroadcastEventPublisher, InMemoryAccountRepository, InMemoryInstrumentRepository,
+        InMemoryOrderBookRepository, SimulationClock, TokenBucketRateLimiter,
+    };
+    use chrono::Duration;
+
+    type TestUseCase = OptionMarksUseCase<
+        SimulationClock,
+        InMemoryAccountRepository,
+        InMemoryOrderBookRepository,
+        InMemoryInstrumentRepository,
+        BroadcastEventPublisher,
+        TokenBucketRateLimiter,
+    >;
+
+    struct Fixture {
+        use_case: TestUseCase,
+        accounts: Arc<InMemoryAccountRepository>,
+        books: Arc<InMemoryOrderBookRepository>,
+        publisher: Arc<BroadcastEventPublisher>,
+        now: Timestamp,
+    }
+
+    async fn quote(books: &InMemoryOrderBookRepository, symbol: &Symbol, bid: f64, ask: f64) {
+        let mut book = books.get_or_create(symbol).await;
+        for (side, price) in [(Side::Buy, bid), (Side::Sell, ask)] {
+            book.add_order(Order::new_limit(
+                symbol.clone(),
+                side,
+                Quantity::from_int(1),
+                Price::from_f64(price),
+                TimeInForce::Gtc,
+            ));
+        }
+        OrderBookWriter::save(books, book).await;
+    }
+
+    /// BTC spot at 50000 and three calls on one expiry quoted at 60% vol
+    async fn fixture() -> Fixture {
+        let clock = Arc::new(SimulationClock::fixed());
+        let now = clock.now();
+        let expiry = now + Duration::days(30);
+        let accounts = Arc::new(InMemoryAccountRepository::new());
+        let books = Arc::new(InMemoryOrderBookRepository::new());
+        let instruments = Arc::new(InMemoryInstrumentRepository::new());
+        let publisher = Arc::new(BroadcastEventPublisher::new(100));
+
+        let spot = Symbol::new("BTCUSDT").unwrap();
+        instruments.add(TradingPairConfig::new(spot.clone(), "BTC", "USDT"));
+        quote(&books, &spot, 49_999.0, 50_001.0).await;
+
+        let pricer = OptionPricer::default();
+        for strike in [45_000, 50_000, 55_000] {
+            let symbol = Symbol::new(format!("BTC-{strike}-C")).unwrap();
+            let config = OptionConfig {
+                strike: Price::from_int(strike),
+                option_type: OptionType::Call,
+                expiration_ms: expiry.timestamp_millis(),
+                exercise_style: ExerciseStyle::European,
+            };
+            instruments.add(TradingPairConfig::option(
+                symbol.clone(),
+                "BTC",
+                "USDT",
+                config,
+            ));
+            let contract =
+                OptionContract::new("BTC", expiry, Price::from_int(strike), OptionType::Call);
+            let fair = pricer.price(&contract, 50_000.0, 0.6, now);
+            quote(&books, &symbol, fair - 1.0, fair + 1.0).await;
+        }
+
+        let use_case = OptionMarksUseCase::new(
+            clock,
+            Arc::clone(&accounts),
+            Arc::clone(&books),
+            instruments,
+            Arc::clone(&publisher),
+            Arc::new(TokenBucketRateLimiter::default()),
+        );
+        Fixture {
+            use_case,
+            accounts,
+            books,
+            publisher,
+            now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_marks_come_from_fitted_surface() {
+        let f = fixture().await;
+
+        let marks = f.use_case.marks().await;
+        assert_eq!(marks.len(), 3);
+        for mark in &marks {
+            assert!((mark.mark_iv - 0.6).abs() < 0.01, "{}", mark.mark_iv);
+            assert!(mark.bid_iv.unwrap() < mark.mark_iv + 1e-9);
+            assert!(mark.ask_iv.unwrap() > mark.mark_iv - 1e-9);
+            assert_eq!(mark.index_price, Price::from_int(50_000));
+        }
+        let atm = marks
+            .iter()
+            .find(|m| m.symbol.as_str() == "BTC-50000-C")
+            .unwrap();
+        assert!(atm.greeks.delta > 0.5 && atm.greeks.delta < 0.6);
+
+        // A trade far from fair value does not move the mark
+        let symbol = Symbol::new("BTC-55000-C").unwrap();
+        quote(&f.books, &symbol, 1.0, 20_000.0).await;
+        let before = marks
+            .iter()
+            .find(|m| m.symbol == symbol)
+            .unwrap()
+            .mark_price;
+        let after = f
+            .use_case
+            .execute(
+                "client",
+                OptionMarkQuery {
+                    symbol: Some("BTC-55000-C".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(after.len(), 1);
+        let drift = (after[0].mark_price.to_f64() - before.to_f64()).abs();
+        assert!(drift / before.to_f64() < 0.05);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_publishes_and_remarks_positions() {
+        let f = fixture().await;
+        let mut rx = f.publisher.subscribe_symbol("BTC");
+
+        let symbol = Symbol::new("BTC-50000-C").unwrap();
+        let mut account = f.accounts.get_or_create("writer").await;
+        account.deposit("USDT", Value::from_int(10_000));
+        account.open_position(
+            symbol.clone(),
+            PositionSide::Short,
+            Quantity::from_int(1),
+            Price::from_int(1),
+            Value::ZERO,
+            f.now,
+        );
+        f.accounts.save(account).await;
+
+        let refresh = f.use_case.refresh().await;
+        assert_eq!(refresh.accounts_marked, 1);
+
+        let mark = refresh
+            .marks
+            .iter()
+            .find(|m| m.symbol == symbol)
+            .unwrap()
+            .mark_price;
+        let account = f.accounts.get_or_create("writer").await;
+        assert_eq!(account.position(&symbol).unwrap().mark_price, mark);
+
+        // Unchanged marks leave the account alone
+        assert_eq!(f.use_case.refresh().await.accounts_marked, 0);
+
+        match rx.try_recv().unwrap() {
+            ExchangeEvent::OptionMark(event) => {
+                assert_eq!(event.underlying, "BTC");
+                assert_eq!(event.marks.len(), 3);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_option_symbol() {
+        let f = fixture().await;
+        let result = f
+            .use_case
+            .execute(
+                "client",
+                OptionMarkQuery {
+                    symbol: Some("ETH-1-C".to_string()),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DepthError::SymbolNotFound(_))));
+    }
+}
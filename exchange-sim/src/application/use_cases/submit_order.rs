@@ -2,10 +2,14 @@ use crate::application::ports::{
     AccountRepository, EventPublisher, InstrumentRepository, OrderBookReader, OrderBookWriter,
     OrderRateLimiter,
 };
+use crate::application::use_cases::option_marks::{
+    OptionMarkConfig, PortfolioMarginCache, portfolio_margin,
+};
 use crate::domain::{
-    AccountError, Clock, DepthUpdateEvent, ExchangeEvent, Order, OrderAcceptedEvent,
-    OrderFilledEvent, OrderStatus, OrderType, OrderValidator, PositionSide, Price, PriceLevel,
-    Quantity, Rate, Side, Symbol, TimeInForce, TradeExecutedEvent, Value,
+    Account, AccountError, Clock, DepthUpdateEvent, ExchangeEvent, MarginCalculator, MarginMode,
+    Order, OrderAcceptedEvent, OrderBook, OrderFilledEvent, OrderStatus, OrderType, OrderValidator,
+    Position, PositionSide, Price, Quantity, Rate, Side, Symbol, TimeInForce, Timestamp,
+    TradeExecutedEvent, TradingPairConfig, Value,
 };
 use std::sync::Arc;
 
@@ -34,6 +38,16 @@ pub struct FillInfo {
     pub commission: Value,
 }
 
+/// A checked order and the state it was checked against
+struct Prepared {
+    symbol: Symbol,
+    instrument: TradingPairConfig,
+    order: Order,
+    book: OrderBook,
+    account: Account,
+    now: Timestamp,
+}
+
 pub struct SubmitOrderUseCase<C, A, OB, I, E, R>
 where
     C: Clock,
@@ -51,6 +65,8 @@ where
     rate_limiter: Arc<R>,
     /// Whether to enforce balance checks (can disable for testing)
     enforce_balances: bool,
+    /// Risk-array calculator kept current by the periodic refresh
+    margin_cache: Option<Arc<PortfolioMarginCache>>,
 }
 
 impl<C, A, OB, I, E, R> SubmitOrderUseCase<C, A, OB, I, E, R>
@@ -78,6 +94,7 @@ where
             event_publisher,
             rate_limiter,
             enforce_balances: true,
+            margin_cache: None,
         }
     }
 
@@ -98,9 +115,17 @@ where
             event_publisher,
             rate_limiter,
             enforce_balances: false,
+            margin_cache: None,
         }
     }
 
+    /// Check portfolio margin against the calculator in `cache`, building
+    /// one per order only until the first refresh fills it
+    pub fn with_margin_cache(mut self, cache: Arc<PortfolioMarginCache>) -> Self {
+        self.margin_cache = Some(cache);
+        self
+    }
+
     pub async fn execute(
         &self,
         client_id: &str,
@@ -114,100 +139,17 @@ where
             });
         }
 
-        // Parse and validate symbol
-        let symbol =
-            Symbol::new(&command.symbol).map_err(|e| OrderError::InvalidSymbol(e.to_string()))?;
-
-        // Get instrument
-        let instrument = self
-            .instrument_repo
-            .get(&symbol)
-            .await
-            .ok_or_else(|| OrderError::SymbolNotFound(command.symbol.clone()))?;
-
+        let Prepared {
+            symbol,
+            instrument,
+            order,
+            mut book,
+            mut account,
+            now,
+        } = self.prepare(client_id, &command).await?;
         let base_asset = instrument.base_asset.clone();
         let quote_asset = instrument.quote_asset.clone();
 
-        // Create order
-        let now = self.clock.now();
-        let mut order = match command.order_type {
-            OrderType::Market => Order::new_market(symbol.clone(), command.side, command.quantity),
-            OrderType::Limit | OrderType::LimitMaker => {
-                let price = command.price.ok_or(OrderError::MissingPrice)?;
-                Order::new_limit(
-                    symbol.clone(),
-                    command.side,
-                    command.quantity,
-                    price,
-                    command.time_in_force,
-                )
-            }
-            _ => {
-                let price = command.price;
-                let mut order = Order::new_limit(
-                    symbol.clone(),
-                    command.side,
-                    command.quantity,
-                    price.unwrap_or(Price::ZERO),
-                    command.time_in_force,
-                );
-                order.order_type = command.order_type;
-                order.stop_price = command.stop_price;
-                order
-            }
-        };
-
-        if let Some(client_order_id) = command.client_order_id {
-            order = order.with_client_order_id(client_order_id);
-        }
-
-        // Get order book
-        let mut book = self.order_book_repo.get_or_create(&symbol).await;
-
-        // Validate order
-        OrderValidator::validate(&order, &instrument, &book)
-            .map_err(|e| OrderError::ValidationFailed(e.message))?;
-
-        // Get account and check/lock balances if enforcement is enabled
-        let mut account = self.account_repo.get_or_create(client_id).await;
-
-        if self.enforce_balances {
-            let order_price = order.price.unwrap_or_else(|| {
-                // For market orders, use best available price as estimate
-                match order.side {
-                    Side::Buy => book.best_ask().unwrap_or(Price::ZERO),
-                    Side::Sell => book.best_bid().unwrap_or(Price::ZERO),
-                }
-            });
-
-            match command.side {
-                Side::Buy => {
-                    // Need quote currency (e.g., USDT) to buy
-                    let required = order_price.mul_qty(order.quantity);
-                    account
-                        .lock(&quote_asset, required)
-                        .map_err(OrderError::AccountError)?;
-                }
-                Side::Sell => {
-                    // Need base currency (e.g., BTC) to sell
-                    // Check if user has the asset or has borrowed it
-                    let balance = account.balance(&base_asset);
-                    let qty_value = Value::from_raw(order.quantity.raw() as i128);
-                    if balance.available.raw() < qty_value.raw() {
-                        // Check if they have borrowed (short selling)
-                        if !account.has_borrowed(&base_asset) {
-                            return Err(OrderError::AccountError(
-                                AccountError::InsufficientBalance,
-                            ));
-                        }
-                    }
-                    account
-                        .lock(&base_asset, qty_value)
-                        .map_err(OrderError::AccountError)?;
-                }
-            }
-        }
-
         // Capture sequence before matching for depth update
         let first_update_id = book.sequence() + 1;
 
@@ -370,8 +312,8 @@ where
 
         // Capture depth state before saving for delta calculation
         let final_update_id = book.sequence();
-        let current_bids: Vec<PriceLevel> = book.get_bids(20);
-        let current_asks: Vec<PriceLevel> = book.get_asks(20);
+        let deltas = book.take_deltas();
+        let (current_bids, current_asks) = book.depth_update_levels(&deltas, 20);
 
         // Save book and account
         self.order_book_repo.save(book).await;
@@ -395,6 +337,16 @@ where
                 .await;
         }
 
+        // Publish order-level (L3) changes
+        for delta in deltas {
+            self.event_publisher
+                .publish_to_symbol(
+                    symbol.as_str(),
+                    ExchangeEvent::MarketByOrder(delta.into_event(&symbol, now)),
+                )
+                .await;
+        }
+
         // Publish fill events
         if !trades.is_empty() {
             let fill_event = OrderFilledEvent {
@@ -422,6 +374,176 @@ where
             fills,
         })
     }
+
+    /// Check a submission without placing it or taking a rate-limit token
+    ///
+    /// Runs the same symbol, validation and balance checks as `execute`.
+    pub async fn validate(
+        &self,
+        client_id: &str,
+        command: &SubmitOrderCommand,
+    ) -> Result<(), OrderError> {
+        self.prepare(client_id, command).await.map(|_| ())
+    }
+
+    /// Build the order and check it against the book and the account, with
+    /// its balance locked on the returned account
+    async fn prepare(
+        &self,
+        client_id: &str,
+        command: &SubmitOrderCommand,
+    ) -> Result<Prepared, OrderError> {
+        // Parse and validate symbol
+        let symbol =
+            Symbol::new(&command.symbol).map_err(|e| OrderError::InvalidSymbol(e.to_string()))?;
+
+        // Get instrument
+        let instrument = self
+            .instrument_repo
+            .get(&symbol)
+            .await
+            .ok_or_else(|| OrderError::SymbolNotFound(command.symbol.clone()))?;
+
+        let base_asset = &instrument.base_asset;
+        let quote_asset = &instrument.quote_asset;
+
+        // Create order
+        let now = self.clock.now();
+        let mut order = match command.order_type {
+            OrderType::Market => Order::new_market(symbol.clone(), command.side, command.quantity),
+            OrderType::Limit | OrderType::LimitMaker => {
+                let price = command.price.ok_or(OrderError::MissingPrice)?;
+                Order::new_limit(
+                    symbol.clone(),
+                    command.side,
+                    command.quantity,
+                    price,
+                    command.time_in_force,
+                )
+            }
+            _ => {
+                let price = command.price;
+                let mut order = Order::new_limit(
+                    symbol.clone(),
+                    command.side,
+                    command.quantity,
+                    price.unwrap_or(Price::ZERO),
+                    command.time_in_force,
+                );
+                order.order_type = command.order_type;
+                order.stop_price = command.stop_price;
+                order
+            }
+        };
+
+        if let Some(client_order_id) = &command.client_order_id {
+            order = order.with_client_order_id(client_order_id.clone());
+        }
+
+        // Get order book
+        let book = self.order_book_repo.get_or_create(&symbol).await;
+
+        // Validate order
+        OrderValidator::validate(&order, &instrument, &book)
+            .map_err(|e| OrderError::ValidationFailed(e.message))?;
+
+        // Get account and check/lock balances if enforcement is enabled
+        let mut account = self.account_repo.get_or_create(client_id).await;
+
+        if self.enforce_balances {
+            let order_price = order.price.unwrap_or_else(|| {
+                // For market orders, use best available price as estimate
+                match order.side {
+                    Side::Buy => book.best_ask().unwrap_or(Price::ZERO),
+                    Side::Sell => book.best_bid().unwrap_or(Price::ZERO),
+                }
+            });
+
+            // Portfolio accounts must still cover the risk array with the
+            // new position on; orders that shrink the requirement always pass.
+            // The order only moves its own underlying's array, so that group
+            // alone is revalued, against the last refresh's market state
+            if account.margin_mode == MarginMode::Portfolio {
+                let calc = match self.margin_cache.as_ref().and_then(|cache| cache.get()) {
+                    Some(calc) => calc,
+                    None => Arc::new(
+                        portfolio_margin(
+                            self.order_book_repo.as_ref(),
+                            self.instrument_repo.as_ref(),
+                            &OptionMarkConfig::default(),
+                            now,
+                        )
+                        .await,
+                    ),
+                };
+                let side = match order.side {
+                    Side::Buy => PositionSide::Long,
+                    Side::Sell => PositionSide::Short,
+                };
+                let mut after = account.clone();
+                after.open_position(
+                    symbol.clone(),
+                    side,
+                    order.quantity,
+                    order_price,
+                    Value::ZERO,
+                    now,
+                );
+
+                let rate = account.maintenance_margin_rate;
+                let before: Vec<&Position> = account.positions().collect();
+                let with_order: Vec<&Position> = after.positions().collect();
+                let added = calc
+                    .symbol_group_margin(&with_order, symbol.as_str(), rate)
+                    .raw()
+                    - calc
+                        .symbol_group_margin(&before, symbol.as_str(), rate)
+                        .raw();
+                if added > 0 {
+                    let required = calc.maintenance_margin(&before, rate).raw() + added;
+                    if after.equity().raw() < required {
+                        return Err(OrderError::AccountError(AccountError::InsufficientMargin));
+                    }
+                }
+            }
+
+            match command.side {
+                Side::Buy => {
+                    // Need quote currency (e.g., USDT) to buy
+                    let required = order_price.mul_qty(order.quantity);
+                    account
+                        .lock(quote_asset, required)
+                        .map_err(OrderError::AccountError)?;
+                }
+                Side::Sell => {
+                    // Need base currency (e.g., BTC) to sell
+                    // Check if user has the asset or has borrowed it
+                    let balance = account.balance(base_asset);
+                    let qty_value = Value::from_raw(order.quantity.raw() as i128);
+                    if balance.available.raw() < qty_value.raw() {
+                        // Check if they have borrowed (short selling)
+                        if !account.has_borrowed(base_asset) {
+                            return Err(OrderError::AccountError(
+                                AccountError::InsufficientBalance,
+                            ));
+                        }
+                    }
+                    account
+                        .lock(base_asset, qty_value)
+                        .map_err(OrderError::AccountError)?;
+                }
+            }
+        }
+
+        Ok(Prepared {
+            symbol,
+            instrument,
+            order,
+            book,
+            account,
+            now,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1,7 +1,12 @@
 //! Trading account entity with balances, positions, and margin management.
 
-use crate::domain::entities::{Loan, Position, PositionSide};
-use crate::domain::services::{AccountMarginCalculator, MarginStatus};
+use crate::domain::entities::{
+    IsolatedMarginAccount, Loan, MarginLedger, MarginLiquidation, Position, PositionSide,
+    TradingPairConfig,
+};
+use crate::domain::services::{
+    AccountMarginCalculator, AssetPrices, MarginCalculator, MarginStatus, MarginTotals,
+};
 use crate::domain::value_objects::{Price, Quantity, Rate, Symbol, Timestamp, Value};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -31,6 +36,9 @@ pub enum MarginMode {
     Cross,
     /// Isolated margin - each position has separate margin
     Isolated,
+    /// Portfolio margin - positions on the same underlying are margined
+    /// together from a scenario grid (see `PortfolioMarginCalculator`)
+    Portfolio,
 }
 
 /// Fee schedule for an account (VIP tier-based discounts)
@@ -166,9 +174,13 @@ pub struct Account {
     /// Open positions by symbol
     positions: HashMap<Symbol, Position>,
 
-    /// Active loans (borrowed assets for short selling)
+    /// Active loans (borrowed assets for short selling and cross margin)
     loans: HashMap<String, Loan>,
 
+    /// Isolated margin wallets by symbol, ring-fenced from the main balances
+    #[serde(default)]
+    isolated: HashMap<Symbol, IsolatedMarginAccount>,
+
     /// Margin configuration (in basis points)
     pub initial_margin_rate: Rate,
     pub maintenance_margin_rate: Rate,
@@ -193,6 +205,7 @@ impl Account {
             balances: HashMap::new(),
             positions: HashMap::new(),
             loans: HashMap::new(),
+            isolated: HashMap::new(),
             initial_margin_rate: Rate::from_bps(1000), // 10%
             maintenance_margin_rate: Rate::from_bps(500), // 5%
             fee_schedule: FeeSchedule::default(),
@@ -377,6 +390,219 @@ impl Account {
         self.loans.contains_key(asset)
     }
 
+    // ========== Spot Margin Operations ==========
+
+    /// Borrow an asset on cross margin, crediting it to the main balances
+    ///
+    /// Unlike `borrow`, no collateral is locked; the caller is expected to
+    /// check the resulting margin level.
+    pub fn margin_borrow(
+        &mut self,
+        asset: &str,
+        amount: Value,
+        interest_rate: Rate,
+        now: Timestamp,
+    ) -> Result<(), AccountError> {
+        self.ensure_can_borrow()?;
+        self.cross_ledger()
+            .borrow(asset, amount, interest_rate, now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Repay a cross margin loan, returning the amount repaid
+    pub fn margin_repay(
+        &mut self,
+        asset: &str,
+        amount: Value,
+        now: Timestamp,
+    ) -> Result<Value, AccountError> {
+        let repaid = self.cross_ledger().repay(asset, amount, now)?;
+        self.updated_at = now;
+        Ok(repaid)
+    }
+
+    /// Move available funds from the main balances into an isolated margin wallet
+    ///
+    /// The wallet is created on first transfer.
+    pub fn transfer_to_isolated(
+        &mut self,
+        pair: &TradingPairConfig,
+        asset: &str,
+        amount: Value,
+        now: Timestamp,
+    ) -> Result<(), AccountError> {
+        if asset != pair.base_asset && asset != pair.quote_asset {
+            return Err(AccountError::InvalidMarginAsset);
+        }
+        self.withdraw(asset, amount)?;
+
+        let wallet = self.isolated.entry(pair.symbol.clone()).or_insert_with(|| {
+            IsolatedMarginAccount::new(
+                pair.symbol.clone(),
+                pair.base_asset.clone(),
+                pair.quote_asset.clone(),
+            )
+        });
+        let balance = wallet.balances_mut().entry(asset.to_string()).or_default();
+        balance.available = balance.available + amount;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Move available funds from an isolated margin wallet back to the main balances
+    pub fn transfer_from_isolated(
+        &mut self,
+        symbol: &Symbol,
+        asset: &str,
+        amount: Value,
+        now: Timestamp,
+    ) -> Result<(), AccountError> {
+        let wallet = self
+            .isolated
+            .get_mut(symbol)
+            .ok_or(AccountError::NoIsolatedMarginAccount)?;
+        if !wallet.holds(asset) {
+            return Err(AccountError::InvalidMarginAsset);
+        }
+        let balance = wallet
+            .balances_mut()
+            .get_mut(asset)
+            .filter(|b| b.available.raw() >= amount.raw())
+            .ok_or(AccountError::InsufficientBalance)?;
+        balance.available = balance.available - amount;
+
+        self.deposit(asset, amount);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Borrow one of the pair's assets inside an isolated margin wallet
+    pub fn isolated_borrow(
+        &mut self,
+        symbol: &Symbol,
+        asset: &str,
+        amount: Value,
+        interest_rate: Rate,
+        now: Timestamp,
+    ) -> Result<(), AccountError> {
+        self.ensure_can_borrow()?;
+        let wallet = self
+            .isolated
+            .get_mut(symbol)
+            .ok_or(AccountError::NoIsolatedMarginAccount)?;
+        if !wallet.holds(asset) {
+            return Err(AccountError::InvalidMarginAsset);
+        }
+        wallet.ledger().borrow(asset, amount, interest_rate, now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Repay an isolated margin loan, returning the amount repaid
+    pub fn isolated_repay(
+        &mut self,
+        symbol: &Symbol,
+        asset: &str,
+        amount: Value,
+        now: Timestamp,
+    ) -> Result<Value, AccountError> {
+        let wallet = self
+            .isolated
+            .get_mut(symbol)
+            .ok_or(AccountError::NoIsolatedMarginAccount)?;
+        let repaid = wallet.ledger().repay(asset, amount, now)?;
+        self.updated_at = now;
+        Ok(repaid)
+    }
+
+    /// Get the isolated margin wallet for a symbol
+    pub fn isolated_margin(&self, symbol: &Symbol) -> Option<&IsolatedMarginAccount> {
+        self.isolated.get(symbol)
+    }
+
+    /// Get all isolated margin wallets
+    pub fn isolated_margins(&self) -> impl Iterator<Item = &IsolatedMarginAccount> {
+        self.isolated.values()
+    }
+
+    /// Whether anything is borrowed on cross or isolated margin
+    pub fn has_margin_debt(&self) -> bool {
+        !self.loans.is_empty() || self.isolated.values().any(|w| w.has_debt())
+    }
+
+    /// Whether any cross or isolated margin loan has interest to accrue up to `now`
+    pub fn margin_interest_due(&self, now: Timestamp) -> bool {
+        self.loans.values().any(|loan| loan.last_accrual < now)
+            || self.isolated.values().any(|w| w.interest_due(now))
+    }
+
+    /// Accrue interest on every cross and isolated margin loan up to `now`
+    pub fn accrue_margin_interest(&mut self, now: Timestamp) {
+        self.cross_ledger().accrue_interest(now);
+        for wallet in self.isolated.values_mut() {
+            wallet.ledger().accrue_interest(now);
+        }
+    }
+
+    /// Value the cross margin wallet (main balances only)
+    pub fn cross_margin_totals(&self, prices: &AssetPrices) -> MarginTotals {
+        let calc = AccountMarginCalculator::default();
+        calc.margin_totals(self.balances.iter(), prices)
+    }
+
+    /// Value one isolated margin wallet
+    pub fn isolated_margin_totals(
+        &self,
+        symbol: &Symbol,
+        prices: &AssetPrices,
+    ) -> Option<MarginTotals> {
+        let calc = AccountMarginCalculator::default();
+        let wallet = self.isolated.get(symbol)?;
+        Some(calc.margin_totals(wallet.all_balances(), prices))
+    }
+
+    /// Liquidate the cross margin wallet against its available balances
+    pub fn liquidate_cross_margin(
+        &mut self,
+        prices: &AssetPrices,
+        fee: Rate,
+        now: Timestamp,
+    ) -> MarginLiquidation {
+        let result = self.cross_ledger().liquidate(prices, fee, now);
+        self.updated_at = now;
+        result
+    }
+
+    /// Liquidate one isolated margin wallet against its own balances
+    pub fn liquidate_isolated_margin(
+        &mut self,
+        symbol: &Symbol,
+        prices: &AssetPrices,
+        fee: Rate,
+        now: Timestamp,
+    ) -> Option<MarginLiquidation> {
+        let result = self
+            .isolated
+            .get_mut(symbol)?
+            .ledger()
+            .liquidate(prices, fee, now);
+        self.updated_at = now;
+        Some(result)
+    }
+
+    fn cross_ledger(&mut self) -> MarginLedger<'_> {
+        MarginLedger::new(&mut self.balances, &mut self.loans)
+    }
+
+    fn ensure_can_borrow(&self) -> Result<(), AccountError> {
+        match self.status {
+            AccountStatus::Frozen => Err(AccountError::AccountFrozen),
+            AccountStatus::Liquidating => Err(AccountError::AccountLiquidating),
+            _ => Ok(()),
+        }
+    }
+
     // ========== Position Operations ==========
 
     /// Open or increase a position
@@ -454,13 +680,19 @@ impl Account {
     }
 
     /// Update mark prices for all positions
+    ///
+    /// Portfolio margin accounts keep their status until
+    /// `refresh_status_with` is called, since the scenario grid needs market
+    /// state the account does not hold.
     pub fn update_mark_prices(&mut self, prices: &HashMap<Symbol, Price>, now: Timestamp) {
         for (symbol, price) in prices {
             if let Some(pos) = self.positions.get_mut(symbol) {
                 pos.update_mark_price(*price, now);
             }
         }
-        self.update_status();
+        if self.margin_mode != MarginMode::Portfolio {
+            self.update_status();
+        }
         self.updated_at = now;
     }
 
@@ -501,6 +733,31 @@ impl Account {
         )
     }
 
+    /// Margin ratio under a specific margin model (e.g. portfolio margin)
+    pub fn margin_ratio_with<M: MarginCalculator>(&self, calc: &AccountMarginCalculator<M>) -> i64 {
+        calc.margin_ratio(
+            self.equity(),
+            self.positions.values(),
+            self.maintenance_margin_rate,
+        )
+    }
+
+    /// Maintenance margin required under a specific margin model
+    pub fn maintenance_margin_with<M: MarginCalculator>(
+        &self,
+        calc: &AccountMarginCalculator<M>,
+    ) -> Value {
+        let positions: Vec<&Position> = self.positions.values().collect();
+        calc.position_calculator()
+            .maintenance_margin(&positions, self.maintenance_margin_rate)
+    }
+
+    /// Re-evaluate the account status under a specific margin model
+    pub fn refresh_status_with<M: MarginCalculator>(&mut self, calc: &AccountMarginCalculator<M>) {
+        let ratio = self.margin_ratio_with(calc);
+        self.set_margin_status(calc.determine_status(ratio, !self.positions.is_empty()));
+    }
+
     /// Check if account has sufficient margin for a new order
     pub fn has_sufficient_margin(&self, required: Value) -> bool {
         self.available_margin().raw() >= required.raw()
@@ -516,6 +773,10 @@ impl Account {
     fn update_status(&mut self) {
         let calc = AccountMarginCalculator::default();
         let status = calc.determine_status(self.margin_ratio(), !self.positions.is_empty());
+        self.set_margin_status(status);
+    }
+
+    fn set_margin_status(&mut self, status: MarginStatus) {
         self.status = match status {
             MarginStatus::Healthy => AccountStatus::Active,
             MarginStatus::MarginCall => AccountStatus::MarginCall,
@@ -537,6 +798,8 @@ pub enum AccountError {
     InsufficientCollateral,
     InsufficientMargin,
     NoActiveLoan,
+    NoIsolatedMarginAccount,
+    InvalidMarginAsset,
     NoPosition,
     AccountFrozen,
     AccountLiquidating,
@@ -549,6 +812,8 @@ impl std::fmt::Display for AccountError {
             Self::InsufficientCollateral => write!(f, "Insufficient collateral"),
             Self::InsufficientMargin => write!(f, "Insufficient margin"),
             Self::NoActiveLoan => write!(f, "No active loan for this asset"),
+            Self::NoIsolatedMarginAccount => write!(f, "Isolated margin account does not exist"),
+            Self::InvalidMarginAsset => write!(f, "Asset is not part of this margin pair"),
             Self::NoPosition => write!(f, "No position for this symbol"),
             Self::AccountFrozen => write!(f, "Account is frozen"),
             Self::AccountLiquidating => write!(f, "Account is being liquidated"),
@@ -773,4 +1038,136 @@ mod tests {
         assert_eq!(maker_rate.bps(), -5); // -50% of 10 = rebate
         assert_eq!(taker_rate.bps(), 10); // 50% of 20
     }
+
+    #[test]
+    fn test_isolated_margin_is_ring_fenced() {
+        let mut account = Account::new("margin");
+        let now = chrono::Utc::now();
+        let pair = TradingPairConfig::new(Symbol::new("BTCUSDT").unwrap(), "BTC", "USDT");
+        let prices = AssetPrices::new("USDT").with_price("BTC", Price::from_int(50000));
+
+        account.deposit("USDT", val(100000));
+        account
+            .transfer_to_isolated(&pair, "USDT", val(10000), now)
+            .unwrap();
+        assert_eq!(
+            account.transfer_to_isolated(&pair, "ETH", val(1), now),
+            Err(AccountError::InvalidMarginAsset)
+        );
+        account
+            .isolated_borrow(&pair.symbol, "BTC", val(1), Rate::from_bps(500), now)
+            .unwrap();
+
+        // Isolated debt never shows up in cross margin or the main balances
+        let cross = account.cross_margin_totals(&prices);
+        assert_eq!(cross.total_asset.raw(), val(90000).raw());
+        assert_eq!(cross.total_liability, Value::ZERO);
+        assert_eq!(account.balance("BTC").available, Value::ZERO);
+        assert_eq!(account.equity().raw(), val(90000).raw());
+
+        let isolated = account
+            .isolated_margin_totals(&pair.symbol, &prices)
+            .unwrap();
+        assert_eq!(isolated.total_asset.raw(), val(60000).raw());
+        assert_eq!(isolated.total_liability.raw(), val(50000).raw());
+        assert!(account.has_margin_debt());
+
+        // Borrowed funds stay in the isolated wallet
+        assert_eq!(
+            account.transfer_from_isolated(&pair.symbol, "USDT", val(20000), now),
+            Err(AccountError::InsufficientBalance)
+        );
+        account
+            .isolated_repay(&pair.symbol, "BTC", val(1), now)
+            .unwrap();
+        account
+            .transfer_from_isolated(&pair.symbol, "USDT", val(10000), now)
+            .unwrap();
+        assert_eq!(account.balance("USDT").available.raw(), val(100000).raw());
+        assert!(!account.has_margin_debt());
+    }
+
+    #[test]
+    fn test_cross_margin_borrow_blocked_when_frozen() {
+        let mut account = Account::new("margin");
+        let now = chrono::Utc::now();
+        account.status = AccountStatus::Frozen;
+        assert_eq!(
+            account.margin_borrow("BTC", val(1), Rate::from_bps(500), now),
+            Err(AccountError::AccountFrozen)
+        );
+
+        account.status = AccountStatus::Active;
+        account
+            .margin_borrow("BTC", val(1), Rate::from_bps(500), now)
+            .unwrap();
+        assert_eq!(account.balance("BTC").borrowed.raw(), val(1).raw());
+        assert_eq!(
+            account.margin_repay("ETH", val(1), now),
+            Err(AccountError::NoActiveLoan)
+        );
+    }
+
+    #[test]
+    fn test_portfolio_margin_status_uses_scenario_requirement() {
+        use crate::domain::instruments::{
+            Instrument, OptionContract, OptionType, PerpetualContract,
+        };
+        use crate::domain::services::PortfolioMarginCalculator;
+
+        let now = chrono::Utc::now();
+        let call = OptionContract::new(
+            "BTC",
+            now + chrono::Duration::days(30),
+            Price::from_int(50_000),
+            OptionType::Call,
+        )
+        .with_symbol("BTC-C-50000");
+        let calc = AccountMarginCalculator::new(
+            PortfolioMarginCalculator::new(now)
+                .with_instrument(Instrument::Option(call))
+                .with_instrument(Instrument::Perpetual(PerpetualContract::linear(
+                    "BTC", "BTCUSDT",
+                )))
+                .with_market("BTC", Price::from_int(50_000), 0.6),
+        );
+
+        let mut account = Account::new("mm");
+        account.margin_mode = MarginMode::Portfolio;
+        let option = Symbol::new("BTC-C-50000").unwrap();
+        let perp = Symbol::new("BTCUSDT").unwrap();
+        account.open_position(
+            option.clone(),
+            PositionSide::Short,
+            Quantity::from_int(1),
+            Price::from_int(3_500),
+            Value::ZERO,
+            now,
+        );
+        account.open_position(
+            perp,
+            PositionSide::Long,
+            Quantity::from_f64(0.54),
+            Price::from_int(50_000),
+            Value::ZERO,
+            now,
+        );
+
+        let requirement = account.maintenance_margin_with(&calc);
+        assert!(requirement.raw() > 0);
+
+        account.deposit("USDT", Value::from_raw(requirement.raw() * 2));
+        account.refresh_status_with(&calc);
+        assert_eq!(account.status, AccountStatus::Active);
+
+        // Mark updates alone leave a portfolio account's status untouched
+        account
+            .withdraw("USDT", Value::from_raw(requirement.raw() * 3 / 2))
+            .unwrap();
+        account.update_mark_prices(&HashMap::from([(option, Price::from_int(3_500))]), now);
+        assert_eq!(account.status, AccountStatus::Active);
+
+        account.refresh_status_with(&calc);
+        assert_eq!(account.status, AccountStatus::Liquidating);
+    }
 }
@@ -70,6 +70,12 @@ impl Loan {
         self.last_accrual = now;
     }
 
+    /// Borrow more against the same loan, accruing interest up to `now` first
+    pub fn increase(&mut self, amount: Value, now: Timestamp) {
+        self.accrue_interest(now);
+        self.principal = self.principal + amount;
+    }
+
     /// Repay part of the loan, returns remaining principal
     pub fn repay(&mut self, amount: Value) -> Value {
         // First pay off interest
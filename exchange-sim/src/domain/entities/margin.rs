@@ -0,0 +1,391 @@
+//! Spot margin wallets: borrowing, repayment, interest and liquidation.
+//!
+//! Cross margin borrows against the account's main balances. Each isolated
+//! margin symbol gets its own wallet holding only that pair's base and quote
+//! assets, so its collateral and debt never mix with anything else.
+
+use crate::domain::entities::{AccountError, AssetBalance, Loan};
+use crate::domain::services::AssetPrices;
+use crate::domain::value_objects::{Rate, Symbol, Timestamp, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Isolated margin wallet for a single trading pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsolatedMarginAccount {
+    pub symbol: Symbol,
+    pub base_asset: String,
+    pub quote_asset: String,
+    balances: HashMap<String, AssetBalance>,
+    loans: HashMap<String, Loan>,
+}
+
+impl IsolatedMarginAccount {
+    pub fn new(
+        symbol: Symbol,
+        base_asset: impl Into<String>,
+        quote_asset: impl Into<String>,
+    ) -> Self {
+        Self {
+            symbol,
+            base_asset: base_asset.into(),
+            quote_asset: quote_asset.into(),
+            balances: HashMap::new(),
+            loans: HashMap::new(),
+        }
+    }
+
+    /// Whether the asset is one of this pair's two assets
+    pub fn holds(&self, asset: &str) -> bool {
+        asset == self.base_asset || asset == self.quote_asset
+    }
+
+    pub fn balance(&self, asset: &str) -> AssetBalance {
+        self.balances.get(asset).copied().unwrap_or_default()
+    }
+
+    pub fn all_balances(&self) -> impl Iterator<Item = (&String, &AssetBalance)> {
+        self.balances.iter()
+    }
+
+    pub fn loan(&self, asset: &str) -> Option<&Loan> {
+        self.loans.get(asset)
+    }
+
+    pub fn has_debt(&self) -> bool {
+        !self.loans.is_empty()
+    }
+
+    /// Whether any loan has interest to accrue up to `now`
+    pub fn interest_due(&self, now: Timestamp) -> bool {
+        self.loans.values().any(|loan| loan.last_accrual < now)
+    }
+
+    pub(crate) fn balances_mut(&mut self) -> &mut HashMap<String, AssetBalance> {
+        &mut self.balances
+    }
+
+    pub(crate) fn ledger(&mut self) -> MarginLedger<'_> {
+        MarginLedger::new(&mut self.balances, &mut self.loans)
+    }
+}
+
+/// Outcome of liquidating a margin wallet
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarginLiquidation {
+    /// Debt repaid per asset (interest first, then principal)
+    pub repaid: Vec<(String, Value)>,
+    /// Collateral taken per asset to buy back the debt
+    pub seized: Vec<(String, Value)>,
+    /// Liquidation fee charged, in the quote asset
+    pub fee: Value,
+    /// Debt left once available collateral ran out, in the quote asset
+    pub shortfall: Value,
+}
+
+impl MarginLiquidation {
+    pub fn is_empty(&self) -> bool {
+        self.repaid.is_empty() && self.seized.is_empty()
+    }
+}
+
+/// Borrow/repay bookkeeping over one wallet's balances and loans
+pub(crate) struct MarginLedger<'a> {
+    balances: &'a mut HashMap<String, AssetBalance>,
+    loans: &'a mut HashMap<String, Loan>,
+}
+
+impl<'a> MarginLedger<'a> {
+    pub(crate) fn new(
+        balances: &'a mut HashMap<String, AssetBalance>,
+        loans: &'a mut HashMap<String, Loan>,
+    ) -> Self {
+        Self { balances, loans }
+    }
+
+    /// Borrow an asset, crediting it as available
+    pub(crate) fn borrow(
+        &mut self,
+        asset: &str,
+        amount: Value,
+        interest_rate: Rate,
+        now: Timestamp,
+    ) {
+        match self.loans.get_mut(asset) {
+            Some(loan) => {
+                loan.increase(amount, now);
+                loan.interest_rate = interest_rate;
+            }
+            None => {
+                let loan = Loan::new(asset, amount, interest_rate, Value::ZERO, now);
+                self.loans.insert(asset.to_string(), loan);
+            }
+        }
+
+        let balance = self.balances.entry(asset.to_string()).or_default();
+        balance.available = balance.available + amount;
+        self.sync_balance(asset);
+    }
+
+    /// Repay up to `amount` of a loan from the available balance
+    ///
+    /// Interest is paid before principal. Returns the amount actually repaid.
+    pub(crate) fn repay(
+        &mut self,
+        asset: &str,
+        amount: Value,
+        now: Timestamp,
+    ) -> Result<Value, AccountError> {
+        let loan = self
+            .loans
+            .get_mut(asset)
+            .ok_or(AccountError::NoActiveLoan)?;
+        loan.accrue_interest(now);
+
+        let repay = Value::from_raw(amount.raw().min(loan.total_owed().raw()));
+        let balance = self.balances.entry(asset.to_string()).or_default();
+        if balance.available.raw() < repay.raw() {
+            return Err(AccountError::InsufficientBalance);
+        }
+
+        balance.available = balance.available - repay;
+        loan.repay(repay);
+        if loan.is_repaid() {
+            self.loans.remove(asset);
+        }
+        self.sync_balance(asset);
+        Ok(repay)
+    }
+
+    /// Accrue interest on every loan up to `now`
+    pub(crate) fn accrue_interest(&mut self, now: Timestamp) {
+        let assets: Vec<String> = self.loans.keys().cloned().collect();
+        for asset in assets {
+            if let Some(loan) = self.loans.get_mut(&asset) {
+                loan.accrue_interest(now);
+            }
+            self.sync_balance(&asset);
+        }
+    }
+
+    /// Repay every loan, seizing available collateral at `prices` plus `fee`
+    ///
+    /// Each debt is paid from the same asset first; the rest is bought back
+    /// with other collateral, quote asset first. Funds locked in open orders
+    /// are not touched, so debt can remain when available collateral runs out.
+    pub(crate) fn liquidate(
+        &mut self,
+        prices: &AssetPrices,
+        fee: Rate,
+        now: Timestamp,
+    ) -> MarginLiquidation {
+        self.accrue_interest(now);
+
+        let mut result = MarginLiquidation::default();
+        let mut debts: Vec<String> = self.loans.keys().cloned().collect();
+        debts.sort();
+
+        for asset in debts {
+            let owed = self.loans[&asset].total_owed();
+            let own = self.balance(&asset).available.raw().min(owed.raw());
+            let remaining = Value::from_raw(owed.raw() - own);
+
+            let mut bought = Value::ZERO;
+            if remaining.raw() > 0
+                && let Some(debt_value) = prices.value_of(&asset, remaining)
+            {
+                let target = debt_value + fee.apply_to_value(debt_value);
+                let mut needed = target;
+
+                let mut collateral: Vec<String> = self
+                    .balances
+                    .iter()
+                    .filter(|(a, b)| **a != asset && b.available.raw() > 0)
+                    .map(|(a, _)| a.clone())
+                    .collect();
+                collateral.sort_by_key(|a| (a != prices.quote_asset(), a.clone()));
+
+                for coll in collateral {
+                    if needed.raw() <= 0 {
+                        break;
+                    }
+                    let available = self.balance(&coll).available;
+                    let Some(value) = prices.value_of(&coll, available) else {
+                        continue;
+                    };
+                    let take = if value.raw() <= needed.raw() {
+                        available
+                    } else {
+                        prices.amount_of(&coll, needed).map_or(available, |amt| {
+                            Value::from_raw(amt.raw().min(available.raw()))
+                        })
+                    };
+                    let taken_value = Value::from_raw(value.raw().min(needed.raw()));
+                    if let Some(balance) = self.balances.get_mut(&coll) {
+                        balance.available = balance.available - take;
+                    }
+                    result.seized.push((coll, take));
+                    needed = needed - taken_value;
+                }
+
+                let seized_value = target - Value::from_raw(needed.raw().max(0));
+                let bought_value =
+                    Value::from_raw(seized_value.raw() * 10_000 / (10_000 + fee.bps() as i128));
+                result.fee = result.fee + (seized_value - bought_value);
+
+                bought = if needed.raw() <= 0 {
+                    remaining
+                } else {
+                    let amount = prices.amount_of(&asset, bought_value).unwrap_or_default();
+                    Value::from_raw(amount.raw().min(remaining.raw()))
+                };
+                if needed.raw() > 0 {
+                    result.shortfall = result.shortfall
+                        + prices
+                            .value_of(&asset, remaining - bought)
+                            .unwrap_or_default();
+                }
+
+                let balance = self.balances.entry(asset.clone()).or_default();
+                balance.available = balance.available + bought;
+            }
+
+            let to_repay = Value::from_raw(own + bought.raw());
+            if to_repay.raw() > 0
+                && let Ok(repaid) = self.repay(&asset, to_repay, now)
+            {
+                result.repaid.push((asset, repaid));
+            }
+        }
+
+        result
+    }
+
+    fn balance(&self, asset: &str) -> AssetBalance {
+        self.balances.get(asset).copied().unwrap_or_default()
+    }
+
+    /// Mirror a loan's principal and interest onto the asset balance
+    fn sync_balance(&mut self, asset: &str) {
+        let (borrowed, interest) = self
+            .loans
+            .get(asset)
+            .map_or((Value::ZERO, Value::ZERO), |l| {
+                (l.principal, l.accrued_interest)
+            });
+        if let Some(balance) = self.balances.get_mut(asset) {
+            balance.borrowed = borrowed;
+            balance.interest = interest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Price;
+
+    fn ledger_parts() -> (HashMap<String, AssetBalance>, HashMap<String, Loan>) {
+        (HashMap::new(), HashMap::new())
+    }
+
+    #[test]
+    fn test_borrow_and_repay_with_interest() {
+        let (mut balances, mut loans) = ledger_parts();
+        let mut ledger = MarginLedger::new(&mut balances, &mut loans);
+        let start = chrono::Utc::now();
+
+        // 10% a year on 365 BTC is 0.1 BTC a day
+        ledger.borrow("BTC", Value::from_int(365), Rate::from_bps(1000), start);
+        ledger.accrue_interest(start + chrono::Duration::days(1));
+
+        let balance = ledger.balance("BTC");
+        assert_eq!(balance.available, Value::from_int(365));
+        assert_eq!(balance.borrowed, Value::from_int(365));
+        assert_eq!(balance.interest, Value::from_raw(10_000_000));
+
+        // Not enough to cover principal plus interest
+        let later = start + chrono::Duration::days(1);
+        assert_eq!(
+            ledger.repay("BTC", Value::from_int(400), later),
+            Err(AccountError::InsufficientBalance)
+        );
+
+        // Interest is paid first
+        let repaid = ledger.repay("BTC", Value::from_int(1), later).unwrap();
+        assert_eq!(repaid, Value::from_int(1));
+        let balance = ledger.balance("BTC");
+        assert_eq!(balance.interest, Value::ZERO);
+        assert_eq!(balance.borrowed, Value::from_raw(36_410_000_000));
+
+        ledger.repay("BTC", Value::from_int(364), later).unwrap();
+        assert_eq!(ledger.balance("BTC").borrowed, Value::from_raw(10_000_000));
+        assert_eq!(ledger.balance("BTC").available, Value::ZERO);
+        assert_eq!(
+            ledger.repay("ETH", Value::from_int(1), later),
+            Err(AccountError::NoActiveLoan)
+        );
+    }
+
+    #[test]
+    fn test_liquidate_seizes_quote_collateral_with_fee() {
+        let (mut balances, mut loans) = ledger_parts();
+        let mut ledger = MarginLedger::new(&mut balances, &mut loans);
+        let now = chrono::Utc::now();
+        let prices = AssetPrices::new("USDT").with_price("BTC", Price::from_int(50000));
+
+        // Borrowed BTC was sold away; only USDT collateral is left
+        ledger.borrow("BTC", Value::from_int(1), Rate::ZERO, now);
+        ledger.balances.get_mut("BTC").unwrap().available = Value::ZERO;
+        ledger.balances.insert(
+            "USDT".to_string(),
+            AssetBalance {
+                available: Value::from_int(54000),
+                ..Default::default()
+            },
+        );
+
+        let result = ledger.liquidate(&prices, Rate::from_bps(100), now);
+
+        assert_eq!(result.repaid, vec![("BTC".to_string(), Value::from_int(1))]);
+        assert_eq!(
+            result.seized,
+            vec![("USDT".to_string(), Value::from_int(50500))]
+        );
+        assert_eq!(result.fee, Value::from_int(500));
+        assert_eq!(result.shortfall, Value::ZERO);
+        assert!(ledger.loans.is_empty());
+        assert_eq!(ledger.balance("USDT").available, Value::from_int(3500));
+        assert_eq!(ledger.balance("BTC").borrowed, Value::ZERO);
+    }
+
+    #[test]
+    fn test_liquidate_reports_shortfall() {
+        let (mut balances, mut loans) = ledger_parts();
+        let mut ledger = MarginLedger::new(&mut balances, &mut loans);
+        let now = chrono::Utc::now();
+        let prices = AssetPrices::new("USDT").with_price("BTC", Price::from_int(50000));
+
+        ledger.borrow("BTC", Value::from_int(1), Rate::ZERO, now);
+        ledger.balances.get_mut("BTC").unwrap().available = Value::ZERO;
+        ledger.balances.insert(
+            "USDT".to_string(),
+            AssetBalance {
+                available: Value::from_int(30300),
+                ..Default::default()
+            },
+        );
+
+        let result = ledger.liquidate(&prices, Rate::from_bps(100), now);
+
+        // 30300 USDT buys 0.6 BTC after the 1% fee
+        assert_eq!(
+            result.repaid,
+            vec![("BTC".to_string(), Value::from_raw(60_000_000))]
+        );
+        assert_eq!(result.fee, Value::from_int(300));
+        assert_eq!(result.shortfall, Value::from_int(20000));
+        assert_eq!(ledger.balance("USDT").available, Value::ZERO);
+        assert_eq!(ledger.balance("BTC").borrowed, Value::from_raw(40_000_000));
+    }
+}
@@ -3,6 +3,7 @@ mod custodian;
 mod instrument;
 mod liquidity_pool;
 mod loan;
+mod margin;
 mod order_book;
 mod position;
 mod withdrawal;
@@ -23,7 +24,9 @@ pub use liquidity_pool::{
 };
 // Note: ExerciseStyle and OptionType are re-exported from domain::instruments to avoid duplication
 pub use loan::Loan;
-pub use order_book::{OrderBook, OrderBookSnapshot};
+pub(crate) use margin::MarginLedger;
+pub use margin::{IsolatedMarginAccount, MarginLiquidation};
+pub use order_book::{BookDelta, OrderBook, OrderBookSnapshot, RestingOrder};
 pub use position::{Position, PositionSide};
 pub use withdrawal::{WithdrawalId, WithdrawalRequest, WithdrawalStatus, WithdrawalStatusEvent};
 
@@ -1,6 +1,7 @@
 use crate::domain::entities::{Order, PriceLevel, Trade};
-use crate::domain::matching::{MatchingAlgorithm, PriceTimeMatcher};
-use crate::domain::value_objects::{OrderId, Price, Quantity, Side, Symbol, Timestamp};
+use crate::domain::events::{MarketByOrderEvent, MboAction};
+use crate::domain::matching::{MatchResult, MatchingAlgorithm, PriceTimeMatcher};
+use crate::domain::value_objects::{OrderId, Price, Quantity, Side, Symbol, Timestamp, TradeId};
 use indexmap::IndexMap;
 use serde::Serialize;
 use std::collections::{BTreeMap, HashMap, VecDeque};
@@ -24,8 +25,16 @@ pub struct OrderBook {
     ask_quantities: IndexMap<Price, Quantity>,
     /// Matching algorithm
     matcher: Arc<dyn MatchingAlgorithm>,
+    /// Anonymised order references for the L3 feed
+    order_refs: HashMap<OrderId, u64>,
+    next_order_ref: u64,
+    /// Order-level changes not yet taken by the publisher
+    pending_deltas: VecDeque<BookDelta>,
 }
 
+/// Pending deltas kept when nothing drains the book (oldest dropped first)
+const MAX_PENDING_DELTAS: usize = 10_000;
+
 impl std::fmt::Debug for OrderBook {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OrderBook")
@@ -99,6 +108,9 @@ impl OrderBook {
             bid_quantities: IndexMap::new(),
             ask_quantities: IndexMap::new(),
             matcher,
+            order_refs: HashMap::new(),
+            next_order_ref: 1,
+            pending_deltas: VecDeque::new(),
         }
     }
 
@@ -120,6 +132,44 @@ impl OrderBook {
         self.sequence
     }
 
+    /// Anonymised reference of a resting order, as used in the L3 feed
+    pub fn order_ref(&self, order_id: OrderId) -> Option<u64> {
+        self.order_refs.get(&order_id).copied()
+    }
+
+    /// Take the order-level changes recorded since the last call
+    pub fn take_deltas(&mut self) -> Vec<BookDelta> {
+        self.pending_deltas.drain(..).collect()
+    }
+
+    /// Advance the sequence and record an order-level change
+    #[allow(clippy::too_many_arguments)]
+    fn record_delta(
+        &mut self,
+        action: MboAction,
+        order_ref: u64,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        remaining: Quantity,
+        trade_id: Option<TradeId>,
+    ) {
+        let sequence = self.increment_sequence();
+        if self.pending_deltas.len() == MAX_PENDING_DELTAS {
+            self.pending_deltas.pop_front();
+        }
+        self.pending_deltas.push_back(BookDelta {
+            sequence,
+            action,
+            order_ref,
+            side,
+            price,
+            quantity,
+            remaining,
+            trade_id,
+        });
+    }
+
     /// Best bid price (highest buy order)
     pub fn best_bid(&self) -> Option<Price> {
         self.bids
@@ -158,6 +208,7 @@ impl OrderBook {
         let price = order.price.expect("Limit order must have price");
         let side = order.side;
         let order_id = order.id;
+        let quantity = order.remaining_quantity();
 
         match side {
             Side::Buy => {
@@ -183,7 +234,19 @@ impl OrderBook {
         }
 
         self.order_index.insert(order_id, (side, price));
-        self.increment_sequence();
+
+        let order_ref = self.next_order_ref;
+        self.next_order_ref += 1;
+        self.order_refs.insert(order_id, order_ref);
+        self.record_delta(
+            MboAction::Add,
+            order_ref,
+            side,
+            price,
+            quantity,
+            quantity,
+            None,
+        );
     }
 
     /// Remove an order from the book
@@ -231,10 +294,98 @@ impl OrderBook {
             }
         };
 
-        self.increment_sequence();
+        let order_ref = self.order_refs.remove(&order_id).unwrap_or_default();
+        self.record_delta(
+            MboAction::Delete,
+            order_ref,
+            side,
+            price,
+            order.remaining_quantity(),
+            Quantity::ZERO,
+            None,
+        );
         Some(order)
     }
 
+    /// Reduce a resting order's quantity in place, keeping its queue position
+    ///
+    /// Returns the updated order, or `None` if the order is unknown or the
+    /// reduction would leave nothing resting (use `remove_order` instead).
+    pub fn reduce_order(&mut self, order_id: OrderId, reduce_by: Quantity) -> Option<&Order> {
+        let (side, price) = *self.order_index.get(&order_id)?;
+        let (queue, quantities) = match side {
+            Side::Buy => (
+                self.bids.get_mut(&PriceKey::bid(price))?,
+                &mut self.bid_quantities,
+            ),
+            Side::Sell => (
+                self.asks.get_mut(&PriceKey::ask(price))?,
+                &mut self.ask_quantities,
+            ),
+        };
+        let order = queue.iter_mut().find(|o| o.id == order_id)?;
+        if reduce_by.is_zero() || reduce_by >= order.remaining_quantity() {
+            return None;
+        }
+
+        order.quantity = order.quantity - reduce_by;
+        let remaining = order.remaining_quantity();
+        if let Some(qty) = quantities.get_mut(&price) {
+            *qty = qty.saturating_sub(reduce_by);
+        }
+
+        let order_ref = self.order_refs.get(&order_id).copied().unwrap_or_default();
+        self.record_delta(
+            MboAction::Reduce,
+            order_ref,
+            side,
+            price,
+            reduce_by,
+            remaining,
+            None,
+        );
+        self.get_order(order_id)
+    }
+
+    /// Change a resting order's client order ID (e.g. after an amend)
+    ///
+    /// Returns false if the order is not resting in the book.
+    pub fn set_client_order_id(&mut self, order_id: OrderId, client_order_id: String) -> bool {
+        let Some(&(side, price)) = self.order_index.get(&order_id) else {
+            return false;
+        };
+        let queue = match side {
+            Side::Buy => self.bids.get_mut(&PriceKey::bid(price)),
+            Side::Sell => self.asks.get_mut(&PriceKey::ask(price)),
+        };
+        match queue.and_then(|q| q.iter_mut().find(|o| o.id == order_id)) {
+            Some(order) => {
+                order.client_order_id = Some(client_order_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resting orders on one side in queue order (best price first, then time)
+    pub fn resting_orders(&self, side: Side) -> Vec<RestingOrder> {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        levels
+            .iter()
+            .flat_map(|(key, queue)| {
+                queue.iter().map(move |o| RestingOrder {
+                    order_ref: self.order_refs.get(&o.id).copied().unwrap_or_default(),
+                    side,
+                    price: Price::from_raw(key.price),
+                    quantity: o.remaining_quantity(),
+                })
+            })
+            .collect()
+    }
+
     /// Get an order by ID
     pub fn get_order(&self, order_id: OrderId) -> Option<&Order> {
         let (side, price) = self.order_index.get(&order_id)?;
@@ -251,6 +402,15 @@ impl OrderBook {
         }
     }
 
+    /// Find a resting order by its client order ID
+    pub fn find_by_client_order_id(&self, client_order_id: &str) -> Option<&Order> {
+        self.bids
+            .values()
+            .chain(self.asks.values())
+            .flat_map(|queue| queue.iter())
+            .find(|o| o.client_order_id.as_deref() == Some(client_order_id))
+    }
+
     /// Match an incoming order against the book
     /// Returns trades and the remaining order (if any)
     pub fn match_order(&mut self, mut order: Order, now: Timestamp) -> (Vec<Trade>, Option<Order>) {
@@ -279,10 +439,6 @@ impl OrderBook {
             None
         };
 
-        if !trades.is_empty() {
-            self.increment_sequence();
-        }
-
         (trades, remaining)
     }
 
@@ -331,6 +487,8 @@ impl OrderBook {
             self.order_index.remove(order_id);
         }
 
+        self.record_executions(&result, Side::Sell, ask_key);
+
         // Clean up empty price level
         if let Some(queue) = self.asks.get(&ask_key) {
             if queue.is_empty() {
@@ -386,6 +544,8 @@ impl OrderBook {
             self.order_index.remove(order_id);
         }
 
+        self.record_executions(&result, Side::Buy, bid_key);
+
         // Clean up empty price level
         if let Some(queue) = self.bids.get(&bid_key) {
             if queue.is_empty() {
@@ -396,6 +556,41 @@ impl OrderBook {
         result.trades
     }
 
+    /// Record one Execute delta per trade against a resting order
+    fn record_executions(&mut self, result: &MatchResult, maker_side: Side, key: PriceKey) {
+        for trade in &result.trades {
+            let maker_id = match maker_side {
+                Side::Buy => trade.buyer_order_id,
+                Side::Sell => trade.seller_order_id,
+            };
+            let (order_ref, remaining) = if result.filled_order_ids.contains(&maker_id) {
+                let order_ref = self.order_refs.remove(&maker_id).unwrap_or_default();
+                (order_ref, Quantity::ZERO)
+            } else {
+                let remaining = match maker_side {
+                    Side::Buy => self.bids.get(&key),
+                    Side::Sell => self.asks.get(&key),
+                }
+                .and_then(|queue| queue.iter().find(|o| o.id == maker_id))
+                .map(|o| o.remaining_quantity())
+                .unwrap_or(Quantity::ZERO);
+                (
+                    self.order_refs.get(&maker_id).copied().unwrap_or_default(),
+                    remaining,
+                )
+            };
+            self.record_delta(
+                MboAction::Execute,
+                order_ref,
+                maker_side,
+                trade.price,
+                trade.quantity,
+                remaining,
+                Some(trade.id),
+            );
+        }
+    }
+
     /// Get top N bid price levels (sorted descending by price - best bid first)
     pub fn get_bids(&self, depth: usize) -> Vec<PriceLevel> {
         let mut levels: Vec<_> = self
@@ -422,6 +617,32 @@ impl OrderBook {
         levels
     }
 
+    /// Levels for a diff depth update covering `deltas`
+    ///
+    /// The top `depth` levels of each side, plus a zero-quantity entry for
+    /// every level the deltas emptied, so a consumer applying the update as
+    /// a diff also drops levels that are gone.
+    pub fn depth_update_levels(
+        &self,
+        deltas: &[BookDelta],
+        depth: usize,
+    ) -> (Vec<PriceLevel>, Vec<PriceLevel>) {
+        let mut bids = self.get_bids(depth);
+        let mut asks = self.get_asks(depth);
+        for delta in deltas {
+            let (levels, quantities) = match delta.side {
+                Side::Buy => (&mut bids, &self.bid_quantities),
+                Side::Sell => (&mut asks, &self.ask_quantities),
+            };
+            if !quantities.contains_key(&delta.price)
+                && !levels.iter().any(|l| l.price == delta.price)
+            {
+                levels.push(PriceLevel::new(delta.price, Quantity::ZERO));
+            }
+        }
+        (bids, asks)
+    }
+
     /// Get full depth snapshot
     pub fn snapshot(&self, depth: Option<usize>) -> OrderBookSnapshot {
         let depth = depth.unwrap_or(usize::MAX);
@@ -445,6 +666,47 @@ impl OrderBook {
     }
 }
 
+/// Order-level change to the book, published as a market-by-order event
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookDelta {
+    pub sequence: u64,
+    pub action: MboAction,
+    pub order_ref: u64,
+    pub side: Side,
+    pub price: Price,
+    /// Add: quantity placed; Reduce/Delete/Execute: quantity removed
+    pub quantity: Quantity,
+    /// Quantity left resting after this change
+    pub remaining: Quantity,
+    pub trade_id: Option<TradeId>,
+}
+
+impl BookDelta {
+    pub fn into_event(self, symbol: &Symbol, timestamp: Timestamp) -> MarketByOrderEvent {
+        MarketByOrderEvent {
+            symbol: symbol.clone(),
+            sequence: self.sequence,
+            action: self.action,
+            order_ref: self.order_ref,
+            side: self.side,
+            price: self.price,
+            quantity: self.quantity,
+            remaining: self.remaining,
+            trade_id: self.trade_id,
+            timestamp,
+        }
+    }
+}
+
+/// Resting order as listed in a market-by-order snapshot
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RestingOrder {
+    pub order_ref: u64,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
 /// Immutable snapshot of order book state
 #[derive(Debug, Clone, Serialize)]
 pub struct OrderBookSnapshot {
@@ -484,6 +746,28 @@ mod tests {
         assert_eq!(book.best_bid(), Some(Price::from_int(100)));
     }
 
+    #[test]
+    fn test_find_by_client_order_id() {
+        let mut book = OrderBook::new(create_symbol());
+        let order = Order::new_limit(
+            create_symbol(),
+            Side::Sell,
+            Quantity::from_int(1),
+            Price::from_int(101),
+            TimeInForce::Gtc,
+        )
+        .with_client_order_id("my-order");
+        let order_id = order.id;
+
+        book.add_order(order);
+
+        assert_eq!(
+            book.find_by_client_order_id("my-order").map(|o| o.id),
+            Some(order_id)
+        );
+        assert!(book.find_by_client_order_id("other").is_none());
+    }
+
     #[test]
     fn test_match_orders() {
         let mut book = OrderBook::new(create_symbol());
@@ -604,4 +888,156 @@ mod tests {
         // Both orders should have remaining quantity
         assert_eq!(book.order_count(), 2);
     }
+
+    fn limit(side: Side, qty: i64, price: i64) -> Order {
+        Order::new_limit(
+            create_symbol(),
+            side,
+            Quantity::from_int(qty),
+            Price::from_int(price),
+            TimeInForce::Gtc,
+        )
+    }
+
+    #[test]
+    fn test_mbo_add_and_delete_deltas() {
+        let mut book = OrderBook::new(create_symbol());
+        let first = limit(Side::Buy, 1, 100);
+        let second = limit(Side::Buy, 2, 100);
+        let first_id = first.id;
+
+        book.add_order(first);
+        book.add_order(second);
+        book.remove_order(first_id);
+
+        let deltas = book.take_deltas();
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].action, MboAction::Add);
+        assert_eq!(deltas[1].action, MboAction::Add);
+        assert_eq!(deltas[2].action, MboAction::Delete);
+        assert_eq!(deltas[0].order_ref, deltas[2].order_ref);
+        assert!(deltas[1].order_ref > deltas[0].order_ref);
+        assert_eq!(deltas[2].remaining, Quantity::ZERO);
+        assert!(
+            deltas
+                .windows(2)
+                .all(|w| w[1].sequence == w[0].sequence + 1)
+        );
+        assert_eq!(book.sequence(), deltas[2].sequence);
+        assert!(book.order_ref(first_id).is_none());
+
+        // Journal is drained
+        assert!(book.take_deltas().is_empty());
+    }
+
+    #[test]
+    fn test_depth_update_levels_zero_emptied_levels() {
+        let mut book = OrderBook::new(create_symbol());
+        let gone = limit(Side::Buy, 1, 99);
+        let gone_id = gone.id;
+        book.add_order(gone);
+        book.add_order(limit(Side::Buy, 2, 100));
+        book.add_order(limit(Side::Sell, 1, 101));
+        book.take_deltas();
+
+        book.remove_order(gone_id);
+        book.add_order(limit(Side::Sell, 3, 102));
+        let deltas = book.take_deltas();
+        let (bids, asks) = book.depth_update_levels(&deltas, 20);
+
+        assert_eq!(
+            bids,
+            vec![
+                PriceLevel::new(Price::from_int(100), Quantity::from_int(2)),
+                PriceLevel::new(Price::from_int(99), Quantity::ZERO),
+            ]
+        );
+        assert_eq!(asks.len(), 2);
+        assert!(asks.iter().all(|l| !l.quantity.is_zero()));
+    }
+
+    #[test]
+    fn test_mbo_execute_deltas() {
+        let mut book = OrderBook::new(create_symbol());
+        let ask1 = limit(Side::Sell, 1, 100);
+        let ask2 = limit(Side::Sell, 2, 100);
+        let ask1_ref_id = ask1.id;
+        let ask2_ref_id = ask2.id;
+        book.add_order(ask1);
+        book.add_order(ask2);
+        let ask1_ref = book.order_ref(ask1_ref_id).unwrap();
+        let ask2_ref = book.order_ref(ask2_ref_id).unwrap();
+        book.take_deltas();
+
+        let (trades, _) = book.match_order(limit(Side::Buy, 2, 100), chrono::Utc::now());
+        assert_eq!(trades.len(), 2);
+
+        let deltas = book.take_deltas();
+        assert_eq!(deltas.len(), 2);
+        assert!(deltas.iter().all(|d| d.action == MboAction::Execute));
+        assert_eq!(deltas[0].order_ref, ask1_ref);
+        assert_eq!(deltas[0].remaining, Quantity::ZERO);
+        assert_eq!(deltas[0].trade_id, Some(trades[0].id));
+        assert_eq!(deltas[1].order_ref, ask2_ref);
+        assert_eq!(deltas[1].quantity, Quantity::from_int(1));
+        assert_eq!(deltas[1].remaining, Quantity::from_int(1));
+        assert!(book.order_ref(ask1_ref_id).is_none());
+        assert_eq!(book.order_ref(ask2_ref_id), Some(ask2_ref));
+    }
+
+    #[test]
+    fn test_reduce_order_keeps_queue_position() {
+        let mut book = OrderBook::new(create_symbol());
+        let first = limit(Side::Sell, 5, 100);
+        let second = limit(Side::Sell, 1, 100);
+        let first_id = first.id;
+        book.add_order(first);
+        book.add_order(second);
+        book.take_deltas();
+
+        assert!(book.reduce_order(first_id, Quantity::from_int(5)).is_none());
+        let reduced = book.reduce_order(first_id, Quantity::from_int(3)).unwrap();
+        assert_eq!(reduced.remaining_quantity(), Quantity::from_int(2));
+
+        let resting = book.resting_orders(Side::Sell);
+        assert_eq!(resting.len(), 2);
+        assert_eq!(resting[0].order_ref, book.order_ref(first_id).unwrap());
+        assert_eq!(resting[0].quantity, Quantity::from_int(2));
+        assert_eq!(book.get_asks(1)[0].quantity, Quantity::from_int(3));
+
+        let deltas = book.take_deltas();
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].action, MboAction::Reduce);
+        assert_eq!(deltas[0].quantity, Quantity::from_int(3));
+        assert_eq!(deltas[0].remaining, Quantity::from_int(2));
+    }
+
+    #[test]
+    fn test_resting_orders_in_queue_order() {
+        let mut book = OrderBook::new(create_symbol());
+        book.add_order(limit(Side::Buy, 1, 99));
+        book.add_order(limit(Side::Buy, 2, 100));
+        book.add_order(limit(Side::Buy, 3, 100));
+
+        let bids = book.resting_orders(Side::Buy);
+        let prices: Vec<_> = bids.iter().map(|o| o.price).collect();
+        let quantities: Vec<_> = bids.iter().map(|o| o.quantity).collect();
+        assert_eq!(
+            prices,
+            vec![
+                Price::from_int(100),
+                Price::from_int(100),
+                Price::from_int(99)
+            ]
+        );
+        assert_eq!(
+            quantities,
+            vec![
+                Quantity::from_int(2),
+                Quantity::from_int(3),
+                Quantity::from_int(1)
+            ]
+        );
+        assert!(book.resting_orders(Side::Sell).is_empty());
+    }
 }
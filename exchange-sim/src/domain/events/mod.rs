@@ -3,13 +3,15 @@ use serde::{Deserialize, Serialize};
 
 // Re-export event types from trading-core
 pub use trading_core::events::{
-    DepthSnapshotEvent, DepthUpdateEvent, OrderAcceptedEvent, OrderCanceledEvent,
-    OrderExpiredEvent, OrderFilledEvent, OrderRejectedEvent, TradeExecutedEvent,
+    DepthSnapshotEvent, DepthUpdateEvent, MarketByOrderEvent, MboAction, OrderAcceptedEvent,
+    OrderCanceledEvent, OrderExpiredEvent, OrderFilledEvent, OrderRejectedEvent,
+    TradeExecutedEvent,
 };
 
 // Re-export event types from use cases for convenience
 pub use crate::application::use_cases::{
-    DepositCreditedEvent, LiquidityAddedEvent, LiquidityRemovedEvent, SwapExecutedEvent,
+    DepositCreditedEvent, LiquidityAddedEvent, LiquidityRemovedEvent, OptionMarkEvent,
+    SwapExecutedEvent,
 };
 
 /// Domain events emitted by the exchange
@@ -34,6 +36,8 @@ pub enum ExchangeEvent {
     DepthUpdate(DepthUpdateEvent),
     /// Full order book snapshot
     DepthSnapshot(DepthSnapshotEvent),
+    /// Order-level (L3) book change
+    MarketByOrder(MarketByOrderEvent),
     /// Withdrawal status changed
     WithdrawalStatus(WithdrawalStatusEvent),
     /// DEX swap executed
@@ -44,4 +48,6 @@ pub enum ExchangeEvent {
     LiquidityRemoved(LiquidityRemovedEvent),
     /// Deposit credited to account
     DepositCredited(DepositCreditedEvent),
+    /// Option marks recomputed from the vol surface
+    OptionMark(OptionMarkEvent),
 }
@@ -8,27 +8,31 @@ pub mod value_objects;
 // Re-export entity types
 pub use entities::{
     Account, AccountError, AccountId, AccountStatus, AddLiquidityOutput, AddLiquidityResult,
-    AmmType, AssetBalance, ClearingMethod, Custodian, CustodianId, CustodianType, FeeSchedule,
-    FuturesConfig, InstrumentStatus, InstrumentType, LiquidityPool, Loan, LpPosition, MarginMode,
-    Network, OptionConfig, Order, OrderBook, OrderBookSnapshot, OrderStatus, PoolError, PoolId,
-    Position, PositionSide, PriceLevel, RemoveLiquidityOutput, RemoveLiquidityResult,
-    SettlementCycle, SwapOutput, SwapResult, Trade, TradingPairConfig, WithdrawalConfig,
-    WithdrawalError, WithdrawalId, WithdrawalRequest, WithdrawalStatus, WithdrawalStatusEvent,
+    AmmType, AssetBalance, BookDelta, ClearingMethod, Custodian, CustodianId, CustodianType,
+    FeeSchedule, FuturesConfig, InstrumentStatus, InstrumentType, IsolatedMarginAccount,
+    LiquidityPool, Loan, LpPosition, MarginLiquidation, MarginMode, Network, OptionConfig, Order,
+    OrderBook, OrderBookSnapshot, OrderStatus, PoolError, PoolId, Position, PositionSide,
+    PriceLevel, RemoveLiquidityOutput, RemoveLiquidityResult, RestingOrder, SettlementCycle,
+    SwapOutput, SwapResult, Trade, TradingPairConfig, WithdrawalConfig, WithdrawalError,
+    WithdrawalId, WithdrawalRequest, WithdrawalStatus, WithdrawalStatusEvent,
 };
 
 // Re-export events
 pub use events::{
-    DepthSnapshotEvent, DepthUpdateEvent, ExchangeEvent, OrderAcceptedEvent, OrderCanceledEvent,
-    OrderExpiredEvent, OrderFilledEvent, OrderRejectedEvent, TradeExecutedEvent,
+    DepthSnapshotEvent, DepthUpdateEvent, ExchangeEvent, MarketByOrderEvent, MboAction,
+    OrderAcceptedEvent, OrderCanceledEvent, OrderExpiredEvent, OrderFilledEvent,
+    OrderRejectedEvent, TradeExecutedEvent,
 };
 
 // Re-export services
 pub use services::{
-    AccountMarginCalculator, AgentTimeView, BlockchainError, BlockchainSimulator, BlockchainState,
-    BlockchainTx, Clock, ClockSource, ControllableClock, DepositAddress, DriftingClock,
-    ExchangeClock, ExternalClockAdapter, MarginCalculator, MarginStatus, NetworkConfig, NetworkSim,
-    NtpSyncEvent, OrderValidator, StandardMarginCalculator, TimeScale, TimeUpdate, TxId, TxStatus,
-    WorldClock,
+    AccountMarginCalculator, AgentTimeView, AssetPrices, BlockchainError, BlockchainSimulator,
+    BlockchainState, BlockchainTx, Clock, ClockSource, ControllableClock, DepositAddress,
+    DriftingClock, ExchangeClock, ExternalClockAdapter, MARGIN_LEVEL_CALL, MARGIN_LEVEL_INITIAL,
+    MARGIN_LEVEL_LIQUIDATION, MarginCalculator, MarginStatus, MarginTotals, NetworkConfig,
+    NetworkSim, NtpSyncEvent, OptionGreeks, OptionPricer, OrderValidator,
+    PortfolioMarginCalculator, RiskArrayConfig, ScenarioPnl, StandardMarginCalculator, TimeScale,
+    TimeUpdate, TxId, TxStatus, UnderlyingMarket, UnderlyingRisk, VolSmile, VolSurface, WorldClock,
 };
 
 // Re-export value objects
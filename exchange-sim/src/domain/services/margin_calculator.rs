@@ -1,7 +1,15 @@
 //! Margin and liquidation calculation services.
 
-use crate::domain::entities::{Position, PositionSide};
+use crate::domain::entities::{AssetBalance, Position, PositionSide};
 use crate::domain::value_objects::{PRICE_SCALE, Price, Quantity, Rate, Value};
+use std::collections::HashMap;
+
+/// Margin level a spot margin wallet must keep after borrowing or transferring out (1.5)
+pub const MARGIN_LEVEL_INITIAL: i64 = PRICE_SCALE * 3 / 2;
+/// Margin level below which a spot margin wallet is in margin call (1.3)
+pub const MARGIN_LEVEL_CALL: i64 = PRICE_SCALE * 13 / 10;
+/// Margin level below which a spot margin wallet is liquidated (1.1)
+pub const MARGIN_LEVEL_LIQUIDATION: i64 = PRICE_SCALE * 11 / 10;
 
 /// Trait for margin calculations - allows different margin models
 pub trait MarginCalculator: Send + Sync {
@@ -17,6 +25,22 @@ pub trait MarginCalculator: Send + Sync {
     /// Calculate required initial margin for a new position
     fn required_margin(&self, quantity: Quantity, price: Price, initial_margin_rate: Rate)
     -> Value;
+
+    /// Maintenance margin required to carry a set of positions
+    ///
+    /// Defaults to the sum of each position's notional times the rate;
+    /// portfolio models override this to let offsetting positions net.
+    fn maintenance_margin(&self, positions: &[&Position], maintenance_margin_rate: Rate) -> Value {
+        let total: i128 = positions
+            .iter()
+            .map(|p| {
+                maintenance_margin_rate
+                    .apply_to_value(p.notional_value())
+                    .raw()
+            })
+            .sum();
+        Value::from_raw(total)
+    }
 }
 
 /// Standard margin calculator used by most exchanges
@@ -123,13 +147,11 @@ impl<M: MarginCalculator> AccountMarginCalculator<M> {
         positions: impl Iterator<Item = &'a Position>,
         maintenance_margin_rate: Rate,
     ) -> i64 {
-        let maintenance_required: i128 = positions
-            .map(|p| {
-                maintenance_margin_rate
-                    .apply_to_value(p.notional_value())
-                    .raw()
-            })
-            .sum();
+        let positions: Vec<&Position> = positions.collect();
+        let maintenance_required = self
+            .calculator
+            .maintenance_margin(&positions, maintenance_margin_rate)
+            .raw();
 
         if maintenance_required == 0 {
             return i64::MAX;
@@ -169,12 +191,124 @@ impl<M: MarginCalculator> AccountMarginCalculator<M> {
         }
     }
 
+    /// Value one spot margin wallet
+    ///
+    /// Only the balances passed in are counted, so isolated wallets stay
+    /// ring-fenced from cross margin and from each other. Assets without a
+    /// price are left out on both sides.
+    pub fn margin_totals<'a>(
+        &self,
+        balances: impl Iterator<Item = (&'a String, &'a AssetBalance)>,
+        prices: &AssetPrices,
+    ) -> MarginTotals {
+        balances.fold(MarginTotals::default(), |mut totals, (asset, balance)| {
+            let liability = balance.borrowed + balance.interest;
+            if let (Some(asset_value), Some(liability_value)) = (
+                prices.value_of(asset, balance.total()),
+                prices.value_of(asset, liability),
+            ) {
+                totals.total_asset = totals.total_asset + asset_value;
+                totals.total_liability = totals.total_liability + liability_value;
+            }
+            totals
+        })
+    }
+
+    /// Determine spot margin status from a margin level (scaled by PRICE_SCALE)
+    pub fn margin_level_status(&self, margin_level: i64) -> MarginStatus {
+        if margin_level < MARGIN_LEVEL_LIQUIDATION {
+            MarginStatus::Liquidating
+        } else if margin_level < MARGIN_LEVEL_CALL {
+            MarginStatus::MarginCall
+        } else {
+            MarginStatus::Healthy
+        }
+    }
+
     /// Get the underlying position calculator
     pub fn position_calculator(&self) -> &M {
         &self.calculator
     }
 }
 
+/// Asset prices in a common quote asset, used to value spot margin wallets
+#[derive(Debug, Clone)]
+pub struct AssetPrices {
+    quote_asset: String,
+    prices: HashMap<String, Price>,
+}
+
+impl AssetPrices {
+    pub fn new(quote_asset: impl Into<String>) -> Self {
+        Self {
+            quote_asset: quote_asset.into(),
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Set the price of an asset in the quote asset
+    pub fn with_price(mut self, asset: impl Into<String>, price: Price) -> Self {
+        self.set(asset, price);
+        self
+    }
+
+    pub fn set(&mut self, asset: impl Into<String>, price: Price) {
+        self.prices.insert(asset.into(), price);
+    }
+
+    pub fn quote_asset(&self) -> &str {
+        &self.quote_asset
+    }
+
+    /// Price of an asset in the quote asset (the quote asset itself is 1)
+    pub fn price(&self, asset: &str) -> Option<Price> {
+        if asset == self.quote_asset {
+            return Some(Price::from_int(1));
+        }
+        self.prices.get(asset).copied()
+    }
+
+    /// Value an amount of an asset in the quote asset
+    pub fn value_of(&self, asset: &str, amount: Value) -> Option<Value> {
+        let price = self.price(asset)?;
+        Some(Value::from_raw(
+            amount.raw() * price.raw() as i128 / PRICE_SCALE as i128,
+        ))
+    }
+
+    /// Convert a quote-asset value into an amount of `asset`
+    pub fn amount_of(&self, asset: &str, value: Value) -> Option<Value> {
+        let price = self.price(asset).filter(|p| !p.is_zero())?;
+        Some(Value::from_raw(
+            value.raw() * PRICE_SCALE as i128 / price.raw() as i128,
+        ))
+    }
+}
+
+/// Assets and liabilities of a spot margin wallet, valued in the quote asset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarginTotals {
+    pub total_asset: Value,
+    pub total_liability: Value,
+}
+
+impl MarginTotals {
+    pub fn net_asset(&self) -> Value {
+        self.total_asset - self.total_liability
+    }
+
+    /// Margin level (total asset / total liability), scaled by PRICE_SCALE
+    ///
+    /// Returns `i64::MAX` when nothing is borrowed.
+    pub fn margin_level(&self) -> i64 {
+        if self.total_liability.raw() <= 0 {
+            return i64::MAX;
+        }
+        (self.total_asset.raw() * PRICE_SCALE as i128 / self.total_liability.raw())
+            .min(i64::MAX as i128) as i64
+    }
+}
+
 /// Margin status for an account
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarginStatus {
@@ -265,4 +399,45 @@ mod tests {
             MarginStatus::Healthy
         );
     }
+
+    #[test]
+    fn test_margin_totals_and_level() {
+        let calc = AccountMarginCalculator::default();
+        let prices = AssetPrices::new("USDT").with_price("BTC", Price::from_int(50000));
+
+        let mut balances = HashMap::new();
+        balances.insert(
+            "USDT".to_string(),
+            AssetBalance {
+                available: Value::from_int(10000),
+                ..Default::default()
+            },
+        );
+        balances.insert(
+            "BTC".to_string(),
+            AssetBalance {
+                available: Value::from_int(1),
+                borrowed: Value::from_int(1),
+                ..Default::default()
+            },
+        );
+        // Unpriced assets are ignored
+        balances.insert(
+            "XYZ".to_string(),
+            AssetBalance {
+                borrowed: Value::from_int(1000),
+                ..Default::default()
+            },
+        );
+
+        let totals = calc.margin_totals(balances.iter(), &prices);
+        assert_eq!(totals.total_asset, Value::from_int(60000));
+        assert_eq!(totals.total_liability, Value::from_int(50000));
+        assert_eq!(totals.margin_level(), PRICE_SCALE * 12 / 10);
+        assert_eq!(
+            calc.margin_level_status(totals.margin_level()),
+            MarginStatus::MarginCall
+        );
+        assert_eq!(MarginTotals::default().margin_level(), i64::MAX);
+    }
 }
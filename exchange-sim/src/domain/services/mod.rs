@@ -1,7 +1,9 @@
 mod blockchain_simulator;
 mod clock;
 mod margin_calculator;
+mod option_pricing;
 mod order_validator;
+mod portfolio_margin;
 mod world_clock;
 
 pub use blockchain_simulator::{
@@ -13,7 +15,16 @@ pub use clock::{
     TimeUpdate,
 };
 pub use margin_calculator::{
-    AccountMarginCalculator, MarginCalculator, MarginStatus, StandardMarginCalculator,
+    AccountMarginCalculator, AssetPrices, MARGIN_LEVEL_CALL, MARGIN_LEVEL_INITIAL,
+    MARGIN_LEVEL_LIQUIDATION, MarginCalculator, MarginStatus, MarginTotals,
+    StandardMarginCalculator,
+};
+pub use option_pricing::{
+    MAX_VOLATILITY, MIN_VOLATILITY, OptionGreeks, OptionPricer, VolSmile, VolSurface,
+    binomial_american, black_scholes, black_scholes_greeks, black76, solve_implied_volatility,
 };
 pub use order_validator::OrderValidator;
+pub use portfolio_margin::{
+    PortfolioMarginCalculator, RiskArrayConfig, ScenarioPnl, UnderlyingMarket, UnderlyingRisk,
+};
 pub use world_clock::{AgentTimeView, DriftingClock, ExchangeClock, NetworkSim, WorldClock};
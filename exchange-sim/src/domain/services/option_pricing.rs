@@ -0,0 +1,634 @@
+//! Option pricing, Greeks, implied volatility and volatility smiles.
+//!
+//! European options are priced with Black-Scholes (spot) or Black-76
+//! (forward); American options with a Cox-Ross-Rubinstein binomial tree.
+//! Prices and Greeks are plain `f64` in the quote asset per unit of
+//! underlying; callers convert to fixed-point at the edges.
+
+use crate::domain::instruments::{ExerciseStyle, OptionContract, OptionType};
+use crate::domain::value_objects::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lowest volatility the solver and smiles will return
+pub const MIN_VOLATILITY: f64 = 0.01;
+/// Highest volatility the implied-vol solver searches
+pub const MAX_VOLATILITY: f64 = 5.0;
+
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Option sensitivities
+///
+/// `vega` is per volatility point (0.01), `theta` per calendar day and
+/// `rho` per rate point (0.01), matching how exchanges quote them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptionGreeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+/// Prices options and solves implied volatility
+#[derive(Debug, Clone)]
+pub struct OptionPricer {
+    /// Continuously compounded risk-free rate
+    pub risk_free_rate: f64,
+    /// Steps used by the binomial tree for American options
+    pub binomial_steps: usize,
+}
+
+impl Default for OptionPricer {
+    fn default() -> Self {
+        Self {
+            risk_free_rate: 0.0,
+            binomial_steps: 200,
+        }
+    }
+}
+
+impl OptionPricer {
+    pub fn new(risk_free_rate: f64) -> Self {
+        Self {
+            risk_free_rate,
+            ..Self::default()
+        }
+    }
+
+    /// Theoretical value of one unit of `option`
+    pub fn price(
+        &self,
+        option: &OptionContract,
+        spot: f64,
+        volatility: f64,
+        now: Timestamp,
+    ) -> f64 {
+        self.price_with(
+            option,
+            spot,
+            volatility,
+            option.years_to_expiry(now),
+            self.risk_free_rate,
+        )
+    }
+
+    /// Greeks of one unit of `option`
+    ///
+    /// Analytic for European options; central finite differences on the
+    /// binomial tree for American ones.
+    pub fn greeks(
+        &self,
+        option: &OptionContract,
+        spot: f64,
+        volatility: f64,
+        now: Timestamp,
+    ) -> OptionGreeks {
+        let years = option.years_to_expiry(now);
+        let strike = option.strike.to_f64();
+        let rate = self.risk_free_rate;
+
+        if option.exercise_style == ExerciseStyle::European {
+            return black_scholes_greeks(option.option_type, spot, strike, years, volatility, rate);
+        }
+
+        let price = |s: f64, v: f64, t: f64, r: f64| self.price_with(option, s, v, t, r);
+        let base = price(spot, volatility, years, rate);
+        let h = spot * 0.01;
+        let up = price(spot + h, volatility, years, rate);
+        let down = price(spot - h, volatility, years, rate);
+        let day = 1.0 / DAYS_PER_YEAR;
+
+        OptionGreeks {
+            delta: (up - down) / (2.0 * h),
+            gamma: (up - 2.0 * base + down) / (h * h),
+            vega: (price(spot, volatility + 0.01, years, rate)
+                - price(spot, (volatility - 0.01).max(MIN_VOLATILITY), years, rate))
+                / 2.0,
+            theta: price(spot, volatility, (years - day).max(0.0), rate) - base,
+            rho: (price(spot, volatility, years, rate + 0.01)
+                - price(spot, volatility, years, rate - 0.01))
+                / 2.0,
+        }
+    }
+
+    /// Volatility at which the model price matches `target`
+    ///
+    /// Returns `None` when the target is outside the no-arbitrage bounds or
+    /// the option has expired.
+    pub fn implied_volatility(
+        &self,
+        option: &OptionContract,
+        target: f64,
+        spot: f64,
+        now: Timestamp,
+    ) -> Option<f64> {
+        let years = option.years_to_expiry(now);
+        if years <= 0.0 || target <= 0.0 || spot <= 0.0 {
+            return None;
+        }
+        solve_implied_volatility(target, |vol| {
+            self.price_with(option, spot, vol, years, self.risk_free_rate)
+        })
+    }
+
+    fn price_with(
+        &self,
+        option: &OptionContract,
+        spot: f64,
+        volatility: f64,
+        years: f64,
+        rate: f64,
+    ) -> f64 {
+        let strike = option.strike.to_f64();
+        match option.exercise_style {
+            ExerciseStyle::European => {
+                black_scholes(option.option_type, spot, strike, years, volatility, rate)
+            }
+            ExerciseStyle::American => binomial_american(
+                option.option_type,
+                spot,
+                strike,
+                years,
+                volatility,
+                rate,
+                self.binomial_steps,
+            ),
+        }
+    }
+}
+
+/// Black-Scholes value of a European option on spot; intrinsic at expiry
+pub fn black_scholes(
+    option_type: OptionType,
+    spot: f64,
+    strike: f64,
+    years: f64,
+    volatility: f64,
+    rate: f64,
+) -> f64 {
+    black76(
+        option_type,
+        spot * (rate * years).exp(),
+        strike,
+        years,
+        volatility,
+        rate,
+    )
+}
+
+/// Black-76 value of a European option on a forward or future
+pub fn black76(
+    option_type: OptionType,
+    forward: f64,
+    strike: f64,
+    years: f64,
+    volatility: f64,
+    rate: f64,
+) -> f64 {
+    let discount = (-rate * years.max(0.0)).exp();
+    if years <= 0.0 || volatility <= 0.0 {
+        return discount * intrinsic(option_type, forward, strike);
+    }
+
+    let (d1, d2) = d1_d2(forward, strike, years, volatility);
+    match option_type {
+        OptionType::Call => discount * (forward * norm_cdf(d1) - strike * norm_cdf(d2)),
+        OptionType::Put => discount * (strike * norm_cdf(-d2) - forward * norm_cdf(-d1)),
+    }
+}
+
+/// Cox-Ross-Rubinstein binomial value of an American option
+pub fn binomial_american(
+    option_type: OptionType,
+    spot: f64,
+    strike: f64,
+    years: f64,
+    volatility: f64,
+    rate: f64,
+    steps: usize,
+) -> f64 {
+    if years <= 0.0 || volatility <= 0.0 || steps == 0 {
+        return intrinsic(option_type, spot, strike);
+    }
+
+    let dt = years / steps as f64;
+    let up = (volatility * dt.sqrt()).exp();
+    let down = 1.0 / up;
+    let growth = (rate * dt).exp();
+    let p_up = ((growth - down) / (up - down)).clamp(0.0, 1.0);
+    let discount = 1.0 / growth;
+
+    let mut values: Vec<f64> = (0..=steps)
+        .map(|i| {
+            let s = spot * up.powi(i as i32) * down.powi((steps - i) as i32);
+            intrinsic(option_type, s, strike)
+        })
+        .collect();
+
+    for step in (0..steps).rev() {
+        for i in 0..=step {
+            let continuation = discount * (p_up * values[i + 1] + (1.0 - p_up) * values[i]);
+            let s = spot * up.powi(i as i32) * down.powi((step - i) as i32);
+            values[i] = continuation.max(intrinsic(option_type, s, strike));
+        }
+    }
+    values[0]
+}
+
+/// Analytic Black-Scholes Greeks
+pub fn black_scholes_greeks(
+    option_type: OptionType,
+    spot: f64,
+    strike: f64,
+    years: f64,
+    volatility: f64,
+    rate: f64,
+) -> OptionGreeks {
+    if years <= 0.0 || volatility <= 0.0 {
+        let in_the_money = intrinsic(option_type, spot, strike) > 0.0;
+        let delta = match (option_type, in_the_money) {
+            (OptionType::Call, true) => 1.0,
+            (OptionType::Put, true) => -1.0,
+            _ => 0.0,
+        };
+        return OptionGreeks {
+            delta,
+            ..OptionGreeks::default()
+        };
+    }
+
+    let sqrt_t = years.sqrt();
+    let forward = spot * (rate * years).exp();
+    let (d1, d2) = d1_d2(forward, strike, years, volatility);
+    let discounted_strike = strike * (-rate * years).exp();
+    let density = norm_pdf(d1);
+
+    let gamma = density / (spot * volatility * sqrt_t);
+    let vega = spot * density * sqrt_t / 100.0;
+    let decay = -spot * density * volatility / (2.0 * sqrt_t);
+
+    match option_type {
+        OptionType::Call => OptionGreeks {
+            delta: norm_cdf(d1),
+            gamma,
+            vega,
+            theta: (decay - rate * discounted_strike * norm_cdf(d2)) / DAYS_PER_YEAR,
+            rho: discounted_strike * years * norm_cdf(d2) / 100.0,
+        },
+        OptionType::Put => OptionGreeks {
+            delta: norm_cdf(d1) - 1.0,
+            gamma,
+            vega,
+            theta: (decay + rate * discounted_strike * norm_cdf(-d2)) / DAYS_PER_YEAR,
+            rho: -discounted_strike * years * norm_cdf(-d2) / 100.0,
+        },
+    }
+}
+
+/// Solve for the volatility at which `price_at(vol) == target`
+///
+/// Bisection on [`MIN_VOLATILITY`, `MAX_VOLATILITY`]; option value is
+/// monotonic in volatility, so this converges for any pricing model.
+pub fn solve_implied_volatility(target: f64, price_at: impl Fn(f64) -> f64) -> Option<f64> {
+    let (mut low, mut high) = (MIN_VOLATILITY, MAX_VOLATILITY);
+    if target < price_at(low) || target > price_at(high) {
+        return None;
+    }
+
+    for _ in 0..60 {
+        let mid = 0.5 * (low + high);
+        if price_at(mid) < target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low < 1e-7 {
+            break;
+        }
+    }
+    Some(0.5 * (low + high))
+}
+
+/// Quadratic volatility smile for one expiry
+///
+/// `vol(k) = a + b·k + c·k²` in log-moneyness `k = ln(strike / forward)`,
+/// fitted by least squares. With fewer than three quotes the fit falls back
+/// to a line or a flat level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolSmile {
+    pub forward: f64,
+    /// `[a, b, c]`
+    pub coefficients: [f64; 3],
+    /// Number of quotes the smile was fitted to
+    pub points: usize,
+}
+
+impl VolSmile {
+    /// Fit a smile to `(strike, implied vol)` quotes; `None` if there are none
+    pub fn fit(forward: f64, quotes: &[(f64, f64)]) -> Option<Self> {
+        if quotes.is_empty() || forward <= 0.0 {
+            return None;
+        }
+        let points: Vec<(f64, f64)> = quotes
+            .iter()
+            .map(|&(strike, vol)| ((strike / forward).ln(), vol))
+            .collect();
+
+        let mean_vol = points.iter().map(|p| p.1).sum::<f64>() / points.len() as f64;
+        let coefficients = match points.len() {
+            1 => [mean_vol, 0.0, 0.0],
+            2 => {
+                let (k0, v0) = points[0];
+                let (k1, v1) = points[1];
+                if (k1 - k0).abs() < f64::EPSILON {
+                    [mean_vol, 0.0, 0.0]
+                } else {
+                    let slope = (v1 - v0) / (k1 - k0);
+                    [v0 - slope * k0, slope, 0.0]
+                }
+            }
+            _ => fit_quadratic(&points).unwrap_or([mean_vol, 0.0, 0.0]),
+        };
+
+        Some(Self {
+            forward,
+            coefficients,
+            points: points.len(),
+        })
+    }
+
+    /// Flat smile at a single volatility
+    pub fn flat(forward: f64, volatility: f64) -> Self {
+        Self {
+            forward,
+            coefficients: [volatility, 0.0, 0.0],
+            points: 0,
+        }
+    }
+
+    /// Volatility at `strike`, floored at [`MIN_VOLATILITY`]
+    pub fn vol(&self, strike: f64) -> f64 {
+        let k = (strike / self.forward).ln();
+        let [a, b, c] = self.coefficients;
+        (a + b * k + c * k * k).max(MIN_VOLATILITY)
+    }
+}
+
+/// Smiles across expiries for one underlying
+#[derive(Debug, Clone, Default)]
+pub struct VolSurface {
+    smiles: BTreeMap<Timestamp, VolSmile>,
+}
+
+impl VolSurface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, expiry: Timestamp, smile: VolSmile) {
+        self.smiles.insert(expiry, smile);
+    }
+
+    pub fn smile(&self, expiry: Timestamp) -> Option<&VolSmile> {
+        self.smiles.get(&expiry)
+    }
+
+    pub fn smiles(&self) -> impl Iterator<Item = (&Timestamp, &VolSmile)> {
+        self.smiles.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.smiles.is_empty()
+    }
+
+    /// Volatility at `strike` for `expiry`
+    ///
+    /// Uses the expiry's own smile when there is one, otherwise interpolates
+    /// total variance between the neighbouring expiries (or takes the nearest
+    /// one outside the fitted range).
+    pub fn vol(&self, expiry: Timestamp, strike: f64, now: Timestamp) -> Option<f64> {
+        if let Some(smile) = self.smiles.get(&expiry) {
+            return Some(smile.vol(strike));
+        }
+
+        let before = self.smiles.range(..expiry).next_back();
+        let after = self.smiles.range(expiry..).next();
+        match (before, after) {
+            (Some((t0, s0)), Some((t1, s1))) => {
+                let years = |t: &Timestamp| years_between(now, *t);
+                let (y0, y1, y) = (years(t0), years(t1), years(&expiry));
+                if y1 <= y0 || y <= 0.0 {
+                    return Some(s0.vol(strike));
+                }
+                let w0 = s0.vol(strike).powi(2) * y0;
+                let w1 = s1.vol(strike).powi(2) * y1;
+                let total_variance = w0 + (w1 - w0) * (y - y0) / (y1 - y0);
+                Some((total_variance.max(0.0) / y).sqrt().max(MIN_VOLATILITY))
+            }
+            (Some((_, smile)), None) | (None, Some((_, smile))) => Some(smile.vol(strike)),
+            (None, None) => None,
+        }
+    }
+}
+
+fn years_between(from: Timestamp, to: Timestamp) -> f64 {
+    (to - from).num_milliseconds() as f64 / (365.25 * 24.0 * 60.0 * 60.0 * 1000.0)
+}
+
+fn intrinsic(option_type: OptionType, underlying: f64, strike: f64) -> f64 {
+    match option_type {
+        OptionType::Call => (underlying - strike).max(0.0),
+        OptionType::Put => (strike - underlying).max(0.0),
+    }
+}
+
+fn d1_d2(forward: f64, strike: f64, years: f64, volatility: f64) -> (f64, f64) {
+    let std_dev = volatility * years.sqrt();
+    let d1 = ((forward / strike).ln() + 0.5 * std_dev * std_dev) / std_dev;
+    (d1, d1 - std_dev)
+}
+
+/// Least-squares fit of `v = a + b·k + c·k²`
+fn fit_quadratic(points: &[(f64, f64)]) -> Option<[f64; 3]> {
+    // Normal equations: sums of k^0..k^4 and v·k^0..k^2
+    let mut s = [0.0f64; 5];
+    let mut t = [0.0f64; 3];
+    for &(k, v) in points {
+        let mut power = 1.0;
+        for (i, sum) in s.iter_mut().enumerate() {
+            *sum += power;
+            if i < 3 {
+                t[i] += v * power;
+            }
+            power *= k;
+        }
+    }
+
+    let m = [[s[0], s[1], s[2]], [s[1], s[2], s[3]], [s[2], s[3], s[4]]];
+    let det = determinant(&m);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+
+    let mut solution = [0.0; 3];
+    for (col, value) in solution.iter_mut().enumerate() {
+        let mut replaced = m;
+        for row in 0..3 {
+            replaced[row][col] = t[row];
+        }
+        *value = determinant(&replaced) / det;
+    }
+    Some(solution)
+}
+
+fn determinant(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26, |error| < 1.5e-7)
+fn norm_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Price;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn now() -> Timestamp {
+        Utc.with_ymd_and_hms(2024, 9, 27, 8, 0, 0).unwrap()
+    }
+
+    fn contract(strike: i64, option_type: OptionType, style: ExerciseStyle) -> OptionContract {
+        OptionContract::new(
+            "BTC",
+            now() + Duration::days(91),
+            Price::from_int(strike),
+            option_type,
+        )
+        .with_exercise_style(style)
+    }
+
+    #[test]
+    fn test_black_scholes_reference_value() {
+        // Hull, Example 15.6: S=42, K=40, r=10%, σ=20%, T=0.5
+        let call = black_scholes(OptionType::Call, 42.0, 40.0, 0.5, 0.2, 0.1);
+        let put = black_scholes(OptionType::Put, 42.0, 40.0, 0.5, 0.2, 0.1);
+        assert!((call - 4.76).abs() < 0.01);
+        assert!((put - 0.81).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_black76_matches_black_scholes_on_forward() {
+        let (s, k, t, v, r): (f64, f64, f64, f64, f64) = (50_000.0, 55_000.0, 0.25, 0.6, 0.05);
+        let forward = s * (r * t).exp();
+        let b76 = black76(OptionType::Call, forward, k, t, v, r);
+        assert!((b76 - black_scholes(OptionType::Call, s, k, t, v, r)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_american_put_has_early_exercise_premium() {
+        let european = black_scholes(OptionType::Put, 40.0, 50.0, 1.0, 0.3, 0.1);
+        let american = binomial_american(OptionType::Put, 40.0, 50.0, 1.0, 0.3, 0.1, 300);
+        assert!(american > european);
+        assert!(american >= 10.0);
+
+        // Without dividends an American call is worth the European one
+        let call = binomial_american(OptionType::Call, 40.0, 50.0, 1.0, 0.3, 0.1, 300);
+        let european_call = black_scholes(OptionType::Call, 40.0, 50.0, 1.0, 0.3, 0.1);
+        assert!((call - european_call).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_greeks_match_finite_differences() {
+        let pricer = OptionPricer::new(0.03);
+        let european = contract(52_000, OptionType::Call, ExerciseStyle::European);
+        let american = contract(52_000, OptionType::Call, ExerciseStyle::American);
+
+        let analytic = pricer.greeks(&european, 50_000.0, 0.6, now());
+        let numeric = pricer.greeks(&american, 50_000.0, 0.6, now());
+
+        assert!(analytic.delta > 0.4 && analytic.delta < 0.6);
+        assert!((analytic.delta - numeric.delta).abs() < 0.01);
+        assert!((analytic.vega - numeric.vega).abs() / analytic.vega < 0.02);
+        assert!(analytic.theta < 0.0 && numeric.theta < 0.0);
+        assert!(analytic.rho > 0.0);
+        assert!(analytic.gamma > 0.0);
+    }
+
+    #[test]
+    fn test_implied_volatility_round_trip() {
+        let pricer = OptionPricer::default();
+        for style in [ExerciseStyle::European, ExerciseStyle::American] {
+            let put = contract(45_000, OptionType::Put, style);
+            let price = pricer.price(&put, 50_000.0, 0.72, now());
+            let iv = pricer
+                .implied_volatility(&put, price, 50_000.0, now())
+                .unwrap();
+            assert!((iv - 0.72).abs() < 1e-3, "{style:?}: {iv}");
+        }
+
+        // Below intrinsic there is no solution
+        let itm = contract(60_000, OptionType::Put, ExerciseStyle::European);
+        assert!(
+            pricer
+                .implied_volatility(&itm, 5_000.0, 50_000.0, now())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_smile_fit_recovers_quadratic() {
+        let forward: f64 = 50_000.0;
+        let true_vol = |k: f64| 0.6 - 0.1 * k + 0.5 * k * k;
+        let quotes: Vec<(f64, f64)> = [40_000.0f64, 45_000.0, 50_000.0, 55_000.0, 60_000.0]
+            .iter()
+            .map(|&strike| (strike, true_vol((strike / forward).ln())))
+            .collect();
+
+        let smile = VolSmile::fit(forward, &quotes).unwrap();
+        assert_eq!(smile.points, 5);
+        assert!((smile.vol(52_000.0) - true_vol((52_000.0f64 / forward).ln())).abs() < 1e-9);
+
+        let flat = VolSmile::fit(forward, &[(50_000.0, 0.5)]).unwrap();
+        assert_eq!(flat.vol(70_000.0), 0.5);
+    }
+
+    #[test]
+    fn test_surface_interpolates_total_variance() {
+        let mut surface = VolSurface::new();
+        let near = now() + Duration::days(30);
+        let far = now() + Duration::days(90);
+        surface.insert(near, VolSmile::flat(50_000.0, 0.8));
+        surface.insert(far, VolSmile::flat(50_000.0, 0.6));
+
+        let mid = surface
+            .vol(now() + Duration::days(60), 50_000.0, now())
+            .unwrap();
+        assert!(mid < 0.8 && mid > 0.6);
+        assert_eq!(surface.vol(near, 1.0, now()), Some(0.8));
+        assert_eq!(
+            surface.vol(now() + Duration::days(365), 50_000.0, now()),
+            Some(0.6)
+        );
+    }
+}
@@ -0,0 +1,559 @@
+//! Portfolio (risk-array) margin for options and futures books.
+//!
+//! Every position on the same underlying is revalued across a grid of
+//! underlying price moves and volatility shocks, SPAN style. The requirement
+//! for an underlying is the worst scenario loss of the whole group, so a
+//! delta-hedged options book is charged for its residual risk rather than
+//! for each leg on its own.
+
+use crate::domain::entities::{Position, PositionSide};
+use crate::domain::instruments::{Instrument, OptionContract};
+use crate::domain::services::{MarginCalculator, StandardMarginCalculator, black_scholes};
+use crate::domain::value_objects::{PRICE_SCALE, Price, Quantity, Rate, Timestamp, Value};
+use std::collections::HashMap;
+
+/// Shape of the scenario grid
+#[derive(Debug, Clone)]
+pub struct RiskArrayConfig {
+    /// Largest underlying move scanned, in bps (1500 = ±15%)
+    pub price_scan_bps: i64,
+    /// Number of price steps on each side of the current price
+    pub price_steps: u32,
+    /// Relative volatility shock, in bps (3000 = vol × 0.7 and × 1.3)
+    pub vol_scan_bps: i64,
+    /// Floor charged per short option, in bps of underlying notional
+    pub short_option_minimum_bps: i64,
+    /// Risk-free rate used to revalue options
+    pub risk_free_rate: f64,
+}
+
+impl Default for RiskArrayConfig {
+    fn default() -> Self {
+        Self {
+            price_scan_bps: 1500,
+            price_steps: 3,
+            vol_scan_bps: 3000,
+            short_option_minimum_bps: 50,
+            risk_free_rate: 0.0,
+        }
+    }
+}
+
+impl RiskArrayConfig {
+    /// All (price move, vol shock) pairs in bps, price moves ascending
+    pub fn scenarios(&self) -> Vec<(i64, i64)> {
+        let steps = self.price_steps.max(1) as i64;
+        let mut grid = Vec::with_capacity((2 * steps as usize + 1) * 3);
+        for step in -steps..=steps {
+            let price_move = self.price_scan_bps * step / steps;
+            for vol_shock in [-self.vol_scan_bps, 0, self.vol_scan_bps] {
+                grid.push((price_move, vol_shock));
+            }
+        }
+        grid
+    }
+}
+
+/// Current state of an underlying used as the centre of the grid
+#[derive(Debug, Clone, Copy)]
+pub struct UnderlyingMarket {
+    pub spot: Price,
+    /// Annualised volatility (0.6 = 60%)
+    pub volatility: f64,
+}
+
+/// Profit or loss of a portfolio under one scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScenarioPnl {
+    pub price_move_bps: i64,
+    pub vol_shock_bps: i64,
+    pub pnl: Value,
+}
+
+/// Risk array result for all positions on one underlying
+#[derive(Debug, Clone)]
+pub struct UnderlyingRisk {
+    pub underlying: String,
+    pub scenarios: Vec<ScenarioPnl>,
+    /// Largest loss across the grid (zero if every scenario is a gain)
+    pub worst_loss: Value,
+    /// Floor for short options that the grid may not capture
+    pub short_option_minimum: Value,
+    /// max(worst_loss, short_option_minimum)
+    pub requirement: Value,
+}
+
+/// Portfolio margin calculator
+///
+/// Positions are matched to instruments by symbol. Positions without a
+/// registered instrument, or whose underlying has no market, fall back to the
+/// standard notional × rate charge.
+#[derive(Debug, Clone)]
+pub struct PortfolioMarginCalculator {
+    config: RiskArrayConfig,
+    instruments: HashMap<String, Instrument>,
+    markets: HashMap<String, UnderlyingMarket>,
+    valuation_time: Timestamp,
+    standard: StandardMarginCalculator,
+}
+
+impl PortfolioMarginCalculator {
+    /// Create a calculator valuing options as of `valuation_time`
+    pub fn new(valuation_time: Timestamp) -> Self {
+        Self {
+            config: RiskArrayConfig::default(),
+            instruments: HashMap::new(),
+            markets: HashMap::new(),
+            valuation_time,
+            standard: StandardMarginCalculator,
+        }
+    }
+
+    pub fn with_config(mut self, config: RiskArrayConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Register the instrument definition for a symbol
+    pub fn with_instrument(mut self, instrument: Instrument) -> Self {
+        self.add_instrument(instrument);
+        self
+    }
+
+    /// Set the spot and volatility of an underlying
+    pub fn with_market(
+        mut self,
+        underlying: impl Into<String>,
+        spot: Price,
+        volatility: f64,
+    ) -> Self {
+        self.set_market(underlying, spot, volatility);
+        self
+    }
+
+    pub fn add_instrument(&mut self, instrument: Instrument) {
+        self.instruments
+            .insert(instrument.symbol().to_string(), instrument);
+    }
+
+    pub fn set_market(&mut self, underlying: impl Into<String>, spot: Price, volatility: f64) {
+        self.markets
+            .insert(underlying.into(), UnderlyingMarket { spot, volatility });
+    }
+
+    pub fn config(&self) -> &RiskArrayConfig {
+        &self.config
+    }
+
+    /// Run the risk array for each underlying the positions touch
+    ///
+    /// Positions that cannot be scenario-valued are skipped here; see
+    /// [`MarginCalculator::maintenance_margin`] for how they are charged.
+    pub fn risk_by_underlying(&self, positions: &[&Position]) -> Vec<UnderlyingRisk> {
+        let mut groups: HashMap<&str, Vec<(&Position, &Instrument)>> = HashMap::new();
+        for position in positions {
+            if let Some(instrument) = self.instruments.get(position.symbol.as_str()) {
+                let underlying = instrument.base_asset();
+                if self.markets.contains_key(underlying) {
+                    groups
+                        .entry(underlying)
+                        .or_default()
+                        .push((position, instrument));
+                }
+            }
+        }
+
+        let mut risks: Vec<UnderlyingRisk> = groups
+            .into_iter()
+            .map(|(underlying, legs)| self.underlying_risk(underlying, &legs))
+            .collect();
+        risks.sort_by(|a, b| a.underlying.cmp(&b.underlying));
+        risks
+    }
+
+    /// Maintenance margin of the positions sharing `symbol`'s underlying
+    ///
+    /// A change to one position only moves its own underlying's risk array,
+    /// so comparing this before and after prices the change without
+    /// revaluing the rest of the book. A symbol the grid cannot value is
+    /// charged on its own at the standard rate.
+    pub fn symbol_group_margin(
+        &self,
+        positions: &[&Position],
+        symbol: &str,
+        maintenance_margin_rate: Rate,
+    ) -> Value {
+        let underlying = self
+            .instruments
+            .get(symbol)
+            .map(Instrument::base_asset)
+            .filter(|u| self.markets.contains_key(*u));
+        let Some(underlying) = underlying else {
+            let own: Vec<&Position> = positions
+                .iter()
+                .copied()
+                .filter(|p| p.symbol.as_str() == symbol)
+                .collect();
+            return self
+                .standard
+                .maintenance_margin(&own, maintenance_margin_rate);
+        };
+
+        let legs: Vec<(&Position, &Instrument)> = positions
+            .iter()
+            .filter_map(|p| {
+                let instrument = self.instruments.get(p.symbol.as_str())?;
+                (instrument.base_asset() == underlying).then_some((*p, instrument))
+            })
+            .collect();
+        if legs.is_empty() {
+            return Value::ZERO;
+        }
+        self.underlying_risk(underlying, &legs).requirement
+    }
+
+    fn underlying_risk(
+        &self,
+        underlying: &str,
+        legs: &[(&Position, &Instrument)],
+    ) -> UnderlyingRisk {
+        let market = self.markets[underlying];
+
+        let scenarios: Vec<ScenarioPnl> = self
+            .config
+            .scenarios()
+            .into_iter()
+            .map(|(price_move_bps, vol_shock_bps)| {
+                let pnl: f64 = legs
+                    .iter()
+                    .map(|(position, instrument)| {
+                        self.scenario_pnl(
+                            position,
+                            instrument,
+                            market,
+                            price_move_bps,
+                            vol_shock_bps,
+                        )
+                    })
+                    .sum();
+                ScenarioPnl {
+                    price_move_bps,
+                    vol_shock_bps,
+                    pnl: Value::from_f64(pnl),
+                }
+            })
+            .collect();
+
+        let worst_loss = scenarios
+            .iter()
+            .map(|s| -s.pnl.raw())
+            .max()
+            .unwrap_or(0)
+            .max(0);
+
+        let short_option_minimum: f64 = legs
+            .iter()
+            .filter(|(position, _)| position.side == PositionSide::Short)
+            .filter_map(|(position, instrument)| match instrument {
+                Instrument::Option(option) => Some(
+                    position.quantity.to_f64()
+                        * multiplier(option.multiplier)
+                        * market.spot.to_f64()
+                        * self.config.short_option_minimum_bps as f64
+                        / 10_000.0,
+                ),
+                _ => None,
+            })
+            .sum();
+        let short_option_minimum = Value::from_f64(short_option_minimum);
+
+        UnderlyingRisk {
+            underlying: underlying.to_string(),
+            scenarios,
+            worst_loss: Value::from_raw(worst_loss),
+            short_option_minimum,
+            requirement: Value::from_raw(worst_loss.max(short_option_minimum.raw())),
+        }
+    }
+
+    /// P&L of one position, in the quote asset, under one scenario
+    fn scenario_pnl(
+        &self,
+        position: &Position,
+        instrument: &Instrument,
+        market: UnderlyingMarket,
+        price_move_bps: i64,
+        vol_shock_bps: i64,
+    ) -> f64 {
+        let sign = match position.side {
+            PositionSide::Long => 1.0,
+            PositionSide::Short => -1.0,
+        };
+        let qty = position.quantity.to_f64();
+        let price_move = price_move_bps as f64 / 10_000.0;
+
+        let change = match instrument {
+            Instrument::Option(option) => {
+                let spot = market.spot.to_f64();
+                let shocked_vol = market.volatility * (1.0 + vol_shock_bps as f64 / 10_000.0);
+                let base = self.option_value(option, spot, market.volatility);
+                let shocked = self.option_value(option, spot * (1.0 + price_move), shocked_vol);
+                (shocked - base) * multiplier(option.multiplier)
+            }
+            // Inverse contracts are sized in quote notional, so their quote
+            // P&L is the notional times the move whatever the price level
+            Instrument::Perpetual(perp) if perp.is_inverse => {
+                multiplier(perp.multiplier) * price_move
+            }
+            Instrument::Future(future) if future.is_inverse => {
+                multiplier(future.multiplier) * price_move
+            }
+            Instrument::Perpetual(perp) => {
+                position.mark_price.to_f64() * multiplier(perp.multiplier) * price_move
+            }
+            Instrument::Future(future) => {
+                position.mark_price.to_f64() * multiplier(future.multiplier) * price_move
+            }
+            Instrument::Spot(_) => position.mark_price.to_f64() * price_move,
+        };
+
+        sign * qty * change
+    }
+
+    /// Theoretical value of one option unit
+    fn option_value(&self, option: &OptionContract, spot: f64, volatility: f64) -> f64 {
+        black_scholes(
+            option.option_type,
+            spot,
+            option.strike.to_f64(),
+            option.years_to_expiry(self.valuation_time),
+            volatility,
+            self.config.risk_free_rate,
+        )
+    }
+}
+
+impl MarginCalculator for PortfolioMarginCalculator {
+    fn unrealized_pnl(&self, position: &Position) -> Value {
+        self.standard.unrealized_pnl(position)
+    }
+
+    fn liquidation_price(&self, position: &Position, maintenance_margin_rate: Rate) -> Price {
+        self.standard
+            .liquidation_price(position, maintenance_margin_rate)
+    }
+
+    fn should_liquidate(&self, position: &Position, maintenance_margin_rate: Rate) -> bool {
+        self.standard
+            .should_liquidate(position, maintenance_margin_rate)
+    }
+
+    fn required_margin(
+        &self,
+        quantity: Quantity,
+        price: Price,
+        initial_margin_rate: Rate,
+    ) -> Value {
+        self.standard
+            .required_margin(quantity, price, initial_margin_rate)
+    }
+
+    /// Sum of the risk array requirement of each underlying, plus the
+    /// standard charge for positions the grid cannot value
+    fn maintenance_margin(&self, positions: &[&Position], maintenance_margin_rate: Rate) -> Value {
+        let scenario_valued = |p: &Position| {
+            self.instruments
+                .get(p.symbol.as_str())
+                .is_some_and(|i| self.markets.contains_key(i.base_asset()))
+        };
+
+        let unvalued: Vec<&Position> = positions
+            .iter()
+            .copied()
+            .filter(|p| !scenario_valued(p))
+            .collect();
+        let fallback = self
+            .standard
+            .maintenance_margin(&unvalued, maintenance_margin_rate);
+
+        let portfolio: i128 = self
+            .risk_by_underlying(positions)
+            .iter()
+            .map(|r| r.requirement.raw())
+            .sum();
+
+        Value::from_raw(fallback.raw() + portfolio)
+    }
+}
+
+fn multiplier(scaled: i64) -> f64 {
+    scaled as f64 / PRICE_SCALE as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::instruments::{OptionType, PerpetualContract};
+    use crate::domain::value_objects::Symbol;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn now() -> Timestamp {
+        Utc.with_ymd_and_hms(2024, 11, 27, 8, 0, 0).unwrap()
+    }
+
+    fn call() -> OptionContract {
+        OptionContract::new(
+            "BTC",
+            now() + Duration::days(30),
+            Price::from_int(50_000),
+            OptionType::Call,
+        )
+        .with_symbol("BTC-27DEC24-50000-C")
+    }
+
+    fn position(symbol: &str, side: PositionSide, qty: Quantity, price: Price) -> Position {
+        Position::new(
+            Symbol::new(symbol).unwrap(),
+            side,
+            qty,
+            price,
+            Value::ZERO,
+            now(),
+        )
+    }
+
+    fn calculator() -> PortfolioMarginCalculator {
+        PortfolioMarginCalculator::new(now())
+            .with_instrument(Instrument::Option(call()))
+            .with_instrument(Instrument::Perpetual(PerpetualContract::linear(
+                "BTC", "BTCUSDT",
+            )))
+            .with_market("BTC", Price::from_int(50_000), 0.6)
+    }
+
+    #[test]
+    fn test_scenario_grid_shape() {
+        let grid = RiskArrayConfig::default().scenarios();
+        assert_eq!(grid.len(), 21);
+        assert_eq!(grid.first(), Some(&(-1500, -3000)));
+        assert_eq!(grid.last(), Some(&(1500, 3000)));
+    }
+
+    #[test]
+    fn test_delta_hedge_offsets_short_call() {
+        let calc = calculator();
+        let short_call = position(
+            "BTC-27DEC24-50000-C",
+            PositionSide::Short,
+            Quantity::from_int(1),
+            Price::from_int(3_500),
+        );
+        // ATM call delta is roughly 0.54
+        let hedge = position(
+            "BTCUSDT",
+            PositionSide::Long,
+            Quantity::from_f64(0.54),
+            Price::from_int(50_000),
+        );
+
+        let rate = Rate::from_bps(500);
+        let naked = calc.maintenance_margin(&[&short_call], rate);
+        let perp_only = calc.maintenance_margin(&[&hedge], rate);
+        let hedged = calc.maintenance_margin(&[&short_call, &hedge], rate);
+
+        assert!(hedged.raw() > 0);
+        assert!(hedged.raw() < naked.raw());
+        assert!(hedged.raw() < naked.raw() + perp_only.raw());
+
+        // The standard model charges each leg separately
+        let standard = StandardMarginCalculator.maintenance_margin(&[&short_call, &hedge], rate);
+        let seller_margin = Rate::from_bps(call().seller_margin_bps)
+            .apply_to_value(call().notional_value(Quantity::from_int(1)));
+        assert!(hedged.raw() < standard.raw() + seller_margin.raw());
+    }
+
+    #[test]
+    fn test_unregistered_positions_use_standard_charge() {
+        let calc = calculator();
+        let other = position(
+            "ETHUSDT",
+            PositionSide::Long,
+            Quantity::from_int(10),
+            Price::from_int(3_000),
+        );
+        let rate = Rate::from_bps(500);
+
+        assert_eq!(
+            calc.maintenance_margin(&[&other], rate),
+            StandardMarginCalculator.maintenance_margin(&[&other], rate)
+        );
+        assert!(calc.risk_by_underlying(&[&other]).is_empty());
+    }
+
+    #[test]
+    fn test_symbol_group_margin_covers_only_its_underlying() {
+        let calc = calculator();
+        let short_call = position(
+            "BTC-27DEC24-50000-C",
+            PositionSide::Short,
+            Quantity::from_int(1),
+            Price::from_int(3_500),
+        );
+        let hedge = position(
+            "BTCUSDT",
+            PositionSide::Long,
+            Quantity::from_f64(0.54),
+            Price::from_int(50_000),
+        );
+        let other = position(
+            "ETHUSDT",
+            PositionSide::Long,
+            Quantity::from_int(10),
+            Price::from_int(3_000),
+        );
+        let rate = Rate::from_bps(500);
+        let positions = [&short_call, &hedge, &other];
+
+        // Either BTC leg prices the whole BTC group, hedge included
+        let btc = calc.maintenance_margin(&[&short_call, &hedge], rate);
+        assert_eq!(calc.symbol_group_margin(&positions, "BTCUSDT", rate), btc);
+        assert_eq!(
+            calc.symbol_group_margin(&positions, "BTC-27DEC24-50000-C", rate),
+            btc
+        );
+
+        // Unvalued symbols are charged alone
+        assert_eq!(
+            calc.symbol_group_margin(&positions, "ETHUSDT", rate),
+            StandardMarginCalculator.maintenance_margin(&[&other], rate)
+        );
+        assert_eq!(
+            calc.symbol_group_margin(&[&other], "BTCUSDT", rate),
+            Value::ZERO
+        );
+    }
+
+    #[test]
+    fn test_short_option_minimum_applies_to_far_otm() {
+        let far_otm = OptionContract::new(
+            "BTC",
+            now() + Duration::days(1),
+            Price::from_int(90_000),
+            OptionType::Call,
+        )
+        .with_symbol("BTC-28NOV24-90000-C");
+        let calc = calculator().with_instrument(Instrument::Option(far_otm));
+        let short = position(
+            "BTC-28NOV24-90000-C",
+            PositionSide::Short,
+            Quantity::from_int(2),
+            Price::from_raw(1),
+        );
+
+        let risk = calc.risk_by_underlying(&[&short]);
+        assert_eq!(risk.len(), 1);
+        // 2 × 50000 × 0.5%
+        assert_eq!(risk[0].short_option_minimum, Value::from_int(500));
+        assert_eq!(risk[0].requirement, Value::from_int(500));
+    }
+}
@@ -159,6 +159,12 @@ pub struct ServerConfig {
     pub port: u16,
     #[serde(default = "default_event_capacity")]
     pub event_capacity: usize,
+    /// FIX acceptor port (FIX disabled when unset)
+    #[serde(default)]
+    pub fix_port: Option<u16>,
+    /// Directory FIX sessions persist sequence numbers to (in memory when unset)
+    #[serde(default)]
+    pub fix_store_dir: Option<String>,
 }
 
 fn default_host() -> String {
@@ -179,6 +185,8 @@ impl Default for ServerConfig {
             host: default_host(),
             port: default_port(),
             event_capacity: default_event_capacity(),
+            fix_port: None,
+            fix_store_dir: None,
         }
     }
 }
@@ -198,6 +206,8 @@ pub struct RateLimitConfigDto {
     pub ws_connections_per_ip: u32,
     #[serde(default = "default_ws_messages_per_second")]
     pub ws_messages_per_second: u32,
+    #[serde(default = "default_ip_ban_secs")]
+    pub ip_ban_secs: u64,
 }
 
 fn default_requests_per_minute() -> u32 {
@@ -218,6 +228,9 @@ fn default_ws_connections_per_ip() -> u32 {
 fn default_ws_messages_per_second() -> u32 {
     5
 }
+fn default_ip_ban_secs() -> u64 {
+    120
+}
 
 impl Default for RateLimitConfigDto {
     fn default() -> Self {
@@ -228,6 +241,7 @@ impl Default for RateLimitConfigDto {
             request_weight_per_minute: default_request_weight_per_minute(),
             ws_connections_per_ip: default_ws_connections_per_ip(),
             ws_messages_per_second: default_ws_messages_per_second(),
+            ip_ban_secs: default_ip_ban_secs(),
         }
     }
 }
@@ -232,12 +232,13 @@ impl OrderBookShard {
             .fetch_add(trades.len() as u64, Ordering::Relaxed);
 
         // Add remaining order to book if it exists and should rest
+        let book = self.books.get_mut(&symbol_str).unwrap();
         if let Some(ref rem) = remaining {
-            // Re-get the book since we need a fresh mutable borrow
-            let book = self.books.get_mut(&symbol_str).unwrap();
             book.add_order(rem.clone());
             self.order_index.insert(rem.id, symbol_str.clone());
         }
+        // Shards publish no L3 feed; don't let order-level deltas pile up
+        book.take_deltas();
 
         // Publish trade events via the event sink abstraction
         for trade in &trades {
@@ -264,6 +265,7 @@ impl OrderBookShard {
 
         if let Some(book) = self.books.get_mut(&symbol_str) {
             if let Some(mut order) = book.remove_order(order_id) {
+                book.take_deltas();
                 order.cancel(timestamp);
                 self.order_index.remove(&order_id);
                 return CancelOrderResponse::Cancelled(order);
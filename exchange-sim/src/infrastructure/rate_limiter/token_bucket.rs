@@ -14,6 +14,7 @@ use std::time::{Duration, Instant};
 /// - Request weight limits (per minute)
 /// - Order limits (per second and per day)
 /// - Per-client tracking
+/// - A ban (HTTP 418) for clients that keep sending while told to back off
 pub struct TokenBucketRateLimiter {
     config: RateLimitConfig,
     /// Per-client rate limit state
@@ -29,6 +30,10 @@ struct ClientState {
     orders_day: Mutex<TokenBucket>,
     /// WebSocket messages per second
     ws_messages: Mutex<TokenBucket>,
+    /// Backoff window after the last denial
+    retry_until: Mutex<Option<Instant>>,
+    /// Active ban
+    banned_until: Mutex<Option<Instant>>,
 }
 
 struct TokenBucket {
@@ -76,6 +81,12 @@ impl TokenBucket {
         (self.capacity - self.tokens) as u32
     }
 
+    /// Usage after refilling to now; partially refilled tokens still count
+    fn used(&mut self) -> u32 {
+        self.refill();
+        (self.capacity - self.tokens).ceil() as u32
+    }
+
     fn limit(&self) -> u32 {
         self.capacity as u32
     }
@@ -105,8 +116,14 @@ impl ClientState {
                 config.ws_messages_per_second,
                 Duration::from_secs(1),
             )),
+            retry_until: Mutex::new(None),
+            banned_until: Mutex::new(None),
         }
     }
+
+    fn deny(&self, retry_after: Duration) {
+        *self.retry_until.lock() = Some(Instant::now() + retry_after);
+    }
 }
 
 impl TokenBucketRateLimiter {
@@ -127,6 +144,27 @@ impl TokenBucketRateLimiter {
         }
         self.clients.get(client_id).unwrap()
     }
+
+    /// Remaining ban for a client, banning it first if it is sending
+    /// inside the backoff window of an earlier denial
+    pub fn check_ban(&self, client_id: &str) -> Option<Duration> {
+        let client = self.get_or_create_client(client_id);
+        let now = Instant::now();
+        let mut banned_until = client.banned_until.lock();
+        if let Some(until) = *banned_until
+            && until > now
+        {
+            return Some(until - now);
+        }
+
+        let mut retry_until = client.retry_until.lock();
+        if retry_until.is_some_and(|until| until > now) {
+            *retry_until = None;
+            *banned_until = Some(now + self.config.ip_ban_duration);
+            return Some(self.config.ip_ban_duration);
+        }
+        None
+    }
 }
 
 impl Default for TokenBucketRateLimiter {
@@ -157,6 +195,7 @@ impl RequestRateLimiter for TokenBucketRateLimiter {
         if allowed {
             RateLimitResult::allowed(bucket.current(), bucket.limit(), weight)
         } else {
+            client.deny(retry_after);
             RateLimitResult::denied(bucket.current(), bucket.limit(), retry_after, weight)
         }
     }
@@ -172,6 +211,7 @@ impl OrderRateLimiter for TokenBucketRateLimiter {
         let (second_ok, second_retry) = second_bucket.try_consume(1);
 
         if !second_ok {
+            client.deny(second_retry);
             return RateLimitResult::denied(
                 second_bucket.current(),
                 second_bucket.limit(),
@@ -186,6 +226,7 @@ impl OrderRateLimiter for TokenBucketRateLimiter {
         if !day_ok {
             // Refund the second bucket since we're denying
             second_bucket.tokens += 1.0;
+            client.deny(day_retry);
             return RateLimitResult::denied(day_bucket.current(), day_bucket.limit(), day_retry, 1);
         }
 
@@ -214,16 +255,16 @@ impl RateLimitAdmin for TokenBucketRateLimiter {
     async fn get_status(&self, client_id: &str) -> RateLimitStatus {
         let client = self.get_or_create_client(client_id);
 
-        let request_weight = client.request_weight.lock();
-        let orders_second = client.orders_second.lock();
-        let orders_day = client.orders_day.lock();
+        let mut request_weight = client.request_weight.lock();
+        let mut orders_second = client.orders_second.lock();
+        let mut orders_day = client.orders_day.lock();
 
         RateLimitStatus {
-            request_weight_used: request_weight.current(),
+            request_weight_used: request_weight.used(),
             request_weight_limit: request_weight.limit(),
-            orders_used_second: orders_second.current(),
+            orders_used_second: orders_second.used(),
             orders_limit_second: orders_second.limit(),
-            orders_used_day: orders_day.current(),
+            orders_used_day: orders_day.used(),
             orders_limit_day: orders_day.limit(),
         }
     }
@@ -234,6 +275,8 @@ impl RateLimitAdmin for TokenBucketRateLimiter {
             client.orders_second.lock().reset();
             client.orders_day.lock().reset();
             client.ws_messages.lock().reset();
+            *client.retry_until.lock() = None;
+            *client.banned_until.lock() = None;
         }
     }
 
@@ -302,4 +345,26 @@ mod tests {
         // client2 should still have quota
         assert!(limiter.check_request("client2", 1).await.allowed);
     }
+
+    #[tokio::test]
+    async fn test_ban_after_ignoring_backoff() {
+        let limiter = TokenBucketRateLimiter::new(RateLimitConfig {
+            request_weight_per_minute: 1,
+            ip_ban_duration: Duration::from_secs(30),
+            ..Default::default()
+        });
+
+        assert!(limiter.check_ban("test").is_none());
+        assert!(limiter.check_request("test", 1).await.allowed);
+        assert!(!limiter.check_request("test", 1).await.allowed);
+
+        // Coming back inside the Retry-After window earns a ban
+        let ban = limiter.check_ban("test").unwrap();
+        assert_eq!(ban, Duration::from_secs(30));
+        assert!(limiter.check_ban("test").unwrap() <= ban);
+        assert!(limiter.check_ban("other").is_none());
+
+        limiter.reset("test").await;
+        assert!(limiter.check_ban("test").is_none());
+    }
 }
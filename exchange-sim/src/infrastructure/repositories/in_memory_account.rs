@@ -59,6 +59,22 @@ impl AccountRepository for InMemoryAccountRepository {
         self.accounts.insert(id, account);
     }
 
+    async fn update(
+        &self,
+        id: AccountId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Account) -> bool + Send),
+    ) -> Option<bool> {
+        // The entry's shard lock is held until the write, so no save can
+        // land between the read and the write
+        let mut entry = self.accounts.get_mut(&id)?;
+        let mut account = entry.clone();
+        let changed = f(&mut account);
+        if changed {
+            *entry = account;
+        }
+        Some(changed)
+    }
+
     async fn get_or_create(&self, owner_id: &str) -> Account {
         // Check existing
         if let Some(account_id) = self.owner_index.get(owner_id) {
@@ -114,6 +130,38 @@ mod tests {
         assert_eq!(retrieved.balance("USDT").available, Value::from_int(10000));
     }
 
+    #[tokio::test]
+    async fn test_update_writes_back_only_changes() {
+        use crate::domain::Value;
+        let repo = InMemoryAccountRepository::new();
+        let id = repo.get_or_create("user1").await.id;
+
+        let changed = repo
+            .update(id, &mut |account| {
+                account.deposit("USDT", Value::from_int(5));
+                false
+            })
+            .await;
+        assert_eq!(changed, Some(false));
+        assert_eq!(
+            repo.get(id).await.unwrap().balance("USDT").available,
+            Value::ZERO
+        );
+
+        let changed = repo
+            .update(id, &mut |account| {
+                account.deposit("USDT", Value::from_int(5));
+                true
+            })
+            .await;
+        assert_eq!(changed, Some(true));
+        assert_eq!(
+            repo.get(id).await.unwrap().balance("USDT").available,
+            Value::from_int(5)
+        );
+        assert_eq!(repo.update(AccountId::new_v4(), &mut |_| true).await, None);
+    }
+
     #[tokio::test]
     async fn test_get_by_owner() {
         let repo = InMemoryAccountRepository::new();
@@ -65,6 +65,8 @@ pub use domain::{
     MarginCalculator,
     Network,
     NetworkConfig,
+    OptionGreeks,
+    OptionPricer,
     Order,
     OrderBook,
     OrderId,
@@ -72,9 +74,11 @@ pub use domain::{
     OrderType,
     PoolError,
     PoolId,
+    PortfolioMarginCalculator,
     Price,
     Quantity,
     RemoveLiquidityResult,
+    RiskArrayConfig,
     SettlementCycle,
     Side,
     StandardMarginCalculator,
@@ -90,6 +94,8 @@ pub use domain::{
     TxId,
     TxStatus,
     Value,
+    VolSmile,
+    VolSurface,
     WithdrawalConfig,
     WithdrawalError,
     WithdrawalId,
@@ -123,6 +129,18 @@ pub use application::{
     GetDepthQuery,
     LiquidityUseCase,
     LiquidityUseCaseError,
+    // Margin use cases
+    MarginConfig,
+    MarginError,
+    MarginMaintenanceResult,
+    MarginUseCase,
+    // Option marks
+    OptionMark,
+    OptionMarkConfig,
+    OptionMarkQuery,
+    OptionMarkRefresh,
+    OptionMarksUseCase,
+    PortfolioMarginCache,
     ProcessDepositError,
     ProcessDepositUseCase,
     ProcessDepositsResult,
@@ -169,6 +187,7 @@ pub use application::ports::{
     WithdrawalWriter,
 };
 
+pub use presentation::fix::{FixAcceptor, FixConfig};
 pub use presentation::{AppState, StreamManager, WsState, create_router};
 
 use axum::Router;
@@ -188,6 +207,10 @@ pub struct ExchangeConfig {
     pub rate_limits: RateLimitConfig,
     /// Event channel capacity
     pub event_capacity: usize,
+    /// FIX 4.4 acceptor port (disabled when None)
+    pub fix_port: Option<u16>,
+    /// FIX session store directory (sessions kept in memory when None)
+    pub fix_store_dir: Option<std::path::PathBuf>,
 }
 
 impl Default for ExchangeConfig {
@@ -198,6 +221,8 @@ impl Default for ExchangeConfig {
             ws_port: 8080,
             rate_limits: RateLimitConfig::default(),
             event_capacity: 10000,
+            fix_port: None,
+            fix_store_dir: None,
         }
     }
 }
@@ -211,6 +236,8 @@ pub struct Exchange<C: Clock + 'static> {
     pub instrument_repo: Arc<InMemoryInstrumentRepository>,
     pub event_publisher: Arc<BroadcastEventPublisher>,
     pub rate_limiter: Arc<TokenBucketRateLimiter>,
+    /// Portfolio margin state written by the periodic refresh
+    pub portfolio_margin: Arc<PortfolioMarginCache>,
 }
 
 impl<C: Clock + 'static> Exchange<C> {
@@ -230,21 +257,75 @@ impl<C: Clock + 'static> Exchange<C> {
             instrument_repo,
             event_publisher,
             rate_limiter,
+            portfolio_margin: Arc::new(PortfolioMarginCache::new()),
         }
     }
 
+    fn app_state(&self) -> Arc<AppState<C>> {
+        Arc::new(
+            AppState::new(
+                Arc::clone(&self.clock),
+                Arc::clone(&self.account_repo),
+                Arc::clone(&self.order_book_repo),
+                Arc::clone(&self.instrument_repo),
+                Arc::clone(&self.event_publisher),
+                Arc::clone(&self.rate_limiter),
+            )
+            .with_portfolio_margin(Arc::clone(&self.portfolio_margin)),
+        )
+    }
+
     /// Create the REST API router
     pub fn rest_router(&self) -> Router {
-        let state = Arc::new(AppState::new(
+        create_router(self.app_state())
+    }
+
+    /// Create a FIX acceptor sharing this exchange's state
+    ///
+    /// Fails when the session store directory in `config` cannot be opened.
+    pub fn fix_acceptor(&self, config: FixConfig) -> std::io::Result<Arc<FixAcceptor<C>>> {
+        Ok(Arc::new(FixAcceptor::open(config, self.app_state())?))
+    }
+
+    /// Create the margin use case over this exchange's state
+    pub fn margin(
+        &self,
+    ) -> MarginUseCase<
+        C,
+        InMemoryAccountRepository,
+        InMemoryOrderBookRepository,
+        InMemoryInstrumentRepository,
+        TokenBucketRateLimiter,
+    > {
+        MarginUseCase::new(
             Arc::clone(&self.clock),
             Arc::clone(&self.account_repo),
             Arc::clone(&self.order_book_repo),
             Arc::clone(&self.instrument_repo),
-            Arc::clone(&self.event_publisher),
             Arc::clone(&self.rate_limiter),
-        ));
+        )
+    }
 
-        create_router(state)
+    /// Create the option marks use case over this exchange's state
+    pub fn option_marks(
+        &self,
+    ) -> OptionMarksUseCase<
+        C,
+        InMemoryAccountRepository,
+        InMemoryOrderBookRepository,
+        InMemoryInstrumentRepository,
+        BroadcastEventPublisher,
+        TokenBucketRateLimiter,
+    > {
+        OptionMarksUseCase::new(
+            Arc::clone(&self.clock),
+            Arc::clone(&self.account_repo),
+            Arc::clone(&self.order_book_repo),
+            Arc::clone(&self.instrument_repo),
+            Arc::clone(&self.event_publisher),
+            Arc::clone(&self.rate_limiter),
+        )
+        .with_margin_cache(Arc::clone(&self.portfolio_margin))
     }
 
     /// Create WebSocket state
@@ -270,6 +351,31 @@ impl<C: Clock + 'static> Exchange<C> {
             }),
         );
 
+        if let Some(fix_port) = self.config.fix_port {
+            let fix_addr = format!("{}:{}", self.config.host, fix_port);
+            let acceptor = self.fix_acceptor(FixConfig {
+                store_dir: self.config.fix_store_dir.clone(),
+                ..FixConfig::default()
+            })?;
+            tokio::spawn(async move {
+                if let Err(e) = acceptor.run(&fix_addr).await {
+                    tracing::error!("FIX acceptor failed: {}", e);
+                }
+            });
+        }
+
+        // Option marks off the vol surface, then margin interest and liquidation
+        let option_marks = self.option_marks();
+        let margin = self.margin();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                option_marks.refresh().await;
+                margin.run_maintenance().await;
+            }
+        });
+
         tracing::info!("Exchange simulator listening on {}", addr);
 
         let listener = TcpListener::bind(&addr).await?;
@@ -318,8 +424,11 @@ impl Exchange<SimulationClock> {
                 request_weight_per_minute: sim_config.rate_limits.request_weight_per_minute,
                 ws_connections_per_ip: sim_config.rate_limits.ws_connections_per_ip,
                 ws_messages_per_second: sim_config.rate_limits.ws_messages_per_second,
+                ip_ban_duration: std::time::Duration::from_secs(sim_config.rate_limits.ip_ban_secs),
             },
             event_capacity: sim_config.server.event_capacity,
+            fix_port: sim_config.server.fix_port,
+            fix_store_dir: sim_config.server.fix_store_dir.map(Into::into),
         };
 
         // Create exchange with empty instrument repo
@@ -341,6 +450,7 @@ impl Exchange<SimulationClock> {
             instrument_repo,
             event_publisher,
             rate_limiter,
+            portfolio_margin: Arc::new(PortfolioMarginCache::new()),
         };
 
         // Add configured markets
@@ -387,6 +497,8 @@ impl Exchange<SimulationClock> {
             );
 
             book.add_order(order);
+            // Seed orders are part of the initial book, not L3 updates
+            book.take_deltas();
             exchange.order_book_repo.save(book).await;
             tracing::info!(
                 "Added seed order: {} {} {} @ {}",
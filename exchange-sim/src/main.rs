@@ -1,5 +1,6 @@
 use exchange_sim::infrastructure::SimulatorConfig;
 use exchange_sim::{Exchange, ExchangeConfig, RateLimitConfig};
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn print_help() {
@@ -16,6 +17,7 @@ OPTIONS:
 ENVIRONMENT VARIABLES:
     HOST                Server host (default: 0.0.0.0)
     PORT                Server port (default: 8080)
+    FIX_PORT            FIX 4.4 acceptor port (default: disabled)
     RUST_LOG            Log level filter
 
 EXAMPLES:
@@ -87,6 +89,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap_or_else(|_| "8080".to_string())
             .parse()
             .unwrap_or(8080);
+        let fix_port: Option<u16> = std::env::var("FIX_PORT").ok().and_then(|p| p.parse().ok());
+        let fix_store_dir = std::env::var("FIX_STORE_DIR").ok().map(Into::into);
 
         let config = ExchangeConfig {
             host,
@@ -99,8 +103,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 request_weight_per_minute: 1200,
                 ws_connections_per_ip: 5,
                 ws_messages_per_second: 5,
+                ip_ban_duration: Duration::from_secs(120),
             },
             event_capacity: 10000,
+            fix_port,
+            fix_store_dir,
         };
 
         tracing::info!("Using default configuration");
@@ -123,6 +130,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         exchange.config.host,
         exchange.config.rest_port
     );
+    if let Some(fix_port) = exchange.config.fix_port {
+        tracing::info!(
+            "FIX 4.4: tcp://{}:{} (TargetCompID=ATHENA)",
+            exchange.config.host,
+            fix_port
+        );
+    }
     tracing::info!("Available endpoints:");
     tracing::info!("  GET  /api/v3/ping");
     tracing::info!("  GET  /api/v3/time");
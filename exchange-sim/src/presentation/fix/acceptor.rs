@@ -0,0 +1,1230 @@
+//! FIX 4.4 TCP acceptor
+//!
+//! Each connection must log on before any application message is accepted.
+//! Order entry and market data requests are served by the same use cases as
+//! the REST handlers, using the AppState shared with the HTTP router, so one
+//! simulated venue serves both protocols.
+
+use dashmap::DashSet;
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinHandle;
+
+use super::market_data::{
+    self, MarketDataRequest, MdEntryType, MdReqRejReason, SubscribedBook, SubscriptionRequestType,
+};
+use super::message::{FixDecoder, FixFieldError, FixMessage, msg_type, tags};
+use super::order_entry::{
+    self, CxlRejResponseTo, ExecType, OpenOrders, OrdRejReason, ReportDetails,
+};
+use super::session::{FixSession, SeqCheck};
+use super::store::{FileSessionStore, InMemorySessionStore, SessionId, SessionStore};
+use crate::application::{
+    AmendOrderCommand, AmendOrderUseCase, CancelError, CancelOrderUseCase, DepthError,
+    GetDepthQuery, GetDepthUseCase, OrderError, SubmitOrderCommand, SubmitOrderUseCase,
+};
+use crate::domain::{Clock, ExchangeEvent, OrderStatus, Quantity, Symbol, Value};
+use crate::presentation::rest::AppState;
+
+/// FIX acceptor configuration
+#[derive(Debug, Clone)]
+pub struct FixConfig {
+    /// CompID of the simulated venue (TargetCompID on inbound messages)
+    pub comp_id: String,
+    /// Maximum time to wait for Logon after connect
+    pub logon_timeout: Duration,
+    /// Heartbeat interval used when the client sends HeartBtInt=0
+    pub default_heartbeat: Duration,
+    /// Directory for persisted sequence numbers and messages (in memory when None)
+    pub store_dir: Option<PathBuf>,
+}
+
+impl Default for FixConfig {
+    fn default() -> Self {
+        FixConfig {
+            comp_id: "ATHENA".to_string(),
+            logon_timeout: Duration::from_secs(10),
+            default_heartbeat: Duration::from_secs(30),
+            store_dir: None,
+        }
+    }
+}
+
+/// FIX acceptor serving order entry and market data sessions
+pub struct FixAcceptor<C: Clock> {
+    config: FixConfig,
+    state: Arc<AppState<C>>,
+    store: Arc<dyn SessionStore>,
+    active_sessions: DashSet<SessionId>,
+}
+
+impl<C: Clock + 'static> FixAcceptor<C> {
+    /// Create an acceptor with an in-memory session store
+    pub fn new(config: FixConfig, state: Arc<AppState<C>>) -> Self {
+        Self::with_store(config, state, Arc::new(InMemorySessionStore::new()))
+    }
+
+    /// Create an acceptor with the store `config.store_dir` selects
+    ///
+    /// Sessions persist to a `FileSessionStore` in that directory, so
+    /// sequence numbers survive a restart; without one they live in memory.
+    pub fn open(config: FixConfig, state: Arc<AppState<C>>) -> io::Result<Self> {
+        let store: Arc<dyn SessionStore> = match &config.store_dir {
+            Some(dir) => Arc::new(FileSessionStore::open(dir)?),
+            None => Arc::new(InMemorySessionStore::new()),
+        };
+        Ok(Self::with_store(config, state, store))
+    }
+
+    /// Create an acceptor with a custom session store (e.g. file-backed)
+    pub fn with_store(
+        config: FixConfig,
+        state: Arc<AppState<C>>,
+        store: Arc<dyn SessionStore>,
+    ) -> Self {
+        FixAcceptor {
+            config,
+            state,
+            store,
+            active_sessions: DashSet::new(),
+        }
+    }
+
+    /// Bind and serve until the listener fails
+    pub async fn run(self: Arc<Self>, addr: &str) -> io::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        tracing::info!("FIX acceptor listening on {}", addr);
+        self.serve(listener).await
+    }
+
+    /// Accept connections on an already bound listener
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let acceptor = Arc::clone(&self);
+            tokio::spawn(async move {
+                tracing::debug!("FIX connection from {}", peer);
+                if let Err(e) = acceptor.handle_connection(stream).await {
+                    tracing::warn!("FIX connection from {} closed with error: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream) -> io::Result<()> {
+        stream.set_nodelay(true)?;
+        let (mut reader, writer) = stream.into_split();
+        let mut decoder = FixDecoder::new();
+        let mut buf = vec![0u8; 8192];
+
+        // Wait for Logon
+        let logon = match tokio::time::timeout(
+            self.config.logon_timeout,
+            read_message(&mut reader, &mut decoder, &mut buf),
+        )
+        .await
+        {
+            Ok(Ok(Some(msg))) => msg,
+            Ok(Ok(None)) => return Ok(()),
+            Ok(Err(e)) => return Err(e),
+            Err(_) => {
+                tracing::debug!("FIX logon timeout");
+                return Ok(());
+            }
+        };
+
+        let Some(mut conn) = Connection::logon(Arc::clone(&self), writer, &logon).await? else {
+            return Ok(());
+        };
+
+        let result = conn.run(&mut reader, &mut decoder, &mut buf).await;
+        conn.shutdown();
+        result
+    }
+}
+
+/// Read until one full message is decoded; `None` on clean EOF
+async fn read_message(
+    reader: &mut tokio::net::tcp::OwnedReadHalf,
+    decoder: &mut FixDecoder,
+    buf: &mut [u8],
+) -> io::Result<Option<FixMessage>> {
+    loop {
+        if let Some(frame) = decoder.next_frame().map_err(invalid_data)? {
+            return FixMessage::decode(&frame).map(Some).map_err(invalid_data);
+        }
+        let n = reader.read(buf).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        decoder.extend(&buf[..n]);
+    }
+}
+
+fn invalid_data(e: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+// ============================================================================
+// Connection
+// ============================================================================
+
+/// The session ended (Logout exchanged or terminated by us)
+struct Disconnect;
+
+struct Connection<C: Clock + 'static> {
+    acceptor: Arc<FixAcceptor<C>>,
+    writer: OwnedWriteHalf,
+    session: FixSession,
+    /// Owner ID used for accounts and rate limits (Account tag or SenderCompID)
+    client_id: String,
+    heartbeat: Duration,
+    last_sent: Instant,
+    last_received: Instant,
+    pending_test_request: Option<(String, Instant)>,
+    /// Messages produced by background tasks (passive fills, market data)
+    outbound_tx: mpsc::UnboundedSender<FixMessage>,
+    outbound_rx: mpsc::UnboundedReceiver<FixMessage>,
+    open_orders: Arc<Mutex<OpenOrders>>,
+    fill_watchers: HashMap<String, JoinHandle<()>>,
+    md_subscriptions: HashMap<String, Vec<JoinHandle<()>>>,
+}
+
+impl<C: Clock + 'static> Connection<C> {
+    /// Validate a Logon and establish the session
+    async fn logon(
+        acceptor: Arc<FixAcceptor<C>>,
+        mut writer: OwnedWriteHalf,
+        logon: &FixMessage,
+    ) -> io::Result<Option<Self>> {
+        let now = acceptor.state.clock.now();
+        let sender = logon
+            .get(tags::SENDER_COMP_ID)
+            .unwrap_or_default()
+            .to_string();
+        let target = logon.get(tags::TARGET_COMP_ID).unwrap_or_default();
+
+        let refuse = |text: &str| {
+            FixMessage::new(msg_type::LOGOUT)
+                .with(tags::TEXT, text)
+                .encode(&super::message::FixHeader {
+                    sender_comp_id: &acceptor.config.comp_id,
+                    target_comp_id: &sender,
+                    seq_num: 1,
+                    sending_time: &super::message::format_utc_timestamp(&now),
+                    poss_dup: false,
+                })
+        };
+
+        if logon.msg_type() != msg_type::LOGON {
+            writer
+                .write_all(&refuse("First message must be Logon"))
+                .await?;
+            return Ok(None);
+        }
+        if sender.is_empty() || target != acceptor.config.comp_id {
+            writer.write_all(&refuse("Unknown CompID")).await?;
+            return Ok(None);
+        }
+        let Ok(seq_num) = logon.seq_num() else {
+            writer.write_all(&refuse("Missing MsgSeqNum")).await?;
+            return Ok(None);
+        };
+
+        let id = SessionId::new(&acceptor.config.comp_id, &sender);
+        if !acceptor.active_sessions.insert(id.clone()) {
+            writer
+                .write_all(&refuse("Session already logged on"))
+                .await?;
+            return Ok(None);
+        }
+
+        let mut session = match FixSession::open(id.clone(), Arc::clone(&acceptor.store)) {
+            Ok(session) => session,
+            Err(e) => {
+                acceptor.active_sessions.remove(&id);
+                return Err(e);
+            }
+        };
+        let reset = logon.get_bool(tags::RESET_SEQ_NUM_FLAG);
+        if reset {
+            session.reset()?;
+        }
+
+        let heartbeat = match logon.get_u64(tags::HEART_BT_INT) {
+            Ok(Some(secs)) if secs > 0 => Duration::from_secs(secs),
+            _ => acceptor.config.default_heartbeat,
+        };
+        let client_id = logon
+            .get(tags::ACCOUNT)
+            .map(str::to_string)
+            .unwrap_or_else(|| sender.clone());
+
+        let (outbound_tx, outbound_rx) = mpsc::unbounded_channel();
+        let mut conn = Connection {
+            acceptor,
+            writer,
+            session,
+            client_id,
+            heartbeat,
+            last_sent: Instant::now(),
+            last_received: Instant::now(),
+            pending_test_request: None,
+            outbound_tx,
+            outbound_rx,
+            open_orders: Arc::new(Mutex::new(OpenOrders::new())),
+            fill_watchers: HashMap::new(),
+            md_subscriptions: HashMap::new(),
+        };
+
+        match conn.session.check_inbound(logon, seq_num) {
+            SeqCheck::TooLow { expected, received } => {
+                let text = format!(
+                    "MsgSeqNum too low, expecting {} but received {}",
+                    expected, received
+                );
+                conn.send(FixMessage::new(msg_type::LOGOUT).with(tags::TEXT, text))
+                    .await?;
+                conn.shutdown();
+                return Ok(None);
+            }
+            check => {
+                let mut reply = FixMessage::new(msg_type::LOGON)
+                    .with(tags::ENCRYPT_METHOD, 0)
+                    .with(tags::HEART_BT_INT, heartbeat.as_secs());
+                if reset {
+                    reply.push(tags::RESET_SEQ_NUM_FLAG, "Y");
+                }
+                conn.send(reply).await?;
+
+                if let SeqCheck::Gap { expected, received } = check {
+                    if let Some(request) = conn.session.resend_request_for_gap(expected, received) {
+                        conn.send(request).await?;
+                    }
+                } else {
+                    conn.session.accept_inbound(seq_num)?;
+                }
+            }
+        }
+
+        tracing::info!(
+            "FIX session {} logged on (heartbeat {}s, next seq in/out {}/{})",
+            conn.session.id(),
+            heartbeat.as_secs(),
+            conn.session.seq_nums().next_target_seq,
+            conn.session.seq_nums().next_sender_seq
+        );
+        Ok(Some(conn))
+    }
+
+    async fn run(
+        &mut self,
+        reader: &mut tokio::net::tcp::OwnedReadHalf,
+        decoder: &mut FixDecoder,
+        buf: &mut [u8],
+    ) -> io::Result<()> {
+        let mut ticker = tokio::time::interval(Duration::from_millis(250));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            // Drain everything already buffered before waiting for more
+            while let Some(frame) = decoder.next_frame().map_err(invalid_data)? {
+                self.last_received = Instant::now();
+                let message = match FixMessage::decode(&frame) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        // Garbled messages are ignored per the session protocol
+                        tracing::debug!("FIX {} dropped garbled message: {}", self.session.id(), e);
+                        continue;
+                    }
+                };
+                if self.on_message(message).await?.is_some() {
+                    return Ok(());
+                }
+            }
+
+            tokio::select! {
+                read = reader.read(buf) => {
+                    let n = read?;
+                    if n == 0 {
+                        tracing::info!("FIX session {} disconnected", self.session.id());
+                        return Ok(());
+                    }
+                    decoder.extend(&buf[..n]);
+                }
+                Some(message) = self.outbound_rx.recv() => {
+                    self.send(message).await?;
+                }
+                _ = ticker.tick() => {
+                    if self.on_timer().await?.is_some() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    fn shutdown(&mut self) {
+        for handle in self.fill_watchers.drain().map(|(_, h)| h) {
+            handle.abort();
+        }
+        for handle in self.md_subscriptions.drain().flat_map(|(_, h)| h) {
+            handle.abort();
+        }
+        self.acceptor.active_sessions.remove(self.session.id());
+    }
+
+    async fn send(&mut self, message: FixMessage) -> io::Result<()> {
+        let now = self.acceptor.state.clock.now();
+        let bytes = self.session.encode_outbound(&message, now)?;
+        self.writer.write_all(&bytes).await?;
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+
+    async fn send_raw(&mut self, frames: Vec<Vec<u8>>) -> io::Result<()> {
+        for frame in frames {
+            self.writer.write_all(&frame).await?;
+        }
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------------
+    // Session layer
+    // ------------------------------------------------------------------------
+
+    async fn on_timer(&mut self) -> io::Result<Option<Disconnect>> {
+        let now = Instant::now();
+
+        if let Some((_, sent_at)) = &self.pending_test_request {
+            if now.duration_since(*sent_at) > self.heartbeat {
+                tracing::warn!(
+                    "FIX session {} did not answer TestRequest, disconnecting",
+                    self.session.id()
+                );
+                self.send(FixMessage::new(msg_type::LOGOUT).with(tags::TEXT, "Heartbeat timeout"))
+                    .await?;
+                return Ok(Some(Disconnect));
+            }
+        } else if now.duration_since(self.last_received) > self.heartbeat + self.heartbeat / 5 {
+            let test_req_id = format!("TEST-{}", self.session.seq_nums().next_sender_seq);
+            self.send(
+                FixMessage::new(msg_type::TEST_REQUEST).with(tags::TEST_REQ_ID, &test_req_id),
+            )
+            .await?;
+            self.pending_test_request = Some((test_req_id, now));
+        }
+
+        if now.duration_since(self.last_sent) >= self.heartbeat {
+            self.send(FixMessage::new(msg_type::HEARTBEAT)).await?;
+        }
+        Ok(None)
+    }
+
+    async fn on_message(&mut self, message: FixMessage) -> io::Result<Option<Disconnect>> {
+        let seq_num = match message.seq_num() {
+            Ok(seq_num) => seq_num,
+            Err(e) => {
+                self.session_reject(&message, 0, e).await?;
+                return Ok(None);
+            }
+        };
+
+        if message.get(tags::SENDER_COMP_ID) != Some(&self.session.id().target_comp_id)
+            || message.get(tags::TARGET_COMP_ID) != Some(&self.session.id().sender_comp_id)
+        {
+            self.send(
+                FixMessage::new(msg_type::REJECT)
+                    .with(tags::REF_SEQ_NUM, seq_num)
+                    .with(tags::SESSION_REJECT_REASON, 9)
+                    .with(tags::TEXT, "CompID problem"),
+            )
+            .await?;
+            self.send(FixMessage::new(msg_type::LOGOUT).with(tags::TEXT, "CompID problem"))
+                .await?;
+            return Ok(Some(Disconnect));
+        }
+
+        // SequenceReset-Reset ignores MsgSeqNum entirely
+        if message.msg_type() == msg_type::SEQUENCE_RESET && !message.get_bool(tags::GAP_FILL_FLAG)
+        {
+            return self.on_sequence_reset(&message, seq_num).await;
+        }
+
+        match self.session.check_inbound(&message, seq_num) {
+            SeqCheck::InOrder => {}
+            SeqCheck::Duplicate => return Ok(None),
+            SeqCheck::TooLow { expected, received } => {
+                let text = format!(
+                    "MsgSeqNum too low, expecting {} but received {}",
+                    expected, received
+                );
+                self.send(FixMessage::new(msg_type::LOGOUT).with(tags::TEXT, text))
+                    .await?;
+                return Ok(Some(Disconnect));
+            }
+            SeqCheck::Gap { expected, received } => {
+                if let Some(request) = self.session.resend_request_for_gap(expected, received) {
+                    self.send(request).await?;
+                }
+                // Resend requests are honoured even when out of sequence
+                if message.msg_type() == msg_type::RESEND_REQUEST {
+                    self.on_resend_request(&message).await?;
+                }
+                return Ok(None);
+            }
+        }
+
+        match message.msg_type() {
+            msg_type::SEQUENCE_RESET => return self.on_sequence_reset(&message, seq_num).await,
+            msg_type::LOGOUT => {
+                self.session.accept_inbound(seq_num)?;
+                self.send(FixMessage::new(msg_type::LOGOUT)).await?;
+                tracing::info!("FIX session {} logged out", self.session.id());
+                return Ok(Some(Disconnect));
+            }
+            _ => {}
+        }
+        self.session.accept_inbound(seq_num)?;
+
+        match message.msg_type() {
+            msg_type::HEARTBEAT => {
+                if let Some(id) = message.get(tags::TEST_REQ_ID)
+                    && self
+                        .pending_test_request
+                        .as_ref()
+                        .is_some_and(|(pending, _)| pending == id)
+                {
+                    self.pending_test_request = None;
+                }
+            }
+            msg_type::TEST_REQUEST => {
+                let mut reply = FixMessage::new(msg_type::HEARTBEAT);
+                if let Some(id) = message.get(tags::TEST_REQ_ID) {
+                    reply.push(tags::TEST_REQ_ID, id);
+                }
+                self.send(reply).await?;
+            }
+            msg_type::RESEND_REQUEST => self.on_resend_request(&message).await?,
+            msg_type::REJECT => {
+                tracing::warn!("FIX session {} sent Reject: {}", self.session.id(), message);
+            }
+            msg_type::LOGON => {
+                self.session_reject(
+                    &message,
+                    seq_num,
+                    FixFieldError::Unsupported(tags::MSG_TYPE),
+                )
+                .await?;
+            }
+            msg_type::NEW_ORDER_SINGLE => self.on_new_order(&message, seq_num).await?,
+            msg_type::ORDER_CANCEL_REQUEST => self.on_cancel(&message, seq_num).await?,
+            msg_type::ORDER_CANCEL_REPLACE_REQUEST => self.on_replace(&message, seq_num).await?,
+            msg_type::MARKET_DATA_REQUEST => self.on_market_data_request(&message, seq_num).await?,
+            other => {
+                self.send(
+                    FixMessage::new(msg_type::REJECT)
+                        .with(tags::REF_SEQ_NUM, seq_num)
+                        .with(tags::REF_MSG_TYPE, other)
+                        .with(tags::SESSION_REJECT_REASON, 11)
+                        .with(tags::TEXT, "Invalid MsgType"),
+                )
+                .await?;
+            }
+        }
+        Ok(None)
+    }
+
+    async fn on_sequence_reset(
+        &mut self,
+        message: &FixMessage,
+        seq_num: u64,
+    ) -> io::Result<Option<Disconnect>> {
+        let new_seq_no = match message
+            .get_u64(tags::NEW_SEQ_NO)
+            .and_then(|v| v.ok_or(FixFieldError::Missing(tags::NEW_SEQ_NO)))
+        {
+            Ok(v) => v,
+            Err(e) => {
+                self.session_reject(message, seq_num, e).await?;
+                return Ok(None);
+            }
+        };
+        if !self.session.reset_inbound(new_seq_no)? {
+            self.send(
+                FixMessage::new(msg_type::REJECT)
+                    .with(tags::REF_SEQ_NUM, seq_num)
+                    .with(tags::REF_TAG_ID, tags::NEW_SEQ_NO)
+                    .with(tags::SESSION_REJECT_REASON, 5)
+                    .with(tags::TEXT, "NewSeqNo lower than expected"),
+            )
+            .await?;
+        }
+        Ok(None)
+    }
+
+    async fn on_resend_request(&mut self, message: &FixMessage) -> io::Result<()> {
+        let begin = message.get_u64(tags::BEGIN_SEQ_NO).ok().flatten();
+        let end = message.get_u64(tags::END_SEQ_NO).ok().flatten();
+        let Some(begin) = begin else {
+            let seq_num = message.seq_num().unwrap_or(0);
+            return self
+                .session_reject(message, seq_num, FixFieldError::Missing(tags::BEGIN_SEQ_NO))
+                .await;
+        };
+
+        let now = self.acceptor.state.clock.now();
+        let frames = self.session.replay(begin, end.unwrap_or(0), now)?;
+        tracing::debug!(
+            "FIX session {} resending {} frames from {}",
+            self.session.id(),
+            frames.len(),
+            begin
+        );
+        self.send_raw(frames).await
+    }
+
+    async fn session_reject(
+        &mut self,
+        message: &FixMessage,
+        seq_num: u64,
+        error: FixFieldError,
+    ) -> io::Result<()> {
+        self.send(
+            FixMessage::new(msg_type::REJECT)
+                .with(tags::REF_SEQ_NUM, seq_num)
+                .with(tags::REF_TAG_ID, error.tag())
+                .with(tags::REF_MSG_TYPE, message.msg_type())
+                .with(tags::SESSION_REJECT_REASON, error.reject_reason())
+                .with(tags::TEXT, error),
+        )
+        .await
+    }
+
+    // ------------------------------------------------------------------------
+    // Order entry
+    // ------------------------------------------------------------------------
+
+    fn submit_use_case(
+        &self,
+    ) -> SubmitOrderUseCase<
+        C,
+        crate::infrastructure::InMemoryAccountRepository,
+        crate::infrastructure::InMemoryOrderBookRepository,
+        crate::infrastructure::InMemoryInstrumentRepository,
+        crate::infrastructure::BroadcastEventPublisher,
+        crate::infrastructure::TokenBucketRateLimiter,
+    > {
+        let state = &self.acceptor.state;
+        SubmitOrderUseCase::new(
+            Arc::clone(&state.clock),
+            Arc::clone(&state.account_repo),
+            Arc::clone(&state.order_book_repo),
+            Arc::clone(&state.instrument_repo),
+            Arc::clone(&state.event_publisher),
+            Arc::clone(&state.rate_limiter),
+        )
+        .with_margin_cache(Arc::clone(&state.portfolio_margin))
+    }
+
+    fn cancel_use_case(
+        &self,
+    ) -> CancelOrderUseCase<
+        C,
+        crate::infrastructure::InMemoryOrderBookRepository,
+        crate::infrastructure::BroadcastEventPublisher,
+        crate::infrastructure::TokenBucketRateLimiter,
+    > {
+        let state = &self.acceptor.state;
+        CancelOrderUseCase::new(
+            Arc::clone(&state.clock),
+            Arc::clone(&state.order_book_repo),
+            Arc::clone(&state.event_publisher),
+            Arc::clone(&state.rate_limiter),
+        )
+    }
+
+    fn amend_use_case(
+        &self,
+    ) -> AmendOrderUseCase<
+        C,
+        crate::infrastructure::InMemoryOrderBookRepository,
+        crate::infrastructure::BroadcastEventPublisher,
+        crate::infrastructure::TokenBucketRateLimiter,
+    > {
+        let state = &self.acceptor.state;
+        AmendOrderUseCase::new(
+            Arc::clone(&state.clock),
+            Arc::clone(&state.order_book_repo),
+            Arc::clone(&state.event_publisher),
+            Arc::clone(&state.rate_limiter),
+        )
+    }
+
+    async fn on_new_order(&mut self, message: &FixMessage, seq_num: u64) -> io::Result<()> {
+        let command = match order_entry::parse_new_order(message) {
+            Ok(command) => command,
+            Err(e) => return self.session_reject(message, seq_num, e).await,
+        };
+        self.submit(message, command, None).await.map(|_| ())
+    }
+
+    /// Submit through the use case and report the outcome
+    ///
+    /// `replaced` carries the OrigClOrdID when this is the new leg of a
+    /// cancel/replace, which changes the first report's ExecType to Replaced.
+    /// Returns whether the order was accepted.
+    async fn submit(
+        &mut self,
+        message: &FixMessage,
+        command: SubmitOrderCommand,
+        replaced: Option<&str>,
+    ) -> io::Result<bool> {
+        let symbol = command.symbol.clone();
+        self.ensure_fill_watcher(&symbol);
+
+        // Hold the open-order lock across matching so the fill watcher cannot
+        // observe a passive fill for this order before it is tracked
+        let open_orders = Arc::clone(&self.open_orders);
+        let mut open = open_orders.lock().await;
+        let result = self
+            .submit_use_case()
+            .execute(&self.client_id, command)
+            .await;
+        let now = self.acceptor.state.clock.now();
+
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                drop(open);
+                let (reason, text) = order_reject_reason(&e);
+                let report = order_entry::reject_report(message, reason, &text, now);
+                return self.send(report).await.map(|_| false);
+            }
+        };
+
+        let order = &result.order;
+        let mut notional = Value::ZERO;
+        let mut cum_qty = Quantity::ZERO;
+        let mut reports = Vec::with_capacity(result.fills.len() + 1);
+
+        let first_exec_type = if replaced.is_some() {
+            ExecType::Replaced
+        } else {
+            ExecType::New
+        };
+        let mut working = order.clone();
+        working.filled_quantity = Quantity::ZERO;
+        working.status = OrderStatus::New;
+        reports.push(order_entry::execution_report(
+            &working,
+            first_exec_type,
+            ReportDetails {
+                orig_cl_ord_id: replaced,
+                ..Default::default()
+            },
+            now,
+        ));
+
+        for fill in &result.fills {
+            notional = notional + fill.price.mul_qty(fill.quantity);
+            cum_qty = cum_qty + fill.quantity;
+            working.fill(fill.quantity, now);
+            if cum_qty >= order.quantity {
+                working.status = OrderStatus::Filled;
+            }
+            reports.push(order_entry::execution_report(
+                &working,
+                ExecType::Trade,
+                ReportDetails {
+                    last_fill: Some((fill.price, fill.quantity)),
+                    avg_px: Some(order_entry::average_price(notional, cum_qty)),
+                    ..Default::default()
+                },
+                now,
+            ));
+        }
+
+        match order.status {
+            OrderStatus::Canceled | OrderStatus::Expired => {
+                // IOC/FOK remainder
+                let exec_type = if order.status == OrderStatus::Expired {
+                    ExecType::Expired
+                } else {
+                    ExecType::Canceled
+                };
+                reports.push(order_entry::execution_report(
+                    order,
+                    exec_type,
+                    ReportDetails {
+                        avg_px: Some(order_entry::average_price(notional, cum_qty)),
+                        ..Default::default()
+                    },
+                    now,
+                ));
+            }
+            status if status.is_active() => open.insert(order.clone(), notional),
+            _ => {}
+        }
+        drop(open);
+
+        for report in reports {
+            self.send(report).await?;
+        }
+        Ok(true)
+    }
+
+    async fn on_cancel(&mut self, message: &FixMessage, seq_num: u64) -> io::Result<()> {
+        let command = match order_entry::parse_cancel(message) {
+            Ok(command) => command,
+            Err(e) => return self.session_reject(message, seq_num, e).await,
+        };
+
+        let open_orders = Arc::clone(&self.open_orders);
+        let mut open = open_orders.lock().await;
+        let result = self
+            .cancel_use_case()
+            .execute(&self.client_id, command)
+            .await;
+        let now = self.acceptor.state.clock.now();
+
+        match result {
+            Ok(result) => {
+                let notional = open
+                    .remove(&result.order.id)
+                    .map(|t| t.notional)
+                    .unwrap_or(Value::ZERO);
+                drop(open);
+                let report = order_entry::execution_report(
+                    &result.order,
+                    ExecType::Canceled,
+                    ReportDetails {
+                        cl_ord_id: message.get(tags::CL_ORD_ID),
+                        orig_cl_ord_id: message.get(tags::ORIG_CL_ORD_ID),
+                        avg_px: Some(order_entry::average_price(
+                            notional,
+                            result.order.filled_quantity,
+                        )),
+                        ..Default::default()
+                    },
+                    now,
+                );
+                self.send(report).await
+            }
+            Err(e) => {
+                drop(open);
+                let reject = order_entry::cancel_reject(
+                    message,
+                    CxlRejResponseTo::Cancel,
+                    matches!(e, CancelError::OrderNotFound),
+                    &e.to_string(),
+                );
+                self.send(reject).await
+            }
+        }
+    }
+
+    /// Cancel/replace as cancel of the original plus a new order
+    ///
+    /// A replace that only lowers OrderQty on a resting order is applied in
+    /// place instead, keeping the order's queue priority.
+    ///
+    /// OrderQty on the replace is the new total quantity, so the new order is
+    /// sized to the quantity still open after what was already filled. A new
+    /// order that fails validation is answered with an OrderCancelReject and
+    /// the original keeps working. If it is still rejected after the cancel
+    /// (a race with the book or account), a Canceled report for OrigClOrdID
+    /// follows the reject so the client sees both.
+    async fn on_replace(&mut self, message: &FixMessage, seq_num: u64) -> io::Result<()> {
+        let (cancel, mut new_order) = match order_entry::parse_cancel(message)
+            .and_then(|c| order_entry::parse_new_order(message).map(|n| (c, n)))
+        {
+            Ok(parsed) => parsed,
+            Err(e) => return self.session_reject(message, seq_num, e).await,
+        };
+
+        let open_orders = Arc::clone(&self.open_orders);
+        let mut open = open_orders.lock().await;
+
+        let amend = open
+            .find(cancel.order_id, cancel.client_order_id.as_deref())
+            .filter(|tracked| order_entry::reduces_in_place(&tracked.order, &new_order))
+            .map(|tracked| AmendOrderCommand {
+                symbol: cancel.symbol.clone(),
+                order_id: Some(tracked.order.id),
+                client_order_id: None,
+                new_quantity: new_order.quantity,
+                new_client_order_id: new_order.client_order_id.clone(),
+            });
+        // If a fill raced the amend it no longer applies; fall back to
+        // cancel/replace
+        if let Some(command) = amend
+            && let Ok(result) = self
+                .amend_use_case()
+                .execute(&self.client_id, command)
+                .await
+        {
+            let notional = open
+                .remove(&result.order.id)
+                .map(|t| t.notional)
+                .unwrap_or(Value::ZERO);
+            open.insert(result.order.clone(), notional);
+            drop(open);
+            let now = self.acceptor.state.clock.now();
+            let report = order_entry::execution_report(
+                &result.order,
+                ExecType::Replaced,
+                ReportDetails {
+                    orig_cl_ord_id: message.get(tags::ORIG_CL_ORD_ID),
+                    avg_px: Some(order_entry::average_price(
+                        notional,
+                        result.order.filled_quantity,
+                    )),
+                    ..Default::default()
+                },
+                now,
+            );
+            return self.send(report).await;
+        }
+
+        // Check the replacement before pulling the original, so one that
+        // would be rejected leaves the original working
+        let mut replacement = new_order.clone();
+        if let Some(tracked) = open.find(cancel.order_id, cancel.client_order_id.as_deref()) {
+            replacement.quantity = new_order
+                .quantity
+                .saturating_sub(tracked.order.filled_quantity);
+        }
+        if !replacement.quantity.is_zero()
+            && let Err(e) = self
+                .submit_use_case()
+                .validate(&self.client_id, &replacement)
+                .await
+        {
+            drop(open);
+            let reject = order_entry::cancel_reject(
+                message,
+                CxlRejResponseTo::Replace,
+                false,
+                &e.to_string(),
+            );
+            return self.send(reject).await;
+        }
+
+        let cancelled = self
+            .cancel_use_case()
+            .execute(&self.client_id, cancel)
+            .await;
+        let (cancelled, notional) = match cancelled {
+            Ok(result) => {
+                let notional = open
+                    .remove(&result.order.id)
+                    .map(|t| t.notional)
+                    .unwrap_or(Value::ZERO);
+                (result.order, notional)
+            }
+            Err(e) => {
+                drop(open);
+                let reject = order_entry::cancel_reject(
+                    message,
+                    CxlRejResponseTo::Replace,
+                    matches!(e, CancelError::OrderNotFound),
+                    &e.to_string(),
+                );
+                return self.send(reject).await;
+            }
+        };
+        drop(open);
+
+        let remaining = new_order.quantity.saturating_sub(cancelled.filled_quantity);
+        if remaining.is_zero() {
+            let now = self.acceptor.state.clock.now();
+            let report = order_entry::execution_report(
+                &cancelled,
+                ExecType::Canceled,
+                ReportDetails {
+                    cl_ord_id: message.get(tags::CL_ORD_ID),
+                    orig_cl_ord_id: message.get(tags::ORIG_CL_ORD_ID),
+                    text: Some("Replace quantity not above filled quantity"),
+                    ..Default::default()
+                },
+                now,
+            );
+            return self.send(report).await;
+        }
+        new_order.quantity = remaining;
+
+        let orig = message.get(tags::ORIG_CL_ORD_ID).map(str::to_string);
+        if self.submit(message, new_order, orig.as_deref()).await? {
+            return Ok(());
+        }
+
+        let now = self.acceptor.state.clock.now();
+        let report = order_entry::execution_report(
+            &cancelled,
+            ExecType::Canceled,
+            ReportDetails {
+                cl_ord_id: orig.as_deref(),
+                avg_px: Some(order_entry::average_price(
+                    notional,
+                    cancelled.filled_quantity,
+                )),
+                text: Some("Replacement order rejected"),
+                ..Default::default()
+            },
+            now,
+        );
+        self.send(report).await
+    }
+
+    /// Watch a symbol's trades for passive fills of this session's orders
+    fn ensure_fill_watcher(&mut self, symbol: &str) {
+        if self
+            .fill_watchers
+            .get(symbol)
+            .is_some_and(|h| !h.is_finished())
+        {
+            return;
+        }
+
+        let mut events = self.acceptor.state.event_publisher.subscribe_symbol(symbol);
+        let open_orders = Arc::clone(&self.open_orders);
+        let outbound = self.outbound_tx.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("FIX fill watcher lagged by {} events", n);
+                        continue;
+                    }
+                    Err(_) => break,
+                };
+                let ExchangeEvent::TradeExecuted(trade) = event else {
+                    continue;
+                };
+                // The maker is the resting order; aggressor fills are reported
+                // from the submit result
+                let maker = if trade.buyer_is_maker {
+                    trade.buyer_order_id
+                } else {
+                    trade.seller_order_id
+                };
+                let filled = open_orders.lock().await.apply_fill(
+                    &maker,
+                    trade.price,
+                    trade.quantity,
+                    trade.timestamp,
+                );
+                if let Some((order, avg_px)) = filled {
+                    let report = order_entry::execution_report(
+                        &order,
+                        ExecType::Trade,
+                        ReportDetails {
+                            last_fill: Some((trade.price, trade.quantity)),
+                            avg_px: Some(avg_px),
+                            ..Default::default()
+                        },
+                        trade.timestamp,
+                    );
+                    if outbound.send(report).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        self.fill_watchers.insert(symbol.to_string(), handle);
+    }
+
+    // ------------------------------------------------------------------------
+    // Market data
+    // ------------------------------------------------------------------------
+
+    async fn on_market_data_request(
+        &mut self,
+        message: &FixMessage,
+        seq_num: u64,
+    ) -> io::Result<()> {
+        let request = match market_data::parse_market_data_request(message) {
+            Ok(request) => request,
+            Err(e) => {
+                let md_req_id = message.get(tags::MD_REQ_ID).unwrap_or_default();
+                let reason = match e {
+                    FixFieldError::Unsupported(tags::SUBSCRIPTION_REQUEST_TYPE) => {
+                        MdReqRejReason::UnsupportedSubscriptionRequestType
+                    }
+                    FixFieldError::Unsupported(tags::MD_ENTRY_TYPE) => {
+                        MdReqRejReason::UnsupportedMdEntryType
+                    }
+                    FixFieldError::Invalid(tags::MARKET_DEPTH) => {
+                        MdReqRejReason::UnsupportedMarketDepth
+                    }
+                    _ => return self.session_reject(message, seq_num, e).await,
+                };
+                let reject = market_data::request_reject(md_req_id, reason, &e.to_string());
+                return self.send(reject).await;
+            }
+        };
+
+        match request.subscription {
+            SubscriptionRequestType::Unsubscribe => {
+                for handle in self
+                    .md_subscriptions
+                    .remove(&request.md_req_id)
+                    .unwrap_or_default()
+                {
+                    handle.abort();
+                }
+                Ok(())
+            }
+            SubscriptionRequestType::Subscribe
+                if self.md_subscriptions.contains_key(&request.md_req_id) =>
+            {
+                let reject = market_data::request_reject(
+                    &request.md_req_id,
+                    MdReqRejReason::DuplicateMdReqId,
+                    "Duplicate MDReqID",
+                );
+                self.send(reject).await
+            }
+            SubscriptionRequestType::Snapshot | SubscriptionRequestType::Subscribe => {
+                let request = Arc::new(request);
+                let mut handles = Vec::new();
+                for symbol in &request.symbols {
+                    if let Some(handle) = self.start_market_data(&request, symbol).await? {
+                        handles.push(handle);
+                    }
+                }
+                if !handles.is_empty() {
+                    self.md_subscriptions
+                        .insert(request.md_req_id.clone(), handles);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Send the full refresh and, for subscriptions, spawn the incremental feed
+    async fn start_market_data(
+        &mut self,
+        request: &Arc<MarketDataRequest>,
+        symbol: &str,
+    ) -> io::Result<Option<JoinHandle<()>>> {
+        let state = &self.acceptor.state;
+        let Ok(parsed) = Symbol::new(symbol) else {
+            let reject = market_data::request_reject(
+                &request.md_req_id,
+                MdReqRejReason::UnknownSymbol,
+                &format!("Invalid symbol: {}", symbol),
+            );
+            self.send(reject).await?;
+            return Ok(None);
+        };
+
+        // Subscribe before taking the snapshot so no update falls in between
+        let events = (request.subscription == SubscriptionRequestType::Subscribe)
+            .then(|| state.event_publisher.subscribe_symbol(parsed.as_str()));
+
+        let use_case = GetDepthUseCase::new(
+            Arc::clone(&state.order_book_repo),
+            Arc::clone(&state.rate_limiter),
+        );
+        let depth = if request.depth == 0 {
+            5000
+        } else {
+            request.depth
+        };
+        let snapshot = use_case
+            .execute(
+                &self.client_id,
+                GetDepthQuery {
+                    symbol: symbol.to_string(),
+                    limit: Some(depth),
+                },
+            )
+            .await;
+
+        let snapshot = match snapshot {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                let reason = match e {
+                    DepthError::RateLimited { .. } => MdReqRejReason::UnsupportedMarketDepth,
+                    _ => MdReqRejReason::UnknownSymbol,
+                };
+                let reject =
+                    market_data::request_reject(&request.md_req_id, reason, &e.to_string());
+                self.send(reject).await?;
+                return Ok(None);
+            }
+        };
+
+        self.send(market_data::snapshot_message(request, symbol, &snapshot))
+            .await?;
+
+        let Some(mut events) = events else {
+            return Ok(None);
+        };
+        let request = Arc::clone(request);
+        let symbol = symbol.to_string();
+        let outbound = self.outbound_tx.clone();
+        let mut book = SubscribedBook::from_snapshot(&snapshot, request.depth);
+
+        Ok(Some(tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("FIX market data for {} lagged by {} events", symbol, n);
+                        continue;
+                    }
+                    Err(_) => break,
+                };
+                let entries = match &event {
+                    ExchangeEvent::DepthUpdate(update)
+                        if request.wants(MdEntryType::Bid) || request.wants(MdEntryType::Offer) =>
+                    {
+                        book.apply(update)
+                            .into_iter()
+                            .filter(|e| request.wants(e.entry_type))
+                            .collect()
+                    }
+                    ExchangeEvent::TradeExecuted(trade) if request.wants(MdEntryType::Trade) => {
+                        vec![market_data::trade_entry(trade)]
+                    }
+                    _ => continue,
+                };
+                if entries.is_empty() {
+                    continue;
+                }
+                let message = market_data::incremental_message(
+                    &request.md_req_id,
+                    &symbol,
+                    book.last_update_id(),
+                    &entries,
+                );
+                if outbound.send(message).is_err() {
+                    break;
+                }
+            }
+        })))
+    }
+}
+
+/// OrdRejReason and text for a failed submit
+fn order_reject_reason(error: &OrderError) -> (OrdRejReason, String) {
+    let reason = match error {
+        OrderError::InvalidSymbol(_) | OrderError::SymbolNotFound(_) => OrdRejReason::UnknownSymbol,
+        OrderError::AccountError(_) => OrdRejReason::OrderExceedsLimit,
+        OrderError::RateLimited { .. } => OrdRejReason::BrokerOption,
+        _ => OrdRejReason::Other,
+    };
+    (reason, error.to_string())
+}
@@ -0,0 +1,446 @@
+//! FIX market data mapping
+//!
+//! MarketDataRequest (V) is answered with a full refresh (W) built from the
+//! same depth query as `GET /api/v3/depth`. Subscriptions then receive
+//! incremental refreshes (X) computed by diffing each `DepthUpdateEvent`
+//! against the last state sent, plus trade entries from `TradeExecutedEvent`.
+
+use std::collections::HashMap;
+
+use super::message::{FixFieldError, FixMessage, msg_type, tags};
+use crate::application::DepthResult;
+use crate::domain::{DepthUpdateEvent, Price, PriceLevel, Quantity, TradeExecutedEvent};
+
+/// SubscriptionRequestType (263)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionRequestType {
+    Snapshot,
+    Subscribe,
+    Unsubscribe,
+}
+
+/// MDEntryType (269) values supported by the simulator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MdEntryType {
+    Bid,
+    Offer,
+    Trade,
+}
+
+impl MdEntryType {
+    pub fn as_fix(&self) -> &'static str {
+        match self {
+            MdEntryType::Bid => "0",
+            MdEntryType::Offer => "1",
+            MdEntryType::Trade => "2",
+        }
+    }
+
+    fn from_fix(value: &str) -> Option<Self> {
+        match value {
+            "0" => Some(MdEntryType::Bid),
+            "1" => Some(MdEntryType::Offer),
+            "2" => Some(MdEntryType::Trade),
+            _ => None,
+        }
+    }
+}
+
+/// MDUpdateAction (279)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MdUpdateAction {
+    New,
+    Change,
+    Delete,
+}
+
+impl MdUpdateAction {
+    pub fn as_fix(&self) -> &'static str {
+        match self {
+            MdUpdateAction::New => "0",
+            MdUpdateAction::Change => "1",
+            MdUpdateAction::Delete => "2",
+        }
+    }
+}
+
+/// MDReqRejReason (281)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MdReqRejReason {
+    UnknownSymbol = 0,
+    DuplicateMdReqId = 1,
+    UnsupportedSubscriptionRequestType = 4,
+    UnsupportedMarketDepth = 5,
+    UnsupportedMdEntryType = 8,
+}
+
+/// Parsed MarketDataRequest
+#[derive(Debug, Clone)]
+pub struct MarketDataRequest {
+    pub md_req_id: String,
+    pub subscription: SubscriptionRequestType,
+    /// Number of levels per side; 0 means full book
+    pub depth: usize,
+    pub entry_types: Vec<MdEntryType>,
+    pub symbols: Vec<String>,
+}
+
+impl MarketDataRequest {
+    pub fn wants(&self, entry_type: MdEntryType) -> bool {
+        self.entry_types.contains(&entry_type)
+    }
+}
+
+/// Parse a MarketDataRequest
+///
+/// Unknown MDEntryType values are reported through `Err(Unsupported(269))`
+/// so the caller can answer with a MarketDataRequestReject.
+pub fn parse_market_data_request(msg: &FixMessage) -> Result<MarketDataRequest, FixFieldError> {
+    let md_req_id = msg.require(tags::MD_REQ_ID)?.to_string();
+    let subscription = match msg.require(tags::SUBSCRIPTION_REQUEST_TYPE)? {
+        "0" => SubscriptionRequestType::Snapshot,
+        "1" => SubscriptionRequestType::Subscribe,
+        "2" => SubscriptionRequestType::Unsubscribe,
+        _ => return Err(FixFieldError::Unsupported(tags::SUBSCRIPTION_REQUEST_TYPE)),
+    };
+    if subscription == SubscriptionRequestType::Unsubscribe {
+        return Ok(MarketDataRequest {
+            md_req_id,
+            subscription,
+            depth: 0,
+            entry_types: Vec::new(),
+            symbols: Vec::new(),
+        });
+    }
+
+    let depth = msg
+        .get_u64(tags::MARKET_DEPTH)?
+        .ok_or(FixFieldError::Missing(tags::MARKET_DEPTH))? as usize;
+    let entry_types = msg
+        .get_all(tags::MD_ENTRY_TYPE)
+        .into_iter()
+        .map(|v| MdEntryType::from_fix(v).ok_or(FixFieldError::Unsupported(tags::MD_ENTRY_TYPE)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if entry_types.is_empty() {
+        return Err(FixFieldError::Missing(tags::MD_ENTRY_TYPE));
+    }
+    let symbols: Vec<String> = msg
+        .get_all(tags::SYMBOL)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if symbols.is_empty() {
+        return Err(FixFieldError::Missing(tags::SYMBOL));
+    }
+
+    Ok(MarketDataRequest {
+        md_req_id,
+        subscription,
+        depth,
+        entry_types,
+        symbols,
+    })
+}
+
+/// One entry of a full or incremental refresh
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdEntry {
+    pub action: MdUpdateAction,
+    pub entry_type: MdEntryType,
+    pub price: Price,
+    pub size: Quantity,
+}
+
+/// Last book state sent on a subscription, used to derive incremental updates
+#[derive(Debug, Clone, Default)]
+pub struct SubscribedBook {
+    bids: HashMap<Price, Quantity>,
+    asks: HashMap<Price, Quantity>,
+    last_update_id: u64,
+    depth: usize,
+}
+
+impl SubscribedBook {
+    /// Seed from the snapshot sent in the full refresh
+    pub fn from_snapshot(snapshot: &DepthResult, depth: usize) -> Self {
+        let to_map = |levels: &[PriceLevel]| {
+            levels
+                .iter()
+                .take(depth_limit(depth))
+                .map(|l| (l.price, l.quantity))
+                .collect()
+        };
+        SubscribedBook {
+            bids: to_map(&snapshot.bids),
+            asks: to_map(&snapshot.asks),
+            last_update_id: snapshot.last_update_id,
+            depth,
+        }
+    }
+
+    /// Diff a depth update against the last state sent
+    ///
+    /// Updates already covered by the snapshot (by update ID) yield nothing.
+    pub fn apply(&mut self, update: &DepthUpdateEvent) -> Vec<MdEntry> {
+        if update.final_update_id <= self.last_update_id {
+            return Vec::new();
+        }
+        self.last_update_id = update.final_update_id;
+
+        let mut entries = Vec::new();
+        diff_side(
+            &mut self.bids,
+            &parse_levels(&update.bids, self.depth),
+            MdEntryType::Bid,
+            &mut entries,
+        );
+        diff_side(
+            &mut self.asks,
+            &parse_levels(&update.asks, self.depth),
+            MdEntryType::Offer,
+            &mut entries,
+        );
+        entries
+    }
+
+    pub fn last_update_id(&self) -> u64 {
+        self.last_update_id
+    }
+}
+
+fn depth_limit(depth: usize) -> usize {
+    if depth == 0 { usize::MAX } else { depth }
+}
+
+fn parse_levels(levels: &[[String; 2]], depth: usize) -> Vec<(Price, Quantity)> {
+    levels
+        .iter()
+        .filter_map(|[p, q]| Some((Price::parse(p).ok()?, Quantity::parse(q).ok()?)))
+        .filter(|(_, q)| !q.is_zero())
+        .take(depth_limit(depth))
+        .collect()
+}
+
+fn diff_side(
+    previous: &mut HashMap<Price, Quantity>,
+    current: &[(Price, Quantity)],
+    entry_type: MdEntryType,
+    entries: &mut Vec<MdEntry>,
+) {
+    let current_map: HashMap<Price, Quantity> = current.iter().copied().collect();
+
+    // Deletes first so a consumer never sees more levels than the depth
+    let mut removed: Vec<Price> = previous
+        .keys()
+        .filter(|p| !current_map.contains_key(p))
+        .copied()
+        .collect();
+    removed.sort();
+    for price in removed {
+        entries.push(MdEntry {
+            action: MdUpdateAction::Delete,
+            entry_type,
+            price,
+            size: Quantity::ZERO,
+        });
+    }
+
+    for (price, size) in current {
+        match previous.get(price) {
+            None => entries.push(MdEntry {
+                action: MdUpdateAction::New,
+                entry_type,
+                price: *price,
+                size: *size,
+            }),
+            Some(prev) if prev != size => entries.push(MdEntry {
+                action: MdUpdateAction::Change,
+                entry_type,
+                price: *price,
+                size: *size,
+            }),
+            _ => {}
+        }
+    }
+
+    *previous = current_map;
+}
+
+/// Trade entry for an incremental refresh
+pub fn trade_entry(trade: &TradeExecutedEvent) -> MdEntry {
+    MdEntry {
+        action: MdUpdateAction::New,
+        entry_type: MdEntryType::Trade,
+        price: trade.price,
+        size: trade.quantity,
+    }
+}
+
+/// MarketDataSnapshotFullRefresh (W)
+pub fn snapshot_message(
+    request: &MarketDataRequest,
+    symbol: &str,
+    snapshot: &DepthResult,
+) -> FixMessage {
+    let limit = depth_limit(request.depth);
+    let mut entries = Vec::new();
+    if request.wants(MdEntryType::Bid) {
+        entries.extend(
+            snapshot
+                .bids
+                .iter()
+                .take(limit)
+                .map(|l| (MdEntryType::Bid, l)),
+        );
+    }
+    if request.wants(MdEntryType::Offer) {
+        entries.extend(
+            snapshot
+                .asks
+                .iter()
+                .take(limit)
+                .map(|l| (MdEntryType::Offer, l)),
+        );
+    }
+
+    let mut msg = FixMessage::new(msg_type::MARKET_DATA_SNAPSHOT)
+        .with(tags::MD_REQ_ID, &request.md_req_id)
+        .with(tags::SYMBOL, symbol)
+        .with(tags::RPT_SEQ, snapshot.last_update_id)
+        .with(tags::NO_MD_ENTRIES, entries.len());
+    for (entry_type, level) in entries {
+        msg.push(tags::MD_ENTRY_TYPE, entry_type.as_fix());
+        msg.push(tags::MD_ENTRY_PX, level.price);
+        msg.push(tags::MD_ENTRY_SIZE, level.quantity);
+    }
+    msg
+}
+
+/// MarketDataIncrementalRefresh (X)
+pub fn incremental_message(
+    md_req_id: &str,
+    symbol: &str,
+    rpt_seq: u64,
+    entries: &[MdEntry],
+) -> FixMessage {
+    let mut msg = FixMessage::new(msg_type::MARKET_DATA_INCREMENTAL)
+        .with(tags::MD_REQ_ID, md_req_id)
+        .with(tags::NO_MD_ENTRIES, entries.len());
+    for entry in entries {
+        msg.push(tags::MD_UPDATE_ACTION, entry.action.as_fix());
+        msg.push(tags::MD_ENTRY_TYPE, entry.entry_type.as_fix());
+        msg.push(tags::SYMBOL, symbol);
+        msg.push(tags::MD_ENTRY_PX, entry.price);
+        if entry.action != MdUpdateAction::Delete {
+            msg.push(tags::MD_ENTRY_SIZE, entry.size);
+        }
+        msg.push(tags::RPT_SEQ, rpt_seq);
+    }
+    msg
+}
+
+/// MarketDataRequestReject (Y)
+pub fn request_reject(md_req_id: &str, reason: MdReqRejReason, text: &str) -> FixMessage {
+    FixMessage::new(msg_type::MARKET_DATA_REQUEST_REJECT)
+        .with(tags::MD_REQ_ID, md_req_id)
+        .with(tags::MD_REQ_REJ_REASON, reason as u32)
+        .with(tags::TEXT, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Symbol;
+
+    fn level(price: i64, qty: i64) -> PriceLevel {
+        PriceLevel::new(Price::from_int(price), Quantity::from_int(qty))
+    }
+
+    fn depth_update(id: u64, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> DepthUpdateEvent {
+        DepthUpdateEvent::new(&Symbol::new("BTCUSDT").unwrap(), id, id, bids, asks, 0)
+    }
+
+    #[test]
+    fn test_parse_market_data_request() {
+        let msg = FixMessage::new(msg_type::MARKET_DATA_REQUEST)
+            .with(tags::MD_REQ_ID, "md1")
+            .with(tags::SUBSCRIPTION_REQUEST_TYPE, "1")
+            .with(tags::MARKET_DEPTH, "5")
+            .with(tags::NO_MD_ENTRY_TYPES, "2")
+            .with(tags::MD_ENTRY_TYPE, "0")
+            .with(tags::MD_ENTRY_TYPE, "1")
+            .with(tags::NO_RELATED_SYM, "1")
+            .with(tags::SYMBOL, "BTCUSDT");
+
+        let req = parse_market_data_request(&msg).unwrap();
+        assert_eq!(req.subscription, SubscriptionRequestType::Subscribe);
+        assert_eq!(req.depth, 5);
+        assert_eq!(req.entry_types, vec![MdEntryType::Bid, MdEntryType::Offer]);
+        assert_eq!(req.symbols, vec!["BTCUSDT".to_string()]);
+    }
+
+    #[test]
+    fn test_incremental_diff() {
+        let snapshot = DepthResult {
+            last_update_id: 10,
+            bids: vec![level(100, 1), level(99, 2)],
+            asks: vec![level(101, 1)],
+        };
+        let mut book = SubscribedBook::from_snapshot(&snapshot, 0);
+
+        // Stale update is ignored
+        assert!(
+            book.apply(&depth_update(10, vec![level(100, 5)], vec![]))
+                .is_empty()
+        );
+
+        let entries = book.apply(&depth_update(
+            11,
+            vec![level(100, 3), level(99, 2)],
+            vec![level(101, 1), level(102, 4)],
+        ));
+
+        assert_eq!(
+            entries,
+            vec![
+                MdEntry {
+                    action: MdUpdateAction::Change,
+                    entry_type: MdEntryType::Bid,
+                    price: Price::from_int(100),
+                    size: Quantity::from_int(3),
+                },
+                MdEntry {
+                    action: MdUpdateAction::New,
+                    entry_type: MdEntryType::Offer,
+                    price: Price::from_int(102),
+                    size: Quantity::from_int(4),
+                },
+            ]
+        );
+
+        let entries = book.apply(&depth_update(12, vec![level(99, 2)], vec![level(102, 4)]));
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.action == MdUpdateAction::Delete));
+        assert_eq!(book.last_update_id(), 12);
+    }
+
+    #[test]
+    fn test_snapshot_respects_depth() {
+        let request = MarketDataRequest {
+            md_req_id: "md1".to_string(),
+            subscription: SubscriptionRequestType::Snapshot,
+            depth: 1,
+            entry_types: vec![MdEntryType::Bid, MdEntryType::Offer],
+            symbols: vec!["BTCUSDT".to_string()],
+        };
+        let snapshot = DepthResult {
+            last_update_id: 3,
+            bids: vec![level(100, 1), level(99, 2)],
+            asks: vec![level(101, 1), level(102, 1)],
+        };
+
+        let msg = snapshot_message(&request, "BTCUSDT", &snapshot);
+        assert_eq!(msg.get(tags::NO_MD_ENTRIES), Some("2"));
+        assert_eq!(msg.get_all(tags::MD_ENTRY_TYPE), vec!["0", "1"]);
+    }
+}
@@ -0,0 +1,571 @@
+//! FIX tag=value message model and wire codec
+//!
+//! Messages are kept as an ordered list of `(tag, value)` pairs so repeating
+//! groups survive a round trip. The header fields `8` (BeginString), `9`
+//! (BodyLength) and `10` (CheckSum) are computed by the encoder and stripped
+//! by the decoder.
+
+use std::fmt;
+
+/// Field delimiter (ASCII SOH)
+pub const SOH: u8 = 0x01;
+
+/// Protocol version served by the acceptor
+pub const BEGIN_STRING: &str = "FIX.4.4";
+
+/// Tag numbers used by the simulator
+pub mod tags {
+    pub const ACCOUNT: u32 = 1;
+    pub const AVG_PX: u32 = 6;
+    pub const BEGIN_SEQ_NO: u32 = 7;
+    pub const BEGIN_STRING: u32 = 8;
+    pub const BODY_LENGTH: u32 = 9;
+    pub const CHECK_SUM: u32 = 10;
+    pub const CL_ORD_ID: u32 = 11;
+    pub const CUM_QTY: u32 = 14;
+    pub const END_SEQ_NO: u32 = 16;
+    pub const EXEC_ID: u32 = 17;
+    pub const EXEC_INST: u32 = 18;
+    pub const LAST_PX: u32 = 31;
+    pub const LAST_QTY: u32 = 32;
+    pub const MSG_SEQ_NUM: u32 = 34;
+    pub const MSG_TYPE: u32 = 35;
+    pub const NEW_SEQ_NO: u32 = 36;
+    pub const ORDER_ID: u32 = 37;
+    pub const ORDER_QTY: u32 = 38;
+    pub const ORD_STATUS: u32 = 39;
+    pub const ORD_TYPE: u32 = 40;
+    pub const ORIG_CL_ORD_ID: u32 = 41;
+    pub const POSS_DUP_FLAG: u32 = 43;
+    pub const PRICE: u32 = 44;
+    pub const REF_SEQ_NUM: u32 = 45;
+    pub const SENDER_COMP_ID: u32 = 49;
+    pub const SENDING_TIME: u32 = 52;
+    pub const SIDE: u32 = 54;
+    pub const SYMBOL: u32 = 55;
+    pub const TARGET_COMP_ID: u32 = 56;
+    pub const TEXT: u32 = 58;
+    pub const TIME_IN_FORCE: u32 = 59;
+    pub const TRANSACT_TIME: u32 = 60;
+    pub const RPT_SEQ: u32 = 83;
+    pub const ENCRYPT_METHOD: u32 = 98;
+    pub const STOP_PX: u32 = 99;
+    pub const CXL_REJ_REASON: u32 = 102;
+    pub const ORD_REJ_REASON: u32 = 103;
+    pub const HEART_BT_INT: u32 = 108;
+    pub const TEST_REQ_ID: u32 = 112;
+    pub const ORIG_SENDING_TIME: u32 = 122;
+    pub const GAP_FILL_FLAG: u32 = 123;
+    pub const RESET_SEQ_NUM_FLAG: u32 = 141;
+    pub const NO_RELATED_SYM: u32 = 146;
+    pub const EXEC_TYPE: u32 = 150;
+    pub const LEAVES_QTY: u32 = 151;
+    pub const MD_REQ_ID: u32 = 262;
+    pub const SUBSCRIPTION_REQUEST_TYPE: u32 = 263;
+    pub const MARKET_DEPTH: u32 = 264;
+    pub const MD_UPDATE_TYPE: u32 = 265;
+    pub const NO_MD_ENTRY_TYPES: u32 = 267;
+    pub const NO_MD_ENTRIES: u32 = 268;
+    pub const MD_ENTRY_TYPE: u32 = 269;
+    pub const MD_ENTRY_PX: u32 = 270;
+    pub const MD_ENTRY_SIZE: u32 = 271;
+    pub const MD_UPDATE_ACTION: u32 = 279;
+    pub const MD_REQ_REJ_REASON: u32 = 281;
+    pub const REF_TAG_ID: u32 = 371;
+    pub const REF_MSG_TYPE: u32 = 372;
+    pub const SESSION_REJECT_REASON: u32 = 373;
+    pub const CXL_REJ_RESPONSE_TO: u32 = 434;
+}
+
+/// Message types (tag 35) handled by the simulator
+pub mod msg_type {
+    pub const HEARTBEAT: &str = "0";
+    pub const TEST_REQUEST: &str = "1";
+    pub const RESEND_REQUEST: &str = "2";
+    pub const REJECT: &str = "3";
+    pub const SEQUENCE_RESET: &str = "4";
+    pub const LOGOUT: &str = "5";
+    pub const EXECUTION_REPORT: &str = "8";
+    pub const ORDER_CANCEL_REJECT: &str = "9";
+    pub const LOGON: &str = "A";
+    pub const NEW_ORDER_SINGLE: &str = "D";
+    pub const ORDER_CANCEL_REQUEST: &str = "F";
+    pub const ORDER_CANCEL_REPLACE_REQUEST: &str = "G";
+    pub const MARKET_DATA_REQUEST: &str = "V";
+    pub const MARKET_DATA_SNAPSHOT: &str = "W";
+    pub const MARKET_DATA_INCREMENTAL: &str = "X";
+    pub const MARKET_DATA_REQUEST_REJECT: &str = "Y";
+
+    /// Session-level (administrative) message types
+    pub fn is_admin(msg_type: &str) -> bool {
+        matches!(
+            msg_type,
+            HEARTBEAT | TEST_REQUEST | RESEND_REQUEST | REJECT | SEQUENCE_RESET | LOGOUT | LOGON
+        )
+    }
+}
+
+/// Header tags owned by the session layer; they are written by
+/// [`FixMessage::encode`] and must not be set on the body.
+const SESSION_HEADER_TAGS: [u32; 6] = [
+    tags::MSG_TYPE,
+    tags::SENDER_COMP_ID,
+    tags::TARGET_COMP_ID,
+    tags::MSG_SEQ_NUM,
+    tags::SENDING_TIME,
+    tags::POSS_DUP_FLAG,
+];
+
+// ============================================================================
+// Message
+// ============================================================================
+
+/// A decoded or to-be-encoded FIX message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessage {
+    msg_type: String,
+    fields: Vec<(u32, String)>,
+}
+
+impl FixMessage {
+    /// Create an empty message of the given type
+    pub fn new(msg_type: impl Into<String>) -> Self {
+        FixMessage {
+            msg_type: msg_type.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn msg_type(&self) -> &str {
+        &self.msg_type
+    }
+
+    /// Append a field (builder style)
+    pub fn with(mut self, tag: u32, value: impl ToString) -> Self {
+        self.push(tag, value);
+        self
+    }
+
+    /// Append a field
+    pub fn push(&mut self, tag: u32, value: impl ToString) {
+        self.fields.push((tag, value.to_string()));
+    }
+
+    /// Replace the first occurrence of a field, or append it
+    pub fn set(&mut self, tag: u32, value: impl ToString) {
+        let value = value.to_string();
+        match self.fields.iter_mut().find(|(t, _)| *t == tag) {
+            Some(field) => field.1 = value,
+            None => self.fields.push((tag, value)),
+        }
+    }
+
+    /// First value of a tag
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// First value of a required tag
+    pub fn require(&self, tag: u32) -> Result<&str, FixFieldError> {
+        self.get(tag).ok_or(FixFieldError::Missing(tag))
+    }
+
+    /// First value of a tag parsed as an integer
+    pub fn get_u64(&self, tag: u32) -> Result<Option<u64>, FixFieldError> {
+        self.get(tag)
+            .map(|v| v.parse().map_err(|_| FixFieldError::Invalid(tag)))
+            .transpose()
+    }
+
+    /// Boolean field (`Y`/`N`), absent means false
+    pub fn get_bool(&self, tag: u32) -> bool {
+        self.get(tag) == Some("Y")
+    }
+
+    /// All values of a (possibly repeating) tag, in message order
+    pub fn get_all(&self, tag: u32) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(t, _)| *t == tag)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// Body fields in message order (excluding BeginString/BodyLength/CheckSum)
+    pub fn fields(&self) -> &[(u32, String)] {
+        &self.fields
+    }
+
+    /// Sequence number of a decoded message
+    pub fn seq_num(&self) -> Result<u64, FixFieldError> {
+        self.get_u64(tags::MSG_SEQ_NUM)?
+            .ok_or(FixFieldError::Missing(tags::MSG_SEQ_NUM))
+    }
+
+    /// Encode with a session header
+    ///
+    /// Header fields already present on the message (e.g. when resending a
+    /// stored message) are replaced by `header`.
+    pub fn encode(&self, header: &FixHeader<'_>) -> Vec<u8> {
+        let mut body = Vec::with_capacity(128);
+        write_field(&mut body, tags::MSG_TYPE, &self.msg_type);
+        write_field(&mut body, tags::SENDER_COMP_ID, header.sender_comp_id);
+        write_field(&mut body, tags::TARGET_COMP_ID, header.target_comp_id);
+        write_field(&mut body, tags::MSG_SEQ_NUM, &header.seq_num.to_string());
+        if header.poss_dup {
+            write_field(&mut body, tags::POSS_DUP_FLAG, "Y");
+        }
+        write_field(&mut body, tags::SENDING_TIME, header.sending_time);
+        for (tag, value) in &self.fields {
+            if !SESSION_HEADER_TAGS.contains(tag) {
+                write_field(&mut body, *tag, value);
+            }
+        }
+
+        let mut out = Vec::with_capacity(body.len() + 32);
+        write_field(&mut out, tags::BEGIN_STRING, BEGIN_STRING);
+        write_field(&mut out, tags::BODY_LENGTH, &body.len().to_string());
+        out.extend_from_slice(&body);
+        let checksum = checksum(&out);
+        write_field(&mut out, tags::CHECK_SUM, &format!("{:03}", checksum));
+        out
+    }
+
+    /// Decode one complete frame (as returned by [`FixDecoder::next_frame`])
+    pub fn decode(frame: &[u8]) -> Result<Self, FixParseError> {
+        if frame.len() < 7 {
+            return Err(FixParseError::MissingHeader(tags::CHECK_SUM));
+        }
+        let mut fields = Vec::new();
+        for raw in frame.split(|b| *b == SOH).filter(|f| !f.is_empty()) {
+            let eq = raw
+                .iter()
+                .position(|b| *b == b'=')
+                .ok_or(FixParseError::MalformedField)?;
+            let tag: u32 = std::str::from_utf8(&raw[..eq])
+                .ok()
+                .and_then(|t| t.parse().ok())
+                .ok_or(FixParseError::MalformedField)?;
+            let value = std::str::from_utf8(&raw[eq + 1..])
+                .map_err(|_| FixParseError::MalformedField)?
+                .to_string();
+            fields.push((tag, value));
+        }
+
+        match fields.first() {
+            Some((tags::BEGIN_STRING, v)) if v == BEGIN_STRING => {}
+            Some((tags::BEGIN_STRING, v)) => {
+                return Err(FixParseError::UnsupportedVersion(v.clone()));
+            }
+            _ => return Err(FixParseError::MissingHeader(tags::BEGIN_STRING)),
+        }
+
+        let expected = checksum(&frame[..frame.len() - 7]);
+        let actual: u32 = fields
+            .last()
+            .filter(|(t, _)| *t == tags::CHECK_SUM)
+            .and_then(|(_, v)| v.parse().ok())
+            .ok_or(FixParseError::MissingHeader(tags::CHECK_SUM))?;
+        if actual != expected {
+            return Err(FixParseError::BadChecksum { expected, actual });
+        }
+
+        let msg_type = fields
+            .iter()
+            .find(|(t, _)| *t == tags::MSG_TYPE)
+            .map(|(_, v)| v.clone())
+            .ok_or(FixParseError::MissingHeader(tags::MSG_TYPE))?;
+
+        fields.retain(|(t, _)| {
+            !matches!(
+                *t,
+                tags::BEGIN_STRING | tags::BODY_LENGTH | tags::CHECK_SUM | tags::MSG_TYPE
+            )
+        });
+
+        Ok(FixMessage { msg_type, fields })
+    }
+}
+
+impl fmt::Display for FixMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "35={}", self.msg_type)?;
+        for (tag, value) in &self.fields {
+            write!(f, "|{}={}", tag, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Session header values applied at encode time
+#[derive(Debug, Clone, Copy)]
+pub struct FixHeader<'a> {
+    pub sender_comp_id: &'a str,
+    pub target_comp_id: &'a str,
+    pub seq_num: u64,
+    pub sending_time: &'a str,
+    pub poss_dup: bool,
+}
+
+fn write_field(buf: &mut Vec<u8>, tag: u32, value: &str) {
+    buf.extend_from_slice(tag.to_string().as_bytes());
+    buf.push(b'=');
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(SOH);
+}
+
+/// FIX checksum: byte sum modulo 256
+pub fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().map(|b| *b as u32).sum::<u32>() % 256
+}
+
+/// Format a timestamp as a FIX UTCTimestamp with milliseconds
+pub fn format_utc_timestamp(ts: &chrono::DateTime<chrono::Utc>) -> String {
+    ts.format("%Y%m%d-%H:%M:%S%.3f").to_string()
+}
+
+// ============================================================================
+// Stream Decoder
+// ============================================================================
+
+/// Incremental frame splitter for a TCP byte stream
+///
+/// Uses BodyLength (tag 9) to find the end of each message, so partial reads
+/// and several messages per read are both handled.
+#[derive(Debug, Default)]
+pub struct FixDecoder {
+    buffer: Vec<u8>,
+}
+
+/// Upper bound on a single message, to stop a bad peer from growing the buffer
+const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+impl FixDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes read from the socket
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Take the next complete frame, if one is buffered
+    pub fn next_frame(&mut self) -> Result<Option<Vec<u8>>, FixParseError> {
+        // Resynchronise on the next BeginString if there is leading garbage
+        let Some(start) = find(&self.buffer, b"8=") else {
+            self.buffer.clear();
+            return Ok(None);
+        };
+        if start > 0 {
+            self.buffer.drain(..start);
+        }
+
+        let Some(begin_end) = self.buffer.iter().position(|b| *b == SOH) else {
+            return Ok(None);
+        };
+        let len_start = begin_end + 1;
+        let Some(len_soh) = self.buffer[len_start..].iter().position(|b| *b == SOH) else {
+            return Ok(None);
+        };
+        let len_field = &self.buffer[len_start..len_start + len_soh];
+        let body_len: usize = len_field
+            .strip_prefix(b"9=")
+            .and_then(|v| std::str::from_utf8(v).ok())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| {
+                self.buffer.clear();
+                FixParseError::MissingHeader(tags::BODY_LENGTH)
+            })?;
+        if body_len > MAX_MESSAGE_LEN {
+            self.buffer.clear();
+            return Err(FixParseError::TooLarge(body_len));
+        }
+
+        // "10=NNN<SOH>" trails the body
+        let body_start = len_start + len_soh + 1;
+        let frame_len = body_start + body_len + 7;
+        if self.buffer.len() < frame_len {
+            return Ok(None);
+        }
+
+        let frame: Vec<u8> = self.buffer.drain(..frame_len).collect();
+        if !frame[body_start + body_len..].starts_with(b"10=") {
+            return Err(FixParseError::MissingHeader(tags::CHECK_SUM));
+        }
+        Ok(Some(frame))
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Framing or structural error in an inbound message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixParseError {
+    MalformedField,
+    MissingHeader(u32),
+    UnsupportedVersion(String),
+    BadChecksum { expected: u32, actual: u32 },
+    TooLarge(usize),
+}
+
+impl fmt::Display for FixParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixParseError::MalformedField => write!(f, "Malformed field"),
+            FixParseError::MissingHeader(tag) => write!(f, "Missing header tag {}", tag),
+            FixParseError::UnsupportedVersion(v) => write!(f, "Unsupported BeginString: {}", v),
+            FixParseError::BadChecksum { expected, actual } => {
+                write!(f, "Bad checksum: expected {}, got {}", expected, actual)
+            }
+            FixParseError::TooLarge(len) => write!(f, "Message too large: {} bytes", len),
+        }
+    }
+}
+
+impl std::error::Error for FixParseError {}
+
+/// Missing or unparsable body field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixFieldError {
+    Missing(u32),
+    Invalid(u32),
+    Unsupported(u32),
+}
+
+impl FixFieldError {
+    pub fn tag(&self) -> u32 {
+        match self {
+            FixFieldError::Missing(tag)
+            | FixFieldError::Invalid(tag)
+            | FixFieldError::Unsupported(tag) => *tag,
+        }
+    }
+
+    /// SessionRejectReason (373) code for this error
+    pub fn reject_reason(&self) -> u32 {
+        match self {
+            FixFieldError::Missing(_) => 1,
+            FixFieldError::Unsupported(_) => 5,
+            FixFieldError::Invalid(_) => 6,
+        }
+    }
+}
+
+impl fmt::Display for FixFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixFieldError::Missing(tag) => write!(f, "Required tag missing: {}", tag),
+            FixFieldError::Invalid(tag) => write!(f, "Incorrect data format for tag {}", tag),
+            FixFieldError::Unsupported(tag) => write!(f, "Value is incorrect for tag {}", tag),
+        }
+    }
+}
+
+impl std::error::Error for FixFieldError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(seq_num: u64) -> FixHeader<'static> {
+        FixHeader {
+            sender_comp_id: "EXCH",
+            target_comp_id: "CLIENT",
+            seq_num,
+            sending_time: "20240101-00:00:00.000",
+            poss_dup: false,
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let msg = FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+            .with(tags::CL_ORD_ID, "abc")
+            .with(tags::SYMBOL, "BTCUSDT")
+            .with(tags::SIDE, "1");
+
+        let bytes = msg.encode(&header(7));
+        let decoded = FixMessage::decode(&bytes).unwrap();
+
+        assert_eq!(decoded.msg_type(), "D");
+        assert_eq!(decoded.get(tags::CL_ORD_ID), Some("abc"));
+        assert_eq!(decoded.get(tags::SENDER_COMP_ID), Some("EXCH"));
+        assert_eq!(decoded.seq_num().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_body_length_and_checksum() {
+        let bytes = FixMessage::new(msg_type::HEARTBEAT).encode(&header(1));
+        let text = String::from_utf8(bytes.clone()).unwrap();
+
+        let body_start = text.find("35=").unwrap();
+        let checksum_start = text.find("\x0110=").unwrap() + 1;
+        let declared: usize = text
+            .split('\x01')
+            .find_map(|f| f.strip_prefix("9="))
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        assert_eq!(declared, checksum_start - body_start);
+        assert_eq!(
+            text[checksum_start + 3..checksum_start + 6]
+                .parse::<u32>()
+                .unwrap(),
+            checksum(&bytes[..checksum_start])
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_checksum() {
+        let mut bytes = FixMessage::new(msg_type::HEARTBEAT).encode(&header(1));
+        let len = bytes.len();
+        bytes[len - 2] = if bytes[len - 2] == b'0' { b'1' } else { b'0' };
+
+        assert!(matches!(
+            FixMessage::decode(&bytes),
+            Err(FixParseError::BadChecksum { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decoder_handles_split_and_batched_frames() {
+        let first = FixMessage::new(msg_type::HEARTBEAT).encode(&header(1));
+        let second = FixMessage::new(msg_type::TEST_REQUEST)
+            .with(tags::TEST_REQ_ID, "t1")
+            .encode(&header(2));
+
+        let mut decoder = FixDecoder::new();
+        decoder.extend(&first[..10]);
+        assert_eq!(decoder.next_frame().unwrap(), None);
+
+        decoder.extend(&first[10..]);
+        decoder.extend(&second);
+        assert_eq!(decoder.next_frame().unwrap(), Some(first));
+        assert_eq!(decoder.next_frame().unwrap(), Some(second));
+        assert_eq!(decoder.next_frame().unwrap(), None);
+    }
+
+    #[test]
+    fn test_repeating_group_order_preserved() {
+        let msg = FixMessage::new(msg_type::MARKET_DATA_SNAPSHOT)
+            .with(tags::NO_MD_ENTRIES, 2)
+            .with(tags::MD_ENTRY_TYPE, "0")
+            .with(tags::MD_ENTRY_PX, "100")
+            .with(tags::MD_ENTRY_TYPE, "1")
+            .with(tags::MD_ENTRY_PX, "101");
+
+        let decoded = FixMessage::decode(&msg.encode(&header(3))).unwrap();
+        assert_eq!(decoded.get_all(tags::MD_ENTRY_PX), vec!["100", "101"]);
+    }
+}
@@ -0,0 +1,21 @@
+//! FIX 4.4 order entry and market data
+//!
+//! A TCP acceptor speaking FIX 4.4 alongside the REST and WebSocket APIs.
+//! Sessions support Logon/Logout, heartbeats and test requests, persistent
+//! sequence numbers with ResendRequest/SequenceReset-GapFill recovery,
+//! NewOrderSingle, OrderCancelRequest, OrderCancelReplaceRequest and
+//! MarketDataRequest (snapshot and incremental refresh).
+
+mod acceptor;
+mod market_data;
+mod message;
+mod order_entry;
+mod session;
+mod store;
+
+pub use acceptor::{FixAcceptor, FixConfig};
+pub use message::{
+    FixDecoder, FixFieldError, FixHeader, FixMessage, FixParseError, msg_type, tags,
+};
+pub use session::{FixSession, SeqCheck};
+pub use store::{FileSessionStore, InMemorySessionStore, SessionId, SessionSeqNums, SessionStore};
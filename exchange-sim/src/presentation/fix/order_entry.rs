@@ -0,0 +1,526 @@
+//! FIX order entry mapping
+//!
+//! Translates NewOrderSingle / OrderCancelRequest / OrderCancelReplaceRequest
+//! into the same use case commands the REST handlers build, and renders
+//! orders back as ExecutionReports.
+
+use std::collections::HashMap;
+
+use super::message::{FixFieldError, FixMessage, format_utc_timestamp, msg_type, tags};
+use crate::application::{CancelOrderCommand, SubmitOrderCommand};
+use crate::domain::{
+    Order, OrderId, OrderStatus, OrderType, Price, QUANTITY_SCALE, Quantity, Side, TimeInForce,
+    Timestamp, Value,
+};
+
+/// ExecType (150) values used by the simulator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecType {
+    New,
+    Canceled,
+    Replaced,
+    Rejected,
+    Expired,
+    Trade,
+}
+
+impl ExecType {
+    pub fn as_fix(&self) -> &'static str {
+        match self {
+            ExecType::New => "0",
+            ExecType::Canceled => "4",
+            ExecType::Replaced => "5",
+            ExecType::Rejected => "8",
+            ExecType::Expired => "C",
+            ExecType::Trade => "F",
+        }
+    }
+}
+
+/// OrdStatus (39) for a domain order status
+pub fn ord_status(status: OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::New => "0",
+        OrderStatus::PartiallyFilled => "1",
+        OrderStatus::Filled => "2",
+        OrderStatus::Canceled => "4",
+        OrderStatus::PendingCancel => "6",
+        OrderStatus::Rejected => "8",
+        OrderStatus::Expired => "C",
+    }
+}
+
+fn side_from_fix(value: &str) -> Option<Side> {
+    match value {
+        "1" => Some(Side::Buy),
+        "2" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn side_to_fix(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "1",
+        Side::Sell => "2",
+    }
+}
+
+fn ord_type_from_fix(value: &str, post_only: bool) -> Option<OrderType> {
+    match (value, post_only) {
+        ("1", _) => Some(OrderType::Market),
+        ("2", false) => Some(OrderType::Limit),
+        ("2", true) => Some(OrderType::LimitMaker),
+        ("3", _) => Some(OrderType::StopLoss),
+        ("4", _) => Some(OrderType::StopLossLimit),
+        _ => None,
+    }
+}
+
+fn ord_type_to_fix(order_type: OrderType) -> &'static str {
+    match order_type {
+        OrderType::Market => "1",
+        OrderType::Limit | OrderType::LimitMaker => "2",
+        OrderType::StopLoss | OrderType::TakeProfit => "3",
+        OrderType::StopLossLimit | OrderType::TakeProfitLimit => "4",
+    }
+}
+
+fn time_in_force_from_fix(value: &str) -> Option<TimeInForce> {
+    match value {
+        // Day orders have no session close in the simulator and behave as GTC
+        "0" | "1" => Some(TimeInForce::Gtc),
+        "3" => Some(TimeInForce::Ioc),
+        "4" => Some(TimeInForce::Fok),
+        "6" => Some(TimeInForce::Gtd),
+        _ => None,
+    }
+}
+
+fn time_in_force_to_fix(tif: TimeInForce) -> &'static str {
+    match tif {
+        TimeInForce::Gtc => "1",
+        TimeInForce::Ioc => "3",
+        TimeInForce::Fok => "4",
+        TimeInForce::Gtd => "6",
+    }
+}
+
+fn parse_quantity(msg: &FixMessage, tag: u32) -> Result<Quantity, FixFieldError> {
+    Quantity::parse(msg.require(tag)?).map_err(|_| FixFieldError::Invalid(tag))
+}
+
+fn parse_price(msg: &FixMessage, tag: u32) -> Result<Option<Price>, FixFieldError> {
+    msg.get(tag)
+        .map(|v| Price::parse(v).map_err(|_| FixFieldError::Invalid(tag)))
+        .transpose()
+}
+
+/// Build a submit command from a NewOrderSingle (or the new leg of a replace)
+pub fn parse_new_order(msg: &FixMessage) -> Result<SubmitOrderCommand, FixFieldError> {
+    let client_order_id = msg.require(tags::CL_ORD_ID)?.to_string();
+    let symbol = msg.require(tags::SYMBOL)?.to_string();
+    let side =
+        side_from_fix(msg.require(tags::SIDE)?).ok_or(FixFieldError::Unsupported(tags::SIDE))?;
+    let quantity = parse_quantity(msg, tags::ORDER_QTY)?;
+
+    // ExecInst '6' = Participate don't initiate (post-only)
+    let post_only = msg
+        .get(tags::EXEC_INST)
+        .is_some_and(|v| v.split(' ').any(|i| i == "6"));
+    let order_type = ord_type_from_fix(msg.require(tags::ORD_TYPE)?, post_only)
+        .ok_or(FixFieldError::Unsupported(tags::ORD_TYPE))?;
+
+    let time_in_force = msg
+        .get(tags::TIME_IN_FORCE)
+        .map(|v| time_in_force_from_fix(v).ok_or(FixFieldError::Unsupported(tags::TIME_IN_FORCE)))
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(SubmitOrderCommand {
+        symbol,
+        side,
+        order_type,
+        quantity,
+        price: parse_price(msg, tags::PRICE)?,
+        stop_price: parse_price(msg, tags::STOP_PX)?,
+        time_in_force,
+        client_order_id: Some(client_order_id),
+    })
+}
+
+/// Build a cancel command from an OrderCancelRequest / OrderCancelReplaceRequest
+///
+/// OrderID (37) takes precedence; otherwise the order is located by
+/// OrigClOrdID (41).
+pub fn parse_cancel(msg: &FixMessage) -> Result<CancelOrderCommand, FixFieldError> {
+    let symbol = msg.require(tags::SYMBOL)?.to_string();
+    let order_id = msg
+        .get(tags::ORDER_ID)
+        .map(|v| OrderId::parse_str(v).map_err(|_| FixFieldError::Invalid(tags::ORDER_ID)))
+        .transpose()?;
+    let orig_client_order_id = msg.require(tags::ORIG_CL_ORD_ID)?.to_string();
+
+    Ok(CancelOrderCommand {
+        symbol,
+        order_id,
+        client_order_id: Some(orig_client_order_id),
+    })
+}
+
+/// Whether a replace only lowers the quantity of a resting order
+///
+/// Such a replace can reduce the order in place and keep its queue
+/// priority; anything else is a cancel plus a new order.
+pub fn reduces_in_place(order: &Order, replacement: &SubmitOrderCommand) -> bool {
+    order.side == replacement.side
+        && order.order_type == replacement.order_type
+        && order.price == replacement.price
+        && order.stop_price == replacement.stop_price
+        && order.time_in_force == replacement.time_in_force
+        && replacement.quantity < order.quantity
+        && replacement.quantity > order.filled_quantity
+}
+
+/// Average price from cumulative notional and quantity
+pub fn average_price(notional: Value, cum_qty: Quantity) -> Price {
+    if cum_qty.is_zero() {
+        return Price::ZERO;
+    }
+    Price::from_raw((notional.raw() * QUANTITY_SCALE as i128 / cum_qty.raw() as i128) as i64)
+}
+
+/// Execution details that vary per report
+#[derive(Debug, Clone, Default)]
+pub struct ReportDetails<'a> {
+    /// LastPx/LastQty for trade reports
+    pub last_fill: Option<(Price, Quantity)>,
+    pub avg_px: Option<Price>,
+    /// ClOrdID of the request, when it differs from the order's (cancel/replace)
+    pub cl_ord_id: Option<&'a str>,
+    pub orig_cl_ord_id: Option<&'a str>,
+    pub text: Option<&'a str>,
+}
+
+/// Render an ExecutionReport for an order
+pub fn execution_report(
+    order: &Order,
+    exec_type: ExecType,
+    details: ReportDetails<'_>,
+    transact_time: Timestamp,
+) -> FixMessage {
+    let cl_ord_id = details
+        .cl_ord_id
+        .or(order.client_order_id.as_deref())
+        .unwrap_or("NONE");
+    let leaves_qty = if order.status.is_active() {
+        order.remaining_quantity()
+    } else {
+        Quantity::ZERO
+    };
+
+    let mut msg = FixMessage::new(msg_type::EXECUTION_REPORT)
+        .with(tags::ORDER_ID, order.id)
+        .with(tags::CL_ORD_ID, cl_ord_id);
+    if let Some(orig) = details.orig_cl_ord_id {
+        msg.push(tags::ORIG_CL_ORD_ID, orig);
+    }
+    msg.push(tags::EXEC_ID, uuid::Uuid::new_v4());
+    msg.push(tags::EXEC_TYPE, exec_type.as_fix());
+    msg.push(tags::ORD_STATUS, ord_status(order.status));
+    msg.push(tags::SYMBOL, &order.symbol);
+    msg.push(tags::SIDE, side_to_fix(order.side));
+    msg.push(tags::ORDER_QTY, order.quantity);
+    msg.push(tags::ORD_TYPE, ord_type_to_fix(order.order_type));
+    if let Some(price) = order
+        .price
+        .filter(|_| order.order_type != OrderType::Market)
+    {
+        msg.push(tags::PRICE, price);
+    }
+    if let Some(stop) = order.stop_price {
+        msg.push(tags::STOP_PX, stop);
+    }
+    msg.push(
+        tags::TIME_IN_FORCE,
+        time_in_force_to_fix(order.time_in_force),
+    );
+    if let Some((last_px, last_qty)) = details.last_fill {
+        msg.push(tags::LAST_PX, last_px);
+        msg.push(tags::LAST_QTY, last_qty);
+    }
+    msg.push(tags::LEAVES_QTY, leaves_qty);
+    msg.push(tags::CUM_QTY, order.filled_quantity);
+    msg.push(tags::AVG_PX, details.avg_px.unwrap_or(Price::ZERO));
+    msg.push(tags::TRANSACT_TIME, format_utc_timestamp(&transact_time));
+    if let Some(text) = details.text {
+        msg.push(tags::TEXT, text);
+    }
+    msg
+}
+
+/// ExecutionReport rejecting a NewOrderSingle that never reached the book
+pub fn reject_report(
+    request: &FixMessage,
+    reason: OrdRejReason,
+    text: &str,
+    transact_time: Timestamp,
+) -> FixMessage {
+    let mut msg = FixMessage::new(msg_type::EXECUTION_REPORT)
+        .with(tags::ORDER_ID, "NONE")
+        .with(
+            tags::CL_ORD_ID,
+            request.get(tags::CL_ORD_ID).unwrap_or("NONE"),
+        )
+        .with(tags::EXEC_ID, uuid::Uuid::new_v4())
+        .with(tags::EXEC_TYPE, ExecType::Rejected.as_fix())
+        .with(tags::ORD_STATUS, ord_status(OrderStatus::Rejected))
+        .with(tags::ORD_REJ_REASON, reason as u32);
+    for tag in [tags::SYMBOL, tags::SIDE, tags::ORDER_QTY, tags::ORD_TYPE] {
+        if let Some(value) = request.get(tag) {
+            msg.push(tag, value);
+        }
+    }
+    msg.push(tags::LEAVES_QTY, 0);
+    msg.push(tags::CUM_QTY, 0);
+    msg.push(tags::AVG_PX, 0);
+    msg.push(tags::TRANSACT_TIME, format_utc_timestamp(&transact_time));
+    msg.push(tags::TEXT, text);
+    msg
+}
+
+/// OrdRejReason (103) values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrdRejReason {
+    BrokerOption = 0,
+    UnknownSymbol = 1,
+    OrderExceedsLimit = 3,
+    Other = 99,
+}
+
+/// CxlRejResponseTo (434) values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CxlRejResponseTo {
+    Cancel = 1,
+    Replace = 2,
+}
+
+/// OrderCancelReject for a cancel or replace that could not be applied
+pub fn cancel_reject(
+    request: &FixMessage,
+    response_to: CxlRejResponseTo,
+    unknown_order: bool,
+    text: &str,
+) -> FixMessage {
+    FixMessage::new(msg_type::ORDER_CANCEL_REJECT)
+        .with(
+            tags::ORDER_ID,
+            request.get(tags::ORDER_ID).unwrap_or("NONE"),
+        )
+        .with(
+            tags::CL_ORD_ID,
+            request.get(tags::CL_ORD_ID).unwrap_or("NONE"),
+        )
+        .with(
+            tags::ORIG_CL_ORD_ID,
+            request.get(tags::ORIG_CL_ORD_ID).unwrap_or("NONE"),
+        )
+        .with(
+            tags::ORD_STATUS,
+            ord_status(if unknown_order {
+                OrderStatus::Rejected
+            } else {
+                OrderStatus::New
+            }),
+        )
+        .with(tags::CXL_REJ_RESPONSE_TO, response_to as u32)
+        .with(tags::CXL_REJ_REASON, if unknown_order { 1 } else { 99 })
+        .with(tags::TEXT, text)
+}
+
+// ============================================================================
+// Open order tracking
+// ============================================================================
+
+/// Resting order owned by a FIX session
+#[derive(Debug, Clone)]
+pub struct TrackedOrder {
+    pub order: Order,
+    /// Cumulative filled notional, for AvgPx
+    pub notional: Value,
+}
+
+/// Orders a session has resting in the book
+///
+/// Passive fills arrive as `TradeExecuted` events keyed by order ID; this
+/// maps them back to the session's orders so ExecutionReports can be sent.
+#[derive(Debug, Default)]
+pub struct OpenOrders {
+    orders: HashMap<OrderId, TrackedOrder>,
+}
+
+impl OpenOrders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, order: Order, notional: Value) {
+        self.orders
+            .insert(order.id, TrackedOrder { order, notional });
+    }
+
+    pub fn remove(&mut self, order_id: &OrderId) -> Option<TrackedOrder> {
+        self.orders.remove(order_id)
+    }
+
+    /// Find a tracked order by OrderID, else by ClOrdID
+    pub fn find(
+        &self,
+        order_id: Option<OrderId>,
+        cl_ord_id: Option<&str>,
+    ) -> Option<&TrackedOrder> {
+        match order_id {
+            Some(id) => self.orders.get(&id),
+            None => self
+                .orders
+                .values()
+                .find(|t| cl_ord_id.is_some() && t.order.client_order_id.as_deref() == cl_ord_id),
+        }
+    }
+
+    /// Apply a passive fill and return the updated order and its average price
+    ///
+    /// Fully filled orders are removed from tracking.
+    pub fn apply_fill(
+        &mut self,
+        order_id: &OrderId,
+        price: Price,
+        quantity: Quantity,
+        now: Timestamp,
+    ) -> Option<(Order, Price)> {
+        let tracked = self.orders.get_mut(order_id)?;
+        tracked.order.fill(quantity, now);
+        tracked.notional = tracked.notional + price.mul_qty(quantity);
+        let avg_px = average_price(tracked.notional, tracked.order.filled_quantity);
+        let order = tracked.order.clone();
+        if order.status == OrderStatus::Filled {
+            self.orders.remove(order_id);
+        }
+        Some((order, avg_px))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Symbol;
+
+    fn new_order_single() -> FixMessage {
+        FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+            .with(tags::CL_ORD_ID, "c1")
+            .with(tags::SYMBOL, "BTCUSDT")
+            .with(tags::SIDE, "1")
+            .with(tags::ORDER_QTY, "1.5")
+            .with(tags::ORD_TYPE, "2")
+            .with(tags::PRICE, "50000")
+            .with(tags::TIME_IN_FORCE, "3")
+    }
+
+    #[test]
+    fn test_parse_new_order() {
+        let cmd = parse_new_order(&new_order_single()).unwrap();
+
+        assert_eq!(cmd.symbol, "BTCUSDT");
+        assert_eq!(cmd.side, Side::Buy);
+        assert_eq!(cmd.order_type, OrderType::Limit);
+        assert_eq!(cmd.quantity, Quantity::parse("1.5").unwrap());
+        assert_eq!(cmd.price, Some(Price::from_int(50000)));
+        assert_eq!(cmd.time_in_force, TimeInForce::Ioc);
+        assert_eq!(cmd.client_order_id.as_deref(), Some("c1"));
+    }
+
+    #[test]
+    fn test_parse_new_order_post_only_and_errors() {
+        let post_only = new_order_single().with(tags::EXEC_INST, "6");
+        assert_eq!(
+            parse_new_order(&post_only).unwrap().order_type,
+            OrderType::LimitMaker
+        );
+
+        let mut bad_side = new_order_single();
+        bad_side.set(tags::SIDE, "9");
+        assert_eq!(
+            parse_new_order(&bad_side).unwrap_err(),
+            FixFieldError::Unsupported(tags::SIDE)
+        );
+
+        let missing_qty = FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+            .with(tags::CL_ORD_ID, "c1")
+            .with(tags::SYMBOL, "BTCUSDT")
+            .with(tags::SIDE, "1");
+        assert_eq!(
+            parse_new_order(&missing_qty).unwrap_err(),
+            FixFieldError::Missing(tags::ORDER_QTY)
+        );
+    }
+
+    #[test]
+    fn test_execution_report_fields() {
+        let mut order = Order::new_limit(
+            Symbol::new("BTCUSDT").unwrap(),
+            Side::Sell,
+            Quantity::from_int(2),
+            Price::from_int(100),
+            TimeInForce::Gtc,
+        )
+        .with_client_order_id("c9");
+        order.fill(Quantity::from_int(1), chrono::Utc::now());
+
+        let report = execution_report(
+            &order,
+            ExecType::Trade,
+            ReportDetails {
+                last_fill: Some((Price::from_int(100), Quantity::from_int(1))),
+                avg_px: Some(Price::from_int(100)),
+                ..Default::default()
+            },
+            chrono::Utc::now(),
+        );
+
+        assert_eq!(report.get(tags::CL_ORD_ID), Some("c9"));
+        assert_eq!(report.get(tags::EXEC_TYPE), Some("F"));
+        assert_eq!(report.get(tags::ORD_STATUS), Some("1"));
+        assert_eq!(report.get(tags::SIDE), Some("2"));
+        assert_eq!(
+            report.get(tags::LEAVES_QTY),
+            Some(Quantity::from_int(1).to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn test_open_orders_apply_fill_tracks_avg_price() {
+        let order = Order::new_limit(
+            Symbol::new("BTCUSDT").unwrap(),
+            Side::Buy,
+            Quantity::from_int(2),
+            Price::from_int(100),
+            TimeInForce::Gtc,
+        );
+        let id = order.id;
+        let now = chrono::Utc::now();
+        let mut open = OpenOrders::new();
+        open.insert(order, Value::ZERO);
+
+        let (partial, avg) = open
+            .apply_fill(&id, Price::from_int(100), Quantity::from_int(1), now)
+            .unwrap();
+        assert_eq!(partial.status, OrderStatus::PartiallyFilled);
+        assert_eq!(avg, Price::from_int(100));
+
+        let (filled, avg) = open
+            .apply_fill(&id, Price::from_int(98), Quantity::from_int(1), now)
+            .unwrap();
+        assert_eq!(filled.status, OrderStatus::Filled);
+        assert_eq!(avg, Price::from_int(99));
+        assert!(open.remove(&id).is_none());
+    }
+}
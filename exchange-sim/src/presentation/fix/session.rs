@@ -0,0 +1,337 @@
+//! FIX session layer: sequencing, persistence and resend
+//!
+//! [`FixSession`] holds the per-counterparty protocol state independent of the
+//! socket, so sequence handling and gap fill can be exercised without I/O.
+
+use std::io;
+use std::sync::Arc;
+
+use super::message::{FixHeader, FixMessage, format_utc_timestamp, msg_type, tags};
+use super::store::{SessionId, SessionSeqNums, SessionStore};
+use crate::domain::Timestamp;
+
+/// Outcome of checking an inbound MsgSeqNum against the expected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeqCheck {
+    /// Sequence number is the one expected
+    InOrder,
+    /// Messages are missing; a ResendRequest should be issued
+    Gap { expected: u64, received: u64 },
+    /// A duplicate flagged with PossDupFlag; safe to ignore
+    Duplicate,
+    /// Lower than expected without PossDupFlag; the session must be terminated
+    TooLow { expected: u64, received: u64 },
+}
+
+/// Protocol state for one logged-on counterparty
+pub struct FixSession {
+    id: SessionId,
+    store: Arc<dyn SessionStore>,
+    seq_nums: SessionSeqNums,
+    /// Highest sequence number covered by an outstanding ResendRequest
+    resend_requested_through: Option<u64>,
+}
+
+impl FixSession {
+    /// Resume a session from the store
+    pub fn open(id: SessionId, store: Arc<dyn SessionStore>) -> io::Result<Self> {
+        let seq_nums = store.seq_nums(&id)?;
+        Ok(FixSession {
+            id,
+            store,
+            seq_nums,
+            resend_requested_through: None,
+        })
+    }
+
+    pub fn id(&self) -> &SessionId {
+        &self.id
+    }
+
+    pub fn seq_nums(&self) -> SessionSeqNums {
+        self.seq_nums
+    }
+
+    /// Reset both directions to 1 and drop the message log (ResetSeqNumFlag)
+    pub fn reset(&mut self) -> io::Result<()> {
+        self.store.reset(&self.id)?;
+        self.seq_nums = SessionSeqNums::default();
+        self.resend_requested_through = None;
+        self.store.set_seq_nums(&self.id, self.seq_nums)
+    }
+
+    /// Assign the next outbound sequence number, persist and encode
+    ///
+    /// Application messages are kept in the store so they can be resent.
+    pub fn encode_outbound(&mut self, message: &FixMessage, now: Timestamp) -> io::Result<Vec<u8>> {
+        let seq_num = self.seq_nums.next_sender_seq;
+        let sending_time = format_utc_timestamp(&now);
+        let bytes = message.encode(&FixHeader {
+            sender_comp_id: &self.id.sender_comp_id,
+            target_comp_id: &self.id.target_comp_id,
+            seq_num,
+            sending_time: &sending_time,
+            poss_dup: false,
+        });
+
+        if !msg_type::is_admin(message.msg_type()) {
+            self.store.store_message(&self.id, seq_num, &bytes)?;
+        }
+        self.seq_nums.next_sender_seq += 1;
+        self.store.set_seq_nums(&self.id, self.seq_nums)?;
+        Ok(bytes)
+    }
+
+    /// Compare an inbound MsgSeqNum with the expected value
+    pub fn check_inbound(&self, message: &FixMessage, seq_num: u64) -> SeqCheck {
+        let expected = self.seq_nums.next_target_seq;
+        if seq_num == expected {
+            SeqCheck::InOrder
+        } else if seq_num > expected {
+            SeqCheck::Gap {
+                expected,
+                received: seq_num,
+            }
+        } else if message.get_bool(tags::POSS_DUP_FLAG) {
+            SeqCheck::Duplicate
+        } else {
+            SeqCheck::TooLow {
+                expected,
+                received: seq_num,
+            }
+        }
+    }
+
+    /// Mark an in-order inbound message as consumed
+    pub fn accept_inbound(&mut self, seq_num: u64) -> io::Result<()> {
+        self.seq_nums.next_target_seq = seq_num + 1;
+        if self
+            .resend_requested_through
+            .is_some_and(|through| seq_num >= through)
+        {
+            self.resend_requested_through = None;
+        }
+        self.store.set_seq_nums(&self.id, self.seq_nums)
+    }
+
+    /// Move the inbound sequence forward (SequenceReset)
+    ///
+    /// Returns false if `new_seq_no` would move the sequence backwards.
+    pub fn reset_inbound(&mut self, new_seq_no: u64) -> io::Result<bool> {
+        if new_seq_no < self.seq_nums.next_target_seq {
+            return Ok(false);
+        }
+        self.seq_nums.next_target_seq = new_seq_no;
+        self.store.set_seq_nums(&self.id, self.seq_nums)?;
+        Ok(true)
+    }
+
+    /// Build a ResendRequest for a detected gap, unless one is outstanding
+    ///
+    /// The request is open-ended (EndSeqNo=0), so it also covers the message
+    /// that revealed the gap, which is dropped rather than queued.
+    pub fn resend_request_for_gap(&mut self, expected: u64, received: u64) -> Option<FixMessage> {
+        if self.resend_requested_through.is_some() {
+            return None;
+        }
+        self.resend_requested_through = Some(received);
+        Some(
+            FixMessage::new(msg_type::RESEND_REQUEST)
+                .with(tags::BEGIN_SEQ_NO, expected)
+                .with(tags::END_SEQ_NO, 0),
+        )
+    }
+
+    /// Encode the replay for a counterparty ResendRequest
+    ///
+    /// Stored application messages are re-sent with PossDupFlag and their
+    /// original SendingTime in OrigSendingTime. Sequence ranges with nothing
+    /// to replay (administrative traffic) are covered by SequenceReset-GapFill.
+    /// These frames reuse their original sequence numbers and do not consume
+    /// new ones.
+    pub fn replay(&self, begin: u64, end: u64, now: Timestamp) -> io::Result<Vec<Vec<u8>>> {
+        let last_sent = self.seq_nums.next_sender_seq.saturating_sub(1);
+        let end = if end == 0 || end > last_sent {
+            last_sent
+        } else {
+            end
+        };
+        let begin = begin.max(1);
+        if begin > end {
+            return Ok(Vec::new());
+        }
+
+        let sending_time = format_utc_timestamp(&now);
+        let header = |seq_num| FixHeader {
+            sender_comp_id: &self.id.sender_comp_id,
+            target_comp_id: &self.id.target_comp_id,
+            seq_num,
+            sending_time: &sending_time,
+            poss_dup: true,
+        };
+
+        let mut frames = Vec::new();
+        let mut gap_start: Option<u64> = None;
+        let stored = self.store.messages(&self.id, begin, end)?;
+        let mut stored = stored.into_iter().peekable();
+
+        for seq_num in begin..=end {
+            let original = match stored.peek() {
+                Some((s, _)) if *s == seq_num => stored.next().map(|(_, bytes)| bytes),
+                _ => None,
+            };
+            let Some(bytes) = original.and_then(|b| FixMessage::decode(&b).ok()) else {
+                gap_start.get_or_insert(seq_num);
+                continue;
+            };
+
+            if let Some(start) = gap_start.take() {
+                frames.push(gap_fill(start, seq_num).encode(&header(start)));
+            }
+
+            let mut message = bytes;
+            if let Some(original_time) = message.get(tags::SENDING_TIME).map(str::to_string) {
+                message.set(tags::ORIG_SENDING_TIME, original_time);
+            }
+            frames.push(message.encode(&header(seq_num)));
+        }
+
+        if let Some(start) = gap_start {
+            frames.push(gap_fill(start, end + 1).encode(&header(start)));
+        }
+
+        Ok(frames)
+    }
+}
+
+fn gap_fill(seq_num: u64, new_seq_no: u64) -> FixMessage {
+    debug_assert!(new_seq_no > seq_num);
+    FixMessage::new(msg_type::SEQUENCE_RESET)
+        .with(tags::GAP_FILL_FLAG, "Y")
+        .with(tags::NEW_SEQ_NO, new_seq_no)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::fix::store::InMemorySessionStore;
+
+    fn session() -> FixSession {
+        FixSession::open(
+            SessionId::new("EXCH", "CLIENT"),
+            Arc::new(InMemorySessionStore::new()),
+        )
+        .unwrap()
+    }
+
+    fn decode(bytes: &[u8]) -> FixMessage {
+        FixMessage::decode(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_outbound_sequence_increments() {
+        let mut session = session();
+        let now = chrono::Utc::now();
+
+        let first = decode(
+            &session
+                .encode_outbound(&FixMessage::new(msg_type::HEARTBEAT), now)
+                .unwrap(),
+        );
+        let second = decode(
+            &session
+                .encode_outbound(&FixMessage::new(msg_type::EXECUTION_REPORT), now)
+                .unwrap(),
+        );
+
+        assert_eq!(first.seq_num().unwrap(), 1);
+        assert_eq!(second.seq_num().unwrap(), 2);
+        assert_eq!(session.seq_nums().next_sender_seq, 3);
+    }
+
+    #[test]
+    fn test_inbound_sequence_checks() {
+        let mut session = session();
+        let msg = FixMessage::new(msg_type::HEARTBEAT);
+
+        assert_eq!(session.check_inbound(&msg, 1), SeqCheck::InOrder);
+        session.accept_inbound(1).unwrap();
+        assert_eq!(
+            session.check_inbound(&msg, 5),
+            SeqCheck::Gap {
+                expected: 2,
+                received: 5
+            }
+        );
+        assert_eq!(
+            session.check_inbound(&msg, 1),
+            SeqCheck::TooLow {
+                expected: 2,
+                received: 1
+            }
+        );
+
+        let dup = FixMessage::new(msg_type::HEARTBEAT).with(tags::POSS_DUP_FLAG, "Y");
+        assert_eq!(session.check_inbound(&dup, 1), SeqCheck::Duplicate);
+    }
+
+    #[test]
+    fn test_resend_request_not_repeated_for_same_gap() {
+        let mut session = session();
+
+        assert!(session.resend_request_for_gap(1, 5).is_some());
+        assert!(session.resend_request_for_gap(1, 6).is_none());
+
+        session.accept_inbound(5).unwrap();
+        assert!(session.resend_request_for_gap(7, 9).is_some());
+    }
+
+    #[test]
+    fn test_replay_gap_fills_admin_messages() {
+        let mut session = session();
+        let now = chrono::Utc::now();
+
+        // 1: admin, 2: app, 3: admin, 4: admin, 5: app
+        for msg_type in [
+            msg_type::HEARTBEAT,
+            msg_type::EXECUTION_REPORT,
+            msg_type::HEARTBEAT,
+            msg_type::HEARTBEAT,
+            msg_type::EXECUTION_REPORT,
+        ] {
+            session
+                .encode_outbound(&FixMessage::new(msg_type), now)
+                .unwrap();
+        }
+
+        let frames: Vec<FixMessage> = session
+            .replay(1, 0, now)
+            .unwrap()
+            .iter()
+            .map(|f| decode(f))
+            .collect();
+
+        let summary: Vec<(String, u64, Option<&str>)> = frames
+            .iter()
+            .map(|m| {
+                (
+                    m.msg_type().to_string(),
+                    m.seq_num().unwrap(),
+                    m.get(tags::NEW_SEQ_NO),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("4".to_string(), 1, Some("2")),
+                ("8".to_string(), 2, None),
+                ("4".to_string(), 3, Some("5")),
+                ("8".to_string(), 5, None),
+            ]
+        );
+        assert!(frames.iter().all(|m| m.get_bool(tags::POSS_DUP_FLAG)));
+        assert!(frames[1].get(tags::ORIG_SENDING_TIME).is_some());
+        assert_eq!(session.seq_nums().next_sender_seq, 6);
+    }
+}
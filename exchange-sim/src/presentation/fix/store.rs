@@ -0,0 +1,368 @@
+//! FIX session persistence
+//!
+//! Sequence numbers and sent application messages must outlive a single TCP
+//! connection so a reconnecting client can resume its session and request
+//! resends. [`InMemorySessionStore`] keeps state for the lifetime of the
+//! process; [`FileSessionStore`] also writes it to disk so sessions survive a
+//! simulator restart.
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Identifies a session from the acceptor's point of view
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId {
+    /// Our CompID (SenderCompID on outbound messages)
+    pub sender_comp_id: String,
+    /// Counterparty CompID (TargetCompID on outbound messages)
+    pub target_comp_id: String,
+}
+
+impl SessionId {
+    pub fn new(sender_comp_id: impl Into<String>, target_comp_id: impl Into<String>) -> Self {
+        SessionId {
+            sender_comp_id: sender_comp_id.into(),
+            target_comp_id: target_comp_id.into(),
+        }
+    }
+
+    fn file_stem(&self) -> String {
+        format!("{}-{}", self.sender_comp_id, self.target_comp_id)
+    }
+}
+
+impl std::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}->{}", self.sender_comp_id, self.target_comp_id)
+    }
+}
+
+/// Next expected sequence numbers for both directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionSeqNums {
+    /// Sequence number of the next message we send
+    pub next_sender_seq: u64,
+    /// Sequence number we expect on the next inbound message
+    pub next_target_seq: u64,
+}
+
+impl Default for SessionSeqNums {
+    fn default() -> Self {
+        SessionSeqNums {
+            next_sender_seq: 1,
+            next_target_seq: 1,
+        }
+    }
+}
+
+/// Persistence for session sequence numbers and the outbound message log
+pub trait SessionStore: Send + Sync {
+    /// Current sequence numbers (defaults to 1/1 for an unknown session)
+    fn seq_nums(&self, session: &SessionId) -> io::Result<SessionSeqNums>;
+
+    /// Persist sequence numbers
+    fn set_seq_nums(&self, session: &SessionId, seq_nums: SessionSeqNums) -> io::Result<()>;
+
+    /// Record an encoded outbound message for later resend
+    fn store_message(&self, session: &SessionId, seq_num: u64, message: &[u8]) -> io::Result<()>;
+
+    /// Stored outbound messages with `begin <= seq_num <= end`, in order
+    fn messages(
+        &self,
+        session: &SessionId,
+        begin: u64,
+        end: u64,
+    ) -> io::Result<Vec<(u64, Vec<u8>)>>;
+
+    /// Drop all state for a session (sequence reset)
+    fn reset(&self, session: &SessionId) -> io::Result<()>;
+}
+
+// ============================================================================
+// In-memory store
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct SessionRecord {
+    seq_nums: SessionSeqNums,
+    messages: BTreeMap<u64, Vec<u8>>,
+}
+
+/// Process-lifetime session store
+#[derive(Debug, Default, Clone)]
+pub struct InMemorySessionStore {
+    sessions: Arc<DashMap<SessionId, SessionRecord>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn seq_nums(&self, session: &SessionId) -> io::Result<SessionSeqNums> {
+        Ok(self
+            .sessions
+            .get(session)
+            .map(|r| r.seq_nums)
+            .unwrap_or_default())
+    }
+
+    fn set_seq_nums(&self, session: &SessionId, seq_nums: SessionSeqNums) -> io::Result<()> {
+        self.sessions.entry(session.clone()).or_default().seq_nums = seq_nums;
+        Ok(())
+    }
+
+    fn store_message(&self, session: &SessionId, seq_num: u64, message: &[u8]) -> io::Result<()> {
+        self.sessions
+            .entry(session.clone())
+            .or_default()
+            .messages
+            .insert(seq_num, message.to_vec());
+        Ok(())
+    }
+
+    fn messages(
+        &self,
+        session: &SessionId,
+        begin: u64,
+        end: u64,
+    ) -> io::Result<Vec<(u64, Vec<u8>)>> {
+        Ok(self
+            .sessions
+            .get(session)
+            .map(|r| {
+                r.messages
+                    .range(begin..=end)
+                    .map(|(seq, msg)| (*seq, msg.clone()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn reset(&self, session: &SessionId) -> io::Result<()> {
+        self.sessions.remove(session);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// File-backed store
+// ============================================================================
+
+/// Session store persisted under a directory
+///
+/// Each session uses two files named after `SENDER-TARGET`:
+/// - `.seqnums`: `next_sender_seq next_target_seq`
+/// - `.body`: append-only log of `seq_num length\n<message>\n` records
+///
+/// State is cached in memory and loaded lazily on first access.
+pub struct FileSessionStore {
+    dir: PathBuf,
+    cache: InMemorySessionStore,
+    loaded: DashMap<SessionId, ()>,
+    write_lock: Mutex<()>,
+}
+
+impl FileSessionStore {
+    /// Open (creating if needed) a store directory
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(FileSessionStore {
+            dir: dir.as_ref().to_path_buf(),
+            cache: InMemorySessionStore::new(),
+            loaded: DashMap::new(),
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    fn seqnums_path(&self, session: &SessionId) -> PathBuf {
+        self.dir.join(format!("{}.seqnums", session.file_stem()))
+    }
+
+    fn body_path(&self, session: &SessionId) -> PathBuf {
+        self.dir.join(format!("{}.body", session.file_stem()))
+    }
+
+    fn ensure_loaded(&self, session: &SessionId) -> io::Result<()> {
+        if self.loaded.contains_key(session) {
+            return Ok(());
+        }
+        let _guard = self.write_lock.lock();
+        if self.loaded.contains_key(session) {
+            return Ok(());
+        }
+
+        if let Ok(content) = fs::read_to_string(self.seqnums_path(session)) {
+            let mut parts = content.split_whitespace().map(str::parse::<u64>);
+            if let (Some(Ok(next_sender_seq)), Some(Ok(next_target_seq))) =
+                (parts.next(), parts.next())
+            {
+                self.cache.set_seq_nums(
+                    session,
+                    SessionSeqNums {
+                        next_sender_seq,
+                        next_target_seq,
+                    },
+                )?;
+            }
+        }
+
+        if let Ok(file) = File::open(self.body_path(session)) {
+            let mut reader = BufReader::new(file);
+            let mut header = String::new();
+            while reader.read_line(&mut header)? > 0 {
+                let mut parts = header.split_whitespace().map(str::parse::<usize>);
+                let (Some(Ok(seq_num)), Some(Ok(len))) = (parts.next(), parts.next()) else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("corrupt FIX message log for session {}", session),
+                    ));
+                };
+                let mut message = vec![0u8; len + 1];
+                reader.read_exact(&mut message)?;
+                message.truncate(len);
+                self.cache
+                    .store_message(session, seq_num as u64, &message)?;
+                header.clear();
+            }
+        }
+
+        self.loaded.insert(session.clone(), ());
+        Ok(())
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn seq_nums(&self, session: &SessionId) -> io::Result<SessionSeqNums> {
+        self.ensure_loaded(session)?;
+        self.cache.seq_nums(session)
+    }
+
+    fn set_seq_nums(&self, session: &SessionId, seq_nums: SessionSeqNums) -> io::Result<()> {
+        self.ensure_loaded(session)?;
+        let _guard = self.write_lock.lock();
+        fs::write(
+            self.seqnums_path(session),
+            format!(
+                "{} {}\n",
+                seq_nums.next_sender_seq, seq_nums.next_target_seq
+            ),
+        )?;
+        self.cache.set_seq_nums(session, seq_nums)
+    }
+
+    fn store_message(&self, session: &SessionId, seq_num: u64, message: &[u8]) -> io::Result<()> {
+        self.ensure_loaded(session)?;
+        let _guard = self.write_lock.lock();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.body_path(session))?;
+        writeln!(file, "{} {}", seq_num, message.len())?;
+        file.write_all(message)?;
+        file.write_all(b"\n")?;
+        self.cache.store_message(session, seq_num, message)
+    }
+
+    fn messages(
+        &self,
+        session: &SessionId,
+        begin: u64,
+        end: u64,
+    ) -> io::Result<Vec<(u64, Vec<u8>)>> {
+        self.ensure_loaded(session)?;
+        self.cache.messages(session, begin, end)
+    }
+
+    fn reset(&self, session: &SessionId) -> io::Result<()> {
+        let _guard = self.write_lock.lock();
+        for path in [self.seqnums_path(session), self.body_path(session)] {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        self.cache.reset(session)?;
+        self.loaded.insert(session.clone(), ());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> SessionId {
+        SessionId::new("EXCH", "CLIENT")
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fix-store-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_in_memory_defaults_and_range() {
+        let store = InMemorySessionStore::new();
+        assert_eq!(
+            store.seq_nums(&session()).unwrap(),
+            SessionSeqNums::default()
+        );
+
+        for seq in 1..=5 {
+            store
+                .store_message(&session(), seq, format!("msg{}", seq).as_bytes())
+                .unwrap();
+        }
+
+        let range = store.messages(&session(), 2, 4).unwrap();
+        assert_eq!(
+            range.iter().map(|(s, _)| *s).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_file_store_survives_reopen() {
+        let dir = temp_dir("reopen");
+        {
+            let store = FileSessionStore::open(&dir).unwrap();
+            store
+                .set_seq_nums(
+                    &session(),
+                    SessionSeqNums {
+                        next_sender_seq: 12,
+                        next_target_seq: 7,
+                    },
+                )
+                .unwrap();
+            store
+                .store_message(&session(), 11, b"8=FIX.4.4\x0135=8\x01")
+                .unwrap();
+        }
+
+        let store = FileSessionStore::open(&dir).unwrap();
+        let seq_nums = store.seq_nums(&session()).unwrap();
+        assert_eq!(seq_nums.next_sender_seq, 12);
+        assert_eq!(seq_nums.next_target_seq, 7);
+        assert_eq!(
+            store.messages(&session(), 1, 20).unwrap(),
+            vec![(11, b"8=FIX.4.4\x0135=8\x01".to_vec())]
+        );
+
+        store.reset(&session()).unwrap();
+        assert_eq!(
+            store.seq_nums(&session()).unwrap(),
+            SessionSeqNums::default()
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
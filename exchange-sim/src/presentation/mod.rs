@@ -1,5 +1,8 @@
+pub mod fix;
+mod option_mark;
 pub mod rest;
 pub mod websocket;
 
+pub use option_mark::FormattedOptionMark;
 pub use rest::{ApiError, AppState, create_router};
 pub use websocket::{StreamManager, WsState, ws_handler};
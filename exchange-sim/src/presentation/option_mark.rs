@@ -0,0 +1,43 @@
+//! Wire formatting of option marks, shared by the REST mark endpoint and
+//! the mark price stream so both report the same precision.
+
+use crate::application::OptionMark;
+
+/// An option mark with every number formatted as sent to clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedOptionMark {
+    pub symbol: String,
+    pub mark_price: String,
+    pub index_price: String,
+    /// Empty when the book has no bid
+    pub bid_iv: String,
+    /// Empty when the book has no ask
+    pub ask_iv: String,
+    pub mark_iv: String,
+    pub delta: String,
+    pub gamma: String,
+    pub vega: String,
+    pub theta: String,
+    pub rho: String,
+    pub risk_free_rate: String,
+}
+
+impl From<&OptionMark> for FormattedOptionMark {
+    fn from(mark: &OptionMark) -> Self {
+        let iv = |v: Option<f64>| v.map(|v| format!("{v:.4}")).unwrap_or_default();
+        Self {
+            symbol: mark.symbol.to_string(),
+            mark_price: mark.mark_price.to_string(),
+            index_price: mark.index_price.to_string(),
+            bid_iv: iv(mark.bid_iv),
+            ask_iv: iv(mark.ask_iv),
+            mark_iv: format!("{:.4}", mark.mark_iv),
+            delta: format!("{:.6}", mark.greeks.delta),
+            gamma: format!("{:.8}", mark.greeks.gamma),
+            vega: format!("{:.6}", mark.greeks.vega),
+            theta: format!("{:.6}", mark.greeks.theta),
+            rho: format!("{:.6}", mark.greeks.rho),
+            risk_free_rate: format!("{:.4}", mark.risk_free_rate),
+        }
+    }
+}
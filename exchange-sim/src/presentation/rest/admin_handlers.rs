@@ -12,7 +12,9 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::application::ports::AccountRepository;
-use crate::domain::{Clock, FeeSchedule, Price, Quantity, Symbol, TradingPairConfig, Value};
+use crate::domain::{
+    Clock, FeeSchedule, MarginMode, Price, Quantity, Symbol, TradingPairConfig, Value,
+};
 use crate::presentation::rest::router::AppState;
 
 // ============================================================================
@@ -40,6 +42,7 @@ pub struct AccountResponse {
     pub owner_id: String,
     pub balances: Vec<BalanceResponse>,
     pub fee_tier: u8,
+    pub margin_mode: MarginMode,
 }
 
 #[derive(Debug, Serialize)]
@@ -120,6 +123,7 @@ pub async fn create_account<C: Clock>(
         owner_id: account.owner_id.clone(),
         balances,
         fee_tier: account.fee_schedule.tier,
+        margin_mode: account.margin_mode,
     };
 
     state.account_repo.save(account).await;
@@ -180,6 +184,7 @@ pub async fn get_account<C: Clock>(
         owner_id: account.owner_id.clone(),
         balances,
         fee_tier: account.fee_schedule.tier,
+        margin_mode: account.margin_mode,
     }))
 }
 
@@ -198,6 +203,21 @@ pub async fn set_fee_tier<C: Clock>(
     Ok(StatusCode::OK)
 }
 
+/// PUT /admin/accounts/{owner_id}/margin-mode - Select Cross, Isolated or Portfolio margin
+pub async fn set_margin_mode<C: Clock>(
+    State(state): State<Arc<AppState<C>>>,
+    Path(owner_id): Path<String>,
+    Json(mode): Json<MarginMode>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let mut account = state.account_repo.get_or_create(&owner_id).await;
+
+    account.margin_mode = mode;
+
+    state.account_repo.save(account).await;
+
+    Ok(StatusCode::OK)
+}
+
 // ============================================================================
 // Market Handlers
 // ============================================================================
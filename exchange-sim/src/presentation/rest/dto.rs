@@ -1,4 +1,6 @@
+use crate::application::OptionMark;
 use crate::domain::{Order, Price, Quantity, Value};
+use crate::presentation::FormattedOptionMark;
 use serde::{Deserialize, Serialize};
 
 /// Request to create a new order (Binance-compatible)
@@ -125,6 +127,29 @@ pub struct CancelOrderResponse {
     pub side: String,
 }
 
+/// Amend order (keep priority) request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendOrderRequest {
+    pub symbol: String,
+    #[serde(default)]
+    pub order_id: Option<i64>,
+    #[serde(default)]
+    pub orig_client_order_id: Option<String>,
+    #[serde(default)]
+    pub new_client_order_id: Option<String>,
+    pub new_qty: String,
+}
+
+/// Amend order response
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendOrderResponse {
+    pub transact_time: i64,
+    /// The order after the amend, in the cancel response's shape
+    pub amended_order: CancelOrderResponse,
+}
+
 /// Depth request query params
 #[derive(Debug, Clone, Deserialize)]
 pub struct DepthQuery {
@@ -146,6 +171,159 @@ pub struct DepthResponse {
     pub asks: Vec<[String; 2]>,
 }
 
+/// Market-by-order snapshot request query params
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarketByOrderQuery {
+    pub symbol: String,
+}
+
+/// Market-by-order snapshot response
+///
+/// Orders are `[orderRef, price, quantity]` in queue order, best price first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketByOrderResponse {
+    pub last_update_id: u64,
+    pub bids: Vec<(u64, String, String)>,
+    pub asks: Vec<(u64, String, String)>,
+}
+
+/// Option mark request query params (all options when `symbol` is omitted)
+#[derive(Debug, Clone, Deserialize)]
+pub struct OptionMarkQueryParams {
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+/// Option mark price, implied vols and Greeks (Binance options-compatible)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionMarkResponse {
+    pub symbol: String,
+    pub mark_price: String,
+    #[serde(rename = "bidIV")]
+    pub bid_iv: String,
+    #[serde(rename = "askIV")]
+    pub ask_iv: String,
+    #[serde(rename = "markIV")]
+    pub mark_iv: String,
+    pub delta: String,
+    pub theta: String,
+    pub gamma: String,
+    pub vega: String,
+    pub rho: String,
+    pub index_price: String,
+    pub risk_free_interest: String,
+}
+
+impl From<&OptionMark> for OptionMarkResponse {
+    fn from(mark: &OptionMark) -> Self {
+        let m = FormattedOptionMark::from(mark);
+        Self {
+            symbol: m.symbol,
+            mark_price: m.mark_price,
+            bid_iv: m.bid_iv,
+            ask_iv: m.ask_iv,
+            mark_iv: m.mark_iv,
+            delta: m.delta,
+            theta: m.theta,
+            gamma: m.gamma,
+            vega: m.vega,
+            rho: m.rho,
+            index_price: m.index_price,
+            risk_free_interest: m.risk_free_rate,
+        }
+    }
+}
+
+/// Margin borrow/repay request (`isIsolated` is "TRUE" or "FALSE")
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginLoanRequest {
+    pub asset: String,
+    pub amount: String,
+    #[serde(default)]
+    pub is_isolated: Option<String>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+/// Isolated margin transfer request
+///
+/// `transFrom`/`transTo` are "SPOT" or "ISOLATED_MARGIN".
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedTransferRequest {
+    pub asset: String,
+    pub symbol: String,
+    pub trans_from: String,
+    pub trans_to: String,
+    pub amount: String,
+}
+
+/// Margin transaction response
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTransactionResponse {
+    pub tran_id: u64,
+}
+
+/// One asset of a margin wallet
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAssetResponse {
+    pub asset: String,
+    pub borrowed: String,
+    pub free: String,
+    pub interest: String,
+    pub locked: String,
+    pub net_asset: String,
+}
+
+/// Cross margin account response (Binance-compatible)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAccountResponse {
+    pub borrow_enabled: bool,
+    pub margin_level: String,
+    pub total_asset_of_btc: String,
+    pub total_liability_of_btc: String,
+    pub total_net_asset_of_btc: String,
+    pub trade_enabled: bool,
+    pub transfer_enabled: bool,
+    pub user_assets: Vec<MarginAssetResponse>,
+}
+
+/// Isolated margin account query params (`symbols` is comma-separated)
+#[derive(Debug, Clone, Deserialize)]
+pub struct IsolatedMarginAccountQuery {
+    #[serde(default)]
+    pub symbols: Option<String>,
+}
+
+/// One isolated margin wallet
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginPairResponse {
+    pub symbol: String,
+    pub base_asset: MarginAssetResponse,
+    pub quote_asset: MarginAssetResponse,
+    pub enabled: bool,
+    pub margin_level: String,
+    pub margin_level_status: String,
+    pub index_price: String,
+}
+
+/// Isolated margin account response (Binance-compatible)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAccountResponse {
+    pub assets: Vec<IsolatedMarginPairResponse>,
+    pub total_asset_of_btc: String,
+    pub total_liability_of_btc: String,
+    pub total_net_asset_of_btc: String,
+}
+
 /// Server time response
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -1,7 +1,7 @@
 use crate::presentation::rest::dto::ErrorResponse;
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
 
@@ -11,6 +11,8 @@ pub struct ApiError {
     pub code: i32,
     pub message: String,
     pub status: StatusCode,
+    /// Sent as `Retry-After` (rounded up to whole seconds)
+    pub retry_after_ms: Option<u64>,
 }
 
 impl ApiError {
@@ -19,6 +21,7 @@ impl ApiError {
             code,
             message: message.into(),
             status: StatusCode::BAD_REQUEST,
+            retry_after_ms: None,
         }
     }
 
@@ -27,6 +30,7 @@ impl ApiError {
             code,
             message: message.into(),
             status: StatusCode::NOT_FOUND,
+            retry_after_ms: None,
         }
     }
 
@@ -40,6 +44,17 @@ impl ApiError {
             code: -1015,
             message: msg,
             status: StatusCode::TOO_MANY_REQUESTS,
+            retry_after_ms,
+        }
+    }
+
+    /// Client kept sending after a 429 and is banned (HTTP 418)
+    pub fn banned(retry_after_ms: u64) -> Self {
+        ApiError {
+            code: -1003,
+            message: format!("Way too many requests; banned for {}ms", retry_after_ms),
+            status: StatusCode::IM_A_TEAPOT,
+            retry_after_ms: Some(retry_after_ms),
         }
     }
 
@@ -48,6 +63,7 @@ impl ApiError {
             code: -1000,
             message: message.into(),
             status: StatusCode::INTERNAL_SERVER_ERROR,
+            retry_after_ms: None,
         }
     }
 
@@ -75,7 +91,13 @@ impl ApiError {
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let body = Json(ErrorResponse::new(self.code, self.message));
-        (self.status, body).into_response()
+        let mut response = (self.status, body).into_response();
+        if let Some(ms) = self.retry_after_ms {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(ms.div_ceil(1000)));
+        }
+        response
     }
 }
 
@@ -91,7 +113,8 @@ impl std::error::Error for ApiError {}
 // Error Mapper Traits (DIP)
 // ============================================================================
 
-use crate::application::{CancelError, DepthError, OrderError};
+use crate::application::{AmendError, CancelError, DepthError, MarginError, OrderError};
+use crate::domain::AccountError;
 
 /// Trait for mapping application errors to API errors (DIP)
 pub trait ErrorMapper<E> {
@@ -134,6 +157,25 @@ impl ErrorMapper<CancelError> for CancelErrorMapper {
     }
 }
 
+/// Amend error mapper
+pub struct AmendErrorMapper;
+
+impl ErrorMapper<AmendError> for AmendErrorMapper {
+    fn map_error(error: AmendError) -> ApiError {
+        match error {
+            AmendError::RateLimited { retry_after_ms } => ApiError::rate_limited(retry_after_ms),
+            AmendError::InvalidSymbol(s) => ApiError::invalid_symbol(&s),
+            AmendError::SymbolNotFound(s) => ApiError::invalid_symbol(&s),
+            AmendError::OrderNotFound => ApiError::unknown_order(),
+            AmendError::MissingOrderId => {
+                ApiError::missing_parameter("orderId or origClientOrderId")
+            }
+            AmendError::ValidationFailed(msg) => ApiError::bad_request(-2038, msg),
+            e @ AmendError::InvalidQuantity { .. } => ApiError::bad_request(-2038, e.to_string()),
+        }
+    }
+}
+
 /// Depth error mapper
 pub struct DepthErrorMapper;
 
@@ -146,3 +188,42 @@ impl ErrorMapper<DepthError> for DepthErrorMapper {
         }
     }
 }
+
+/// Margin error mapper
+pub struct MarginErrorMapper;
+
+impl ErrorMapper<MarginError> for MarginErrorMapper {
+    fn map_error(error: MarginError) -> ApiError {
+        match error {
+            MarginError::RateLimited { retry_after_ms } => ApiError::rate_limited(retry_after_ms),
+            MarginError::InvalidSymbol(s) => ApiError::invalid_symbol(&s),
+            MarginError::SymbolNotFound(s) => ApiError::invalid_symbol(&s),
+            MarginError::InvalidAmount => ApiError::invalid_parameter("amount", "must be positive"),
+            MarginError::UnsupportedAsset(_) => {
+                ApiError::bad_request(-3027, "Not a valid margin asset.")
+            }
+            MarginError::ExceedsBorrowLimit => ApiError::bad_request(
+                -3006,
+                "Your borrow amount has exceed maximum borrow amount.",
+            ),
+            MarginError::ExceedsTransferLimit => {
+                ApiError::bad_request(-3020, "Transfer out amount exceeds max amount.")
+            }
+            MarginError::AccountError(e) => match e {
+                AccountError::InsufficientBalance => {
+                    ApiError::bad_request(-3041, "Balance is not enough")
+                }
+                AccountError::NoActiveLoan => {
+                    ApiError::bad_request(-3015, "Repayment amount has exceeded borrowing amount.")
+                }
+                AccountError::NoIsolatedMarginAccount => {
+                    ApiError::bad_request(-11001, "Isolated margin account does not exist.")
+                }
+                AccountError::InvalidMarginAsset => {
+                    ApiError::bad_request(-3027, "Not a valid margin asset.")
+                }
+                other => ApiError::bad_request(-3003, other.to_string()),
+            },
+        }
+    }
+}
@@ -6,12 +6,15 @@ use axum::{
 use std::sync::Arc;
 
 use crate::application::{
-    CancelOrderCommand, CancelOrderUseCase, ExchangeInfoError, GetDepthQuery, GetDepthUseCase,
-    GetExchangeInfoUseCase, SubmitOrderCommand, SubmitOrderUseCase,
+    AmendOrderCommand, AmendOrderUseCase, CancelOrderCommand, CancelOrderUseCase,
+    ExchangeInfoError, GetDepthQuery, GetDepthUseCase, GetExchangeInfoUseCase,
+    GetMarketByOrderQuery, GetMarketByOrderUseCase, OptionMarkQuery, OptionMarksUseCase,
+    SubmitOrderCommand, SubmitOrderUseCase,
 };
-use crate::domain::{Clock, OrderType, Price, Quantity, Side, TimeInForce};
+use crate::domain::{Clock, Order, OrderType, Price, Quantity, Side, TimeInForce};
 use crate::presentation::rest::{
-    ApiError, CancelErrorMapper, DepthErrorMapper, ErrorMapper, OrderErrorMapper, dto::*,
+    AmendErrorMapper, ApiError, CancelErrorMapper, DepthErrorMapper, ErrorMapper, OrderErrorMapper,
+    dto::*,
 };
 
 use super::AppState;
@@ -88,6 +91,73 @@ pub async fn depth<C: Clock>(
     }))
 }
 
+/// GET /api/v3/mbo
+///
+/// Market-by-order (L3) snapshot: every resting order in queue order.
+pub async fn market_by_order<C: Clock>(
+    headers: HeaderMap,
+    Query(query): Query<MarketByOrderQuery>,
+    State(state): State<Arc<AppState<C>>>,
+) -> Result<Json<MarketByOrderResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+
+    let use_case = GetMarketByOrderUseCase::new(
+        Arc::clone(&state.order_book_repo),
+        Arc::clone(&state.rate_limiter),
+    );
+
+    let result = use_case
+        .execute(
+            &client_id,
+            GetMarketByOrderQuery {
+                symbol: query.symbol,
+            },
+        )
+        .await
+        .map_err(DepthErrorMapper::map_error)?;
+
+    let to_row = |o: &crate::domain::RestingOrder| {
+        (o.order_ref, o.price.to_string(), o.quantity.to_string())
+    };
+    Ok(Json(MarketByOrderResponse {
+        last_update_id: result.last_update_id,
+        bids: result.bids.iter().map(to_row).collect(),
+        asks: result.asks.iter().map(to_row).collect(),
+    }))
+}
+
+/// GET /eapi/v1/mark
+///
+/// Option marks off the fitted vol surface, with implied vols and Greeks.
+pub async fn option_mark<C: Clock>(
+    headers: HeaderMap,
+    Query(query): Query<OptionMarkQueryParams>,
+    State(state): State<Arc<AppState<C>>>,
+) -> Result<Json<Vec<OptionMarkResponse>>, ApiError> {
+    let client_id = extract_client_id(&headers);
+
+    let use_case = OptionMarksUseCase::new(
+        Arc::clone(&state.clock),
+        Arc::clone(&state.account_repo),
+        Arc::clone(&state.order_book_repo),
+        Arc::clone(&state.instrument_repo),
+        Arc::clone(&state.event_publisher),
+        Arc::clone(&state.rate_limiter),
+    );
+
+    let marks = use_case
+        .execute(
+            &client_id,
+            OptionMarkQuery {
+                symbol: query.symbol,
+            },
+        )
+        .await
+        .map_err(DepthErrorMapper::map_error)?;
+
+    Ok(Json(marks.iter().map(OptionMarkResponse::from).collect()))
+}
+
 /// POST /api/v3/order
 pub async fn create_order<C: Clock>(
     headers: HeaderMap,
@@ -161,7 +231,8 @@ pub async fn create_order<C: Clock>(
         Arc::clone(&state.instrument_repo),
         Arc::clone(&state.event_publisher),
         Arc::clone(&state.rate_limiter),
-    );
+    )
+    .with_margin_cache(Arc::clone(&state.portfolio_margin));
 
     let result = use_case
         .execute(&client_id, command)
@@ -212,10 +283,55 @@ pub async fn cancel_order<C: Clock>(
         .await
         .map_err(CancelErrorMapper::map_error)?;
 
-    let order = &result.order;
-    Ok(Json(CancelOrderResponse {
+    Ok(Json(order_response(
+        &result.order,
+        result.order.client_order_id.clone(),
+    )))
+}
+
+/// PUT /api/v3/order/amend/keepPriority
+///
+/// Reduces a resting order's quantity without losing its queue position.
+pub async fn amend_order<C: Clock>(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState<C>>>,
+    Query(req): Query<AmendOrderRequest>,
+) -> Result<Json<AmendOrderResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+
+    let new_quantity = Quantity::parse(&req.new_qty)
+        .map_err(|_| ApiError::bad_request(-1100, "Invalid newQty"))?;
+    let command = AmendOrderCommand {
+        symbol: req.symbol.clone(),
+        order_id: req.order_id.map(|id| uuid::Uuid::from_u128(id as u128)),
+        client_order_id: req.orig_client_order_id.clone(),
+        new_quantity,
+        new_client_order_id: req.new_client_order_id.clone(),
+    };
+
+    let use_case = AmendOrderUseCase::new(
+        Arc::clone(&state.clock),
+        Arc::clone(&state.order_book_repo),
+        Arc::clone(&state.event_publisher),
+        Arc::clone(&state.rate_limiter),
+    );
+
+    let result = use_case
+        .execute(&client_id, command)
+        .await
+        .map_err(AmendErrorMapper::map_error)?;
+
+    Ok(Json(AmendOrderResponse {
+        transact_time: state.clock.now_millis(),
+        amended_order: order_response(&result.order, result.orig_client_order_id),
+    }))
+}
+
+/// Order fields shared by the cancel and amend responses
+fn order_response(order: &Order, orig_client_order_id: Option<String>) -> CancelOrderResponse {
+    CancelOrderResponse {
         symbol: order.symbol.to_string(),
-        orig_client_order_id: order.client_order_id.clone().unwrap_or_default(),
+        orig_client_order_id: orig_client_order_id.unwrap_or_default(),
         order_id: order.id.as_u128() as i64,
         order_list_id: -1,
         client_order_id: order
@@ -233,11 +349,11 @@ pub async fn cancel_order<C: Clock>(
         time_in_force: order.time_in_force.to_string(),
         order_type: order.order_type.to_string(),
         side: order.side.to_string(),
-    }))
+    }
 }
 
 /// Extract client ID from headers (IP address or API key)
-fn extract_client_id(headers: &HeaderMap) -> String {
+pub(super) fn extract_client_id(headers: &HeaderMap) -> String {
     headers
         .get("X-MBX-APIKEY")
         .and_then(|v| v.to_str().ok())
@@ -0,0 +1,274 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::HeaderMap,
+};
+use std::sync::Arc;
+
+use crate::application::{
+    IsolatedTransferCommand, IsolatedTransferDirection, MarginAssetSummary, MarginLoanCommand,
+    MarginTransaction, MarginUseCase,
+};
+use crate::domain::{AssetPrices, Clock, MarginStatus, MarginTotals, Price, Value};
+use crate::infrastructure::{
+    InMemoryAccountRepository, InMemoryInstrumentRepository, InMemoryOrderBookRepository,
+    TokenBucketRateLimiter,
+};
+use crate::presentation::rest::{ApiError, ErrorMapper, MarginErrorMapper, dto::*};
+
+use super::AppState;
+use super::handlers::extract_client_id;
+
+type RestMarginUseCase<C> = MarginUseCase<
+    C,
+    InMemoryAccountRepository,
+    InMemoryOrderBookRepository,
+    InMemoryInstrumentRepository,
+    TokenBucketRateLimiter,
+>;
+
+/// Margin level reported when a wallet has no liability (Binance convention)
+const NO_LIABILITY_MARGIN_LEVEL: &str = "999.00000000";
+
+fn margin_use_case<C: Clock>(state: &AppState<C>) -> RestMarginUseCase<C> {
+    MarginUseCase::new(
+        Arc::clone(&state.clock),
+        Arc::clone(&state.account_repo),
+        Arc::clone(&state.order_book_repo),
+        Arc::clone(&state.instrument_repo),
+        Arc::clone(&state.rate_limiter),
+    )
+}
+
+/// POST /sapi/v1/margin/loan
+pub async fn borrow<C: Clock>(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState<C>>>,
+    Json(req): Json<MarginLoanRequest>,
+) -> Result<Json<MarginTransactionResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+    let command = parse_loan_request(req)?;
+
+    let result = margin_use_case(&state)
+        .borrow(&client_id, command)
+        .await
+        .map_err(MarginErrorMapper::map_error)?;
+
+    Ok(Json(transaction_response(result)))
+}
+
+/// POST /sapi/v1/margin/repay
+pub async fn repay<C: Clock>(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState<C>>>,
+    Json(req): Json<MarginLoanRequest>,
+) -> Result<Json<MarginTransactionResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+    let command = parse_loan_request(req)?;
+
+    let result = margin_use_case(&state)
+        .repay(&client_id, command)
+        .await
+        .map_err(MarginErrorMapper::map_error)?;
+
+    Ok(Json(transaction_response(result)))
+}
+
+/// POST /sapi/v1/margin/isolated/transfer
+pub async fn isolated_transfer<C: Clock>(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState<C>>>,
+    Json(req): Json<IsolatedTransferRequest>,
+) -> Result<Json<MarginTransactionResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+
+    let direction = match (req.trans_from.as_str(), req.trans_to.as_str()) {
+        ("SPOT", "ISOLATED_MARGIN") => IsolatedTransferDirection::ToIsolated,
+        ("ISOLATED_MARGIN", "SPOT") => IsolatedTransferDirection::ToSpot,
+        _ => {
+            return Err(ApiError::invalid_parameter(
+                "transFrom",
+                "must be SPOT or ISOLATED_MARGIN and differ from transTo",
+            ));
+        }
+    };
+
+    let command = IsolatedTransferCommand {
+        asset: req.asset,
+        symbol: req.symbol,
+        amount: parse_amount(&req.amount)?,
+        direction,
+    };
+
+    let result = margin_use_case(&state)
+        .transfer_isolated(&client_id, command)
+        .await
+        .map_err(MarginErrorMapper::map_error)?;
+
+    Ok(Json(transaction_response(result)))
+}
+
+/// GET /sapi/v1/margin/account
+pub async fn account<C: Clock>(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState<C>>>,
+) -> Result<Json<MarginAccountResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+
+    let summary = margin_use_case(&state)
+        .account(&client_id)
+        .await
+        .map_err(MarginErrorMapper::map_error)?;
+
+    let (total_asset, total_liability, total_net_asset) =
+        btc_totals(&summary.totals, &summary.prices);
+    Ok(Json(MarginAccountResponse {
+        borrow_enabled: true,
+        margin_level: margin_level(&summary.totals),
+        total_asset_of_btc: total_asset,
+        total_liability_of_btc: total_liability,
+        total_net_asset_of_btc: total_net_asset,
+        trade_enabled: true,
+        transfer_enabled: true,
+        user_assets: summary.assets.iter().map(asset_response).collect(),
+    }))
+}
+
+/// GET /sapi/v1/margin/isolated/account
+pub async fn isolated_account<C: Clock>(
+    headers: HeaderMap,
+    Query(query): Query<IsolatedMarginAccountQuery>,
+    State(state): State<Arc<AppState<C>>>,
+) -> Result<Json<IsolatedMarginAccountResponse>, ApiError> {
+    let client_id = extract_client_id(&headers);
+
+    let symbols: Vec<String> = query
+        .symbols
+        .as_deref()
+        .map(|s| {
+            s.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let summary = margin_use_case(&state)
+        .isolated_accounts(&client_id, &symbols)
+        .await
+        .map_err(MarginErrorMapper::map_error)?;
+
+    let mut totals = MarginTotals::default();
+    let assets = summary
+        .wallets
+        .iter()
+        .map(|wallet| {
+            totals.total_asset = totals.total_asset + wallet.totals.total_asset;
+            totals.total_liability = totals.total_liability + wallet.totals.total_liability;
+            IsolatedMarginPairResponse {
+                symbol: wallet.symbol.to_string(),
+                base_asset: asset_response(&wallet.base),
+                quote_asset: asset_response(&wallet.quote),
+                enabled: true,
+                margin_level: margin_level(&wallet.totals),
+                margin_level_status: status_name(wallet.status).to_string(),
+                index_price: summary
+                    .prices
+                    .price(&wallet.base.asset)
+                    .unwrap_or(Price::ZERO)
+                    .to_string(),
+            }
+        })
+        .collect();
+
+    let (total_asset, total_liability, total_net_asset) = btc_totals(&totals, &summary.prices);
+    Ok(Json(IsolatedMarginAccountResponse {
+        assets,
+        total_asset_of_btc: total_asset,
+        total_liability_of_btc: total_liability,
+        total_net_asset_of_btc: total_net_asset,
+    }))
+}
+
+fn parse_loan_request(req: MarginLoanRequest) -> Result<MarginLoanCommand, ApiError> {
+    let isolated = match req.is_isolated.as_deref() {
+        None | Some("FALSE") => false,
+        Some("TRUE") => true,
+        Some(_) => {
+            return Err(ApiError::invalid_parameter(
+                "isIsolated",
+                "must be TRUE or FALSE",
+            ));
+        }
+    };
+    let symbol = if isolated {
+        Some(
+            req.symbol
+                .ok_or_else(|| ApiError::missing_parameter("symbol"))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(MarginLoanCommand {
+        asset: req.asset,
+        amount: parse_amount(&req.amount)?,
+        symbol,
+    })
+}
+
+fn parse_amount(amount: &str) -> Result<Value, ApiError> {
+    amount
+        .parse::<f64>()
+        .map(Value::from_f64)
+        .map_err(|_| ApiError::invalid_parameter("amount", "invalid decimal"))
+}
+
+fn transaction_response(tx: MarginTransaction) -> MarginTransactionResponse {
+    MarginTransactionResponse {
+        tran_id: tx.tran_id,
+    }
+}
+
+fn asset_response(summary: &MarginAssetSummary) -> MarginAssetResponse {
+    let balance = &summary.balance;
+    MarginAssetResponse {
+        asset: summary.asset.clone(),
+        borrowed: balance.borrowed.to_string(),
+        free: balance.available.to_string(),
+        interest: balance.interest.to_string(),
+        locked: balance.locked.to_string(),
+        net_asset: balance.net().to_string(),
+    }
+}
+
+fn margin_level(totals: &MarginTotals) -> String {
+    match totals.margin_level() {
+        i64::MAX => NO_LIABILITY_MARGIN_LEVEL.to_string(),
+        level => Price::from_raw(level).to_string(),
+    }
+}
+
+fn status_name(status: MarginStatus) -> &'static str {
+    match status {
+        MarginStatus::Healthy => "NORMAL",
+        MarginStatus::MarginCall => "MARGIN_CALL",
+        MarginStatus::Liquidating => "FORCE_LIQUIDATION",
+    }
+}
+
+/// Asset, liability and net asset totals expressed in BTC
+fn btc_totals(totals: &MarginTotals, prices: &AssetPrices) -> (String, String, String) {
+    let in_btc = |value: Value| {
+        prices
+            .amount_of("BTC", value)
+            .unwrap_or(Value::ZERO)
+            .to_string()
+    };
+    (
+        in_btc(totals.total_asset),
+        in_btc(totals.total_liability),
+        in_btc(totals.net_asset()),
+    )
+}
@@ -2,8 +2,16 @@ mod admin_handlers;
 mod dto;
 mod error;
 mod handlers;
+mod margin_handlers;
+mod rate_limit_headers;
 mod router;
 
 pub use dto::*;
-pub use error::{ApiError, CancelErrorMapper, DepthErrorMapper, ErrorMapper, OrderErrorMapper};
+pub use error::{
+    AmendErrorMapper, ApiError, CancelErrorMapper, DepthErrorMapper, ErrorMapper,
+    MarginErrorMapper, OrderErrorMapper,
+};
+pub use rate_limit_headers::{
+    ORDER_COUNT_DAY_HEADER, ORDER_COUNT_SECOND_HEADER, USED_WEIGHT_HEADER, rate_limit_headers,
+};
 pub use router::{AppState, create_router};
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use super::{ApiError, AppState, handlers::extract_client_id};
+use crate::application::ports::RateLimitAdmin;
+use crate::domain::Clock;
+
+pub const USED_WEIGHT_HEADER: &str = "x-mbx-used-weight-1m";
+pub const ORDER_COUNT_SECOND_HEADER: &str = "x-mbx-order-count-1s";
+pub const ORDER_COUNT_DAY_HEADER: &str = "x-mbx-order-count-1d";
+
+/// Binance-style rate limit headers
+///
+/// Every response reports the client's used request weight; order
+/// endpoints also report order counts per window. Clients that come back
+/// inside the Retry-After window of a 429 are banned with a 418.
+pub async fn rate_limit_headers<C: Clock>(
+    State(state): State<Arc<AppState<C>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client_id = extract_client_id(request.headers());
+    let is_order = request.uri().path().ends_with("/order");
+
+    let mut response = match state.rate_limiter.check_ban(&client_id) {
+        Some(ban) => ApiError::banned(ban.as_millis() as u64).into_response(),
+        None => next.run(request).await,
+    };
+
+    let status = state.rate_limiter.get_status(&client_id).await;
+    let headers = response.headers_mut();
+    let mut insert = |name: &'static str, value: u32| {
+        headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+    };
+    insert(USED_WEIGHT_HEADER, status.request_weight_used);
+    if is_order {
+        insert(ORDER_COUNT_SECOND_HEADER, status.orders_used_second);
+        insert(ORDER_COUNT_DAY_HEADER, status.orders_used_day);
+    }
+    response
+}
@@ -1,12 +1,13 @@
 use axum::{
-    Router,
+    Router, middleware,
     routing::{delete, get, post, put},
 };
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 
-use super::{admin_handlers, handlers};
+use super::{admin_handlers, handlers, margin_handlers, rate_limit_headers};
+use crate::application::use_cases::PortfolioMarginCache;
 use crate::domain::Clock;
 use crate::infrastructure::{
     BroadcastEventPublisher, InMemoryAccountRepository, InMemoryInstrumentRepository,
@@ -21,6 +22,8 @@ pub struct AppState<C: Clock> {
     pub instrument_repo: Arc<InMemoryInstrumentRepository>,
     pub event_publisher: Arc<BroadcastEventPublisher>,
     pub rate_limiter: Arc<TokenBucketRateLimiter>,
+    /// Portfolio margin state from the periodic refresh
+    pub portfolio_margin: Arc<PortfolioMarginCache>,
 }
 
 impl<C: Clock> AppState<C> {
@@ -39,8 +42,15 @@ impl<C: Clock> AppState<C> {
             instrument_repo,
             event_publisher,
             rate_limiter,
+            portfolio_margin: Arc::new(PortfolioMarginCache::new()),
         }
     }
+
+    /// Share the portfolio margin state the periodic refresh writes to
+    pub fn with_portfolio_margin(mut self, cache: Arc<PortfolioMarginCache>) -> Self {
+        self.portfolio_margin = cache;
+        self
+    }
 }
 
 /// Create the REST API router
@@ -51,9 +61,30 @@ pub fn create_router<C: Clock + 'static>(state: Arc<AppState<C>>) -> Router {
         .route("/api/v3/time", get(handlers::server_time::<C>))
         .route("/api/v3/exchangeInfo", get(handlers::exchange_info::<C>))
         .route("/api/v3/depth", get(handlers::depth::<C>))
+        .route("/api/v3/mbo", get(handlers::market_by_order::<C>))
+        .route("/eapi/v1/mark", get(handlers::option_mark::<C>))
         // Trading endpoints
         .route("/api/v3/order", post(handlers::create_order::<C>))
         .route("/api/v3/order", delete(handlers::cancel_order::<C>))
+        .route(
+            "/api/v3/order/amend/keepPriority",
+            put(handlers::amend_order::<C>),
+        )
+        // Margin endpoints
+        .route("/sapi/v1/margin/loan", post(margin_handlers::borrow::<C>))
+        .route("/sapi/v1/margin/repay", post(margin_handlers::repay::<C>))
+        .route(
+            "/sapi/v1/margin/account",
+            get(margin_handlers::account::<C>),
+        )
+        .route(
+            "/sapi/v1/margin/isolated/account",
+            get(margin_handlers::isolated_account::<C>),
+        )
+        .route(
+            "/sapi/v1/margin/isolated/transfer",
+            post(margin_handlers::isolated_transfer::<C>),
+        )
         // Admin/Bootstrap endpoints (for testing)
         .route("/admin/accounts", post(admin_handlers::create_account::<C>))
         .route(
@@ -68,6 +99,10 @@ pub fn create_router<C: Clock + 'static>(state: Arc<AppState<C>>) -> Router {
             "/admin/accounts/{owner_id}/fee-tier",
             put(admin_handlers::set_fee_tier::<C>),
         )
+        .route(
+            "/admin/accounts/{owner_id}/margin-mode",
+            put(admin_handlers::set_margin_mode::<C>),
+        )
         .route("/admin/markets", post(admin_handlers::create_market::<C>))
         .route("/admin/markets", get(admin_handlers::list_markets::<C>))
         .route(
@@ -75,6 +110,10 @@ pub fn create_router<C: Clock + 'static>(state: Arc<AppState<C>>) -> Router {
             get(admin_handlers::get_market::<C>),
         )
         // Middleware
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            rate_limit_headers::<C>,
+        ))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
         .with_state(state)
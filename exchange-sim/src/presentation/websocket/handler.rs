@@ -40,12 +40,12 @@ async fn handle_socket<C: Clock>(socket: WebSocket, state: Arc<WsState<C>>) {
         Arc::new(parking_lot::Mutex::new(HashSet::new()));
 
     // Channel for outgoing messages
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(100);
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Message>(100);
 
     // Spawn task to forward messages to WebSocket
     let send_task = tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
-            if sender.send(Message::Text(msg.into())).await.is_err() {
+            if sender.send(msg).await.is_err() {
                 break;
             }
         }
@@ -59,15 +59,27 @@ async fn handle_socket<C: Clock>(socket: WebSocket, state: Arc<WsState<C>>) {
     // Handle incoming messages
     let rate_limiter = Arc::clone(&state.rate_limiter);
     let client_id = "ws-client"; // Simplified - in production, use connection ID
-
-    while let Some(Ok(msg)) = receiver.next().await {
+    let mut disconnect = state.stream_manager.disconnect_signal();
+    let mut forced = false;
+
+    loop {
+        let msg = tokio::select! {
+            msg = receiver.next() => match msg {
+                Some(Ok(msg)) => msg,
+                _ => break,
+            },
+            _ = disconnect.recv() => {
+                forced = true;
+                break;
+            }
+        };
         if let Message::Text(text) = msg {
             // Rate limit check
             let rate_result = rate_limiter.check_ws_message(client_id).await;
             if !rate_result.allowed {
                 let error = WsResponse::error(None, -1015, "Too many messages");
                 if let Ok(json) = serde_json::to_string(&error) {
-                    let _ = tx.send(json).await;
+                    let _ = tx.send(Message::Text(json.into())).await;
                 }
                 continue;
             }
@@ -87,18 +99,26 @@ async fn handle_socket<C: Clock>(socket: WebSocket, state: Arc<WsState<C>>) {
 
                             tokio::spawn(async move {
                                 while let Ok(event) = event_rx.recv().await {
-                                    if let Some(msg) =
+                                    let frame = if let Some(bytes) =
+                                        manager.event_to_binary(&stream_name, &event)
+                                    {
+                                        Message::Binary(bytes.into())
+                                    } else if let Some(msg) =
                                         manager.event_to_message(&stream_name, &event)
                                     {
                                         let response = WsResponse::Stream {
                                             stream: msg.stream,
                                             data: msg.data,
                                         };
-                                        if let Ok(json) = serde_json::to_string(&response) {
-                                            if tx.send(json).await.is_err() {
-                                                break;
-                                            }
+                                        match serde_json::to_string(&response) {
+                                            Ok(json) => Message::Text(json.into()),
+                                            Err(_) => continue,
                                         }
+                                    } else {
+                                        continue;
+                                    };
+                                    if tx.send(frame).await.is_err() {
+                                        break;
                                     }
                                 }
                             });
@@ -107,7 +127,7 @@ async fn handle_socket<C: Clock>(socket: WebSocket, state: Arc<WsState<C>>) {
 
                     let response = WsResponse::ok(id);
                     if let Ok(json) = serde_json::to_string(&response) {
-                        let _ = tx.send(json).await;
+                        let _ = tx.send(Message::Text(json.into())).await;
                     }
                 }
                 Ok(WsRequest::Unsubscribe { id, params }) => {
@@ -118,20 +138,20 @@ async fn handle_socket<C: Clock>(socket: WebSocket, state: Arc<WsState<C>>) {
 
                     let response = WsResponse::ok(id);
                     if let Ok(json) = serde_json::to_string(&response) {
-                        let _ = tx.send(json).await;
+                        let _ = tx.send(Message::Text(json.into())).await;
                     }
                 }
                 Ok(WsRequest::ListSubscriptions { id }) => {
                     let current_subs: Vec<String> = subs.lock().iter().cloned().collect();
                     let response = WsResponse::subscriptions(id, current_subs);
                     if let Ok(json) = serde_json::to_string(&response) {
-                        let _ = tx.send(json).await;
+                        let _ = tx.send(Message::Text(json.into())).await;
                     }
                 }
                 Err(e) => {
                     let error = WsResponse::error(None, -1, format!("Invalid request: {}", e));
                     if let Ok(json) = serde_json::to_string(&error) {
-                        let _ = tx.send(json).await;
+                        let _ = tx.send(Message::Text(json.into())).await;
                     }
                 }
             }
@@ -139,6 +159,11 @@ async fn handle_socket<C: Clock>(socket: WebSocket, state: Arc<WsState<C>>) {
     }
 
     // Cleanup
+    if forced {
+        // Subscription tasks still hold senders; drop the socket outright
+        send_task.abort();
+        return;
+    }
     drop(tx);
     let _ = send_task.await;
 }
@@ -1,3 +1,6 @@
+use crate::application::OptionMark;
+use crate::domain::{MboAction, Side};
+use crate::presentation::FormattedOptionMark;
 use serde::{Deserialize, Serialize};
 
 /// WebSocket incoming message
@@ -101,6 +104,88 @@ pub struct TradeMessage {
     pub is_buyer_maker: bool,
 }
 
+/// Market-by-order (L3) stream message
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketByOrderMessage {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "u")]
+    pub sequence: u64,
+    #[serde(rename = "x")]
+    pub action: MboAction,
+    #[serde(rename = "i")]
+    pub order_ref: u64,
+    #[serde(rename = "S")]
+    pub side: Side,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "r")]
+    pub remaining: String,
+    #[serde(rename = "t", skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+}
+
+/// Option mark price stream message, one per option on the underlying
+#[derive(Debug, Clone, Serialize)]
+pub struct OptionMarkPriceMessage {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "mp")]
+    pub mark_price: String,
+    #[serde(rename = "i")]
+    pub index_price: String,
+    #[serde(rename = "b")]
+    pub bid_iv: String,
+    #[serde(rename = "a")]
+    pub ask_iv: String,
+    #[serde(rename = "vo")]
+    pub mark_iv: String,
+    #[serde(rename = "d")]
+    pub delta: String,
+    #[serde(rename = "g")]
+    pub gamma: String,
+    #[serde(rename = "v")]
+    pub vega: String,
+    #[serde(rename = "t")]
+    pub theta: String,
+    #[serde(rename = "r")]
+    pub rho: String,
+    #[serde(rename = "rf")]
+    pub risk_free_rate: String,
+}
+
+impl OptionMarkPriceMessage {
+    pub fn new(mark: &OptionMark, event_time: i64) -> Self {
+        let m = FormattedOptionMark::from(mark);
+        Self {
+            event_type: "markPrice".to_string(),
+            event_time,
+            symbol: m.symbol,
+            mark_price: m.mark_price,
+            index_price: m.index_price,
+            bid_iv: m.bid_iv,
+            ask_iv: m.ask_iv,
+            mark_iv: m.mark_iv,
+            delta: m.delta,
+            gamma: m.gamma,
+            vega: m.vega,
+            theta: m.theta,
+            rho: m.rho,
+            risk_free_rate: m.risk_free_rate,
+        }
+    }
+}
+
 /// Generic wrapper for all stream messages
 #[derive(Debug, Clone, Serialize)]
 pub struct WsMessage {
@@ -1,10 +1,12 @@
-use crate::domain::{ExchangeEvent, PriceLevel};
+use crate::domain::{ExchangeEvent, MarketByOrderEvent, PriceLevel};
 use crate::infrastructure::BroadcastEventPublisher;
 use dashmap::DashMap;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
-use super::message::{DepthUpdateMessage, TradeMessage, WsMessage};
+use super::message::{
+    DepthUpdateMessage, MarketByOrderMessage, OptionMarkPriceMessage, TradeMessage, WsMessage,
+};
 
 /// Type alias for depth snapshot state: (bids, asks, update_id)
 type DepthSnapshot = (Vec<PriceLevel>, Vec<PriceLevel>, u64);
@@ -21,6 +23,12 @@ pub enum StreamType {
     Depth1000ms,
     Trade,
     AggTrade,
+    /// Market-by-order (L3) events as JSON
+    MarketByOrder,
+    /// Market-by-order (L3) events as binary frames
+    MarketByOrderBinary,
+    /// Option marks, IVs and Greeks for an underlying (e.g. "btc@markPrice")
+    OptionMarkPrice,
 }
 
 impl StreamType {
@@ -32,6 +40,9 @@ impl StreamType {
             "depth@1000ms" => Some(Self::Depth1000ms),
             "trade" => Some(Self::Trade),
             "aggTrade" => Some(Self::AggTrade),
+            "mbo" => Some(Self::MarketByOrder),
+            "mbo@bin" => Some(Self::MarketByOrderBinary),
+            "markPrice" => Some(Self::OptionMarkPrice),
             _ => None,
         }
     }
@@ -49,15 +60,12 @@ pub struct ParsedStream {
 }
 
 impl ParsedStream {
-    /// Parse a stream name like "btcusdt@depth" or "btcusdt@trade"
+    /// Parse a stream name like "btcusdt@depth" or "btcusdt@depth@100ms"
     pub fn parse(stream: &str) -> Option<Self> {
-        let parts: Vec<&str> = stream.split('@').collect();
-        if parts.len() != 2 {
-            return None;
-        }
+        let (symbol, suffix) = stream.split_once('@')?;
 
-        let symbol = parts[0].to_uppercase();
-        let stream_type = StreamType::from_suffix(parts[1])?;
+        let symbol = symbol.to_uppercase();
+        let stream_type = StreamType::from_suffix(suffix)?;
 
         Some(Self {
             symbol,
@@ -73,6 +81,8 @@ pub struct StreamManager {
     depth_streams: Arc<DashMap<String, bool>>,
     /// Track previous depth state for delta calculation
     previous_depth: Arc<DashMap<String, DepthSnapshot>>,
+    /// Fired to close every open connection
+    disconnects: broadcast::Sender<()>,
 }
 
 impl StreamManager {
@@ -81,9 +91,22 @@ impl StreamManager {
             publisher,
             depth_streams: Arc::new(DashMap::new()),
             previous_depth: Arc::new(DashMap::new()),
+            disconnects: broadcast::channel(1).0,
         }
     }
 
+    /// Close every open WebSocket connection (for reconnect testing)
+    ///
+    /// Returns the number of connections told to close.
+    pub fn disconnect_all(&self) -> usize {
+        self.disconnects.send(()).unwrap_or(0)
+    }
+
+    /// Notified when `disconnect_all` is called
+    pub fn disconnect_signal(&self) -> broadcast::Receiver<()> {
+        self.disconnects.subscribe()
+    }
+
     /// Subscribe to a stream and return a receiver
     pub fn subscribe(&self, stream: &str) -> Option<broadcast::Receiver<ExchangeEvent>> {
         let parsed = ParsedStream::parse(stream)?;
@@ -133,6 +156,51 @@ impl StreamManager {
                     data: serde_json::to_value(msg).ok()?,
                 })
             }
+            (StreamType::MarketByOrder, ExchangeEvent::MarketByOrder(mbo)) => {
+                let msg = MarketByOrderMessage {
+                    event_type: "mbo".to_string(),
+                    event_time: mbo.timestamp.timestamp_millis(),
+                    symbol: mbo.symbol.to_string(),
+                    sequence: mbo.sequence,
+                    action: mbo.action,
+                    order_ref: mbo.order_ref,
+                    side: mbo.side,
+                    price: mbo.price.to_string(),
+                    quantity: mbo.quantity.to_string(),
+                    remaining: mbo.remaining.to_string(),
+                    trade_id: mbo.trade_id.map(|id| id.to_string()),
+                };
+                Some(WsMessage {
+                    stream: stream.to_string(),
+                    data: serde_json::to_value(msg).ok()?,
+                })
+            }
+            (StreamType::OptionMarkPrice, ExchangeEvent::OptionMark(event)) => {
+                let event_time = event.timestamp.timestamp_millis();
+                let msgs: Vec<OptionMarkPriceMessage> = event
+                    .marks
+                    .iter()
+                    .map(|mark| OptionMarkPriceMessage::new(mark, event_time))
+                    .collect();
+                Some(WsMessage {
+                    stream: stream.to_string(),
+                    data: serde_json::to_value(msgs).ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert an exchange event to a binary frame for binary streams
+    pub fn event_to_binary(&self, stream: &str, event: &ExchangeEvent) -> Option<Vec<u8>> {
+        let parsed = ParsedStream::parse(stream)?;
+
+        match (&parsed.stream_type, event) {
+            (StreamType::MarketByOrderBinary, ExchangeEvent::MarketByOrder(mbo)) => {
+                let mut buf = Vec::with_capacity(MarketByOrderEvent::BINARY_HEADER_LEN + 16);
+                mbo.encode_binary(&mut buf);
+                Some(buf)
+            }
             _ => None,
         }
     }
@@ -219,6 +287,7 @@ impl Clone for StreamManager {
             publisher: Arc::clone(&self.publisher),
             depth_streams: Arc::clone(&self.depth_streams),
             previous_depth: Arc::clone(&self.previous_depth),
+            disconnects: self.disconnects.clone(),
         }
     }
 }
@@ -548,6 +548,88 @@ async fn test_cancel_nonexistent_order() {
     assert_eq!(json["code"].as_i64().unwrap(), -2013);
 }
 
+#[tokio::test]
+async fn test_amend_order_reduces_in_place() {
+    let state = create_test_state_with_account("BTCUSDT", "trader1").await;
+    let sym = Symbol::new("BTCUSDT").unwrap();
+
+    // Two bids at one level; the first one's priority must survive the amend
+    {
+        let mut book = state.order_book_repo.get_or_create(&sym).await;
+        for cl_ord_id in ["first", "second"] {
+            let mut order = exchange_sim::domain::Order::new_limit(
+                sym.clone(),
+                Side::Buy,
+                Quantity::from_int(2),
+                Price::from_int(49000),
+                TimeInForce::Gtc,
+            );
+            order.client_order_id = Some(cl_ord_id.to_string());
+            book.add_order(order);
+        }
+        book.take_deltas();
+        state.order_book_repo.save(book).await;
+    }
+    let mut events = state.event_publisher.subscribe_symbol("BTCUSDT");
+
+    let app = create_router(Arc::clone(&state));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(
+                    "/api/v3/order/amend/keepPriority?symbol=BTCUSDT\
+                     &origClientOrderId=first&newClientOrderId=amended&newQty=0.5",
+                )
+                .header("X-MBX-APIKEY", "trader1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: JsonValue = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["amendedOrder"]["origClientOrderId"], "first");
+    assert_eq!(json["amendedOrder"]["clientOrderId"], "amended");
+    assert_eq!(json["amendedOrder"]["origQty"], "0.50000000");
+
+    let book = state.order_book_repo.get(&sym).await.unwrap();
+    let bids = book.resting_orders(Side::Buy);
+    assert_eq!(bids[0].quantity, Quantity::parse("0.5").unwrap());
+    assert_eq!(bids[1].quantity, Quantity::from_int(2));
+
+    // Depth update, then the order-level reduce
+    let mut reduce = None;
+    while let Ok(event) = events.try_recv() {
+        if let exchange_sim::domain::ExchangeEvent::MarketByOrder(mbo) = event {
+            reduce = Some(mbo);
+        }
+    }
+    let reduce = reduce.expect("no market-by-order event");
+    assert_eq!(reduce.action, exchange_sim::domain::MboAction::Reduce);
+    assert_eq!(reduce.order_ref, bids[0].order_ref);
+    assert_eq!(reduce.quantity, Quantity::parse("1.5").unwrap());
+    assert_eq!(reduce.sequence, book.sequence());
+
+    // Raising the quantity is not an amend
+    let app = create_router(Arc::clone(&state));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/api/v3/order/amend/keepPriority?symbol=BTCUSDT&origClientOrderId=amended&newQty=3")
+                .header("X-MBX-APIKEY", "trader1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 // ============================================================================
 // Admin API Tests
 // ============================================================================
@@ -589,6 +671,41 @@ async fn test_create_account() {
     assert_eq!(json["fee_tier"].as_u64().unwrap(), 1);
 }
 
+#[tokio::test]
+async fn test_set_margin_mode() {
+    let state = create_test_state_with_account("BTCUSDT", "mm").await;
+    let app = create_router(state);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/admin/accounts/mm/margin-mode")
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#""Portfolio""#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/accounts/mm")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: JsonValue = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["margin_mode"], "Portfolio");
+}
+
 #[tokio::test]
 async fn test_create_market() {
     let state = create_test_state();
@@ -706,6 +823,59 @@ async fn test_rate_limit_requests() {
     }
 }
 
+#[tokio::test]
+async fn test_rate_limit_headers_and_ban() {
+    let state = create_test_state_with_market("BTCUSDT").await;
+    let depth = || {
+        Request::builder()
+            .uri("/api/v3/depth?symbol=BTCUSDT&limit=5000")
+            .header("X-MBX-APIKEY", "header_client")
+            .body(Body::empty())
+            .unwrap()
+    };
+    let used_weight = |response: &axum::response::Response| -> u32 {
+        response.headers()["x-mbx-used-weight-1m"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+
+    // limit=5000 weighs 50 against 1200 per minute
+    let app = create_router(Arc::clone(&state));
+    let response = app.oneshot(depth()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(used_weight(&response), 50);
+
+    let mut status = StatusCode::OK;
+    for _ in 0..30 {
+        let response = create_router(Arc::clone(&state))
+            .oneshot(depth())
+            .await
+            .unwrap();
+        status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            assert!(response.headers().contains_key("retry-after"));
+            assert!(used_weight(&response) > 1150);
+            break;
+        }
+    }
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    // Ignoring Retry-After gets the client banned
+    let response = create_router(Arc::clone(&state))
+        .oneshot(depth())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+    let retry_after: u64 = response.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(retry_after, 120);
+}
+
 // ============================================================================
 // Order Book Rebuild Test
 // ============================================================================
@@ -895,3 +1065,313 @@ async fn test_concurrent_order_submission() {
         "Expected 10 bid levels from concurrent orders"
     );
 }
+
+// ============================================================================
+// Margin Tests
+// ============================================================================
+
+async fn post_json(
+    app: &axum::Router,
+    uri: &str,
+    api_key: &str,
+    body: JsonValue,
+) -> (StatusCode, JsonValue) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .header("X-MBX-APIKEY", api_key)
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn get_json(app: &axum::Router, uri: &str, api_key: &str) -> (StatusCode, JsonValue) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("X-MBX-APIKEY", api_key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_margin_borrow_repay_and_isolated_transfer() {
+    let state = create_test_state_with_account("BTCUSDT", "trader1").await;
+
+    // Quote BTC at 50000 so collateral can be valued
+    let sym = Symbol::new("BTCUSDT").unwrap();
+    let mut book = state.order_book_repo.get(&sym).await.unwrap();
+    for (side, price) in [(Side::Buy, 49_900), (Side::Sell, 50_100)] {
+        book.add_order(exchange_sim::domain::Order::new_limit(
+            sym.clone(),
+            side,
+            Quantity::from_int(1),
+            Price::from_int(price),
+            TimeInForce::Gtc,
+        ));
+    }
+    state.order_book_repo.save(book).await;
+
+    let app = create_router(state);
+
+    // Cross margin: 600k of collateral easily supports a 100k loan
+    let (status, json) = post_json(
+        &app,
+        "/sapi/v1/margin/loan",
+        "trader1",
+        json!({"asset": "USDT", "amount": "100000"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["tranId"].as_u64().is_some());
+
+    let (status, json) = get_json(&app, "/sapi/v1/margin/account", "trader1").await;
+    assert_eq!(status, StatusCode::OK);
+    let usdt = json["userAssets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["asset"] == "USDT")
+        .unwrap();
+    assert_eq!(usdt["borrowed"], "100000.00000000");
+    assert_eq!(usdt["free"], "200000.00000000");
+
+    // Borrowing far beyond the collateral is rejected
+    let (status, json) = post_json(
+        &app,
+        "/sapi/v1/margin/loan",
+        "trader1",
+        json!({"asset": "USDT", "amount": "2000000"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["code"], -3006);
+
+    let (status, _) = post_json(
+        &app,
+        "/sapi/v1/margin/repay",
+        "trader1",
+        json!({"asset": "USDT", "amount": "100000"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Isolated margin: fund the BTCUSDT wallet and borrow against it
+    let (status, _) = post_json(
+        &app,
+        "/sapi/v1/margin/isolated/transfer",
+        "trader1",
+        json!({
+            "asset": "BTC",
+            "symbol": "BTCUSDT",
+            "transFrom": "SPOT",
+            "transTo": "ISOLATED_MARGIN",
+            "amount": "1"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, json) = post_json(
+        &app,
+        "/sapi/v1/margin/loan",
+        "trader1",
+        json!({"asset": "USDT", "amount": "20000", "isIsolated": "TRUE"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["code"], -1102);
+
+    let (status, _) = post_json(
+        &app,
+        "/sapi/v1/margin/loan",
+        "trader1",
+        json!({"asset": "USDT", "amount": "20000", "isIsolated": "TRUE", "symbol": "BTCUSDT"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, json) = get_json(
+        &app,
+        "/sapi/v1/margin/isolated/account?symbols=BTCUSDT",
+        "trader1",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let wallet = &json["assets"][0];
+    assert_eq!(wallet["symbol"], "BTCUSDT");
+    assert_eq!(wallet["baseAsset"]["free"], "1.00000000");
+    assert_eq!(wallet["quoteAsset"]["borrowed"], "20000.00000000");
+    assert_eq!(wallet["marginLevel"], "3.50000000");
+    assert_eq!(wallet["marginLevelStatus"], "NORMAL");
+
+    // Withdrawing the collateral would leave the loan unbacked
+    let (status, json) = post_json(
+        &app,
+        "/sapi/v1/margin/isolated/transfer",
+        "trader1",
+        json!({
+            "asset": "BTC",
+            "symbol": "BTCUSDT",
+            "transFrom": "ISOLATED_MARGIN",
+            "transTo": "SPOT",
+            "amount": "1"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["code"], -3020);
+}
+
+#[tokio::test]
+async fn test_option_mark_endpoint() {
+    use exchange_sim::domain::{Clock, ExerciseStyle, OptionConfig, OptionType, Order};
+
+    let state = create_test_state_with_market("BTCUSDT").await;
+    let expiry = state.clock.now() + chrono::Duration::days(30);
+
+    let quote = |symbol: &Symbol, bid: i64, ask: i64| {
+        let state = Arc::clone(&state);
+        let symbol = symbol.clone();
+        async move {
+            let mut book = state.order_book_repo.get_or_create(&symbol).await;
+            for (side, price) in [(Side::Buy, bid), (Side::Sell, ask)] {
+                book.add_order(Order::new_limit(
+                    symbol.clone(),
+                    side,
+                    Quantity::from_int(1),
+                    Price::from_int(price),
+                    TimeInForce::Gtc,
+                ));
+            }
+            OrderBookWriter::save(state.order_book_repo.as_ref(), book).await;
+        }
+    };
+    quote(&Symbol::new("BTCUSDT").unwrap(), 49_990, 50_010).await;
+
+    let option = Symbol::new("BTC-50000-C").unwrap();
+    state.instrument_repo.add(TradingPairConfig::option(
+        option.clone(),
+        "BTC",
+        "USDT",
+        OptionConfig {
+            strike: Price::from_int(50_000),
+            option_type: OptionType::Call,
+            expiration_ms: expiry.timestamp_millis(),
+            exercise_style: ExerciseStyle::European,
+        },
+    ));
+    // Roughly 60% vol for a 30-day at-the-money call
+    quote(&option, 3_350, 3_450).await;
+
+    let app = create_router(state);
+    let (status, json) = get_json(&app, "/eapi/v1/mark?symbol=BTC-50000-C", "trader1").await;
+    assert_eq!(status, StatusCode::OK);
+    let mark = &json[0];
+    assert_eq!(mark["symbol"], "BTC-50000-C");
+    assert_eq!(mark["indexPrice"], "50000.00000000");
+
+    let mark_iv: f64 = mark["markIV"].as_str().unwrap().parse().unwrap();
+    let bid_iv: f64 = mark["bidIV"].as_str().unwrap().parse().unwrap();
+    let ask_iv: f64 = mark["askIV"].as_str().unwrap().parse().unwrap();
+    assert!(bid_iv < mark_iv && mark_iv < ask_iv);
+    assert!((mark_iv - 0.6).abs() < 0.05, "{mark_iv}");
+
+    let mark_price: f64 = mark["markPrice"].as_str().unwrap().parse().unwrap();
+    assert!(mark_price > 3_350.0 && mark_price < 3_450.0);
+    let delta: f64 = mark["delta"].as_str().unwrap().parse().unwrap();
+    assert!(delta > 0.5 && delta < 0.6);
+
+    let (status, _) = get_json(&app, "/eapi/v1/mark?symbol=ETH-1-C", "trader1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_seeded_book_publishes_only_new_mbo_deltas() {
+    let seed_ask = |price| {
+        json!({
+            "symbol": "BTCUSDT",
+            "owner_id": "mm",
+            "side": "SELL",
+            "price": Price::from_int(price),
+            "quantity": Quantity::from_int(1)
+        })
+    };
+    let config = json!({
+        "markets": [{ "symbol": "BTCUSDT", "base_asset": "BTC", "quote_asset": "USDT" }],
+        "accounts": [
+            { "owner_id": "mm", "deposits": [{ "asset": "BTC", "amount": Value::from_int(10) }] },
+            {
+                "owner_id": "trader1",
+                "deposits": [{ "asset": "USDT", "amount": Value::from_int(100000) }]
+            }
+        ],
+        "seed_orders": [seed_ask(51000), seed_ask(52000)]
+    });
+    let config =
+        exchange_sim::infrastructure::SimulatorConfig::from_json(&config.to_string()).unwrap();
+    let exchange = exchange_sim::Exchange::from_config(config).await.unwrap();
+    let mut events = exchange.event_publisher().subscribe();
+    let app = exchange.rest_router();
+
+    let (status, snapshot) = get_json(&app, "/api/v3/mbo?symbol=BTCUSDT", "trader1").await;
+    assert_eq!(status, StatusCode::OK);
+    let last_update_id = snapshot["lastUpdateId"].as_u64().unwrap();
+    let seeded: Vec<u64> = snapshot["asks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|level| level[0].as_u64().unwrap())
+        .collect();
+    assert_eq!(seeded.len(), 2);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/v3/order",
+        "trader1",
+        json!({
+            "symbol": "BTCUSDT",
+            "side": "BUY",
+            "type": "LIMIT",
+            "timeInForce": "GTC",
+            "quantity": "1.0",
+            "price": "50000.0"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Seed orders are in the snapshot; the feed carries only the new order
+    let mut deltas = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let exchange_sim::domain::ExchangeEvent::MarketByOrder(delta) = event {
+            deltas.push(delta);
+        }
+    }
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(deltas[0].action, exchange_sim::domain::MboAction::Add);
+    assert_eq!(deltas[0].side, Side::Buy);
+    assert!(deltas[0].sequence > last_update_id);
+    assert!(!seeded.contains(&deltas[0].order_ref));
+}
@@ -0,0 +1,548 @@
+//! Integration tests for the FIX 4.4 acceptor
+//!
+//! Drives real TCP sessions against an acceptor on an ephemeral port:
+//! - Logon and sequence number handling across reconnects
+//! - Order entry, passive fills and cancels via ExecutionReports
+//! - Market data snapshot and incremental refresh
+//! - ResendRequest / gap fill recovery
+
+use exchange_sim::{
+    OrderBookReader, Value,
+    application::ports::AccountRepository,
+    domain::{Symbol, TradingPairConfig},
+    infrastructure::{
+        BroadcastEventPublisher, InMemoryAccountRepository, InMemoryInstrumentRepository,
+        InMemoryOrderBookRepository, SimulationClock, TokenBucketRateLimiter,
+    },
+    presentation::fix::{
+        FixAcceptor, FixConfig, FixDecoder, FixHeader, FixMessage, msg_type, tags,
+    },
+    presentation::rest::AppState,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const SYMBOL: &str = "BTCUSDT";
+
+// ============================================================================
+// Test Fixtures
+// ============================================================================
+
+async fn start_acceptor() -> String {
+    let state = Arc::new(AppState::new(
+        Arc::new(SimulationClock::new()),
+        Arc::new(InMemoryAccountRepository::new()),
+        Arc::new(InMemoryOrderBookRepository::new()),
+        Arc::new(InMemoryInstrumentRepository::new()),
+        Arc::new(BroadcastEventPublisher::new(1000)),
+        Arc::new(TokenBucketRateLimiter::default()),
+    ));
+
+    let symbol = Symbol::new(SYMBOL).unwrap();
+    state
+        .instrument_repo
+        .add(TradingPairConfig::new(symbol.clone(), "BTC", "USDT"));
+    let _ = state.order_book_repo.get_or_create(&symbol).await;
+
+    for owner in ["MAKER", "TAKER"] {
+        let mut account = state.account_repo.get_or_create(owner).await;
+        account.deposit("USDT", Value::from_int(1_000_000));
+        account.deposit("BTC", Value::from_int(100));
+        state.account_repo.save(account).await;
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let acceptor = Arc::new(FixAcceptor::new(FixConfig::default(), state));
+    tokio::spawn(acceptor.serve(listener));
+    addr
+}
+
+/// Minimal FIX initiator for driving the acceptor
+struct FixClient {
+    stream: TcpStream,
+    decoder: FixDecoder,
+    comp_id: String,
+    next_seq: u64,
+}
+
+impl FixClient {
+    async fn connect(addr: &str, comp_id: &str) -> Self {
+        FixClient {
+            stream: TcpStream::connect(addr).await.unwrap(),
+            decoder: FixDecoder::new(),
+            comp_id: comp_id.to_string(),
+            next_seq: 1,
+        }
+    }
+
+    async fn logon(addr: &str, comp_id: &str) -> Self {
+        let mut client = Self::connect(addr, comp_id).await;
+        client
+            .send(
+                FixMessage::new(msg_type::LOGON)
+                    .with(tags::ENCRYPT_METHOD, 0)
+                    .with(tags::HEART_BT_INT, 30)
+                    .with(tags::RESET_SEQ_NUM_FLAG, "Y"),
+            )
+            .await;
+        let reply = client.recv().await;
+        assert_eq!(reply.msg_type(), msg_type::LOGON);
+        client
+    }
+
+    async fn send_with_seq(&mut self, message: FixMessage, seq_num: u64) {
+        let sending_time = chrono::Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string();
+        let bytes = message.encode(&FixHeader {
+            sender_comp_id: &self.comp_id,
+            target_comp_id: "ATHENA",
+            seq_num,
+            sending_time: &sending_time,
+            poss_dup: false,
+        });
+        self.stream.write_all(&bytes).await.unwrap();
+    }
+
+    async fn send(&mut self, message: FixMessage) {
+        let seq_num = self.next_seq;
+        self.next_seq += 1;
+        self.send_with_seq(message, seq_num).await;
+    }
+
+    async fn recv(&mut self) -> FixMessage {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            let mut buf = [0u8; 4096];
+            loop {
+                if let Some(frame) = self.decoder.next_frame().unwrap() {
+                    return FixMessage::decode(&frame).unwrap();
+                }
+                let n = self.stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed");
+                self.decoder.extend(&buf[..n]);
+            }
+        })
+        .await
+        .expect("timed out waiting for FIX message")
+    }
+
+    /// Receive until a message of the given type arrives, skipping heartbeats
+    async fn recv_type(&mut self, msg_type: &str) -> FixMessage {
+        loop {
+            let message = self.recv().await;
+            if message.msg_type() == msg_type {
+                return message;
+            }
+        }
+    }
+}
+
+fn limit_order(cl_ord_id: &str, side: &str, qty: &str, price: &str) -> FixMessage {
+    FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+        .with(tags::CL_ORD_ID, cl_ord_id)
+        .with(tags::SYMBOL, SYMBOL)
+        .with(tags::SIDE, side)
+        .with(tags::ORDER_QTY, qty)
+        .with(tags::ORD_TYPE, "2")
+        .with(tags::PRICE, price)
+        .with(tags::TIME_IN_FORCE, "1")
+        .with(tags::TRANSACT_TIME, "20240101-00:00:00.000")
+}
+
+// ============================================================================
+// Session Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_logon_rejects_unknown_target() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::connect(&addr, "MAKER").await;
+
+    let bytes = FixMessage::new(msg_type::LOGON)
+        .with(tags::HEART_BT_INT, 30)
+        .encode(&FixHeader {
+            sender_comp_id: "MAKER",
+            target_comp_id: "SOMEONE_ELSE",
+            seq_num: 1,
+            sending_time: "20240101-00:00:00.000",
+            poss_dup: false,
+        });
+    client.stream.write_all(&bytes).await.unwrap();
+
+    let reply = client.recv().await;
+    assert_eq!(reply.msg_type(), msg_type::LOGOUT);
+}
+
+#[tokio::test]
+async fn test_test_request_answered_with_heartbeat() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    client
+        .send(FixMessage::new(msg_type::TEST_REQUEST).with(tags::TEST_REQ_ID, "ping"))
+        .await;
+
+    let reply = client.recv_type(msg_type::HEARTBEAT).await;
+    assert_eq!(reply.get(tags::TEST_REQ_ID), Some("ping"));
+}
+
+#[tokio::test]
+async fn test_sequence_numbers_persist_across_reconnect() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+    client.send(limit_order("c1", "1", "1", "40000")).await;
+    client.recv_type(msg_type::EXECUTION_REPORT).await;
+    client.send(FixMessage::new(msg_type::LOGOUT)).await;
+    client.recv_type(msg_type::LOGOUT).await;
+    let next_seq = client.next_seq;
+    drop(client);
+    // Let the acceptor release the session after the Logout exchange
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Reconnect without ResetSeqNumFlag and continue the sequence
+    let mut client = FixClient::connect(&addr, "MAKER").await;
+    client.next_seq = next_seq;
+    client
+        .send(
+            FixMessage::new(msg_type::LOGON)
+                .with(tags::ENCRYPT_METHOD, 0)
+                .with(tags::HEART_BT_INT, 30),
+        )
+        .await;
+    let reply = client.recv().await;
+    assert_eq!(reply.msg_type(), msg_type::LOGON);
+    // Logon, ExecutionReport and Logout were sent on the first connection
+    assert_eq!(reply.seq_num().unwrap(), 4);
+}
+
+#[tokio::test]
+async fn test_inbound_gap_triggers_resend_request() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    // Skip sequence numbers 2-4
+    client
+        .send_with_seq(FixMessage::new(msg_type::HEARTBEAT), 5)
+        .await;
+
+    let request = client.recv_type(msg_type::RESEND_REQUEST).await;
+    assert_eq!(request.get(tags::BEGIN_SEQ_NO), Some("2"));
+    assert_eq!(request.get(tags::END_SEQ_NO), Some("0"));
+
+    // Gap fill 2-4, then the server expects 5 again
+    client
+        .send_with_seq(
+            FixMessage::new(msg_type::SEQUENCE_RESET)
+                .with(tags::GAP_FILL_FLAG, "Y")
+                .with(tags::NEW_SEQ_NO, 5),
+            2,
+        )
+        .await;
+    client.next_seq = 5;
+    client
+        .send(FixMessage::new(msg_type::TEST_REQUEST).with(tags::TEST_REQ_ID, "after-gap"))
+        .await;
+    let reply = client.recv_type(msg_type::HEARTBEAT).await;
+    assert_eq!(reply.get(tags::TEST_REQ_ID), Some("after-gap"));
+}
+
+#[tokio::test]
+async fn test_resend_request_replays_application_messages() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+    client.send(limit_order("c1", "1", "1", "40000")).await;
+    let original = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(original.seq_num().unwrap(), 2);
+
+    client
+        .send(
+            FixMessage::new(msg_type::RESEND_REQUEST)
+                .with(tags::BEGIN_SEQ_NO, 1)
+                .with(tags::END_SEQ_NO, 0),
+        )
+        .await;
+
+    // Seq 1 (Logon) is gap filled, seq 2 is resent as a possible duplicate
+    let gap_fill = client.recv_type(msg_type::SEQUENCE_RESET).await;
+    assert_eq!(gap_fill.seq_num().unwrap(), 1);
+    assert_eq!(gap_fill.get(tags::NEW_SEQ_NO), Some("2"));
+    assert!(gap_fill.get_bool(tags::GAP_FILL_FLAG));
+
+    let resent = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(resent.seq_num().unwrap(), 2);
+    assert!(resent.get_bool(tags::POSS_DUP_FLAG));
+    assert_eq!(resent.get(tags::CL_ORD_ID), Some("c1"));
+    assert!(resent.get(tags::ORIG_SENDING_TIME).is_some());
+}
+
+// ============================================================================
+// Order Entry Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_new_order_acknowledged() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    client.send(limit_order("c1", "2", "1.5", "50000")).await;
+
+    let report = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(report.get(tags::CL_ORD_ID), Some("c1"));
+    assert_eq!(report.get(tags::EXEC_TYPE), Some("0"));
+    assert_eq!(report.get(tags::ORD_STATUS), Some("0"));
+    assert_eq!(report.get(tags::SYMBOL), Some(SYMBOL));
+    assert!(report.get(tags::ORDER_ID).is_some());
+}
+
+#[tokio::test]
+async fn test_unknown_symbol_rejected() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    let mut order = limit_order("c1", "1", "1", "100");
+    order.set(tags::SYMBOL, "ETHUSDT");
+    client.send(order).await;
+
+    let report = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(report.get(tags::EXEC_TYPE), Some("8"));
+    assert_eq!(report.get(tags::ORD_STATUS), Some("8"));
+}
+
+#[tokio::test]
+async fn test_passive_fill_reported_to_maker() {
+    let addr = start_acceptor().await;
+    let mut maker = FixClient::logon(&addr, "MAKER").await;
+    let mut taker = FixClient::logon(&addr, "TAKER").await;
+
+    maker.send(limit_order("m1", "2", "1", "50000")).await;
+    let ack = maker.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(ack.get(tags::EXEC_TYPE), Some("0"));
+
+    taker.send(limit_order("t1", "1", "1", "50000")).await;
+    let taker_ack = taker.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(taker_ack.get(tags::EXEC_TYPE), Some("0"));
+    let taker_fill = taker.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(taker_fill.get(tags::EXEC_TYPE), Some("F"));
+    assert_eq!(taker_fill.get(tags::ORD_STATUS), Some("2"));
+
+    let maker_fill = maker.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(maker_fill.get(tags::CL_ORD_ID), Some("m1"));
+    assert_eq!(maker_fill.get(tags::EXEC_TYPE), Some("F"));
+    assert_eq!(maker_fill.get(tags::ORD_STATUS), Some("2"));
+    assert_eq!(maker_fill.get(tags::LAST_PX), Some("50000.00000000"));
+    assert_eq!(maker_fill.get(tags::LAST_QTY), Some("1.00000000"));
+}
+
+#[tokio::test]
+async fn test_cancel_by_orig_cl_ord_id() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    client.send(limit_order("c1", "1", "1", "40000")).await;
+    client.recv_type(msg_type::EXECUTION_REPORT).await;
+
+    client
+        .send(
+            FixMessage::new(msg_type::ORDER_CANCEL_REQUEST)
+                .with(tags::ORIG_CL_ORD_ID, "c1")
+                .with(tags::CL_ORD_ID, "c2")
+                .with(tags::SYMBOL, SYMBOL)
+                .with(tags::SIDE, "1")
+                .with(tags::TRANSACT_TIME, "20240101-00:00:00.000"),
+        )
+        .await;
+
+    let report = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(report.get(tags::EXEC_TYPE), Some("4"));
+    assert_eq!(report.get(tags::ORD_STATUS), Some("4"));
+    assert_eq!(report.get(tags::CL_ORD_ID), Some("c2"));
+    assert_eq!(report.get(tags::ORIG_CL_ORD_ID), Some("c1"));
+
+    // A second cancel for the same order is rejected
+    client
+        .send(
+            FixMessage::new(msg_type::ORDER_CANCEL_REQUEST)
+                .with(tags::ORIG_CL_ORD_ID, "c1")
+                .with(tags::CL_ORD_ID, "c3")
+                .with(tags::SYMBOL, SYMBOL)
+                .with(tags::SIDE, "1")
+                .with(tags::TRANSACT_TIME, "20240101-00:00:00.000"),
+        )
+        .await;
+    let reject = client.recv_type(msg_type::ORDER_CANCEL_REJECT).await;
+    assert_eq!(reject.get(tags::CL_ORD_ID), Some("c3"));
+}
+
+#[tokio::test]
+async fn test_cancel_replace_reprices_order() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    client.send(limit_order("c1", "1", "2", "40000")).await;
+    client.recv_type(msg_type::EXECUTION_REPORT).await;
+
+    let mut replace = FixMessage::new(msg_type::ORDER_CANCEL_REPLACE_REQUEST)
+        .with(tags::ORIG_CL_ORD_ID, "c1")
+        .with(tags::CL_ORD_ID, "c2");
+    for (tag, value) in limit_order("c2", "1", "3", "41000").fields() {
+        if *tag != tags::CL_ORD_ID {
+            replace.push(*tag, value);
+        }
+    }
+    client.send(replace).await;
+
+    let report = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(report.get(tags::EXEC_TYPE), Some("5"));
+    assert_eq!(report.get(tags::CL_ORD_ID), Some("c2"));
+    assert_eq!(report.get(tags::ORIG_CL_ORD_ID), Some("c1"));
+    assert_eq!(report.get(tags::PRICE), Some("41000.00000000"));
+    assert_eq!(report.get(tags::LEAVES_QTY), Some("3.00000000"));
+}
+
+#[tokio::test]
+async fn test_replace_to_smaller_quantity_amends_in_place() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    client.send(limit_order("c1", "1", "2", "40000")).await;
+    let ack = client.recv_type(msg_type::EXECUTION_REPORT).await;
+
+    let mut replace = FixMessage::new(msg_type::ORDER_CANCEL_REPLACE_REQUEST)
+        .with(tags::ORIG_CL_ORD_ID, "c1")
+        .with(tags::CL_ORD_ID, "c2");
+    for (tag, value) in limit_order("c2", "1", "0.5", "40000").fields() {
+        if *tag != tags::CL_ORD_ID {
+            replace.push(*tag, value);
+        }
+    }
+    client.send(replace).await;
+
+    // Same order, reduced: no cancel of the original
+    let report = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(report.get(tags::EXEC_TYPE), Some("5"));
+    assert_eq!(report.get(tags::ORDER_ID), ack.get(tags::ORDER_ID));
+    assert_eq!(report.get(tags::CL_ORD_ID), Some("c2"));
+    assert_eq!(report.get(tags::ORIG_CL_ORD_ID), Some("c1"));
+    assert_eq!(report.get(tags::LEAVES_QTY), Some("0.50000000"));
+
+    // The order now answers to the new ClOrdID
+    client
+        .send(
+            FixMessage::new(msg_type::ORDER_CANCEL_REQUEST)
+                .with(tags::ORIG_CL_ORD_ID, "c2")
+                .with(tags::CL_ORD_ID, "c3")
+                .with(tags::SYMBOL, SYMBOL)
+                .with(tags::SIDE, "1")
+                .with(tags::TRANSACT_TIME, "20240101-00:00:00.000"),
+        )
+        .await;
+    let canceled = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(canceled.get(tags::EXEC_TYPE), Some("4"));
+    assert_eq!(canceled.get(tags::ORDER_ID), ack.get(tags::ORDER_ID));
+}
+
+#[tokio::test]
+async fn test_rejected_replacement_keeps_original() {
+    let addr = start_acceptor().await;
+    let mut client = FixClient::logon(&addr, "MAKER").await;
+
+    client.send(limit_order("c1", "1", "2", "40000")).await;
+    let ack = client.recv_type(msg_type::EXECUTION_REPORT).await;
+
+    // 100 BTC at 41000 needs more USDT than the account holds
+    let mut replace = FixMessage::new(msg_type::ORDER_CANCEL_REPLACE_REQUEST)
+        .with(tags::ORIG_CL_ORD_ID, "c1")
+        .with(tags::CL_ORD_ID, "c2");
+    for (tag, value) in limit_order("c2", "1", "100", "41000").fields() {
+        if *tag != tags::CL_ORD_ID {
+            replace.push(*tag, value);
+        }
+    }
+    client.send(replace).await;
+
+    let reject = client.recv_type(msg_type::ORDER_CANCEL_REJECT).await;
+    assert_eq!(reject.get(tags::CXL_REJ_RESPONSE_TO), Some("2"));
+    assert_eq!(reject.get(tags::CL_ORD_ID), Some("c2"));
+    assert_eq!(reject.get(tags::ORIG_CL_ORD_ID), Some("c1"));
+    assert_eq!(reject.get(tags::ORD_STATUS), Some("0"));
+
+    // The original is still resting under its own ClOrdID
+    client
+        .send(
+            FixMessage::new(msg_type::ORDER_CANCEL_REQUEST)
+                .with(tags::ORIG_CL_ORD_ID, "c1")
+                .with(tags::CL_ORD_ID, "c3")
+                .with(tags::SYMBOL, SYMBOL)
+                .with(tags::SIDE, "1")
+                .with(tags::TRANSACT_TIME, "20240101-00:00:00.000"),
+        )
+        .await;
+    let canceled = client.recv_type(msg_type::EXECUTION_REPORT).await;
+    assert_eq!(canceled.get(tags::EXEC_TYPE), Some("4"));
+    assert_eq!(canceled.get(tags::ORDER_ID), ack.get(tags::ORDER_ID));
+    assert_eq!(canceled.get(tags::LEAVES_QTY), Some("0.00000000"));
+}
+
+// ============================================================================
+// Market Data Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_market_data_snapshot_and_incremental() {
+    let addr = start_acceptor().await;
+    let mut maker = FixClient::logon(&addr, "MAKER").await;
+    let mut md = FixClient::logon(&addr, "TAKER").await;
+
+    maker.send(limit_order("m1", "1", "2", "49000")).await;
+    maker.recv_type(msg_type::EXECUTION_REPORT).await;
+
+    md.send(
+        FixMessage::new(msg_type::MARKET_DATA_REQUEST)
+            .with(tags::MD_REQ_ID, "md1")
+            .with(tags::SUBSCRIPTION_REQUEST_TYPE, "1")
+            .with(tags::MARKET_DEPTH, 10)
+            .with(tags::NO_MD_ENTRY_TYPES, 2)
+            .with(tags::MD_ENTRY_TYPE, "0")
+            .with(tags::MD_ENTRY_TYPE, "1")
+            .with(tags::NO_RELATED_SYM, 1)
+            .with(tags::SYMBOL, SYMBOL),
+    )
+    .await;
+
+    let snapshot = md.recv_type(msg_type::MARKET_DATA_SNAPSHOT).await;
+    assert_eq!(snapshot.get(tags::MD_REQ_ID), Some("md1"));
+    assert_eq!(snapshot.get(tags::NO_MD_ENTRIES), Some("1"));
+    assert_eq!(snapshot.get(tags::MD_ENTRY_TYPE), Some("0"));
+    assert_eq!(snapshot.get(tags::MD_ENTRY_PX), Some("49000.00000000"));
+
+    maker.send(limit_order("m2", "2", "1", "51000")).await;
+    maker.recv_type(msg_type::EXECUTION_REPORT).await;
+
+    let incremental = md.recv_type(msg_type::MARKET_DATA_INCREMENTAL).await;
+    assert_eq!(incremental.get(tags::MD_UPDATE_ACTION), Some("0"));
+    assert_eq!(incremental.get(tags::MD_ENTRY_TYPE), Some("1"));
+    assert_eq!(incremental.get(tags::MD_ENTRY_PX), Some("51000.00000000"));
+    assert_eq!(incremental.get(tags::MD_ENTRY_SIZE), Some("1.00000000"));
+}
+
+#[tokio::test]
+async fn test_market_data_unknown_symbol_rejected() {
+    let addr = start_acceptor().await;
+    let mut md = FixClient::logon(&addr, "TAKER").await;
+
+    md.send(
+        FixMessage::new(msg_type::MARKET_DATA_REQUEST)
+            .with(tags::MD_REQ_ID, "md1")
+            .with(tags::SUBSCRIPTION_REQUEST_TYPE, "0")
+            .with(tags::MARKET_DEPTH, 1)
+            .with(tags::NO_MD_ENTRY_TYPES, 1)
+            .with(tags::MD_ENTRY_TYPE, "0")
+            .with(tags::NO_RELATED_SYM, 1)
+            .with(tags::SYMBOL, "DOGEUSDT"),
+    )
+    .await;
+
+    let reject = md.recv_type(msg_type::MARKET_DATA_REQUEST_REJECT).await;
+    assert_eq!(reject.get(tags::MD_REQ_ID), Some("md1"));
+}
@@ -0,0 +1,201 @@
+//! Integration tests for portfolio margin accounts
+//!
+//! Tests cover:
+//! - Status refresh from the periodic option mark pass
+//! - Pre-trade rejection when the risk array is not covered, before and
+//!   after the refresh has cached its calculator
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use exchange_sim::domain::{
+    AccountStatus, ExerciseStyle, MarginMode, OptionConfig, OptionContract, OptionType,
+    PositionSide,
+};
+use exchange_sim::{
+    AccountRepository, Clock, Exchange, ExchangeConfig, OptionPricer, Order, OrderBook,
+    OrderBookWriter, Price, Quantity, Side, SimulationClock, Symbol, TimeInForce,
+    TradingPairConfig, Value,
+};
+use serde_json::json;
+use tower::ServiceExt;
+
+const CALL: &str = "BTC-50000-C";
+
+/// Replace a book with one bid and one ask
+async fn quote(exchange: &Exchange<SimulationClock>, symbol: &Symbol, bid: f64, ask: f64) {
+    let mut book = OrderBook::new(symbol.clone());
+    for (side, price) in [(Side::Buy, bid), (Side::Sell, ask)] {
+        book.add_order(Order::new_limit(
+            symbol.clone(),
+            side,
+            Quantity::from_int(1),
+            Price::from_f64(price),
+            TimeInForce::Gtc,
+        ));
+    }
+    OrderBookWriter::save(exchange.order_book_repo.as_ref(), book).await;
+}
+
+/// BTC spot at 50000 and a 30 day at-the-money call quoted at 60% vol
+async fn setup_exchange() -> (Exchange<SimulationClock>, f64) {
+    let exchange = Exchange::fixed_time(ExchangeConfig::default());
+    let now = exchange.clock.now();
+    let expiry = now + chrono::Duration::days(30);
+
+    let spot = Symbol::new("BTCUSDT").unwrap();
+    exchange
+        .instrument_repo
+        .add(TradingPairConfig::new(spot.clone(), "BTC", "USDT"));
+    quote(&exchange, &spot, 49_999.0, 50_001.0).await;
+
+    let call = Symbol::new(CALL).unwrap();
+    exchange.instrument_repo.add(TradingPairConfig::option(
+        call.clone(),
+        "BTC",
+        "USDT",
+        OptionConfig {
+            strike: Price::from_int(50_000),
+            option_type: OptionType::Call,
+            expiration_ms: expiry.timestamp_millis(),
+            exercise_style: ExerciseStyle::European,
+        },
+    ));
+    let contract = OptionContract::new("BTC", expiry, Price::from_int(50_000), OptionType::Call);
+    let fair = OptionPricer::default().price(&contract, 50_000.0, 0.6, now);
+    quote(&exchange, &call, fair - 1.0, fair + 1.0).await;
+
+    (exchange, fair)
+}
+
+#[tokio::test]
+async fn test_portfolio_account_crosses_maintenance_margin() {
+    let (exchange, fair) = setup_exchange().await;
+    let now = exchange.clock.now();
+    let call = Symbol::new(CALL).unwrap();
+
+    {
+        let mut writer = exchange.account_repo.get_or_create("writer").await;
+        writer.margin_mode = MarginMode::Portfolio;
+        writer.deposit("USDT", Value::from_int(10_000));
+        writer.open_position(
+            call.clone(),
+            PositionSide::Short,
+            Quantity::from_int(1),
+            Price::from_f64(fair),
+            Value::ZERO,
+            now,
+        );
+        exchange.account_repo.save(writer).await;
+    }
+
+    exchange.option_marks().refresh().await;
+    let writer = exchange.account_repo.get_by_owner("writer").await.unwrap();
+    assert_eq!(writer.status, AccountStatus::Active);
+
+    // A 20% rally puts the short call deep in the money
+    quote(
+        &exchange,
+        &Symbol::new("BTCUSDT").unwrap(),
+        59_999.0,
+        60_001.0,
+    )
+    .await;
+    exchange.option_marks().refresh().await;
+    let writer = exchange.account_repo.get_by_owner("writer").await.unwrap();
+    assert_eq!(writer.status, AccountStatus::Liquidating);
+    assert!(writer.position(&call).unwrap().mark_price > Price::from_f64(fair));
+}
+
+#[tokio::test]
+async fn test_portfolio_order_rejected_without_margin() {
+    let (exchange, fair) = setup_exchange().await;
+
+    for (owner, mode) in [
+        ("portfolio", MarginMode::Portfolio),
+        ("cross", MarginMode::Cross),
+    ] {
+        let mut account = exchange.account_repo.get_or_create(owner).await;
+        account.margin_mode = mode;
+        account.deposit("BTC", Value::from_int(10));
+        exchange.account_repo.save(account).await;
+    }
+
+    let app = exchange.rest_router();
+    let order = |owner: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/v3/order")
+            .header("Content-Type", "application/json")
+            .header("X-MBX-APIKEY", owner)
+            .body(Body::from(
+                json!({
+                    "symbol": CALL,
+                    "side": "SELL",
+                    "type": "LIMIT",
+                    "timeInForce": "GTC",
+                    "quantity": "5",
+                    "price": format!("{:.2}", fair * 2.0),
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    // Five naked calls against no cash collateral
+    let response = app.clone().oneshot(order("portfolio")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let account = exchange
+        .account_repo
+        .get_by_owner("portfolio")
+        .await
+        .unwrap();
+    assert_eq!(account.balance("BTC").locked, Value::ZERO);
+
+    // The same order is only balance-checked outside portfolio margin
+    let response = app.oneshot(order("cross")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_portfolio_order_checked_against_refreshed_state() {
+    let (exchange, fair) = setup_exchange().await;
+    let mut account = exchange.account_repo.get_or_create("portfolio").await;
+    account.margin_mode = MarginMode::Portfolio;
+    account.deposit("BTC", Value::from_int(10));
+    exchange.account_repo.save(account).await;
+
+    assert!(exchange.portfolio_margin.get().is_none());
+    exchange.option_marks().refresh().await;
+    assert!(exchange.portfolio_margin.get().is_some());
+
+    let app = exchange.rest_router();
+    let order = |quantity: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/v3/order")
+            .header("Content-Type", "application/json")
+            .header("X-MBX-APIKEY", "portfolio")
+            .body(Body::from(
+                json!({
+                    "symbol": CALL,
+                    "side": "SELL",
+                    "type": "LIMIT",
+                    "timeInForce": "GTC",
+                    "quantity": quantity,
+                    "price": format!("{:.2}", fair * 2.0),
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+
+    // The refreshed calculator prices the naked calls the same way
+    let response = app.clone().oneshot(order("5")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let account = exchange
+        .account_repo
+        .get_by_owner("portfolio")
+        .await
+        .unwrap();
+    assert_eq!(account.balance("BTC").locked, Value::ZERO);
+}
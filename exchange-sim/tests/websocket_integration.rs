@@ -6,7 +6,10 @@
 use axum::{Router, routing::get};
 use exchange_sim::{
     OrderBookReader, OrderBookWriter,
-    domain::{Price, Quantity, Side, Symbol, TimeInForce, TradingPairConfig},
+    domain::{
+        MarketByOrderEvent, MboAction, Price, Quantity, Side, Symbol, TimeInForce,
+        TradingPairConfig,
+    },
     infrastructure::{
         BroadcastEventPublisher, InMemoryAccountRepository, InMemoryInstrumentRepository,
         InMemoryOrderBookRepository, SimulationClock, TokenBucketRateLimiter,
@@ -647,3 +650,120 @@ async fn test_depth_update_sequence_sync() {
         initial_update_id
     );
 }
+
+#[tokio::test]
+async fn test_market_by_order_snapshot_and_stream() {
+    let (addr, order_book_repo) = start_full_test_server().await;
+    let client = reqwest::Client::new();
+
+    client
+        .post(format!("http://{}/admin/accounts", addr))
+        .json(&json!({
+            "owner_id": "mbo_trader",
+            "deposits": [{ "asset": "USDT", "amount": 100000.0 }]
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    // Two asks queued at the same price
+    let sym = Symbol::new("BTCUSDT").unwrap();
+    let mut book = order_book_repo.get(&sym).await.unwrap();
+    for qty in [1, 2] {
+        book.add_order(exchange_sim::domain::Order::new_limit(
+            sym.clone(),
+            Side::Sell,
+            Quantity::from_int(qty),
+            Price::from_int(50000),
+            TimeInForce::Gtc,
+        ));
+    }
+    order_book_repo.save(book).await;
+
+    // Snapshot lists every order in queue order
+    let snapshot: Value = client
+        .get(format!("http://{}/api/v3/mbo?symbol=BTCUSDT", addr))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let last_update_id = snapshot["lastUpdateId"].as_u64().unwrap();
+    let asks = snapshot["asks"].as_array().unwrap();
+    assert_eq!(asks.len(), 2);
+    assert_eq!(asks[0][2], "1.00000000");
+    assert_eq!(asks[1][2], "2.00000000");
+    let head_ref = asks[0][0].as_u64().unwrap();
+
+    let ws_url = format!("ws://{}/ws", addr);
+    let (mut text_ws, _) = connect_async(&ws_url).await.unwrap();
+    let (mut binary_ws, _) = connect_async(&ws_url).await.unwrap();
+    for (ws, stream) in [
+        (&mut text_ws, "btcusdt@mbo"),
+        (&mut binary_ws, "btcusdt@mbo@bin"),
+    ] {
+        let subscribe_msg = json!({ "method": "SUBSCRIBE", "params": [stream], "id": 1 });
+        ws.send(Message::Text(subscribe_msg.to_string().into()))
+            .await
+            .unwrap();
+        let _ = tokio::time::timeout(Duration::from_secs(1), ws.next()).await;
+    }
+
+    // Take out the head of the queue
+    let order_resp = client
+        .post(format!("http://{}/api/v3/order", addr))
+        .header("X-MBX-APIKEY", "mbo_trader")
+        .json(&json!({
+            "symbol": "BTCUSDT",
+            "side": "BUY",
+            "type": "LIMIT",
+            "quantity": "1.0",
+            "price": "50000",
+            "timeInForce": "GTC"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(order_resp.status(), 200);
+
+    // Events at or below the snapshot's lastUpdateId are already applied
+    let execute = loop {
+        let msg = tokio::time::timeout(Duration::from_secs(2), text_ws.next())
+            .await
+            .expect("timed out waiting for mbo event")
+            .unwrap()
+            .unwrap();
+        let Message::Text(text) = msg else { continue };
+        let json: Value = serde_json::from_str(&text).unwrap();
+        let data = &json["data"];
+        if data["u"].as_u64().unwrap() > last_update_id {
+            break data.clone();
+        }
+    };
+    assert_eq!(execute["e"], "mbo");
+    assert_eq!(execute["x"], "E");
+    assert_eq!(execute["i"].as_u64().unwrap(), head_ref);
+    assert_eq!(execute["r"], "0.00000000");
+    assert!(execute["t"].is_string());
+
+    let event = loop {
+        let msg = tokio::time::timeout(Duration::from_secs(2), binary_ws.next())
+            .await
+            .expect("timed out waiting for binary mbo event")
+            .unwrap()
+            .unwrap();
+        let Message::Binary(bytes) = msg else {
+            continue;
+        };
+        let (event, used) = MarketByOrderEvent::decode_binary(&bytes).unwrap();
+        assert_eq!(used, bytes.len());
+        if event.sequence > last_update_id {
+            break event;
+        }
+    };
+    assert_eq!(event.action, MboAction::Execute);
+    assert_eq!(event.order_ref, head_ref);
+    assert_eq!(event.price, Price::from_int(50000));
+    assert!(event.remaining.is_zero());
+}
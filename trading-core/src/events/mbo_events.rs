@@ -0,0 +1,186 @@
+use crate::value_objects::{Price, Quantity, Side, Symbol, Timestamp, TradeId};
+use serde::{Deserialize, Serialize};
+
+/// Market-by-order (L3) action on a single resting order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MboAction {
+    /// Order added to the back of its price level queue
+    #[serde(rename = "A")]
+    Add,
+    /// Resting quantity reduced without losing queue position
+    #[serde(rename = "R")]
+    Reduce,
+    /// Order removed from the book (cancel or expiry)
+    #[serde(rename = "D")]
+    Delete,
+    /// Resting order executed against an aggressor
+    #[serde(rename = "E")]
+    Execute,
+}
+
+impl MboAction {
+    pub fn as_byte(&self) -> u8 {
+        match self {
+            MboAction::Add => b'A',
+            MboAction::Reduce => b'R',
+            MboAction::Delete => b'D',
+            MboAction::Execute => b'E',
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'A' => Some(MboAction::Add),
+            b'R' => Some(MboAction::Reduce),
+            b'D' => Some(MboAction::Delete),
+            b'E' => Some(MboAction::Execute),
+            _ => None,
+        }
+    }
+}
+
+/// Market-by-order (L3) event
+///
+/// Orders are keyed by `order_ref`, an anonymised per-book reference that
+/// stays stable for the life of the resting order. `sequence` is the order
+/// book sequence number after the change, so consumers can detect gaps and
+/// align with a snapshot's `last_update_id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketByOrderEvent {
+    pub symbol: Symbol,
+    pub sequence: u64,
+    pub action: MboAction,
+    pub order_ref: u64,
+    pub side: Side,
+    pub price: Price,
+    /// Add: quantity placed; Reduce/Delete/Execute: quantity removed
+    pub quantity: Quantity,
+    /// Quantity left resting after this event
+    pub remaining: Quantity,
+    /// Trade that caused an Execute
+    pub trade_id: Option<TradeId>,
+    pub timestamp: Timestamp,
+}
+
+impl MarketByOrderEvent {
+    /// Fixed part of the binary encoding (everything except the symbol bytes)
+    pub const BINARY_HEADER_LEN: usize = 3 + 8 * 6 + 16;
+
+    /// Append the binary encoding to `buf`
+    ///
+    /// Layout (little-endian): action u8, side u8 ('B'/'S'), symbol length u8,
+    /// symbol bytes, sequence u64, order_ref u64, price i64, quantity i64,
+    /// remaining i64, timestamp i64 (ns since epoch), trade id 16 bytes
+    /// (all zero when absent). Prices and quantities are raw fixed-point.
+    pub fn encode_binary(&self, buf: &mut Vec<u8>) {
+        let symbol = self.symbol.as_str().as_bytes();
+        buf.reserve(Self::BINARY_HEADER_LEN + symbol.len());
+        buf.push(self.action.as_byte());
+        buf.push(if self.side.is_buy() { b'B' } else { b'S' });
+        buf.push(symbol.len() as u8);
+        buf.extend_from_slice(symbol);
+        buf.extend_from_slice(&self.sequence.to_le_bytes());
+        buf.extend_from_slice(&self.order_ref.to_le_bytes());
+        buf.extend_from_slice(&self.price.raw().to_le_bytes());
+        buf.extend_from_slice(&self.quantity.raw().to_le_bytes());
+        buf.extend_from_slice(&self.remaining.raw().to_le_bytes());
+        let nanos = self.timestamp.timestamp_nanos_opt().unwrap_or(0);
+        buf.extend_from_slice(&nanos.to_le_bytes());
+        buf.extend_from_slice(self.trade_id.unwrap_or_default().as_bytes());
+    }
+
+    /// Decode one binary-encoded event, returning it and the bytes consumed
+    pub fn decode_binary(bytes: &[u8]) -> Result<(Self, usize), &'static str> {
+        if bytes.len() < 3 {
+            return Err("truncated MBO message");
+        }
+        let action = MboAction::from_byte(bytes[0]).ok_or("invalid MBO action")?;
+        let side = match bytes[1] {
+            b'B' => Side::Buy,
+            b'S' => Side::Sell,
+            _ => return Err("invalid MBO side"),
+        };
+        let symbol_len = bytes[2] as usize;
+        let len = Self::BINARY_HEADER_LEN + symbol_len;
+        if bytes.len() < len {
+            return Err("truncated MBO message");
+        }
+        let symbol = std::str::from_utf8(&bytes[3..3 + symbol_len])
+            .ok()
+            .and_then(|s| Symbol::new(s).ok())
+            .ok_or("invalid MBO symbol")?;
+
+        let mut offset = 3 + symbol_len;
+        let mut next_u64 = || {
+            let value = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+            offset += 8;
+            value
+        };
+        let sequence = next_u64();
+        let order_ref = next_u64();
+        let price = Price::from_raw(next_u64() as i64);
+        let quantity = Quantity::from_raw(next_u64() as i64);
+        let remaining = Quantity::from_raw(next_u64() as i64);
+        let timestamp = chrono::DateTime::from_timestamp_nanos(next_u64() as i64);
+        let trade_id = TradeId::from_bytes(bytes[len - 16..len].try_into().unwrap());
+
+        Ok((
+            MarketByOrderEvent {
+                symbol,
+                sequence,
+                action,
+                order_ref,
+                side,
+                price,
+                quantity,
+                remaining,
+                trade_id: (!trade_id.is_nil()).then_some(trade_id),
+                timestamp,
+            },
+            len,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(action: MboAction, trade_id: Option<TradeId>) -> MarketByOrderEvent {
+        MarketByOrderEvent {
+            symbol: Symbol::new("BTCUSDT").unwrap(),
+            sequence: 42,
+            action,
+            order_ref: 7,
+            side: Side::Sell,
+            price: Price::from_f64(50000.5),
+            quantity: Quantity::from_f64(0.25),
+            remaining: Quantity::from_f64(0.75),
+            trade_id,
+            timestamp: chrono::DateTime::from_timestamp_nanos(1_700_000_000_123_456_789),
+        }
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let mut buf = Vec::new();
+        let add = event(MboAction::Add, None);
+        let exec = event(MboAction::Execute, Some(TradeId::new_v4()));
+        add.encode_binary(&mut buf);
+        exec.encode_binary(&mut buf);
+
+        let (first, used) = MarketByOrderEvent::decode_binary(&buf).unwrap();
+        assert_eq!(used, MarketByOrderEvent::BINARY_HEADER_LEN + 7);
+        assert_eq!(first, add);
+        let (second, _) = MarketByOrderEvent::decode_binary(&buf[used..]).unwrap();
+        assert_eq!(second, exec);
+    }
+
+    #[test]
+    fn test_binary_rejects_truncated() {
+        let mut buf = Vec::new();
+        event(MboAction::Delete, None).encode_binary(&mut buf);
+        assert!(MarketByOrderEvent::decode_binary(&buf[..buf.len() - 1]).is_err());
+        assert!(MarketByOrderEvent::decode_binary(&[b'X', b'B', 0]).is_err());
+    }
+}
@@ -1,8 +1,10 @@
 mod depth_events;
+mod mbo_events;
 mod order_events;
 mod trade_events;
 
 pub use depth_events::{DepthSnapshotEvent, DepthUpdateEvent};
+pub use mbo_events::{MarketByOrderEvent, MboAction};
 pub use order_events::{
     OrderAcceptedEvent, OrderCanceledEvent, OrderExpiredEvent, OrderFilledEvent, OrderRejectedEvent,
 };
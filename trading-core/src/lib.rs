@@ -15,12 +15,13 @@ pub use entities::{Order, OrderStatus, PriceLevel, Trade};
 
 // Re-export events at crate root
 pub use events::{
-    DepthSnapshotEvent, DepthUpdateEvent, OrderAcceptedEvent, OrderCanceledEvent,
-    OrderExpiredEvent, OrderFilledEvent, OrderRejectedEvent, TradeExecutedEvent,
+    DepthSnapshotEvent, DepthUpdateEvent, MarketByOrderEvent, MboAction, OrderAcceptedEvent,
+    OrderCanceledEvent, OrderExpiredEvent, OrderFilledEvent, OrderRejectedEvent,
+    TradeExecutedEvent,
 };
 
 // Re-export stats at crate root
-pub use stats::{Ema, RollingStats};
+pub use stats::{Ema, RollingStats, Vpin};
 
 // Re-export messages at crate root for IPC
 pub use messages::{
@@ -62,7 +62,7 @@ impl From<CompactLevel> for PriceLevel {
 /// - On initial connection
 /// - When strategy requests a resync
 /// - Periodically to allow late-joining subscribers
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderBookSnapshot {
     /// Exchange identifier
     pub exchange: String,
@@ -110,7 +110,7 @@ impl OrderBookSnapshot {
 /// 1. Validate sequence (first_update_id <= expected <= final_update_id)
 /// 2. Apply deltas to local order book
 /// 3. Request snapshot on sequence gap
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DepthUpdate {
     /// Exchange identifier
     pub exchange: String,
@@ -158,7 +158,7 @@ impl DepthUpdate {
 /// Trade update message
 ///
 /// Notification of an executed trade on the exchange.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TradeUpdate {
     /// Exchange identifier
     pub exchange: String,
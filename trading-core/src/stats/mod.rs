@@ -12,9 +12,11 @@
 
 mod ema;
 mod rolling;
+mod vpin;
 
 pub use ema::Ema;
 pub use rolling::RollingStats;
+pub use vpin::{VolumeBucket, Vpin};
 
 /// Scale factor for 8 decimal places (crypto standard)
 pub const SCALE_8: i64 = 100_000_000;
@@ -0,0 +1,165 @@
+//! Volume-synchronised probability of informed trading
+//!
+//! VPIN = sum(|buy - sell|) / sum(buy + sell) over the last N volume
+//! buckets. Buckets close on traded volume rather than time, so a busy
+//! minute fills many buckets and a quiet one may fill none. A trade that
+//! overflows a bucket is split across it and the next.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Aggressor volume in one bucket (scaled integers)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeBucket {
+    pub buy: i64,
+    pub sell: i64,
+}
+
+impl VolumeBucket {
+    #[inline]
+    pub fn total(&self) -> i64 {
+        self.buy + self.sell
+    }
+
+    #[inline]
+    pub fn imbalance(&self) -> i64 {
+        (self.buy - self.sell).abs()
+    }
+}
+
+/// VPIN over a rolling window of equal-volume buckets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vpin {
+    /// Volume that closes a bucket
+    bucket_volume: i64,
+    /// Completed buckets kept
+    window: usize,
+    /// Completed buckets, oldest first
+    buckets: VecDeque<VolumeBucket>,
+    /// Bucket being filled
+    current: VolumeBucket,
+    /// Running sums over `buckets`
+    imbalance: i128,
+    total: i128,
+}
+
+impl Vpin {
+    /// Buckets of `bucket_volume` (scaled), averaged over the last `window`
+    pub fn new(bucket_volume: i64, window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            bucket_volume: bucket_volume.max(1),
+            window,
+            buckets: VecDeque::with_capacity(window),
+            current: VolumeBucket::default(),
+            imbalance: 0,
+            total: 0,
+        }
+    }
+
+    /// Add a trade of `volume` (scaled) with its aggressor side
+    pub fn record(&mut self, volume: i64, is_buy: bool) {
+        let mut remaining = volume.max(0);
+        while remaining > 0 {
+            let take = remaining.min(self.bucket_volume - self.current.total());
+            if is_buy {
+                self.current.buy += take;
+            } else {
+                self.current.sell += take;
+            }
+            remaining -= take;
+
+            if self.current.total() >= self.bucket_volume {
+                self.close_bucket();
+            }
+        }
+    }
+
+    fn close_bucket(&mut self) {
+        if self.buckets.len() >= self.window
+            && let Some(oldest) = self.buckets.pop_front()
+        {
+            self.imbalance -= oldest.imbalance() as i128;
+            self.total -= oldest.total() as i128;
+        }
+        let bucket = std::mem::take(&mut self.current);
+        self.imbalance += bucket.imbalance() as i128;
+        self.total += bucket.total() as i128;
+        self.buckets.push_back(bucket);
+    }
+
+    /// VPIN over the completed buckets, `None` before the first closes
+    pub fn value(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.imbalance as f64 / self.total as f64)
+    }
+
+    /// Completed buckets, oldest first
+    pub fn buckets(&self) -> impl Iterator<Item = &VolumeBucket> {
+        self.buckets.iter()
+    }
+
+    /// Volume that closes a bucket
+    pub fn bucket_volume(&self) -> i64 {
+        self.bucket_volume
+    }
+
+    /// Number of buckets averaged over
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Drop all buckets
+    pub fn reset(&mut self) {
+        self.buckets.clear();
+        self.current = VolumeBucket::default();
+        self.imbalance = 0;
+        self.total = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vpin_over_completed_buckets() {
+        let mut vpin = Vpin::new(10, 2);
+        vpin.record(8, true);
+        vpin.record(1, false);
+        // Bucket still open
+        assert!(vpin.value().is_none());
+
+        // Closes 8/2, then 5/5
+        vpin.record(1, false);
+        vpin.record(5, true);
+        vpin.record(5, false);
+        // |8 - 2| + |5 - 5| over 20
+        assert!((vpin.value().unwrap() - 0.3).abs() < 1e-12);
+
+        // A third bucket pushes out the first
+        vpin.record(10, false);
+        assert!((vpin.value().unwrap() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_large_trade_split_across_buckets() {
+        let mut vpin = Vpin::new(10, 5);
+        vpin.record(5, false);
+        vpin.record(25, true);
+
+        let buckets: Vec<_> = vpin.buckets().copied().collect();
+        assert_eq!(
+            buckets,
+            vec![
+                VolumeBucket { buy: 5, sell: 5 },
+                VolumeBucket { buy: 10, sell: 0 },
+                VolumeBucket { buy: 10, sell: 0 },
+            ]
+        );
+        // 0 + 10 + 10 over 30
+        assert!((vpin.value().unwrap() - 2.0 / 3.0).abs() < 1e-12);
+
+        vpin.reset();
+        assert!(vpin.value().is_none());
+    }
+}
@@ -0,0 +1,11 @@
+//! Application Layer - Ports and the backtest runner
+//!
+//! The runner only talks to market data, strategies and the venue through
+//! ports, so recorded and synthetic data, or a different venue model, plug
+//! in without changing it.
+
+pub mod ports;
+pub mod services;
+
+pub use ports::*;
+pub use services::*;
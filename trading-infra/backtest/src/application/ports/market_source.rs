@@ -0,0 +1,33 @@
+//! Market Event Source Port - Market data in event-time order
+
+use crate::domain::MarketEvent;
+
+/// Error type for market data sources
+#[derive(Debug, Clone)]
+pub struct SourceError {
+    pub message: String,
+}
+
+impl SourceError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SourceError: {}", self.message)
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+/// Port for the market data a backtest replays
+///
+/// Events must come in non-decreasing timestamp order.
+pub trait MarketEventSource {
+    /// Next event, or `None` once the data is exhausted
+    fn next_event(&mut self) -> Result<Option<MarketEvent>, SourceError>;
+}
@@ -0,0 +1,13 @@
+//! Application Ports - Boundaries of the backtest
+//!
+//! - MarketEventSource: Where replayed or generated market data comes from
+//! - BacktestStrategy: A signal generator the runner can hold alongside others
+//! - VenuePort: The simulated exchange our orders execute against
+
+mod market_source;
+mod strategy;
+mod venue;
+
+pub use market_source::{MarketEventSource, SourceError};
+pub use strategy::BacktestStrategy;
+pub use venue::VenuePort;
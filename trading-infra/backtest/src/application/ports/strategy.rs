@@ -0,0 +1,28 @@
+//! Backtest Strategy Port - Signal generators behind one object-safe trait
+//!
+//! `SignalGeneratorPort::on_tick` is generic over the market data and clock,
+//! so generators cannot be boxed together. The backtest always feeds them the
+//! same concrete types, which this trait fixes.
+
+use strategy::application::ports::SignalGeneratorPort;
+use strategy::domain::Signal;
+use strategy::infrastructure::{EventClock, MarketDataAdapter};
+
+/// A strategy run by the backtest
+pub trait BacktestStrategy {
+    /// Strategy name for logging
+    fn name(&self) -> &str;
+
+    /// Evaluate the strategy on the current books at event time
+    fn on_tick(&mut self, market_data: &MarketDataAdapter, clock: &EventClock) -> Vec<Signal>;
+}
+
+impl<G: SignalGeneratorPort> BacktestStrategy for G {
+    fn name(&self) -> &str {
+        SignalGeneratorPort::name(self)
+    }
+
+    fn on_tick(&mut self, market_data: &MarketDataAdapter, clock: &EventClock) -> Vec<Signal> {
+        SignalGeneratorPort::on_tick(self, market_data, clock)
+    }
+}
@@ -0,0 +1,24 @@
+//! Venue Port - The simulated exchange
+
+use crate::domain::{ChildOrder, MarketEvent, VenueEvent};
+
+/// Port for the venue our orders execute against
+///
+/// Requests travel to the venue with latency: `submit` and `cancel` take
+/// effect once `process_arrivals` is called at or after their arrival time.
+pub trait VenuePort {
+    /// Apply a market data event to the venue's book
+    fn on_market_event(&mut self, event: &MarketEvent) -> Vec<VenueEvent>;
+
+    /// Send an order at `now_ns`
+    fn submit(&mut self, order: ChildOrder, now_ns: u64);
+
+    /// Request cancellation of a working order at `now_ns`
+    fn cancel(&mut self, order_id: u64, now_ns: u64);
+
+    /// When the next in-flight request reaches the venue
+    fn next_arrival_ns(&self) -> Option<u64>;
+
+    /// Handle every request that has reached the venue by `now_ns`
+    fn process_arrivals(&mut self, now_ns: u64) -> Vec<VenueEvent>;
+}
@@ -0,0 +1,275 @@
+//! Backtest Runner - The event loop
+//!
+//! Replays market events in time order. For each event the runner:
+//! 1. Handles everything scheduled before it: child orders due to be sent,
+//!    requests reaching the venue, expiries and equity samples
+//! 2. Moves the strategies' event clock to the event's time
+//! 3. Applies it to the strategies' books and to the venue's
+//! 4. Ticks every strategy and plans child orders for their signals
+//!
+//! Nothing reads the system clock, so identical inputs give identical
+//! reports.
+
+use std::collections::{BTreeMap, HashSet};
+
+use strategy::application::ports::Clock;
+use strategy::infrastructure::{EventClock, MarketDataAdapter};
+use strategy::{OrderBookManager, TradeTapeManager};
+
+use crate::application::ports::{BacktestStrategy, MarketEventSource, SourceError, VenuePort};
+use crate::application::services::ExecutionPlanner;
+use crate::domain::{
+    BacktestReport, ChildOrder, MarketEvent, MarketEventKind, Portfolio, ReportBuilder, VenueEvent,
+};
+
+/// Runner settings
+#[derive(Debug, Clone, Copy)]
+pub struct BacktestConfig {
+    /// Equity sampling period, which the Sharpe ratio is annualised from
+    pub sample_interval_ns: u64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval_ns: 1_000_000_000,
+        }
+    }
+}
+
+/// Something the runner does at a future time
+#[derive(Debug, Clone)]
+enum Action {
+    Send(ChildOrder),
+    Cancel(u64),
+}
+
+/// Runs strategies over a market event source against a venue
+pub struct BacktestRunner<S: MarketEventSource, V: VenuePort> {
+    config: BacktestConfig,
+    source: S,
+    venue: V,
+    planner: ExecutionPlanner,
+    strategies: Vec<Box<dyn BacktestStrategy>>,
+    clock: EventClock,
+    books: OrderBookManager,
+    market_data: MarketDataAdapter,
+    portfolio: Portfolio,
+    report: ReportBuilder,
+    /// Pending actions by (time, sequence)
+    scheduled: BTreeMap<(u64, u64), Action>,
+    next_action: u64,
+    /// Orders sent and not yet done
+    working: HashSet<u64>,
+    next_sample_ns: Option<u64>,
+}
+
+impl<S: MarketEventSource, V: VenuePort> BacktestRunner<S, V> {
+    pub fn new(source: S, venue: V, planner: ExecutionPlanner, config: BacktestConfig) -> Self {
+        let books = OrderBookManager::new();
+        let trades = TradeTapeManager::new();
+        Self {
+            config,
+            source,
+            venue,
+            planner,
+            strategies: Vec::new(),
+            clock: EventClock::default(),
+            market_data: MarketDataAdapter::new(books.clone()).with_trades(trades),
+            books,
+            portfolio: Portfolio::new(),
+            report: ReportBuilder::new(),
+            scheduled: BTreeMap::new(),
+            next_action: 0,
+            working: HashSet::new(),
+            next_sample_ns: None,
+        }
+    }
+
+    /// Add a strategy; strategies tick in the order they were added
+    pub fn with_strategy(mut self, strategy: impl BacktestStrategy + 'static) -> Self {
+        self.strategies.push(Box::new(strategy));
+        self
+    }
+
+    /// Add a boxed strategy
+    pub fn add_strategy(&mut self, strategy: Box<dyn BacktestStrategy>) {
+        self.strategies.push(strategy);
+    }
+
+    /// The strategies' event clock
+    pub fn clock(&self) -> &EventClock {
+        &self.clock
+    }
+
+    /// Run until the source is exhausted
+    ///
+    /// Orders still in flight or resting when the data ends are left
+    /// unfilled; open positions are marked to the last mid.
+    pub fn run(mut self) -> Result<BacktestReport, SourceError> {
+        tracing::info!(
+            "Backtesting {} strategies with {} execution",
+            self.strategies.len(),
+            self.planner.model_name()
+        );
+        while let Some(event) = self.source.next_event()? {
+            self.on_event(event);
+        }
+        if self.next_sample_ns.is_some() {
+            self.report
+                .record_equity(self.clock.now_ns(), &self.portfolio);
+        }
+
+        let report = self
+            .report
+            .build(&self.portfolio, self.config.sample_interval_ns);
+        tracing::info!(
+            "Backtest done: {} events, {} signals, {} fills, net P&L {:.4}",
+            report.events,
+            report.signals,
+            report.fills,
+            report.net_pnl
+        );
+        Ok(report)
+    }
+
+    fn on_event(&mut self, event: MarketEvent) {
+        let now_ns = event.timestamp_ns;
+        self.advance_to(now_ns);
+        self.clock.advance_to(now_ns);
+        self.report.record_event(now_ns);
+        if self.next_sample_ns.is_none() {
+            self.next_sample_ns = Some(now_ns);
+        }
+
+        match &event.kind {
+            MarketEventKind::Snapshot(snapshot) => self.books.apply_snapshot(snapshot),
+            MarketEventKind::Depth(update) => {
+                if !self.books.apply_delta(update) {
+                    tracing::warn!(
+                        "Sequence gap for {}:{} in backtest data",
+                        update.exchange,
+                        update.symbol
+                    );
+                }
+            }
+            MarketEventKind::Trade(trade) => self.market_data.trade_tapes().record(trade),
+        }
+        let venue_events = self.venue.on_market_event(&event);
+        self.handle_venue_events(venue_events);
+        if let Some(mid) = self
+            .books
+            .book(event.exchange(), event.symbol())
+            .mid_price()
+        {
+            self.portfolio.mark(&event.instrument(), mid);
+        }
+
+        self.tick_strategies(now_ns);
+        // Slices due now go out without waiting for the next event
+        self.advance_to(now_ns);
+    }
+
+    fn tick_strategies(&mut self, now_ns: u64) {
+        for strategy in &mut self.strategies {
+            for signal in strategy.on_tick(&self.market_data, &self.clock) {
+                if !signal.is_actionable() {
+                    continue;
+                }
+                self.report.record_signal();
+                for order in self.planner.plan(&signal, &self.market_data, now_ns) {
+                    self.report.record_intent(
+                        &order.signal_id,
+                        &order.strategy_id,
+                        &order.instrument,
+                        order.side,
+                        now_ns,
+                        order.quantity.to_f64(),
+                        order.arrival_mid.to_f64(),
+                    );
+                    let send_at_ns = order.send_at_ns;
+                    Self::schedule(
+                        &mut self.scheduled,
+                        &mut self.next_action,
+                        send_at_ns,
+                        Action::Send(order),
+                    );
+                }
+            }
+        }
+    }
+
+    fn schedule(
+        scheduled: &mut BTreeMap<(u64, u64), Action>,
+        next_action: &mut u64,
+        at_ns: u64,
+        action: Action,
+    ) {
+        scheduled.insert((at_ns, *next_action), action);
+        *next_action += 1;
+    }
+
+    /// Handle everything due at or before `until_ns`, in time order
+    fn advance_to(&mut self, until_ns: u64) {
+        loop {
+            let next_action = self.scheduled.first_key_value().map(|((t, _), _)| *t);
+            let next_arrival = self.venue.next_arrival_ns();
+            let Some(now_ns) = [next_action, next_arrival, self.next_sample_ns]
+                .into_iter()
+                .flatten()
+                .min()
+                .filter(|t| *t <= until_ns)
+            else {
+                return;
+            };
+
+            if next_action == Some(now_ns) {
+                let (_, action) = self.scheduled.pop_first().unwrap();
+                self.execute(action, now_ns);
+            } else if next_arrival == Some(now_ns) {
+                let events = self.venue.process_arrivals(now_ns);
+                self.handle_venue_events(events);
+            } else {
+                self.report.record_equity(now_ns, &self.portfolio);
+                self.next_sample_ns = Some(now_ns + self.config.sample_interval_ns.max(1));
+            }
+        }
+    }
+
+    fn execute(&mut self, action: Action, now_ns: u64) {
+        match action {
+            Action::Send(order) => {
+                if let Some(expire_at_ns) = order.expire_at_ns {
+                    Self::schedule(
+                        &mut self.scheduled,
+                        &mut self.next_action,
+                        expire_at_ns,
+                        Action::Cancel(order.id),
+                    );
+                }
+                self.working.insert(order.id);
+                self.report.record_order();
+                self.venue.submit(order, now_ns);
+            }
+            Action::Cancel(order_id) => {
+                if self.working.contains(&order_id) {
+                    self.venue.cancel(order_id, now_ns);
+                }
+            }
+        }
+    }
+
+    fn handle_venue_events(&mut self, events: Vec<VenueEvent>) {
+        for event in events {
+            match event {
+                VenueEvent::Fill(fill) => {
+                    self.portfolio.apply_fill(&fill);
+                    self.report.record_fill(&fill);
+                }
+                VenueEvent::Done { order_id, .. } => {
+                    self.working.remove(&order_id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Execution Planner - Turns signals into child orders
+//!
+//! A signal's quantity is its strength times the configured base quantity
+//! (times the hedge ratio for each leg of a multi-leg signal). The
+//! execution model splits it into slices over the horizon; each slice
+//! becomes a child order sent at its offset from the signal.
+//!
+//! Slices urgent enough cross the spread. The rest join the best price on
+//! their side and are cancelled when the next slice is due.
+
+use chrono::Duration;
+use execution::{ExecutionModel, MarketState};
+use strategy::application::ports::{MarketDataPort, OrderBookReader, SymbolKey};
+use strategy::domain::{Signal, SignalDirection};
+use strategy::infrastructure::MarketDataAdapter;
+use trading_core::{Price, PriceLevel, Quantity, Side};
+
+use crate::domain::{ChildOrder, OrderStyle, instrument_key};
+
+/// Book levels summed into the market state's depth
+const DEPTH_LEVELS: usize = 10;
+
+/// How signals are sized and worked
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionConfig {
+    /// Quantity traded for a signal of full strength
+    pub base_quantity: f64,
+    /// Time the execution model spreads a signal over
+    pub horizon_ns: u64,
+    /// Slices at or above this urgency cross the spread
+    ///
+    /// A slice's urgency is the larger of the model's and the signal's.
+    pub aggressive_urgency: f64,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            base_quantity: 1.0,
+            horizon_ns: 60_000_000_000,
+            aggressive_urgency: 0.7,
+        }
+    }
+}
+
+/// One instrument a signal asks to trade
+struct Target {
+    instrument: String,
+    key: SymbolKey,
+    side: Side,
+    quantity: Quantity,
+}
+
+/// Plans the child orders for each signal through an execution model
+pub struct ExecutionPlanner {
+    model: Box<dyn ExecutionModel>,
+    config: ExecutionConfig,
+    next_order_id: u64,
+}
+
+impl ExecutionPlanner {
+    pub fn new(model: Box<dyn ExecutionModel>, config: ExecutionConfig) -> Self {
+        Self {
+            model,
+            config,
+            next_order_id: 1,
+        }
+    }
+
+    /// Name of the execution model
+    pub fn model_name(&self) -> &str {
+        self.model.name()
+    }
+
+    pub fn config(&self) -> &ExecutionConfig {
+        &self.config
+    }
+
+    /// Child orders for `signal`, acted on at `now_ns`
+    ///
+    /// Instruments without a two-sided book are skipped.
+    pub fn plan(
+        &mut self,
+        signal: &Signal,
+        market_data: &MarketDataAdapter,
+        now_ns: u64,
+    ) -> Vec<ChildOrder> {
+        let mut orders = Vec::new();
+        for target in self.targets(signal) {
+            let book = market_data.book(&target.key);
+            let Some(arrival_mid) = book.mid_price() else {
+                tracing::debug!("No book for {}, skipping signal leg", target.instrument);
+                continue;
+            };
+            let market = market_state(book.as_ref());
+            let horizon = Duration::nanoseconds(self.config.horizon_ns as i64);
+            let schedule = self
+                .model
+                .compute_schedule(target.quantity, horizon, &market);
+
+            let offsets: Vec<u64> = schedule
+                .slices
+                .iter()
+                .map(|s| s.time_offset.num_nanoseconds().unwrap_or(0).max(0) as u64)
+                .collect();
+            for (i, slice) in schedule.slices.iter().enumerate() {
+                if slice.quantity.is_zero() {
+                    continue;
+                }
+                let urgency = slice.urgency.max(signal.urgency.value());
+                let style = if urgency >= self.config.aggressive_urgency {
+                    OrderStyle::Aggressive
+                } else {
+                    OrderStyle::Passive
+                };
+                let send_at_ns = now_ns + offsets[i];
+                let next_offset = offsets
+                    .get(i + 1)
+                    .copied()
+                    .filter(|next| *next > offsets[i])
+                    .unwrap_or(self.config.horizon_ns.max(offsets[i] + 1));
+                let expire_at_ns = match style {
+                    OrderStyle::Aggressive => None,
+                    OrderStyle::Passive => Some(now_ns + next_offset),
+                };
+
+                orders.push(ChildOrder {
+                    id: self.next_order_id,
+                    signal_id: signal.signal_id.to_string(),
+                    strategy_id: signal.strategy_id.to_string(),
+                    instrument: target.instrument.clone(),
+                    side: target.side,
+                    quantity: slice.quantity,
+                    style,
+                    send_at_ns,
+                    expire_at_ns,
+                    arrival_mid,
+                });
+                self.next_order_id += 1;
+            }
+        }
+        orders
+    }
+
+    /// Instruments, sides and quantities a signal asks for
+    fn targets(&self, signal: &Signal) -> Vec<Target> {
+        let size = self.config.base_quantity * signal.strength.abs();
+        if !signal.is_multi_leg() {
+            return side_of(signal.direction)
+                .and_then(|side| target(&signal.symbol, side, size))
+                .into_iter()
+                .collect();
+        }
+        signal
+            .legs
+            .iter()
+            .filter_map(|leg| {
+                let ratio = (leg.ratio as f64 / 100_000_000.0).abs();
+                let instrument = instrument_key(&leg.venue, &leg.symbol);
+                target(&instrument, side_of(leg.direction)?, size * ratio)
+            })
+            .collect()
+    }
+}
+
+fn side_of(direction: SignalDirection) -> Option<Side> {
+    match direction {
+        SignalDirection::Buy => Some(Side::Buy),
+        SignalDirection::Sell => Some(Side::Sell),
+        SignalDirection::None => None,
+    }
+}
+
+/// A target for `instrument` (`exchange:symbol`), unless the quantity is zero
+fn target(instrument: &str, side: Side, quantity: f64) -> Option<Target> {
+    let (exchange, symbol) = instrument.split_once(':').unwrap_or(("", instrument));
+    let quantity = Quantity::from_f64(quantity);
+    if quantity.is_zero() {
+        return None;
+    }
+    Some(Target {
+        instrument: instrument.to_string(),
+        key: SymbolKey::new(exchange, symbol),
+        side,
+        quantity,
+    })
+}
+
+/// Market state an execution model schedules against
+fn market_state(book: &dyn OrderBookReader) -> MarketState {
+    let level = |l: strategy::application::ports::BookLevel| PriceLevel::new(l.price, l.size);
+    MarketState {
+        best_bid: book.best_bid().map(level),
+        best_ask: book.best_ask().map(level),
+        mid_price: book.mid_price().unwrap_or(Price::ZERO),
+        spread: book.spread().unwrap_or(Price::ZERO),
+        bid_depth: book.total_bid_depth(DEPTH_LEVELS),
+        ask_depth: book.total_ask_depth(DEPTH_LEVELS),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use execution::TwapModel;
+    use strategy::OrderBookManager;
+    use strategy::domain::{Leg, StrategyId, StrategyType, Urgency};
+    use trading_core::{CompactLevel, OrderBookSnapshot};
+
+    fn market_data() -> MarketDataAdapter {
+        let books = OrderBookManager::new();
+        for symbol in ["BTCUSDT", "ETHUSDT"] {
+            books.apply_snapshot(
+                &OrderBookSnapshot::new("binance", symbol, 1)
+                    .with_bids(vec![CompactLevel::from_types(
+                        Price::from_int(100),
+                        Quantity::from_int(10),
+                    )])
+                    .with_asks(vec![CompactLevel::from_types(
+                        Price::from_int(102),
+                        Quantity::from_int(10),
+                    )]),
+            );
+        }
+        MarketDataAdapter::new(books)
+    }
+
+    fn planner(slices: usize) -> ExecutionPlanner {
+        ExecutionPlanner::new(
+            Box::new(TwapModel::with_slices(slices)),
+            ExecutionConfig {
+                base_quantity: 4.0,
+                horizon_ns: 120_000_000_000,
+                aggressive_urgency: 0.7,
+            },
+        )
+    }
+
+    fn signal() -> strategy::domain::SignalBuilder {
+        Signal::builder(
+            StrategyId::new("mr"),
+            StrategyType::MeanReversion,
+            "binance:BTCUSDT",
+        )
+        .strength(0.5)
+        .urgency(Urgency::low())
+    }
+
+    #[test]
+    fn test_passive_schedule() {
+        let mut planner = planner(2);
+        let orders = planner.plan(&signal().buy().build(), &market_data(), 1_000);
+
+        assert_eq!(orders.len(), 2);
+        let total: f64 = orders.iter().map(|o| o.quantity.to_f64()).sum();
+        assert_eq!(total, 2.0);
+        assert_eq!(orders[0].id, 1);
+        assert_eq!(orders[1].send_at_ns, 1_000 + 60_000_000_000);
+        assert!(orders.iter().all(|o| o.style == OrderStyle::Passive));
+        // Each slice rests until the next one is due
+        assert_eq!(orders[0].expire_at_ns, Some(orders[1].send_at_ns));
+        assert_eq!(orders[1].expire_at_ns, Some(1_000 + 120_000_000_000));
+        assert_eq!(orders[0].arrival_mid, Price::from_int(101));
+    }
+
+    #[test]
+    fn test_urgent_multi_leg_signal() {
+        let mut planner = planner(1);
+        let pair = signal()
+            .buy()
+            .urgency(Urgency::high())
+            .leg(Leg::buy("BTCUSDT", 1.0, "binance"))
+            .leg(Leg::sell("ETHUSDT", 0.5, "binance"))
+            .build();
+        let orders = planner.plan(&pair, &market_data(), 0);
+
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[1].instrument, "binance:ETHUSDT");
+        assert_eq!(orders[1].side, Side::Sell);
+        assert_eq!(orders[1].quantity, Quantity::from_int(1));
+        assert!(orders.iter().all(|o| o.style == OrderStyle::Aggressive));
+        assert!(orders.iter().all(|o| o.expire_at_ns.is_none()));
+
+        assert!(
+            planner
+                .plan(&signal().build(), &market_data(), 0)
+                .is_empty()
+        );
+    }
+}
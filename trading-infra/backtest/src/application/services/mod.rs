@@ -0,0 +1,7 @@
+//! Application Services - The backtest loop and order planning
+
+mod backtest_runner;
+mod execution_planner;
+
+pub use backtest_runner::{BacktestConfig, BacktestRunner};
+pub use execution_planner::{ExecutionConfig, ExecutionPlanner};
@@ -0,0 +1,53 @@
+//! Market events replayed into a backtest
+//!
+//! The gateway's IPC messages, stamped with the event time they happened at.
+
+use trading_core::{DepthUpdate, OrderBookSnapshot, TradeUpdate};
+
+/// Market data payload
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketEventKind {
+    Snapshot(OrderBookSnapshot),
+    Depth(DepthUpdate),
+    Trade(TradeUpdate),
+}
+
+/// A market data message at a point in event time
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketEvent {
+    /// Event time in nanoseconds since the Unix epoch
+    pub timestamp_ns: u64,
+    pub kind: MarketEventKind,
+}
+
+impl MarketEvent {
+    pub fn new(timestamp_ns: u64, kind: MarketEventKind) -> Self {
+        Self { timestamp_ns, kind }
+    }
+
+    pub fn exchange(&self) -> &str {
+        match &self.kind {
+            MarketEventKind::Snapshot(s) => &s.exchange,
+            MarketEventKind::Depth(u) => &u.exchange,
+            MarketEventKind::Trade(t) => &t.exchange,
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        match &self.kind {
+            MarketEventKind::Snapshot(s) => &s.symbol,
+            MarketEventKind::Depth(u) => &u.symbol,
+            MarketEventKind::Trade(t) => &t.symbol,
+        }
+    }
+
+    /// Instrument key in signal format, `exchange:symbol`
+    pub fn instrument(&self) -> String {
+        instrument_key(self.exchange(), self.symbol())
+    }
+}
+
+/// Instrument key in signal format, `exchange:symbol`
+pub fn instrument_key(exchange: &str, symbol: &str) -> String {
+    format!("{}:{}", exchange, symbol)
+}
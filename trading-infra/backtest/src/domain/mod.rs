@@ -0,0 +1,13 @@
+//! Domain Layer - Backtest events, orders, fills and results
+
+pub mod market_event;
+pub mod order;
+pub mod portfolio;
+pub mod report;
+
+pub use market_event::{MarketEvent, MarketEventKind, instrument_key};
+pub use order::{ChildOrder, Fill, OrderStyle, VenueEvent};
+pub use portfolio::Portfolio;
+pub use report::{
+    BacktestReport, EquityPoint, ReportBuilder, SignalAttribution, max_drawdown, sharpe_ratio,
+};
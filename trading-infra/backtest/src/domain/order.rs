@@ -0,0 +1,87 @@
+//! Child orders sent to the simulated venue and the fills they produce
+
+use serde::Serialize;
+use trading_core::{Price, Quantity, Side};
+
+/// How a child order is worked at the venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OrderStyle {
+    /// Market order; whatever does not fill immediately is dropped
+    Aggressive,
+    /// Joins the best price on its own side and rests until filled or expired
+    Passive,
+}
+
+/// One slice of a signal's execution schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildOrder {
+    /// Backtest-unique order id
+    pub id: u64,
+    pub signal_id: String,
+    pub strategy_id: String,
+    /// Instrument key, `exchange:symbol`
+    pub instrument: String,
+    pub side: Side,
+    pub quantity: Quantity,
+    pub style: OrderStyle,
+    /// When the order leaves the strategy (before venue latency)
+    pub send_at_ns: u64,
+    /// When a resting order is cancelled
+    pub expire_at_ns: Option<u64>,
+    /// Mid price when the signal was acted on
+    pub arrival_mid: Price,
+}
+
+/// An execution of one of our orders
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Fill {
+    pub order_id: u64,
+    pub signal_id: String,
+    pub strategy_id: String,
+    pub instrument: String,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,
+    /// Fee in quote currency
+    pub fee: f64,
+    /// Whether our order was resting when it traded
+    pub is_maker: bool,
+    pub timestamp_ns: u64,
+    pub arrival_mid: Price,
+}
+
+impl Fill {
+    /// +1 for buys, -1 for sells
+    pub fn sign(&self) -> f64 {
+        match self.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        }
+    }
+
+    /// Traded value in quote currency
+    pub fn notional(&self) -> f64 {
+        self.price.to_f64() * self.quantity.to_f64()
+    }
+
+    /// Cost versus the arrival mid in basis points; positive is worse
+    pub fn slippage_bps(&self) -> f64 {
+        let arrival = self.arrival_mid.to_f64();
+        if arrival <= 0.0 {
+            return 0.0;
+        }
+        self.sign() * (self.price.to_f64() - arrival) / arrival * 10_000.0
+    }
+}
+
+/// What the venue reports back about our orders
+#[derive(Debug, Clone, PartialEq)]
+pub enum VenueEvent {
+    Fill(Fill),
+    /// The order is no longer working: filled, cancelled, expired or an
+    /// unfilled aggressive remainder. `unfilled` never executes.
+    Done {
+        order_id: u64,
+        unfilled: Quantity,
+    },
+}
@@ -0,0 +1,136 @@
+//! Positions and cash per instrument, marked to market
+
+use std::collections::BTreeMap;
+use trading_core::Price;
+
+use super::order::Fill;
+
+#[derive(Debug, Clone, Default)]
+struct Holding {
+    /// Base quantity, negative when short
+    position: f64,
+    /// Quote currency paid (negative) or received (positive), before fees
+    cash: f64,
+    mark: Option<f64>,
+}
+
+impl Holding {
+    fn value(&self) -> f64 {
+        self.cash + self.position * self.mark.unwrap_or(0.0)
+    }
+}
+
+/// Running account of everything the backtest traded
+///
+/// Each instrument is settled in its own quote currency; P&L across
+/// instruments is only meaningful when they share one.
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    holdings: BTreeMap<String, Holding>,
+    fees: f64,
+    turnover: f64,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Book a fill
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        let holding = self.holdings.entry(fill.instrument.clone()).or_default();
+        let quantity = fill.quantity.to_f64();
+        holding.position += fill.sign() * quantity;
+        holding.cash -= fill.sign() * fill.notional();
+        // Until the market marks it, value the position at its fill price
+        holding.mark.get_or_insert(fill.price.to_f64());
+        self.fees += fill.fee;
+        self.turnover += fill.notional();
+    }
+
+    /// Set the price open positions in `instrument` are valued at
+    pub fn mark(&mut self, instrument: &str, price: Price) {
+        if let Some(holding) = self.holdings.get_mut(instrument) {
+            holding.mark = Some(price.to_f64());
+        }
+    }
+
+    /// Current mark for `instrument`, if it has traded
+    pub fn mark_price(&self, instrument: &str) -> Option<f64> {
+        self.holdings.get(instrument).and_then(|h| h.mark)
+    }
+
+    /// Base position in `instrument`
+    pub fn position(&self, instrument: &str) -> f64 {
+        self.holdings
+            .get(instrument)
+            .map(|h| h.position)
+            .unwrap_or(0.0)
+    }
+
+    /// Positions by instrument
+    pub fn positions(&self) -> BTreeMap<String, f64> {
+        self.holdings
+            .iter()
+            .map(|(instrument, h)| (instrument.clone(), h.position))
+            .collect()
+    }
+
+    /// Marked-to-market P&L before fees
+    pub fn gross_pnl(&self) -> f64 {
+        self.holdings.values().map(Holding::value).sum()
+    }
+
+    /// Marked-to-market P&L after fees
+    pub fn net_pnl(&self) -> f64 {
+        self.gross_pnl() - self.fees
+    }
+
+    /// Total fees paid
+    pub fn fees(&self) -> f64 {
+        self.fees
+    }
+
+    /// Total traded value
+    pub fn turnover(&self) -> f64 {
+        self.turnover
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trading_core::{Quantity, Side};
+
+    fn fill(side: Side, price: i64, quantity: i64, fee: f64) -> Fill {
+        Fill {
+            order_id: 0,
+            signal_id: "s".to_string(),
+            strategy_id: "mr".to_string(),
+            instrument: "binance:BTCUSDT".to_string(),
+            side,
+            price: Price::from_int(price),
+            quantity: Quantity::from_int(quantity),
+            fee,
+            is_maker: false,
+            timestamp_ns: 0,
+            arrival_mid: Price::from_int(100),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut portfolio = Portfolio::new();
+        portfolio.apply_fill(&fill(Side::Buy, 100, 2, 0.1));
+        assert_eq!(portfolio.gross_pnl(), 0.0);
+
+        portfolio.mark("binance:BTCUSDT", Price::from_int(103));
+        assert_eq!(portfolio.gross_pnl(), 6.0);
+
+        portfolio.apply_fill(&fill(Side::Sell, 102, 2, 0.1));
+        assert_eq!(portfolio.position("binance:BTCUSDT"), 0.0);
+        assert_eq!(portfolio.gross_pnl(), 4.0);
+        assert!((portfolio.net_pnl() - 3.8).abs() < 1e-9);
+        assert_eq!(portfolio.turnover(), 404.0);
+    }
+}
@@ -0,0 +1,342 @@
+//! Backtest results: P&L, costs, risk and per-signal attribution
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use trading_core::Side;
+
+use super::order::Fill;
+use super::portfolio::Portfolio;
+
+const NANOS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0 * 1e9;
+
+/// Portfolio value at a sample time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct EquityPoint {
+    pub timestamp_ns: u64,
+    /// Net P&L in quote currency
+    pub equity: f64,
+}
+
+/// What one signal traded in one instrument and what it earned
+///
+/// P&L marks the signal's fills to the final price, so the attributions
+/// add up to the backtest's net P&L.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SignalAttribution {
+    pub signal_id: String,
+    pub strategy_id: String,
+    pub instrument: String,
+    pub side: Side,
+    /// Event time the signal was acted on
+    pub timestamp_ns: u64,
+    pub requested: f64,
+    pub filled: f64,
+    /// Average fill price, zero when nothing filled
+    pub avg_price: f64,
+    pub arrival_mid: f64,
+    pub fees: f64,
+    /// Fill cost versus the arrival mid; positive is worse
+    pub slippage_bps: f64,
+    pub pnl: f64,
+    #[serde(skip)]
+    signed_quantity: f64,
+    #[serde(skip)]
+    signed_cost: f64,
+    #[serde(skip)]
+    notional: f64,
+}
+
+impl SignalAttribution {
+    fn record(&mut self, fill: &Fill) {
+        let quantity = fill.quantity.to_f64();
+        self.filled += quantity;
+        self.fees += fill.fee;
+        self.notional += fill.notional();
+        self.signed_quantity += fill.sign() * quantity;
+        self.signed_cost += fill.sign() * fill.notional();
+    }
+
+    fn finish(&mut self, mark: Option<f64>) {
+        if self.filled > 0.0 {
+            self.avg_price = self.notional / self.filled;
+        }
+        let arrival_notional = self.arrival_mid * self.filled;
+        if arrival_notional > 0.0 {
+            self.slippage_bps = (self.signed_cost - self.signed_quantity * self.arrival_mid)
+                / arrival_notional
+                * 10_000.0;
+        }
+        let mark = mark.unwrap_or(self.avg_price);
+        self.pnl = self.signed_quantity * mark - self.signed_cost - self.fees;
+    }
+}
+
+/// Summary of a backtest run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BacktestReport {
+    pub start_ns: u64,
+    pub end_ns: u64,
+    pub events: u64,
+    pub signals: u64,
+    pub orders: u64,
+    pub fills: u64,
+    /// P&L before fees, marked to the last mid
+    pub gross_pnl: f64,
+    pub fees: f64,
+    pub net_pnl: f64,
+    /// Total traded value
+    pub turnover: f64,
+    /// Share of traded value filled as maker
+    pub maker_share: f64,
+    /// Notional-weighted fill cost versus arrival mid; positive is worse
+    pub slippage_bps: f64,
+    /// Annualised Sharpe ratio of equity changes between samples
+    pub sharpe: f64,
+    /// Largest peak-to-trough fall in equity, in quote currency
+    pub max_drawdown: f64,
+    pub positions: BTreeMap<String, f64>,
+    pub equity_curve: Vec<EquityPoint>,
+    pub attribution: Vec<SignalAttribution>,
+}
+
+/// Collects fills, equity samples and counters while a backtest runs
+#[derive(Debug, Clone, Default)]
+pub struct ReportBuilder {
+    start_ns: Option<u64>,
+    end_ns: u64,
+    events: u64,
+    signals: u64,
+    orders: u64,
+    fills: u64,
+    maker_notional: f64,
+    slippage_cost: f64,
+    arrival_notional: f64,
+    equity_curve: Vec<EquityPoint>,
+    attribution: Vec<SignalAttribution>,
+    /// (signal, instrument) to its attribution entry
+    index: HashMap<(String, String), usize>,
+}
+
+impl ReportBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a market event at `timestamp_ns`
+    pub fn record_event(&mut self, timestamp_ns: u64) {
+        self.start_ns.get_or_insert(timestamp_ns);
+        self.end_ns = self.end_ns.max(timestamp_ns);
+        self.events += 1;
+    }
+
+    pub fn record_signal(&mut self) {
+        self.signals += 1;
+    }
+
+    /// Open the attribution entry a signal's orders in `instrument` fill into
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_intent(
+        &mut self,
+        signal_id: &str,
+        strategy_id: &str,
+        instrument: &str,
+        side: Side,
+        timestamp_ns: u64,
+        requested: f64,
+        arrival_mid: f64,
+    ) {
+        let key = (signal_id.to_string(), instrument.to_string());
+        if let Some(&i) = self.index.get(&key) {
+            self.attribution[i].requested += requested;
+            return;
+        }
+        self.index.insert(key, self.attribution.len());
+        self.attribution.push(SignalAttribution {
+            signal_id: signal_id.to_string(),
+            strategy_id: strategy_id.to_string(),
+            instrument: instrument.to_string(),
+            side,
+            timestamp_ns,
+            requested,
+            filled: 0.0,
+            avg_price: 0.0,
+            arrival_mid,
+            fees: 0.0,
+            slippage_bps: 0.0,
+            pnl: 0.0,
+            signed_quantity: 0.0,
+            signed_cost: 0.0,
+            notional: 0.0,
+        });
+    }
+
+    pub fn record_order(&mut self) {
+        self.orders += 1;
+    }
+
+    pub fn record_fill(&mut self, fill: &Fill) {
+        self.fills += 1;
+        if fill.is_maker {
+            self.maker_notional += fill.notional();
+        }
+        let arrival = fill.arrival_mid.to_f64() * fill.quantity.to_f64();
+        self.slippage_cost += fill.sign() * (fill.notional() - arrival);
+        self.arrival_notional += arrival;
+
+        let key = (fill.signal_id.clone(), fill.instrument.clone());
+        if let Some(&i) = self.index.get(&key) {
+            self.attribution[i].record(fill);
+        }
+    }
+
+    /// Sample the portfolio's equity at `timestamp_ns`
+    pub fn record_equity(&mut self, timestamp_ns: u64, portfolio: &Portfolio) {
+        self.equity_curve.push(EquityPoint {
+            timestamp_ns,
+            equity: portfolio.net_pnl(),
+        });
+    }
+
+    /// Finish the report; `sample_interval_ns` is the equity sampling period
+    pub fn build(mut self, portfolio: &Portfolio, sample_interval_ns: u64) -> BacktestReport {
+        for attribution in &mut self.attribution {
+            attribution.finish(portfolio.mark_price(&attribution.instrument));
+        }
+        let turnover = portfolio.turnover();
+        let periods_per_year = NANOS_PER_YEAR / sample_interval_ns.max(1) as f64;
+
+        BacktestReport {
+            start_ns: self.start_ns.unwrap_or(0),
+            end_ns: self.end_ns,
+            events: self.events,
+            signals: self.signals,
+            orders: self.orders,
+            fills: self.fills,
+            gross_pnl: portfolio.gross_pnl(),
+            fees: portfolio.fees(),
+            net_pnl: portfolio.net_pnl(),
+            turnover,
+            maker_share: ratio(self.maker_notional, turnover),
+            slippage_bps: ratio(self.slippage_cost, self.arrival_notional) * 10_000.0,
+            sharpe: sharpe_ratio(&self.equity_curve, periods_per_year),
+            max_drawdown: max_drawdown(&self.equity_curve),
+            positions: portfolio.positions(),
+            equity_curve: self.equity_curve,
+            attribution: self.attribution,
+        }
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+/// Annualised Sharpe ratio of the changes between equity samples
+///
+/// Zero when there are fewer than two changes or equity never moved.
+pub fn sharpe_ratio(equity: &[EquityPoint], periods_per_year: f64) -> f64 {
+    let changes: Vec<f64> = equity
+        .windows(2)
+        .map(|w| w[1].equity - w[0].equity)
+        .collect();
+    if changes.len() < 2 {
+        return 0.0;
+    }
+    let n = changes.len() as f64;
+    let mean = changes.iter().sum::<f64>() / n;
+    let variance = changes.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let std_dev = variance.sqrt();
+    if std_dev < f64::EPSILON {
+        return 0.0;
+    }
+    mean / std_dev * periods_per_year.sqrt()
+}
+
+/// Largest peak-to-trough fall in equity
+pub fn max_drawdown(equity: &[EquityPoint]) -> f64 {
+    let mut peak = f64::NEG_INFINITY;
+    let mut drawdown: f64 = 0.0;
+    for point in equity {
+        peak = peak.max(point.equity);
+        drawdown = drawdown.max(peak - point.equity);
+    }
+    drawdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trading_core::{Price, Quantity};
+
+    fn points(equity: &[f64]) -> Vec<EquityPoint> {
+        equity
+            .iter()
+            .enumerate()
+            .map(|(i, &equity)| EquityPoint {
+                timestamp_ns: i as u64,
+                equity,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_risk_metrics() {
+        let curve = points(&[0.0, 5.0, 2.0, 8.0, 1.0, 4.0]);
+        assert_eq!(max_drawdown(&curve), 7.0);
+        assert_eq!(max_drawdown(&points(&[0.0, 1.0, 2.0])), 0.0);
+
+        let rising = points(&[0.0, 1.0, 3.0, 4.0]);
+        assert!(sharpe_ratio(&rising, 1.0) > 0.0);
+        assert_eq!(sharpe_ratio(&points(&[0.0, 1.0, 2.0]), 1.0), 0.0);
+        assert_eq!(sharpe_ratio(&points(&[0.0, 1.0]), 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_attribution_adds_up_to_net_pnl() {
+        let fill = |signal: &str, side, price, fee| Fill {
+            order_id: 0,
+            signal_id: signal.to_string(),
+            strategy_id: "mr".to_string(),
+            instrument: "binance:BTCUSDT".to_string(),
+            side,
+            price: Price::from_int(price),
+            quantity: Quantity::from_int(1),
+            fee,
+            is_maker: side == Side::Sell,
+            timestamp_ns: 0,
+            arrival_mid: Price::from_int(100),
+        };
+
+        let mut builder = ReportBuilder::new();
+        let mut portfolio = Portfolio::new();
+        builder.record_intent("a", "mr", "binance:BTCUSDT", Side::Buy, 0, 1.0, 100.0);
+        builder.record_intent("b", "mr", "binance:BTCUSDT", Side::Sell, 1, 2.0, 100.0);
+        for fill in [
+            fill("a", Side::Buy, 101, 0.5),
+            fill("b", Side::Sell, 103, 0.25),
+        ] {
+            portfolio.apply_fill(&fill);
+            builder.record_fill(&fill);
+        }
+        portfolio.mark("binance:BTCUSDT", Price::from_int(102));
+
+        let report = builder.build(&portfolio, 1_000_000_000);
+        assert_eq!(report.fills, 2);
+        assert!((report.net_pnl - 1.25).abs() < 1e-9);
+        let total: f64 = report.attribution.iter().map(|a| a.pnl).sum();
+        assert!((total - report.net_pnl).abs() < 1e-9);
+
+        let a = &report.attribution[0];
+        assert_eq!((a.filled, a.avg_price), (1.0, 101.0));
+        assert!((a.slippage_bps - 100.0).abs() < 1e-9);
+        // Selling above arrival is a gain
+        assert!((report.attribution[1].slippage_bps + 300.0).abs() < 1e-9);
+        assert_eq!(report.attribution[1].requested, 2.0);
+        assert!((report.maker_share - 103.0 / 204.0).abs() < 1e-9);
+        assert!((report.slippage_bps - (1.0 - 3.0) / 200.0 * 10_000.0).abs() < 1e-9);
+    }
+}
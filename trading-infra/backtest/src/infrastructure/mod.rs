@@ -0,0 +1,10 @@
+//! Infrastructure Layer - Concrete sources and venues
+//!
+//! - Sources: Recorded captures, ABM-generated books and in-memory events
+//! - SimulatedVenue: exchange-sim order books with queue position and latency
+
+pub mod simulated_venue;
+pub mod sources;
+
+pub use simulated_venue::{SimulatedVenue, VenueConfig};
+pub use sources::*;
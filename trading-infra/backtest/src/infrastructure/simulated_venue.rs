@@ -0,0 +1,596 @@
+//! Simulated Venue - exchange-sim order books driven by replayed market data
+//!
+//! Each instrument is an exchange-sim `OrderBook` under a fixed
+//! `SimulationClock`. Replayed L2 levels are kept in the book as resting
+//! liquidity orders, so our passive orders queue behind whatever was already
+//! at their price:
+//! - A level growing adds liquidity at the back of the queue, behind us.
+//! - A level shrinking is treated as cancellations, taken from the back of
+//!   the queue first.
+//! - A trade print executes from the front, so it only reaches our order
+//!   once the quantity ahead of it has traded.
+//!
+//! Our aggressive orders take whatever liquidity the book shows. The next
+//! depth update or snapshot resets the levels they consumed, so their impact
+//! does not persist.
+
+use std::collections::{HashMap, VecDeque};
+
+use exchange_sim::{ControllableClock, OrderBook, SimulationClock};
+use trading_core::{
+    CompactLevel, DepthUpdate, Order, OrderBookSnapshot, OrderId, Price, Quantity, Side, Symbol,
+    TimeInForce, Timestamp, Trade, TradeUpdate,
+};
+
+use crate::application::ports::VenuePort;
+use crate::domain::{ChildOrder, Fill, MarketEvent, MarketEventKind, OrderStyle, VenueEvent};
+
+/// Venue latency and fees
+#[derive(Debug, Clone, Copy)]
+pub struct VenueConfig {
+    /// One-way latency of order and cancel requests
+    pub latency_ns: u64,
+    /// Fee on resting fills, in basis points of notional
+    pub maker_fee_bps: f64,
+    /// Fee on aggressive fills, in basis points of notional
+    pub taker_fee_bps: f64,
+}
+
+impl Default for VenueConfig {
+    fn default() -> Self {
+        Self {
+            latency_ns: 1_000_000,
+            maker_fee_bps: 1.0,
+            taker_fee_bps: 5.0,
+        }
+    }
+}
+
+/// A request on its way to the venue
+#[derive(Debug, Clone)]
+enum Request {
+    Submit(ChildOrder),
+    Cancel(u64),
+}
+
+/// One instrument's book and the market liquidity resting in it
+struct SimBook {
+    book: OrderBook,
+    /// Replayed liquidity order ids per level, oldest first
+    liquidity: HashMap<(Side, Price), VecDeque<OrderId>>,
+}
+
+impl SimBook {
+    fn new(symbol: Symbol) -> Self {
+        Self {
+            book: OrderBook::new(symbol),
+            liquidity: HashMap::new(),
+        }
+    }
+
+    /// Market quantity resting at a level, forgetting orders that traded away
+    fn market_quantity(&mut self, side: Side, price: Price) -> Quantity {
+        let Some(ids) = self.liquidity.get_mut(&(side, price)) else {
+            return Quantity::ZERO;
+        };
+        let book = &self.book;
+        ids.retain(|id| book.get_order(*id).is_some());
+        ids.iter()
+            .filter_map(|id| book.get_order(*id))
+            .fold(Quantity::ZERO, |acc, o| acc + o.remaining_quantity())
+    }
+
+    /// Set the market quantity at a level; returns trades if it crossed
+    fn set_level(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        now: Timestamp,
+    ) -> Vec<Trade> {
+        let current = self.market_quantity(side, price);
+        if quantity > current {
+            return self.add_liquidity(side, price, quantity - current, now);
+        }
+
+        let mut excess = current - quantity;
+        if let Some(ids) = self.liquidity.get_mut(&(side, price)) {
+            while !excess.is_zero() {
+                let Some(&id) = ids.back() else {
+                    break;
+                };
+                let remaining = self
+                    .book
+                    .get_order(id)
+                    .map(|o| o.remaining_quantity())
+                    .unwrap_or(Quantity::ZERO);
+                if remaining <= excess {
+                    self.book.remove_order(id);
+                    ids.pop_back();
+                    excess = excess - remaining;
+                } else {
+                    self.book.reduce_order(id, excess);
+                    excess = Quantity::ZERO;
+                }
+            }
+            if ids.is_empty() {
+                self.liquidity.remove(&(side, price));
+            }
+        }
+        Vec::new()
+    }
+
+    /// Add market liquidity at the back of a level, matching anything it crosses
+    fn add_liquidity(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        now: Timestamp,
+    ) -> Vec<Trade> {
+        let order = Order::new_limit(
+            self.book.symbol().clone(),
+            side,
+            quantity,
+            price,
+            TimeInForce::Gtc,
+        );
+        let (trades, rest) = self.book.match_order(order, now);
+        if let Some(rest) = rest {
+            self.liquidity
+                .entry((side, price))
+                .or_default()
+                .push_back(rest.id);
+            self.book.add_order(rest);
+        }
+        trades
+    }
+
+    fn apply_levels(&mut self, side: Side, levels: &[CompactLevel], now: Timestamp) -> Vec<Trade> {
+        levels
+            .iter()
+            .flat_map(|level| {
+                self.set_level(
+                    side,
+                    Price::from_raw(level.price_raw),
+                    Quantity::from_raw(level.quantity_raw.max(0)),
+                    now,
+                )
+            })
+            .collect()
+    }
+
+    fn apply_snapshot(&mut self, snapshot: &OrderBookSnapshot, now: Timestamp) -> Vec<Trade> {
+        // Clear stale levels first so the new ones don't trade against them
+        let stale: Vec<(Side, Price)> = self
+            .liquidity
+            .keys()
+            .filter(|(side, price)| {
+                let levels = match side {
+                    Side::Buy => &snapshot.bids,
+                    Side::Sell => &snapshot.asks,
+                };
+                !levels.iter().any(|l| l.price_raw == price.raw())
+            })
+            .copied()
+            .collect();
+        for (side, price) in stale {
+            self.set_level(side, price, Quantity::ZERO, now);
+        }
+
+        let mut trades = self.apply_levels(Side::Buy, &snapshot.bids, now);
+        trades.extend(self.apply_levels(Side::Sell, &snapshot.asks, now));
+        trades
+    }
+
+    fn apply_depth(&mut self, update: &DepthUpdate, now: Timestamp) -> Vec<Trade> {
+        let mut trades = self.apply_levels(Side::Buy, &update.bids, now);
+        trades.extend(self.apply_levels(Side::Sell, &update.asks, now));
+        trades
+    }
+
+    /// Replay a print as an aggressor sweeping the book up to its price
+    fn apply_trade(&mut self, trade: &TradeUpdate, now: Timestamp) -> Vec<Trade> {
+        let side = if trade.buyer_is_maker {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+        let order = Order::new_limit(
+            self.book.symbol().clone(),
+            side,
+            trade.quantity(),
+            trade.price(),
+            TimeInForce::Ioc,
+        );
+        self.book.match_order(order, now).0
+    }
+}
+
+/// One of our orders at the venue
+struct WorkingOrder {
+    order: ChildOrder,
+    venue_id: OrderId,
+    filled: Quantity,
+}
+
+/// In-process venue over exchange-sim order books
+///
+/// Requests arrive in the order they were sent: latency is the same for
+/// every request, and the runner sends them in time order.
+pub struct SimulatedVenue {
+    config: VenueConfig,
+    clock: SimulationClock,
+    books: HashMap<String, SimBook>,
+    in_flight: VecDeque<(u64, Request)>,
+    working: HashMap<u64, WorkingOrder>,
+    /// exchange-sim order id to our order id
+    venue_ids: HashMap<OrderId, u64>,
+}
+
+impl SimulatedVenue {
+    pub fn new(config: VenueConfig) -> Self {
+        Self {
+            config,
+            clock: SimulationClock::at(to_timestamp(0)),
+            books: HashMap::new(),
+            in_flight: VecDeque::new(),
+            working: HashMap::new(),
+            venue_ids: HashMap::new(),
+        }
+    }
+
+    /// The venue's simulated time, set to each event or arrival it handles
+    pub fn clock(&self) -> &SimulationClock {
+        &self.clock
+    }
+
+    /// Number of our orders still working
+    pub fn working_orders(&self) -> usize {
+        self.working.len()
+    }
+
+    /// Book for an instrument key, created on first use
+    fn book(&mut self, instrument: &str) -> Option<&mut SimBook> {
+        if !self.books.contains_key(instrument) {
+            let symbol = instrument.split_once(':').map_or(instrument, |(_, s)| s);
+            let symbol = match Symbol::new(symbol) {
+                Ok(symbol) => symbol,
+                Err(e) => {
+                    tracing::warn!("Cannot simulate {}: {}", instrument, e);
+                    return None;
+                }
+            };
+            self.books
+                .insert(instrument.to_string(), SimBook::new(symbol));
+        }
+        self.books.get_mut(instrument)
+    }
+
+    fn set_time(&self, now_ns: u64) -> Timestamp {
+        let now = to_timestamp(now_ns);
+        self.clock.set_time(now);
+        now
+    }
+
+    /// Turn matches involving our orders into fills and completions
+    fn our_executions(&mut self, trades: &[Trade], now_ns: u64) -> Vec<VenueEvent> {
+        let mut events = Vec::new();
+        for trade in trades {
+            for venue_id in [trade.buyer_order_id, trade.seller_order_id] {
+                let Some(&order_id) = self.venue_ids.get(&venue_id) else {
+                    continue;
+                };
+                let Some(working) = self.working.get_mut(&order_id) else {
+                    continue;
+                };
+                let is_maker = trade.maker_order_id() == venue_id;
+                let notional = trade.price.to_f64() * trade.quantity.to_f64();
+                let fee_bps = if is_maker {
+                    self.config.maker_fee_bps
+                } else {
+                    self.config.taker_fee_bps
+                };
+                working.filled = working.filled + trade.quantity;
+                events.push(VenueEvent::Fill(Fill {
+                    order_id,
+                    signal_id: working.order.signal_id.clone(),
+                    strategy_id: working.order.strategy_id.clone(),
+                    instrument: working.order.instrument.clone(),
+                    side: working.order.side,
+                    price: trade.price,
+                    quantity: trade.quantity,
+                    fee: notional * fee_bps / 10_000.0,
+                    is_maker,
+                    timestamp_ns: now_ns,
+                    arrival_mid: working.order.arrival_mid,
+                }));
+                if working.filled >= working.order.quantity {
+                    events.push(self.finish(order_id, Quantity::ZERO));
+                }
+            }
+        }
+        events
+    }
+
+    /// Stop tracking an order
+    fn finish(&mut self, order_id: u64, unfilled: Quantity) -> VenueEvent {
+        if let Some(working) = self.working.remove(&order_id) {
+            self.venue_ids.remove(&working.venue_id);
+        }
+        VenueEvent::Done { order_id, unfilled }
+    }
+
+    fn execute_submit(&mut self, order: ChildOrder, now_ns: u64) -> Vec<VenueEvent> {
+        let now = self.set_time(now_ns);
+        let unfilled = VenueEvent::Done {
+            order_id: order.id,
+            unfilled: order.quantity,
+        };
+        let Some(book) = self.book(&order.instrument) else {
+            return vec![unfilled];
+        };
+        let symbol = book.book.symbol().clone();
+        let venue_order = match order.style {
+            OrderStyle::Aggressive => Order::new_market(symbol, order.side, order.quantity),
+            OrderStyle::Passive => {
+                let best = match order.side {
+                    Side::Buy => book.book.best_bid(),
+                    Side::Sell => book.book.best_ask(),
+                };
+                // Nothing to join
+                let Some(price) = best else {
+                    return vec![unfilled];
+                };
+                Order::new_limit(symbol, order.side, order.quantity, price, TimeInForce::Gtc)
+            }
+        };
+
+        let (trades, rest) = book.book.match_order(venue_order.clone(), now);
+        let rest = match (order.style, rest) {
+            (OrderStyle::Passive, Some(rest)) => {
+                book.book.add_order(rest);
+                None
+            }
+            (_, rest) => rest,
+        };
+
+        let order_id = order.id;
+        let style = order.style;
+        self.venue_ids.insert(venue_order.id, order_id);
+        self.working.insert(
+            order_id,
+            WorkingOrder {
+                order,
+                venue_id: venue_order.id,
+                filled: Quantity::ZERO,
+            },
+        );
+        let mut events = self.our_executions(&trades, now_ns);
+        if style == OrderStyle::Aggressive && self.working.contains_key(&order_id) {
+            // Whatever the book could not fill is dropped
+            let unfilled = rest
+                .map(|o| o.remaining_quantity())
+                .unwrap_or(venue_order.quantity);
+            events.push(self.finish(order_id, unfilled));
+        }
+        events
+    }
+
+    fn execute_cancel(&mut self, order_id: u64, now_ns: u64) -> Vec<VenueEvent> {
+        self.set_time(now_ns);
+        let Some(working) = self.working.get(&order_id) else {
+            // Already filled or done
+            return Vec::new();
+        };
+        let venue_id = working.venue_id;
+        let unfilled = working.order.quantity.saturating_sub(working.filled);
+        if let Some(book) = self.books.get_mut(&working.order.instrument) {
+            book.book.remove_order(venue_id);
+        }
+        vec![self.finish(order_id, unfilled)]
+    }
+}
+
+impl Default for SimulatedVenue {
+    fn default() -> Self {
+        Self::new(VenueConfig::default())
+    }
+}
+
+impl VenuePort for SimulatedVenue {
+    fn on_market_event(&mut self, event: &MarketEvent) -> Vec<VenueEvent> {
+        let now = self.set_time(event.timestamp_ns);
+        let Some(book) = self.book(&event.instrument()) else {
+            return Vec::new();
+        };
+        let trades = match &event.kind {
+            MarketEventKind::Snapshot(snapshot) => book.apply_snapshot(snapshot, now),
+            MarketEventKind::Depth(update) => book.apply_depth(update, now),
+            MarketEventKind::Trade(trade) => book.apply_trade(trade, now),
+        };
+        self.our_executions(&trades, event.timestamp_ns)
+    }
+
+    fn submit(&mut self, order: ChildOrder, now_ns: u64) {
+        self.in_flight
+            .push_back((now_ns + self.config.latency_ns, Request::Submit(order)));
+    }
+
+    fn cancel(&mut self, order_id: u64, now_ns: u64) {
+        self.in_flight
+            .push_back((now_ns + self.config.latency_ns, Request::Cancel(order_id)));
+    }
+
+    fn next_arrival_ns(&self) -> Option<u64> {
+        self.in_flight.front().map(|(arrival, _)| *arrival)
+    }
+
+    fn process_arrivals(&mut self, now_ns: u64) -> Vec<VenueEvent> {
+        let mut events = Vec::new();
+        while let Some((arrival, _)) = self.in_flight.front() {
+            if *arrival > now_ns {
+                break;
+            }
+            let (arrival, request) = self.in_flight.pop_front().unwrap();
+            events.extend(match request {
+                Request::Submit(order) => self.execute_submit(order, arrival),
+                Request::Cancel(order_id) => self.execute_cancel(order_id, arrival),
+            });
+        }
+        events
+    }
+}
+
+fn to_timestamp(ns: u64) -> Timestamp {
+    Timestamp::from_timestamp_nanos(ns as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange_sim::Clock;
+
+    const INSTRUMENT: &str = "binance:BTCUSDT";
+
+    fn level(price: i64, quantity: i64) -> CompactLevel {
+        CompactLevel::from_types(Price::from_int(price), Quantity::from_int(quantity))
+    }
+
+    fn snapshot_event(ts: u64, bid_qty: i64, ask_qty: i64) -> MarketEvent {
+        let snapshot = OrderBookSnapshot::new("binance", "BTCUSDT", 1)
+            .with_bids(vec![level(100, bid_qty)])
+            .with_asks(vec![level(101, ask_qty)]);
+        MarketEvent::new(ts, MarketEventKind::Snapshot(snapshot))
+    }
+
+    fn sell_print(ts: u64, price: i64, quantity: i64) -> MarketEvent {
+        let trade = TradeUpdate::new(
+            "binance",
+            "BTCUSDT",
+            ts,
+            Price::from_int(price),
+            Quantity::from_int(quantity),
+            true,
+        );
+        MarketEvent::new(ts, MarketEventKind::Trade(trade))
+    }
+
+    fn child(id: u64, side: Side, quantity: i64, style: OrderStyle) -> ChildOrder {
+        ChildOrder {
+            id,
+            signal_id: "sig".to_string(),
+            strategy_id: "mr".to_string(),
+            instrument: INSTRUMENT.to_string(),
+            side,
+            quantity: Quantity::from_int(quantity),
+            style,
+            send_at_ns: 0,
+            expire_at_ns: None,
+            arrival_mid: Price::from_raw(100_50000000),
+        }
+    }
+
+    fn fills(events: &[VenueEvent]) -> Vec<&Fill> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                VenueEvent::Fill(fill) => Some(fill),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aggressive_order_after_latency() {
+        let mut venue = SimulatedVenue::new(VenueConfig {
+            latency_ns: 10,
+            maker_fee_bps: 0.0,
+            taker_fee_bps: 10.0,
+        });
+        venue.on_market_event(&snapshot_event(0, 5, 3));
+
+        venue.submit(child(1, Side::Buy, 5, OrderStyle::Aggressive), 100);
+        assert_eq!(venue.next_arrival_ns(), Some(110));
+        assert!(venue.process_arrivals(109).is_empty());
+
+        let events = venue.process_arrivals(110);
+        let filled = fills(&events);
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].price, Price::from_int(101));
+        assert_eq!(filled[0].quantity, Quantity::from_int(3));
+        assert!(!filled[0].is_maker);
+        assert!((filled[0].fee - 303.0 * 0.001).abs() < 1e-9);
+        assert_eq!(filled[0].timestamp_ns, 110);
+        // Only 3 were offered; the rest is dropped
+        assert_eq!(
+            events.last(),
+            Some(&VenueEvent::Done {
+                order_id: 1,
+                unfilled: Quantity::from_int(2)
+            })
+        );
+        assert_eq!(venue.working_orders(), 0);
+        assert_eq!(venue.clock().now_nanos(), 110);
+    }
+
+    #[test]
+    fn test_passive_order_waits_for_queue_ahead() {
+        let mut venue = SimulatedVenue::new(VenueConfig {
+            latency_ns: 0,
+            ..Default::default()
+        });
+        venue.on_market_event(&snapshot_event(0, 5, 5));
+        venue.submit(child(1, Side::Buy, 2, OrderStyle::Passive), 1);
+        assert!(venue.process_arrivals(1).is_empty());
+
+        // Size joining behind us, then leaving again, does not move us up
+        venue.on_market_event(&snapshot_event(2, 8, 5));
+        venue.on_market_event(&snapshot_event(3, 5, 5));
+
+        // 4 of the 5 ahead trade
+        assert!(fills(&venue.on_market_event(&sell_print(4, 100, 4))).is_empty());
+
+        // The next print takes the last 1 ahead and 1 of ours
+        let events = venue.on_market_event(&sell_print(5, 100, 2));
+        let filled = fills(&events);
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].quantity, Quantity::from_int(1));
+        assert!(filled[0].is_maker);
+
+        venue.cancel(1, 6);
+        assert_eq!(
+            venue.process_arrivals(6),
+            vec![VenueEvent::Done {
+                order_id: 1,
+                unfilled: Quantity::from_int(1)
+            }]
+        );
+        // Cancelling again is a no-op
+        venue.cancel(1, 7);
+        assert!(venue.process_arrivals(7).is_empty());
+    }
+
+    #[test]
+    fn test_market_moving_through_resting_order_fills_it() {
+        let mut venue = SimulatedVenue::new(VenueConfig {
+            latency_ns: 0,
+            ..Default::default()
+        });
+        venue.on_market_event(&snapshot_event(0, 5, 5));
+        venue.submit(child(1, Side::Buy, 2, OrderStyle::Passive), 0);
+        venue.process_arrivals(0);
+
+        // Asks drop through our bid, taking the 5 ahead of us first
+        let update = DepthUpdate::new("binance", "BTCUSDT", 2, 2)
+            .with_asks(vec![level(101, 0), level(99, 8)]);
+        let events = venue.on_market_event(&MarketEvent::new(1, MarketEventKind::Depth(update)));
+        let filled = fills(&events);
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].price, Price::from_int(100));
+        assert_eq!(filled[0].quantity, Quantity::from_int(2));
+        assert!(filled[0].is_maker);
+        assert_eq!(events.len(), 2);
+        assert_eq!(venue.working_orders(), 0);
+    }
+}
@@ -0,0 +1,33 @@
+//! In-memory market events
+
+use std::collections::VecDeque;
+
+use crate::application::ports::{MarketEventSource, SourceError};
+use crate::domain::MarketEvent;
+
+/// Replays events held in memory, sorted by timestamp
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    events: VecDeque<MarketEvent>,
+}
+
+impl MemorySource {
+    /// Events are sorted by timestamp; equal timestamps keep their order
+    pub fn new(mut events: Vec<MarketEvent>) -> Self {
+        events.sort_by_key(|e| e.timestamp_ns);
+        Self {
+            events: events.into(),
+        }
+    }
+
+    /// Events not yet replayed
+    pub fn remaining(&self) -> usize {
+        self.events.len()
+    }
+}
+
+impl MarketEventSource for MemorySource {
+    fn next_event(&mut self) -> Result<Option<MarketEvent>, SourceError> {
+        Ok(self.events.pop_front())
+    }
+}
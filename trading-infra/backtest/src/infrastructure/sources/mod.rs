@@ -0,0 +1,13 @@
+//! Market Event Sources
+//!
+//! - CaptureSource: A session recorded by the transport capture writer
+//! - SyntheticSource: Books generated by the ABM synthetic order book model
+//! - MemorySource: Events built in memory, mostly for tests
+
+mod capture;
+mod memory;
+mod synthetic;
+
+pub use capture::CaptureSource;
+pub use memory::MemorySource;
+pub use synthetic::{SyntheticConfig, SyntheticSource};
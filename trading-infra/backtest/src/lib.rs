@@ -0,0 +1,69 @@
+//! Backtest Crate
+//!
+//! Event-driven backtesting of strategies against the exchange simulator.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌─────────────────────────┐
+//! │    MarketEventSource    │  capture on disk, ABM-generated books
+//! └────────────┬────────────┘
+//!              │ events in time order
+//!              ▼
+//! ┌──────────────────────────────────────────────────────┐
+//! │                   BacktestRunner                     │
+//! │                                                      │
+//! │  EventClock ─► strategy books ─► BacktestStrategy(s) │
+//! │                                        │ Signals     │
+//! │                                        ▼             │
+//! │                  ExecutionPlanner (ExecutionModel)   │
+//! │                                        │ ChildOrders │
+//! │  Portfolio ◄── fills ──┐               ▼             │
+//! │  ReportBuilder         │        VenuePort (latency)  │
+//! └────────────────────────┼─────────────────────────────┘
+//!                          │
+//!              ┌───────────┴────────────┐
+//!              │     SimulatedVenue     │  exchange-sim OrderBook,
+//!              │                        │  queue position, fees
+//!              └────────────────────────┘
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use backtest::{
+//!     BacktestConfig, BacktestRunner, ExecutionConfig, ExecutionPlanner, SimulatedVenue,
+//!     SyntheticConfig, SyntheticSource, VenueConfig,
+//! };
+//! use execution::TwapModel;
+//! use strategy::application::ports::SymbolKey;
+//! use strategy::infrastructure::MeanReversionHFT;
+//!
+//! let strategy =
+//!     MeanReversionHFT::with_defaults("mr", vec![SymbolKey::new("binance", "BTCUSDT")]);
+//! let report = BacktestRunner::new(
+//!     SyntheticSource::new(SyntheticConfig::default()),
+//!     SimulatedVenue::new(VenueConfig::default()),
+//!     ExecutionPlanner::new(Box::new(TwapModel::default()), ExecutionConfig::default()),
+//!     BacktestConfig::default(),
+//! )
+//! .with_strategy(strategy)
+//! .run()
+//! .unwrap();
+//! println!("net P&L {:.2}, Sharpe {:.2}", report.net_pnl, report.sharpe);
+//! ```
+
+// Clean Architecture layers
+pub mod application;
+pub mod domain;
+pub mod infrastructure;
+
+// Re-export key types
+pub use application::ports::{BacktestStrategy, MarketEventSource, SourceError, VenuePort};
+pub use application::services::{
+    BacktestConfig, BacktestRunner, ExecutionConfig, ExecutionPlanner,
+};
+pub use domain::{BacktestReport, ChildOrder, Fill, MarketEvent, MarketEventKind, Portfolio};
+pub use infrastructure::{
+    CaptureSource, MemorySource, SimulatedVenue, SyntheticConfig, SyntheticSource, VenueConfig,
+};
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::warn;
+use trading_core::DepthSnapshotEvent;
+
+use crate::domain::{
+    BookUpdate, BookUpdateKind, LevelBook, OrderBookWriter, QualifiedSymbol, SequenceCheck,
+    StreamData, SyncStatus, VenueAdapter, VenueError, WsEvent,
+};
+use crate::infrastructure::WsRequestSender;
+
+/// Per-symbol state
+struct SymbolBook {
+    status: SyncStatus,
+    book: LevelBook,
+    /// Last venue update id applied (venues with per-symbol ids)
+    venue_update_id: Option<u64>,
+    /// Update id handed to the writer; contiguous per symbol
+    update_id: u64,
+}
+
+impl Default for SymbolBook {
+    fn default() -> Self {
+        SymbolBook {
+            status: SyncStatus::Uninitialized,
+            book: LevelBook::new(),
+            venue_update_id: None,
+            update_id: 0,
+        }
+    }
+}
+
+/// Keeps books in sync for venues that send snapshots on the stream.
+///
+/// Application layer - feeds raw frames through a `VenueAdapter`, enforces
+/// the venue's sequencing and checksum rules, and writes snapshots and
+/// deltas to the order books with contiguous update ids so downstream
+/// consumers see the same shape as Binance-sourced books.
+///
+/// Symbols that lose sync are returned from `on_frame`; the caller should
+/// resubscribe them, which makes the venue send a fresh snapshot.
+pub struct VenueBookSync<B: OrderBookWriter> {
+    adapter: Arc<dyn VenueAdapter>,
+    order_books: Arc<B>,
+    books: HashMap<QualifiedSymbol, SymbolBook>,
+    /// Last connection-wide sequence number seen
+    last_sequence: Option<u64>,
+}
+
+impl<B: OrderBookWriter> VenueBookSync<B> {
+    pub fn new(adapter: Arc<dyn VenueAdapter>, order_books: Arc<B>) -> Self {
+        VenueBookSync {
+            adapter,
+            order_books,
+            books: HashMap::new(),
+            last_sequence: None,
+        }
+    }
+
+    pub fn adapter(&self) -> &Arc<dyn VenueAdapter> {
+        &self.adapter
+    }
+
+    /// Sync status of a symbol
+    pub fn status(&self, key: &QualifiedSymbol) -> SyncStatus {
+        self.books
+            .get(key)
+            .map(|b| b.status)
+            .unwrap_or(SyncStatus::Uninitialized)
+    }
+
+    /// Local book for a symbol
+    pub fn book(&self, key: &QualifiedSymbol) -> Option<&LevelBook> {
+        self.books.get(key).map(|b| &b.book)
+    }
+
+    /// Forget connection state; call after reconnecting
+    pub fn reset(&mut self) {
+        self.last_sequence = None;
+        for book in self.books.values_mut() {
+            book.status = SyncStatus::OutOfSync;
+        }
+    }
+
+    /// Sync from a venue connection's events until the connection is dropped
+    ///
+    /// The connection is expected to send the adapter's subscribe messages
+    /// itself on every (re)connect; symbols that lose sync in between are
+    /// resubscribed over `requests`.
+    pub async fn run(mut self, requests: WsRequestSender, mut events: mpsc::Receiver<WsEvent>) {
+        let exchange_id = self.adapter.exchange_id().clone();
+        while let Some(event) = events.recv().await {
+            match event {
+                WsEvent::RawMessage(text) => {
+                    let lost = match self.on_frame(&text) {
+                        Ok(lost) => lost,
+                        Err(e) => {
+                            warn!(exchange = %exchange_id, "Unusable venue frame: {}", e);
+                            continue;
+                        }
+                    };
+                    if lost.is_empty() {
+                        continue;
+                    }
+                    let symbols: Vec<String> = lost
+                        .iter()
+                        .filter_map(|key| self.adapter.venue_symbol(key))
+                        .collect();
+                    for message in self.adapter.subscribe_messages(&symbols) {
+                        if let Err(e) = requests.send_text(message).await {
+                            warn!(exchange = %exchange_id, "Failed to resubscribe: {}", e);
+                            return;
+                        }
+                    }
+                }
+                WsEvent::Disconnected => self.reset(),
+                WsEvent::Error(e) => warn!(exchange = %exchange_id, "Venue stream error: {}", e),
+                _ => {}
+            }
+        }
+    }
+
+    /// Process one inbound text frame. Returns symbols that lost sync.
+    pub fn on_frame(&mut self, text: &str) -> Result<Vec<QualifiedSymbol>, VenueError> {
+        let frame = self.adapter.parse_frame(text)?;
+        let mut lost = Vec::new();
+
+        if let Some(sequence) = frame.sequence {
+            if let Some(last) = self.last_sequence {
+                match self.adapter.check_frame_sequence(last, sequence) {
+                    SequenceCheck::Stale => return Ok(lost),
+                    SequenceCheck::Gap => {
+                        warn!(last, sequence, "Frame sequence gap - resyncing all books");
+                        for (key, book) in &mut self.books {
+                            if book.status == SyncStatus::Synced {
+                                book.status = SyncStatus::OutOfSync;
+                                lost.push(key.clone());
+                            }
+                        }
+                    }
+                    SequenceCheck::Apply => {}
+                }
+            }
+            self.last_sequence = Some(sequence);
+        }
+
+        for update in frame.updates {
+            let key = update.key.clone();
+            if !self.apply(update)? && !lost.contains(&key) {
+                lost.push(key);
+            }
+        }
+        Ok(lost)
+    }
+
+    /// Apply one update. Returns false if the symbol just lost sync.
+    fn apply(&mut self, update: BookUpdate) -> Result<bool, VenueError> {
+        let adapter = Arc::clone(&self.adapter);
+        let state = self.books.entry(update.key.clone()).or_default();
+
+        match update.kind {
+            BookUpdateKind::Snapshot => state.book.clear(),
+            BookUpdateKind::Delta => {
+                if state.status != SyncStatus::Synced {
+                    // Wait for the snapshot a resubscribe brings
+                    return Ok(true);
+                }
+                if let Some(last) = state.venue_update_id {
+                    match adapter.check_update(last, &update) {
+                        SequenceCheck::Apply => {}
+                        SequenceCheck::Stale => return Ok(true),
+                        SequenceCheck::Gap => {
+                            warn!(key = %update.key, last, "Update id gap - resyncing");
+                            state.status = SyncStatus::OutOfSync;
+                            return Ok(false);
+                        }
+                    }
+                }
+            }
+        }
+
+        state.book.apply(true, &update.bids)?;
+        state.book.apply(false, &update.asks)?;
+        if let Some(depth) = adapter.book_depth() {
+            state.book.truncate(depth);
+        }
+        if let Some(expected) = update.checksum
+            && let Some(actual) = adapter.checksum(&state.book)
+            && actual != expected
+        {
+            warn!(key = %update.key, expected, actual, "Book checksum mismatch - resyncing");
+            state.status = SyncStatus::OutOfSync;
+            return Ok(false);
+        }
+
+        state.venue_update_id = update.final_update_id.or(state.venue_update_id);
+        state.update_id += 1;
+        match update.kind {
+            BookUpdateKind::Snapshot => {
+                let depth = adapter.book_depth().unwrap_or(usize::MAX);
+                let (bids, asks) = state.book.levels(depth);
+                self.order_books.apply_snapshot(
+                    &update.key,
+                    &DepthSnapshotEvent {
+                        last_update_id: state.update_id,
+                        bids,
+                        asks,
+                    },
+                );
+                state.status = SyncStatus::Synced;
+                Ok(true)
+            }
+            BookUpdateKind::Delta => {
+                let applied = self.order_books.apply_update(
+                    &update.key.exchange,
+                    &StreamData::DepthUpdate {
+                        symbol: update.key.symbol.clone(),
+                        event_time: 0,
+                        first_update_id: state.update_id,
+                        final_update_id: state.update_id,
+                        bids: update.bids,
+                        asks: update.asks,
+                    },
+                );
+                if !applied {
+                    state.status = SyncStatus::OutOfSync;
+                }
+                Ok(applied)
+            }
+        }
+    }
+}
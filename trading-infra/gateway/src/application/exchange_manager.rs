@@ -2,10 +2,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use crate::config::GatewayConfigFile;
-use crate::domain::{ExchangeId, OrderBookWriter, WsEvent};
-use crate::infrastructure::{RestClient, WsClient};
+use crate::config::{GatewayConfigFile, VenueProtocol};
+use crate::domain::{ExchangeId, OrderBookWriter, VenueAdapter, WsEvent};
+use crate::infrastructure::{RestClient, RestRateLimiter, WsClient, adapter_for};
 
+use super::book_sync::VenueBookSync;
 use super::market_data_handler::MarketDataHandler;
 
 /// Manages connections to multiple exchanges
@@ -19,8 +20,11 @@ where
 }
 
 struct ExchangeConnection {
-    rest_client: RestClient,
+    /// Binance-protocol REST API; other venues snapshot over the stream
+    rest_client: Option<RestClient>,
     ws_client: WsClient,
+    /// Protocol adapter for venues kept in sync by `VenueBookSync`
+    adapter: Option<Arc<dyn VenueAdapter>>,
     event_sender: Option<mpsc::Sender<WsEvent>>,
 }
 
@@ -41,19 +45,41 @@ where
     pub fn initialize(&mut self) {
         for exchange_config in self.config.enabled_exchanges() {
             let exchange_id = ExchangeId::new(&exchange_config.id);
+            let ws_client = WsClient::new(exchange_config.ws_url.clone())
+                .with_policy(self.config.global.reconnect_policy());
+
+            // Other protocols snapshot over the stream; they go through VenueBookSync
+            if exchange_config.protocol != VenueProtocol::Binance {
+                let adapter = adapter_for(exchange_config);
+                let ws_client = ws_client
+                    .with_init_frames(adapter.subscribe_messages(&exchange_config.symbols));
+                self.handlers.insert(
+                    exchange_id,
+                    ExchangeConnection {
+                        rest_client: None,
+                        ws_client,
+                        adapter: Some(adapter),
+                        event_sender: None,
+                    },
+                );
+                continue;
+            }
 
             let rest_client = RestClient::new(
                 exchange_config.rest_url.clone(),
                 exchange_config.api_key.clone(),
-            );
-
-            let ws_client = WsClient::new(exchange_config.ws_url.clone());
+            )
+            .with_api_secret(&exchange_config.api_secret)
+            .with_rate_limiter(Arc::new(RestRateLimiter::new(
+                exchange_config.rate_limits.rest_limits(),
+            )));
 
             self.handlers.insert(
                 exchange_id,
                 ExchangeConnection {
-                    rest_client,
+                    rest_client: Some(rest_client),
                     ws_client,
+                    adapter: None,
                     event_sender: None,
                 },
             );
@@ -99,6 +125,13 @@ where
     ) -> Option<mpsc::Sender<WsEvent>> {
         let connection = self.handlers.get_mut(exchange_id)?;
 
+        // Align private request timestamps with the exchange clock
+        if let Some(rest_client) = &connection.rest_client
+            && let Err(e) = rest_client.sync_time().await
+        {
+            tracing::warn!("Failed to sync clock with {}: {}", exchange_id, e);
+        }
+
         // Connect WebSocket
         let (ws_sender, mut ws_receiver) = match connection.ws_client.connect().await {
             Ok((sender, receiver)) => (sender, receiver),
@@ -108,18 +141,25 @@ where
             }
         };
 
-        // Create market data config
-        let md_config = market_data_config.to_market_data_config(exchange_id.clone(), symbols);
-
-        // Create market data handler
-        let handler = Arc::new(MarketDataHandler::with_arcs(
-            md_config,
-            Arc::new(connection.rest_client.clone()),
-            Arc::clone(&self.order_books),
-        ));
-
-        // Start the handler
-        let event_sender = handler.start(ws_sender).await;
+        let event_sender = match (&connection.adapter, &connection.rest_client) {
+            (Some(adapter), _) => {
+                let sync = VenueBookSync::new(Arc::clone(adapter), Arc::clone(&self.order_books));
+                let (event_sender, events) = mpsc::channel(1024);
+                tokio::spawn(sync.run(ws_sender, events));
+                event_sender
+            }
+            (None, Some(rest_client)) => {
+                let md_config =
+                    market_data_config.to_market_data_config(exchange_id.clone(), symbols);
+                let handler = Arc::new(MarketDataHandler::with_arcs(
+                    md_config,
+                    Arc::new(rest_client.clone()),
+                    Arc::clone(&self.order_books),
+                ));
+                handler.start(ws_sender).await
+            }
+            (None, None) => return None,
+        };
 
         // Forward WS events to the handler
         let event_sender_clone = event_sender.clone();
@@ -137,9 +177,9 @@ where
         Some(event_sender)
     }
 
-    /// Get the REST client for a specific exchange
+    /// Get the REST client for a Binance-protocol exchange
     pub fn rest_client(&self, exchange_id: &ExchangeId) -> Option<&RestClient> {
-        self.handlers.get(exchange_id).map(|c| &c.rest_client)
+        self.handlers.get(exchange_id)?.rest_client.as_ref()
     }
 
     /// Get list of connected exchange IDs
@@ -153,12 +153,15 @@ where
             handler.run_snapshot_loop().await;
         });
 
-        // Subscribe to depth streams for all symbols
+        // Subscribe to depth and trade streams for all symbols
         let streams: Vec<String> = self
             .config
             .symbols
             .iter()
-            .map(|s| format!("{}@depth", s.to_lowercase()))
+            .flat_map(|s| {
+                let symbol = s.to_lowercase();
+                [format!("{}@depth", symbol), format!("{}@trade", symbol)]
+            })
             .collect();
 
         if !streams.is_empty()
@@ -198,10 +201,18 @@ where
                 }) => {
                     self.handle_depth_update(&symbol, first_update_id, final_update_id, bids, asks);
                 }
+                // Trades need no book sync and are forwarded as they arrive
+                WsEvent::StreamData(trade @ StreamData::Trade { .. }) => {
+                    self.order_books
+                        .apply_update(&self.config.exchange_id, &trade);
+                }
                 WsEvent::Disconnected => {
                     tracing::warn!("WebSocket disconnected, marking all symbols out of sync");
                     self.mark_all_out_of_sync();
                 }
+                WsEvent::Reconnected => {
+                    tracing::info!("WebSocket reconnected, resyncing symbols");
+                }
                 WsEvent::Error(e) => {
                     tracing::error!("WebSocket error: {}", e);
                 }
@@ -374,6 +385,8 @@ where
         tracing::info!("{} synced successfully", symbol_upper);
     }
 
+    /// Every symbol that was syncing or synced on the lost connection needs a
+    /// fresh snapshot; buffered updates from that connection are useless
     fn mark_all_out_of_sync(&self) {
         let mut state = self.state.lock();
 
@@ -381,7 +394,7 @@ where
             .symbols
             .iter_mut()
             .filter_map(|(symbol, sym_state)| {
-                if sym_state.status == SyncStatus::Synced {
+                if sym_state.status != SyncStatus::Uninitialized {
                     sym_state.status = SyncStatus::OutOfSync;
                     sym_state.buffer.clear();
                     Some(symbol.clone())
@@ -1,9 +1,15 @@
+pub mod book_sync;
 pub mod config;
 pub mod exchange_manager;
 pub mod market_data_handler;
+pub mod order_manager;
 pub mod snapshot_buffer;
+pub mod user_data;
 
+pub use book_sync::VenueBookSync;
 pub use config::{GatewayConfig, MarketDataConfig};
 pub use exchange_manager::ExchangeManager;
 pub use market_data_handler::MarketDataHandler;
+pub use order_manager::{Drift, OmsError, OrderManager, ReconcileReport};
 pub use snapshot_buffer::SnapshotBuffer;
+pub use user_data::{UserDataError, UserDataStream};
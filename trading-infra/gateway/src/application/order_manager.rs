@@ -0,0 +1,700 @@
+//! Order Manager
+//!
+//! Gateway OMS. Assigns client order ids, submits and cancels through one
+//! `OrderVenue` per exchange and folds every venue report into the order
+//! state machine. Fills are de-duplicated per order, so an execution seen in
+//! the REST response and again on the user-data stream counts once.
+//! Positions and open orders are kept per `QualifiedSymbol`; periodic
+//! reconciliation against the venue's open orders and trade history flags
+//! drift.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use thiserror::Error;
+use trading_core::{Price, Quantity, Side};
+
+use crate::domain::{
+    ExchangeId, ExecutionReport, FetchError, Fill, ManagedOrder, OrderRequest, OrderState,
+    OrderStateError, OrderVenue, Position, QualifiedSymbol,
+};
+
+#[derive(Error, Debug)]
+pub enum OmsError {
+    #[error("No order venue for exchange {0}")]
+    UnknownExchange(ExchangeId),
+    #[error("Unknown order {0}")]
+    UnknownOrder(String),
+    #[error("Order {0} is no longer working")]
+    NotWorking(String),
+    #[error(transparent)]
+    State(#[from] OrderStateError),
+    #[error("Venue error: {0}")]
+    Venue(#[from] FetchError),
+}
+
+/// Disagreement between the OMS and the venue
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    /// The venue has a working order the OMS does not know
+    UnknownOrder {
+        client_order_id: String,
+        venue_order_id: i64,
+    },
+    /// The OMS thinks the order is working but the venue does not list it
+    MissingOnVenue {
+        client_order_id: String,
+        state: OrderState,
+    },
+    /// The venue reports a different state
+    StateMismatch {
+        client_order_id: String,
+        local: OrderState,
+        venue: OrderState,
+    },
+    /// Executed quantity differs after merging the trade history
+    QuantityMismatch {
+        client_order_id: String,
+        local: Quantity,
+        venue: Quantity,
+    },
+    /// A trade that never arrived through REST or the stream (now applied)
+    MissedFill {
+        client_order_id: String,
+        trade_id: i64,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::UnknownOrder {
+                client_order_id,
+                venue_order_id,
+            } => write!(
+                f,
+                "venue order {} ({}) is not tracked",
+                venue_order_id, client_order_id
+            ),
+            Drift::MissingOnVenue {
+                client_order_id,
+                state,
+            } => write!(
+                f,
+                "{} is {} locally but not open on the venue",
+                client_order_id, state
+            ),
+            Drift::StateMismatch {
+                client_order_id,
+                local,
+                venue,
+            } => write!(
+                f,
+                "{} is {} locally, {} on the venue",
+                client_order_id, local, venue
+            ),
+            Drift::QuantityMismatch {
+                client_order_id,
+                local,
+                venue,
+            } => write!(
+                f,
+                "{} executed {} locally, {} on the venue",
+                client_order_id, local, venue
+            ),
+            Drift::MissedFill {
+                client_order_id,
+                trade_id,
+            } => write!(f, "{} missed trade {}", client_order_id, trade_id),
+        }
+    }
+}
+
+/// Result of reconciling one symbol
+#[derive(Debug, Clone)]
+pub struct ReconcileReport {
+    pub key: QualifiedSymbol,
+    pub drifts: Vec<Drift>,
+}
+
+impl ReconcileReport {
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+}
+
+#[derive(Default)]
+struct OrderBook {
+    orders: HashMap<String, ManagedOrder>,
+    venue_ids: HashMap<(ExchangeId, i64), String>,
+    positions: HashMap<QualifiedSymbol, Position>,
+}
+
+impl OrderBook {
+    fn find(&self, exchange_id: &ExchangeId, report: &ExecutionReport) -> Option<String> {
+        if self.orders.contains_key(&report.client_order_id) {
+            return Some(report.client_order_id.clone());
+        }
+        self.venue_ids
+            .get(&(exchange_id.clone(), report.venue_order_id))
+            .cloned()
+    }
+
+    fn record_fills(&mut self, key: &QualifiedSymbol, side: Side, fills: &[Fill]) {
+        let position = self
+            .positions
+            .entry(key.clone())
+            .or_insert_with(|| Position::new(key.clone()));
+        for fill in fills {
+            position.apply_fill(side, fill);
+        }
+    }
+
+    /// Apply a report to a known order, returning the order and new fills
+    fn apply(
+        &mut self,
+        client_order_id: &str,
+        report: &ExecutionReport,
+    ) -> Result<(ManagedOrder, Vec<Fill>), OmsError> {
+        let order = self
+            .orders
+            .get_mut(client_order_id)
+            .ok_or_else(|| OmsError::UnknownOrder(client_order_id.to_string()))?;
+        // Fills reach the position even if the status is out of order
+        let new_fills = order.apply_report_fills(report);
+        let status = order.apply_report_status(report);
+        let order = order.clone();
+
+        self.venue_ids.insert(
+            (order.key().exchange.clone(), report.venue_order_id),
+            order.client_order_id.clone(),
+        );
+        self.record_fills(order.key(), order.request.side, &new_fills);
+        status?;
+        Ok((order, new_fills))
+    }
+}
+
+/// Tracks order lifecycle, fills and positions across venues
+pub struct OrderManager<V: OrderVenue> {
+    venues: HashMap<ExchangeId, Arc<V>>,
+    id_prefix: String,
+    sequence: AtomicU64,
+    book: Mutex<OrderBook>,
+}
+
+impl<V: OrderVenue> OrderManager<V> {
+    /// Create an OMS whose client order ids start with `id_prefix`
+    ///
+    /// Ids also carry the session start time so they stay unique across
+    /// restarts.
+    pub fn new(id_prefix: impl Into<String>) -> Self {
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        OrderManager {
+            venues: HashMap::new(),
+            id_prefix: format!("{}-{:x}", id_prefix.into(), session),
+            sequence: AtomicU64::new(0),
+            book: Mutex::new(OrderBook::default()),
+        }
+    }
+
+    /// Register the order venue for an exchange
+    pub fn with_venue(mut self, exchange_id: ExchangeId, venue: Arc<V>) -> Self {
+        self.venues.insert(exchange_id, venue);
+        self
+    }
+
+    /// Generate a fresh client order id
+    pub fn next_client_order_id(&self) -> String {
+        let n = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        format!("{}-{}", self.id_prefix, n)
+    }
+
+    /// Submit an order under a fresh client order id
+    pub async fn submit(&self, request: OrderRequest) -> Result<ManagedOrder, OmsError> {
+        let client_order_id = self.next_client_order_id();
+        self.submit_with_id(&client_order_id, request).await
+    }
+
+    /// Submit an order under a caller-chosen client order id
+    ///
+    /// Idempotent: resubmitting a known id returns the tracked order without
+    /// contacting the venue. An API rejection leaves the order `Rejected`;
+    /// a network failure leaves it `PendingNew` until a report or
+    /// reconciliation settles it.
+    pub async fn submit_with_id(
+        &self,
+        client_order_id: &str,
+        request: OrderRequest,
+    ) -> Result<ManagedOrder, OmsError> {
+        let venue = self.venue(&request.key.exchange)?;
+        {
+            let mut book = self.book.lock();
+            if let Some(existing) = book.orders.get(client_order_id) {
+                return Ok(existing.clone());
+            }
+            book.orders.insert(
+                client_order_id.to_string(),
+                ManagedOrder::new(client_order_id, request.clone()),
+            );
+        }
+
+        match venue.submit_order(client_order_id, &request).await {
+            Ok(report) => Ok(self.book.lock().apply(client_order_id, &report)?.0),
+            Err(e) => {
+                if let FetchError::Api { code, message } = &e
+                    && let Some(order) = self.book.lock().orders.get_mut(client_order_id)
+                {
+                    order.reject(format!("{}: {}", code, message))?;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Cancel a working order
+    ///
+    /// The order is `PendingCancel` until the venue answers. A refused
+    /// cancel puts it back to its previous state.
+    pub async fn cancel(&self, client_order_id: &str) -> Result<ManagedOrder, OmsError> {
+        self.request_cancel(client_order_id, OrderState::PendingCancel)
+            .await
+    }
+
+    /// Cancel-replace: cancel a working order, then submit its replacement
+    ///
+    /// Returns the replacement order. Nothing is submitted if the original
+    /// filled or could not be canceled.
+    pub async fn replace(
+        &self,
+        client_order_id: &str,
+        quantity: Quantity,
+        price: Option<Price>,
+    ) -> Result<ManagedOrder, OmsError> {
+        let canceled = self
+            .request_cancel(client_order_id, OrderState::PendingReplace)
+            .await?;
+        if canceled.state != OrderState::Canceled {
+            return Err(OmsError::NotWorking(client_order_id.to_string()));
+        }
+
+        let mut request = canceled.request;
+        request.quantity = quantity;
+        request.price = price.or(request.price);
+        self.submit(request).await
+    }
+
+    /// Apply a user-data stream execution report
+    ///
+    /// Returns the fills that had not been seen yet.
+    pub fn on_execution_report(
+        &self,
+        exchange_id: &ExchangeId,
+        report: &ExecutionReport,
+    ) -> Result<Vec<Fill>, OmsError> {
+        let mut book = self.book.lock();
+        let client_order_id = book
+            .find(exchange_id, report)
+            .ok_or_else(|| OmsError::UnknownOrder(report.client_order_id.clone()))?;
+        Ok(book.apply(&client_order_id, report)?.1)
+    }
+
+    /// Compare local state with the venue's open orders and trade history
+    ///
+    /// Missed trades are applied; everything else is reported, not changed.
+    pub async fn reconcile(&self, key: &QualifiedSymbol) -> Result<ReconcileReport, OmsError> {
+        let venue = self.venue(&key.exchange)?;
+        let trades = venue.trades(&key.symbol).await?;
+        let open = venue.open_orders(&key.symbol).await?;
+
+        let mut drifts = Vec::new();
+        let mut book = self.book.lock();
+
+        for trade in trades {
+            let Some(client_order_id) = book
+                .venue_ids
+                .get(&(key.exchange.clone(), trade.venue_order_id))
+                .cloned()
+            else {
+                continue;
+            };
+            let Some(order) = book.orders.get_mut(&client_order_id) else {
+                continue;
+            };
+            let side = order.request.side;
+            let trade_id = trade.fill.trade_id;
+            if order.apply_fill(trade.fill.clone()) {
+                book.record_fills(key, side, &[trade.fill]);
+                drifts.push(Drift::MissedFill {
+                    client_order_id,
+                    trade_id,
+                });
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for report in &open {
+            let Some(client_order_id) = book.find(&key.exchange, report) else {
+                drifts.push(Drift::UnknownOrder {
+                    client_order_id: report.client_order_id.clone(),
+                    venue_order_id: report.venue_order_id,
+                });
+                continue;
+            };
+            seen.insert(client_order_id.clone());
+
+            let local = book.orders[&client_order_id].state;
+            let venue_state = OrderState::from(report.status);
+            if local.is_terminal() || local == OrderState::PendingNew {
+                drifts.push(Drift::StateMismatch {
+                    client_order_id: client_order_id.clone(),
+                    local,
+                    venue: venue_state,
+                });
+            }
+            if !local.is_terminal() {
+                // Picks up acknowledgements lost to network failures
+                book.apply(&client_order_id, report)?;
+            }
+
+            let filled = book.orders[&client_order_id].filled_quantity;
+            if filled != report.executed_qty {
+                drifts.push(Drift::QuantityMismatch {
+                    client_order_id,
+                    local: filled,
+                    venue: report.executed_qty,
+                });
+            }
+        }
+
+        for order in book.orders.values() {
+            if order.key() == key && order.state.is_open() && !seen.contains(&order.client_order_id)
+            {
+                drifts.push(Drift::MissingOnVenue {
+                    client_order_id: order.client_order_id.clone(),
+                    state: order.state,
+                });
+            }
+        }
+        drop(book);
+
+        for drift in &drifts {
+            tracing::warn!("Order drift on {}: {}", key, drift);
+        }
+        Ok(ReconcileReport {
+            key: key.clone(),
+            drifts,
+        })
+    }
+
+    /// Reconcile `keys` every `interval` until the future is dropped
+    pub async fn run_reconciliation(&self, keys: &[QualifiedSymbol], interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            for key in keys {
+                if let Err(e) = self.reconcile(key).await {
+                    tracing::warn!("Failed to reconcile {}: {}", key, e);
+                }
+            }
+        }
+    }
+
+    /// A tracked order by client order id
+    pub fn order(&self, client_order_id: &str) -> Option<ManagedOrder> {
+        self.book.lock().orders.get(client_order_id).cloned()
+    }
+
+    /// Working orders for a symbol
+    pub fn open_orders(&self, key: &QualifiedSymbol) -> Vec<ManagedOrder> {
+        self.book
+            .lock()
+            .orders
+            .values()
+            .filter(|o| o.key() == key && o.state.is_open())
+            .cloned()
+            .collect()
+    }
+
+    /// Net position for a symbol
+    pub fn position(&self, key: &QualifiedSymbol) -> Option<Position> {
+        self.book.lock().positions.get(key).cloned()
+    }
+
+    /// All positions with a fill history
+    pub fn positions(&self) -> Vec<Position> {
+        self.book.lock().positions.values().cloned().collect()
+    }
+
+    async fn request_cancel(
+        &self,
+        client_order_id: &str,
+        pending: OrderState,
+    ) -> Result<ManagedOrder, OmsError> {
+        let key = {
+            let mut book = self.book.lock();
+            let order = book
+                .orders
+                .get_mut(client_order_id)
+                .ok_or_else(|| OmsError::UnknownOrder(client_order_id.to_string()))?;
+            order.transition(pending)?;
+            order.key().clone()
+        };
+        let venue = self.venue(&key.exchange)?;
+
+        match venue.cancel_order(&key.symbol, client_order_id).await {
+            Ok(report) => Ok(self.book.lock().apply(client_order_id, &report)?.0),
+            Err(e) => {
+                if matches!(e, FetchError::Api { .. })
+                    && let Some(order) = self.book.lock().orders.get_mut(client_order_id)
+                {
+                    order.cancel_rejected()?;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    fn venue(&self, exchange_id: &ExchangeId) -> Result<Arc<V>, OmsError> {
+        self.venues
+            .get(exchange_id)
+            .cloned()
+            .ok_or_else(|| OmsError::UnknownExchange(exchange_id.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::VenueTrade;
+    use async_trait::async_trait;
+    use trading_core::OrderStatus;
+
+    /// Venue that fills one unit per submit and serves scripted queries
+    #[derive(Default)]
+    struct ScriptedVenue {
+        submits: Mutex<u32>,
+        open: Mutex<Vec<ExecutionReport>>,
+        trades: Mutex<Vec<VenueTrade>>,
+    }
+
+    fn fill(trade_id: i64, qty: i64) -> Fill {
+        Fill {
+            trade_id,
+            price: Price::from_int(100),
+            quantity: Quantity::from_int(qty),
+            commission: Quantity::ZERO,
+            commission_asset: "USDT".to_string(),
+        }
+    }
+
+    fn report(
+        client_order_id: &str,
+        venue_order_id: i64,
+        status: OrderStatus,
+        executed: i64,
+        fills: Vec<Fill>,
+    ) -> ExecutionReport {
+        ExecutionReport {
+            symbol: "BTCUSDT".to_string(),
+            client_order_id: client_order_id.to_string(),
+            venue_order_id,
+            status,
+            executed_qty: Quantity::from_int(executed),
+            fills,
+        }
+    }
+
+    #[async_trait]
+    impl OrderVenue for ScriptedVenue {
+        async fn submit_order(
+            &self,
+            client_order_id: &str,
+            request: &OrderRequest,
+        ) -> Result<ExecutionReport, FetchError> {
+            if request.quantity > Quantity::from_int(100) {
+                return Err(FetchError::Api {
+                    code: -2010,
+                    message: "Account has insufficient balance".to_string(),
+                });
+            }
+            let mut submits = self.submits.lock();
+            *submits += 1;
+            Ok(report(
+                client_order_id,
+                *submits as i64,
+                OrderStatus::PartiallyFilled,
+                1,
+                vec![fill(0, 1)],
+            ))
+        }
+
+        async fn cancel_order(
+            &self,
+            _symbol: &str,
+            client_order_id: &str,
+        ) -> Result<ExecutionReport, FetchError> {
+            Ok(report(client_order_id, 1, OrderStatus::Canceled, 1, vec![]))
+        }
+
+        async fn open_orders(&self, _symbol: &str) -> Result<Vec<ExecutionReport>, FetchError> {
+            Ok(self.open.lock().clone())
+        }
+
+        async fn trades(&self, _symbol: &str) -> Result<Vec<VenueTrade>, FetchError> {
+            Ok(self.trades.lock().clone())
+        }
+    }
+
+    fn key() -> QualifiedSymbol {
+        QualifiedSymbol::new(ExchangeId::simulator(), "BTCUSDT")
+    }
+
+    fn oms(venue: &Arc<ScriptedVenue>) -> OrderManager<ScriptedVenue> {
+        OrderManager::new("gw").with_venue(ExchangeId::simulator(), Arc::clone(venue))
+    }
+
+    fn buy(qty: i64) -> OrderRequest {
+        OrderRequest::limit(
+            key(),
+            Side::Buy,
+            Quantity::from_int(qty),
+            Price::from_int(100),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_submit_and_fill_dedup() {
+        let venue = Arc::new(ScriptedVenue::default());
+        let oms = oms(&venue);
+
+        let order = oms.submit_with_id("c1", buy(3)).await.unwrap();
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+        let again = oms.submit_with_id("c1", buy(3)).await.unwrap();
+        assert_eq!(again.venue_order_id, Some(1));
+        assert_eq!(*venue.submits.lock(), 1);
+
+        // Stream repeats the REST fill, then adds one
+        let exchange = ExchangeId::simulator();
+        let repeated = report("c1", 1, OrderStatus::PartiallyFilled, 1, vec![fill(0, 1)]);
+        assert!(
+            oms.on_execution_report(&exchange, &repeated)
+                .unwrap()
+                .is_empty()
+        );
+        let next = report("", 1, OrderStatus::PartiallyFilled, 2, vec![fill(1, 1)]);
+        assert_eq!(oms.on_execution_report(&exchange, &next).unwrap().len(), 1);
+
+        assert_eq!(
+            oms.order("c1").unwrap().filled_quantity,
+            Quantity::from_int(2)
+        );
+        assert_eq!(
+            oms.position(&key()).unwrap().quantity,
+            Quantity::from_int(2)
+        );
+        assert_eq!(oms.open_orders(&key()).len(), 1);
+
+        let canceled = oms.cancel("c1").await.unwrap();
+        assert_eq!(canceled.state, OrderState::Canceled);
+        assert!(oms.open_orders(&key()).is_empty());
+
+        // Generated ids are unique
+        assert_ne!(oms.next_client_order_id(), oms.next_client_order_id());
+    }
+
+    #[tokio::test]
+    async fn test_late_fill_after_cancel_reaches_position() {
+        let venue = Arc::new(ScriptedVenue::default());
+        let oms = oms(&venue);
+        oms.submit_with_id("c1", buy(3)).await.unwrap();
+        assert_eq!(oms.cancel("c1").await.unwrap().state, OrderState::Canceled);
+
+        // Trade executed before the cancel, reported after it
+        let late = report("c1", 1, OrderStatus::PartiallyFilled, 2, vec![fill(1, 1)]);
+        let fills = oms
+            .on_execution_report(&ExchangeId::simulator(), &late)
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+
+        let order = oms.order("c1").unwrap();
+        assert_eq!(order.state, OrderState::Canceled);
+        assert_eq!(order.filled_quantity, Quantity::from_int(2));
+        assert_eq!(
+            oms.position(&key()).unwrap().quantity,
+            Quantity::from_int(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_error_rejects_order() {
+        let venue = Arc::new(ScriptedVenue::default());
+        let oms = oms(&venue);
+
+        let err = oms.submit_with_id("big", buy(1000)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            OmsError::Venue(FetchError::Api { code: -2010, .. })
+        ));
+        let order = oms.order("big").unwrap();
+        assert_eq!(order.state, OrderState::Rejected);
+        assert!(order.reject_reason.unwrap().contains("insufficient"));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_flags_drift() {
+        let venue = Arc::new(ScriptedVenue::default());
+        let oms = oms(&venue);
+        oms.submit_with_id("c1", buy(3)).await.unwrap();
+        oms.submit_with_id("c2", buy(3)).await.unwrap();
+
+        // c1 filled a trade we never saw; c2 is gone; 99 is not ours
+        *venue.trades.lock() = vec![
+            VenueTrade {
+                venue_order_id: 1,
+                fill: fill(0, 1),
+            },
+            VenueTrade {
+                venue_order_id: 1,
+                fill: fill(5, 1),
+            },
+        ];
+        *venue.open.lock() = vec![
+            report("c1", 1, OrderStatus::PartiallyFilled, 2, vec![]),
+            report("other", 99, OrderStatus::New, 0, vec![]),
+        ];
+
+        let result = oms.reconcile(&key()).await.unwrap();
+        assert!(!result.is_clean());
+        assert!(result.drifts.contains(&Drift::MissedFill {
+            client_order_id: "c1".to_string(),
+            trade_id: 5,
+        }));
+        assert!(result.drifts.contains(&Drift::UnknownOrder {
+            client_order_id: "other".to_string(),
+            venue_order_id: 99,
+        }));
+        assert!(result.drifts.contains(&Drift::MissingOnVenue {
+            client_order_id: "c2".to_string(),
+            state: OrderState::PartiallyFilled,
+        }));
+        assert_eq!(result.drifts.len(), 3);
+        assert_eq!(
+            oms.position(&key()).unwrap().quantity,
+            Quantity::from_int(3)
+        );
+
+        // Settled state reconciles cleanly apart from the known leftovers
+        *venue.open.lock() = vec![report("c1", 1, OrderStatus::PartiallyFilled, 2, vec![])];
+        let result = oms.reconcile(&key()).await.unwrap();
+        assert_eq!(result.drifts.len(), 1);
+    }
+}
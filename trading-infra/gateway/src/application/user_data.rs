@@ -0,0 +1,185 @@
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::domain::{ExchangeId, OrderVenue, QualifiedSymbol, WsEvent};
+use crate::infrastructure::{
+    ReconnectPolicy, RestClient, RestError, WsClient, WsError, parse_execution_report,
+};
+
+use super::order_manager::{OmsError, OrderManager};
+
+/// Listen keys expire after 60 minutes without a keepalive
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Error, Debug)]
+pub enum UserDataError {
+    #[error("Listen key request failed: {0}")]
+    ListenKey(#[from] RestError),
+    #[error("User-data connection failed: {0}")]
+    Connection(#[from] WsError),
+}
+
+/// Follows an exchange's user-data stream and feeds execution reports to the OMS
+///
+/// A listen key is opened over REST and kept alive while the stream runs.
+/// When the key expires or the connection is given up, a new key is opened.
+/// Reports can be missed while disconnected, so the tracked symbols are
+/// reconciled after every (re)connect.
+pub struct UserDataStream<V: OrderVenue> {
+    exchange_id: ExchangeId,
+    rest_client: RestClient,
+    ws_url: String,
+    policy: ReconnectPolicy,
+    oms: Arc<OrderManager<V>>,
+    symbols: Vec<QualifiedSymbol>,
+    keepalive_interval: Duration,
+}
+
+impl<V: OrderVenue> UserDataStream<V> {
+    /// `ws_url` is the venue's raw stream base; the listen key is appended to it
+    pub fn new(
+        exchange_id: ExchangeId,
+        rest_client: RestClient,
+        ws_url: impl Into<String>,
+        oms: Arc<OrderManager<V>>,
+    ) -> Self {
+        UserDataStream {
+            exchange_id,
+            rest_client,
+            ws_url: ws_url.into(),
+            policy: ReconnectPolicy::default(),
+            oms,
+            symbols: Vec::new(),
+            keepalive_interval: KEEPALIVE_INTERVAL,
+        }
+    }
+
+    /// Reconnect settings for the stream and for reopening listen keys
+    pub fn with_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Symbols to reconcile after every (re)connect
+    pub fn with_symbols(mut self, symbols: &[String]) -> Self {
+        self.symbols = symbols
+            .iter()
+            .map(|symbol| QualifiedSymbol::new(self.exchange_id.clone(), symbol.as_str()))
+            .collect();
+        self
+    }
+
+    pub fn with_keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
+
+    /// Run until a listen key cannot be opened within the policy's attempts
+    pub async fn run(self) {
+        let mut attempt = 0;
+        loop {
+            match self.serve().await {
+                Ok(()) => attempt = 0,
+                Err(e) => {
+                    tracing::warn!("User-data stream for {} failed: {}", self.exchange_id, e);
+                    if self.policy.max_attempts > 0 && attempt + 1 >= self.policy.max_attempts {
+                        tracing::error!(
+                            "Giving up on the {} user-data stream after {} attempts",
+                            self.exchange_id,
+                            attempt + 1
+                        );
+                        return;
+                    }
+                    tokio::time::sleep(self.policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// One listen key, from opening it until it expires or the stream ends
+    async fn serve(&self) -> Result<(), UserDataError> {
+        let listen_key = self.rest_client.create_listen_key().await?;
+        let url = format!("{}/{}", self.ws_url.trim_end_matches('/'), listen_key);
+        let (_requests, mut events) = WsClient::new(url)
+            .with_policy(self.policy.clone())
+            .connect()
+            .await?;
+        tracing::info!("Following {} user-data stream", self.exchange_id);
+        self.reconcile().await;
+
+        let mut keepalive = tokio::time::interval(self.keepalive_interval);
+        keepalive.tick().await;
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(WsEvent::RawMessage(text)) => {
+                        if !self.on_message(&text) {
+                            return Ok(());
+                        }
+                    }
+                    Some(WsEvent::Reconnected) => self.reconcile().await,
+                    Some(WsEvent::Error(e)) => {
+                        tracing::warn!("{} user-data stream: {}", self.exchange_id, e);
+                    }
+                    Some(_) => {}
+                    None => return Ok(()),
+                },
+                _ = keepalive.tick() => {
+                    self.rest_client.keepalive_listen_key(&listen_key).await?;
+                }
+            }
+        }
+    }
+
+    /// Apply one stream message; false once the listen key has expired
+    fn on_message(&self, text: &str) -> bool {
+        let Ok(data) = serde_json::from_str::<Value>(text) else {
+            tracing::debug!("Unparseable user-data message: {}", text);
+            return true;
+        };
+        if data.get("e").and_then(Value::as_str) == Some("listenKeyExpired") {
+            tracing::warn!("{} listen key expired, reopening", self.exchange_id);
+            return false;
+        }
+        // Balance and account updates are not tracked here
+        let Some(report) = parse_execution_report(&data) else {
+            return true;
+        };
+
+        match self.oms.on_execution_report(&self.exchange_id, &report) {
+            Ok(fills) => {
+                for fill in fills {
+                    tracing::info!(
+                        "{} {} filled {} @ {} (trade {})",
+                        self.exchange_id,
+                        report.client_order_id,
+                        fill.quantity,
+                        fill.price,
+                        fill.trade_id
+                    );
+                }
+            }
+            // Orders placed outside this gateway share the stream
+            Err(OmsError::UnknownOrder(id)) => {
+                tracing::debug!("Ignoring report for untracked order {}", id);
+            }
+            Err(e) => tracing::warn!(
+                "Failed to apply report for {}: {}",
+                report.client_order_id,
+                e
+            ),
+        }
+        true
+    }
+
+    async fn reconcile(&self) {
+        for key in &self.symbols {
+            if let Err(e) = self.oms.reconcile(key).await {
+                tracing::warn!("Failed to reconcile {}: {}", key, e);
+            }
+        }
+    }
+}
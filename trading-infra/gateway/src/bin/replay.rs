@@ -0,0 +1,166 @@
+//! Replay Binary
+//!
+//! Publishes a market data capture recorded by the gateway back onto the
+//! configured market data transport, so strategies can run against a past
+//! session without a live venue.
+
+use std::collections::HashSet;
+
+use gateway::{QualifiedSymbol, load_config, load_default_config, published_symbol};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use transport::{CaptureReader, ReplaySpeed, Replayer, TransportFactory, TransportType};
+
+fn print_help() {
+    eprintln!(
+        r#"Replay - publish a gateway market data capture
+
+USAGE:
+    replay --capture <DIR> [OPTIONS]
+
+OPTIONS:
+    --capture <DIR>         Capture directory written by the gateway
+    --config <PATH>         Gateway configuration whose market_data transport
+                            receives the replay (default: embedded config)
+    --speed <SPEED>         1x, 10x, 0.5x or max (default: 1x)
+    --from <NS>             Start at this timestamp (ns since epoch)
+    --until <NS>            Stop after this timestamp (ns since epoch)
+    --symbol <EX:SYMBOL>    Only replay this symbol; repeatable
+    --info                  Print the capture index and exit
+    --help                  Print this help message
+
+ENVIRONMENT VARIABLES:
+    RUST_LOG                Log level filter
+
+EXAMPLES:
+    # Replay one symbol ten times faster than recorded
+    replay --capture capture/ --config gateway_config.json \
+        --speed 10x --symbol binance:BTCUSDT
+"#
+    );
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "replay=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let args: Vec<String> = std::env::args().collect();
+    let mut capture_dir: Option<String> = None;
+    let mut config_path: Option<String> = None;
+    let mut speed = ReplaySpeed::RealTime;
+    let mut from_ns: Option<u64> = None;
+    let mut until_ns: Option<u64> = None;
+    let mut symbols: HashSet<QualifiedSymbol> = HashSet::new();
+    let mut info_only = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        let mut value = || {
+            i += 1;
+            args.get(i).cloned().unwrap_or_else(|| {
+                eprintln!("Error: {} requires an argument", arg);
+                std::process::exit(1);
+            })
+        };
+        match arg {
+            "--help" | "-h" => {
+                print_help();
+                return Ok(());
+            }
+            "--capture" => capture_dir = Some(value()),
+            "--config" | "-c" => config_path = Some(value()),
+            "--speed" => speed = value().parse()?,
+            "--from" => from_ns = Some(value().parse()?),
+            "--until" => until_ns = Some(value().parse()?),
+            "--symbol" => {
+                let symbol = value();
+                let key = QualifiedSymbol::parse(&symbol)
+                    .ok_or_else(|| format!("expected EXCHANGE:SYMBOL, got {}", symbol))?;
+                symbols.insert(key);
+            }
+            "--info" => info_only = true,
+            arg => {
+                eprintln!("Unknown argument: {}", arg);
+                print_help();
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let Some(capture_dir) = capture_dir else {
+        eprintln!("Error: --capture is required");
+        print_help();
+        std::process::exit(1);
+    };
+    let reader = CaptureReader::open(&capture_dir)?;
+
+    if info_only {
+        println!(
+            "{}: {} messages in {} chunks, {:?} to {:?}",
+            capture_dir,
+            reader.len(),
+            reader.chunks().len(),
+            reader.start_ns(),
+            reader.end_ns()
+        );
+        for chunk in reader.chunks() {
+            println!(
+                "  {}  {} messages  ts {}..{}  seq {}..{}",
+                chunk.file,
+                chunk.messages,
+                chunk.first_timestamp_ns,
+                chunk.last_timestamp_ns,
+                chunk.first_sequence,
+                chunk.last_sequence
+            );
+        }
+        return Ok(());
+    }
+
+    let config = match config_path {
+        Some(path) => load_config(path)?,
+        None => load_default_config()?,
+    };
+    let market_data = &config.transport.market_data;
+    if market_data.transport_type == TransportType::Channel {
+        tracing::warn!(
+            "Market data transport is an in-process channel; no other process will receive it"
+        );
+    }
+    let publisher = TransportFactory::create_publisher(market_data)?;
+
+    let mut replayer = Replayer::new(reader).with_speed(speed);
+    if let Some(from_ns) = from_ns {
+        replayer = replayer.seek(from_ns);
+    }
+    if let Some(until_ns) = until_ns {
+        replayer = replayer.until(until_ns);
+    }
+    if !symbols.is_empty() {
+        replayer = replayer.with_filter(move |_, data| {
+            published_symbol(data).is_some_and(|key| symbols.contains(&key))
+        });
+    }
+
+    tracing::info!(
+        "Replaying {} messages from {} at {}",
+        replayer.reader().len(),
+        capture_dir,
+        speed
+    );
+    let stats = replayer.run(publisher.as_ref())?;
+    tracing::info!(
+        "Replayed {} messages ({} filtered out), ts {:?} to {:?}",
+        stats.published,
+        stats.filtered,
+        stats.first_timestamp_ns,
+        stats.last_timestamp_ns
+    );
+    Ok(())
+}
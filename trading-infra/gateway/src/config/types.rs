@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use transport::{CaptureConfig, CodecKind, SequencingConfig, TransportConfig};
 
 /// Root configuration for the gateway
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,6 +8,28 @@ pub struct GatewayConfigFile {
     pub exchanges: Vec<ExchangeConfig>,
     #[serde(default)]
     pub global: GlobalConfig,
+    #[serde(default)]
+    pub transport: GatewayTransportConfig,
+}
+
+/// Transports between the gateway and strategy processes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GatewayTransportConfig {
+    /// Outbound deltas and snapshots
+    #[serde(default)]
+    pub market_data: TransportConfig,
+    /// Inbound snapshot and retransmit requests
+    #[serde(default)]
+    pub snapshot_requests: TransportConfig,
+    /// Frame format for `market_data`; strategies read either
+    #[serde(default)]
+    pub codec: CodecKind,
+    /// Retransmit buffer kept for `market_data` gaps
+    #[serde(default)]
+    pub sequencing: SequencingConfig,
+    /// Record everything published on `market_data` to disk
+    #[serde(default)]
+    pub capture: Option<CaptureConfig>,
 }
 
 /// Configuration for a single exchange
@@ -29,10 +52,16 @@ pub struct ExchangeConfig {
     /// API secret for signing requests
     #[serde(default)]
     pub api_secret: String,
+    /// Follow our orders over the user-data stream (needs an API key)
+    #[serde(default = "default_true")]
+    pub user_data_stream: bool,
     /// Rate limiting configuration
     #[serde(default)]
     pub rate_limits: RateLimitConfig,
-    /// Symbols to subscribe to
+    /// Wire protocol spoken by the exchange
+    #[serde(default)]
+    pub protocol: VenueProtocol,
+    /// Symbols to subscribe to, in the venue's own format
     #[serde(default)]
     pub symbols: Vec<String>,
     /// Market data handling configuration
@@ -40,13 +69,28 @@ pub struct ExchangeConfig {
     pub market_data: MarketDataConfigJson,
 }
 
+/// Exchange wire protocols with a venue adapter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VenueProtocol {
+    #[default]
+    Binance,
+    Coinbase,
+    Kraken,
+}
+
 /// Rate limiting configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
+    /// Strategy snapshot requests served per second
     #[serde(default = "default_requests_per_second")]
     pub requests_per_second: u32,
+    /// Orders per second across the API key
     #[serde(default = "default_orders_per_second")]
     pub orders_per_second: u32,
+    /// REST request weight per minute across the API key
+    #[serde(default = "default_weight_per_minute")]
+    pub weight_per_minute: u32,
 }
 
 impl Default for RateLimitConfig {
@@ -54,6 +98,19 @@ impl Default for RateLimitConfig {
         RateLimitConfig {
             requests_per_second: default_requests_per_second(),
             orders_per_second: default_orders_per_second(),
+            weight_per_minute: default_weight_per_minute(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Starting limits for the exchange's shared REST limiter
+    pub fn rest_limits(&self) -> crate::infrastructure::RateLimits {
+        crate::infrastructure::RateLimits {
+            weight_limit: self.weight_per_minute,
+            weight_interval: Duration::from_secs(60),
+            order_limit: self.orders_per_second,
+            order_interval: Duration::from_secs(1),
         }
     }
 }
@@ -95,23 +152,36 @@ impl MarketDataConfigJson {
 /// Global configuration that applies to all exchanges
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalConfig {
-    /// Delay between reconnection attempts in milliseconds
+    /// Delay before the first reconnection attempt in milliseconds
+    /// (doubles per failed attempt)
     #[serde(default = "default_reconnect_delay")]
     pub reconnect_delay_ms: u64,
-    /// Maximum number of reconnection attempts
+    /// Upper bound on the reconnection delay in milliseconds
+    #[serde(default = "default_max_reconnect_delay")]
+    pub max_reconnect_delay_ms: u64,
+    /// Maximum number of consecutive reconnection attempts (0 = unlimited)
     #[serde(default = "default_max_reconnect_attempts")]
     pub max_reconnect_attempts: u32,
     /// Heartbeat interval in milliseconds
     #[serde(default = "default_heartbeat_interval")]
     pub heartbeat_interval_ms: u64,
+    /// Reconnect when a connection has been silent this long, in milliseconds
+    #[serde(default = "default_stale_timeout")]
+    pub stale_timeout_ms: u64,
+    /// Recycle connections older than this, in seconds
+    #[serde(default = "default_max_connection_age")]
+    pub max_connection_age_secs: u64,
 }
 
 impl Default for GlobalConfig {
     fn default() -> Self {
         GlobalConfig {
             reconnect_delay_ms: default_reconnect_delay(),
+            max_reconnect_delay_ms: default_max_reconnect_delay(),
             max_reconnect_attempts: default_max_reconnect_attempts(),
             heartbeat_interval_ms: default_heartbeat_interval(),
+            stale_timeout_ms: default_stale_timeout(),
+            max_connection_age_secs: default_max_connection_age(),
         }
     }
 }
@@ -124,6 +194,18 @@ impl GlobalConfig {
     pub fn heartbeat_interval(&self) -> Duration {
         Duration::from_millis(self.heartbeat_interval_ms)
     }
+
+    /// WebSocket reconnect and heartbeat settings
+    pub fn reconnect_policy(&self) -> crate::infrastructure::ReconnectPolicy {
+        crate::infrastructure::ReconnectPolicy {
+            initial_delay: self.reconnect_delay(),
+            max_delay: Duration::from_millis(self.max_reconnect_delay_ms),
+            max_attempts: self.max_reconnect_attempts,
+            heartbeat_interval: self.heartbeat_interval(),
+            stale_timeout: Duration::from_millis(self.stale_timeout_ms),
+            max_connection_age: Duration::from_secs(self.max_connection_age_secs),
+        }
+    }
 }
 
 // Default value functions for serde
@@ -139,6 +221,10 @@ fn default_orders_per_second() -> u32 {
     5
 }
 
+fn default_weight_per_minute() -> u32 {
+    1200
+}
+
 fn default_snapshot_interval() -> u64 {
     100
 }
@@ -159,6 +245,19 @@ fn default_heartbeat_interval() -> u64 {
     30000
 }
 
+fn default_max_reconnect_delay() -> u64 {
+    60000
+}
+
+fn default_stale_timeout() -> u64 {
+    90000
+}
+
+fn default_max_connection_age() -> u64 {
+    // Binance drops connections at 24h
+    23 * 3600 + 50 * 60
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +299,10 @@ mod tests {
         let config: GatewayConfigFile = serde_json::from_str(json).unwrap();
         assert_eq!(config.exchanges.len(), 1);
         assert_eq!(config.global.reconnect_delay_ms, 3000);
+        assert_eq!(
+            config.transport.market_data.transport_type,
+            transport::TransportType::Channel
+        );
     }
 
     #[test]
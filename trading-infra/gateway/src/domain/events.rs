@@ -71,6 +71,8 @@ pub enum WsEvent {
     Error(String),
     /// Disconnected
     Disconnected,
+    /// Connection re-established and subscriptions replayed
+    Reconnected,
 }
 
 /// WebSocket request messages (Binance-compatible)
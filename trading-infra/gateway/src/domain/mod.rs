@@ -1,11 +1,21 @@
 pub mod events;
 pub mod exchange;
+pub mod order;
 pub mod sync_status;
 pub mod traits;
+pub mod venue;
 
 pub use events::{StreamData, WsEvent, WsRequest, WsResponse};
 pub use exchange::{ExchangeId, QualifiedSymbol};
+pub use order::{
+    ExecutionReport, Fill, ManagedOrder, OrderRequest, OrderState, OrderStateError, Position,
+    VenueTrade,
+};
 pub use sync_status::SyncStatus;
 pub use traits::{
-    DepthFetcher, FetchError, OrderBookWriter, SnapshotWriter, StreamParser, UpdateWriter,
+    DepthFetcher, FetchError, OrderBookWriter, OrderVenue, SnapshotWriter, StreamParser,
+    UpdateWriter, VenueAdapter,
+};
+pub use venue::{
+    BookLevel, BookUpdate, BookUpdateKind, LevelBook, SequenceCheck, VenueError, VenueFrame,
 };
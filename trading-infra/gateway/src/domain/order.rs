@@ -0,0 +1,530 @@
+//! Order Lifecycle
+//!
+//! Orders tracked by the gateway OMS. Everything the venue says about an
+//! order (REST responses, user-data stream execution reports, open order
+//! queries) is folded into a `ManagedOrder` through a validated state
+//! machine:
+//!
+//! ```text
+//! PendingNew ──► New ──► PartiallyFilled ──► Filled
+//!     │           │  ▲          │  ▲
+//!     ▼           ▼  │          ▼  │
+//!  Rejected   PendingCancel / PendingReplace ──► Canceled / Expired
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+
+use trading_core::{OrderStatus, OrderType, Price, Quantity, Side, TimeInForce};
+
+use super::exchange::QualifiedSymbol;
+
+/// Gateway-side order state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderState {
+    /// Sent, not yet acknowledged
+    PendingNew,
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected,
+    Expired,
+    /// Cancel sent, not yet acknowledged
+    PendingCancel,
+    /// Cancel-replace sent, not yet acknowledged
+    PendingReplace,
+}
+
+impl OrderState {
+    /// No further transitions are possible
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Filled | OrderState::Canceled | OrderState::Rejected | OrderState::Expired
+        )
+    }
+
+    /// Working (or possibly working) on the venue
+    pub fn is_open(&self) -> bool {
+        !self.is_terminal()
+    }
+
+    /// Waiting on the venue to acknowledge a cancel or replace
+    pub fn is_pending_cancel(&self) -> bool {
+        matches!(self, OrderState::PendingCancel | OrderState::PendingReplace)
+    }
+
+    /// Whether the state machine allows moving from `self` to `to`
+    pub fn can_transition_to(&self, to: OrderState) -> bool {
+        use OrderState::*;
+
+        if *self == to {
+            return !self.is_terminal() || to == Filled;
+        }
+        match self {
+            PendingNew => matches!(
+                to,
+                New | PartiallyFilled | Filled | Rejected | Canceled | Expired
+            ),
+            New | PartiallyFilled => matches!(
+                to,
+                PartiallyFilled | Filled | Canceled | Expired | PendingCancel | PendingReplace
+            ),
+            // Leaving back to New/PartiallyFilled means the cancel was refused
+            PendingCancel | PendingReplace => {
+                matches!(to, New | PartiallyFilled | Filled | Canceled | Expired)
+            }
+            Filled | Canceled | Rejected | Expired => false,
+        }
+    }
+}
+
+impl From<OrderStatus> for OrderState {
+    fn from(status: OrderStatus) -> Self {
+        match status {
+            OrderStatus::New => OrderState::New,
+            OrderStatus::PartiallyFilled => OrderState::PartiallyFilled,
+            OrderStatus::Filled => OrderState::Filled,
+            OrderStatus::Canceled => OrderState::Canceled,
+            OrderStatus::Rejected => OrderState::Rejected,
+            OrderStatus::Expired => OrderState::Expired,
+            OrderStatus::PendingCancel => OrderState::PendingCancel,
+        }
+    }
+}
+
+impl fmt::Display for OrderState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Transition refused by the order state machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderStateError {
+    pub client_order_id: String,
+    pub from: OrderState,
+    pub to: OrderState,
+}
+
+impl fmt::Display for OrderStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Order {} cannot move from {} to {}",
+            self.client_order_id, self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for OrderStateError {}
+
+/// Order to submit to a venue
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    pub key: QualifiedSymbol,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub quantity: Quantity,
+    pub price: Option<Price>,
+    pub time_in_force: TimeInForce,
+}
+
+impl OrderRequest {
+    pub fn limit(key: QualifiedSymbol, side: Side, quantity: Quantity, price: Price) -> Self {
+        OrderRequest {
+            key,
+            side,
+            order_type: OrderType::Limit,
+            quantity,
+            price: Some(price),
+            time_in_force: TimeInForce::Gtc,
+        }
+    }
+
+    pub fn market(key: QualifiedSymbol, side: Side, quantity: Quantity) -> Self {
+        OrderRequest {
+            key,
+            side,
+            order_type: OrderType::Market,
+            quantity,
+            price: None,
+            time_in_force: TimeInForce::Ioc,
+        }
+    }
+}
+
+/// A single execution against an order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fill {
+    /// Venue trade id, unique per order
+    pub trade_id: i64,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub commission: Quantity,
+    pub commission_asset: String,
+}
+
+/// What the venue reports about one order
+///
+/// Built from REST order responses, user-data stream execution reports and
+/// open order queries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionReport {
+    pub symbol: String,
+    pub client_order_id: String,
+    pub venue_order_id: i64,
+    pub status: OrderStatus,
+    /// Cumulative executed quantity
+    pub executed_qty: Quantity,
+    /// Executions carried by this report (may be empty)
+    pub fills: Vec<Fill>,
+}
+
+/// A trade from the venue's trade history
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueTrade {
+    pub venue_order_id: i64,
+    pub fill: Fill,
+}
+
+/// An order as tracked by the OMS
+#[derive(Debug, Clone)]
+pub struct ManagedOrder {
+    pub client_order_id: String,
+    pub request: OrderRequest,
+    pub venue_order_id: Option<i64>,
+    pub state: OrderState,
+    pub filled_quantity: Quantity,
+    pub fills: Vec<Fill>,
+    pub reject_reason: Option<String>,
+    /// State to fall back to if a pending cancel/replace is refused
+    resume_state: Option<OrderState>,
+    trade_ids: HashSet<i64>,
+}
+
+impl ManagedOrder {
+    pub fn new(client_order_id: impl Into<String>, request: OrderRequest) -> Self {
+        ManagedOrder {
+            client_order_id: client_order_id.into(),
+            request,
+            venue_order_id: None,
+            state: OrderState::PendingNew,
+            filled_quantity: Quantity::ZERO,
+            fills: Vec::new(),
+            reject_reason: None,
+            resume_state: None,
+            trade_ids: HashSet::new(),
+        }
+    }
+
+    pub fn key(&self) -> &QualifiedSymbol {
+        &self.request.key
+    }
+
+    /// Quantity still working
+    pub fn remaining(&self) -> Quantity {
+        self.request.quantity.saturating_sub(self.filled_quantity)
+    }
+
+    /// Volume-weighted fill price
+    pub fn average_price(&self) -> Option<Price> {
+        if self.filled_quantity.is_zero() {
+            return None;
+        }
+        let notional: f64 = self
+            .fills
+            .iter()
+            .map(|f| f.price.to_f64() * f.quantity.to_f64())
+            .sum();
+        Some(Price::from_f64(notional / self.filled_quantity.to_f64()))
+    }
+
+    /// Move to `to` if the state machine allows it
+    pub fn transition(&mut self, to: OrderState) -> Result<(), OrderStateError> {
+        if !self.state.can_transition_to(to) {
+            return Err(OrderStateError {
+                client_order_id: self.client_order_id.clone(),
+                from: self.state,
+                to,
+            });
+        }
+        if to.is_pending_cancel() && !self.state.is_pending_cancel() {
+            self.resume_state = Some(self.state);
+        } else if !to.is_pending_cancel() {
+            self.resume_state = None;
+        }
+        self.state = to;
+        Ok(())
+    }
+
+    /// Venue refused the order
+    pub fn reject(&mut self, reason: impl Into<String>) -> Result<(), OrderStateError> {
+        self.transition(OrderState::Rejected)?;
+        self.reject_reason = Some(reason.into());
+        Ok(())
+    }
+
+    /// Venue refused a cancel or replace; the order keeps working
+    pub fn cancel_rejected(&mut self) -> Result<(), OrderStateError> {
+        let resume = self.resume_state.unwrap_or(OrderState::New);
+        self.transition(resume)
+    }
+
+    /// Record an execution
+    ///
+    /// Returns false if the trade was already recorded.
+    pub fn apply_fill(&mut self, fill: Fill) -> bool {
+        if !self.trade_ids.insert(fill.trade_id) {
+            return false;
+        }
+        self.filled_quantity = self.filled_quantity + fill.quantity;
+        self.fills.push(fill);
+
+        let next = if self.filled_quantity >= self.request.quantity {
+            OrderState::Filled
+        } else {
+            OrderState::PartiallyFilled
+        };
+        if self.state.is_pending_cancel() && next == OrderState::PartiallyFilled {
+            // Still waiting on the cancel; refusal now resumes as partially filled
+            self.resume_state = Some(next);
+        } else if self.state.can_transition_to(next) {
+            self.state = next;
+            self.resume_state = None;
+        }
+        true
+    }
+
+    /// Fold a venue report into the order
+    ///
+    /// Returns the fills that were not seen before. On a state error the
+    /// fills are still recorded; use `apply_report_fills` and
+    /// `apply_report_status` to act on them regardless.
+    pub fn apply_report(&mut self, report: &ExecutionReport) -> Result<Vec<Fill>, OrderStateError> {
+        let new_fills = self.apply_report_fills(report);
+        self.apply_report_status(report)?;
+        Ok(new_fills)
+    }
+
+    /// Record a report's venue id and fills, returning those not seen before
+    pub fn apply_report_fills(&mut self, report: &ExecutionReport) -> Vec<Fill> {
+        self.venue_order_id = Some(report.venue_order_id);
+        report
+            .fills
+            .iter()
+            .filter(|fill| self.apply_fill((*fill).clone()))
+            .cloned()
+            .collect()
+    }
+
+    /// Move to a report's status, ignoring states it has already passed
+    pub fn apply_report_status(&mut self, report: &ExecutionReport) -> Result<(), OrderStateError> {
+        let venue_state = OrderState::from(report.status);
+        if venue_state == self.state {
+            return Ok(());
+        }
+        if self.state.is_terminal() && !venue_state.is_terminal() {
+            // A report sent before the order closed; its fills still count
+            return Ok(());
+        }
+        if self.state.is_pending_cancel() && !venue_state.is_terminal() {
+            // A report sent before the venue saw our cancel; fills already
+            // updated the state a refusal resumes to
+            return Ok(());
+        }
+        if venue_state == OrderState::PendingCancel {
+            return Ok(());
+        }
+        if venue_state == OrderState::New && self.state == OrderState::PartiallyFilled {
+            // Stale acknowledgement that arrived after a fill
+            return Ok(());
+        }
+        self.transition(venue_state)
+    }
+}
+
+/// Net position per symbol, built from fills
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub key: QualifiedSymbol,
+    /// Signed: positive long, negative short
+    pub quantity: Quantity,
+    /// Average entry price of the open quantity
+    pub average_price: Price,
+    pub realized_pnl: f64,
+}
+
+impl Position {
+    pub fn new(key: QualifiedSymbol) -> Self {
+        Position {
+            key,
+            quantity: Quantity::ZERO,
+            average_price: Price::ZERO,
+            realized_pnl: 0.0,
+        }
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.quantity.is_zero()
+    }
+
+    /// Apply an execution on `side`
+    pub fn apply_fill(&mut self, side: Side, fill: &Fill) {
+        let qty = match side {
+            Side::Buy => fill.quantity.to_f64(),
+            Side::Sell => -fill.quantity.to_f64(),
+        };
+        let current = self.quantity.to_f64();
+        let price = fill.price.to_f64();
+        let entry = self.average_price.to_f64();
+
+        if current == 0.0 || current.signum() == qty.signum() {
+            // Opening or adding
+            let total = current + qty;
+            self.average_price = Price::from_f64((entry * current + price * qty) / total);
+        } else {
+            // Reducing, closing or flipping
+            let closed = qty.abs().min(current.abs());
+            self.realized_pnl += closed * (price - entry) * current.signum();
+            if qty.abs() > current.abs() {
+                self.average_price = fill.price;
+            } else if qty.abs() == current.abs() {
+                self.average_price = Price::ZERO;
+            }
+        }
+        self.quantity = self.quantity
+            + match side {
+                Side::Buy => fill.quantity,
+                Side::Sell => Quantity::from_raw(-fill.quantity.raw()),
+            };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ExchangeId;
+
+    fn order(qty: i64) -> ManagedOrder {
+        ManagedOrder::new(
+            "c1",
+            OrderRequest::limit(
+                QualifiedSymbol::new(ExchangeId::simulator(), "BTCUSDT"),
+                Side::Buy,
+                Quantity::from_int(qty),
+                Price::from_int(100),
+            ),
+        )
+    }
+
+    fn fill(trade_id: i64, qty: i64, price: i64) -> Fill {
+        Fill {
+            trade_id,
+            price: Price::from_int(price),
+            quantity: Quantity::from_int(qty),
+            commission: Quantity::ZERO,
+            commission_asset: "USDT".to_string(),
+        }
+    }
+
+    fn report(status: OrderStatus, fills: Vec<Fill>) -> ExecutionReport {
+        ExecutionReport {
+            symbol: "BTCUSDT".to_string(),
+            client_order_id: "c1".to_string(),
+            venue_order_id: 7,
+            status,
+            executed_qty: Quantity::ZERO,
+            fills,
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_and_fill_dedup() {
+        let mut order = order(3);
+        assert_eq!(order.state, OrderState::PendingNew);
+
+        order
+            .apply_report(&report(OrderStatus::New, vec![]))
+            .unwrap();
+        assert_eq!(order.state, OrderState::New);
+        assert_eq!(order.venue_order_id, Some(7));
+
+        // Same trade from the REST response and the stream
+        let new = order
+            .apply_report(&report(OrderStatus::PartiallyFilled, vec![fill(1, 1, 100)]))
+            .unwrap();
+        assert_eq!(new.len(), 1);
+        assert!(!order.apply_fill(fill(1, 1, 100)));
+        assert_eq!(order.filled_quantity, Quantity::from_int(1));
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+
+        order.apply_fill(fill(2, 2, 103));
+        assert_eq!(order.state, OrderState::Filled);
+        assert_eq!(order.average_price(), Some(Price::from_int(102)));
+
+        // Terminal
+        assert!(order.transition(OrderState::Canceled).is_err());
+    }
+
+    #[test]
+    fn test_pending_cancel_resumes_on_refusal() {
+        let mut order = order(3);
+        order.transition(OrderState::New).unwrap();
+        order.transition(OrderState::PendingCancel).unwrap();
+
+        // Fill racing the cancel keeps it pending
+        order.apply_fill(fill(1, 1, 100));
+        assert_eq!(order.state, OrderState::PendingCancel);
+        order
+            .apply_report(&report(OrderStatus::New, vec![]))
+            .unwrap();
+        assert_eq!(order.state, OrderState::PendingCancel);
+
+        order.cancel_rejected().unwrap();
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+
+        order.transition(OrderState::PendingReplace).unwrap();
+        order
+            .apply_report(&report(OrderStatus::Canceled, vec![]))
+            .unwrap();
+        assert_eq!(order.state, OrderState::Canceled);
+        assert!(OrderState::PendingNew.can_transition_to(OrderState::Rejected));
+        assert!(!OrderState::PendingNew.can_transition_to(OrderState::PendingCancel));
+    }
+
+    #[test]
+    fn test_late_report_after_terminal_keeps_fills() {
+        let mut order = order(3);
+        order.transition(OrderState::New).unwrap();
+        order.transition(OrderState::PendingCancel).unwrap();
+        order
+            .apply_report(&report(OrderStatus::Canceled, vec![]))
+            .unwrap();
+
+        // Stream report for a trade that executed before the cancel
+        let new = order
+            .apply_report(&report(OrderStatus::PartiallyFilled, vec![fill(1, 1, 100)]))
+            .unwrap();
+        assert_eq!(new.len(), 1);
+        assert_eq!(order.state, OrderState::Canceled);
+        assert_eq!(order.filled_quantity, Quantity::from_int(1));
+    }
+
+    #[test]
+    fn test_position_average_and_realized_pnl() {
+        let key = QualifiedSymbol::new(ExchangeId::simulator(), "BTCUSDT");
+        let mut position = Position::new(key);
+
+        position.apply_fill(Side::Buy, &fill(1, 1, 100));
+        position.apply_fill(Side::Buy, &fill(2, 1, 110));
+        assert_eq!(position.quantity, Quantity::from_int(2));
+        assert_eq!(position.average_price, Price::from_int(105));
+
+        position.apply_fill(Side::Sell, &fill(3, 3, 120));
+        assert_eq!(position.quantity, Quantity::from_int(-1));
+        assert_eq!(position.average_price, Price::from_int(120));
+        assert!((position.realized_pnl - 30.0).abs() < 1e-9);
+    }
+}
@@ -4,8 +4,10 @@ use trading_core::DepthSnapshotEvent;
 
 use super::events::StreamData;
 use super::exchange::{ExchangeId, QualifiedSymbol};
+use super::order::{ExecutionReport, OrderRequest, VenueTrade};
+use super::venue::{BookUpdate, LevelBook, SequenceCheck, VenueError, VenueFrame};
 
-/// Domain error for depth fetching operations
+/// Domain error for venue REST operations
 ///
 /// This is a domain-level abstraction that doesn't expose infrastructure details.
 /// Infrastructure implementations convert their specific errors to this type.
@@ -44,6 +46,101 @@ pub trait DepthFetcher: Send + Sync {
     ) -> Result<DepthSnapshotEvent, FetchError>;
 }
 
+/// Trait for order entry and order queries on a venue
+///
+/// Implemented by REST clients; the OMS depends only on this abstraction.
+#[async_trait]
+pub trait OrderVenue: Send + Sync {
+    /// Submit an order under the given client order id
+    async fn submit_order(
+        &self,
+        client_order_id: &str,
+        request: &OrderRequest,
+    ) -> Result<ExecutionReport, FetchError>;
+
+    /// Cancel an order by client order id
+    async fn cancel_order(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Result<ExecutionReport, FetchError>;
+
+    /// Orders currently working on the venue
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<ExecutionReport>, FetchError>;
+
+    /// Recent trades of this account
+    async fn trades(&self, symbol: &str) -> Result<Vec<VenueTrade>, FetchError>;
+}
+
+/// Protocol adapter for one venue
+///
+/// Captures everything that differs between exchange protocols: subscribe
+/// messages, frame parsing, sequencing rules, book checksums, symbol
+/// normalisation and order-entry payloads. Implementations are stateless
+/// apart from their configured symbols.
+pub trait VenueAdapter: Send + Sync {
+    /// Exchange this adapter speaks for
+    fn exchange_id(&self) -> &ExchangeId;
+
+    /// Canonical symbol for a venue symbol (e.g. "XBT/USD" -> "BTCUSD")
+    fn normalize_symbol(&self, venue_symbol: &str) -> QualifiedSymbol;
+
+    /// Venue symbol for a canonical one, if configured
+    fn venue_symbol(&self, key: &QualifiedSymbol) -> Option<String>;
+
+    /// Text frames that subscribe to the books of `venue_symbols`
+    fn subscribe_messages(&self, venue_symbols: &[String]) -> Vec<String>;
+
+    /// Whether book snapshots arrive on the stream (otherwise over REST)
+    fn snapshots_over_stream(&self) -> bool;
+
+    /// Parse one inbound text frame
+    fn parse_frame(&self, text: &str) -> Result<VenueFrame, VenueError>;
+
+    /// Check a connection-wide frame sequence number against the last seen
+    fn check_frame_sequence(&self, last: u64, sequence: u64) -> SequenceCheck {
+        if sequence <= last {
+            SequenceCheck::Stale
+        } else if sequence == last + 1 {
+            SequenceCheck::Apply
+        } else {
+            SequenceCheck::Gap
+        }
+    }
+
+    /// Check a delta's update ids against the last applied update id
+    ///
+    /// Default is the Binance rule: drop deltas ending at or before the last
+    /// id, apply the one spanning `last + 1`, and treat anything later as a gap.
+    fn check_update(&self, last_update_id: u64, update: &BookUpdate) -> SequenceCheck {
+        match (update.first_update_id, update.final_update_id) {
+            (Some(first), Some(last)) => {
+                if last <= last_update_id {
+                    SequenceCheck::Stale
+                } else if first <= last_update_id + 1 {
+                    SequenceCheck::Apply
+                } else {
+                    SequenceCheck::Gap
+                }
+            }
+            _ => SequenceCheck::Apply,
+        }
+    }
+
+    /// Levels per side the venue maintains (the book is truncated to this)
+    fn book_depth(&self) -> Option<usize> {
+        None
+    }
+
+    /// Checksum of `book` as the venue computes it, if the venue has one
+    fn checksum(&self, _book: &LevelBook) -> Option<u32> {
+        None
+    }
+
+    /// Order-entry payload for a new order
+    fn order_payload(&self, client_order_id: &str, request: &OrderRequest) -> serde_json::Value;
+}
+
 /// Trait for applying full order book snapshots
 ///
 /// Implements Interface Segregation - separated from update operations.
@@ -0,0 +1,193 @@
+//! Venue Protocol Types
+//!
+//! Venue-neutral shapes produced by `VenueAdapter`s: book updates with
+//! whatever sequencing and checksum data the venue provides, and a small
+//! price-level book that keeps the venue's own strings so checksums can be
+//! computed exactly as the venue does.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use trading_core::{Price, Quantity};
+
+use super::exchange::QualifiedSymbol;
+
+/// Error parsing or applying venue data
+#[derive(Debug, Clone, PartialEq)]
+pub enum VenueError {
+    /// Frame did not match the venue protocol
+    Parse(String),
+    /// Symbol not configured for this venue
+    UnknownSymbol(String),
+}
+
+impl fmt::Display for VenueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VenueError::Parse(msg) => write!(f, "Parse error: {}", msg),
+            VenueError::UnknownSymbol(symbol) => write!(f, "Unknown symbol: {}", symbol),
+        }
+    }
+}
+
+impl std::error::Error for VenueError {}
+
+/// Whether a book update is a full image or a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookUpdateKind {
+    Snapshot,
+    Delta,
+}
+
+/// One book image or change for one symbol
+///
+/// Levels are `[price, quantity]` in the venue's formatting; a zero quantity
+/// removes the level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookUpdate {
+    pub key: QualifiedSymbol,
+    pub kind: BookUpdateKind,
+    /// First venue update id covered (venues with per-symbol ids)
+    pub first_update_id: Option<u64>,
+    /// Last venue update id covered
+    pub final_update_id: Option<u64>,
+    pub bids: Vec<[String; 2]>,
+    pub asks: Vec<[String; 2]>,
+    /// Checksum of the book after applying this update
+    pub checksum: Option<u32>,
+}
+
+/// Everything one inbound frame carried
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VenueFrame {
+    /// Connection-wide sequence number, for venues that number every frame
+    pub sequence: Option<u64>,
+    pub updates: Vec<BookUpdate>,
+}
+
+/// Outcome of a sequencing check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// Next in order
+    Apply,
+    /// Already covered; drop it
+    Stale,
+    /// Something was missed; resync
+    Gap,
+}
+
+/// A price level in venue formatting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookLevel {
+    pub price: String,
+    pub quantity: String,
+}
+
+/// Price-level book keyed by parsed price, keeping venue strings
+#[derive(Debug, Clone, Default)]
+pub struct LevelBook {
+    bids: BTreeMap<Price, BookLevel>,
+    asks: BTreeMap<Price, BookLevel>,
+}
+
+impl LevelBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Apply `[price, quantity]` levels to one side
+    pub fn apply(&mut self, is_bid: bool, levels: &[[String; 2]]) -> Result<(), VenueError> {
+        let side = if is_bid {
+            &mut self.bids
+        } else {
+            &mut self.asks
+        };
+        for [price, quantity] in levels {
+            let key = Price::parse(price)
+                .map_err(|e| VenueError::Parse(format!("price {}: {}", price, e)))?;
+            let qty = Quantity::parse(quantity)
+                .map_err(|e| VenueError::Parse(format!("quantity {}: {}", quantity, e)))?;
+            if qty.is_zero() {
+                side.remove(&key);
+            } else {
+                side.insert(
+                    key,
+                    BookLevel {
+                        price: price.clone(),
+                        quantity: quantity.clone(),
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop levels beyond `depth` on each side
+    pub fn truncate(&mut self, depth: usize) {
+        while self.bids.len() > depth {
+            self.bids.pop_first();
+        }
+        while self.asks.len() > depth {
+            self.asks.pop_last();
+        }
+    }
+
+    /// Bids, best first
+    pub fn bids(&self) -> impl Iterator<Item = &BookLevel> {
+        self.bids.values().rev()
+    }
+
+    /// Asks, best first
+    pub fn asks(&self) -> impl Iterator<Item = &BookLevel> {
+        self.asks.values()
+    }
+
+    /// Top `depth` levels per side as `[price, quantity]`
+    pub fn levels(&self, depth: usize) -> (Vec<[String; 2]>, Vec<[String; 2]>) {
+        let pair = |l: &BookLevel| [l.price.clone(), l.quantity.clone()];
+        (
+            self.bids().take(depth).map(pair).collect(),
+            self.asks().take(depth).map(pair).collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: &str, qty: &str) -> [String; 2] {
+        [price.to_string(), qty.to_string()]
+    }
+
+    #[test]
+    fn test_level_book_apply_and_truncate() {
+        let mut book = LevelBook::new();
+        book.apply(
+            true,
+            &[level("100.0", "1"), level("101.0", "2"), level("99.5", "3")],
+        )
+        .unwrap();
+        book.apply(false, &[level("102.0", "1"), level("103.0", "1")])
+            .unwrap();
+        book.apply(true, &[level("101.00", "0")]).unwrap();
+
+        let best: Vec<&str> = book.bids().map(|l| l.price.as_str()).collect();
+        assert_eq!(best, vec!["100.0", "99.5"]);
+
+        book.truncate(1);
+        let (bids, asks) = book.levels(10);
+        assert_eq!(bids, vec![level("100.0", "1")]);
+        assert_eq!(asks, vec![level("102.0", "1")]);
+        assert!(book.apply(true, &[level("x", "1")]).is_err());
+    }
+}
@@ -2,17 +2,31 @@
 //!
 //! This layer contains adapters for systems we consume from:
 //! - RestClient: HTTP client for exchange REST APIs
+//! - RestRateLimiter: Weight-aware limiter shared by an exchange's REST calls
+//! - Signers: Request signing for private REST endpoints
 //! - WsClient: WebSocket client for exchange streams
 //! - Parsers: Stream data parsing from exchange formats
+//! - Venues: Protocol adapters for Binance, Coinbase and Kraken
 //!
 //! Follows Hexagonal Architecture:
 //! - Infrastructure = inbound (exchanges → gateway)
 //! - Presentation = outbound (gateway → consumers)
 
 pub mod parsers;
+pub mod rate_limiter;
 pub mod rest_client;
+pub mod signer;
+pub mod venues;
 pub mod ws_client;
 
-pub use parsers::{DepthParser, StreamDataParser, TradeParser};
-pub use rest_client::{NewOrderRequest, OrderResponse, RestClient, RestError};
-pub use ws_client::{WsClient, WsError, WsRequestSender};
+pub use parsers::{
+    DepthParser, StreamDataParser, TradeParser, parse_execution_report, parse_order_status,
+};
+pub use rate_limiter::{Lane, RateLimitMetrics, RateLimits, RestRateLimiter};
+pub use rest_client::{
+    EndpointCost, FillResponse, NewOrderRequest, OrderResponse, RestClient, RestError,
+    TradeResponse, endpoint_cost,
+};
+pub use signer::{Ed25519Signer, HmacSha256Signer, RequestSigner, SignerError};
+pub use venues::{BinanceAdapter, CoinbaseAdapter, KrakenAdapter, adapter_for};
+pub use ws_client::{ReconnectPolicy, WsClient, WsError, WsRequestSender};
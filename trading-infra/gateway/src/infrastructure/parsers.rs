@@ -1,7 +1,8 @@
 use serde_json::Value;
 use tracing::debug;
+use trading_core::{OrderStatus, Price, Quantity};
 
-use crate::domain::{StreamData, StreamParser};
+use crate::domain::{ExecutionReport, Fill, StreamData, StreamParser};
 
 /// Default stream data parser that combines all available parsers
 ///
@@ -119,6 +120,61 @@ impl StreamParser for TradeParser {
     }
 }
 
+/// Parse a venue order status
+///
+/// Accepts Binance names (`PARTIALLY_FILLED`) and the simulator's
+/// underscore-free spelling (`PARTIALLYFILLED`).
+pub fn parse_order_status(status: &str) -> Option<OrderStatus> {
+    match status.replace('_', "").to_uppercase().as_str() {
+        "NEW" => Some(OrderStatus::New),
+        "PARTIALLYFILLED" => Some(OrderStatus::PartiallyFilled),
+        "FILLED" => Some(OrderStatus::Filled),
+        "CANCELED" => Some(OrderStatus::Canceled),
+        "REJECTED" => Some(OrderStatus::Rejected),
+        "EXPIRED" | "EXPIREDINMATCH" => Some(OrderStatus::Expired),
+        "PENDINGCANCEL" => Some(OrderStatus::PendingCancel),
+        _ => None,
+    }
+}
+
+/// Parse a user-data stream `executionReport` event
+///
+/// Trade executions (`x` = `TRADE`) carry their fill; other execution types
+/// only report the order status.
+pub fn parse_execution_report(data: &Value) -> Option<ExecutionReport> {
+    if data.get("e")?.as_str()? != "executionReport" {
+        return None;
+    }
+    let str_field = |key: &str| data.get(key).and_then(Value::as_str);
+
+    let fills = if str_field("x") == Some("TRADE") {
+        vec![Fill {
+            trade_id: data.get("t")?.as_i64()?,
+            price: Price::parse(str_field("L")?).ok()?,
+            quantity: Quantity::parse(str_field("l")?).ok()?,
+            commission: Quantity::parse(str_field("n").unwrap_or("0")).ok()?,
+            commission_asset: str_field("N").unwrap_or_default().to_string(),
+        }]
+    } else {
+        Vec::new()
+    };
+
+    // Cancels report the canceled order's id in `C`
+    let client_order_id = match str_field("C") {
+        Some(orig) if !orig.is_empty() => orig,
+        _ => str_field("c")?,
+    };
+
+    Some(ExecutionReport {
+        symbol: str_field("s")?.to_string(),
+        client_order_id: client_order_id.to_string(),
+        venue_order_id: data.get("i")?.as_i64()?,
+        status: parse_order_status(str_field("X")?)?,
+        executed_qty: Quantity::parse(str_field("z")?).ok()?,
+        fills,
+    })
+}
+
 fn parse_price_levels(value: &Value) -> Option<Vec<[String; 2]>> {
     let arr = value.as_array()?;
     let mut levels = Vec::with_capacity(arr.len());
@@ -286,4 +342,43 @@ mod tests {
         });
         assert!(parser.parse("btcusdt@depth", &depth_data).is_some());
     }
+
+    #[test]
+    fn test_execution_report_parser() {
+        let data = serde_json::json!({
+            "e": "executionReport",
+            "E": 1499405658658i64,
+            "s": "BTCUSDT",
+            "c": "gw-1",
+            "C": "",
+            "S": "BUY",
+            "o": "LIMIT",
+            "q": "2.00000000",
+            "p": "50000.00",
+            "x": "TRADE",
+            "X": "PARTIALLY_FILLED",
+            "i": 4293153,
+            "l": "0.50000000",
+            "z": "0.50000000",
+            "L": "49999.50",
+            "n": "0.00050000",
+            "N": "BNB",
+            "t": 12
+        });
+        let report = parse_execution_report(&data).unwrap();
+        assert_eq!(report.client_order_id, "gw-1");
+        assert_eq!(report.venue_order_id, 4293153);
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.fills.len(), 1);
+        assert_eq!(report.fills[0].trade_id, 12);
+        assert_eq!(report.fills[0].price, Price::parse("49999.50").unwrap());
+
+        assert_eq!(
+            parse_order_status("PARTIALLYFILLED"),
+            Some(OrderStatus::PartiallyFilled)
+        );
+        assert!(
+            parse_execution_report(&serde_json::json!({"e": "outboundAccountPosition"})).is_none()
+        );
+    }
 }
@@ -0,0 +1,419 @@
+//! REST Rate Limiter
+//!
+//! One limiter is shared by every REST call to an exchange. Requests are
+//! charged a per-endpoint weight up front; the venue's `X-MBX-USED-WEIGHT-*`
+//! and `X-MBX-ORDER-COUNT-*` response headers then replace the local
+//! estimate, so usage from other processes on the same key is accounted for.
+//!
+//! Lanes keep headroom for what matters most: cancels may use the whole
+//! budget, orders most of it and queries (snapshots, reconciliation) less,
+//! and a lower lane never goes ahead of a waiting higher one. A 429 or 418
+//! blocks every lane until the venue's Retry-After has passed.
+
+use parking_lot::Mutex;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// Back off this long on a 418 without Retry-After
+const DEFAULT_BAN: Duration = Duration::from_secs(120);
+
+/// Priority lane of a request, highest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    Cancel = 0,
+    Order = 1,
+    Query = 2,
+}
+
+impl Lane {
+    /// Share of the weight budget this lane may use
+    fn share(self) -> f64 {
+        match self {
+            Lane::Cancel => 1.0,
+            Lane::Order => 0.9,
+            Lane::Query => 0.7,
+        }
+    }
+}
+
+/// Limits a limiter starts from
+#[derive(Debug, Clone)]
+pub struct RateLimits {
+    /// Request weight per `weight_interval`
+    pub weight_limit: u32,
+    pub weight_interval: Duration,
+    /// Orders per `order_interval`
+    pub order_limit: u32,
+    pub order_interval: Duration,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        RateLimits {
+            weight_limit: 1200,
+            weight_interval: Duration::from_secs(60),
+            order_limit: 10,
+            order_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Snapshot of limiter state
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitMetrics {
+    pub weight_used: u32,
+    pub weight_limit: u32,
+    pub orders_used: u32,
+    pub order_limit: u32,
+    /// Remaining venue-imposed backoff
+    pub blocked_for: Option<Duration>,
+    /// Requests that had to wait
+    pub throttled: u64,
+    /// 429/418 responses received
+    pub rejected: u64,
+}
+
+impl RateLimitMetrics {
+    pub fn weight_headroom(&self) -> u32 {
+        self.weight_limit.saturating_sub(self.weight_used)
+    }
+
+    pub fn order_headroom(&self) -> u32 {
+        self.order_limit.saturating_sub(self.orders_used)
+    }
+}
+
+/// Fixed counting window
+#[derive(Debug)]
+struct Window {
+    interval: Duration,
+    limit: u32,
+    used: u32,
+    started: Instant,
+}
+
+impl Window {
+    fn new(limit: u32, interval: Duration) -> Self {
+        Window {
+            interval,
+            limit,
+            used: 0,
+            started: Instant::now(),
+        }
+    }
+
+    fn roll(&mut self, now: Instant) {
+        if now.duration_since(self.started) >= self.interval {
+            self.used = 0;
+            self.started = now;
+        }
+    }
+
+    fn fits(&self, amount: u32, share: f64) -> bool {
+        amount == 0 || (self.used + amount) as f64 <= self.limit as f64 * share
+    }
+
+    fn resets_in(&self, now: Instant) -> Duration {
+        self.interval
+            .saturating_sub(now.duration_since(self.started))
+    }
+}
+
+struct State {
+    weight: Window,
+    orders: Window,
+    blocked_until: Option<Instant>,
+    /// Waiters per lane
+    waiting: [usize; 3],
+    throttled: u64,
+    rejected: u64,
+}
+
+/// Weight-aware limiter shared by an exchange's REST calls
+pub struct RestRateLimiter {
+    state: Mutex<State>,
+    released: Notify,
+}
+
+impl Default for RestRateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimits::default())
+    }
+}
+
+impl RestRateLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        RestRateLimiter {
+            state: Mutex::new(State {
+                weight: Window::new(limits.weight_limit, limits.weight_interval),
+                orders: Window::new(limits.order_limit, limits.order_interval),
+                blocked_until: None,
+                waiting: [0; 3],
+                throttled: 0,
+                rejected: 0,
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    /// Wait until `weight` (and `orders` order slots) can be spent in `lane`
+    pub async fn acquire(&self, lane: Lane, weight: u32, orders: u32) {
+        let mut waiter: Option<Waiter<'_>> = None;
+        loop {
+            let wait = {
+                let mut state = self.state.lock();
+                let now = Instant::now();
+                state.weight.roll(now);
+                state.orders.roll(now);
+
+                match state.blocked_until {
+                    Some(until) if until > now => until - now,
+                    _ => {
+                        let preempted = state.waiting[..lane as usize].iter().any(|n| *n > 0);
+                        let fits = state.weight.fits(weight, lane.share())
+                            && state.orders.fits(orders, 1.0);
+                        if !preempted && fits {
+                            state.weight.used += weight;
+                            state.orders.used += orders;
+                            return;
+                        }
+                        if fits {
+                            // Only waiting on a higher lane; it will notify
+                            state.weight.resets_in(now).max(Duration::from_millis(10))
+                        } else if !state.weight.fits(weight, lane.share()) {
+                            state.weight.resets_in(now)
+                        } else {
+                            state.orders.resets_in(now)
+                        }
+                    }
+                }
+            };
+
+            if waiter.is_none() {
+                let mut state = self.state.lock();
+                state.waiting[lane as usize] += 1;
+                state.throttled += 1;
+                waiter = Some(Waiter {
+                    limiter: self,
+                    lane,
+                });
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = self.released.notified() => {}
+            }
+        }
+    }
+
+    /// Follow the venue's view of usage and back off on 429/418
+    pub fn observe(&self, status: StatusCode, headers: &HeaderMap) {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        state.weight.roll(now);
+        state.orders.roll(now);
+
+        for (name, value) in headers {
+            let Some(value) = value.to_str().ok().and_then(|v| v.parse::<u32>().ok()) else {
+                continue;
+            };
+            let name = name.as_str();
+            if let Some(interval) = name
+                .strip_prefix("x-mbx-used-weight-")
+                .and_then(parse_interval)
+                && interval == state.weight.interval
+            {
+                state.weight.used = value;
+            } else if let Some(interval) = name
+                .strip_prefix("x-mbx-order-count-")
+                .and_then(parse_interval)
+                && interval == state.orders.interval
+            {
+                state.orders.used = value;
+            }
+        }
+
+        let backoff = match status {
+            StatusCode::TOO_MANY_REQUESTS => {
+                retry_after(headers).unwrap_or_else(|| state.weight.resets_in(now))
+            }
+            StatusCode::IM_A_TEAPOT => retry_after(headers).unwrap_or(DEFAULT_BAN),
+            _ => return,
+        };
+        state.rejected += 1;
+        let until = now + backoff;
+        if state.blocked_until.is_none_or(|current| current < until) {
+            state.blocked_until = Some(until);
+        }
+        tracing::warn!(
+            "Rate limited with HTTP {}, backing off {:?}",
+            status,
+            backoff
+        );
+    }
+
+    pub fn metrics(&self) -> RateLimitMetrics {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        state.weight.roll(now);
+        state.orders.roll(now);
+        RateLimitMetrics {
+            weight_used: state.weight.used,
+            weight_limit: state.weight.limit,
+            orders_used: state.orders.used,
+            order_limit: state.orders.limit,
+            blocked_for: state
+                .blocked_until
+                .filter(|until| *until > now)
+                .map(|until| until - now),
+            throttled: state.throttled,
+            rejected: state.rejected,
+        }
+    }
+}
+
+/// Deregisters a waiting request, including when its future is dropped
+struct Waiter<'a> {
+    limiter: &'a RestRateLimiter,
+    lane: Lane,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        self.limiter.state.lock().waiting[self.lane as usize] -= 1;
+        self.limiter.released.notify_waiters();
+    }
+}
+
+/// Header interval suffix such as "1m", "10s" or "1d"
+fn parse_interval(suffix: &str) -> Option<Duration> {
+    let split = suffix.find(|c: char| !c.is_ascii_digit())?;
+    let count: u64 = suffix[..split].parse().ok()?;
+    let unit = match &suffix[split..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    Some(Duration::from_secs(count * unit))
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use std::sync::Arc;
+
+    fn limits(weight: u32) -> RateLimits {
+        RateLimits {
+            weight_limit: weight,
+            weight_interval: Duration::from_secs(60),
+            order_limit: 2,
+            order_interval: Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("1m"), Some(Duration::from_secs(60)));
+        assert_eq!(parse_interval("10s"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_interval("1d"), Some(Duration::from_secs(86400)));
+        assert_eq!(parse_interval("m"), None);
+    }
+
+    #[tokio::test]
+    async fn test_headers_replace_local_estimate() {
+        let limiter = RestRateLimiter::new(limits(100));
+        limiter.acquire(Lane::Query, 5, 0).await;
+        assert_eq!(limiter.metrics().weight_used, 5);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-mbx-used-weight-1m", HeaderValue::from(42));
+        headers.insert("x-mbx-order-count-10s", HeaderValue::from(1));
+        // Different window than the limiter tracks
+        headers.insert("x-mbx-order-count-1d", HeaderValue::from(900));
+        limiter.observe(StatusCode::OK, &headers);
+
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.weight_used, 42);
+        assert_eq!(metrics.weight_headroom(), 58);
+        assert_eq!(metrics.orders_used, 1);
+        assert_eq!(metrics.order_headroom(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_after_blocks_all_lanes() {
+        let limiter = RestRateLimiter::new(limits(100));
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from(3));
+        limiter.observe(StatusCode::TOO_MANY_REQUESTS, &headers);
+        assert_eq!(limiter.metrics().rejected, 1);
+
+        let start = Instant::now();
+        limiter.acquire(Lane::Cancel, 1, 0).await;
+        assert!(start.elapsed() >= Duration::from_secs(3));
+        assert_eq!(limiter.metrics().blocked_for, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_query_lane_leaves_headroom_for_cancels() {
+        let limiter = Arc::new(RestRateLimiter::new(limits(10)));
+        // Queries stop at 70% of the budget
+        for _ in 0..7 {
+            limiter.acquire(Lane::Query, 1, 0).await;
+        }
+        let query = tokio::spawn({
+            let limiter = Arc::clone(&limiter);
+            async move { limiter.acquire(Lane::Query, 1, 0).await }
+        });
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(!query.is_finished());
+
+        // A cancel still gets through immediately
+        let start = Instant::now();
+        limiter.acquire(Lane::Cancel, 3, 0).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(limiter.metrics().weight_headroom(), 0);
+
+        // The query goes out once the window rolls over
+        query.await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(59));
+        assert_eq!(limiter.metrics().throttled, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_waiting_cancel_preempts_query() {
+        let limiter = Arc::new(RestRateLimiter::new(limits(10)));
+        limiter.acquire(Lane::Cancel, 10, 0).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let spawn = |lane: Lane, name: &'static str| {
+            let limiter = Arc::clone(&limiter);
+            let order = Arc::clone(&order);
+            tokio::spawn(async move {
+                limiter.acquire(lane, 5, 0).await;
+                order.lock().push(name);
+            })
+        };
+        let query = spawn(Lane::Query, "query");
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        let cancel = spawn(Lane::Cancel, "cancel");
+
+        cancel.await.unwrap();
+        query.await.unwrap();
+        assert_eq!(*order.lock(), vec!["cancel", "query"]);
+    }
+}
@@ -1,10 +1,24 @@
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, Method};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
-use trading_core::{DepthSnapshotEvent, OrderId, Price, Quantity, Side, TimeInForce};
+use trading_core::{DepthSnapshotEvent, OrderType, Price, Quantity, Side, TimeInForce};
+use url::form_urlencoded;
 
-use crate::domain::{DepthFetcher, FetchError};
+use super::parsers::parse_order_status;
+use super::rate_limiter::{Lane, RestRateLimiter};
+use super::signer::{HmacSha256Signer, RequestSigner};
+use crate::domain::{
+    DepthFetcher, ExecutionReport, FetchError, Fill, OrderRequest, OrderVenue, VenueTrade,
+};
+
+/// Binance "Timestamp for this request is outside of the recvWindow"
+const CLOCK_SKEW_ERROR: i32 = -1021;
+
+const DEFAULT_RECV_WINDOW_MS: u64 = 5000;
 
 #[derive(Error, Debug)]
 pub enum RestError {
@@ -29,11 +43,21 @@ impl From<RestError> for FetchError {
 
 /// REST API client for the exchange simulator
 /// Infrastructure component - handles HTTP communication
+///
+/// Private endpoints carry `timestamp` and `recvWindow` and, when a signer
+/// is configured, a `signature` over the query string followed by the body.
+/// Timestamps are corrected by an offset estimated from the server clock.
+/// Every request passes through a rate limiter shared by all clones.
 #[derive(Clone)]
 pub struct RestClient {
     client: Client,
     base_url: String,
     api_key: String,
+    signer: Option<Arc<dyn RequestSigner>>,
+    recv_window_ms: u64,
+    /// Estimated server time minus local time
+    time_offset_ms: Arc<AtomicI64>,
+    limiter: Arc<RestRateLimiter>,
 }
 
 impl RestClient {
@@ -42,7 +66,40 @@ impl RestClient {
             client: Client::new(),
             base_url,
             api_key,
+            signer: None,
+            recv_window_ms: DEFAULT_RECV_WINDOW_MS,
+            time_offset_ms: Arc::new(AtomicI64::new(0)),
+            limiter: Arc::new(RestRateLimiter::default()),
+        }
+    }
+
+    /// Sign private requests with HMAC-SHA256 (an empty secret leaves them unsigned)
+    pub fn with_api_secret(self, secret: &str) -> Self {
+        if secret.is_empty() {
+            return self;
         }
+        self.with_signer(Arc::new(HmacSha256Signer::new(secret)))
+    }
+
+    /// Sign private requests with a venue-specific signer
+    pub fn with_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn with_recv_window(mut self, recv_window_ms: u64) -> Self {
+        self.recv_window_ms = recv_window_ms;
+        self
+    }
+
+    /// Share a rate limiter, e.g. one per exchange API key
+    pub fn with_rate_limiter(mut self, limiter: Arc<RestRateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    pub fn rate_limiter(&self) -> &Arc<RestRateLimiter> {
+        &self.limiter
     }
 
     /// Get server time
@@ -57,6 +114,23 @@ impl RestClient {
         Ok(resp.server_time)
     }
 
+    /// Re-estimate the server clock offset, assuming symmetric latency
+    pub async fn sync_time(&self) -> Result<i64, RestError> {
+        let sent = local_time_ms();
+        let server_time = self.get_server_time().await?;
+        let received = local_time_ms();
+
+        let offset = server_time - (sent + received) / 2;
+        self.time_offset_ms.store(offset, Ordering::Relaxed);
+        tracing::debug!("Server clock offset for {}: {}ms", self.base_url, offset);
+        Ok(offset)
+    }
+
+    /// Current estimate of server time minus local time
+    pub fn time_offset_ms(&self) -> i64 {
+        self.time_offset_ms.load(Ordering::Relaxed)
+    }
+
     /// Get order book depth snapshot
     pub async fn get_depth(
         &self,
@@ -70,54 +144,210 @@ impl RestClient {
 
     /// Place a new order
     pub async fn place_order(&self, request: NewOrderRequest) -> Result<OrderResponse, RestError> {
-        self.post("/api/v3/order", &request).await
+        self.signed_post("/api/v3/order", &[], &request).await
     }
 
-    /// Cancel an order
+    /// Cancel an order by venue order id
     pub async fn cancel_order(
         &self,
         symbol: &str,
-        order_id: OrderId,
+        order_id: i64,
     ) -> Result<OrderResponse, RestError> {
-        let path = format!("/api/v3/order?symbol={}&orderId={}", symbol, order_id);
-        self.delete(&path).await
+        self.signed_delete(
+            "/api/v3/order",
+            &[
+                ("symbol", symbol.to_string()),
+                ("orderId", order_id.to_string()),
+            ],
+        )
+        .await
     }
 
-    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, RestError> {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
+    /// Cancel an order by client order id
+    pub async fn cancel_order_by_client_id(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Result<OrderResponse, RestError> {
+        self.signed_delete(
+            "/api/v3/order",
+            &[
+                ("symbol", symbol.to_string()),
+                ("origClientOrderId", client_order_id.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Orders still working for a symbol
+    pub async fn get_open_orders(&self, symbol: &str) -> Result<Vec<OrderResponse>, RestError> {
+        self.signed_get("/api/v3/openOrders", &[("symbol", symbol.to_string())])
+            .await
+    }
+
+    /// Account trade history for a symbol
+    pub async fn get_my_trades(&self, symbol: &str) -> Result<Vec<TradeResponse>, RestError> {
+        self.signed_get("/api/v3/myTrades", &[("symbol", symbol.to_string())])
+            .await
+    }
+
+    /// Open a user-data stream, returning its listen key
+    pub async fn create_listen_key(&self) -> Result<String, RestError> {
+        #[derive(Deserialize)]
+        struct ListenKeyResponse {
+            #[serde(rename = "listenKey")]
+            listen_key: String,
+        }
+
+        let resp: ListenKeyResponse = self
+            .keyed(Method::POST, "/api/v3/userDataStream", &[])
             .await?;
+        Ok(resp.listen_key)
+    }
 
-        self.handle_response(resp).await
+    /// Extend a listen key's validity by another 60 minutes
+    pub async fn keepalive_listen_key(&self, listen_key: &str) -> Result<(), RestError> {
+        let _: serde_json::Value = self
+            .keyed(
+                Method::PUT,
+                "/api/v3/userDataStream",
+                &[("listenKey", listen_key.to_string())],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// GET a private endpoint
+    pub async fn signed_get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<T, RestError> {
+        self.send_signed(Method::GET, path, params, None).await
     }
 
-    async fn post<T: DeserializeOwned, B: Serialize>(
+    /// POST a JSON body to a private endpoint
+    pub async fn signed_post<T: DeserializeOwned, B: Serialize>(
         &self,
         path: &str,
+        params: &[(&str, String)],
         body: &B,
     ) -> Result<T, RestError> {
+        let body = serde_json::to_string(body).map_err(|e| RestError::Parse(e.to_string()))?;
+        self.send_signed(Method::POST, path, params, Some(body))
+            .await
+    }
+
+    /// DELETE on a private endpoint
+    pub async fn signed_delete<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<T, RestError> {
+        self.send_signed(Method::DELETE, path, params, None).await
+    }
+
+    /// Send a private request, resyncing the clock once on a skew rejection
+    async fn send_signed<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, String)],
+        body: Option<String>,
+    ) -> Result<T, RestError> {
+        let mut resynced = false;
+        let cost = endpoint_cost(&method, path);
+        loop {
+            self.limiter
+                .acquire(cost.lane, cost.weight, cost.orders)
+                .await;
+            let query = self.signed_query(params, body.as_deref());
+            let url = format!("{}{}?{}", self.base_url, path, query);
+            let mut request = self
+                .client
+                .request(method.clone(), &url)
+                .header("X-MBX-APIKEY", &self.api_key);
+            if let Some(body) = &body {
+                request = request
+                    .header("Content-Type", "application/json")
+                    .body(body.clone());
+            }
+
+            match self.handle_response(request.send().await?).await {
+                Err(RestError::Api { code, .. }) if code == CLOCK_SKEW_ERROR && !resynced => {
+                    tracing::warn!("Clock skew rejected by {}, resyncing", self.base_url);
+                    resynced = true;
+                    self.sync_time().await?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Query string with timestamp, recvWindow and (if signing) signature
+    fn signed_query(&self, params: &[(&str, String)], body: Option<&str>) -> String {
+        let timestamp = local_time_ms() + self.time_offset_ms();
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        for (key, value) in params {
+            query.append_pair(key, value);
+        }
+        query
+            .append_pair("recvWindow", &self.recv_window_ms.to_string())
+            .append_pair("timestamp", &timestamp.to_string());
+        let mut query = query.finish();
+
+        if let Some(signer) = &self.signer {
+            let signature = match body {
+                Some(body) => signer.sign(&format!("{}{}", query, body)),
+                None => signer.sign(&query),
+            };
+            // Base64 signatures carry `+`, `/` and `=`
+            query.push_str("&signature=");
+            query.extend(form_urlencoded::byte_serialize(signature.as_bytes()));
+        }
+        query
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, RestError> {
+        let cost = endpoint_cost(&Method::GET, path);
+        self.limiter
+            .acquire(cost.lane, cost.weight, cost.orders)
+            .await;
+
         let url = format!("{}{}", self.base_url, path);
         let resp = self
             .client
-            .post(&url)
+            .get(&url)
             .header("X-MBX-APIKEY", &self.api_key)
-            .header("Content-Type", "application/json")
-            .json(body)
             .send()
             .await?;
 
         self.handle_response(resp).await
     }
 
-    async fn delete<T: DeserializeOwned>(&self, path: &str) -> Result<T, RestError> {
-        let url = format!("{}{}", self.base_url, path);
+    /// Request an endpoint that takes the API key but no signature
+    async fn keyed<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<T, RestError> {
+        let cost = endpoint_cost(&method, path);
+        self.limiter
+            .acquire(cost.lane, cost.weight, cost.orders)
+            .await;
+
+        let mut url = format!("{}{}", self.base_url, path);
+        if !params.is_empty() {
+            let query = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(params.iter().map(|(k, v)| (*k, v.as_str())))
+                .finish();
+            url = format!("{}?{}", url, query);
+        }
         let resp = self
             .client
-            .delete(&url)
+            .request(method, &url)
             .header("X-MBX-APIKEY", &self.api_key)
             .send()
             .await?;
@@ -130,6 +360,7 @@ impl RestClient {
         resp: reqwest::Response,
     ) -> Result<T, RestError> {
         let status = resp.status();
+        self.limiter.observe(status, resp.headers());
         let text = resp.text().await?;
 
         if !status.is_success() {
@@ -146,6 +377,60 @@ impl RestClient {
     }
 }
 
+/// What a request costs against the venue's limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointCost {
+    pub lane: Lane,
+    pub weight: u32,
+    pub orders: u32,
+}
+
+/// Binance request weights per endpoint (depth scales with `limit`)
+pub fn endpoint_cost(method: &Method, path: &str) -> EndpointCost {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let read = |weight| EndpointCost {
+        lane: Lane::Query,
+        weight,
+        orders: 0,
+    };
+    match (method.as_str(), path) {
+        ("DELETE", "/api/v3/order") => EndpointCost {
+            lane: Lane::Cancel,
+            weight: 1,
+            orders: 0,
+        },
+        ("POST", "/api/v3/order") => EndpointCost {
+            lane: Lane::Order,
+            weight: 1,
+            orders: 1,
+        },
+        (_, "/api/v3/depth") => {
+            let limit = form_urlencoded::parse(query.as_bytes())
+                .find(|(k, _)| k == "limit")
+                .and_then(|(_, v)| v.parse::<u32>().ok())
+                .unwrap_or(100);
+            read(match limit {
+                0..=100 => 1,
+                101..=500 => 5,
+                501..=1000 => 10,
+                _ => 50,
+            })
+        }
+        (_, "/api/v3/exchangeInfo") => read(10),
+        (_, "/api/v3/openOrders") => read(3),
+        (_, "/api/v3/myTrades") => read(10),
+        (_, "/api/v3/userDataStream") => read(2),
+        _ => read(1),
+    }
+}
+
+fn local_time_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
 #[derive(Deserialize)]
 struct ApiError {
     code: i32,
@@ -199,12 +484,27 @@ impl NewOrderRequest {
     }
 }
 
+impl From<&OrderRequest> for NewOrderRequest {
+    fn from(request: &OrderRequest) -> Self {
+        NewOrderRequest {
+            symbol: request.key.symbol.clone(),
+            side: request.side,
+            order_type: request.order_type.to_string(),
+            time_in_force: (request.order_type != OrderType::Market)
+                .then_some(request.time_in_force),
+            quantity: request.quantity.to_string(),
+            price: request.price.map(|p| p.to_string()),
+            client_order_id: None,
+        }
+    }
+}
+
 /// Response from order operations
 #[derive(Debug, Clone, Deserialize)]
 pub struct OrderResponse {
     pub symbol: String,
     #[serde(rename = "orderId")]
-    pub order_id: u64,
+    pub order_id: i64,
     #[serde(rename = "clientOrderId")]
     pub client_order_id: Option<String>,
     #[serde(rename = "transactTime")]
@@ -220,6 +520,88 @@ pub struct OrderResponse {
     #[serde(rename = "type")]
     pub order_type: String,
     pub side: String,
+    #[serde(default)]
+    pub fills: Vec<FillResponse>,
+}
+
+/// Execution in an order response
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillResponse {
+    pub price: String,
+    pub qty: String,
+    pub commission: String,
+    pub commission_asset: String,
+    pub trade_id: i64,
+}
+
+/// Entry in the account trade history
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeResponse {
+    pub symbol: String,
+    pub id: i64,
+    pub order_id: i64,
+    pub price: String,
+    pub qty: String,
+    pub commission: String,
+    pub commission_asset: String,
+    pub time: i64,
+    pub is_buyer: bool,
+}
+
+impl FillResponse {
+    fn to_fill(&self) -> Result<Fill, RestError> {
+        Ok(Fill {
+            trade_id: self.trade_id,
+            price: parse_decimal(&self.price, Price::parse)?,
+            quantity: parse_decimal(&self.qty, Quantity::parse)?,
+            commission: parse_decimal(&self.commission, Quantity::parse)?,
+            commission_asset: self.commission_asset.clone(),
+        })
+    }
+}
+
+impl OrderResponse {
+    /// Convert to the domain execution report
+    pub fn to_report(&self) -> Result<ExecutionReport, RestError> {
+        Ok(ExecutionReport {
+            symbol: self.symbol.clone(),
+            client_order_id: self.client_order_id.clone().unwrap_or_default(),
+            venue_order_id: self.order_id,
+            status: parse_order_status(&self.status)
+                .ok_or_else(|| RestError::Parse(format!("unknown status {}", self.status)))?,
+            executed_qty: parse_decimal(&self.executed_qty, Quantity::parse)?,
+            fills: self
+                .fills
+                .iter()
+                .map(FillResponse::to_fill)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TradeResponse {
+    /// Convert to the domain trade
+    pub fn to_trade(&self) -> Result<VenueTrade, RestError> {
+        Ok(VenueTrade {
+            venue_order_id: self.order_id,
+            fill: Fill {
+                trade_id: self.id,
+                price: parse_decimal(&self.price, Price::parse)?,
+                quantity: parse_decimal(&self.qty, Quantity::parse)?,
+                commission: parse_decimal(&self.commission, Quantity::parse)?,
+                commission_asset: self.commission_asset.clone(),
+            },
+        })
+    }
+}
+
+fn parse_decimal<T>(
+    value: &str,
+    parse: fn(&str) -> Result<T, &'static str>,
+) -> Result<T, RestError> {
+    parse(value).map_err(|e| RestError::Parse(format!("{}: {}", e, value)))
 }
 
 /// Implement DepthFetcher trait for RestClient (Dependency Inversion)
@@ -238,3 +620,248 @@ impl DepthFetcher for RestClient {
             .map_err(FetchError::from)
     }
 }
+
+#[async_trait]
+impl OrderVenue for RestClient {
+    async fn submit_order(
+        &self,
+        client_order_id: &str,
+        request: &OrderRequest,
+    ) -> Result<ExecutionReport, FetchError> {
+        let request = NewOrderRequest::from(request).with_client_order_id(client_order_id);
+        let mut report = self.place_order(request).await?.to_report()?;
+        if report.client_order_id.is_empty() {
+            report.client_order_id = client_order_id.to_string();
+        }
+        Ok(report)
+    }
+
+    async fn cancel_order(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Result<ExecutionReport, FetchError> {
+        let mut report = self
+            .cancel_order_by_client_id(symbol, client_order_id)
+            .await?
+            .to_report()?;
+        report.client_order_id = client_order_id.to_string();
+        Ok(report)
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<ExecutionReport>, FetchError> {
+        let orders = self.get_open_orders(symbol).await?;
+        Ok(orders
+            .iter()
+            .map(OrderResponse::to_report)
+            .collect::<Result<_, _>>()?)
+    }
+
+    async fn trades(&self, symbol: &str) -> Result<Vec<VenueTrade>, FetchError> {
+        let trades = self.get_my_trades(symbol).await?;
+        Ok(trades
+            .iter()
+            .map(TradeResponse::to_trade)
+            .collect::<Result<_, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Minimal HTTP server: records request targets and bodies and answers
+    /// the first order request with a clock-skew error
+    async fn stub_exchange(server_time: i64) -> (String, Arc<Mutex<Vec<(String, String)>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            let mut orders = 0;
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let (head, body) = loop {
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&buf).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(|v| v.parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break (head.to_string(), body.to_string());
+                        }
+                    }
+                };
+                let target = head.split_whitespace().nth(1).unwrap().to_string();
+                seen.lock().unwrap().push((target.clone(), body));
+
+                let (status, json) = if target.starts_with("/api/v3/time") {
+                    ("200 OK", format!(r#"{{"serverTime":{}}}"#, server_time))
+                } else if orders == 0 {
+                    orders += 1;
+                    (
+                        "400 Bad Request",
+                        r#"{"code":-1021,"msg":"Timestamp for this request is outside of the recvWindow."}"#.to_string(),
+                    )
+                } else {
+                    (
+                        "200 OK",
+                        r#"{"symbol":"BTCUSDT","orderId":7,"clientOrderId":null,"transactTime":1,"price":"50000","origQty":"1","executedQty":"0","status":"NEW","timeInForce":"GTC","type":"LIMIT","side":"BUY"}"#.to_string(),
+                    )
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    json.len(),
+                    json
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn query_param<'a>(target: &'a str, key: &str) -> Option<&'a str> {
+        target
+            .split_once('?')?
+            .1
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+    }
+
+    #[tokio::test]
+    async fn test_signed_order_resyncs_clock_on_skew() {
+        // Server clock an hour ahead of ours
+        let server_time = local_time_ms() + 3_600_000;
+        let (url, requests) = stub_exchange(server_time).await;
+        let secret = "secret";
+        let client = RestClient::new(url, "key".to_string()).with_api_secret(secret);
+
+        let request = NewOrderRequest::limit(
+            "BTCUSDT",
+            Side::Buy,
+            Quantity::from_int(1),
+            Price::from_int(50000),
+        );
+        let response = client.place_order(request).await.unwrap();
+        assert_eq!(response.order_id, 7);
+
+        let offset = client.time_offset_ms();
+        assert!((offset - 3_600_000).abs() < 1_000, "offset {}", offset);
+
+        let requests = requests.lock().unwrap();
+        let targets: Vec<&str> = requests
+            .iter()
+            .map(|(t, _)| t.split('?').next().unwrap())
+            .collect();
+        assert_eq!(
+            targets,
+            vec!["/api/v3/order", "/api/v3/time", "/api/v3/order"]
+        );
+
+        // The retry carries a corrected timestamp and a signature over query + body
+        let (target, body) = &requests[2];
+        let timestamp: i64 = query_param(target, "timestamp").unwrap().parse().unwrap();
+        assert!((timestamp - server_time).abs() < 1_000);
+        assert_eq!(query_param(target, "recvWindow"), Some("5000"));
+
+        let (unsigned, signature) = target
+            .split_once('?')
+            .unwrap()
+            .1
+            .rsplit_once("&signature=")
+            .unwrap();
+        let expected = HmacSha256Signer::new(secret).sign(&format!("{}{}", unsigned, body));
+        assert_eq!(signature, expected);
+        assert!(body.contains(r#""symbol":"BTCUSDT""#));
+    }
+
+    #[tokio::test]
+    async fn test_ed25519_signature_is_url_encoded() {
+        use crate::infrastructure::signer::Ed25519Signer;
+        use base64::Engine;
+        use ring::signature::{ED25519, Ed25519KeyPair, UnparsedPublicKey};
+
+        let (url, requests) = stub_exchange(local_time_ms()).await;
+        let der = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let signer = Arc::new(Ed25519Signer::from_pkcs8(der.as_ref()).unwrap());
+        let client = RestClient::new(url, "key".to_string()).with_signer(signer.clone());
+
+        let request = NewOrderRequest::limit(
+            "BTCUSDT",
+            Side::Buy,
+            Quantity::from_int(1),
+            Price::from_int(50000),
+        );
+        client.place_order(request).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let (target, body) = requests.last().unwrap();
+        let (unsigned, raw) = target
+            .split_once('?')
+            .unwrap()
+            .1
+            .rsplit_once("&signature=")
+            .unwrap();
+        // Base64 padding always ends in `=`, so a raw signature would show it
+        assert!(!raw.contains(['+', '/', '=']), "{}", raw);
+
+        // Decoded the way the venue decodes it
+        let (_, signature) = form_urlencoded::parse(target.split_once('?').unwrap().1.as_bytes())
+            .find(|(key, _)| key == "signature")
+            .unwrap();
+        let signature = base64::engine::general_purpose::STANDARD
+            .decode(signature.as_bytes())
+            .unwrap();
+        UnparsedPublicKey::new(&ED25519, signer.public_key())
+            .verify(format!("{}{}", unsigned, body).as_bytes(), &signature)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_without_secret() {
+        let (url, requests) = stub_exchange(local_time_ms()).await;
+        let client = RestClient::new(url, "key".to_string()).with_api_secret("");
+
+        let _ = client.cancel_order("BTCUSDT", 7).await;
+
+        let requests = requests.lock().unwrap();
+        let target = &requests[0].0;
+        assert_eq!(query_param(target, "symbol"), Some("BTCUSDT"));
+        assert!(query_param(target, "timestamp").is_some());
+        assert!(query_param(target, "signature").is_none());
+    }
+
+    #[test]
+    fn test_endpoint_cost() {
+        let depth = |limit: u32| {
+            endpoint_cost(
+                &Method::GET,
+                &format!("/api/v3/depth?symbol=BTCUSDT&limit={limit}"),
+            )
+        };
+        assert_eq!(depth(100).weight, 1);
+        assert_eq!(depth(500).weight, 5);
+        assert_eq!(depth(5000).weight, 50);
+        assert_eq!(depth(100).lane, Lane::Query);
+
+        let cancel = endpoint_cost(&Method::DELETE, "/api/v3/order");
+        assert_eq!((cancel.lane, cancel.orders), (Lane::Cancel, 0));
+        let order = endpoint_cost(&Method::POST, "/api/v3/order");
+        assert_eq!((order.lane, order.orders), (Lane::Order, 1));
+    }
+}
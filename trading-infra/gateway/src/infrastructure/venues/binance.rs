@@ -0,0 +1,95 @@
+//! Binance protocol adapter
+//!
+//! Depth deltas on `<symbol>@depth` carry per-symbol `U`/`u` update ids and
+//! snapshots come from REST, so this adapter only parses deltas.
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use crate::domain::{
+    BookUpdate, BookUpdateKind, ExchangeId, OrderRequest, QualifiedSymbol, StreamData,
+    StreamParser, VenueAdapter, VenueError, VenueFrame,
+};
+use crate::infrastructure::parsers::DepthParser;
+use crate::infrastructure::rest_client::NewOrderRequest;
+
+pub struct BinanceAdapter {
+    exchange_id: ExchangeId,
+    symbols: HashMap<String, String>,
+}
+
+impl BinanceAdapter {
+    pub fn new(exchange_id: impl Into<ExchangeId>, symbols: Vec<String>) -> Self {
+        Self {
+            exchange_id: exchange_id.into(),
+            symbols: super::symbol_map(symbols, |s| s.to_uppercase()),
+        }
+    }
+}
+
+impl VenueAdapter for BinanceAdapter {
+    fn exchange_id(&self) -> &ExchangeId {
+        &self.exchange_id
+    }
+
+    fn normalize_symbol(&self, venue_symbol: &str) -> QualifiedSymbol {
+        QualifiedSymbol::new(self.exchange_id.clone(), venue_symbol)
+    }
+
+    fn venue_symbol(&self, key: &QualifiedSymbol) -> Option<String> {
+        self.symbols.get(&key.symbol).cloned()
+    }
+
+    fn subscribe_messages(&self, venue_symbols: &[String]) -> Vec<String> {
+        let params: Vec<String> = venue_symbols
+            .iter()
+            .map(|s| format!("{}@depth", s.to_lowercase()))
+            .collect();
+        vec![json!({ "method": "SUBSCRIBE", "params": params, "id": 1 }).to_string()]
+    }
+
+    fn snapshots_over_stream(&self) -> bool {
+        false
+    }
+
+    fn parse_frame(&self, text: &str) -> Result<VenueFrame, VenueError> {
+        let value: Value =
+            serde_json::from_str(text).map_err(|e| VenueError::Parse(e.to_string()))?;
+        let Some(stream) = value.get("stream").and_then(|s| s.as_str()) else {
+            // Subscription acks and other control frames
+            return Ok(VenueFrame::default());
+        };
+        if !DepthParser.can_parse(stream) {
+            return Ok(VenueFrame::default());
+        }
+        let data = value.get("data").unwrap_or(&Value::Null);
+        match DepthParser.parse(stream, data) {
+            Some(StreamData::DepthUpdate {
+                symbol,
+                first_update_id,
+                final_update_id,
+                bids,
+                asks,
+                ..
+            }) => Ok(VenueFrame {
+                sequence: None,
+                updates: vec![BookUpdate {
+                    key: self.normalize_symbol(&symbol),
+                    kind: BookUpdateKind::Delta,
+                    first_update_id: Some(first_update_id),
+                    final_update_id: Some(final_update_id),
+                    bids,
+                    asks,
+                    checksum: None,
+                }],
+            }),
+            _ => Err(VenueError::Parse(format!("bad depth update on {}", stream))),
+        }
+    }
+
+    fn order_payload(&self, client_order_id: &str, request: &OrderRequest) -> Value {
+        let order = NewOrderRequest::from(request).with_client_order_id(client_order_id);
+        serde_json::to_value(order).unwrap_or(Value::Null)
+    }
+}
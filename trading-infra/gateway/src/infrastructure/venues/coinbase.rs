@@ -0,0 +1,160 @@
+//! Coinbase protocol adapter (Advanced Trade WebSocket)
+//!
+//! The `level2` channel sends a snapshot per product on subscribe, then
+//! updates carrying absolute quantities per level. Every message on the
+//! connection has a `sequence_num`; a skipped number means a lost message
+//! for some product, so the whole connection must resync. There is no book
+//! checksum.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use trading_core::{OrderType, Side, TimeInForce};
+
+use crate::domain::{
+    BookUpdate, BookUpdateKind, ExchangeId, OrderRequest, QualifiedSymbol, VenueAdapter,
+    VenueError, VenueFrame,
+};
+
+pub struct CoinbaseAdapter {
+    exchange_id: ExchangeId,
+    symbols: HashMap<String, String>,
+}
+
+impl CoinbaseAdapter {
+    pub fn new(exchange_id: impl Into<ExchangeId>, symbols: Vec<String>) -> Self {
+        Self {
+            exchange_id: exchange_id.into(),
+            symbols: super::symbol_map(symbols, |s| super::canonical_symbol(s, |a| a)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Message {
+    channel: String,
+    sequence_num: Option<u64>,
+    /// Shape depends on the channel
+    #[serde(default)]
+    events: Value,
+}
+
+#[derive(Deserialize)]
+struct L2Event {
+    #[serde(rename = "type")]
+    kind: String,
+    product_id: String,
+    #[serde(default)]
+    updates: Vec<L2Update>,
+}
+
+#[derive(Deserialize)]
+struct L2Update {
+    side: String,
+    price_level: String,
+    new_quantity: String,
+}
+
+impl VenueAdapter for CoinbaseAdapter {
+    fn exchange_id(&self) -> &ExchangeId {
+        &self.exchange_id
+    }
+
+    fn normalize_symbol(&self, venue_symbol: &str) -> QualifiedSymbol {
+        QualifiedSymbol::new(
+            self.exchange_id.clone(),
+            super::canonical_symbol(venue_symbol, |a| a),
+        )
+    }
+
+    fn venue_symbol(&self, key: &QualifiedSymbol) -> Option<String> {
+        self.symbols.get(&key.symbol).cloned()
+    }
+
+    fn subscribe_messages(&self, venue_symbols: &[String]) -> Vec<String> {
+        vec![
+            json!({ "type": "subscribe", "product_ids": venue_symbols, "channel": "level2" })
+                .to_string(),
+            // Keeps the connection alive while books are quiet
+            json!({ "type": "subscribe", "channel": "heartbeats" }).to_string(),
+        ]
+    }
+
+    fn snapshots_over_stream(&self) -> bool {
+        true
+    }
+
+    fn parse_frame(&self, text: &str) -> Result<VenueFrame, VenueError> {
+        let message: Message =
+            serde_json::from_str(text).map_err(|e| VenueError::Parse(e.to_string()))?;
+        let mut frame = VenueFrame {
+            sequence: message.sequence_num,
+            updates: Vec::new(),
+        };
+        if message.channel != "l2_data" {
+            return Ok(frame);
+        }
+
+        let events: Vec<L2Event> =
+            serde_json::from_value(message.events).map_err(|e| VenueError::Parse(e.to_string()))?;
+        for event in events {
+            let kind = match event.kind.as_str() {
+                "snapshot" => BookUpdateKind::Snapshot,
+                "update" => BookUpdateKind::Delta,
+                other => return Err(VenueError::Parse(format!("l2 event type {}", other))),
+            };
+            let mut bids = Vec::new();
+            let mut asks = Vec::new();
+            for update in event.updates {
+                let level = [update.price_level, update.new_quantity];
+                match update.side.as_str() {
+                    "bid" => bids.push(level),
+                    "offer" | "ask" => asks.push(level),
+                    other => return Err(VenueError::Parse(format!("l2 side {}", other))),
+                }
+            }
+            frame.updates.push(BookUpdate {
+                key: self.normalize_symbol(&event.product_id),
+                kind,
+                first_update_id: None,
+                final_update_id: None,
+                bids,
+                asks,
+                checksum: None,
+            });
+        }
+        Ok(frame)
+    }
+
+    fn order_payload(&self, client_order_id: &str, request: &OrderRequest) -> Value {
+        let product_id = self
+            .venue_symbol(&request.key)
+            .unwrap_or_else(|| request.key.symbol.clone());
+        let base_size = request.quantity.to_string();
+        let configuration = match (request.order_type, request.price) {
+            (OrderType::Limit | OrderType::LimitMaker, Some(price)) => {
+                let key = match request.time_in_force {
+                    TimeInForce::Ioc => "sor_limit_ioc",
+                    TimeInForce::Fok => "limit_limit_fok",
+                    _ => "limit_limit_gtc",
+                };
+                let mut limit = json!({ "base_size": base_size, "limit_price": price.to_string() });
+                if request.time_in_force != TimeInForce::Ioc {
+                    limit["post_only"] = json!(request.order_type == OrderType::LimitMaker);
+                }
+                json!({ key: limit })
+            }
+            _ => json!({ "market_market_ioc": { "base_size": base_size } }),
+        };
+        json!({
+            "client_order_id": client_order_id,
+            "product_id": product_id,
+            "side": match request.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            },
+            "order_configuration": configuration,
+        })
+    }
+}
@@ -0,0 +1,215 @@
+//! Kraken protocol adapter (WebSocket v1 `book` channel)
+//!
+//! Book frames are arrays of `[channelID, payload..., channelName, pair]`.
+//! The first payload is a snapshot (`as`/`bs`); later ones carry changes
+//! (`a`/`b`) and a CRC32 checksum `c` of the top ten levels. There are no
+//! sequence numbers, so the checksum is the only way to detect drift, and
+//! the local book must be truncated to the subscribed depth after every
+//! update for the checksum to match.
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+use trading_core::{OrderType, Side, TimeInForce};
+
+use crate::domain::{
+    BookLevel, BookUpdate, BookUpdateKind, ExchangeId, LevelBook, OrderRequest, QualifiedSymbol,
+    VenueAdapter, VenueError, VenueFrame,
+};
+
+/// Levels per side covered by the checksum
+const CHECKSUM_DEPTH: usize = 10;
+
+pub struct KrakenAdapter {
+    exchange_id: ExchangeId,
+    symbols: HashMap<String, String>,
+    depth: usize,
+}
+
+impl KrakenAdapter {
+    pub fn new(exchange_id: impl Into<ExchangeId>, symbols: Vec<String>) -> Self {
+        Self {
+            exchange_id: exchange_id.into(),
+            symbols: super::symbol_map(symbols, canonical),
+            depth: CHECKSUM_DEPTH,
+        }
+    }
+
+    /// Subscribe with a different book depth (10, 25, 100, 500 or 1000)
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+}
+
+/// Kraken uses ISO-4217-style codes for some assets
+fn canonical(venue_symbol: &str) -> String {
+    super::canonical_symbol(venue_symbol, |asset| match asset {
+        "XBT" => "BTC",
+        "XDG" => "DOGE",
+        other => other,
+    })
+}
+
+/// Append a level in checksum form: decimal points and leading zeros dropped
+fn push_checksum_level(input: &mut String, level: &BookLevel) {
+    for value in [&level.price, &level.quantity] {
+        let digits: String = value.chars().filter(|c| *c != '.').collect();
+        input.push_str(digits.trim_start_matches('0'));
+    }
+}
+
+fn parse_levels(value: &Value) -> Result<Vec<[String; 2]>, VenueError> {
+    let levels = value
+        .as_array()
+        .ok_or_else(|| VenueError::Parse("book levels not an array".to_string()))?;
+    levels
+        .iter()
+        .map(|level| {
+            let price = level.get(0).and_then(|v| v.as_str());
+            let volume = level.get(1).and_then(|v| v.as_str());
+            match (price, volume) {
+                (Some(price), Some(volume)) => Ok([price.to_string(), volume.to_string()]),
+                _ => Err(VenueError::Parse(format!("book level {}", level))),
+            }
+        })
+        .collect()
+}
+
+impl VenueAdapter for KrakenAdapter {
+    fn exchange_id(&self) -> &ExchangeId {
+        &self.exchange_id
+    }
+
+    fn normalize_symbol(&self, venue_symbol: &str) -> QualifiedSymbol {
+        QualifiedSymbol::new(self.exchange_id.clone(), canonical(venue_symbol))
+    }
+
+    fn venue_symbol(&self, key: &QualifiedSymbol) -> Option<String> {
+        self.symbols.get(&key.symbol).cloned()
+    }
+
+    fn subscribe_messages(&self, venue_symbols: &[String]) -> Vec<String> {
+        vec![
+            json!({
+                "event": "subscribe",
+                "pair": venue_symbols,
+                "subscription": { "name": "book", "depth": self.depth }
+            })
+            .to_string(),
+        ]
+    }
+
+    fn snapshots_over_stream(&self) -> bool {
+        true
+    }
+
+    fn parse_frame(&self, text: &str) -> Result<VenueFrame, VenueError> {
+        let value: Value =
+            serde_json::from_str(text).map_err(|e| VenueError::Parse(e.to_string()))?;
+        // Objects are events: heartbeat, systemStatus, subscriptionStatus
+        let Some(parts) = value.as_array() else {
+            return Ok(VenueFrame::default());
+        };
+        let (channel, pair) = match parts.as_slice() {
+            [_, .., channel, pair] if parts.len() >= 4 => (channel.as_str(), pair.as_str()),
+            _ => return Err(VenueError::Parse(format!("short frame {}", text))),
+        };
+        let (Some(channel), Some(pair)) = (channel, pair) else {
+            return Err(VenueError::Parse(format!("frame without pair {}", text)));
+        };
+        if !channel.starts_with("book") {
+            return Ok(VenueFrame::default());
+        }
+
+        let mut update = BookUpdate {
+            key: self.normalize_symbol(pair),
+            kind: BookUpdateKind::Delta,
+            first_update_id: None,
+            final_update_id: None,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            checksum: None,
+        };
+        for payload in &parts[1..parts.len() - 2] {
+            let payload = payload
+                .as_object()
+                .ok_or_else(|| VenueError::Parse(format!("book payload {}", payload)))?;
+            for (field, levels) in payload {
+                match field.as_str() {
+                    "as" | "bs" => update.kind = BookUpdateKind::Snapshot,
+                    "a" | "b" => {}
+                    "c" => {
+                        let checksum = levels
+                            .as_str()
+                            .and_then(|c| c.parse().ok())
+                            .ok_or_else(|| VenueError::Parse(format!("checksum {}", levels)))?;
+                        update.checksum = Some(checksum);
+                        continue;
+                    }
+                    _ => continue,
+                }
+                let parsed = parse_levels(levels)?;
+                if field.starts_with('b') {
+                    update.bids.extend(parsed);
+                } else {
+                    update.asks.extend(parsed);
+                }
+            }
+        }
+
+        Ok(VenueFrame {
+            sequence: None,
+            updates: vec![update],
+        })
+    }
+
+    fn book_depth(&self) -> Option<usize> {
+        Some(self.depth)
+    }
+
+    /// CRC32 over the top ten asks (best first) then the top ten bids
+    fn checksum(&self, book: &LevelBook) -> Option<u32> {
+        let mut input = String::new();
+        for level in book.asks().take(CHECKSUM_DEPTH) {
+            push_checksum_level(&mut input, level);
+        }
+        for level in book.bids().take(CHECKSUM_DEPTH) {
+            push_checksum_level(&mut input, level);
+        }
+        Some(crc32fast::hash(input.as_bytes()))
+    }
+
+    fn order_payload(&self, client_order_id: &str, request: &OrderRequest) -> Value {
+        let pair = self
+            .venue_symbol(&request.key)
+            .unwrap_or_else(|| request.key.symbol.clone());
+        let mut payload = json!({
+            "ordertype": match request.order_type {
+                OrderType::Market => "market",
+                _ => "limit",
+            },
+            "type": match request.side {
+                Side::Buy => "buy",
+                Side::Sell => "sell",
+            },
+            "volume": request.quantity.to_string(),
+            "pair": pair,
+            "cl_ord_id": client_order_id,
+        });
+        if let Some(price) = request.price {
+            payload["price"] = json!(price.to_string());
+        }
+        if request.order_type != OrderType::Market {
+            payload["timeinforce"] = json!(match request.time_in_force {
+                TimeInForce::Ioc => "IOC",
+                TimeInForce::Gtd => "GTD",
+                _ => "GTC",
+            });
+        }
+        if request.order_type == OrderType::LimitMaker {
+            payload["oflags"] = json!("post");
+        }
+        payload
+    }
+}
@@ -0,0 +1,50 @@
+//! Venue Adapters
+//!
+//! One `VenueAdapter` per exchange protocol. `adapter_for` picks the adapter
+//! named by an exchange's configured protocol.
+
+pub mod binance;
+pub mod coinbase;
+pub mod kraken;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub use binance::BinanceAdapter;
+pub use coinbase::CoinbaseAdapter;
+pub use kraken::KrakenAdapter;
+
+use crate::config::{ExchangeConfig, VenueProtocol};
+use crate::domain::VenueAdapter;
+
+/// Build the adapter for an exchange's configured protocol
+pub fn adapter_for(config: &ExchangeConfig) -> Arc<dyn VenueAdapter> {
+    let id = config.id.as_str();
+    let symbols = config.symbols.clone();
+    match config.protocol {
+        VenueProtocol::Binance => Arc::new(BinanceAdapter::new(id, symbols)),
+        VenueProtocol::Coinbase => Arc::new(CoinbaseAdapter::new(id, symbols)),
+        VenueProtocol::Kraken => Arc::new(KrakenAdapter::new(id, symbols)),
+    }
+}
+
+/// Canonical form of a venue symbol: separators dropped, uppercased,
+/// venue-specific asset codes mapped via `alias`
+pub(crate) fn canonical_symbol(venue_symbol: &str, alias: impl Fn(&str) -> &str) -> String {
+    venue_symbol
+        .split(['/', '-', '_'])
+        .map(|part| alias(&part.to_uppercase()).to_string())
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Canonical -> venue symbol lookup for configured symbols
+pub(crate) fn symbol_map(
+    symbols: Vec<String>,
+    normalize: impl Fn(&str) -> String,
+) -> HashMap<String, String> {
+    symbols
+        .into_iter()
+        .map(|venue| (normalize(&venue), venue))
+        .collect()
+}
@@ -1,13 +1,19 @@
 use futures_util::{SinkExt, StreamExt};
+use rand::Rng;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 use thiserror::Error;
+use tokio::net::TcpStream;
 use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio::time::Instant;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
 
 use super::parsers::StreamDataParser;
 use crate::domain::{WsEvent, WsRequest, WsResponse};
 
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 #[derive(Error, Debug)]
 pub enum WsError {
     #[error("Connection error: {0}")]
@@ -20,119 +26,357 @@ pub enum WsError {
     NotConnected,
 }
 
+/// Reconnect and heartbeat settings for a supervised connection
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Delay before the first retry; doubles on every failed attempt
+    pub initial_delay: Duration,
+    /// Upper bound on the retry delay
+    pub max_delay: Duration,
+    /// Consecutive failed attempts before giving up (0 = retry forever)
+    pub max_attempts: u32,
+    /// Interval between pings
+    pub heartbeat_interval: Duration,
+    /// Reconnect when nothing has been received for this long
+    pub stale_timeout: Duration,
+    /// Reconnect proactively once a connection is this old
+    /// (Binance closes connections after 24 hours)
+    pub max_connection_age: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            max_attempts: 10,
+            heartbeat_interval: Duration::from_secs(30),
+            stale_timeout: Duration::from_secs(90),
+            max_connection_age: Duration::from_secs(23 * 3600 + 50 * 60),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before retry `attempt` (0-based): exponential, capped, with
+    /// jitter drawn from the upper half so reconnecting clients spread out
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .initial_delay
+            .saturating_mul(1u32 << attempt.min(16))
+            .min(self.max_delay);
+        let half = delay / 2;
+        half + half.mul_f64(rand::thread_rng().r#gen::<f64>())
+    }
+}
+
 /// WebSocket client for streaming market data
 /// Infrastructure component - handles WebSocket communication
+///
+/// The connection is supervised: it is pinged every heartbeat interval,
+/// re-established with backoff when it drops or goes stale, and recycled
+/// before the venue's connection age limit. After every reconnect the active
+/// subscriptions are replayed. Consumers see `WsEvent::Disconnected` when a
+/// connection is lost and `WsEvent::Reconnected` once it is back.
 pub struct WsClient {
     url: String,
+    policy: ReconnectPolicy,
+    init_frames: Vec<String>,
 }
 
 impl WsClient {
     pub fn new(url: String) -> Self {
-        WsClient { url }
+        WsClient {
+            url,
+            policy: ReconnectPolicy::default(),
+            init_frames: Vec::new(),
+        }
+    }
+
+    /// Use custom reconnect and heartbeat settings
+    pub fn with_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Text frames sent first on every connection, e.g. a venue's own
+    /// subscribe messages
+    pub fn with_init_frames(mut self, frames: Vec<String>) -> Self {
+        self.init_frames = frames;
+        self
     }
 
     /// Connect and return channels for sending requests and receiving events
+    ///
+    /// Fails if the first connection cannot be made; later drops are
+    /// handled by reconnecting.
     pub async fn connect(&self) -> Result<(WsRequestSender, mpsc::Receiver<WsEvent>), WsError> {
         let (ws_stream, _) = connect_async(&self.url).await?;
-        let (mut write, mut read) = ws_stream.split();
 
         // Channel for sending requests to the WebSocket
-        let (req_tx, mut req_rx) = mpsc::channel::<WsRequest>(32);
+        let (req_tx, req_rx) = mpsc::channel::<Outbound>(32);
 
         // Channel for receiving events from the WebSocket
         let (event_tx, event_rx) = mpsc::channel::<WsEvent>(1024);
 
-        // Spawn task to handle outgoing messages
-        let event_tx_clone = event_tx.clone();
-        tokio::spawn(async move {
-            while let Some(req) = req_rx.recv().await {
-                let json = match serde_json::to_string(&req) {
-                    Ok(j) => j,
-                    Err(e) => {
-                        let _ = event_tx_clone.send(WsEvent::Error(e.to_string())).await;
-                        continue;
-                    }
-                };
+        let request_id = Arc::new(AtomicU64::new(1));
+        let supervisor = Supervisor {
+            url: self.url.clone(),
+            policy: self.policy.clone(),
+            init_frames: self.init_frames.clone(),
+            requests: req_rx,
+            events: event_tx,
+            request_id: Arc::clone(&request_id),
+            requests_open: true,
+            subscriptions: Vec::new(),
+            // Parser is created in infrastructure layer - keeps domain free of concrete dependencies
+            parser: StreamDataParser::new(),
+        };
+        tokio::spawn(supervisor.run(ws_stream));
 
-                if let Err(e) = write.send(Message::Text(json.into())).await {
-                    let _ = event_tx_clone.send(WsEvent::Error(e.to_string())).await;
-                    break;
+        Ok((
+            WsRequestSender {
+                tx: req_tx,
+                request_id,
+            },
+            event_rx,
+        ))
+    }
+}
+
+/// A frame queued for the connection
+enum Outbound {
+    Request(WsRequest),
+    /// Venue-specific frame sent as-is
+    Text(String),
+}
+
+/// Why a connection ended
+enum Exit {
+    /// The event receiver was dropped; stop for good
+    Closed,
+    /// Dropped, failed or went stale
+    Lost(String),
+    /// Reached the maximum connection age
+    Expired,
+}
+
+/// Owns the connection and its reconnect loop
+struct Supervisor {
+    url: String,
+    policy: ReconnectPolicy,
+    init_frames: Vec<String>,
+    requests: mpsc::Receiver<Outbound>,
+    events: mpsc::Sender<WsEvent>,
+    request_id: Arc<AtomicU64>,
+    requests_open: bool,
+    /// Streams to replay after a reconnect, in subscription order
+    subscriptions: Vec<String>,
+    parser: StreamDataParser,
+}
+
+impl Supervisor {
+    async fn run(mut self, first: WsStream) {
+        let mut stream = first;
+        let mut replay = None;
+        loop {
+            let immediate = match self.serve(stream, replay.take()).await {
+                Exit::Closed => return,
+                Exit::Lost(reason) => {
+                    tracing::warn!("WebSocket {} lost: {}", self.url, reason);
+                    false
                 }
+                Exit::Expired => {
+                    tracing::info!("WebSocket {} reached its age limit, recycling", self.url);
+                    true
+                }
+            };
+            if self.events.send(WsEvent::Disconnected).await.is_err() {
+                return;
             }
-        });
 
-        // Spawn task to handle incoming messages
-        // Parser is created in infrastructure layer - keeps domain free of concrete dependencies
-        let parser = StreamDataParser::new();
+            stream = match self.reconnect(immediate).await {
+                Some(stream) => stream,
+                None => return,
+            };
+            if !self.subscriptions.is_empty() {
+                let id = self.request_id.fetch_add(1, Ordering::SeqCst);
+                replay = Some(WsRequest::subscribe(id, self.subscriptions.clone()));
+            }
+            tracing::info!(
+                "WebSocket {} reconnected, replaying {} subscriptions",
+                self.url,
+                self.subscriptions.len()
+            );
+            if self.events.send(WsEvent::Reconnected).await.is_err() {
+                return;
+            }
+        }
+    }
 
-        tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => match serde_json::from_str::<WsResponse>(&text) {
-                        Ok(response) => {
-                            let event = match &response {
-                                WsResponse::Result { id, result } => WsEvent::Response {
-                                    id: *id,
-                                    result: result.clone(),
-                                },
-                                WsResponse::Stream { stream, data } => {
-                                    if let Some(stream_data) = parser.parse(stream, data) {
-                                        WsEvent::StreamData(stream_data)
-                                    } else {
-                                        WsEvent::RawMessage(text.to_string())
-                                    }
-                                }
-                                WsResponse::Error { id, code, msg } => WsEvent::ApiError {
-                                    id: *id,
-                                    code: *code,
-                                    msg: msg.clone(),
-                                },
-                            };
-
-                            if event_tx.send(event).await.is_err() {
-                                break;
-                            }
+    /// Pump one connection until it ends
+    async fn serve(&mut self, stream: WsStream, first: Option<WsRequest>) -> Exit {
+        let (mut write, mut read) = stream.split();
+
+        for frame in &self.init_frames {
+            if let Err(e) = write.send(Message::Text(frame.as_str().into())).await {
+                return Exit::Lost(e.to_string());
+            }
+        }
+        if let Some(request) = first
+            && let Err(e) = send_request(&mut write, &request).await
+        {
+            return Exit::Lost(e.to_string());
+        }
+
+        let mut last_seen = Instant::now();
+        let mut heartbeat = tokio::time::interval(self.policy.heartbeat_interval);
+        heartbeat.tick().await;
+        let expiry = tokio::time::sleep(self.policy.max_connection_age);
+        tokio::pin!(expiry);
+
+        loop {
+            tokio::select! {
+                request = self.requests.recv(), if self.requests_open => {
+                    // Senders may all be dropped while the stream keeps flowing
+                    let sent = match request {
+                        Some(Outbound::Request(request)) => {
+                            self.track(&request);
+                            send_request(&mut write, &request).await
                         }
-                        Err(_) => {
-                            if event_tx
-                                .send(WsEvent::RawMessage(text.to_string()))
-                                .await
-                                .is_err()
-                            {
-                                break;
+                        Some(Outbound::Text(text)) => write
+                            .send(Message::Text(text.into()))
+                            .await
+                            .map_err(WsError::from),
+                        None => {
+                            self.requests_open = false;
+                            continue;
+                        }
+                    };
+                    if let Err(e) = sent {
+                        return Exit::Lost(e.to_string());
+                    }
+                }
+                msg = read.next() => {
+                    last_seen = Instant::now();
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            let event = self.to_event(&text);
+                            if self.events.send(event).await.is_err() {
+                                return Exit::Closed;
                             }
                         }
-                    },
-                    Ok(Message::Close(_)) => {
-                        let _ = event_tx.send(WsEvent::Disconnected).await;
-                        break;
+                        Some(Ok(Message::Close(_))) => {
+                            return Exit::Lost("closed by server".to_string());
+                        }
+                        Some(Ok(Message::Ping(data))) => {
+                            tracing::trace!("Received ping: {:?}", data);
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) => return Exit::Lost(e.to_string()),
+                        None => return Exit::Lost("stream ended".to_string()),
                     }
-                    Ok(Message::Ping(data)) => {
-                        tracing::trace!("Received ping: {:?}", data);
+                }
+                _ = heartbeat.tick() => {
+                    if last_seen.elapsed() > self.policy.stale_timeout {
+                        return Exit::Lost(format!(
+                            "nothing received for {:?}",
+                            last_seen.elapsed()
+                        ));
                     }
-                    Ok(_) => {}
-                    Err(e) => {
-                        let _ = event_tx.send(WsEvent::Error(e.to_string())).await;
-                        break;
+                    if let Err(e) = write.send(Message::Ping(Default::default())).await {
+                        return Exit::Lost(e.to_string());
                     }
                 }
+                _ = &mut expiry => {
+                    let _ = write.close().await;
+                    return Exit::Expired;
+                }
             }
-        });
+        }
+    }
 
-        Ok((
-            WsRequestSender {
-                tx: req_tx,
-                request_id: Arc::new(AtomicU64::new(1)),
+    /// Connect again, backing off between failures
+    async fn reconnect(&mut self, immediate: bool) -> Option<WsStream> {
+        let mut attempt = 0;
+        loop {
+            if !(immediate && attempt == 0) {
+                tokio::time::sleep(self.policy.backoff(attempt)).await;
+            }
+            if self.events.is_closed() {
+                return None;
+            }
+            attempt += 1;
+
+            match connect_async(&self.url).await {
+                Ok((stream, _)) => return Some(stream),
+                Err(e) => {
+                    tracing::warn!(
+                        "Reconnect attempt {} to {} failed: {}",
+                        attempt,
+                        self.url,
+                        e
+                    );
+                    if self.policy.max_attempts > 0 && attempt >= self.policy.max_attempts {
+                        let _ = self
+                            .events
+                            .send(WsEvent::Error(format!(
+                                "giving up on {} after {} attempts",
+                                self.url, attempt
+                            )))
+                            .await;
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keep the replay list in step with outgoing requests
+    fn track(&mut self, request: &WsRequest) {
+        match request {
+            WsRequest::Subscribe { params, .. } => {
+                for stream in params {
+                    if !self.subscriptions.contains(stream) {
+                        self.subscriptions.push(stream.clone());
+                    }
+                }
+            }
+            WsRequest::Unsubscribe { params, .. } => {
+                self.subscriptions.retain(|s| !params.contains(s));
+            }
+            WsRequest::ListSubscriptions { .. } => {}
+        }
+    }
+
+    fn to_event(&self, text: &str) -> WsEvent {
+        let Ok(response) = serde_json::from_str::<WsResponse>(text) else {
+            return WsEvent::RawMessage(text.to_string());
+        };
+        match response {
+            WsResponse::Result { id, result } => WsEvent::Response { id, result },
+            WsResponse::Stream { stream, data } => match self.parser.parse(&stream, &data) {
+                Some(stream_data) => WsEvent::StreamData(stream_data),
+                None => WsEvent::RawMessage(text.to_string()),
             },
-            event_rx,
-        ))
+            WsResponse::Error { id, code, msg } => WsEvent::ApiError { id, code, msg },
+        }
     }
 }
 
+async fn send_request<S>(write: &mut S, request: &WsRequest) -> Result<(), WsError>
+where
+    S: SinkExt<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    let json = serde_json::to_string(request)?;
+    write.send(Message::Text(json.into())).await?;
+    Ok(())
+}
+
 /// Handle for sending WebSocket requests
 #[derive(Clone)]
 pub struct WsRequestSender {
-    tx: mpsc::Sender<WsRequest>,
+    tx: mpsc::Sender<Outbound>,
     request_id: Arc<AtomicU64>,
 }
 
@@ -145,7 +389,7 @@ impl WsRequestSender {
     pub async fn subscribe(&self, streams: Vec<String>) -> Result<u64, WsError> {
         let id = self.next_id();
         self.tx
-            .send(WsRequest::subscribe(id, streams))
+            .send(Outbound::Request(WsRequest::subscribe(id, streams)))
             .await
             .map_err(|_| WsError::ChannelClosed)?;
         Ok(id)
@@ -155,19 +399,83 @@ impl WsRequestSender {
     pub async fn unsubscribe(&self, streams: Vec<String>) -> Result<u64, WsError> {
         let id = self.next_id();
         self.tx
-            .send(WsRequest::unsubscribe(id, streams))
+            .send(Outbound::Request(WsRequest::unsubscribe(id, streams)))
             .await
             .map_err(|_| WsError::ChannelClosed)?;
         Ok(id)
     }
 
+    /// Send a venue-specific text frame as-is (not replayed on reconnect)
+    pub async fn send_text(&self, text: String) -> Result<(), WsError> {
+        self.tx
+            .send(Outbound::Text(text))
+            .await
+            .map_err(|_| WsError::ChannelClosed)
+    }
+
     /// List current subscriptions
     pub async fn list_subscriptions(&self) -> Result<u64, WsError> {
         let id = self.next_id();
         self.tx
-            .send(WsRequest::list_subscriptions(id))
+            .send(Outbound::Request(WsRequest::list_subscriptions(id)))
             .await
             .map_err(|_| WsError::ChannelClosed)?;
         Ok(id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn fast_policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            max_attempts: 5,
+            heartbeat_interval: Duration::from_millis(20),
+            stale_timeout: Duration::from_millis(100),
+            max_connection_age: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_capped_and_jittered() {
+        let policy = fast_policy();
+        for attempt in 0..10 {
+            let full = (policy.initial_delay * 2u32.pow(attempt)).min(policy.max_delay);
+            let delay = policy.backoff(attempt);
+            assert!(delay >= full / 2 && delay <= full, "{:?}", delay);
+        }
+        assert!(policy.backoff(40) <= policy.max_delay);
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_stale_connection() {
+        // Server completes the handshake and then never reads, so pings go unanswered
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (accepted_tx, mut accepted) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(tokio_tungstenite::accept_async(stream).await.unwrap());
+                accepted_tx.send(()).unwrap();
+            }
+        });
+
+        let client = WsClient::new(url).with_policy(fast_policy());
+        let (_sender, mut events) = client.connect().await.unwrap();
+        accepted.recv().await.unwrap();
+
+        for expected in ["Disconnected", "Reconnected"] {
+            let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(format!("{:?}", event), expected);
+        }
+        accepted.recv().await.unwrap();
+    }
+}
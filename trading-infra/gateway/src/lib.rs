@@ -10,6 +10,8 @@
 //! - Receives order book deltas from exchange streams
 //! - Forwards deltas to strategy via transport layer
 //! - Handles snapshot requests from strategy (with rate limiting)
+//! - Tracks order lifecycle, fills and positions (OMS)
+//! - Speaks non-Binance protocols through pluggable venue adapters
 //!
 //! ```text
 //! ┌─────────────┐     ┌─────────────┐     ┌─────────────┐
@@ -52,19 +54,40 @@ pub mod presentation;
 pub use domain::events::{StreamData, WsEvent, WsRequest, WsResponse};
 pub use domain::sync_status::SyncStatus;
 pub use domain::traits::{
-    DepthFetcher, FetchError, OrderBookWriter, SnapshotWriter, StreamParser, UpdateWriter,
+    DepthFetcher, FetchError, OrderBookWriter, OrderVenue, SnapshotWriter, StreamParser,
+    UpdateWriter, VenueAdapter,
+};
+pub use domain::venue::{
+    BookUpdate, BookUpdateKind, LevelBook, SequenceCheck, VenueError, VenueFrame,
+};
+pub use domain::{
+    ExchangeId, ExecutionReport, Fill, ManagedOrder, OrderRequest, OrderState, Position,
+    QualifiedSymbol,
 };
-pub use domain::{ExchangeId, QualifiedSymbol};
 
+pub use application::book_sync::VenueBookSync;
 pub use application::config::{GatewayConfig, MarketDataConfig};
 pub use application::exchange_manager::ExchangeManager;
 pub use application::market_data_handler::MarketDataHandler;
+pub use application::order_manager::{Drift, OmsError, OrderManager, ReconcileReport};
 pub use application::snapshot_buffer::SnapshotBuffer;
+pub use application::user_data::{UserDataError, UserDataStream};
 
-pub use infrastructure::parsers::{DepthParser, StreamDataParser, TradeParser};
+pub use infrastructure::parsers::{
+    DepthParser, StreamDataParser, TradeParser, parse_execution_report,
+};
+pub use infrastructure::rate_limiter::{Lane, RateLimitMetrics, RateLimits, RestRateLimiter};
 pub use infrastructure::rest_client::{RestClient, RestError};
-pub use infrastructure::ws_client::{WsClient, WsRequestSender};
+pub use infrastructure::signer::{Ed25519Signer, HmacSha256Signer, RequestSigner, SignerError};
+pub use infrastructure::venues::{BinanceAdapter, CoinbaseAdapter, KrakenAdapter, adapter_for};
+pub use infrastructure::ws_client::{ReconnectPolicy, WsClient, WsRequestSender};
 
-pub use presentation::MarketDataPublisher;
+pub use presentation::{
+    DeltaForwarder, MarketDataPublisher, SnapshotRequestService, depth_topic, published_symbol,
+    trade_topic,
+};
 
-pub use config::{ExchangeConfig, GatewayConfigFile, load_config, load_default_config};
+pub use config::{
+    ExchangeConfig, GatewayConfigFile, GatewayTransportConfig, VenueProtocol, load_config,
+    load_config_from_str, load_default_config,
+};
@@ -1,13 +1,259 @@
 //! Gateway Binary
 //!
-//! Runs the market data gateway process.
-
-fn main() {
-    println!("Gateway starting...");
-    // TODO: Implement gateway main loop
-    // 1. Load configuration
-    // 2. Create transport publisher
-    // 3. Create snapshot buffer
-    // 4. Connect to exchanges
-    // 5. Forward deltas to strategy
+//! Runs the market data gateway process: connects to every enabled exchange,
+//! keeps each symbol synced, forwards snapshots and deltas to strategies over
+//! the configured transport and answers their snapshot requests. Orders
+//! placed under the exchange API keys are tracked from each user-data stream.
+
+use std::sync::Arc;
+
+use gateway::{
+    DeltaForwarder, ExchangeId, ExchangeManager, MarketDataPublisher, OrderManager,
+    SnapshotRequestService, UserDataStream, load_config, load_default_config,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use transport::{
+    CaptureWriter, RecordingPublisher, SequencedPublisher, TransportFactory, TransportType,
+};
+
+fn print_help() {
+    eprintln!(
+        r#"Gateway - market data gateway between exchanges and strategies
+
+USAGE:
+    gateway [OPTIONS]
+
+OPTIONS:
+    --config <PATH>     Load configuration from JSON file (default: embedded config)
+    --help              Print this help message
+
+ENVIRONMENT VARIABLES:
+    RUST_LOG            Log level filter
+
+EXAMPLES:
+    # Run against a local exchange-sim on port 8080
+    gateway --config gateway_config.json
+"#
+    );
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "gateway=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // Parse command line arguments
+    let args: Vec<String> = std::env::args().collect();
+    let mut config_path: Option<String> = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_help();
+                return Ok(());
+            }
+            "--config" | "-c" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --config requires a path argument");
+                    std::process::exit(1);
+                }
+                config_path = Some(args[i].clone());
+            }
+            arg => {
+                eprintln!("Unknown argument: {}", arg);
+                print_help();
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let config = match config_path {
+        Some(path) => {
+            tracing::info!("Loading configuration from: {}", path);
+            load_config(path)?
+        }
+        None => {
+            tracing::info!("Using embedded default configuration");
+            load_default_config()?
+        }
+    };
+    config.validate()?;
+
+    // Transport to strategies
+    let transport = &config.transport;
+    if transport.market_data.transport_type == TransportType::Channel {
+        tracing::warn!(
+            "Market data transport is an in-process channel; no other process will receive it"
+        );
+    }
+    // Retransmits bypass the capture, which already holds the originals
+    let sequenced = Arc::new(SequencedPublisher::new(
+        TransportFactory::create_publisher(&transport.market_data)?,
+        "gateway",
+        &transport.sequencing,
+    ));
+    let mut md_transport: Box<dyn transport::Publisher> = Box::new(Arc::clone(&sequenced));
+    if let Some(capture) = &transport.capture {
+        tracing::info!("Capturing market data to {}", capture.dir.display());
+        let writer = CaptureWriter::create(capture.clone())?;
+        md_transport = Box::new(RecordingPublisher::new(md_transport, writer));
+    }
+    let requests = TransportFactory::create_subscriber(&transport.snapshot_requests)?;
+    let publisher =
+        Arc::new(MarketDataPublisher::new(md_transport, "gateway").with_codec(transport.codec));
+
+    // Exchange connections feed the forwarder, which publishes synced data
+    let mut manager =
+        ExchangeManager::new(config.clone(), DeltaForwarder::new(Arc::clone(&publisher)));
+    manager.initialize();
+    let started = manager.start_all().await;
+    if started.is_empty() {
+        return Err("no exchange could be connected".into());
+    }
+    for exchange_id in started.keys() {
+        tracing::info!("Streaming market data from {}", exchange_id);
+    }
+
+    // Strategy snapshot requests, limited by the strictest exchange
+    let requests_per_second = config
+        .enabled_exchanges()
+        .iter()
+        .map(|e| e.rate_limits.requests_per_second)
+        .min()
+        .unwrap_or(10);
+    let mut snapshots = SnapshotRequestService::new(Arc::clone(&publisher), requests_per_second)
+        .with_retransmits(sequenced);
+    for exchange_id in started.keys() {
+        if let Some(client) = manager.rest_client(exchange_id) {
+            snapshots = snapshots.with_fetcher(exchange_id.clone(), Arc::new(client.clone()));
+        }
+    }
+
+    // Order state and positions, kept current by the user-data streams
+    let mut oms = OrderManager::new("gateway");
+    for exchange_id in started.keys() {
+        if let Some(client) = manager.rest_client(exchange_id) {
+            oms = oms.with_venue(exchange_id.clone(), Arc::new(client.clone()));
+        }
+    }
+    let oms = Arc::new(oms);
+    for exchange in config.enabled_exchanges() {
+        let exchange_id = ExchangeId::new(&exchange.id);
+        if !exchange.user_data_stream || exchange.api_key.is_empty() {
+            continue;
+        }
+        let Some(client) = manager
+            .rest_client(&exchange_id)
+            .filter(|_| started.contains_key(&exchange_id))
+        else {
+            continue;
+        };
+        let stream = UserDataStream::new(
+            exchange_id,
+            client.clone(),
+            exchange.ws_url.clone(),
+            Arc::clone(&oms),
+        )
+        .with_policy(config.global.reconnect_policy())
+        .with_symbols(&exchange.symbols);
+        tokio::spawn(stream.run());
+    }
+
+    let heartbeat = {
+        let publisher = Arc::clone(&publisher);
+        let oms = Arc::clone(&oms);
+        let interval = config.global.heartbeat_interval();
+        let limiters: Vec<_> = started
+            .keys()
+            .filter_map(|id| {
+                let client = manager.rest_client(id)?;
+                Some((id.clone(), Arc::clone(client.rate_limiter())))
+            })
+            .collect();
+        async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                tracing::info!("Published {} messages", publisher.sequence());
+                for position in oms.positions() {
+                    tracing::info!(
+                        "Position {}: {} @ {}",
+                        position.key,
+                        position.quantity,
+                        position.average_price
+                    );
+                }
+                for (exchange_id, limiter) in &limiters {
+                    let m = limiter.metrics();
+                    tracing::info!(
+                        "{} REST headroom: weight {}/{}, orders {}/{}, throttled {}, rejected {}",
+                        exchange_id,
+                        m.weight_headroom(),
+                        m.weight_limit,
+                        m.order_headroom(),
+                        m.order_limit,
+                        m.throttled,
+                        m.rejected
+                    );
+                }
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = snapshots.run(requests) => {}
+        _ = heartbeat => {}
+        _ = shutdown_signal() => {}
+    }
+
+    tracing::info!(
+        "Shutting down after {} messages from {:?}",
+        publisher.sequence(),
+        manager
+            .connected_exchanges()
+            .iter()
+            .map(ExchangeId::as_str)
+            .collect::<Vec<_>>()
+    );
+    publisher.flush()?;
+    Ok(())
+}
+
+/// Resolves on SIGINT or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received SIGINT"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
 }
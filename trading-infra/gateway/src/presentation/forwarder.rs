@@ -0,0 +1,242 @@
+//! Delta Forwarder
+//!
+//! Order book writer that forwards instead of building books. The market data
+//! handler syncs each symbol against a REST snapshot and hands over the
+//! snapshot and every delta after it; the forwarder checks update id
+//! continuity and publishes them to strategy processes. Trades need no sync
+//! and are published as they arrive.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+
+use trading_core::{DepthSnapshotEvent, DepthUpdate, Price, Quantity, TradeUpdate};
+
+use super::publisher::{MarketDataPublisher, compact_levels};
+use crate::domain::{ExchangeId, QualifiedSymbol, SnapshotWriter, StreamData, UpdateWriter};
+
+/// Publishes synced snapshots and deltas through a `MarketDataPublisher`
+#[derive(Clone)]
+pub struct DeltaForwarder {
+    publisher: Arc<MarketDataPublisher>,
+    /// Last forwarded update id per symbol
+    last_update_ids: Arc<DashMap<QualifiedSymbol, u64>>,
+}
+
+impl DeltaForwarder {
+    pub fn new(publisher: Arc<MarketDataPublisher>) -> Self {
+        DeltaForwarder {
+            publisher,
+            last_update_ids: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Last update id forwarded for a symbol
+    pub fn last_update_id(&self, key: &QualifiedSymbol) -> Option<u64> {
+        self.last_update_ids.get(key).map(|id| *id)
+    }
+
+    /// The underlying publisher
+    pub fn publisher(&self) -> &Arc<MarketDataPublisher> {
+        &self.publisher
+    }
+
+    fn forward_trade(&self, exchange_id: &ExchangeId, update: &StreamData) {
+        let StreamData::Trade {
+            symbol,
+            trade_id,
+            price,
+            quantity,
+            trade_time,
+            is_buyer_maker,
+            ..
+        } = update
+        else {
+            return;
+        };
+        let (Ok(price), Ok(quantity)) = (Price::parse(price), Quantity::parse(quantity)) else {
+            tracing::debug!("Dropping unparseable trade {} for {}", trade_id, symbol);
+            return;
+        };
+
+        let mut trade = TradeUpdate::new(
+            exchange_id.as_str(),
+            &symbol.to_uppercase(),
+            *trade_id,
+            price,
+            quantity,
+            *is_buyer_maker,
+        );
+        // Exchange trade time, in milliseconds
+        trade.timestamp_ns = (*trade_time as u64).saturating_mul(1_000_000);
+
+        if let Err(e) = self.publisher.publish_trade(&trade) {
+            tracing::debug!("Failed to publish trade for {}: {}", symbol, e);
+        }
+    }
+}
+
+impl SnapshotWriter for DeltaForwarder {
+    fn apply_snapshot(&self, key: &QualifiedSymbol, snapshot: &DepthSnapshotEvent) {
+        self.last_update_ids
+            .insert(key.clone(), snapshot.last_update_id);
+
+        if let Err(e) =
+            self.publisher
+                .publish_snapshot(key.exchange.as_str(), &key.symbol, snapshot)
+        {
+            tracing::debug!("Failed to publish snapshot for {}: {}", key, e);
+        }
+    }
+}
+
+impl UpdateWriter for DeltaForwarder {
+    fn apply_update(&self, exchange_id: &ExchangeId, update: &StreamData) -> bool {
+        let (symbol, first_update_id, final_update_id, bids, asks) = match update {
+            StreamData::DepthUpdate {
+                symbol,
+                first_update_id,
+                final_update_id,
+                bids,
+                asks,
+                ..
+            } => (symbol, first_update_id, final_update_id, bids, asks),
+            StreamData::Trade { .. } => {
+                self.forward_trade(exchange_id, update);
+                return true;
+            }
+        };
+
+        let key = QualifiedSymbol::new(exchange_id.clone(), symbol);
+        let Some(mut last) = self.last_update_ids.get_mut(&key) else {
+            // No snapshot yet
+            return false;
+        };
+
+        let expected = *last + 1;
+        if *final_update_id < expected {
+            // Already covered by the snapshot or an earlier delta
+            return true;
+        }
+        if *first_update_id > expected {
+            return false;
+        }
+        *last = *final_update_id;
+        drop(last);
+
+        let delta = DepthUpdate::new(
+            exchange_id.as_str(),
+            &key.symbol,
+            *first_update_id,
+            *final_update_id,
+        )
+        .with_bids(compact_levels(bids))
+        .with_asks(compact_levels(asks));
+
+        if let Err(e) = self.publisher.publish_delta(&delta) {
+            tracing::debug!("Failed to publish delta for {}: {}", key, e);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use transport::{
+        MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, MSG_TRADE, Subscriber, WireMessage, channel_pair,
+    };
+
+    fn depth_update(first: u64, last: u64) -> StreamData {
+        StreamData::DepthUpdate {
+            symbol: "BTCUSDT".to_string(),
+            event_time: 0,
+            first_update_id: first,
+            final_update_id: last,
+            bids: vec![["50000.10".to_string(), "1.5".to_string()]],
+            asks: vec![],
+        }
+    }
+
+    #[test]
+    fn test_forwards_snapshot_then_contiguous_deltas() {
+        let (publisher, subscriber) = channel_pair(100);
+        let forwarder = DeltaForwarder::new(Arc::new(MarketDataPublisher::new(
+            Box::new(publisher),
+            "gateway",
+        )));
+        let exchange = ExchangeId::simulator();
+        let key = QualifiedSymbol::new(exchange.clone(), "BTCUSDT");
+
+        // Deltas before the snapshot are refused
+        assert!(!forwarder.apply_update(&exchange, &depth_update(1, 1)));
+
+        forwarder.apply_snapshot(
+            &key,
+            &DepthSnapshotEvent {
+                last_update_id: 10,
+                bids: vec![],
+                asks: vec![],
+            },
+        );
+        assert!(forwarder.apply_update(&exchange, &depth_update(8, 10))); // stale
+        assert!(forwarder.apply_update(&exchange, &depth_update(9, 12)));
+        assert!(forwarder.apply_update(&exchange, &depth_update(13, 13)));
+        assert!(!forwarder.apply_update(&exchange, &depth_update(15, 16))); // gap
+        assert_eq!(forwarder.last_update_id(&key), Some(13));
+
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| {
+                let msg: WireMessage = bincode::deserialize(data).unwrap();
+                received.push(msg);
+            })
+            .unwrap();
+
+        let types: Vec<u8> = received.iter().map(|m| m.msg_type).collect();
+        assert_eq!(
+            types,
+            vec![MSG_ORDER_BOOK_SNAPSHOT, MSG_DEPTH_UPDATE, MSG_DEPTH_UPDATE]
+        );
+        let delta: DepthUpdate = bincode::deserialize(&received[1].payload).unwrap();
+        assert_eq!(delta.exchange, "simulator");
+        assert_eq!((delta.first_update_id, delta.final_update_id), (9, 12));
+        assert_eq!(delta.bids[0].price_raw, 5_000_010_000_000);
+        assert_eq!(delta.bids[0].quantity_raw, 150_000_000);
+    }
+
+    #[test]
+    fn test_forwards_trades_without_snapshot() {
+        let (publisher, subscriber) = channel_pair(100);
+        let forwarder = DeltaForwarder::new(Arc::new(MarketDataPublisher::new(
+            Box::new(publisher),
+            "gateway",
+        )));
+        let exchange = ExchangeId::simulator();
+        let trade = StreamData::Trade {
+            symbol: "btcusdt".to_string(),
+            trade_id: 7,
+            price: "50000.10".to_string(),
+            quantity: "0.5".to_string(),
+            buyer_order_id: 1,
+            seller_order_id: 2,
+            trade_time: 1_700_000_000_000,
+            is_buyer_maker: true,
+        };
+        assert!(forwarder.apply_update(&exchange, &trade));
+
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| received.push(WireMessage::deserialize(data).unwrap()))
+            .unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].msg_type, MSG_TRADE);
+        assert_eq!(received[0].topic, "md.simulator.BTCUSDT.trade");
+        let decoded: TradeUpdate = received[0].decode_payload().unwrap();
+        assert_eq!(decoded.symbol, "BTCUSDT");
+        assert_eq!(decoded.trade_id, 7);
+        assert_eq!(decoded.price_raw, 5_000_010_000_000);
+        assert_eq!(decoded.quantity_raw, 50_000_000);
+        assert!(decoded.buyer_is_maker);
+        assert_eq!(decoded.timestamp_ns, 1_700_000_000_000_000_000);
+    }
+}
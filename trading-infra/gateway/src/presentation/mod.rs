@@ -2,11 +2,17 @@
 //!
 //! This layer contains adapters for systems that consume from us:
 //! - MarketDataPublisher: Publishes order book data to strategy processes
+//! - DeltaForwarder: Forwards synced snapshots, deltas and trades through the publisher
+//! - SnapshotRequestService: Answers snapshot requests from strategy processes
 //!
 //! Follows Hexagonal Architecture:
 //! - Infrastructure = inbound (exchanges → gateway)
 //! - Presentation = outbound (gateway → consumers)
 
+mod forwarder;
 mod publisher;
+mod snapshot_service;
 
-pub use publisher::MarketDataPublisher;
+pub use forwarder::DeltaForwarder;
+pub use publisher::{MarketDataPublisher, depth_topic, published_symbol, trade_topic};
+pub use snapshot_service::SnapshotRequestService;
@@ -1,21 +1,85 @@
 //! Market Data Publisher
 //!
-//! Publishes order book deltas and snapshots to strategy via transport layer.
+//! Publishes order book deltas, snapshots and trades to strategy via transport layer.
 //! Gateway does NOT build order books - it only forwards deltas from exchanges.
 
+use std::collections::HashMap;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use trading_core::{CompactLevel, DepthSnapshotEvent, DepthUpdate, OrderBookSnapshot};
+use trading_core::{
+    CompactLevel, DepthSnapshotEvent, DepthUpdate, OrderBookSnapshot, Price, QualifiedSymbol,
+    Quantity, TradeUpdate,
+};
 use transport::{
-    MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, Publisher, TransportError, WireMessage,
+    Codec, CodecKind, CodecMessage, MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, MSG_TRADE,
+    Publisher, TransportError, WireMessage, market_data_topic,
 };
 
+/// Convert exchange `[price, quantity]` string levels to fixed-point
+///
+/// Parsed exactly so a level from a snapshot and the same level from a
+/// delta always land on the same `price_raw` key. Unparseable levels are
+/// dropped.
+pub(crate) fn compact_levels(levels: &[[String; 2]]) -> Vec<CompactLevel> {
+    levels
+        .iter()
+        .filter_map(|[price, qty]| {
+            Some(CompactLevel::from_types(
+                Price::parse(price).ok()?,
+                Quantity::parse(qty).ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Symbol a published market data frame is about
+///
+/// Used to filter captures on replay; reads either codec. `None` for other
+/// message types or frames that fail to decode.
+pub fn published_symbol(data: &[u8]) -> Option<QualifiedSymbol> {
+    let codec = CodecKind::detect(data);
+    match WireMessage::header(data).ok()?.msg_type {
+        MSG_DEPTH_UPDATE => {
+            let update: DepthUpdate = codec.decode(data).ok()?;
+            Some(QualifiedSymbol::new(update.exchange, update.symbol))
+        }
+        MSG_ORDER_BOOK_SNAPSHOT => {
+            let snapshot: OrderBookSnapshot = codec.decode(data).ok()?;
+            Some(QualifiedSymbol::new(snapshot.exchange, snapshot.symbol))
+        }
+        MSG_TRADE => {
+            let trade: TradeUpdate = codec.decode(data).ok()?;
+            Some(QualifiedSymbol::new(trade.exchange, trade.symbol))
+        }
+        _ => None,
+    }
+}
+
+/// Topic a symbol's deltas and snapshots are published on
+pub fn depth_topic(exchange: &str, symbol: &str) -> String {
+    market_data_topic(exchange, &symbol.to_uppercase(), "depth")
+}
+
+/// Topic a symbol's trades are published on
+pub fn trade_topic(exchange: &str, symbol: &str) -> String {
+    market_data_topic(exchange, &symbol.to_uppercase(), "trade")
+}
+
 /// Publisher for market data updates to strategy processes
+///
+/// Each symbol is its own topic and sequenced stream, so a strategy
+/// subscribed to a subset of symbols sees contiguous sequences.
 pub struct MarketDataPublisher {
     transport: Box<dyn Publisher>,
-    sequence: AtomicU64,
+    /// Next sequence per topic
+    sequences: Mutex<HashMap<String, u64>>,
+    published: AtomicU64,
     source: String,
+    codec: CodecKind,
+    /// Encode buffer, reused under the sequence lock
+    buf: Mutex<Vec<u8>>,
 }
 
 impl MarketDataPublisher {
@@ -23,13 +87,18 @@ impl MarketDataPublisher {
     pub fn new(transport: Box<dyn Publisher>, source: impl Into<String>) -> Self {
         MarketDataPublisher {
             transport,
-            sequence: AtomicU64::new(0),
+            sequences: Mutex::new(HashMap::new()),
+            published: AtomicU64::new(0),
             source: source.into(),
+            codec: CodecKind::default(),
+            buf: Mutex::new(Vec::new()),
         }
     }
 
-    fn next_sequence(&self) -> u64 {
-        self.sequence.fetch_add(1, Ordering::SeqCst)
+    /// Frame messages with `codec` instead of bincode
+    pub fn with_codec(mut self, codec: CodecKind) -> Self {
+        self.codec = codec;
+        self
     }
 
     fn timestamp_ns() -> u64 {
@@ -39,23 +108,30 @@ impl MarketDataPublisher {
             .unwrap_or(0)
     }
 
-    /// Forward a depth update delta to strategy
-    pub fn publish_delta(&self, update: &DepthUpdate) -> Result<(), TransportError> {
-        let payload =
-            bincode::serialize(update).map_err(|e| TransportError::Serialization(e.to_string()))?;
+    /// Sequence, encode and publish on a topic
+    fn send<M: CodecMessage>(&self, topic: String, message: &M) -> Result<(), TransportError> {
+        // Held across the publish so a stream's sequences leave in order
+        let mut sequences = self.sequences.lock().unwrap();
+        let next = sequences.entry(topic.clone()).or_insert(0);
 
-        let msg = WireMessage {
-            msg_type: MSG_DEPTH_UPDATE,
-            sequence: self.next_sequence(),
-            timestamp_ns: Self::timestamp_ns(),
-            source: self.source.clone(),
-            payload,
-        };
+        let mut buf = self.buf.lock().unwrap();
+        buf.clear();
+        self.codec
+            .encode_into(*next, &self.source, &topic, message, &mut buf)?;
 
-        let data =
-            bincode::serialize(&msg).map_err(|e| TransportError::Serialization(e.to_string()))?;
+        *next += 1;
+        self.published.fetch_add(1, Ordering::Relaxed);
+        self.transport.publish_to(&topic, &buf)
+    }
 
-        self.transport.publish(&data)
+    /// Forward a depth update delta to strategy
+    pub fn publish_delta(&self, update: &DepthUpdate) -> Result<(), TransportError> {
+        self.send(depth_topic(&update.exchange, &update.symbol), update)
+    }
+
+    /// Forward a public trade to strategy
+    pub fn publish_trade(&self, trade: &TradeUpdate) -> Result<(), TransportError> {
+        self.send(trade_topic(&trade.exchange, &trade.symbol), trade)
     }
 
     /// Forward a snapshot to strategy (when strategy requests it)
@@ -71,52 +147,16 @@ impl MarketDataPublisher {
             symbol: symbol.to_string(),
             last_update_id: snapshot.last_update_id,
             timestamp_ns: Self::timestamp_ns(),
-            bids: snapshot
-                .bids
-                .iter()
-                .map(|arr| {
-                    let price = arr[0].parse::<f64>().unwrap_or(0.0);
-                    let qty = arr[1].parse::<f64>().unwrap_or(0.0);
-                    CompactLevel {
-                        price_raw: (price * 100_000_000.0) as i64,
-                        quantity_raw: (qty * 100_000_000.0) as i64,
-                    }
-                })
-                .collect(),
-            asks: snapshot
-                .asks
-                .iter()
-                .map(|arr| {
-                    let price = arr[0].parse::<f64>().unwrap_or(0.0);
-                    let qty = arr[1].parse::<f64>().unwrap_or(0.0);
-                    CompactLevel {
-                        price_raw: (price * 100_000_000.0) as i64,
-                        quantity_raw: (qty * 100_000_000.0) as i64,
-                    }
-                })
-                .collect(),
+            bids: compact_levels(&snapshot.bids),
+            asks: compact_levels(&snapshot.asks),
         };
 
-        let payload = bincode::serialize(&ipc_snapshot)
-            .map_err(|e| TransportError::Serialization(e.to_string()))?;
-
-        let msg = WireMessage {
-            msg_type: MSG_ORDER_BOOK_SNAPSHOT,
-            sequence: self.next_sequence(),
-            timestamp_ns: Self::timestamp_ns(),
-            source: self.source.clone(),
-            payload,
-        };
-
-        let data =
-            bincode::serialize(&msg).map_err(|e| TransportError::Serialization(e.to_string()))?;
-
-        self.transport.publish(&data)
+        self.send(depth_topic(exchange, symbol), &ipc_snapshot)
     }
 
-    /// Get current sequence number
+    /// Number of messages published across all topics
     pub fn sequence(&self) -> u64 {
-        self.sequence.load(Ordering::SeqCst)
+        self.published.load(Ordering::Relaxed)
     }
 
     /// Flush any buffered data
@@ -151,6 +191,7 @@ mod tests {
                 assert_eq!(msg.msg_type, MSG_DEPTH_UPDATE);
                 assert_eq!(msg.sequence, 0);
                 assert_eq!(msg.source, "gateway");
+                assert_eq!(msg.topic, "md.binance.BTCUSDT.depth");
 
                 let decoded: DepthUpdate = bincode::deserialize(&msg.payload).unwrap();
                 assert_eq!(decoded.exchange, "binance");
@@ -162,6 +203,34 @@ mod tests {
         assert!(received);
     }
 
+    #[test]
+    fn test_publish_trade() {
+        let (publisher, subscriber) = channel_pair(100);
+        let md_pub = MarketDataPublisher::new(Box::new(publisher), "gateway");
+
+        let trade = TradeUpdate::new(
+            "binance",
+            "btcusdt",
+            42,
+            Price::from_int(50000),
+            Quantity::from_int(2),
+            true,
+        );
+        md_pub.publish_trade(&trade).unwrap();
+
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| received.push(WireMessage::deserialize(data).unwrap()))
+            .unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].msg_type, MSG_TRADE);
+        assert_eq!(received[0].topic, "md.binance.BTCUSDT.trade");
+        let decoded: TradeUpdate = received[0].decode_payload().unwrap();
+        assert_eq!(decoded.trade_id, 42);
+        assert_eq!(decoded.quantity(), Quantity::from_int(2));
+        assert!(decoded.buyer_is_maker);
+    }
+
     #[test]
     fn test_publish_snapshot() {
         let (publisher, subscriber) = channel_pair(100);
@@ -193,4 +262,108 @@ mod tests {
 
         assert!(received);
     }
+
+    #[test]
+    fn test_published_symbol() {
+        for codec in [CodecKind::Bincode, CodecKind::Fixed] {
+            let (publisher, subscriber) = channel_pair(100);
+            let md_pub = MarketDataPublisher::new(Box::new(publisher), "gateway").with_codec(codec);
+
+            md_pub
+                .publish_delta(&DepthUpdate::new("binance", "btcusdt", 1, 1))
+                .unwrap();
+            md_pub
+                .publish_snapshot(
+                    "kraken",
+                    "ETHUSD",
+                    &DepthSnapshotEvent {
+                        last_update_id: 1,
+                        bids: vec![],
+                        asks: vec![],
+                    },
+                )
+                .unwrap();
+
+            md_pub
+                .publish_trade(&TradeUpdate::new(
+                    "coinbase",
+                    "BTC-USD",
+                    7,
+                    Price::from_int(50000),
+                    Quantity::from_int(1),
+                    false,
+                ))
+                .unwrap();
+
+            let mut symbols = Vec::new();
+            subscriber
+                .poll(&mut |data| symbols.push(published_symbol(data).unwrap().to_string()))
+                .unwrap();
+            assert_eq!(
+                symbols,
+                vec!["binance:BTCUSDT", "kraken:ETHUSD", "coinbase:BTC-USD"],
+                "{:?}",
+                codec
+            );
+        }
+
+        let other = WireMessage::with_raw_payload(transport::MessageType::Signal, 0, "x", vec![]);
+        assert!(published_symbol(&other.serialize().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_sequences_per_topic() {
+        let (publisher, subscriber) = channel_pair(100);
+        let md_pub = MarketDataPublisher::new(Box::new(publisher), "gateway");
+
+        for symbol in ["BTCUSDT", "ETHUSDT", "BTCUSDT", "btcusdt"] {
+            md_pub
+                .publish_delta(&DepthUpdate::new("binance", symbol, 1, 1))
+                .unwrap();
+        }
+        assert_eq!(md_pub.sequence(), 4);
+
+        let mut streams = Vec::new();
+        subscriber
+            .poll(&mut |data| {
+                let header = WireMessage::header(data).unwrap();
+                streams.push((header.topic, header.sequence));
+            })
+            .unwrap();
+        let btc = "md.binance.BTCUSDT.depth".to_string();
+        assert_eq!(
+            streams,
+            vec![
+                (btc.clone(), 0),
+                ("md.binance.ETHUSDT.depth".to_string(), 0),
+                (btc.clone(), 1),
+                (btc, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_publish_fixed_layout() {
+        let (publisher, subscriber) = channel_pair(100);
+        let md_pub =
+            MarketDataPublisher::new(Box::new(publisher), "gateway").with_codec(CodecKind::Fixed);
+
+        let update = DepthUpdate::new("binance", "BTCUSDT", 100, 105)
+            .with_bids(vec![CompactLevel::new(50000_00000000, 1_00000000)]);
+        md_pub.publish_delta(&update).unwrap();
+        md_pub.publish_delta(&update).unwrap();
+
+        let mut sequences = Vec::new();
+        subscriber
+            .poll(&mut |data| {
+                let frame = transport::FrameView::new(data).unwrap();
+                assert_eq!(frame.topic(), "md.binance.BTCUSDT.depth");
+                let view = frame.message::<DepthUpdate>().unwrap();
+                assert_eq!(view.final_update_id(), 105);
+                assert_eq!(view.bids().to_vec(), update.bids);
+                sequences.push(frame.sequence());
+            })
+            .unwrap();
+        assert_eq!(sequences, vec![0, 1]);
+    }
 }
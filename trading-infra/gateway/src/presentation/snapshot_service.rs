@@ -0,0 +1,276 @@
+//! Snapshot Request Service
+//!
+//! Answers `MSG_SNAPSHOT_REQUEST` messages from strategy processes. Requests
+//! are deduplicated and rate limited through the `SnapshotBuffer`, fetched
+//! over REST from the named exchange and published back as snapshots.
+//! `MSG_RETRANSMIT_REQUEST`s arriving on the same transport are answered
+//! straight from the publisher's retransmit buffer.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use trading_core::SnapshotRequest;
+use transport::{
+    MSG_RETRANSMIT_REQUEST, MSG_SNAPSHOT_REQUEST, RetransmitRequest, SequencedPublisher,
+    Subscriber, TransportError, WireMessage,
+};
+
+use super::publisher::MarketDataPublisher;
+use crate::application::SnapshotBuffer;
+use crate::domain::{DepthFetcher, ExchangeId, FetchError};
+
+/// Depth requested for strategy snapshots
+const SNAPSHOT_DEPTH: u32 = 100;
+
+/// Serves strategy snapshot requests
+pub struct SnapshotRequestService<F: DepthFetcher> {
+    publisher: Arc<MarketDataPublisher>,
+    fetchers: HashMap<ExchangeId, Arc<F>>,
+    buffer: SnapshotBuffer,
+    retransmits: Option<Arc<SequencedPublisher>>,
+}
+
+impl<F: DepthFetcher> SnapshotRequestService<F> {
+    /// Create a service allowing `requests_per_second` REST snapshot fetches
+    pub fn new(publisher: Arc<MarketDataPublisher>, requests_per_second: u32) -> Self {
+        SnapshotRequestService {
+            publisher,
+            fetchers: HashMap::new(),
+            buffer: SnapshotBuffer::new(requests_per_second),
+            retransmits: None,
+        }
+    }
+
+    /// Register the snapshot source for an exchange
+    pub fn with_fetcher(mut self, exchange_id: ExchangeId, fetcher: Arc<F>) -> Self {
+        self.fetchers.insert(exchange_id, fetcher);
+        self
+    }
+
+    /// Answer retransmit requests from the market data publisher's buffer
+    pub fn with_retransmits(mut self, publisher: Arc<SequencedPublisher>) -> Self {
+        self.retransmits = Some(publisher);
+        self
+    }
+
+    /// Queue snapshot requests waiting on the transport
+    ///
+    /// Returns the number of requests queued (duplicates are not counted).
+    pub fn receive(&mut self, requests: &dyn Subscriber) -> Result<usize, TransportError> {
+        let mut queued = 0;
+        let buffer = &mut self.buffer;
+        let retransmits = self.retransmits.as_deref();
+        requests.poll(&mut |data| {
+            let Ok(msg) = bincode::deserialize::<WireMessage>(data) else {
+                return;
+            };
+            if msg.msg_type == MSG_RETRANSMIT_REQUEST
+                && let Some(retransmits) = retransmits
+                && let Ok(request) = msg.decode_payload::<RetransmitRequest>()
+            {
+                match retransmits.retransmit(&request) {
+                    Ok(outcome) => tracing::debug!(
+                        "Retransmit {}..={} for {}: {:?}",
+                        request.from_sequence,
+                        request.to_sequence,
+                        msg.source,
+                        outcome
+                    ),
+                    Err(e) => tracing::warn!("Failed to retransmit: {}", e),
+                }
+                return;
+            }
+            if msg.msg_type != MSG_SNAPSHOT_REQUEST {
+                return;
+            }
+            if let Ok(request) = bincode::deserialize::<SnapshotRequest>(&msg.payload)
+                && buffer.request_snapshot(&request.exchange, &request.symbol)
+            {
+                queued += 1;
+            }
+        })?;
+        Ok(queued)
+    }
+
+    /// Fetch and publish the next queued snapshot if the rate limit allows
+    ///
+    /// Returns false when nothing was ready.
+    pub async fn process_next(&mut self) -> bool {
+        let Some(request) = self.buffer.get_next_if_ready() else {
+            return false;
+        };
+
+        let exchange_id = ExchangeId::new(&request.exchange);
+        let Some(fetcher) = self.fetchers.get(&exchange_id) else {
+            tracing::warn!(
+                "Snapshot requested for unknown exchange {}",
+                request.exchange
+            );
+            return true;
+        };
+
+        match fetcher
+            .get_depth(&request.symbol, Some(SNAPSHOT_DEPTH))
+            .await
+        {
+            Ok(snapshot) => {
+                if let Err(e) = self.publisher.publish_snapshot(
+                    exchange_id.as_str(),
+                    &request.symbol.to_uppercase(),
+                    &snapshot,
+                ) {
+                    tracing::warn!("Failed to publish snapshot for {}: {}", request.symbol, e);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to fetch snapshot for {}:{}: {}",
+                    request.exchange,
+                    request.symbol,
+                    e
+                );
+                // Retry transient failures; API errors won't fix themselves
+                if matches!(e, FetchError::Network(_)) {
+                    self.buffer
+                        .request_snapshot(&request.exchange, &request.symbol);
+                }
+            }
+        }
+        true
+    }
+
+    /// Serve requests until the future is dropped
+    pub async fn run(mut self, requests: Box<dyn Subscriber>) {
+        let mut ticker = tokio::time::interval(Duration::from_millis(10));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.receive(requests.as_ref()) {
+                tracing::warn!("Failed to poll snapshot requests: {}", e);
+            }
+            while self.process_next().await {}
+        }
+    }
+
+    /// Number of requests waiting on the rate limit
+    pub fn pending(&self) -> usize {
+        self.buffer.pending_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use trading_core::{DepthSnapshotEvent, OrderBookSnapshot};
+    use transport::{MSG_ORDER_BOOK_SNAPSHOT, Publisher, channel_pair};
+
+    struct FixedDepth;
+
+    #[async_trait]
+    impl DepthFetcher for FixedDepth {
+        async fn get_depth(
+            &self,
+            symbol: &str,
+            _limit: Option<u32>,
+        ) -> Result<DepthSnapshotEvent, FetchError> {
+            if symbol != "BTCUSDT" {
+                return Err(FetchError::Api {
+                    code: -1121,
+                    message: "Invalid symbol".to_string(),
+                });
+            }
+            Ok(DepthSnapshotEvent {
+                last_update_id: 42,
+                bids: vec![["50000".to_string(), "1".to_string()]],
+                asks: vec![["50001".to_string(), "2".to_string()]],
+            })
+        }
+    }
+
+    fn request(exchange: &str, symbol: &str) -> Vec<u8> {
+        let msg = WireMessage::new(
+            transport::MessageType::SnapshotRequest,
+            0,
+            "strategy",
+            &SnapshotRequest::new(exchange, symbol),
+        )
+        .unwrap();
+        msg.serialize().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_serves_snapshot_requests() {
+        let (md_pub, md_sub) = channel_pair(100);
+        let (req_pub, req_sub) = channel_pair(100);
+        let publisher = Arc::new(MarketDataPublisher::new(Box::new(md_pub), "gateway"));
+        let mut service = SnapshotRequestService::new(publisher, 1000)
+            .with_fetcher(ExchangeId::simulator(), Arc::new(FixedDepth));
+
+        req_pub.publish(&request("simulator", "BTCUSDT")).unwrap();
+        req_pub.publish(&request("simulator", "BTCUSDT")).unwrap(); // duplicate
+        req_pub.publish(&request("nowhere", "BTCUSDT")).unwrap();
+        assert_eq!(service.receive(&req_sub).unwrap(), 2);
+
+        assert!(service.process_next().await);
+        let mut snapshots = Vec::new();
+        md_sub
+            .poll(&mut |data| {
+                let msg: WireMessage = bincode::deserialize(data).unwrap();
+                assert_eq!(msg.msg_type, MSG_ORDER_BOOK_SNAPSHOT);
+                snapshots.push(msg.decode_payload::<OrderBookSnapshot>().unwrap());
+            })
+            .unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].exchange, "simulator");
+        assert_eq!(snapshots[0].symbol, "BTCUSDT");
+        assert_eq!(snapshots[0].last_update_id, 42);
+
+        // Unknown exchanges are dropped rather than retried
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        assert!(service.process_next().await);
+        assert_eq!(service.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_answers_retransmit_requests() {
+        let (md_pub, md_sub) = channel_pair(100);
+        let (req_pub, req_sub) = channel_pair(100);
+        let sequenced = Arc::new(SequencedPublisher::new(
+            Box::new(md_pub),
+            "gateway",
+            &transport::SequencingConfig::default(),
+        ));
+        let publisher = Arc::new(MarketDataPublisher::new(
+            Box::new(Arc::clone(&sequenced)),
+            "gateway",
+        ));
+        let mut service = SnapshotRequestService::<FixedDepth>::new(Arc::clone(&publisher), 1000)
+            .with_retransmits(sequenced);
+
+        let update = trading_core::DepthUpdate::new("simulator", "BTCUSDT", 1, 1);
+        for _ in 0..3 {
+            publisher.publish_delta(&update).unwrap();
+        }
+        let retransmit = WireMessage::new(
+            transport::MessageType::RetransmitRequest,
+            0,
+            "strategy",
+            &RetransmitRequest {
+                source: "gateway".to_string(),
+                topic: "md.simulator.BTCUSDT.depth".to_string(),
+                from_sequence: 1,
+                to_sequence: 2,
+            },
+        )
+        .unwrap();
+        req_pub.publish(&retransmit.serialize().unwrap()).unwrap();
+        assert_eq!(service.receive(&req_sub).unwrap(), 0);
+
+        let mut sequences = Vec::new();
+        md_sub
+            .poll(&mut |data| sequences.push(WireMessage::header(data).unwrap().sequence))
+            .unwrap();
+        assert_eq!(sequences, vec![0, 1, 2, 1, 2]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Exchange to strategy market data test
+//!
+//! Runs exchange-sim, the gateway's exchange manager and publisher, and a
+//! strategy `MarketDataSubscriber` in one process, wired as the gateway
+//! binary wires them. The book the strategy rebuilds from the snapshot and
+//! the deltas after it must match the simulator's own depth.
+
+use axum::{extract::State, routing::get};
+use exchange_sim::{
+    Exchange, ExchangeConfig, Value,
+    application::ports::AccountRepository,
+    domain::{Symbol, TradingPairConfig},
+    presentation::ws_handler,
+};
+use gateway::{
+    DeltaForwarder, ExchangeId, ExchangeManager, MarketDataPublisher, SnapshotRequestService,
+    load_config_from_str,
+};
+use serde_json::{Value as JsonValue, json};
+use std::sync::Arc;
+use std::time::Duration;
+use strategy::{MarketDataSubscriber, OrderBookManager};
+use tokio::net::TcpListener;
+use trading_core::{Price, PriceLevel, Quantity};
+use transport::{SequencedPublisher, SequencingConfig, channel_pair};
+
+const DEPTH: usize = 20;
+
+struct Sim {
+    addr: String,
+    http: reqwest::Client,
+}
+
+impl Sim {
+    /// Place a GTC limit order under a client order id
+    async fn place(&self, id: &str, side: &str, price: &str, quantity: &str) {
+        let response = self
+            .http
+            .post(format!("http://{}/api/v3/order", self.addr))
+            .header("X-MBX-APIKEY", "maker")
+            .json(&json!({
+                "symbol": "BTCUSDT",
+                "side": side,
+                "type": "LIMIT",
+                "timeInForce": "GTC",
+                "quantity": quantity,
+                "price": price,
+                "newClientOrderId": id
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+
+    async fn cancel(&self, id: &str) {
+        let response = self
+            .http
+            .delete(format!(
+                "http://{}/api/v3/order?symbol=BTCUSDT&origClientOrderId={}",
+                self.addr, id
+            ))
+            .header("X-MBX-APIKEY", "maker")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+
+    /// The simulator's depth as fixed-point levels
+    async fn depth(&self) -> (Vec<PriceLevel>, Vec<PriceLevel>) {
+        let body: JsonValue = self
+            .http
+            .get(format!(
+                "http://{}/api/v3/depth?symbol=BTCUSDT&limit={}",
+                self.addr, DEPTH
+            ))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let levels = |side: &str| -> Vec<PriceLevel> {
+            body[side]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|level| {
+                    PriceLevel::new(
+                        Price::parse(level[0].as_str().unwrap()).unwrap(),
+                        Quantity::parse(level[1].as_str().unwrap()).unwrap(),
+                    )
+                })
+                .collect()
+        };
+        (levels("bids"), levels("asks"))
+    }
+}
+
+async fn start_sim() -> Sim {
+    let exchange = Exchange::new(ExchangeConfig::default());
+    exchange
+        .add_trading_pair(TradingPairConfig::new(
+            Symbol::new("BTCUSDT").unwrap(),
+            "BTC",
+            "USDT",
+        ))
+        .await;
+    let mut account = exchange.account_repo.get_or_create("maker").await;
+    account.deposit("USDT", Value::from_int(10_000_000));
+    account.deposit("BTC", Value::from_int(1_000));
+    exchange.account_repo.save(account).await;
+
+    let ws_state = exchange.ws_state();
+    let router = exchange.rest_router().route(
+        "/ws",
+        get(move |ws| ws_handler(ws, State(Arc::clone(&ws_state)))),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    Sim {
+        addr,
+        http: reqwest::Client::new(),
+    }
+}
+
+/// Poll the subscriber until its BTCUSDT book equals the simulator's depth
+async fn wait_for_match(sim: &Sim, subscriber: &MarketDataSubscriber, books: &OrderBookManager) {
+    let book = books.book("simulator", "BTCUSDT");
+    let result = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            subscriber.poll().unwrap();
+            let expected = sim.depth().await;
+            if book.is_initialized() && (book.top_bids(DEPTH), book.top_asks(DEPTH)) == expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await;
+    if result.is_err() {
+        panic!(
+            "strategy book {:?} never matched simulator depth {:?}",
+            (book.top_bids(DEPTH), book.top_asks(DEPTH)),
+            sim.depth().await
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_strategy_book_matches_simulator_depth() {
+    let sim = start_sim().await;
+    // Resting liquidity the REST snapshot will carry
+    for (id, side, price) in [
+        ("b1", "BUY", "49990"),
+        ("b2", "BUY", "49995.5"),
+        ("a1", "SELL", "50005"),
+        ("a2", "SELL", "50010.25"),
+    ] {
+        sim.place(id, side, price, "1.5").await;
+    }
+
+    let config = load_config_from_str(
+        &json!({
+            "exchanges": [{
+                "id": "simulator",
+                "name": "Simulator",
+                "rest_url": format!("http://{}", sim.addr),
+                "ws_url": format!("ws://{}/ws", sim.addr),
+                "symbols": ["BTCUSDT"]
+            }],
+            "global": {
+                "reconnect_delay_ms": 10,
+                "heartbeat_interval_ms": 50
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    // Gateway side, as in the gateway binary
+    let (md_pub, md_sub) = channel_pair(10_000);
+    let (requests_pub, requests_sub) = channel_pair(100);
+    let sequenced = Arc::new(SequencedPublisher::new(
+        Box::new(md_pub),
+        "gateway",
+        &SequencingConfig::default(),
+    ));
+    let publisher = Arc::new(MarketDataPublisher::new(
+        Box::new(Arc::clone(&sequenced)),
+        "gateway",
+    ));
+    let mut manager = ExchangeManager::new(config, DeltaForwarder::new(Arc::clone(&publisher)));
+    manager.initialize();
+    assert_eq!(manager.start_all().await.len(), 1);
+
+    let exchange_id = ExchangeId::new("simulator");
+    let client = manager.rest_client(&exchange_id).unwrap().clone();
+    let snapshots = SnapshotRequestService::new(Arc::clone(&publisher), 10)
+        .with_retransmits(sequenced)
+        .with_fetcher(exchange_id, Arc::new(client));
+    tokio::spawn(snapshots.run(Box::new(requests_sub)));
+
+    // Strategy side
+    let books = OrderBookManager::new();
+    let subscriber =
+        MarketDataSubscriber::new(Box::new(md_sub), Box::new(requests_pub), books.clone());
+
+    wait_for_match(&sim, &subscriber, &books).await;
+
+    // Deltas: new levels, a cancel, an add to an existing level and a
+    // crossing order that takes out part of the best ask
+    sim.place("b3", "BUY", "49998", "2").await;
+    sim.place("a3", "SELL", "50001", "0.75").await;
+    sim.place("b4", "BUY", "49990", "0.5").await;
+    sim.cancel("b3").await;
+    sim.place("t1", "BUY", "50001", "0.25").await;
+    wait_for_match(&sim, &subscriber, &books).await;
+
+    let book = books.book("simulator", "BTCUSDT");
+    assert_eq!(
+        book.best_ask().unwrap(),
+        PriceLevel::new(Price::from_int(50_001), Quantity::parse("0.5").unwrap())
+    );
+    assert_eq!(
+        book.best_bid().unwrap(),
+        PriceLevel::new(
+            Price::parse("49995.5").unwrap(),
+            Quantity::parse("1.5").unwrap()
+        )
+    );
+    assert_eq!(subscriber.sequence_stats().gaps, 0);
+}
@@ -0,0 +1,90 @@
+//! REST rate limiter tests against exchange-sim
+//!
+//! The simulator reports used weight in `X-MBX-USED-WEIGHT-1M`, answers
+//! 429 with Retry-After and bans clients that ignore it. The gateway
+//! limiter must follow the headers and back off far enough to avoid a ban.
+
+use exchange_sim::{
+    Exchange, ExchangeConfig, OrderBookReader, RateLimitConfig,
+    domain::{Symbol, TradingPairConfig},
+};
+use gateway::{RateLimits, RestClient, RestError, RestRateLimiter};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+async fn start_sim(request_weight_per_minute: u32) -> String {
+    let exchange = Exchange::new(ExchangeConfig {
+        rate_limits: RateLimitConfig {
+            request_weight_per_minute,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let symbol = Symbol::new("BTCUSDT").unwrap();
+    exchange
+        .add_trading_pair(TradingPairConfig::new(symbol.clone(), "BTC", "USDT"))
+        .await;
+    exchange.order_book_repo.get_or_create(&symbol).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    let router = exchange.rest_router();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    addr
+}
+
+fn client(url: &str, limits: RateLimits) -> RestClient {
+    RestClient::new(url.to_string(), "shared-key".to_string())
+        .with_rate_limiter(Arc::new(RestRateLimiter::new(limits)))
+}
+
+#[tokio::test]
+async fn test_limiter_follows_venue_weight_headers() {
+    let url = start_sim(1200).await;
+    let ours = client(&url, RateLimits::default());
+
+    // limit=1000 weighs 10
+    ours.get_depth("BTCUSDT", Some(1000)).await.unwrap();
+    assert_eq!(ours.rate_limiter().metrics().weight_used, 10);
+
+    // Another process on the same key spends weight we never charged
+    let other = client(&url, RateLimits::default());
+    for _ in 0..3 {
+        other.get_depth("BTCUSDT", Some(1000)).await.unwrap();
+    }
+    ours.get_server_time().await.unwrap();
+
+    let metrics = ours.rate_limiter().metrics();
+    assert_eq!(metrics.weight_used, 40);
+    assert_eq!(metrics.weight_headroom(), 1160);
+}
+
+#[tokio::test]
+async fn test_backs_off_on_429_without_getting_banned() {
+    // One unit of weight refills per second
+    let url = start_sim(60).await;
+    // Limiter configured too generously, so the venue has to push back
+    let client = client(&url, RateLimits::default());
+
+    let mut rejected = None;
+    for _ in 0..100 {
+        if let Err(e) = client.get_depth("BTCUSDT", Some(5)).await {
+            rejected = Some(e);
+            break;
+        }
+    }
+    assert!(matches!(rejected, Some(RestError::Api { code: -1015, .. })));
+
+    let metrics = client.rate_limiter().metrics();
+    assert_eq!(metrics.rejected, 1);
+    assert!(metrics.blocked_for.unwrap() <= Duration::from_secs(1));
+
+    // The next call waits out Retry-After instead of drawing a 418
+    let start = tokio::time::Instant::now();
+    client.get_depth("BTCUSDT", Some(5)).await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(500));
+    assert_eq!(client.rate_limiter().metrics().rejected, 1);
+}
@@ -0,0 +1,193 @@
+//! User-data stream tests against a stub venue
+//!
+//! The stub opens listen keys over REST and pushes raw `executionReport`
+//! events on `/ws/<listenKey>`. Fills must reach the OMS, the key must be
+//! kept alive, and an expired key must be replaced.
+
+use axum::{
+    Json, Router,
+    extract::{
+        Path, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+    routing::{get, post},
+};
+use gateway::{
+    ExchangeId, OrderManager, OrderRequest, OrderState, QualifiedSymbol, ReconnectPolicy,
+    RestClient, UserDataStream,
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use trading_core::{Price, Quantity, Side};
+
+#[derive(Clone)]
+struct Venue {
+    opened: Arc<AtomicUsize>,
+    keepalives: Arc<AtomicUsize>,
+    connections: Arc<AtomicUsize>,
+    events: broadcast::Sender<String>,
+}
+
+async fn open_listen_key(State(venue): State<Venue>) -> Json<Value> {
+    let n = venue.opened.fetch_add(1, Ordering::SeqCst) + 1;
+    Json(json!({ "listenKey": format!("key-{}", n) }))
+}
+
+async fn keepalive(State(venue): State<Venue>) -> Json<Value> {
+    venue.keepalives.fetch_add(1, Ordering::SeqCst);
+    Json(json!({}))
+}
+
+async fn new_order(Json(body): Json<Value>) -> Json<Value> {
+    Json(json!({
+        "symbol": body["symbol"],
+        "orderId": 42,
+        "clientOrderId": body["newClientOrderId"],
+        "price": body["price"],
+        "origQty": body["quantity"],
+        "executedQty": "0",
+        "status": "NEW",
+        "type": "LIMIT",
+        "side": body["side"]
+    }))
+}
+
+async fn empty() -> Json<Value> {
+    Json(json!([]))
+}
+
+async fn user_data(
+    ws: WebSocketUpgrade,
+    Path(_listen_key): Path<String>,
+    State(venue): State<Venue>,
+) -> Response {
+    ws.on_upgrade(move |socket| push_events(socket, venue))
+}
+
+async fn push_events(mut socket: WebSocket, venue: Venue) {
+    let mut events = venue.events.subscribe();
+    venue.connections.fetch_add(1, Ordering::SeqCst);
+    while let Ok(event) = events.recv().await {
+        if socket.send(Message::Text(event.into())).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn start_venue() -> (String, Venue) {
+    let venue = Venue {
+        opened: Arc::new(AtomicUsize::new(0)),
+        keepalives: Arc::new(AtomicUsize::new(0)),
+        connections: Arc::new(AtomicUsize::new(0)),
+        events: broadcast::channel(16).0,
+    };
+    let router = Router::new()
+        .route(
+            "/api/v3/userDataStream",
+            post(open_listen_key).put(keepalive),
+        )
+        .route("/api/v3/order", post(new_order))
+        .route("/api/v3/openOrders", get(empty))
+        .route("/api/v3/myTrades", get(empty))
+        .route("/ws/{listen_key}", get(user_data))
+        .with_state(venue.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    (addr, venue)
+}
+
+async fn wait_until(what: &str, condition: impl Fn() -> bool) {
+    let waited = tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await;
+    assert!(waited.is_ok(), "timed out waiting for {}", what);
+}
+
+#[tokio::test]
+async fn test_stream_fills_reach_oms_and_expired_key_is_replaced() {
+    let (addr, venue) = start_venue().await;
+    let exchange_id = ExchangeId::new("stub");
+    let key = QualifiedSymbol::new(exchange_id.clone(), "BTCUSDT");
+    let client = RestClient::new(format!("http://{}", addr), "key".to_string());
+
+    let oms =
+        Arc::new(OrderManager::new("gw").with_venue(exchange_id.clone(), Arc::new(client.clone())));
+    let order = oms
+        .submit_with_id(
+            "c1",
+            OrderRequest::limit(
+                key.clone(),
+                Side::Buy,
+                Quantity::from_int(2),
+                Price::from_int(100),
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(order.state, OrderState::New);
+
+    let stream = UserDataStream::new(
+        exchange_id,
+        client,
+        format!("ws://{}/ws", addr),
+        Arc::clone(&oms),
+    )
+    .with_policy(ReconnectPolicy {
+        initial_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+        ..Default::default()
+    })
+    .with_symbols(&["BTCUSDT".to_string()])
+    .with_keepalive_interval(Duration::from_millis(50));
+    tokio::spawn(stream.run());
+    wait_until("connection", || {
+        venue.connections.load(Ordering::SeqCst) == 1
+    })
+    .await;
+
+    let trade = json!({
+        "e": "executionReport",
+        "s": "BTCUSDT",
+        "c": "c1",
+        "C": "",
+        "i": 42,
+        "x": "TRADE",
+        "X": "PARTIALLY_FILLED",
+        "z": "1",
+        "t": 7,
+        "L": "100",
+        "l": "1",
+        "n": "0",
+        "N": "USDT"
+    });
+    venue.events.send(trade.to_string()).unwrap();
+    wait_until("fill", || {
+        oms.position(&key)
+            .is_some_and(|p| p.quantity == Quantity::from_int(1))
+    })
+    .await;
+    assert_eq!(oms.order("c1").unwrap().state, OrderState::PartiallyFilled);
+
+    wait_until("keepalive", || venue.keepalives.load(Ordering::SeqCst) > 0).await;
+
+    venue
+        .events
+        .send(json!({ "e": "listenKeyExpired" }).to_string())
+        .unwrap();
+    wait_until("new listen key", || {
+        venue.opened.load(Ordering::SeqCst) == 2 && venue.connections.load(Ordering::SeqCst) == 2
+    })
+    .await;
+}
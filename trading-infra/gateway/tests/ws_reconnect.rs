@@ -0,0 +1,227 @@
+//! WebSocket reconnect tests against exchange-sim
+//!
+//! The simulator drops every open connection on demand. The gateway client
+//! must come back, replay its subscriptions and get the market data handler
+//! to take a fresh snapshot.
+
+use axum::{extract::State, routing::get};
+use exchange_sim::{
+    Exchange, ExchangeConfig, StreamManager, Value,
+    application::ports::AccountRepository,
+    domain::{Symbol, TradingPairConfig},
+    presentation::ws_handler,
+};
+use gateway::{
+    ExchangeId, ExchangeManager, QualifiedSymbol, ReconnectPolicy, SnapshotWriter, StreamData,
+    UpdateWriter, WsClient, WsEvent, load_config_from_str,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use trading_core::DepthSnapshotEvent;
+
+struct Sim {
+    addr: String,
+    streams: Arc<StreamManager>,
+}
+
+impl Sim {
+    /// Rest an order so BTCUSDT has a book and emits a depth update
+    async fn place_order(&self, price: &str) {
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/api/v3/order", self.addr))
+            .header("X-MBX-APIKEY", "maker")
+            .json(&json!({
+                "symbol": "BTCUSDT",
+                "side": "BUY",
+                "type": "LIMIT",
+                "timeInForce": "GTC",
+                "quantity": "1",
+                "price": price
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+}
+
+async fn start_sim() -> Sim {
+    let exchange = Exchange::new(ExchangeConfig::default());
+    exchange
+        .add_trading_pair(TradingPairConfig::new(
+            Symbol::new("BTCUSDT").unwrap(),
+            "BTC",
+            "USDT",
+        ))
+        .await;
+    let mut account = exchange.account_repo.get_or_create("maker").await;
+    account.deposit("USDT", Value::from_int(10_000_000));
+    exchange.account_repo.save(account).await;
+
+    let ws_state = exchange.ws_state();
+    let streams = Arc::clone(&ws_state.stream_manager);
+    let router = exchange.rest_router().route(
+        "/ws",
+        get(move |ws| ws_handler(ws, State(Arc::clone(&ws_state)))),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    Sim { addr, streams }
+}
+
+fn fast_policy() -> ReconnectPolicy {
+    ReconnectPolicy {
+        initial_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(100),
+        max_attempts: 10,
+        heartbeat_interval: Duration::from_millis(50),
+        stale_timeout: Duration::from_secs(5),
+        max_connection_age: Duration::from_secs(60),
+    }
+}
+
+/// Wait for the first event matching `pred`
+async fn expect_event(
+    events: &mut mpsc::Receiver<WsEvent>,
+    pred: impl Fn(&WsEvent) -> bool,
+) -> WsEvent {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let event = events.recv().await.expect("event channel closed");
+            if pred(&event) {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for event")
+}
+
+#[tokio::test]
+async fn test_resubscribes_after_forced_disconnect() {
+    let sim = start_sim().await;
+    let client = WsClient::new(format!("ws://{}/ws", sim.addr)).with_policy(fast_policy());
+    let (sender, mut events) = client.connect().await.unwrap();
+
+    let id = sender
+        .subscribe(vec!["btcusdt@depth".to_string()])
+        .await
+        .unwrap();
+    expect_event(
+        &mut events,
+        |e| matches!(e, WsEvent::Response { id: r, .. } if *r == id),
+    )
+    .await;
+
+    assert_eq!(sim.streams.disconnect_all(), 1);
+    expect_event(&mut events, |e| matches!(e, WsEvent::Disconnected)).await;
+    expect_event(&mut events, |e| matches!(e, WsEvent::Reconnected)).await;
+    // The replayed SUBSCRIBE is acknowledged under a fresh id
+    expect_event(
+        &mut events,
+        |e| matches!(e, WsEvent::Response { id: r, .. } if *r > id),
+    )
+    .await;
+
+    sim.place_order("50000").await;
+    let event = expect_event(&mut events, |e| matches!(e, WsEvent::StreamData(_))).await;
+    assert!(matches!(
+        event,
+        WsEvent::StreamData(StreamData::DepthUpdate { ref symbol, .. }) if symbol == "BTCUSDT"
+    ));
+}
+
+#[tokio::test]
+async fn test_recycles_connection_before_age_limit() {
+    let sim = start_sim().await;
+    let policy = ReconnectPolicy {
+        max_connection_age: Duration::from_millis(200),
+        ..fast_policy()
+    };
+    let client = WsClient::new(format!("ws://{}/ws", sim.addr)).with_policy(policy);
+    let (sender, mut events) = client.connect().await.unwrap();
+    sender
+        .subscribe(vec!["btcusdt@depth".to_string()])
+        .await
+        .unwrap();
+
+    expect_event(&mut events, |e| matches!(e, WsEvent::Disconnected)).await;
+    expect_event(&mut events, |e| matches!(e, WsEvent::Reconnected)).await;
+
+    sim.place_order("50000").await;
+    expect_event(&mut events, |e| matches!(e, WsEvent::StreamData(_))).await;
+}
+
+/// Counts snapshots; accepts every update
+#[derive(Clone, Default)]
+struct SnapshotCounter {
+    snapshots: Arc<AtomicUsize>,
+}
+
+impl SnapshotWriter for SnapshotCounter {
+    fn apply_snapshot(&self, _key: &QualifiedSymbol, _snapshot: &DepthSnapshotEvent) {
+        self.snapshots.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl UpdateWriter for SnapshotCounter {
+    fn apply_update(&self, _exchange_id: &ExchangeId, _update: &StreamData) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn test_handler_resnapshots_after_reconnect() {
+    let sim = start_sim().await;
+    sim.place_order("50000").await;
+
+    let config = load_config_from_str(
+        &json!({
+            "exchanges": [{
+                "id": "simulator",
+                "name": "Simulator",
+                "rest_url": format!("http://{}", sim.addr),
+                "ws_url": format!("ws://{}/ws", sim.addr),
+                "symbols": ["BTCUSDT"],
+                "market_data": { "snapshot_interval_ms": 10 }
+            }],
+            "global": {
+                "reconnect_delay_ms": 10,
+                "heartbeat_interval_ms": 50
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let counter = SnapshotCounter::default();
+    let mut manager = ExchangeManager::new(config, counter.clone());
+    manager.initialize();
+    assert_eq!(manager.start_all().await.len(), 1);
+
+    let wait_for = |n: usize| {
+        let snapshots = Arc::clone(&counter.snapshots);
+        async move {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while snapshots.load(Ordering::SeqCst) < n {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("timed out waiting for snapshot")
+        }
+    };
+    wait_for(1).await;
+
+    assert_eq!(sim.streams.disconnect_all(), 1);
+    wait_for(2).await;
+}
@@ -12,6 +12,7 @@
 //! ~0.9+ → Extreme, consider pulling quotes
 
 use crate::domain::{ToxicityLevel, ToxicityMetrics};
+use trading_core::Vpin;
 
 /// Configuration for toxic flow detection
 #[derive(Debug, Clone, Copy)]
@@ -40,29 +41,9 @@ impl Default for ToxicFlowConfig {
     }
 }
 
-/// Volume bucket for VPIN calculation
-#[derive(Debug, Clone, Copy, Default)]
-struct VolumeBucket {
-    buy_volume: i64,
-    sell_volume: i64,
-}
-
-impl VolumeBucket {
-    fn total(&self) -> i64 {
-        self.buy_volume + self.sell_volume
-    }
-
-    fn imbalance(&self) -> i64 {
-        (self.buy_volume - self.sell_volume).abs()
-    }
-}
-
 /// Toxic flow detector using VPIN methodology
 pub struct ToxicFlowDetector {
-    config: ToxicFlowConfig,
-    buckets: Vec<VolumeBucket>,
-    current_bucket: VolumeBucket,
-    bucket_index: usize,
+    vpin: Vpin,
     /// Order flow imbalance tracking
     bid_improvement: f64,
     bid_deterioration: f64,
@@ -73,10 +54,7 @@ pub struct ToxicFlowDetector {
 impl ToxicFlowDetector {
     pub fn new(config: ToxicFlowConfig) -> Self {
         Self {
-            config,
-            buckets: vec![VolumeBucket::default(); config.num_buckets],
-            current_bucket: VolumeBucket::default(),
-            bucket_index: 0,
+            vpin: Vpin::new(config.bucket_size, config.num_buckets),
             bid_improvement: 0.0,
             bid_deterioration: 0.0,
             ask_improvement: 0.0,
@@ -86,19 +64,7 @@ impl ToxicFlowDetector {
 
     /// Record a trade
     pub fn record_trade(&mut self, volume: i64, is_buy: bool) {
-        if is_buy {
-            self.current_bucket.buy_volume += volume;
-        } else {
-            self.current_bucket.sell_volume += volume;
-        }
-
-        // Check if bucket is full
-        if self.current_bucket.total() >= self.config.bucket_size {
-            // Store bucket and move to next
-            self.buckets[self.bucket_index] = self.current_bucket;
-            self.bucket_index = (self.bucket_index + 1) % self.config.num_buckets;
-            self.current_bucket = VolumeBucket::default();
-        }
+        self.vpin.record(volume, is_buy);
     }
 
     /// Record order book change for OFI
@@ -125,14 +91,7 @@ impl ToxicFlowDetector {
 
     /// Calculate VPIN
     fn calculate_vpin(&self) -> f64 {
-        let total_imbalance: i64 = self.buckets.iter().map(|b| b.imbalance()).sum();
-        let total_volume: i64 = self.buckets.iter().map(|b| b.total()).sum();
-
-        if total_volume == 0 {
-            return 0.3; // Default to normal
-        }
-
-        total_imbalance as f64 / total_volume as f64
+        self.vpin.value().unwrap_or(0.3) // Default to normal
     }
 
     /// Calculate OFI
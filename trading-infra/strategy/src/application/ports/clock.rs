@@ -0,0 +1,37 @@
+//! Clock Port - Abstraction for strategy time
+//!
+//! Strategies and the engine read time only through this port, so the same
+//! code runs on wall-clock time live and on event time in replay or
+//! simulation, with identical results for identical inputs.
+
+use std::time::Duration;
+
+/// Source of time for strategies and the engine loop
+///
+/// Time is nanoseconds since the Unix epoch, the same unit market data
+/// timestamps use.
+pub trait Clock: Send + Sync {
+    /// Current time in nanoseconds since the Unix epoch
+    fn now_ns(&self) -> u64;
+
+    /// Current time in milliseconds since the Unix epoch
+    fn now_ms(&self) -> u64 {
+        self.now_ns() / 1_000_000
+    }
+
+    /// Block until the clock reaches `deadline_ns`
+    ///
+    /// Gives up after `timeout` of real time so callers can check for
+    /// shutdown. Returns whether the deadline was reached.
+    fn wait_until(&self, deadline_ns: u64, timeout: Duration) -> bool;
+}
+
+impl<C: Clock + ?Sized> Clock for std::sync::Arc<C> {
+    fn now_ns(&self) -> u64 {
+        (**self).now_ns()
+    }
+
+    fn wait_until(&self, deadline_ns: u64, timeout: Duration) -> bool {
+        (**self).wait_until(deadline_ns, timeout)
+    }
+}
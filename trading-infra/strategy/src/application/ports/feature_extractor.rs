@@ -1,9 +1,10 @@
 //! Feature Extractor Port - Abstraction for feature extraction
 //!
 //! Defines how features are extracted from market data.
-//! Follows Interface Segregation - extractors only need OrderBookReader.
+//! Follows Interface Segregation - extractors only need OrderBookReader
+//! or, for trade-based features, TradeReader.
 
-use crate::application::ports::market_data::OrderBookReader;
+use crate::application::ports::market_data::{OrderBookReader, TradeReader};
 use crate::domain::Features;
 use std::sync::Arc;
 
@@ -27,6 +28,21 @@ pub trait FeatureExtractorPort: Send + Sync {
     fn name(&self) -> &'static str;
 }
 
+/// Port for extracting features from the trade tape
+///
+/// The trade-flow counterpart of `FeatureExtractorPort`, for order-flow
+/// features that the book alone can't give (aggressor volume, trade VWAP).
+pub trait TradeFeatureExtractorPort: Send + Sync {
+    /// Extract features from a symbol's trades
+    fn extract(&self, trades: &dyn TradeReader) -> Features;
+
+    /// Get the names of features this extractor produces
+    fn feature_names(&self) -> Vec<&'static str>;
+
+    /// Get extractor name for identification
+    fn name(&self) -> &'static str;
+}
+
 /// Pipeline for combining multiple feature extractors
 pub struct FeatureExtractionPipeline {
     extractors: Vec<Arc<dyn FeatureExtractorPort>>,
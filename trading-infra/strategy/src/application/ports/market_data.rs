@@ -3,6 +3,8 @@
 //! This port defines how the signal generation domain accesses market data.
 //! Infrastructure layer provides concrete implementations.
 
+use crate::domain::{AggressorVolume, BookFlow, TradePrint, VolumeBucket};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use trading_core::{Price, Quantity};
 
@@ -47,10 +49,42 @@ pub trait OrderBookReader: Send + Sync {
 
     /// Get the last update timestamp (microseconds)
     fn last_update_time(&self) -> Option<u64>;
+
+    /// Get the exchange sequence number of the last update
+    fn last_update_id(&self) -> Option<u64> {
+        None
+    }
+
+    /// Order flow of every book change stamped at or after `since_ns`,
+    /// if this source records it
+    fn flow_since(&self, _since_ns: u64) -> Option<BookFlow> {
+        None
+    }
+}
+
+/// Abstraction for the trade prints of one symbol
+///
+/// Lookbacks are given as an absolute start time in exchange nanoseconds,
+/// so callers choose whether "now" is the wall clock or the last print.
+pub trait TradeReader: Send + Sync {
+    /// Most recent trade
+    fn last_trade(&self) -> Option<TradePrint>;
+
+    /// Trades stamped at or after `since_ns`, oldest first
+    fn trades_since(&self, since_ns: u64) -> Vec<TradePrint>;
+
+    /// Volume-weighted average trade price since `since_ns`
+    fn vwap_since(&self, since_ns: u64) -> Option<Price>;
+
+    /// Buy and sell aggressor volume since `since_ns`
+    fn aggressor_volume_since(&self, since_ns: u64) -> AggressorVolume;
+
+    /// Aggressor volume in `bucket_ns` buckets since `since_ns`
+    fn volume_buckets_since(&self, since_ns: u64, bucket_ns: u64) -> Vec<VolumeBucket>;
 }
 
 /// Symbol identifier for market data lookups
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SymbolKey {
     pub exchange: String,
     pub symbol: String,
@@ -87,6 +121,11 @@ pub trait MarketDataPort: Send + Sync {
 
     /// Get all available symbols
     fn symbols(&self) -> Vec<SymbolKey>;
+
+    /// Get the trade tape for a symbol, if this source carries trades
+    fn trades(&self, _key: &SymbolKey) -> Option<Arc<dyn TradeReader>> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -3,18 +3,27 @@
 //! Ports define the boundaries of the application layer:
 //! - Input Ports: How external actors interact with the domain (SignalGenerator)
 //! - Output Ports: How the domain interacts with external systems (MarketData, SignalPublisher)
+//! - Clock: Where strategies and the engine read time from
+//! - TrainingLog: Where scored features and their outcomes are recorded
 //!
 //! Following hexagonal/clean architecture, the domain depends on these abstractions,
 //! and infrastructure provides concrete implementations.
 
+mod clock;
 mod feature_extractor;
 mod market_data;
 mod signal_generator;
 mod signal_publisher;
+mod training_log;
 
+pub use clock::Clock;
 pub use feature_extractor::{
     FeatureExtractionPipeline, FeatureExtractorPort, StatefulFeatureExtractor,
+    TradeFeatureExtractorPort,
+};
+pub use market_data::{BookLevel, MarketDataPort, OrderBookReader, SymbolKey, TradeReader};
+pub use signal_generator::{
+    GeneratorConfig, ParamsError, SignalGeneratorFactory, SignalGeneratorPort,
 };
-pub use market_data::{BookLevel, MarketDataPort, OrderBookReader, SymbolKey};
-pub use signal_generator::{GeneratorConfig, SignalGeneratorFactory, SignalGeneratorPort};
 pub use signal_publisher::{PublishError, SignalChannelFactory, SignalPublisher, SignalSubscriber};
+pub use training_log::{TrainingLogError, TrainingLogPort, TrainingSample};
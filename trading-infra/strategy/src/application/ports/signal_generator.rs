@@ -3,6 +3,7 @@
 //! Defines the interface for signal generation strategies.
 //! Strategies implement this port and run on dedicated threads.
 
+use crate::application::ports::clock::Clock;
 use crate::application::ports::market_data::{MarketDataPort, SymbolKey};
 use crate::domain::{Signal, StrategyId, StrategyType};
 
@@ -39,6 +40,28 @@ impl GeneratorConfig {
     }
 }
 
+/// Error applying reloaded strategy parameters
+#[derive(Debug, Clone)]
+pub struct ParamsError {
+    pub message: String,
+}
+
+impl ParamsError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ParamsError: {}", self.message)
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
 /// Port for signal generation strategies
 ///
 /// This is the core abstraction for trading strategies. Implementations:
@@ -58,7 +81,14 @@ pub trait SignalGeneratorPort: Send + 'static {
     /// 2. Extract features / analyze data
     /// 3. Generate signals based on strategy logic
     /// 4. Return signals (empty vec if no signal)
-    fn on_tick<M: MarketDataPort>(&mut self, market_data: &M) -> Vec<Signal>;
+    ///
+    /// Time (cooldowns, signal timestamps) must come from `clock`, never
+    /// from the system, so replayed and simulated runs are reproducible.
+    fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+        &mut self,
+        market_data: &M,
+        clock: &C,
+    ) -> Vec<Signal>;
 
     /// Get strategy name (convenience method)
     fn name(&self) -> &str {
@@ -75,6 +105,18 @@ pub trait SignalGeneratorPort: Send + 'static {
 
     /// Called when strategy is stopping
     fn on_stop(&mut self) {}
+
+    /// Replace strategy parameters between ticks
+    ///
+    /// `params` is the strategy's section of the configuration. Rolling
+    /// state must survive the update. Strategies without tunable
+    /// parameters reject it.
+    fn update_params(&mut self, _params: &serde_json::Value) -> Result<(), ParamsError> {
+        Err(ParamsError::new(format!(
+            "strategy '{}' does not support parameter reload",
+            self.name()
+        )))
+    }
 }
 
 /// Factory trait for creating signal generators
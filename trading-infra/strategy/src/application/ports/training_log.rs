@@ -0,0 +1,60 @@
+//! Training Log Port - Where scored feature vectors and outcomes go
+//!
+//! Models are trained offline on what the strategy saw and what happened
+//! next. This port abstracts where those samples are written (files,
+//! queues, memory for tests).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A feature vector with its score and the return that followed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingSample {
+    /// Symbol key, `exchange:symbol`
+    pub symbol: String,
+    /// When the features were extracted (nanoseconds)
+    pub timestamp_ns: u64,
+    /// How far ahead the outcome was measured (nanoseconds)
+    pub horizon_ns: u64,
+    /// Model that scored the features
+    pub model_version: String,
+    /// Extracted features
+    pub features: HashMap<String, f64>,
+    /// Model score, if the model could score the features
+    pub score: Option<f64>,
+    /// Mid price return over the horizon (bps)
+    pub outcome_bps: f64,
+}
+
+/// Error type for training log failures
+#[derive(Debug, Clone)]
+pub struct TrainingLogError {
+    pub message: String,
+}
+
+impl TrainingLogError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for TrainingLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TrainingLogError: {}", self.message)
+    }
+}
+
+impl std::error::Error for TrainingLogError {}
+
+/// Port for recording training samples
+pub trait TrainingLogPort: Send {
+    /// Record one sample
+    fn record(&mut self, sample: &TrainingSample) -> Result<(), TrainingLogError>;
+
+    /// Make recorded samples durable
+    fn flush(&mut self) -> Result<(), TrainingLogError> {
+        Ok(())
+    }
+}
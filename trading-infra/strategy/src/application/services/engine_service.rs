@@ -3,15 +3,21 @@
 //! This service coordinates signal generation without depending on
 //! concrete implementations. Uses dependency injection through ports.
 
-use crate::application::ports::{MarketDataPort, SignalGeneratorPort, SignalPublisher};
+use crate::application::ports::{Clock, MarketDataPort, SignalGeneratorPort, SignalPublisher};
 use crate::domain::Signal;
+use arc_swap::ArcSwapOption;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info};
 
+/// Longest real-time wait before the loop re-checks shutdown
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Configuration for the signal engine service
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EngineServiceConfig {
     /// Tick interval in milliseconds
     pub tick_interval_ms: u64,
@@ -31,6 +37,39 @@ impl Default for EngineServiceConfig {
     }
 }
 
+/// Pending parameter update for a running generator
+///
+/// Cloned handles share one slot: the latest push wins and the engine
+/// takes it before the next tick, so the generator is never touched from
+/// another thread. Parameters the generator accepts are reported back
+/// through `applied`; rejected ones leave it unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct ParamUpdates {
+    pending: Arc<ArcSwapOption<serde_json::Value>>,
+    applied: Arc<ArcSwapOption<serde_json::Value>>,
+}
+
+impl ParamUpdates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue parameters, replacing any not yet applied
+    pub fn push(&self, params: serde_json::Value) {
+        self.pending.store(Some(Arc::new(params)));
+    }
+
+    /// Take the pending parameters, if any
+    pub fn take(&self) -> Option<Arc<serde_json::Value>> {
+        self.pending.swap(None)
+    }
+
+    /// Parameters the generator last accepted; `None` until an update succeeds
+    pub fn applied(&self) -> Option<Arc<serde_json::Value>> {
+        self.applied.load_full()
+    }
+}
+
 /// Signal generation engine use case
 ///
 /// Orchestrates the signal generation loop:
@@ -48,23 +87,51 @@ impl EngineService {
     /// Run a signal generator in a loop
     ///
     /// This is the core loop that:
-    /// - Ticks at the configured interval
+    /// - Ticks at the configured interval of `clock` time
     /// - Calls the strategy's on_tick method
     /// - Publishes generated signals
     /// - Logs periodic statistics
-    pub fn run_generator<G, M, P>(
+    pub fn run_generator<G, M, P, C>(
+        generator: G,
+        market_data: Arc<M>,
+        publisher: P,
+        clock: C,
+        config: EngineServiceConfig,
+        shutdown: Arc<AtomicBool>,
+    ) where
+        G: SignalGeneratorPort,
+        M: MarketDataPort,
+        P: SignalPublisher,
+        C: Clock,
+    {
+        Self::run_generator_with_updates(
+            generator,
+            market_data,
+            publisher,
+            clock,
+            config,
+            shutdown,
+            ParamUpdates::new(),
+        );
+    }
+
+    /// Run a signal generator, applying parameter updates between ticks
+    pub fn run_generator_with_updates<G, M, P, C>(
         mut generator: G,
         market_data: Arc<M>,
         publisher: P,
+        clock: C,
         config: EngineServiceConfig,
         shutdown: Arc<AtomicBool>,
+        updates: ParamUpdates,
     ) where
         G: SignalGeneratorPort,
         M: MarketDataPort,
         P: SignalPublisher,
+        C: Clock,
     {
         let strategy_name = generator.name().to_string();
-        let tick_interval = Duration::from_millis(config.tick_interval_ms);
+        let tick_interval_ns = config.tick_interval_ms * 1_000_000;
 
         info!(
             "Starting signal generator '{}' with {}ms tick interval",
@@ -76,12 +143,27 @@ impl EngineService {
         let mut last_stats_time = Instant::now();
         let mut tick_count: u64 = 0;
         let mut signal_count: u64 = 0;
+        let mut next_tick_ns = clock.now_ns();
 
         while !shutdown.load(Ordering::Relaxed) {
-            let tick_start = Instant::now();
+            // An event clock may not move for a while; keep checking shutdown
+            if !clock.wait_until(next_tick_ns, SHUTDOWN_CHECK_INTERVAL) {
+                continue;
+            }
+            let tick_ns = clock.now_ns();
+
+            if let Some(params) = updates.take() {
+                match generator.update_params(&params) {
+                    Ok(()) => {
+                        info!("Generator '{}' reloaded parameters", strategy_name);
+                        updates.applied.store(Some(params));
+                    }
+                    Err(e) => error!("Generator '{}' kept parameters: {}", strategy_name, e),
+                }
+            }
 
             // Generate signals
-            let signals = generator.on_tick(market_data.as_ref());
+            let signals = generator.on_tick(market_data.as_ref(), &clock);
             tick_count += 1;
 
             // Publish signals
@@ -104,10 +186,10 @@ impl EngineService {
                 last_stats_time = Instant::now();
             }
 
-            // Maintain tick rate
-            let elapsed = tick_start.elapsed();
-            if elapsed < tick_interval {
-                std::thread::sleep(tick_interval - elapsed);
+            // Maintain tick rate; ticks missed while behind are skipped
+            next_tick_ns += tick_interval_ns;
+            if next_tick_ns <= tick_ns {
+                next_tick_ns = tick_ns + tick_interval_ns;
             }
         }
 
@@ -119,13 +201,19 @@ impl EngineService {
     }
 
     /// Process a single tick (useful for testing)
-    pub fn tick<G, M, P>(generator: &mut G, market_data: &M, publisher: &P) -> Vec<Signal>
+    pub fn tick<G, M, P, C>(
+        generator: &mut G,
+        market_data: &M,
+        publisher: &P,
+        clock: &C,
+    ) -> Vec<Signal>
     where
         G: SignalGeneratorPort,
         M: MarketDataPort,
         P: SignalPublisher,
+        C: Clock + ?Sized,
     {
-        let signals = generator.on_tick(market_data);
+        let signals = generator.on_tick(market_data, clock);
 
         for signal in &signals {
             if let Err(e) = publisher.publish(signal.clone()) {
@@ -141,9 +229,10 @@ impl EngineService {
 mod tests {
     use super::*;
     use crate::application::ports::{
-        BookLevel, GeneratorConfig, OrderBookReader, PublishError, SymbolKey,
+        BookLevel, GeneratorConfig, OrderBookReader, ParamsError, PublishError, SymbolKey,
     };
     use crate::domain::{Signal, SignalDirection, StrategyId, StrategyType};
+    use crate::infrastructure::EventClock;
     use std::collections::HashMap;
     use std::sync::Mutex;
     use trading_core::{Price, Quantity};
@@ -230,6 +319,7 @@ mod tests {
     struct MockGenerator {
         config: GeneratorConfig,
         tick_count: u64,
+        params: Arc<Mutex<Vec<serde_json::Value>>>,
     }
 
     impl SignalGeneratorPort for MockGenerator {
@@ -237,7 +327,11 @@ mod tests {
             &self.config
         }
 
-        fn on_tick<M: MarketDataPort>(&mut self, market_data: &M) -> Vec<Signal> {
+        fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+            &mut self,
+            market_data: &M,
+            clock: &C,
+        ) -> Vec<Signal> {
             self.tick_count += 1;
 
             let key = &self.config.symbols[0];
@@ -256,12 +350,21 @@ mod tests {
                     .strength(0.5)
                     .confidence(0.8)
                     .prices(mid, mid)
+                    .timestamp_ms(clock.now_ms())
                     .build(),
                 ];
             }
 
             vec![]
         }
+
+        fn update_params(&mut self, params: &serde_json::Value) -> Result<(), ParamsError> {
+            if params.get("threshold").is_some_and(|t| !t.is_number()) {
+                return Err(ParamsError::new("threshold must be a number"));
+            }
+            self.params.lock().unwrap().push(params.clone());
+            Ok(())
+        }
     }
 
     #[test]
@@ -289,11 +392,185 @@ mod tests {
                 vec![key],
             ),
             tick_count: 0,
+            params: Arc::new(Mutex::new(Vec::new())),
         };
 
-        let result = EngineService::tick(&mut generator, &market_data, &publisher);
+        let clock = EventClock::new(5_000_000);
+        let result = EngineService::tick(&mut generator, &market_data, &publisher, &clock);
 
         assert_eq!(result.len(), 1);
+        assert_eq!(result[0].timestamp_ms, 5);
         assert_eq!(signals.lock().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_params_applied_between_ticks() {
+        let market_data = Arc::new(MockMarketData {
+            books: HashMap::new(),
+        });
+        let params = Arc::new(Mutex::new(Vec::new()));
+        let generator = MockGenerator {
+            config: GeneratorConfig::new(
+                StrategyId::new("test"),
+                StrategyType::MeanReversion,
+                vec![SymbolKey::new("test", "BTCUSDT")],
+            ),
+            tick_count: 0,
+            params: params.clone(),
+        };
+        let publisher = MockPublisher {
+            signals: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        // Only the latest of two queued updates is applied
+        let updates = ParamUpdates::new();
+        updates.push(serde_json::json!({ "threshold": 1 }));
+        updates.push(serde_json::json!({ "threshold": 2 }));
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let updates = updates.clone();
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || {
+                EngineService::run_generator_with_updates(
+                    generator,
+                    market_data,
+                    publisher,
+                    EventClock::new(0),
+                    EngineServiceConfig {
+                        tick_interval_ms: 1,
+                        ..Default::default()
+                    },
+                    shutdown,
+                    updates,
+                )
+            })
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        shutdown.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert_eq!(
+            *params.lock().unwrap(),
+            vec![serde_json::json!({ "threshold": 2 })]
+        );
+        assert!(updates.take().is_none());
+        assert_eq!(
+            updates.applied().as_deref(),
+            Some(&serde_json::json!({ "threshold": 2 }))
+        );
+    }
+
+    #[test]
+    fn test_rejected_params_not_reported_applied() {
+        let params = Arc::new(Mutex::new(Vec::new()));
+        let generator = MockGenerator {
+            config: GeneratorConfig::new(
+                StrategyId::new("test"),
+                StrategyType::MeanReversion,
+                vec![SymbolKey::new("test", "BTCUSDT")],
+            ),
+            tick_count: 0,
+            params: params.clone(),
+        };
+        let updates = ParamUpdates::new();
+        updates.push(serde_json::json!({ "threshold": "x" }));
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let updates = updates.clone();
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || {
+                EngineService::run_generator_with_updates(
+                    generator,
+                    Arc::new(MockMarketData {
+                        books: HashMap::new(),
+                    }),
+                    MockPublisher {
+                        signals: Arc::new(Mutex::new(Vec::new())),
+                    },
+                    EventClock::new(0),
+                    EngineServiceConfig::default(),
+                    shutdown,
+                    updates,
+                )
+            })
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        shutdown.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert!(updates.take().is_none());
+        assert!(params.lock().unwrap().is_empty());
+        assert!(updates.applied().is_none());
+    }
+
+    #[test]
+    fn test_ticks_follow_event_clock() {
+        let key = SymbolKey::new("test", "BTCUSDT");
+        let mut books = HashMap::new();
+        books.insert(
+            key.to_string(),
+            Arc::new(MockOrderBook {
+                mid: Price::from_int(100),
+            }),
+        );
+        let signals = Arc::new(Mutex::new(Vec::new()));
+        let generator = MockGenerator {
+            config: GeneratorConfig::new(
+                StrategyId::new("test"),
+                StrategyType::MeanReversion,
+                vec![key],
+            ),
+            tick_count: 0,
+            params: Arc::new(Mutex::new(Vec::new())),
+        };
+        let publisher = MockPublisher {
+            signals: signals.clone(),
+        };
+
+        let clock = EventClock::new(0);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let clock = clock.clone();
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || {
+                EngineService::run_generator(
+                    generator,
+                    Arc::new(MockMarketData { books }),
+                    publisher,
+                    clock,
+                    EngineServiceConfig {
+                        tick_interval_ms: 10,
+                        ..Default::default()
+                    },
+                    shutdown,
+                )
+            })
+        };
+
+        // Ticks happen only as event time reaches each tick boundary
+        let timestamps = || -> Vec<u64> {
+            std::thread::sleep(Duration::from_millis(30));
+            signals
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|s| s.timestamp_ms)
+                .collect()
+        };
+        assert_eq!(timestamps(), vec![0]);
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(timestamps(), vec![0]);
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(timestamps(), vec![0, 10]);
+        // A jump runs one tick, not one per missed interval
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(timestamps(), vec![0, 10, 1010]);
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(timestamps(), vec![0, 10, 1010, 1020]);
+
+        shutdown.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+    }
 }
@@ -4,5 +4,7 @@
 //! coordinating ports and domain entities.
 
 mod engine_service;
+mod model_scorer;
 
-pub use engine_service::{EngineService, EngineServiceConfig};
+pub use engine_service::{EngineService, EngineServiceConfig, ParamUpdates};
+pub use model_scorer::{ModelHandle, ModelScore, ModelScorer};
@@ -0,0 +1,363 @@
+//! Model Scorer Service - Online inference over extracted features
+//!
+//! Runs a symbol's book (and optionally trades) through the feature
+//! extractors and scores the result with the current model. The model sits
+//! behind a `ModelHandle`, so a new one can be swapped in between ticks
+//! without restarting the strategy.
+//!
+//! With a training log attached, one sample per horizon per symbol is held
+//! until the horizon has passed, then written with the mid return that
+//! followed.
+
+use crate::application::ports::{
+    FeatureExtractionPipeline, OrderBookReader, SymbolKey, TradeFeatureExtractorPort, TradeReader,
+    TrainingLogPort, TrainingSample,
+};
+use crate::domain::{Model, ModelError, ModelTarget};
+use arc_swap::ArcSwap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Shared slot holding the model currently in use
+///
+/// Cloned handles share the slot; readers see a swap on their next load.
+#[derive(Debug, Clone)]
+pub struct ModelHandle {
+    current: Arc<ArcSwap<Model>>,
+}
+
+impl ModelHandle {
+    pub fn new(model: Model) -> Self {
+        Self {
+            current: Arc::new(ArcSwap::from_pointee(model)),
+        }
+    }
+
+    /// The model in use
+    pub fn load(&self) -> Arc<Model> {
+        self.current.load_full()
+    }
+
+    /// Replace the model, returning the previous one
+    pub fn store(&self, model: Model) -> Arc<Model> {
+        self.current.swap(Arc::new(model))
+    }
+}
+
+/// A scored feature vector
+#[derive(Debug, Clone)]
+pub struct ModelScore {
+    pub value: f64,
+    pub target: ModelTarget,
+    pub model_version: String,
+    /// Everything the extractors produced
+    pub features: HashMap<String, f64>,
+}
+
+/// A sample waiting for its outcome
+#[derive(Debug, Clone)]
+struct PendingSample {
+    timestamp_ns: u64,
+    mid: f64,
+    model_version: String,
+    features: HashMap<String, f64>,
+    score: Option<f64>,
+}
+
+/// Training log and the samples waiting on it
+struct TrainingCapture {
+    log: Box<dyn TrainingLogPort>,
+    horizon_ns: u64,
+    pending: HashMap<SymbolKey, VecDeque<PendingSample>>,
+}
+
+impl TrainingCapture {
+    /// Write every sample of `key` whose horizon has passed
+    fn resolve(&mut self, key: &SymbolKey, mid: f64, now_ns: u64) {
+        let Some(pending) = self.pending.get_mut(key) else {
+            return;
+        };
+        while let Some(sample) = pending.front() {
+            if now_ns.saturating_sub(sample.timestamp_ns) < self.horizon_ns {
+                break;
+            }
+            let Some(sample) = pending.pop_front() else {
+                break;
+            };
+            let record = TrainingSample {
+                symbol: key.to_string(),
+                timestamp_ns: sample.timestamp_ns,
+                horizon_ns: self.horizon_ns,
+                model_version: sample.model_version,
+                features: sample.features,
+                score: sample.score,
+                outcome_bps: (mid / sample.mid - 1.0) * 10_000.0,
+            };
+            if let Err(e) = self.log.record(&record) {
+                warn!("Dropping training sample for {}: {}", key, e);
+            }
+        }
+    }
+
+    /// Hold a sample unless one from the last horizon is already waiting
+    fn capture(&mut self, key: &SymbolKey, sample: PendingSample) {
+        let pending = self.pending.entry(key.clone()).or_default();
+        let due = pending.back().is_none_or(|last| {
+            sample.timestamp_ns.saturating_sub(last.timestamp_ns) >= self.horizon_ns
+        });
+        if due {
+            pending.push_back(sample);
+        }
+    }
+}
+
+/// Feature extraction plus scoring with a hot-swappable model
+pub struct ModelScorer {
+    pipeline: FeatureExtractionPipeline,
+    trade_extractor: Option<Arc<dyn TradeFeatureExtractorPort>>,
+    model: ModelHandle,
+    training: Option<TrainingCapture>,
+}
+
+impl ModelScorer {
+    /// Create a scorer, checking the extractors produce the model's inputs
+    pub fn new(pipeline: FeatureExtractionPipeline, model: Model) -> Result<Self, ModelError> {
+        Self::with_extractors(pipeline, None, model)
+    }
+
+    /// Create a scorer that also extracts trade features
+    pub fn with_extractors(
+        pipeline: FeatureExtractionPipeline,
+        trade_extractor: Option<Arc<dyn TradeFeatureExtractorPort>>,
+        model: Model,
+    ) -> Result<Self, ModelError> {
+        let scorer = Self {
+            pipeline,
+            trade_extractor,
+            model: ModelHandle::new(model),
+            training: None,
+        };
+        scorer.model.load().check_inputs(&scorer.feature_names())?;
+        Ok(scorer)
+    }
+
+    /// Record features and outcomes `horizon_ns` later to `log`
+    pub fn with_training_log(mut self, log: Box<dyn TrainingLogPort>, horizon_ns: u64) -> Self {
+        self.training = Some(TrainingCapture {
+            log,
+            horizon_ns: horizon_ns.max(1),
+            pending: HashMap::new(),
+        });
+        self
+    }
+
+    /// Handle to the model slot
+    pub fn model(&self) -> &ModelHandle {
+        &self.model
+    }
+
+    /// Every feature the extractors produce
+    pub fn feature_names(&self) -> Vec<&'static str> {
+        let mut names = self.pipeline.all_feature_names();
+        if let Some(extractor) = &self.trade_extractor {
+            names.extend(extractor.feature_names());
+        }
+        names
+    }
+
+    /// Swap in a new model if the extractors produce its inputs
+    ///
+    /// On error the current model stays in use.
+    pub fn swap_model(&self, model: Model) -> Result<Arc<Model>, ModelError> {
+        model.check_inputs(&self.feature_names())?;
+        Ok(self.model.store(model))
+    }
+
+    /// Extract features for a symbol and score them
+    ///
+    /// `None` when the model cannot score the features (e.g. an input a
+    /// linear model needs is absent from this book).
+    pub fn score(
+        &mut self,
+        key: &SymbolKey,
+        book: &dyn OrderBookReader,
+        trades: Option<&dyn TradeReader>,
+        now_ns: u64,
+    ) -> Option<ModelScore> {
+        let mut features = self.pipeline.extract(book).to_f64_map();
+        if let (Some(extractor), Some(trades)) = (&self.trade_extractor, trades) {
+            features.extend(extractor.extract(trades).to_f64_map());
+        }
+        features.retain(|_, value| value.is_finite());
+
+        let model = self.model.load();
+        let value = model.score_features(&features);
+
+        let mid = book.mid_price().map(|p| p.to_f64()).filter(|m| *m > 0.0);
+        if let (Some(training), Some(mid)) = (&mut self.training, mid) {
+            training.resolve(key, mid, now_ns);
+            training.capture(
+                key,
+                PendingSample {
+                    timestamp_ns: now_ns,
+                    mid,
+                    model_version: model.version().to_string(),
+                    features: features.clone(),
+                    score: value,
+                },
+            );
+        }
+
+        Some(ModelScore {
+            value: value?,
+            target: model.target(),
+            model_version: model.version().to_string(),
+            features,
+        })
+    }
+
+    /// Flush the training log, if any
+    pub fn flush(&mut self) {
+        if let Some(training) = &mut self.training
+            && let Err(e) = training.log.flush()
+        {
+            warn!("Failed to flush training log: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{BookLevel, FeatureExtractorPort, TrainingLogError};
+    use crate::domain::Features;
+    use std::sync::Mutex;
+    use trading_core::{Price, Quantity};
+
+    struct MidBook(f64);
+
+    impl OrderBookReader for MidBook {
+        fn is_initialized(&self) -> bool {
+            true
+        }
+        fn best_bid(&self) -> Option<BookLevel> {
+            None
+        }
+        fn best_ask(&self) -> Option<BookLevel> {
+            None
+        }
+        fn mid_price(&self) -> Option<Price> {
+            Some(Price::from_f64(self.0))
+        }
+        fn spread(&self) -> Option<Price> {
+            None
+        }
+        fn bid_levels(&self, _depth: usize) -> Vec<BookLevel> {
+            vec![]
+        }
+        fn ask_levels(&self, _depth: usize) -> Vec<BookLevel> {
+            vec![]
+        }
+        fn total_bid_depth(&self, _levels: usize) -> Quantity {
+            Quantity::ZERO
+        }
+        fn total_ask_depth(&self, _levels: usize) -> Quantity {
+            Quantity::ZERO
+        }
+        fn last_update_time(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    struct MidExtractor;
+
+    impl FeatureExtractorPort for MidExtractor {
+        fn extract(&self, book: &dyn OrderBookReader) -> Features {
+            let mut features = Features::new();
+            if let Some(mid) = book.mid_price() {
+                features.set("mid_price", mid.to_f64());
+            }
+            features
+        }
+
+        fn feature_names(&self) -> Vec<&'static str> {
+            vec!["mid_price"]
+        }
+
+        fn name(&self) -> &'static str {
+            "mid"
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MemoryLog(Arc<Mutex<Vec<TrainingSample>>>);
+
+    impl TrainingLogPort for MemoryLog {
+        fn record(&mut self, sample: &TrainingSample) -> Result<(), TrainingLogError> {
+            self.0.lock().unwrap().push(sample.clone());
+            Ok(())
+        }
+    }
+
+    fn linear(version: &str, feature: &str, weight: f64) -> Model {
+        Model::from_json(&format!(
+            r#"{{ "version": "{}", "features": ["{}"],
+                  "model": {{ "type": "linear", "coefficients": {{ "{}": {} }} }} }}"#,
+            version, feature, feature, weight
+        ))
+        .unwrap()
+    }
+
+    fn pipeline() -> FeatureExtractionPipeline {
+        FeatureExtractionPipeline::new().with_extractor(Arc::new(MidExtractor))
+    }
+
+    #[test]
+    fn test_score_and_swap() {
+        let key = SymbolKey::new("binance", "BTCUSDT");
+        let mut scorer = ModelScorer::new(pipeline(), linear("v1", "mid_price", 0.01)).unwrap();
+
+        let score = scorer.score(&key, &MidBook(100.0), None, 0).unwrap();
+        assert!((score.value - 1.0).abs() < 1e-9);
+        assert_eq!(score.model_version, "v1");
+        assert_eq!(score.features["mid_price"], 100.0);
+
+        // A model needing features the pipeline lacks is refused
+        let err = scorer
+            .swap_model(linear("v2", "imbalance", 1.0))
+            .unwrap_err();
+        assert!(matches!(err, ModelError::MissingInputs(_)));
+        assert!(ModelScorer::new(pipeline(), linear("v2", "imbalance", 1.0)).is_err());
+
+        let old = scorer.swap_model(linear("v3", "mid_price", -0.02)).unwrap();
+        assert_eq!(old.version(), "v1");
+        let score = scorer.score(&key, &MidBook(100.0), None, 0).unwrap();
+        assert!((score.value + 2.0).abs() < 1e-9);
+        assert_eq!(score.model_version, "v3");
+    }
+
+    #[test]
+    fn test_training_samples_get_outcomes() {
+        let key = SymbolKey::new("binance", "BTCUSDT");
+        let log = MemoryLog::default();
+        let mut scorer = ModelScorer::new(pipeline(), linear("v1", "mid_price", 0.01))
+            .unwrap()
+            .with_training_log(Box::new(log.clone()), 1_000);
+
+        scorer.score(&key, &MidBook(100.0), None, 0);
+        // Inside the horizon: no new sample, nothing resolved
+        scorer.score(&key, &MidBook(100.5), None, 500);
+        assert!(log.0.lock().unwrap().is_empty());
+
+        scorer.score(&key, &MidBook(101.0), None, 1_000);
+        let samples = log.0.lock().unwrap().clone();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].symbol, "binance:BTCUSDT");
+        assert_eq!(samples[0].timestamp_ns, 0);
+        assert_eq!(samples[0].model_version, "v1");
+        assert_eq!(samples[0].features["mid_price"], 100.0);
+        assert!((samples[0].score.unwrap() - 1.0).abs() < 1e-9);
+        assert!((samples[0].outcome_bps - 100.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+
+use super::types::{StrategyConfigFile, StrategyInstanceConfig};
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to parse config: {0}")]
+    ParseError(#[from] serde_json::Error),
+    #[error("No enabled strategies in config")]
+    NoEnabledStrategies,
+    #[error("Duplicate strategy id: {0}")]
+    DuplicateStrategy(String),
+    #[error("Strategy {0} has no symbols")]
+    NoSymbols(String),
+    #[error("Strategy {id} needs {expected} symbols, has {found}")]
+    SymbolCount {
+        id: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Load strategy configuration from a JSON file
+pub fn load_config<P: AsRef<Path>>(path: P) -> Result<StrategyConfigFile, ConfigError> {
+    let content = std::fs::read_to_string(path)?;
+    let config: StrategyConfigFile = serde_json::from_str(&content)?;
+    Ok(config)
+}
+
+/// Load configuration from a JSON string
+pub fn load_config_from_str(json: &str) -> Result<StrategyConfigFile, ConfigError> {
+    let config: StrategyConfigFile = serde_json::from_str(json)?;
+    Ok(config)
+}
+
+/// Load the default embedded configuration
+pub fn load_default_config() -> Result<StrategyConfigFile, ConfigError> {
+    let default_config = include_str!("strategy_config.json");
+    load_config_from_str(default_config)
+}
+
+impl StrategyConfigFile {
+    /// Get only enabled strategies
+    pub fn enabled_strategies(&self) -> Vec<&StrategyInstanceConfig> {
+        self.strategies.iter().filter(|s| s.enabled).collect()
+    }
+
+    /// Get a specific strategy by ID
+    pub fn get_strategy(&self, id: &str) -> Option<&StrategyInstanceConfig> {
+        self.strategies.iter().find(|s| s.id == id)
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut ids = HashSet::new();
+        for strategy in &self.strategies {
+            if !ids.insert(strategy.id.as_str()) {
+                return Err(ConfigError::DuplicateStrategy(strategy.id.clone()));
+            }
+            if strategy.symbols.is_empty() {
+                return Err(ConfigError::NoSymbols(strategy.id.clone()));
+            }
+            if let Some(expected) = strategy.kind.symbol_count()
+                && strategy.symbols.len() != expected
+            {
+                return Err(ConfigError::SymbolCount {
+                    id: strategy.id.clone(),
+                    expected,
+                    found: strategy.symbols.len(),
+                });
+            }
+        }
+        if self.enabled_strategies().is_empty() {
+            return Err(ConfigError::NoEnabledStrategies);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::SymbolKey;
+    use crate::config::StrategyKind;
+    use crate::domain::StrategyType;
+    use crate::infrastructure::{
+        CycleArbitrageConfig, MeanReversionConfig, ModelStrategyConfig, OrderFlowConfig,
+        PairsTradingConfig,
+    };
+    use serde::Deserialize;
+
+    #[test]
+    fn test_load_default_config() {
+        let config = load_default_config().unwrap();
+        config.validate().unwrap();
+
+        let strategy = config.get_strategy("mean_reversion_binance").unwrap();
+        assert_eq!(strategy.kind, StrategyKind::MeanReversionHft);
+        assert_eq!(strategy.symbols.len(), 2);
+
+        let params = MeanReversionConfig::deserialize(&strategy.params).unwrap();
+        assert_eq!(params.rolling_window, 100);
+
+        let pairs = config.get_strategy("pairs_eth_btc").unwrap();
+        assert_eq!(pairs.kind, StrategyKind::PairsTrading);
+        assert!(!pairs.enabled);
+        assert_eq!(
+            pairs.generator_config().strategy_type,
+            StrategyType::StatArb
+        );
+        let params = PairsTradingConfig::deserialize(&pairs.params).unwrap();
+        assert_eq!(params.entry_z, 2.0);
+
+        let arb = config.get_strategy("cycle_arb_binance").unwrap();
+        assert_eq!(arb.kind, StrategyKind::CycleArbitrage);
+        let params = CycleArbitrageConfig::deserialize(&arb.params).unwrap();
+        assert_eq!(params.cycles[0].legs.len(), 3);
+        assert_eq!(params.taker_fee_bps["binance"], 7.5);
+
+        let flow = config.get_strategy("order_flow_btc").unwrap();
+        assert_eq!(flow.kind, StrategyKind::OrderFlow);
+        let params = OrderFlowConfig::deserialize(&flow.params).unwrap();
+        params.validate().unwrap();
+        assert_eq!(params.model.weights["ofi"], 1.5);
+
+        let model = config.get_strategy("model_btc").unwrap();
+        assert_eq!(model.kind, StrategyKind::Model);
+        let params = ModelStrategyConfig::deserialize(&model.params).unwrap();
+        assert_eq!(params.model_path, "models/btc_book.json");
+        assert!(params.training_log_path.is_some());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = load_config_from_str(
+            r#"{
+                "strategies": [{
+                    "id": "mr",
+                    "type": "mean_reversion_hft",
+                    "symbols": [{ "exchange": "binance", "symbol": "BTCUSDT" }]
+                }]
+            }"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.source, "strategy");
+        assert_eq!(config.engine.tick_interval_ms, 10);
+        let generator = config.strategies[0].generator_config();
+        assert_eq!(generator.strategy_id.as_str(), "mr");
+        assert_eq!(generator.min_signal_interval_ms, 100);
+
+        // Missing params run with the strategy's defaults
+        let params = MeanReversionConfig::deserialize(&config.strategies[0].params).unwrap();
+        assert_eq!(
+            params.min_skew_bps,
+            MeanReversionConfig::default().min_skew_bps
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut config = load_default_config().unwrap();
+        config.strategies.push(config.strategies[0].clone());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DuplicateStrategy(_))
+        ));
+
+        config.strategies.truncate(1);
+        config.strategies[0].kind = StrategyKind::PairsTrading;
+        config.strategies[0]
+            .symbols
+            .push(SymbolKey::new("binance", "SOLUSDT"));
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::SymbolCount {
+                expected: 2,
+                found: 3,
+                ..
+            })
+        ));
+
+        config.strategies[0].symbols.clear();
+        assert!(matches!(config.validate(), Err(ConfigError::NoSymbols(_))));
+
+        config.strategies.clear();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::NoEnabledStrategies)
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use transport::{CodecKind, SequencingConfig, TransportConfig};
+
+use crate::application::ports::{GeneratorConfig, SymbolKey};
+use crate::application::services::EngineServiceConfig;
+use crate::domain::{StrategyId, StrategyType};
+
+/// Root configuration for the strategy process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyConfigFile {
+    /// Source name stamped on published frames
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// Tick loop settings shared by every strategy
+    #[serde(default)]
+    pub engine: EngineServiceConfig,
+    pub strategies: Vec<StrategyInstanceConfig>,
+    #[serde(default)]
+    pub transport: StrategyTransportConfig,
+}
+
+/// Transports between the strategy process, gateway and execution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyTransportConfig {
+    /// Inbound deltas, snapshots and trades from the gateway
+    #[serde(default)]
+    pub market_data: TransportConfig,
+    /// Outbound snapshot and retransmit requests to the gateway
+    #[serde(default)]
+    pub snapshot_requests: TransportConfig,
+    /// Outbound signals to execution
+    #[serde(default)]
+    pub signals: TransportConfig,
+    /// Frame format for `signals`
+    #[serde(default)]
+    pub codec: CodecKind,
+    /// Gap recovery on `market_data`
+    #[serde(default)]
+    pub sequencing: SequencingConfig,
+    /// Sleep between market data polls that find nothing (microseconds)
+    #[serde(default = "default_poll_interval_us")]
+    pub poll_interval_us: u64,
+}
+
+impl StrategyTransportConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_micros(self.poll_interval_us)
+    }
+}
+
+/// Strategy implementations the process can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    /// `MeanReversionHFT`, parameters are a `MeanReversionConfig`
+    MeanReversionHft,
+    /// `PairsTrading`, parameters are a `PairsTradingConfig`
+    PairsTrading,
+    /// `CycleArbitrage`, parameters are a `CycleArbitrageConfig`
+    CycleArbitrage,
+    /// `OrderFlowStrategy`, parameters are an `OrderFlowConfig`
+    OrderFlow,
+    /// `ModelStrategy`, parameters are a `ModelStrategyConfig`
+    Model,
+}
+
+impl StrategyKind {
+    /// Strategy classification of the implementation
+    pub fn strategy_type(self) -> StrategyType {
+        match self {
+            StrategyKind::MeanReversionHft => StrategyType::MeanReversion,
+            StrategyKind::PairsTrading => StrategyType::StatArb,
+            StrategyKind::CycleArbitrage => StrategyType::TriangularArb,
+            StrategyKind::OrderFlow => StrategyType::OrderFlow,
+            StrategyKind::Model => StrategyType::Model,
+        }
+    }
+
+    /// Number of symbols the implementation needs, if fixed
+    pub fn symbol_count(self) -> Option<usize> {
+        match self {
+            StrategyKind::MeanReversionHft => None,
+            StrategyKind::PairsTrading => Some(2),
+            StrategyKind::CycleArbitrage => None,
+            StrategyKind::OrderFlow | StrategyKind::Model => None,
+        }
+    }
+}
+
+/// One running strategy instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyInstanceConfig {
+    /// Unique strategy identifier, also its signal topic
+    pub id: String,
+    /// Which implementation to run
+    #[serde(rename = "type")]
+    pub kind: StrategyKind,
+    /// Whether this instance is started
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Symbols the strategy watches
+    pub symbols: Vec<SymbolKey>,
+    /// Minimum interval between signals (milliseconds)
+    #[serde(default = "default_min_signal_interval_ms")]
+    pub min_signal_interval_ms: u64,
+    /// Implementation parameters; reloaded on SIGHUP
+    #[serde(default = "default_params")]
+    pub params: serde_json::Value,
+}
+
+impl StrategyInstanceConfig {
+    /// Generator configuration for this instance
+    pub fn generator_config(&self) -> GeneratorConfig {
+        GeneratorConfig::new(
+            StrategyId::new(self.id.clone()),
+            self.kind.strategy_type(),
+            self.symbols.clone(),
+        )
+        .with_min_interval(self.min_signal_interval_ms)
+    }
+}
+
+fn default_source() -> String {
+    "strategy".to_string()
+}
+
+fn default_poll_interval_us() -> u64 {
+    100
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_min_signal_interval_ms() -> u64 {
+    100
+}
+
+fn default_params() -> serde_json::Value {
+    serde_json::Value::Object(Default::default())
+}
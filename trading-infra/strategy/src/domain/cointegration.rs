@@ -0,0 +1,331 @@
+//! Cointegration statistics for pairs trading
+//!
+//! - `KalmanHedge`: Online estimate of `y = beta * x + alpha`
+//! - `ou_half_life`: Mean reversion half-life of a spread
+//! - `adf_statistic`: Augmented Dickey-Fuller t-statistic of a spread
+//!
+//! Everything here works on plain `f64` series; callers decide what the
+//! series are (prices, log prices) and how often they are sampled.
+
+/// One step of the hedge ratio filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KalmanUpdate {
+    /// `y` minus its prediction from the prior estimate
+    pub forecast_error: f64,
+    /// Variance of the prediction
+    pub forecast_variance: f64,
+    /// Hedge ratio after the update
+    pub hedge_ratio: f64,
+    /// Intercept after the update
+    pub intercept: f64,
+}
+
+impl KalmanUpdate {
+    /// Forecast error in standard deviations
+    pub fn z_score(&self) -> f64 {
+        if self.forecast_variance > 0.0 {
+            self.forecast_error / self.forecast_variance.sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Kalman filter tracking a time-varying hedge ratio and intercept
+///
+/// The state `[beta, alpha]` starts at zero and follows a random walk with
+/// covariance `delta / (1 - delta) * I`; observations `y = beta * x + alpha`
+/// carry noise of variance `observation_variance`. Smaller `delta` gives a
+/// steadier hedge ratio.
+///
+/// State noise on `beta` enters the forecast scaled by `x^2`, so feed
+/// series centred near zero (e.g. log prices less their first value).
+#[derive(Debug, Clone)]
+pub struct KalmanHedge {
+    delta: f64,
+    observation_variance: f64,
+    /// `[beta, alpha]`
+    state: [f64; 2],
+    covariance: [[f64; 2]; 2],
+    updates: u64,
+}
+
+impl KalmanHedge {
+    pub fn new(delta: f64, observation_variance: f64) -> Self {
+        Self {
+            delta,
+            observation_variance,
+            state: [0.0; 2],
+            covariance: [[1.0, 0.0], [0.0, 1.0]],
+            updates: 0,
+        }
+    }
+
+    /// Change the noise settings, keeping the current estimate
+    pub fn set_noise(&mut self, delta: f64, observation_variance: f64) {
+        self.delta = delta;
+        self.observation_variance = observation_variance;
+    }
+
+    pub fn hedge_ratio(&self) -> f64 {
+        self.state[0]
+    }
+
+    pub fn intercept(&self) -> f64 {
+        self.state[1]
+    }
+
+    /// Observations taken so far
+    pub fn updates(&self) -> u64 {
+        self.updates
+    }
+
+    /// `y` less its fitted value at the current estimate
+    pub fn spread(&self, x: f64, y: f64) -> f64 {
+        y - self.state[0] * x - self.state[1]
+    }
+
+    /// Take one observation
+    pub fn update(&mut self, x: f64, y: f64) -> KalmanUpdate {
+        self.updates += 1;
+
+        // Predict: state unchanged, covariance grows by the state noise
+        let w = self.delta / (1.0 - self.delta);
+        let mut r = self.covariance;
+        r[0][0] += w;
+        r[1][1] += w;
+
+        // Observation vector h = [x, 1]
+        let h = [x, 1.0];
+        let rh = [
+            r[0][0] * h[0] + r[0][1] * h[1],
+            r[1][0] * h[0] + r[1][1] * h[1],
+        ];
+        let forecast_variance = h[0] * rh[0] + h[1] * rh[1] + self.observation_variance;
+        let forecast_error = y - (self.state[0] * h[0] + self.state[1] * h[1]);
+
+        if forecast_variance > 0.0 {
+            let gain = [rh[0] / forecast_variance, rh[1] / forecast_variance];
+            self.state[0] += gain[0] * forecast_error;
+            self.state[1] += gain[1] * forecast_error;
+            // P = R - K h' R
+            for (i, row) in self.covariance.iter_mut().enumerate() {
+                for (j, cell) in row.iter_mut().enumerate() {
+                    *cell = r[i][j] - gain[i] * rh[j];
+                }
+            }
+        }
+
+        KalmanUpdate {
+            forecast_error,
+            forecast_variance,
+            hedge_ratio: self.state[0],
+            intercept: self.state[1],
+        }
+    }
+}
+
+/// Half-life of mean reversion, in samples
+///
+/// Fits `s[t] - s[t-1] = a + b * s[t-1]`, the discrete Ornstein-Uhlenbeck
+/// process, and returns `-ln 2 / ln(1 + b)`. `None` when the spread does
+/// not revert (`b >= 0`) or the series is too short.
+pub fn ou_half_life(spread: &[f64]) -> Option<f64> {
+    if spread.len() < 3 {
+        return None;
+    }
+    let rows: Vec<Vec<f64>> = spread[..spread.len() - 1]
+        .iter()
+        .map(|&s| vec![1.0, s])
+        .collect();
+    let diffs: Vec<f64> = spread.windows(2).map(|w| w[1] - w[0]).collect();
+    let fit = ols(&rows, &diffs)?;
+    let b = fit.coefficients[1];
+    if b >= 0.0 || b <= -1.0 {
+        return None;
+    }
+    Some(-std::f64::consts::LN_2 / (1.0 + b).ln())
+}
+
+/// Augmented Dickey-Fuller t-statistic with a constant and `lags` lagged
+/// differences
+///
+/// Fits `ds[t] = a + b * s[t-1] + sum(c_i * ds[t-i])` and returns the
+/// t-statistic of `b`. More negative means more evidence the spread is
+/// stationary; with a constant the 5% critical value is about -2.86.
+pub fn adf_statistic(spread: &[f64], lags: usize) -> Option<f64> {
+    let diffs: Vec<f64> = spread.windows(2).map(|w| w[1] - w[0]).collect();
+    // diffs[t - 1] = s[t] - s[t-1]; the first usable t has `lags` earlier diffs
+    let mut rows = Vec::new();
+    let mut targets = Vec::new();
+    for t in (lags + 1)..spread.len() {
+        let mut row = Vec::with_capacity(lags + 2);
+        row.push(1.0);
+        row.push(spread[t - 1]);
+        for i in 1..=lags {
+            row.push(diffs[t - 1 - i]);
+        }
+        rows.push(row);
+        targets.push(diffs[t - 1]);
+    }
+    let fit = ols(&rows, &targets)?;
+    let se = fit.std_errors[1];
+    (se > 0.0).then(|| fit.coefficients[1] / se)
+}
+
+/// Least-squares coefficients and their standard errors
+struct OlsFit {
+    coefficients: Vec<f64>,
+    std_errors: Vec<f64>,
+}
+
+/// Ordinary least squares through the normal equations
+///
+/// `None` when there are no more observations than regressors or the
+/// regressors are collinear.
+fn ols(rows: &[Vec<f64>], targets: &[f64]) -> Option<OlsFit> {
+    let k = rows.first()?.len();
+    let n = rows.len();
+    if n <= k {
+        return None;
+    }
+
+    let mut xtx = vec![vec![0.0; k]; k];
+    let mut xty = vec![0.0; k];
+    for (row, &y) in rows.iter().zip(targets) {
+        for i in 0..k {
+            xty[i] += row[i] * y;
+            for j in 0..k {
+                xtx[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let inverse = invert(xtx)?;
+    let coefficients: Vec<f64> = (0..k)
+        .map(|i| (0..k).map(|j| inverse[i][j] * xty[j]).sum())
+        .collect();
+
+    let rss: f64 = rows
+        .iter()
+        .zip(targets)
+        .map(|(row, &y)| {
+            let fitted: f64 = row.iter().zip(&coefficients).map(|(x, c)| x * c).sum();
+            (y - fitted).powi(2)
+        })
+        .sum();
+    let sigma2 = rss / (n - k) as f64;
+    let std_errors = (0..k)
+        .map(|i| (sigma2 * inverse[i][i]).max(0.0).sqrt())
+        .collect();
+
+    Some(OlsFit {
+        coefficients,
+        std_errors,
+    })
+}
+
+/// Gauss-Jordan inverse with partial pivoting
+fn invert(mut a: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut inverse: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        inverse.swap(col, pivot);
+
+        let scale = a[col][col];
+        for j in 0..n {
+            a[col][j] /= scale;
+            inverse[col][j] /= scale;
+        }
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let factor = a[row][col];
+            if factor == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                a[row][j] -= factor * a[col][j];
+                inverse[row][j] -= factor * inverse[col][j];
+            }
+        }
+    }
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic standard-ish noise in [-1, 1)
+    struct Noise(u64);
+
+    impl Noise {
+        fn next(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+        }
+    }
+
+    fn ar1(phi: f64, n: usize, seed: u64) -> Vec<f64> {
+        let mut noise = Noise(seed);
+        let mut s = 0.0;
+        (0..n)
+            .map(|_| {
+                s = phi * s + noise.next();
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kalman_tracks_hedge_ratio() {
+        let mut noise = Noise(7);
+        let mut kalman = KalmanHedge::new(1e-4, 1e-3);
+        let mut x = 0.0;
+        for _ in 0..2_000 {
+            x += noise.next();
+            let y = 1.5 * x + 10.0 + 0.05 * noise.next();
+            kalman.update(x, y);
+        }
+        assert!((kalman.hedge_ratio() - 1.5).abs() < 0.02);
+        assert!((kalman.intercept() - 10.0).abs() < 0.5);
+        assert!(kalman.spread(x, 1.5 * x + 10.0).abs() < 0.5);
+
+        let update = kalman.update(x, 1.5 * x + 10.0);
+        assert!(update.forecast_variance > 0.0);
+        assert!(update.z_score().abs() < 3.0);
+    }
+
+    #[test]
+    fn test_half_life_of_ar1() {
+        // phi = 0.9 reverts with half-life ln 2 / -ln 0.9 = 6.58 samples
+        let half_life = ou_half_life(&ar1(0.9, 5_000, 3)).unwrap();
+        assert!((half_life - 6.58).abs() < 1.0, "half-life {}", half_life);
+
+        // A random walk does not revert
+        let walk: Vec<f64> = ar1(1.0, 500, 5).iter().map(|s| s + 1_000.0).collect();
+        assert!(ou_half_life(&walk).is_none_or(|h| h > 100.0));
+        assert!(ou_half_life(&[1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_adf_separates_stationary_from_random_walk() {
+        let stationary = adf_statistic(&ar1(0.5, 500, 11), 1).unwrap();
+        let walk = adf_statistic(&ar1(1.0, 500, 11), 1).unwrap();
+        assert!(stationary < -2.86, "stationary ADF {}", stationary);
+        assert!(walk > -2.86, "random walk ADF {}", walk);
+
+        assert!(adf_statistic(&[1.0, 2.0, 3.0], 1).is_none());
+    }
+}
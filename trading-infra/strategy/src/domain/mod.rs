@@ -4,17 +4,33 @@
 //! All types here are framework-agnostic and represent core trading concepts.
 
 mod calculations;
+mod cointegration;
 mod direction;
 mod features;
+mod model;
 pub mod order_book;
+mod order_flow;
 mod signal;
 mod strategy;
+pub mod trade_tape;
 mod value_objects;
 
 pub use calculations::{Calculations, Imbalance, Microprice, Spread, Vwap};
+pub use cointegration::{KalmanHedge, KalmanUpdate, adf_statistic, ou_half_life};
 pub use direction::SignalDirection;
 pub use features::{Features, Leg, Urgency};
+pub use model::{
+    Link, Model, ModelError, ModelKindSpec, ModelSpec, ModelTarget, TreeNodeSpec, TreeSpec,
+    sign_confidence,
+};
 pub use order_book::{OrderBookManager, SharedOrderBook};
+pub use order_flow::{
+    BookDepth, BookFlow, BookFlowConfig, FlowEvent, FlowLog, QueueDepletion, multi_level_ofi,
+};
 pub use signal::{Signal, SignalBuilder, SignalId};
 pub use strategy::{StrategyId, StrategyType};
+pub use trade_tape::{
+    AggressorSide, AggressorVolume, SharedTradeTape, TradePrint, TradeTapeConfig, TradeTapeManager,
+    VolumeBucket,
+};
 pub use value_objects::{BasisPoints, Confidence, Ratio, Strength, Volatility, ZScore};
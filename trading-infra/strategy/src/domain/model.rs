@@ -0,0 +1,532 @@
+//! Scoring models over named features
+//!
+//! Trained models are loaded from a JSON file:
+//!
+//! ```json
+//! {
+//!   "version": "book_gbdt_2026_10_01",
+//!   "target": "return_bps",
+//!   "features": ["imbalance", "spread_bps"],
+//!   "model": {
+//!     "type": "tree_ensemble",
+//!     "base_score": 0.0,
+//!     "trees": [
+//!       { "nodes": [
+//!         { "feature": "imbalance", "threshold": 0.2, "left": 1, "right": 2 },
+//!         { "leaf": -0.4 },
+//!         { "leaf": 0.9 }
+//!       ] }
+//!     ]
+//!   }
+//! }
+//! ```
+//!
+//! `model` is one of:
+//! - `linear`: `intercept` plus `coefficients` by feature name
+//! - `logistic`: the same, passed through the logistic function
+//! - `tree_ensemble`: `base_score` plus the sum of each tree's leaf, then
+//!   `link` (`identity` or `logistic`). A split sends inputs below
+//!   `threshold` left; missing inputs go left unless `default_left` is false.
+//!
+//! `features` fixes the model's inputs and their order; every feature a
+//! model uses must be listed. Loading compiles names to input positions.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Errors loading or binding a model
+#[derive(Debug, Error)]
+pub enum ModelError {
+    #[error("failed to read model file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse model: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("feature '{0}' is listed more than once")]
+    DuplicateFeature(String),
+
+    #[error("model uses feature '{0}' missing from its feature list")]
+    UndeclaredFeature(String),
+
+    #[error("tree {tree}: {reason}")]
+    InvalidTree { tree: usize, reason: String },
+
+    #[error("features not produced by the extractors: {}", .0.join(", "))]
+    MissingInputs(Vec<String>),
+}
+
+/// What a model's score means
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTarget {
+    /// Expected return over the strategy's horizon, in basis points
+    #[default]
+    ReturnBps,
+    /// Probability that the price is higher after the horizon
+    UpProbability,
+}
+
+/// Output transform of a tree ensemble
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Link {
+    #[default]
+    Identity,
+    Logistic,
+}
+
+/// A node of a tree in the JSON dump
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TreeNodeSpec {
+    Split {
+        feature: String,
+        threshold: f64,
+        left: usize,
+        right: usize,
+        #[serde(default = "default_left")]
+        default_left: bool,
+    },
+    Leaf {
+        leaf: f64,
+    },
+}
+
+fn default_left() -> bool {
+    true
+}
+
+/// A tree in the JSON dump; node 0 is the root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeSpec {
+    pub nodes: Vec<TreeNodeSpec>,
+}
+
+/// The `model` section of a model file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModelKindSpec {
+    Linear {
+        #[serde(default)]
+        intercept: f64,
+        coefficients: HashMap<String, f64>,
+    },
+    Logistic {
+        #[serde(default)]
+        intercept: f64,
+        coefficients: HashMap<String, f64>,
+    },
+    TreeEnsemble {
+        #[serde(default)]
+        base_score: f64,
+        #[serde(default)]
+        link: Link,
+        trees: Vec<TreeSpec>,
+    },
+}
+
+/// A model file as written by training
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelSpec {
+    pub version: String,
+    #[serde(default)]
+    pub target: ModelTarget,
+    pub features: Vec<String>,
+    pub model: ModelKindSpec,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Node {
+    Split {
+        input: usize,
+        threshold: f64,
+        left: usize,
+        right: usize,
+        default_left: bool,
+    },
+    Leaf(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Scorer {
+    Linear {
+        intercept: f64,
+        weights: Vec<f64>,
+        logistic: bool,
+    },
+    Trees {
+        base_score: f64,
+        link: Link,
+        trees: Vec<Vec<Node>>,
+    },
+}
+
+/// A validated model with features compiled to input positions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    version: String,
+    target: ModelTarget,
+    features: Vec<String>,
+    scorer: Scorer,
+}
+
+impl Model {
+    /// Parse and validate a model file's contents
+    pub fn from_json(json: &str) -> Result<Self, ModelError> {
+        Self::from_spec(serde_json::from_str(json)?)
+    }
+
+    /// Validate a model and compile its feature names
+    pub fn from_spec(spec: ModelSpec) -> Result<Self, ModelError> {
+        let mut positions = HashMap::with_capacity(spec.features.len());
+        for (i, name) in spec.features.iter().enumerate() {
+            if positions.insert(name.as_str(), i).is_some() {
+                return Err(ModelError::DuplicateFeature(name.clone()));
+            }
+        }
+        let position = |name: &str| {
+            positions
+                .get(name)
+                .copied()
+                .ok_or_else(|| ModelError::UndeclaredFeature(name.to_string()))
+        };
+
+        let linear = |intercept, coefficients: HashMap<String, f64>, logistic| {
+            let mut weights = vec![0.0; spec.features.len()];
+            for (name, weight) in &coefficients {
+                weights[position(name)?] = *weight;
+            }
+            Ok::<_, ModelError>(Scorer::Linear {
+                intercept,
+                weights,
+                logistic,
+            })
+        };
+        let scorer = match spec.model {
+            ModelKindSpec::Linear {
+                intercept,
+                coefficients,
+            } => linear(intercept, coefficients, false)?,
+            ModelKindSpec::Logistic {
+                intercept,
+                coefficients,
+            } => linear(intercept, coefficients, true)?,
+            ModelKindSpec::TreeEnsemble {
+                base_score,
+                link,
+                trees,
+            } => Scorer::Trees {
+                base_score,
+                link,
+                trees: trees
+                    .into_iter()
+                    .enumerate()
+                    .map(|(t, tree)| compile_tree(t, tree, &position))
+                    .collect::<Result<_, _>>()?,
+            },
+        };
+
+        Ok(Self {
+            version: spec.version,
+            target: spec.target,
+            features: spec.features,
+            scorer,
+        })
+    }
+
+    /// Version stamped on signals scored by this model
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn target(&self) -> ModelTarget {
+        self.target
+    }
+
+    /// Input feature names, in input order
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// Check every input is among the features extractors produce
+    pub fn check_inputs(&self, available: &[&str]) -> Result<(), ModelError> {
+        let missing: Vec<String> = self
+            .features
+            .iter()
+            .filter(|name| !available.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ModelError::MissingInputs(missing))
+        }
+    }
+
+    /// Input vector for a feature map, NaN where a feature is absent
+    pub fn inputs(&self, features: &HashMap<String, f64>) -> Vec<f64> {
+        self.features
+            .iter()
+            .map(|name| features.get(name).copied().unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Score an input vector
+    ///
+    /// `None` if the vector has the wrong length, or for linear and
+    /// logistic models when an input is missing.
+    pub fn score(&self, inputs: &[f64]) -> Option<f64> {
+        if inputs.len() != self.features.len() {
+            return None;
+        }
+        match &self.scorer {
+            Scorer::Linear {
+                intercept,
+                weights,
+                logistic,
+            } => {
+                if inputs.iter().any(|x| x.is_nan()) {
+                    return None;
+                }
+                let z = intercept + weights.iter().zip(inputs).map(|(w, x)| w * x).sum::<f64>();
+                Some(if *logistic { sigmoid(z) } else { z })
+            }
+            Scorer::Trees {
+                base_score,
+                link,
+                trees,
+            } => {
+                let z = base_score + trees.iter().map(|t| leaf_value(t, inputs)).sum::<f64>();
+                Some(match link {
+                    Link::Identity => z,
+                    Link::Logistic => sigmoid(z),
+                })
+            }
+        }
+    }
+
+    /// Score a feature map
+    pub fn score_features(&self, features: &HashMap<String, f64>) -> Option<f64> {
+        self.score(&self.inputs(features))
+    }
+}
+
+/// Probability that a return forecast with normal residuals of `std`
+/// has the right sign, rescaled to 0-1 as `2 * P(right sign) - 1`
+pub fn sign_confidence(expected: f64, std: f64) -> f64 {
+    if std <= 0.0 {
+        return 1.0;
+    }
+    erf(expected.abs() / (std * std::f64::consts::SQRT_2))
+}
+
+/// Error function, Abramowitz and Stegun 7.1.26 (error below 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let y = 1.0 - poly * (-x * x).exp();
+    if x < 0.0 { -y } else { y }
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Compile a tree, requiring children after their parent so it cannot loop
+fn compile_tree(
+    t: usize,
+    tree: TreeSpec,
+    position: &impl Fn(&str) -> Result<usize, ModelError>,
+) -> Result<Vec<Node>, ModelError> {
+    let invalid = |reason: String| ModelError::InvalidTree { tree: t, reason };
+    if tree.nodes.is_empty() {
+        return Err(invalid("no nodes".to_string()));
+    }
+    let len = tree.nodes.len();
+    tree.nodes
+        .into_iter()
+        .enumerate()
+        .map(|(i, node)| match node {
+            TreeNodeSpec::Leaf { leaf } => Ok(Node::Leaf(leaf)),
+            TreeNodeSpec::Split {
+                feature,
+                threshold,
+                left,
+                right,
+                default_left,
+            } => {
+                for child in [left, right] {
+                    if child <= i || child >= len {
+                        return Err(invalid(format!("node {} has invalid child {}", i, child)));
+                    }
+                }
+                Ok(Node::Split {
+                    input: position(&feature)?,
+                    threshold,
+                    left,
+                    right,
+                    default_left,
+                })
+            }
+        })
+        .collect()
+}
+
+fn leaf_value(tree: &[Node], inputs: &[f64]) -> f64 {
+    let mut i = 0;
+    loop {
+        match tree[i] {
+            Node::Leaf(value) => return value,
+            Node::Split {
+                input,
+                threshold,
+                left,
+                right,
+                default_left,
+            } => {
+                let x = inputs[input];
+                let go_left = if x.is_nan() {
+                    default_left
+                } else {
+                    x < threshold
+                };
+                i = if go_left { left } else { right };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_linear_and_logistic() {
+        let linear = Model::from_json(
+            r#"{
+                "version": "lin_v1",
+                "features": ["imbalance", "spread_bps"],
+                "model": { "type": "linear", "intercept": 0.5,
+                           "coefficients": { "imbalance": 2.0, "spread_bps": -0.1 } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(linear.version(), "lin_v1");
+        assert_eq!(linear.target(), ModelTarget::ReturnBps);
+        let x = features(&[("imbalance", 0.5), ("spread_bps", 3.0), ("other", 9.0)]);
+        assert!((linear.score_features(&x).unwrap() - 1.2).abs() < 1e-12);
+        // Missing inputs can't be scored
+        assert!(
+            linear
+                .score_features(&features(&[("imbalance", 0.5)]))
+                .is_none()
+        );
+
+        let logistic = Model::from_json(
+            r#"{
+                "version": "log_v1",
+                "target": "up_probability",
+                "features": ["imbalance"],
+                "model": { "type": "logistic", "coefficients": { "imbalance": 4.0 } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(logistic.target(), ModelTarget::UpProbability);
+        assert_eq!(logistic.score(&[0.0]), Some(0.5));
+        assert!(logistic.score(&[1.0]).unwrap() > 0.98);
+        assert!(logistic.score(&[1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_sign_confidence() {
+        // One standard deviation: P(right sign) = 0.841
+        assert!((sign_confidence(2.0, 2.0) - 0.6827).abs() < 1e-3);
+        assert_eq!(sign_confidence(-2.0, 2.0), sign_confidence(2.0, 2.0));
+        assert!(sign_confidence(0.0, 2.0).abs() < 1e-6);
+        assert_eq!(sign_confidence(1.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_tree_ensemble() {
+        let model = Model::from_json(
+            r#"{
+                "version": "gbdt_v1",
+                "features": ["imbalance", "spread_bps"],
+                "model": {
+                    "type": "tree_ensemble",
+                    "base_score": 0.1,
+                    "trees": [
+                        { "nodes": [
+                            { "feature": "imbalance", "threshold": 0.2, "left": 1, "right": 2 },
+                            { "leaf": -0.4 },
+                            { "feature": "spread_bps", "threshold": 5.0, "left": 3, "right": 4,
+                              "default_left": false },
+                            { "leaf": 0.9 },
+                            { "leaf": 0.3 }
+                        ] },
+                        { "nodes": [ { "leaf": 0.05 } ] }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let score = |imbalance: f64, spread: f64| model.score(&[imbalance, spread]).unwrap();
+        assert!((score(0.0, 1.0) - (0.1 - 0.4 + 0.05)).abs() < 1e-12);
+        assert!((score(0.5, 1.0) - (0.1 + 0.9 + 0.05)).abs() < 1e-12);
+        assert!((score(0.5, 8.0) - (0.1 + 0.3 + 0.05)).abs() < 1e-12);
+        // Missing values follow the default branch
+        assert!((score(f64::NAN, 1.0) - (0.1 - 0.4 + 0.05)).abs() < 1e-12);
+        assert!((score(0.5, f64::NAN) - (0.1 + 0.3 + 0.05)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_validation() {
+        let spec = |features: &str, model: &str| {
+            format!(
+                r#"{{ "version": "v", "features": {}, "model": {} }}"#,
+                features, model
+            )
+        };
+        let linear = r#"{ "type": "linear", "coefficients": { "a": 1.0 } }"#;
+
+        assert!(matches!(
+            Model::from_json(&spec(r#"["b"]"#, linear)),
+            Err(ModelError::UndeclaredFeature(name)) if name == "a"
+        ));
+        assert!(matches!(
+            Model::from_json(&spec(r#"["a", "a"]"#, linear)),
+            Err(ModelError::DuplicateFeature(_))
+        ));
+        assert!(matches!(
+            Model::from_json(&spec(
+                r#"["a"]"#,
+                r#"{ "type": "tree_ensemble", "trees": [ { "nodes": [
+                    { "feature": "a", "threshold": 0.0, "left": 0, "right": 1 },
+                    { "leaf": 1.0 } ] } ] }"#
+            )),
+            Err(ModelError::InvalidTree { tree: 0, .. })
+        ));
+        assert!(matches!(
+            Model::from_json("{ not json"),
+            Err(ModelError::Parse(_))
+        ));
+
+        let model = Model::from_json(&spec(r#"["a", "b"]"#, linear)).unwrap();
+        model.check_inputs(&["a", "b", "c"]).unwrap();
+        let err = model.check_inputs(&["a"]).unwrap_err();
+        assert!(matches!(&err, ModelError::MissingInputs(names) if names == &["b".to_string()]));
+    }
+}
@@ -2,11 +2,14 @@
 //!
 //! Builds and maintains local order book state from deltas received via transport.
 //! Uses lock-free reads via ArcSwap for high-performance concurrent access.
+//! Every applied snapshot or delta also records its order flow, so flow
+//! features see each book change rather than only the states they sample.
 
+use super::order_flow::{BookDepth, BookFlow, BookFlowConfig, FlowEvent, FlowLog};
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 #[allow(unused_imports)] // Used in tests
 use trading_core::CompactLevel;
 use trading_core::{
@@ -19,9 +22,23 @@ struct OrderBookState {
     bids: BTreeMap<i64, i64>, // price_raw -> quantity_raw (descending for best bid)
     asks: BTreeMap<i64, i64>, // price_raw -> quantity_raw (ascending for best ask)
     last_update_id: u64,
+    /// Exchange timestamp of the last snapshot or delta (0 if unknown)
+    last_update_ns: u64,
     initialized: bool,
 }
 
+impl OrderBookState {
+    /// The first `levels` levels per side as `(price, size)`
+    fn depth(&self, levels: usize) -> BookDepth {
+        let pair =
+            |(&p, &q): (&i64, &i64)| (Price::from_raw(p).to_f64(), Quantity::from_raw(q).to_f64());
+        BookDepth::new(
+            self.bids.iter().rev().take(levels).map(pair).collect(),
+            self.asks.iter().take(levels).map(pair).collect(),
+        )
+    }
+}
+
 /// Multi-exchange order book manager with lock-free reads.
 ///
 /// Architecture:
@@ -36,15 +53,54 @@ struct OrderBookState {
 #[derive(Clone)]
 pub struct OrderBookManager {
     books: Arc<DashMap<QualifiedSymbol, Arc<ArcSwap<OrderBookState>>>>,
+    flows: Arc<DashMap<QualifiedSymbol, Arc<RwLock<FlowLog>>>>,
+    flow_config: BookFlowConfig,
 }
 
 impl OrderBookManager {
     pub fn new() -> Self {
+        Self::with_flow_config(BookFlowConfig::default())
+    }
+
+    /// Record order flow with `config` instead of the defaults
+    pub fn with_flow_config(flow_config: BookFlowConfig) -> Self {
         OrderBookManager {
             books: Arc::new(DashMap::new()),
+            flows: Arc::new(DashMap::new()),
+            flow_config,
         }
     }
 
+    fn get_or_create_flow(&self, key: &QualifiedSymbol) -> Arc<RwLock<FlowLog>> {
+        if let Some(entry) = self.flows.get(key) {
+            return Arc::clone(&entry);
+        }
+        self.flows.entry(key.clone()).or_default().clone()
+    }
+
+    /// Record the change between two states of a book
+    fn record_flow(
+        &self,
+        key: &QualifiedSymbol,
+        prev: &OrderBookState,
+        curr: &OrderBookState,
+        timestamp_ns: u64,
+    ) {
+        let levels = self.flow_config.levels;
+        if levels == 0 || !prev.initialized {
+            return;
+        }
+        let (prev, curr) = (prev.depth(levels), curr.depth(levels));
+        if prev == curr {
+            return;
+        }
+        let event = FlowEvent::between(&prev, &curr, levels, timestamp_ns);
+        self.get_or_create_flow(key)
+            .write()
+            .unwrap()
+            .record(event, &self.flow_config);
+    }
+
     /// Get or create an ArcSwap entry for a symbol
     fn get_or_create_swap(&self, key: &QualifiedSymbol) -> Arc<ArcSwap<OrderBookState>> {
         // Fast path: check if exists
@@ -68,6 +124,7 @@ impl OrderBookManager {
         let key = QualifiedSymbol::new(exchange, symbol);
         SharedOrderBook {
             swap: self.get_or_create_swap(&key),
+            flow: self.get_or_create_flow(&key),
             key,
         }
     }
@@ -76,6 +133,7 @@ impl OrderBookManager {
     pub fn book_by_key(&self, key: &QualifiedSymbol) -> SharedOrderBook {
         SharedOrderBook {
             swap: self.get_or_create_swap(key),
+            flow: self.get_or_create_flow(key),
             key: key.clone(),
         }
     }
@@ -90,6 +148,7 @@ impl OrderBookManager {
             bids: BTreeMap::new(),
             asks: BTreeMap::new(),
             last_update_id: snapshot.last_update_id,
+            last_update_ns: snapshot.timestamp_ns,
             initialized: true,
         };
 
@@ -105,6 +164,9 @@ impl OrderBookManager {
             }
         }
 
+        // A resync counts as one change from the state it replaces
+        self.record_flow(&key, &swap.load(), &new_state, snapshot.timestamp_ns);
+
         // Atomic swap - readers see old or new, never partial
         swap.store(Arc::new(new_state));
     }
@@ -151,6 +213,8 @@ impl OrderBookManager {
         }
 
         new_state.last_update_id = update.final_update_id;
+        new_state.last_update_ns = update.timestamp_ns;
+        self.record_flow(&key, &current, &new_state, update.timestamp_ns);
 
         // Atomic swap
         swap.store(Arc::new(new_state));
@@ -188,6 +252,7 @@ impl Default for OrderBookManager {
 #[derive(Clone)]
 pub struct SharedOrderBook {
     swap: Arc<ArcSwap<OrderBookState>>,
+    flow: Arc<RwLock<FlowLog>>,
     key: QualifiedSymbol,
 }
 
@@ -278,6 +343,16 @@ impl SharedOrderBook {
         self.load().last_update_id
     }
 
+    /// Get the exchange timestamp of the last update, 0 if unknown - lock-free
+    pub fn last_update_ns(&self) -> u64 {
+        self.load().last_update_ns
+    }
+
+    /// Order flow of the book changes stamped at or after `since_ns`
+    pub fn flow_since(&self, since_ns: u64) -> BookFlow {
+        self.flow.read().unwrap().since(since_ns)
+    }
+
     /// Check if the book is initialized - lock-free
     pub fn is_initialized(&self) -> bool {
         self.load().initialized
@@ -379,6 +454,39 @@ mod tests {
         assert_eq!(book.best_bid().unwrap().quantity.raw(), 2_00000000);
     }
 
+    #[test]
+    fn test_every_delta_records_flow() {
+        let manager = OrderBookManager::new();
+        manager.apply_snapshot(&OrderBookSnapshot {
+            exchange: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            last_update_id: 100,
+            timestamp_ns: 1_000,
+            bids: vec![CompactLevel::new(50000_00000000, 1_00000000)],
+            asks: vec![CompactLevel::new(50100_00000000, 1_00000000)],
+        });
+
+        // The bid queue doubles and shrinks back between two reads
+        for (id, size) in [(101, 2_00000000), (102, 1_00000000)] {
+            assert!(manager.apply_delta(&DepthUpdate {
+                exchange: "binance".to_string(),
+                symbol: "BTCUSDT".to_string(),
+                first_update_id: id,
+                final_update_id: id,
+                timestamp_ns: id * 10,
+                bids: vec![CompactLevel::new(50000_00000000, size)],
+                asks: vec![],
+            }));
+        }
+
+        let book = manager.book("binance", "BTCUSDT");
+        let flow = book.flow_since(0);
+        assert_eq!(flow.changes, 2);
+        assert!(flow.ofi[0].abs() < 1e-12);
+        assert!((flow.bid_depletion - 0.5).abs() < 1e-12);
+        assert_eq!(book.flow_since(1_015).changes, 1);
+    }
+
     #[test]
     fn test_delta_removes_level() {
         let manager = OrderBookManager::new();
@@ -0,0 +1,330 @@
+//! Order flow statistics from successive book states
+//!
+//! - `multi_level_ofi`: Order flow imbalance per level (Cont, Kukanov and
+//!   Stoikov), generalised to the first N levels
+//! - `QueueDepletion`: Volume taken off the best bid and ask queues
+//! - `FlowEvent` / `FlowLog`: Both of the above per book change, kept over
+//!   a rolling window and summed into a `BookFlow`
+//!
+//! Books are plain `(price, size)` vectors, best level first, so callers
+//! can build them from any reader. VPIN lives in `trading_core::Vpin`.
+
+use std::collections::VecDeque;
+
+/// The first levels of a book, best first, as `(price, size)`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookDepth {
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl BookDepth {
+    pub fn new(bids: Vec<(f64, f64)>, asks: Vec<(f64, f64)>) -> Self {
+        Self { bids, asks }
+    }
+
+    /// Levels present on both sides
+    pub fn levels(&self) -> usize {
+        self.bids.len().min(self.asks.len())
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        let bid = self.bids.first()?.0;
+        let ask = self.asks.first()?.0;
+        Some((bid + ask) / 2.0)
+    }
+
+    /// Mean of bid and ask size at a level
+    pub fn mean_size(&self, level: usize) -> Option<f64> {
+        let bid = self.bids.get(level)?.1;
+        let ask = self.asks.get(level)?.1;
+        Some((bid + ask) / 2.0)
+    }
+}
+
+/// Order flow imbalance at each of the first `levels` levels
+///
+/// At level m, the bid contributes its new size if its price rose, the
+/// size change if unchanged and minus its old size if it fell; the ask
+/// mirrors this, and the level's OFI is the bid contribution less the
+/// ask's. Positive values are buying pressure. Levels missing from either
+/// state are left out.
+pub fn multi_level_ofi(prev: &BookDepth, curr: &BookDepth, levels: usize) -> Vec<f64> {
+    let levels = levels.min(prev.levels()).min(curr.levels());
+    (0..levels)
+        .map(|m| {
+            let (pb, qb) = prev.bids[m];
+            let (cb, cqb) = curr.bids[m];
+            let bid = if cb > pb {
+                cqb
+            } else if cb == pb {
+                cqb - qb
+            } else {
+                -qb
+            };
+
+            let (pa, qa) = prev.asks[m];
+            let (ca, cqa) = curr.asks[m];
+            let ask = if ca < pa {
+                cqa
+            } else if ca == pa {
+                cqa - qa
+            } else {
+                -qa
+            };
+
+            bid - ask
+        })
+        .collect()
+}
+
+/// Size taken off the best queues between two book states
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueueDepletion {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl QueueDepletion {
+    /// A queue shrinking at an unchanged price lost the difference; a queue
+    /// whose price moved away from the spread lost all of it
+    pub fn between(prev: &BookDepth, curr: &BookDepth) -> Self {
+        let (Some(&(pb, qb)), Some(&(cb, cqb))) = (prev.bids.first(), curr.bids.first()) else {
+            return Self::default();
+        };
+        let (Some(&(pa, qa)), Some(&(ca, cqa))) = (prev.asks.first(), curr.asks.first()) else {
+            return Self::default();
+        };
+
+        let bid = if cb == pb {
+            (qb - cqb).max(0.0)
+        } else if cb < pb {
+            qb
+        } else {
+            0.0
+        };
+        let ask = if ca == pa {
+            (qa - cqa).max(0.0)
+        } else if ca > pa {
+            qa
+        } else {
+            0.0
+        };
+        Self { bid, ask }
+    }
+}
+
+/// Order flow from one book change
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowEvent {
+    /// Exchange timestamp of the change
+    pub timestamp_ns: u64,
+    /// Per-level imbalance in units of the level's mean size
+    pub ofi: Vec<f64>,
+    /// Depletion as a share of the previous best queue
+    pub bid_depletion: f64,
+    pub ask_depletion: f64,
+}
+
+impl FlowEvent {
+    /// Flow of the change from `prev` to `curr` over the first `levels`
+    pub fn between(prev: &BookDepth, curr: &BookDepth, levels: usize, timestamp_ns: u64) -> Self {
+        let ofi = multi_level_ofi(prev, curr, levels)
+            .into_iter()
+            .enumerate()
+            .map(|(m, e)| {
+                let scale =
+                    (prev.mean_size(m).unwrap_or(0.0) + curr.mean_size(m).unwrap_or(0.0)) / 2.0;
+                if scale > 0.0 { e / scale } else { 0.0 }
+            })
+            .collect();
+        let depletion = QueueDepletion::between(prev, curr);
+        let share = |taken: f64, queue: Option<&(f64, f64)>| match queue {
+            Some(&(_, size)) if size > 0.0 => taken / size,
+            _ => 0.0,
+        };
+        Self {
+            timestamp_ns,
+            ofi,
+            bid_depletion: share(depletion.bid, prev.bids.first()),
+            ask_depletion: share(depletion.ask, prev.asks.first()),
+        }
+    }
+}
+
+/// Order flow summed over the book changes in a window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookFlow {
+    /// Book changes in the window
+    pub changes: usize,
+    /// Per-level imbalance, best level first
+    pub ofi: Vec<f64>,
+    pub bid_depletion: f64,
+    pub ask_depletion: f64,
+}
+
+/// Book flow recording and retention
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookFlowConfig {
+    /// Levels measured per change; 0 turns recording off
+    pub levels: usize,
+    /// Most changes kept per symbol
+    pub capacity: usize,
+    /// Changes older than this, relative to the newest, are dropped
+    pub max_age_ns: u64,
+}
+
+impl Default for BookFlowConfig {
+    fn default() -> Self {
+        Self {
+            levels: 10,
+            capacity: 10_000,
+            max_age_ns: 60_000_000_000,
+        }
+    }
+}
+
+/// Flow events for one symbol, oldest first
+#[derive(Debug, Default)]
+pub struct FlowLog {
+    events: VecDeque<FlowEvent>,
+}
+
+impl FlowLog {
+    pub fn record(&mut self, event: FlowEvent, config: &BookFlowConfig) {
+        let at = self
+            .events
+            .partition_point(|e| e.timestamp_ns <= event.timestamp_ns);
+        self.events.insert(at, event);
+
+        let newest = self.events.back().map_or(0, |e| e.timestamp_ns);
+        while self.events.len() > config.capacity
+            || self
+                .events
+                .front()
+                .is_some_and(|e| e.timestamp_ns + config.max_age_ns < newest)
+        {
+            self.events.pop_front();
+        }
+    }
+
+    /// Flow of the changes stamped at or after `since_ns`
+    pub fn since(&self, since_ns: u64) -> BookFlow {
+        let start = self.events.partition_point(|e| e.timestamp_ns < since_ns);
+        let mut flow = BookFlow::default();
+        for event in self.events.range(start..) {
+            if flow.ofi.len() < event.ofi.len() {
+                flow.ofi.resize(event.ofi.len(), 0.0);
+            }
+            for (total, e) in flow.ofi.iter_mut().zip(&event.ofi) {
+                *total += e;
+            }
+            flow.bid_depletion += event.bid_depletion;
+            flow.ask_depletion += event.ask_depletion;
+            flow.changes += 1;
+        }
+        flow
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth(bid: (f64, f64), ask: (f64, f64)) -> BookDepth {
+        BookDepth::new(
+            vec![bid, (bid.0 - 1.0, 10.0)],
+            vec![ask, (ask.0 + 1.0, 10.0)],
+        )
+    }
+
+    #[test]
+    fn test_ofi_cases() {
+        let prev = depth((100.0, 5.0), (101.0, 5.0));
+
+        // Bid queue grows at the same price
+        let ofi = multi_level_ofi(&prev, &depth((100.0, 8.0), (101.0, 5.0)), 2);
+        assert_eq!(ofi, vec![3.0, 0.0]);
+
+        // Bid steps up with 2, ask lifted away leaving 4 at 102
+        let ofi = multi_level_ofi(&prev, &depth((100.5, 2.0), (102.0, 4.0)), 1);
+        assert_eq!(ofi, vec![2.0 + 5.0]);
+
+        // Bid knocked down, ask improves with 3
+        let ofi = multi_level_ofi(&prev, &depth((99.0, 6.0), (100.5, 3.0)), 1);
+        assert_eq!(ofi, vec![-5.0 - 3.0]);
+
+        // Levels beyond either book are dropped
+        assert_eq!(multi_level_ofi(&prev, &prev, 10).len(), 2);
+    }
+
+    #[test]
+    fn test_queue_depletion() {
+        let prev = depth((100.0, 5.0), (101.0, 5.0));
+
+        let d = QueueDepletion::between(&prev, &depth((100.0, 2.0), (101.0, 6.0)));
+        assert_eq!(d, QueueDepletion { bid: 3.0, ask: 0.0 });
+
+        // Ask consumed, bid joined at a better price
+        let d = QueueDepletion::between(&prev, &depth((100.5, 1.0), (102.0, 4.0)));
+        assert_eq!(d, QueueDepletion { bid: 0.0, ask: 5.0 });
+    }
+
+    #[test]
+    fn test_flow_log_window() {
+        let config = BookFlowConfig {
+            levels: 2,
+            capacity: 3,
+            max_age_ns: 1_000,
+        };
+        let prev = depth((100.0, 5.0), (101.0, 5.0));
+        // Bid grows by 5 against a mean level-1 size of 6.25
+        let event = FlowEvent::between(&prev, &depth((100.0, 10.0), (101.0, 5.0)), 2, 100);
+        assert_eq!(event.ofi, vec![0.8, 0.0]);
+        // Ask loses 2 of 5
+        let taken = FlowEvent::between(&prev, &depth((100.0, 5.0), (101.0, 3.0)), 2, 200);
+        assert!((taken.ask_depletion - 0.4).abs() < 1e-12);
+
+        let mut log = FlowLog::default();
+        log.record(event.clone(), &config);
+        log.record(taken, &config);
+        let flow = log.since(0);
+        assert_eq!(flow.changes, 2);
+        assert!((flow.ofi[0] - (0.8 + 2.0 / 4.5)).abs() < 1e-12);
+        assert_eq!(log.since(150).changes, 1);
+
+        // Too old relative to the newest, then over capacity
+        log.record(
+            FlowEvent {
+                timestamp_ns: 1_150,
+                ..event.clone()
+            },
+            &config,
+        );
+        assert_eq!(log.len(), 2);
+        log.record(
+            FlowEvent {
+                timestamp_ns: 1_160,
+                ..event.clone()
+            },
+            &config,
+        );
+        log.record(
+            FlowEvent {
+                timestamp_ns: 1_170,
+                ..event
+            },
+            &config,
+        );
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.since(0).ask_depletion, 0.0);
+    }
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 use std::time::{SystemTime, UNIX_EPOCH};
-use trading_core::Price;
+use trading_core::{Price, SignalMessage};
 use uuid::Uuid;
 
 /// Unique signal identifier
@@ -92,6 +92,8 @@ pub struct Signal {
     pub half_life_seconds: Option<f64>,
     /// Uncertainty in fair value estimate
     pub model_variance: Option<f64>,
+    /// How long after `timestamp_ms` the signal may be acted on (ms)
+    pub ttl_ms: Option<u64>,
 
     // === Metadata ===
     /// Key features that drove the signal
@@ -135,10 +137,50 @@ impl Signal {
         self.age_ms() > max_age_ms
     }
 
+    /// When the signal stops being actionable, if it has a time to live
+    pub fn expires_at_ms(&self) -> Option<u64> {
+        self.ttl_ms.map(|ttl| self.timestamp_ms.saturating_add(ttl))
+    }
+
+    /// Check if the signal's time to live has passed at `now_ms`
+    pub fn is_expired_at(&self, now_ms: u64) -> bool {
+        self.expires_at_ms().is_some_and(|expiry| now_ms > expiry)
+    }
+
     /// Check if this is a multi-leg signal
     pub fn is_multi_leg(&self) -> bool {
         !self.legs.is_empty()
     }
+
+    /// Convert to the wire message published to execution
+    ///
+    /// `symbol` is split on the first `:` into exchange and symbol, as
+    /// produced by `SymbolKey`'s `Display`; without one the exchange is empty.
+    pub fn to_message(&self) -> SignalMessage {
+        let (exchange, symbol) = self.symbol.split_once(':').unwrap_or(("", &self.symbol));
+        let raw = |price: Option<Price>| price.map(|p| p.raw()).unwrap_or(0);
+        SignalMessage {
+            signal_id: self.signal_id.to_string(),
+            strategy_id: self.strategy_id.to_string(),
+            strategy_type: self.strategy_type.code(),
+            exchange: exchange.to_string(),
+            symbol: symbol.to_string(),
+            direction: match self.direction {
+                SignalDirection::Buy => 1,
+                SignalDirection::Sell => -1,
+                SignalDirection::None => 0,
+            },
+            strength: self.strength.clamp(-1.0, 1.0) as f32,
+            confidence: self.confidence.clamp(0.0, 1.0) as f32,
+            current_price_raw: self.current_price.raw(),
+            fair_value_raw: self.fair_value.raw(),
+            entry_price_raw: raw(self.entry_price),
+            target_price_raw: raw(self.target_price),
+            stop_price_raw: raw(self.stop_price),
+            expected_edge_bps: self.expected_edge_bps.unwrap_or(0.0).round() as i32,
+            timestamp_ms: self.timestamp_ms,
+        }
+    }
 }
 
 /// Builder for constructing Signal entities
@@ -159,8 +201,10 @@ pub struct SignalBuilder {
     expected_edge_bps: Option<f64>,
     half_life_seconds: Option<f64>,
     model_variance: Option<f64>,
+    ttl_ms: Option<u64>,
     features: HashMap<String, f64>,
     model_version: String,
+    timestamp_ms: Option<u64>,
 }
 
 impl SignalBuilder {
@@ -187,8 +231,10 @@ impl SignalBuilder {
             expected_edge_bps: None,
             half_life_seconds: None,
             model_variance: None,
+            ttl_ms: None,
             features: HashMap::new(),
             model_version: "1.0.0".to_string(),
+            timestamp_ms: None,
         }
     }
 
@@ -283,6 +329,12 @@ impl SignalBuilder {
         self
     }
 
+    /// Set how long the signal may be acted on (ms)
+    pub fn ttl_ms(mut self, ttl_ms: u64) -> Self {
+        self.ttl_ms = Some(ttl_ms);
+        self
+    }
+
     /// Add a single feature
     pub fn feature(mut self, name: impl Into<String>, value: f64) -> Self {
         self.features.insert(name.into(), value);
@@ -307,10 +359,16 @@ impl SignalBuilder {
         self
     }
 
+    /// Stamp the signal with a strategy clock time instead of system time
+    pub fn timestamp_ms(mut self, timestamp_ms: u64) -> Self {
+        self.timestamp_ms = Some(timestamp_ms);
+        self
+    }
+
     /// Build the signal
     pub fn build(self) -> Signal {
         Signal {
-            timestamp_ms: current_timestamp_ms(),
+            timestamp_ms: self.timestamp_ms.unwrap_or_else(current_timestamp_ms),
             signal_id: SignalId::new(),
             strategy_id: self.strategy_id,
             strategy_type: self.strategy_type,
@@ -328,6 +386,7 @@ impl SignalBuilder {
             expected_edge_bps: self.expected_edge_bps,
             half_life_seconds: self.half_life_seconds,
             model_variance: self.model_variance,
+            ttl_ms: self.ttl_ms,
             features: self.features,
             model_version: self.model_version,
         }
@@ -369,6 +428,37 @@ mod tests {
         assert_eq!(signal.features.get("z_score"), Some(&-2.1));
     }
 
+    #[test]
+    fn test_to_message() {
+        let signal = Signal::builder(
+            StrategyId::new("mr_btc"),
+            StrategyType::MeanReversion,
+            "binance:BTCUSDT",
+        )
+        .sell()
+        .strength(0.5)
+        .confidence(0.9)
+        .prices(Price::from_int(50000), Price::from_int(49990))
+        .stop_price(Price::from_int(50100))
+        .expected_edge_bps(2.4)
+        .build();
+
+        let msg = signal.to_message();
+        assert_eq!(msg.signal_id, signal.signal_id.as_str());
+        assert_eq!(msg.strategy_id, "mr_btc");
+        assert_eq!(msg.strategy_type, StrategyType::MeanReversion.code());
+        assert_eq!(
+            (msg.exchange.as_str(), msg.symbol.as_str()),
+            ("binance", "BTCUSDT")
+        );
+        assert!(msg.is_sell());
+        assert_eq!(msg.fair_value_raw, Price::from_int(49990).raw());
+        assert_eq!(msg.stop_price_raw, Price::from_int(50100).raw());
+        assert_eq!(msg.entry_price_raw, 0);
+        assert_eq!(msg.expected_edge_bps, 2);
+        assert_eq!(msg.timestamp_ms, signal.timestamp_ms);
+    }
+
     #[test]
     fn test_signal_id() {
         let id1 = SignalId::new();
@@ -389,4 +479,24 @@ mod tests {
         assert!(signal.age_ms() < 100);
         assert!(!signal.is_stale(1000));
     }
+
+    #[test]
+    fn test_signal_ttl() {
+        let builder = Signal::builder(
+            StrategyId::new("arb"),
+            StrategyType::TriangularArb,
+            "binance:BTCUSDT",
+        )
+        .timestamp_ms(1_000);
+        let signal = builder.ttl_ms(50).build();
+
+        assert_eq!(signal.expires_at_ms(), Some(1_050));
+        assert!(!signal.is_expired_at(1_050));
+        assert!(signal.is_expired_at(1_051));
+
+        let no_ttl = Signal::builder(StrategyId::new("mr"), StrategyType::MeanReversion, "X")
+            .timestamp_ms(1_000)
+            .build();
+        assert!(!no_ttl.is_expired_at(u64::MAX));
+    }
 }
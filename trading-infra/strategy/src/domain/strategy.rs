@@ -58,6 +58,8 @@ pub enum StrategyType {
     MarketMaking,
     /// Order flow / microstructure strategies
     OrderFlow,
+    /// Trained model scoring (features in, expected return out)
+    Model,
 }
 
 impl StrategyType {
@@ -76,6 +78,11 @@ impl StrategyType {
             StrategyType::StatArb | StrategyType::LatencyArb | StrategyType::TriangularArb
         )
     }
+
+    /// Wire code carried in `SignalMessage::strategy_type`
+    pub fn code(self) -> u8 {
+        self as u8
+    }
 }
 
 #[cfg(test)]
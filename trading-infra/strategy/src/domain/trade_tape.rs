@@ -0,0 +1,415 @@
+//! Trade Tape for Strategy
+//!
+//! Rolling per-symbol record of trade prints received via transport, with
+//! the aggregates order-flow strategies read: last trade, VWAP, aggressor
+//! volume and signed volume buckets over a lookback.
+
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+use trading_core::{ExchangeId, Price, QualifiedSymbol, Quantity, TradeUpdate};
+
+/// Side that crossed the spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggressorSide {
+    Buy,
+    Sell,
+}
+
+/// A single executed trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradePrint {
+    pub trade_id: u64,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub aggressor: AggressorSide,
+    /// Exchange timestamp in nanoseconds since epoch
+    pub timestamp_ns: u64,
+}
+
+impl TradePrint {
+    /// Quantity signed by aggressor: positive for buys, negative for sells
+    pub fn signed_quantity(&self) -> Quantity {
+        match self.aggressor {
+            AggressorSide::Buy => self.quantity,
+            AggressorSide::Sell => Quantity::from_raw(-self.quantity.raw()),
+        }
+    }
+}
+
+impl From<&TradeUpdate> for TradePrint {
+    fn from(trade: &TradeUpdate) -> Self {
+        Self {
+            trade_id: trade.trade_id,
+            price: trade.price(),
+            quantity: trade.quantity(),
+            // A passive buyer means the seller crossed
+            aggressor: if trade.buyer_is_maker {
+                AggressorSide::Sell
+            } else {
+                AggressorSide::Buy
+            },
+            timestamp_ns: trade.timestamp_ns,
+        }
+    }
+}
+
+/// Volume split by aggressor side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AggressorVolume {
+    pub buy: Quantity,
+    pub sell: Quantity,
+}
+
+impl AggressorVolume {
+    fn add(&mut self, print: &TradePrint) {
+        let side = match print.aggressor {
+            AggressorSide::Buy => &mut self.buy,
+            AggressorSide::Sell => &mut self.sell,
+        };
+        *side = Quantity::from_raw(side.raw() + print.quantity.raw());
+    }
+
+    pub fn total(&self) -> Quantity {
+        Quantity::from_raw(self.buy.raw() + self.sell.raw())
+    }
+
+    /// Buy minus sell volume
+    pub fn net(&self) -> Quantity {
+        Quantity::from_raw(self.buy.raw() - self.sell.raw())
+    }
+
+    /// (buy - sell) / (buy + sell) in [-1, 1]; `None` without volume
+    pub fn imbalance(&self) -> Option<f64> {
+        let total = self.total().raw();
+        (total != 0).then(|| self.net().raw() as f64 / total as f64)
+    }
+}
+
+/// Aggressor volume over one time bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolumeBucket {
+    /// Bucket start, a multiple of the bucket width
+    pub start_ns: u64,
+    pub volume: AggressorVolume,
+    pub trades: usize,
+}
+
+/// Trade tape retention
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeTapeConfig {
+    /// Most prints kept per symbol
+    pub capacity: usize,
+    /// Prints older than this, relative to the newest, are dropped
+    pub max_age_ns: u64,
+}
+
+impl Default for TradeTapeConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            max_age_ns: 300_000_000_000,
+        }
+    }
+}
+
+/// Prints for one symbol, oldest first
+#[derive(Debug, Default)]
+struct TradeTape {
+    prints: VecDeque<TradePrint>,
+    total: u64,
+}
+
+impl TradeTape {
+    fn record(&mut self, print: TradePrint, config: &TradeTapeConfig) {
+        // Keep the tape time-ordered even if a late print arrives
+        let at = self
+            .prints
+            .partition_point(|p| p.timestamp_ns <= print.timestamp_ns);
+        self.prints.insert(at, print);
+        self.total += 1;
+
+        let newest = self.prints.back().map_or(0, |p| p.timestamp_ns);
+        while self.prints.len() > config.capacity
+            || self
+                .prints
+                .front()
+                .is_some_and(|p| p.timestamp_ns + config.max_age_ns < newest)
+        {
+            self.prints.pop_front();
+        }
+    }
+
+    fn since(&self, since_ns: u64) -> impl Iterator<Item = &TradePrint> {
+        let start = self.prints.partition_point(|p| p.timestamp_ns < since_ns);
+        self.prints.range(start..)
+    }
+}
+
+/// Multi-exchange trade tapes, one per symbol
+///
+/// Cloning shares the tapes; the subscriber writes and strategies read.
+#[derive(Clone)]
+pub struct TradeTapeManager {
+    tapes: Arc<DashMap<QualifiedSymbol, Arc<RwLock<TradeTape>>>>,
+    config: TradeTapeConfig,
+}
+
+impl TradeTapeManager {
+    pub fn new() -> Self {
+        Self::with_config(TradeTapeConfig::default())
+    }
+
+    pub fn with_config(config: TradeTapeConfig) -> Self {
+        Self {
+            tapes: Arc::new(DashMap::new()),
+            config,
+        }
+    }
+
+    fn get_or_create(&self, key: &QualifiedSymbol) -> Arc<RwLock<TradeTape>> {
+        if let Some(entry) = self.tapes.get(key) {
+            return Arc::clone(&entry);
+        }
+        self.tapes.entry(key.clone()).or_default().clone()
+    }
+
+    /// Record a trade from gateway (IPC format)
+    pub fn record(&self, trade: &TradeUpdate) {
+        let key = QualifiedSymbol::new(trade.exchange.clone(), trade.symbol.clone());
+        let tape = self.get_or_create(&key);
+        tape.write()
+            .unwrap()
+            .record(TradePrint::from(trade), &self.config);
+    }
+
+    /// Get a handle to a symbol's tape by exchange and symbol
+    pub fn tape(
+        &self,
+        exchange: impl Into<ExchangeId>,
+        symbol: impl Into<String>,
+    ) -> SharedTradeTape {
+        self.tape_by_key(&QualifiedSymbol::new(exchange, symbol))
+    }
+
+    /// Get a handle to a symbol's tape by qualified symbol
+    pub fn tape_by_key(&self, key: &QualifiedSymbol) -> SharedTradeTape {
+        SharedTradeTape {
+            tape: self.get_or_create(key),
+            key: key.clone(),
+        }
+    }
+
+    /// Symbols with at least one recorded trade
+    pub fn symbols(&self) -> Vec<QualifiedSymbol> {
+        self.tapes
+            .iter()
+            .filter(|entry| entry.value().read().unwrap().total > 0)
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+}
+
+impl Default for TradeTapeManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read handle to one symbol's trade tape
+#[derive(Clone)]
+pub struct SharedTradeTape {
+    tape: Arc<RwLock<TradeTape>>,
+    key: QualifiedSymbol,
+}
+
+impl SharedTradeTape {
+    pub fn qualified_symbol(&self) -> &QualifiedSymbol {
+        &self.key
+    }
+
+    /// Prints currently retained
+    pub fn len(&self) -> usize {
+        self.tape.read().unwrap().prints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Prints recorded since startup, including evicted ones
+    pub fn total_trades(&self) -> u64 {
+        self.tape.read().unwrap().total
+    }
+
+    /// Most recent print
+    pub fn last_trade(&self) -> Option<TradePrint> {
+        self.tape.read().unwrap().prints.back().copied()
+    }
+
+    /// Price of the most recent print
+    pub fn last_price(&self) -> Option<Price> {
+        self.last_trade().map(|print| print.price)
+    }
+
+    /// Prints stamped at or after `since_ns`, oldest first
+    pub fn trades_since(&self, since_ns: u64) -> Vec<TradePrint> {
+        self.tape.read().unwrap().since(since_ns).copied().collect()
+    }
+
+    /// Volume-weighted average price of prints since `since_ns`
+    pub fn vwap_since(&self, since_ns: u64) -> Option<Price> {
+        let tape = self.tape.read().unwrap();
+        let mut value: i128 = 0;
+        let mut volume: i128 = 0;
+        for print in tape.since(since_ns) {
+            value += print.price.raw() as i128 * print.quantity.raw() as i128;
+            volume += print.quantity.raw() as i128;
+        }
+        (volume != 0).then(|| Price::from_raw((value / volume) as i64))
+    }
+
+    /// Buy and sell aggressor volume since `since_ns`
+    pub fn aggressor_volume_since(&self, since_ns: u64) -> AggressorVolume {
+        let tape = self.tape.read().unwrap();
+        let mut volume = AggressorVolume::default();
+        for print in tape.since(since_ns) {
+            volume.add(print);
+        }
+        volume
+    }
+
+    /// Aggressor volume in consecutive `bucket_ns` buckets since `since_ns`
+    ///
+    /// Buckets are aligned to multiples of `bucket_ns` and run from the first
+    /// retained print to the newest, including empty ones.
+    pub fn volume_buckets_since(&self, since_ns: u64, bucket_ns: u64) -> Vec<VolumeBucket> {
+        let bucket_ns = bucket_ns.max(1);
+        let tape = self.tape.read().unwrap();
+        let mut buckets: Vec<VolumeBucket> = Vec::new();
+        for print in tape.since(since_ns) {
+            let start_ns = print.timestamp_ns - print.timestamp_ns % bucket_ns;
+            let next = buckets.last().map(|b| b.start_ns + bucket_ns);
+            if let Some(mut empty) = next {
+                while empty < start_ns {
+                    buckets.push(VolumeBucket {
+                        start_ns: empty,
+                        volume: AggressorVolume::default(),
+                        trades: 0,
+                    });
+                    empty += bucket_ns;
+                }
+            }
+            if buckets.last().is_none_or(|b| b.start_ns != start_ns) {
+                buckets.push(VolumeBucket {
+                    start_ns,
+                    volume: AggressorVolume::default(),
+                    trades: 0,
+                });
+            }
+            let bucket = buckets.last_mut().unwrap();
+            bucket.volume.add(print);
+            bucket.trades += 1;
+        }
+        buckets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1_000_000;
+
+    fn trade(id: u64, price: i64, quantity: i64, buyer_is_maker: bool, ts_ms: u64) -> TradeUpdate {
+        let mut trade = TradeUpdate::new(
+            "binance",
+            "BTCUSDT",
+            id,
+            Price::from_int(price),
+            Quantity::from_int(quantity),
+            buyer_is_maker,
+        );
+        trade.timestamp_ns = ts_ms * MS;
+        trade
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let tapes = TradeTapeManager::new();
+        tapes.record(&trade(1, 100, 1, false, 0)); // buy 1 @ 100
+        tapes.record(&trade(2, 102, 3, true, 10)); // sell 3 @ 102
+        tapes.record(&trade(3, 101, 2, false, 20)); // buy 2 @ 101
+
+        let tape = tapes.tape("binance", "BTCUSDT");
+        assert_eq!(tape.len(), 3);
+        assert_eq!(tape.last_price(), Some(Price::from_int(101)));
+        assert_eq!(tape.last_trade().unwrap().aggressor, AggressorSide::Buy);
+
+        // (100 + 306 + 202) / 6
+        assert_eq!(
+            tape.vwap_since(0),
+            Some(Price::from_raw(Price::from_int(608).raw() / 6))
+        );
+        assert_eq!(tape.vwap_since(15 * MS), Some(Price::from_int(101)));
+        assert_eq!(tape.vwap_since(25 * MS), None);
+
+        let volume = tape.aggressor_volume_since(0);
+        assert_eq!(volume.buy, Quantity::from_int(3));
+        assert_eq!(volume.sell, Quantity::from_int(3));
+        assert_eq!(volume.imbalance(), Some(0.0));
+        assert_eq!(
+            tape.aggressor_volume_since(5 * MS).net(),
+            Quantity::from_int(-1)
+        );
+        assert_eq!(tapes.symbols().len(), 1);
+    }
+
+    #[test]
+    fn test_volume_buckets() {
+        let tapes = TradeTapeManager::new();
+        tapes.record(&trade(1, 100, 1, false, 3));
+        tapes.record(&trade(2, 100, 2, true, 7));
+        tapes.record(&trade(3, 100, 4, false, 31));
+
+        let buckets = tapes
+            .tape("binance", "BTCUSDT")
+            .volume_buckets_since(0, 10 * MS);
+        let summary: Vec<_> = buckets
+            .iter()
+            .map(|b| (b.start_ns / MS, b.trades, b.volume.net()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 2, Quantity::from_int(-1)),
+                (10, 0, Quantity::ZERO),
+                (20, 0, Quantity::ZERO),
+                (30, 1, Quantity::from_int(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retention() {
+        let tapes = TradeTapeManager::with_config(TradeTapeConfig {
+            capacity: 3,
+            max_age_ns: 100 * MS,
+        });
+        for (id, ts) in [(1, 0), (2, 10), (3, 20), (4, 30)] {
+            tapes.record(&trade(id, 100, 1, false, ts));
+        }
+        let tape = tapes.tape("binance", "BTCUSDT");
+        assert_eq!(tape.len(), 3);
+        assert_eq!(tape.total_trades(), 4);
+        assert_eq!(tape.trades_since(0)[0].trade_id, 2);
+
+        // A late print slots in by time; the newest ages the rest out
+        tapes.record(&trade(5, 100, 1, false, 25));
+        let ids: Vec<_> = tape.trades_since(0).iter().map(|p| p.trade_id).collect();
+        assert_eq!(ids, vec![3, 5, 4]);
+        tapes.record(&trade(6, 100, 1, false, 125));
+        let ids: Vec<_> = tape.trades_since(0).iter().map(|p| p.trade_id).collect();
+        assert_eq!(ids, vec![5, 4, 6]);
+    }
+}
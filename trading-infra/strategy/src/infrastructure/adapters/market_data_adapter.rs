@@ -1,10 +1,16 @@
 //! Market Data Adapter - Adapts OrderBookManager to MarketDataPort
 //!
-//! This adapter implements the MarketDataPort and OrderBookReader traits
-//! using the concrete OrderBookManager and SharedOrderBook types.
+//! This adapter implements the MarketDataPort, OrderBookReader and
+//! TradeReader traits using the concrete OrderBookManager, SharedOrderBook,
+//! TradeTapeManager and SharedTradeTape types.
 
-use crate::application::ports::{BookLevel, MarketDataPort, OrderBookReader, SymbolKey};
+use crate::application::ports::{
+    BookLevel, MarketDataPort, OrderBookReader, SymbolKey, TradeReader,
+};
 use crate::domain::order_book::{OrderBookManager, SharedOrderBook};
+use crate::domain::{
+    AggressorVolume, BookFlow, SharedTradeTape, TradePrint, TradeTapeManager, VolumeBucket,
+};
 use std::sync::Arc;
 use trading_core::QualifiedSymbol;
 use trading_core::{Price, Quantity};
@@ -95,20 +101,61 @@ impl OrderBookReader for OrderBookReaderAdapter {
     }
 
     fn last_update_time(&self) -> Option<u64> {
-        // The current SharedOrderBook doesn't track time, just update ID
-        // We return the update ID as a proxy for time ordering
-        Some(self.book.last_update_id())
+        let ns = self.book.last_update_ns();
+        (ns > 0).then_some(ns / 1_000)
+    }
+
+    fn last_update_id(&self) -> Option<u64> {
+        self.book
+            .is_initialized()
+            .then(|| self.book.last_update_id())
+    }
+
+    fn flow_since(&self, since_ns: u64) -> Option<BookFlow> {
+        Some(self.book.flow_since(since_ns))
+    }
+}
+
+impl TradeReader for SharedTradeTape {
+    fn last_trade(&self) -> Option<TradePrint> {
+        SharedTradeTape::last_trade(self)
+    }
+
+    fn trades_since(&self, since_ns: u64) -> Vec<TradePrint> {
+        SharedTradeTape::trades_since(self, since_ns)
+    }
+
+    fn vwap_since(&self, since_ns: u64) -> Option<Price> {
+        SharedTradeTape::vwap_since(self, since_ns)
+    }
+
+    fn aggressor_volume_since(&self, since_ns: u64) -> AggressorVolume {
+        SharedTradeTape::aggressor_volume_since(self, since_ns)
+    }
+
+    fn volume_buckets_since(&self, since_ns: u64, bucket_ns: u64) -> Vec<VolumeBucket> {
+        SharedTradeTape::volume_buckets_since(self, since_ns, bucket_ns)
     }
 }
 
 /// Adapter that implements MarketDataPort for OrderBookManager
 pub struct MarketDataAdapter {
     manager: OrderBookManager,
+    trades: TradeTapeManager,
 }
 
 impl MarketDataAdapter {
     pub fn new(manager: OrderBookManager) -> Self {
-        Self { manager }
+        Self {
+            manager,
+            trades: TradeTapeManager::new(),
+        }
+    }
+
+    /// Serve trades from `trades`, typically the subscriber's tapes
+    pub fn with_trades(mut self, trades: TradeTapeManager) -> Self {
+        self.trades = trades;
+        self
     }
 
     /// Get the underlying OrderBookManager
@@ -116,6 +163,11 @@ impl MarketDataAdapter {
         &self.manager
     }
 
+    /// Get the underlying TradeTapeManager
+    pub fn trade_tapes(&self) -> &TradeTapeManager {
+        &self.trades
+    }
+
     /// Convert SymbolKey to QualifiedSymbol
     fn to_qualified(key: &SymbolKey) -> QualifiedSymbol {
         QualifiedSymbol::new(key.exchange.clone(), key.symbol.clone())
@@ -148,6 +200,11 @@ impl MarketDataPort for MarketDataAdapter {
             .map(|qs| Self::from_qualified(&qs))
             .collect()
     }
+
+    fn trades(&self, key: &SymbolKey) -> Option<Arc<dyn TradeReader>> {
+        let qs = Self::to_qualified(key);
+        Some(Arc::new(self.trades.tape_by_key(&qs)))
+    }
 }
 
 /// Convenience function to create a market data adapter
@@ -158,7 +215,7 @@ pub fn adapt_market_data(manager: OrderBookManager) -> MarketDataAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use trading_core::{CompactLevel, OrderBookSnapshot};
+    use trading_core::{CompactLevel, OrderBookSnapshot, TradeUpdate};
 
     #[test]
     fn test_order_book_reader_adapter() {
@@ -206,6 +263,17 @@ mod tests {
 
         let total_bid = adapter.total_bid_depth(2);
         assert_eq!(total_bid.raw(), 3_00000000); // 1 + 2
+
+        // Untimestamped snapshot: sequence only
+        assert_eq!(adapter.last_update_time(), None);
+        assert_eq!(adapter.last_update_id(), Some(100));
+        manager.apply_snapshot(&OrderBookSnapshot {
+            last_update_id: 101,
+            timestamp_ns: 1_700_000_000_123_456_789,
+            ..snapshot
+        });
+        assert_eq!(adapter.last_update_time(), Some(1_700_000_000_123_456));
+        assert_eq!(adapter.last_update_id(), Some(101));
     }
 
     #[test]
@@ -235,4 +303,26 @@ mod tests {
         assert_eq!(symbols.len(), 1);
         assert_eq!(symbols[0].symbol, "BTCUSDT");
     }
+
+    #[test]
+    fn test_market_data_adapter_trades() {
+        let tapes = TradeTapeManager::new();
+        let adapter = MarketDataAdapter::new(OrderBookManager::new()).with_trades(tapes.clone());
+        let key = SymbolKey::new("binance", "BTCUSDT");
+
+        let trades = adapter.trades(&key).unwrap();
+        assert!(trades.last_trade().is_none());
+
+        tapes.record(&TradeUpdate::new(
+            "binance",
+            "BTCUSDT",
+            1,
+            Price::from_int(50000),
+            Quantity::from_int(2),
+            true,
+        ));
+        assert_eq!(trades.last_trade().unwrap().price, Price::from_int(50000));
+        assert_eq!(trades.aggressor_volume_since(0).sell, Quantity::from_int(2));
+        assert_eq!(trades.vwap_since(0), Some(Price::from_int(50000)));
+    }
 }
@@ -4,10 +4,16 @@
 //! These bridge the abstract domain/application layers with real implementations.
 
 mod market_data_adapter;
+mod model_file_adapter;
 mod signal_channel_adapter;
+mod signal_transport_adapter;
+mod training_log_adapter;
 
 pub use market_data_adapter::{MarketDataAdapter, OrderBookReaderAdapter, adapt_market_data};
+pub use model_file_adapter::{ModelFileWatcher, load_model_file};
 pub use signal_channel_adapter::{
     BoundedChannelFactory, BoundedSignalPublisher, BoundedSignalSubscriber, ChannelFactory,
     ChannelSignalPublisher, ChannelSignalSubscriber, create_signal_channel,
 };
+pub use signal_transport_adapter::TransportSignalPublisher;
+pub use training_log_adapter::JsonlTrainingLog;
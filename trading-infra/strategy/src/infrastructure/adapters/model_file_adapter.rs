@@ -0,0 +1,110 @@
+//! Model File Adapter - Loads models from disk and notices new versions
+//!
+//! Training writes a new model file in place (ideally by renaming over the
+//! old one); the watcher compares modification times so strategies can
+//! poll cheaply and reload only when the file changed.
+
+use crate::domain::{Model, ModelError};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Read and validate a model file
+pub fn load_model_file(path: impl AsRef<Path>) -> Result<Model, ModelError> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path).map_err(|source| ModelError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    Model::from_json(&json)
+}
+
+/// Model file and the modification time it was last read at
+#[derive(Debug, Clone)]
+pub struct ModelFileWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ModelFileWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            modified: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the file now
+    pub fn load(&mut self) -> Result<Model, ModelError> {
+        self.modified = self.modified_time();
+        load_model_file(&self.path)
+    }
+
+    /// Read the file if it changed since the last load
+    ///
+    /// A file that fails to load is not retried until it changes again.
+    pub fn poll(&mut self) -> Result<Option<Model>, ModelError> {
+        let modified = self.modified_time();
+        if modified.is_none() || modified == self.modified {
+            return Ok(None);
+        }
+        self.load().map(Some)
+    }
+
+    fn modified_time(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn model_json(version: &str) -> String {
+        format!(
+            r#"{{ "version": "{}", "features": ["imbalance"],
+                  "model": {{ "type": "linear", "coefficients": {{ "imbalance": 1.0 }} }} }}"#,
+            version
+        )
+    }
+
+    /// Write and push the mtime forward so coarse filesystem clocks still
+    /// see a change
+    fn write(path: &Path, contents: &str, age: u64) {
+        std::fs::write(path, contents).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + age))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_watcher_reloads_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.json");
+        write(&path, &model_json("v1"), 0);
+
+        let mut watcher = ModelFileWatcher::new(&path);
+        assert_eq!(watcher.load().unwrap().version(), "v1");
+        assert!(watcher.poll().unwrap().is_none());
+
+        write(&path, &model_json("v2"), 1);
+        assert_eq!(watcher.poll().unwrap().unwrap().version(), "v2");
+        assert!(watcher.poll().unwrap().is_none());
+
+        // A broken file errors once, then waits for the next change
+        write(&path, "{ broken", 2);
+        assert!(matches!(watcher.poll(), Err(ModelError::Parse(_))));
+        assert!(watcher.poll().unwrap().is_none());
+
+        assert!(matches!(
+            load_model_file(dir.path().join("missing.json")),
+            Err(ModelError::Io { .. })
+        ));
+    }
+}
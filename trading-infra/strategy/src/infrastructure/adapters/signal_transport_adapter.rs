@@ -0,0 +1,124 @@
+//! Signal Transport Adapter - Publishes signals to execution over transport
+//!
+//! Implements `SignalPublisher` by converting each `Signal` to a
+//! `SignalMessage` and publishing it with `MSG_SIGNAL` on the strategy's
+//! signal topic. Each strategy is its own sequenced stream.
+
+use crate::application::ports::{PublishError, SignalPublisher};
+use crate::domain::Signal;
+use std::sync::{Arc, Mutex};
+use transport::{Codec, CodecKind, Publisher, signal_topic};
+
+/// SignalPublisher implementation over a transport publisher
+pub struct TransportSignalPublisher {
+    transport: Arc<dyn Publisher>,
+    source: String,
+    topic: String,
+    codec: CodecKind,
+    /// Next sequence and encode buffer, held together across the publish
+    state: Mutex<(u64, Vec<u8>)>,
+}
+
+impl TransportSignalPublisher {
+    /// Publish `strategy`'s signals on its signal topic
+    ///
+    /// `transport` may be shared by every strategy in the process.
+    pub fn new(transport: Arc<dyn Publisher>, source: impl Into<String>, strategy: &str) -> Self {
+        Self {
+            transport,
+            source: source.into(),
+            topic: signal_topic(strategy),
+            codec: CodecKind::default(),
+            state: Mutex::new((0, Vec::new())),
+        }
+    }
+
+    /// Frame messages with `codec` instead of bincode
+    pub fn with_codec(mut self, codec: CodecKind) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Topic signals are published on
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Number of signals published
+    pub fn sequence(&self) -> u64 {
+        self.state.lock().unwrap().0
+    }
+}
+
+impl SignalPublisher for TransportSignalPublisher {
+    fn publish(&self, signal: Signal) -> Result<(), PublishError> {
+        let message = signal.to_message();
+        let mut state = self.state.lock().unwrap();
+        let (sequence, buf) = &mut *state;
+
+        buf.clear();
+        self.codec
+            .encode_into(*sequence, &self.source, &self.topic, &message, buf)
+            .map_err(|e| PublishError::new(format!("Encode failed: {}", e)))?;
+        self.transport
+            .publish_to(&self.topic, buf)
+            .map_err(|e| PublishError::new(format!("Transport error: {}", e)))?;
+        *sequence += 1;
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.transport.is_active()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{StrategyId, StrategyType};
+    use trading_core::{Price, SignalMessage};
+    use transport::{MSG_SIGNAL, Subscriber, WireMessage, channel_pair};
+
+    #[test]
+    fn test_publish_signal() {
+        let (publisher, subscriber) = channel_pair(100);
+        let signals = TransportSignalPublisher::new(Arc::new(publisher), "strategy", "mr_btc")
+            .with_codec(CodecKind::Fixed);
+
+        for _ in 0..2 {
+            let signal = Signal::builder(
+                StrategyId::new("mr_btc"),
+                StrategyType::MeanReversion,
+                "binance:BTCUSDT",
+            )
+            .buy()
+            .strength(0.4)
+            .prices(Price::from_int(100), Price::from_int(101))
+            .build();
+            signals.publish(signal).unwrap();
+        }
+        assert_eq!(signals.sequence(), 2);
+
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| {
+                let header = WireMessage::header(data).unwrap();
+                assert_eq!(header.msg_type, MSG_SIGNAL);
+                assert_eq!(header.topic, "signals.mr_btc");
+                assert_eq!(header.source, "strategy");
+                let msg: SignalMessage = CodecKind::detect(data).decode(data).unwrap();
+                received.push((header.sequence, msg));
+            })
+            .unwrap();
+
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1].0, 1);
+        let msg = &received[0].1;
+        assert_eq!(
+            (msg.exchange.as_str(), msg.symbol.as_str()),
+            ("binance", "BTCUSDT")
+        );
+        assert!(msg.is_buy());
+        assert_eq!(msg.fair_value_raw, Price::from_int(101).raw());
+    }
+}
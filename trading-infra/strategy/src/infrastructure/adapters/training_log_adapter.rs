@@ -0,0 +1,81 @@
+//! Training Log Adapter - Appends training samples to a JSON lines file
+//!
+//! Implements TrainingLogPort with one JSON object per line, the format
+//! offline training reads.
+
+use crate::application::ports::{TrainingLogError, TrainingLogPort, TrainingSample};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Training log writing JSON lines to a file
+pub struct JsonlTrainingLog {
+    writer: BufWriter<File>,
+}
+
+impl JsonlTrainingLog {
+    /// Open `path` for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl TrainingLogPort for JsonlTrainingLog {
+    fn record(&mut self, sample: &TrainingSample) -> Result<(), TrainingLogError> {
+        serde_json::to_writer(&mut self.writer, sample)
+            .map_err(|e| TrainingLogError::new(e.to_string()))?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|e| TrainingLogError::new(e.to_string()))
+    }
+
+    fn flush(&mut self) -> Result<(), TrainingLogError> {
+        self.writer
+            .flush()
+            .map_err(|e| TrainingLogError::new(e.to_string()))
+    }
+}
+
+impl Drop for JsonlTrainingLog {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_appends_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("samples.jsonl");
+        let sample = TrainingSample {
+            symbol: "binance:BTCUSDT".to_string(),
+            timestamp_ns: 1,
+            horizon_ns: 1_000,
+            model_version: "v1".to_string(),
+            features: HashMap::from([("imbalance".to_string(), 0.25)]),
+            score: Some(1.5),
+            outcome_bps: -2.0,
+        };
+
+        let mut log = JsonlTrainingLog::open(&path).unwrap();
+        log.record(&sample).unwrap();
+        drop(log);
+        let mut log = JsonlTrainingLog::open(&path).unwrap();
+        log.record(&sample).unwrap();
+        log.flush().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<TrainingSample> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, vec![sample.clone(), sample]);
+    }
+}
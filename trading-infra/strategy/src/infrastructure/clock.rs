@@ -0,0 +1,131 @@
+//! Clock implementations
+//!
+//! `WallClock` follows system time for live trading. `EventClock` only
+//! moves when told to, from replayed message timestamps or a simulation
+//! loop, so strategy runs are reproducible.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::application::ports::Clock;
+
+/// System time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WallClock;
+
+impl WallClock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Clock for WallClock {
+    fn now_ns(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    }
+
+    fn wait_until(&self, deadline_ns: u64, timeout: Duration) -> bool {
+        let remaining = Duration::from_nanos(deadline_ns.saturating_sub(self.now_ns()));
+        if remaining > timeout {
+            std::thread::sleep(timeout);
+            return false;
+        }
+        std::thread::sleep(remaining);
+        true
+    }
+}
+
+/// Event time, advanced explicitly and never backwards
+///
+/// Clones share the same time, so a driver (replay, simulation) can
+/// advance the clock a running engine waits on.
+#[derive(Debug, Clone, Default)]
+pub struct EventClock {
+    inner: Arc<(Mutex<u64>, Condvar)>,
+}
+
+impl EventClock {
+    /// Create a clock starting at `start_ns`
+    pub fn new(start_ns: u64) -> Self {
+        Self {
+            inner: Arc::new((Mutex::new(start_ns), Condvar::new())),
+        }
+    }
+
+    /// Move to `time_ns`; earlier times are ignored
+    pub fn advance_to(&self, time_ns: u64) {
+        let (now, changed) = &*self.inner;
+        let mut now = now.lock().unwrap();
+        if time_ns > *now {
+            *now = time_ns;
+            changed.notify_all();
+        }
+    }
+
+    /// Move forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        let (now, changed) = &*self.inner;
+        let mut now = now.lock().unwrap();
+        *now = now.saturating_add(duration.as_nanos() as u64);
+        changed.notify_all();
+    }
+}
+
+impl Clock for EventClock {
+    fn now_ns(&self) -> u64 {
+        *self.inner.0.lock().unwrap()
+    }
+
+    fn wait_until(&self, deadline_ns: u64, timeout: Duration) -> bool {
+        let (now, changed) = &*self.inner;
+        let now = now.lock().unwrap();
+        let (now, _) = changed
+            .wait_timeout_while(now, timeout, |now| *now < deadline_ns)
+            .unwrap();
+        *now >= deadline_ns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_clock() {
+        let clock = EventClock::new(1_000);
+        assert_eq!(clock.now_ns(), 1_000);
+
+        clock.advance_to(5_000_000);
+        clock.advance_to(2_000); // never backwards
+        assert_eq!(clock.now_ms(), 5);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(clock.now_ns(), 6_000_000);
+        assert!(clock.wait_until(6_000_000, Duration::ZERO));
+        assert!(!clock.wait_until(7_000_000, Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn test_event_clock_wakes_waiter() {
+        let clock = EventClock::new(0);
+        let waiter = {
+            let clock = clock.clone();
+            std::thread::spawn(move || clock.wait_until(100, Duration::from_secs(5)))
+        };
+        std::thread::sleep(Duration::from_millis(10));
+        clock.advance_to(100);
+        assert!(waiter.join().unwrap());
+    }
+
+    #[test]
+    fn test_wall_clock() {
+        let clock = WallClock::new();
+        let now = clock.now_ns();
+        assert!(now > 1_600_000_000_000_000_000);
+        assert!(clock.wait_until(now, Duration::ZERO));
+        assert!(!clock.wait_until(now + 60_000_000_000, Duration::from_millis(1)));
+    }
+}
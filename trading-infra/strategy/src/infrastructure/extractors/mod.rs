@@ -5,6 +5,8 @@
 
 mod order_book_extractor;
 mod price_extractor;
+mod trade_flow_extractor;
 
 pub use order_book_extractor::OrderBookExtractor;
 pub use price_extractor::PriceExtractor;
+pub use trade_flow_extractor::TradeFlowExtractor;
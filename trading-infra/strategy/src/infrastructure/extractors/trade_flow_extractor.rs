@@ -0,0 +1,155 @@
+//! Trade Flow Feature Extractor - Infrastructure implementation
+//!
+//! Implements TradeFeatureExtractorPort over the TradeReader abstraction.
+//! Windows end at the newest print, so features depend only on the tape
+//! and replay identically.
+
+use crate::application::ports::{TradeFeatureExtractorPort, TradeReader};
+use crate::domain::{BasisPoints, Features, Ratio};
+
+/// Trade flow feature extractor
+///
+/// Produces last trade, trade VWAP, aggressor volume and the signed volume
+/// of the most recent bucket over a lookback window.
+#[derive(Debug, Clone)]
+pub struct TradeFlowExtractor {
+    /// Lookback window in nanoseconds
+    window_ns: u64,
+    /// Signed volume bucket width in nanoseconds
+    bucket_ns: u64,
+}
+
+impl TradeFlowExtractor {
+    /// Create with a 60s window and 1s buckets
+    pub fn new() -> Self {
+        Self::with_window(60_000_000_000, 1_000_000_000)
+    }
+
+    /// Create with a custom window and bucket width
+    pub fn with_window(window_ns: u64, bucket_ns: u64) -> Self {
+        Self {
+            window_ns: window_ns.max(1),
+            bucket_ns: bucket_ns.max(1),
+        }
+    }
+}
+
+impl Default for TradeFlowExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TradeFeatureExtractorPort for TradeFlowExtractor {
+    fn extract(&self, trades: &dyn TradeReader) -> Features {
+        let mut features = Features::new();
+
+        let Some(last) = trades.last_trade() else {
+            features.set("has_trades", 0.0);
+            return features;
+        };
+        features.set("has_trades", 1.0);
+        features.set("last_trade_price", last.price.to_f64());
+
+        let since_ns = last.timestamp_ns.saturating_sub(self.window_ns);
+        let window = trades.trades_since(since_ns);
+        features.set("trade_count", window.len() as f64);
+
+        let volume = trades.aggressor_volume_since(since_ns);
+        features.set("buy_volume", volume.buy.to_f64());
+        features.set("sell_volume", volume.sell.to_f64());
+        features.set("net_volume", volume.net().to_f64());
+        if let Some(imbalance) = volume.imbalance() {
+            features.set_ratio("aggressor_imbalance", Ratio::new(imbalance));
+        }
+
+        if let Some(vwap) = trades.vwap_since(since_ns) {
+            features.set("trade_vwap", vwap.to_f64());
+            if let Some(deviation) = BasisPoints::from_price_diff(last.price, vwap) {
+                features.set_bps("vwap_deviation_bps", deviation);
+            }
+        }
+
+        if let Some(bucket) = trades.volume_buckets_since(since_ns, self.bucket_ns).last() {
+            features.set("last_bucket_net_volume", bucket.volume.net().to_f64());
+        }
+
+        features
+    }
+
+    fn feature_names(&self) -> Vec<&'static str> {
+        vec![
+            "has_trades",
+            "last_trade_price",
+            "trade_count",
+            "buy_volume",
+            "sell_volume",
+            "net_volume",
+            "aggressor_imbalance",
+            "trade_vwap",
+            "vwap_deviation_bps",
+            "last_bucket_net_volume",
+        ]
+    }
+
+    fn name(&self) -> &'static str {
+        "trade_flow"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TradeTapeManager;
+    use trading_core::{Price, Quantity, TradeUpdate};
+
+    const SEC: u64 = 1_000_000_000;
+
+    fn record(tapes: &TradeTapeManager, id: u64, price: i64, qty: i64, sell: bool, ts_s: u64) {
+        let mut trade = TradeUpdate::new(
+            "binance",
+            "BTCUSDT",
+            id,
+            Price::from_int(price),
+            Quantity::from_int(qty),
+            sell,
+        );
+        trade.timestamp_ns = ts_s * SEC;
+        tapes.record(&trade);
+    }
+
+    #[test]
+    fn test_extract_trade_flow() {
+        let tapes = TradeTapeManager::new();
+        record(&tapes, 1, 90, 10, false, 0); // outside the window
+        record(&tapes, 2, 100, 3, false, 100);
+        record(&tapes, 3, 100, 1, true, 101);
+        record(&tapes, 4, 102, 2, false, 102);
+
+        let extractor = TradeFlowExtractor::with_window(10 * SEC, SEC);
+        let features = extractor.extract(&tapes.tape("binance", "BTCUSDT"));
+
+        assert_eq!(features.get("has_trades"), Some(1.0));
+        assert_eq!(features.get("trade_count"), Some(3.0));
+        assert_eq!(features.get("buy_volume"), Some(5.0));
+        assert_eq!(features.get("sell_volume"), Some(1.0));
+        assert_eq!(features.get("net_volume"), Some(4.0));
+        assert_eq!(features.get("last_bucket_net_volume"), Some(2.0));
+
+        let imbalance = features.get_ratio("aggressor_imbalance").unwrap();
+        assert!((imbalance.value() - 4.0 / 6.0).abs() < 1e-6);
+
+        // (300 + 100 + 204) / 6
+        let vwap = features.get("trade_vwap").unwrap();
+        assert!((vwap - 604.0 / 6.0).abs() < 1e-6);
+        assert!(features.get_bps("vwap_deviation_bps").unwrap().value() > 0.0);
+    }
+
+    #[test]
+    fn test_no_trades() {
+        let tapes = TradeTapeManager::new();
+        let features = TradeFlowExtractor::new().extract(&tapes.tape("binance", "ETHUSDT"));
+        assert_eq!(features.get("has_trades"), Some(0.0));
+        assert!(!features.contains("trade_count"));
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! The infrastructure layer provides concrete implementations of:
 //! - Adapters: Connect domain/application to external systems
+//! - Clock: Wall-clock and event-time clocks
 //! - Extractors: Feature extraction implementations
 //! - Strategies: Trading strategy implementations
 //! - Subscriber: Market data transport subscriber
@@ -10,11 +11,13 @@
 //! External frameworks and libraries are used here.
 
 pub mod adapters;
+pub mod clock;
 pub mod extractors;
 pub mod strategies;
 pub mod subscriber;
 
 pub use adapters::*;
+pub use clock::{EventClock, WallClock};
 pub use extractors::*;
 pub use strategies::*;
 pub use subscriber::MarketDataSubscriber;
@@ -0,0 +1,772 @@
+//! Cycle Arbitrage Strategy
+//!
+//! Triangular and cross-venue arbitrage over configured conversion cycles.
+//! A cycle is a list of legs, each buying or selling one book; the output
+//! asset of a leg is the input of the next and the last leg returns to the
+//! asset the first started from:
+//!
+//! ```text
+//! Triangular:  USDT ─buy BTCUSDT─► BTC ─buy ETHBTC─► ETH ─sell ETHUSDT─► USDT
+//! Cross-venue: USDT ─buy binance:BTCUSDT─► BTC ─sell kraken:BTCUSDT─► USDT
+//! ```
+//!
+//! # Strategy Logic
+//!
+//! 1. Skip cycles with a stale book: exchange timestamp older than
+//!    `max_book_age_ms`, or, for books without timestamps, no new
+//!    `last_update_id` within it
+//! 2. Walk each leg's depth, rounding to its lot size and taking the
+//!    venue's taker fee from what the leg receives
+//! 3. Try start amounts up to `max_start_amount` and keep the most
+//!    profitable executable size
+//! 4. Emit when the net edge after fees clears `min_edge_bps`: one atomic
+//!    multi-leg signal, high urgency, `signal_ttl_ms` to live
+//!
+//! Input a leg cannot use after lot rounding is counted as lost, so the
+//! edge is a lower bound. Leg ratios are the executable quantities in each leg's base asset, so
+//! the signal is sized at strength 1.
+
+use crate::application::ports::{
+    BookLevel, Clock, GeneratorConfig, MarketDataPort, OrderBookReader, ParamsError,
+    SignalGeneratorPort, SymbolKey,
+};
+use crate::domain::{Leg, Signal, SignalDirection, StrategyId, StrategyType, Urgency};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One conversion in a cycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CycleLeg {
+    pub exchange: String,
+    pub symbol: String,
+    /// `BUY` spends quote for base, `SELL` spends base for quote
+    pub side: SignalDirection,
+    /// Quantity increment of the book, in base units (0 for none)
+    #[serde(default)]
+    pub lot_size: f64,
+}
+
+impl CycleLeg {
+    pub fn buy(exchange: impl Into<String>, symbol: impl Into<String>, lot_size: f64) -> Self {
+        Self {
+            exchange: exchange.into(),
+            symbol: symbol.into(),
+            side: SignalDirection::Buy,
+            lot_size,
+        }
+    }
+
+    pub fn sell(exchange: impl Into<String>, symbol: impl Into<String>, lot_size: f64) -> Self {
+        Self {
+            exchange: exchange.into(),
+            symbol: symbol.into(),
+            side: SignalDirection::Sell,
+            lot_size,
+        }
+    }
+
+    pub fn key(&self) -> SymbolKey {
+        SymbolKey::new(&self.exchange, &self.symbol)
+    }
+}
+
+/// A named sequence of conversions back to the starting asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArbCycle {
+    pub name: String,
+    pub legs: Vec<CycleLeg>,
+}
+
+/// Configuration for the cycle arbitrage strategy
+///
+/// Fields left out of a config take their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CycleArbitrageConfig {
+    /// Cycles to evaluate on every tick
+    pub cycles: Vec<ArbCycle>,
+    /// Taker fee per exchange (bps)
+    pub taker_fee_bps: HashMap<String, f64>,
+    /// Taker fee for exchanges not in `taker_fee_bps` (bps)
+    pub default_taker_fee_bps: f64,
+    /// Minimum edge after fees to signal (bps)
+    pub min_edge_bps: f64,
+    /// Largest amount of the starting asset put through a cycle
+    pub max_start_amount: f64,
+    /// Start amounts tried between 0 and `max_start_amount`
+    pub size_steps: usize,
+    /// Book levels walked per leg
+    pub depth_levels: usize,
+    /// Books older than this are not traded (milliseconds)
+    pub max_book_age_ms: u64,
+    /// How long signals may be acted on (milliseconds)
+    pub signal_ttl_ms: u64,
+}
+
+impl Default for CycleArbitrageConfig {
+    fn default() -> Self {
+        Self {
+            cycles: Vec::new(),
+            taker_fee_bps: HashMap::new(),
+            default_taker_fee_bps: 10.0, // 10 bps taker
+            min_edge_bps: 2.0,           // 2 bps after fees
+            max_start_amount: 10_000.0,  // In the first leg's input asset
+            size_steps: 50,              // Sizes tried per cycle
+            depth_levels: 20,            // Levels walked per leg
+            max_book_age_ms: 500,        // Half a second
+            signal_ttl_ms: 100,          // Edge is gone quickly
+        }
+    }
+}
+
+/// What one leg does at a given size
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LegFill {
+    /// Base quantity traded
+    quantity: f64,
+    /// Quote amount paid or received before fees
+    notional: f64,
+    /// Last price reached in the book
+    worst_price: f64,
+    /// Asset handed to the next leg, after fees
+    output: f64,
+}
+
+impl LegFill {
+    fn vwap(&self) -> f64 {
+        self.notional / self.quantity
+    }
+}
+
+/// A cycle run at one start amount
+#[derive(Debug, Clone)]
+struct CycleRun {
+    /// Starting asset actually spent by the first leg
+    start: f64,
+    /// Starting asset received from the last leg
+    end: f64,
+    fills: Vec<LegFill>,
+}
+
+impl CycleRun {
+    fn profit(&self) -> f64 {
+        self.end - self.start
+    }
+
+    fn edge_bps(&self) -> f64 {
+        if self.start > 0.0 {
+            self.profit() / self.start * 10_000.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A leg's book and costs, captured for one evaluation
+struct LegBook {
+    side: SignalDirection,
+    /// Asks for buys, bids for sells, best first, as `(price, size)`
+    levels: Vec<(f64, f64)>,
+    fee: f64,
+    lot_size: f64,
+}
+
+impl LegBook {
+    /// Trade `input` of the leg's input asset through the book
+    fn fill(&self, input: f64, with_fees: bool) -> Option<LegFill> {
+        let quantity = match self.side {
+            SignalDirection::Buy => {
+                // Base affordable with `input` quote
+                let mut remaining = input;
+                let mut quantity = 0.0;
+                for &(price, size) in &self.levels {
+                    let take = size.min(remaining / price);
+                    quantity += take;
+                    remaining -= take * price;
+                    if take < size {
+                        break;
+                    }
+                }
+                quantity
+            }
+            SignalDirection::Sell => input.min(self.levels.iter().map(|(_, size)| size).sum()),
+            SignalDirection::None => return None,
+        };
+        let quantity = round_to_lot(quantity, self.lot_size);
+        if quantity <= 0.0 {
+            return None;
+        }
+
+        let (notional, worst_price) = walk(&self.levels, quantity);
+        let gross = match self.side {
+            SignalDirection::Buy => quantity,
+            _ => notional,
+        };
+        let fee = if with_fees { self.fee } else { 0.0 };
+        Some(LegFill {
+            quantity,
+            notional,
+            worst_price,
+            output: gross * (1.0 - fee),
+        })
+    }
+
+    /// Input asset a fill consumed
+    fn spent(&self, fill: &LegFill) -> f64 {
+        match self.side {
+            SignalDirection::Buy => fill.notional,
+            _ => fill.quantity,
+        }
+    }
+}
+
+/// Quote notional and worst price of taking `quantity` from `levels`
+fn walk(levels: &[(f64, f64)], quantity: f64) -> (f64, f64) {
+    let mut remaining = quantity;
+    let mut notional = 0.0;
+    let mut worst_price = 0.0;
+    for &(price, size) in levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let take = size.min(remaining);
+        notional += take * price;
+        worst_price = price;
+        remaining -= take;
+    }
+    (notional, worst_price)
+}
+
+/// Round down to a multiple of `lot_size`
+fn round_to_lot(quantity: f64, lot_size: f64) -> f64 {
+    if lot_size > 0.0 {
+        // Tolerate float error just under a whole number of lots
+        ((quantity / lot_size) + 1e-9).floor() * lot_size
+    } else {
+        quantity
+    }
+}
+
+/// Cycle Arbitrage Strategy Implementation
+///
+/// Implements `SignalGeneratorPort` for use with the strategy engine.
+pub struct CycleArbitrage {
+    config: GeneratorConfig,
+    params: CycleArbitrageConfig,
+    /// Last update id seen per book and the clock time it first appeared
+    book_updates: HashMap<SymbolKey, (u64, u64)>,
+    /// Leg update ids each cycle last signalled on
+    signalled: HashMap<String, Vec<Option<u64>>>,
+    /// Tick counter for statistics
+    tick_count: u64,
+}
+
+impl CycleArbitrage {
+    /// Create a new cycle arbitrage strategy
+    pub fn new(config: GeneratorConfig, params: CycleArbitrageConfig) -> Self {
+        Self {
+            config,
+            params,
+            book_updates: HashMap::new(),
+            signalled: HashMap::new(),
+            tick_count: 0,
+        }
+    }
+
+    /// Create with default parameters for `cycles`, watching every leg's book
+    pub fn with_cycles(strategy_id: impl Into<String>, cycles: Vec<ArbCycle>) -> Self {
+        let mut symbols: Vec<SymbolKey> = Vec::new();
+        for leg in cycles.iter().flat_map(|c| &c.legs) {
+            if !symbols.contains(&leg.key()) {
+                symbols.push(leg.key());
+            }
+        }
+        let config = GeneratorConfig::new(
+            StrategyId::new(strategy_id),
+            StrategyType::TriangularArb,
+            symbols,
+        );
+        Self::new(
+            config,
+            CycleArbitrageConfig {
+                cycles,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Current strategy parameters
+    pub fn params(&self) -> &CycleArbitrageConfig {
+        &self.params
+    }
+
+    /// Replace parameters; cycles no longer configured are forgotten
+    pub fn set_params(&mut self, params: CycleArbitrageConfig) {
+        self.signalled
+            .retain(|name, _| params.cycles.iter().any(|c| &c.name == name));
+        self.params = params;
+    }
+
+    fn taker_fee(&self, exchange: &str) -> f64 {
+        self.params
+            .taker_fee_bps
+            .get(exchange)
+            .copied()
+            .unwrap_or(self.params.default_taker_fee_bps)
+            / 10_000.0
+    }
+
+    /// Track update ids so books without timestamps can age
+    fn observe_books<M: MarketDataPort>(&mut self, market_data: &M, now_ns: u64) {
+        let keys: Vec<SymbolKey> = self
+            .params
+            .cycles
+            .iter()
+            .flat_map(|c| c.legs.iter().map(CycleLeg::key))
+            .collect();
+        for key in keys {
+            let Some(update_id) = market_data.book(&key).last_update_id() else {
+                continue;
+            };
+            let entry = self.book_updates.entry(key).or_insert((update_id, now_ns));
+            if entry.0 != update_id {
+                *entry = (update_id, now_ns);
+            }
+        }
+    }
+
+    /// Check whether a book is too old to trade on
+    fn is_stale<B: OrderBookReader + ?Sized>(
+        &self,
+        key: &SymbolKey,
+        book: &B,
+        now_ns: u64,
+    ) -> bool {
+        let max_age_ns = self.params.max_book_age_ms * 1_000_000;
+        if let Some(updated_us) = book.last_update_time() {
+            return now_ns.saturating_sub(updated_us * 1_000) > max_age_ns;
+        }
+        match self.book_updates.get(key) {
+            Some(&(_, seen_ns)) => now_ns.saturating_sub(seen_ns) > max_age_ns,
+            None => true,
+        }
+    }
+
+    /// Capture the leg books, or `None` if any is missing or stale
+    fn leg_books<M: MarketDataPort>(
+        &self,
+        cycle: &ArbCycle,
+        market_data: &M,
+        now_ns: u64,
+    ) -> Option<(Vec<LegBook>, Vec<Option<u64>>)> {
+        let mut books = Vec::with_capacity(cycle.legs.len());
+        let mut update_ids = Vec::with_capacity(cycle.legs.len());
+        for leg in &cycle.legs {
+            let key = leg.key();
+            let book = market_data.book(&key);
+            if !book.is_initialized() || self.is_stale(&key, book.as_ref(), now_ns) {
+                return None;
+            }
+            let levels = match leg.side {
+                SignalDirection::Buy => book.ask_levels(self.params.depth_levels),
+                SignalDirection::Sell => book.bid_levels(self.params.depth_levels),
+                SignalDirection::None => return None,
+            };
+            if levels.is_empty() {
+                return None;
+            }
+            update_ids.push(book.last_update_id());
+            books.push(LegBook {
+                side: leg.side,
+                levels: levels
+                    .iter()
+                    .map(|l: &BookLevel| (l.price.to_f64(), l.size.to_f64()))
+                    .filter(|(price, size)| *price > 0.0 && *size > 0.0)
+                    .collect(),
+                fee: self.taker_fee(&leg.exchange),
+                lot_size: leg.lot_size,
+            });
+        }
+        Some((books, update_ids))
+    }
+
+    /// Put `start` through every leg
+    fn run_cycle(books: &[LegBook], start: f64, with_fees: bool) -> Option<CycleRun> {
+        let mut amount = start;
+        let mut fills = Vec::with_capacity(books.len());
+        for book in books {
+            let fill = book.fill(amount, with_fees)?;
+            amount = fill.output;
+            fills.push(fill);
+        }
+        Some(CycleRun {
+            start: books[0].spent(&fills[0]),
+            end: amount,
+            fills,
+        })
+    }
+
+    /// Most profitable executable run of a cycle
+    fn best_run(&self, books: &[LegBook]) -> Option<CycleRun> {
+        let steps = self.params.size_steps.max(1);
+        (1..=steps)
+            .filter_map(|step| {
+                let start = self.params.max_start_amount * step as f64 / steps as f64;
+                Self::run_cycle(books, start, true)
+            })
+            .max_by(|a, b| a.profit().total_cmp(&b.profit()))
+    }
+
+    /// Evaluate a cycle and build its signal if the edge is there
+    fn evaluate<M: MarketDataPort>(
+        &mut self,
+        cycle: &ArbCycle,
+        market_data: &M,
+        now_ns: u64,
+    ) -> Option<Signal> {
+        let (books, update_ids) = self.leg_books(cycle, market_data, now_ns)?;
+        // One signal per state of the books
+        if self.signalled.get(&cycle.name) == Some(&update_ids) {
+            return None;
+        }
+
+        let run = self.best_run(&books)?;
+        let net_edge_bps = run.edge_bps();
+        if run.profit() <= 0.0 || net_edge_bps < self.params.min_edge_bps {
+            return None;
+        }
+        let gross_edge_bps = Self::run_cycle(&books, run.start, false)
+            .map(|r| r.edge_bps())
+            .unwrap_or(net_edge_bps);
+
+        self.signalled.insert(cycle.name.clone(), update_ids);
+        Some(self.build_signal(cycle, &run, gross_edge_bps, now_ns))
+    }
+
+    fn build_signal(
+        &self,
+        cycle: &ArbCycle,
+        run: &CycleRun,
+        gross_edge_bps: f64,
+        now_ns: u64,
+    ) -> Signal {
+        let net_edge_bps = run.edge_bps();
+        let first = &cycle.legs[0];
+        let first_fill = &run.fills[0];
+
+        let mut features = HashMap::new();
+        features.insert("gross_edge_bps".to_string(), gross_edge_bps);
+        features.insert("net_edge_bps".to_string(), net_edge_bps);
+        features.insert("start_amount".to_string(), run.start);
+        features.insert("end_amount".to_string(), run.end);
+        for (i, fill) in run.fills.iter().enumerate() {
+            features.insert(format!("leg{}_vwap", i), fill.vwap());
+            features.insert(format!("leg{}_worst_price", i), fill.worst_price);
+        }
+
+        let legs = cycle
+            .legs
+            .iter()
+            .zip(&run.fills)
+            .map(|(leg, fill)| Leg::new(&leg.symbol, leg.side, fill.quantity, &leg.exchange))
+            .collect();
+
+        // Confidence grows with the edge's margin over the threshold
+        let confidence = if self.params.min_edge_bps > 0.0 {
+            (net_edge_bps / (2.0 * self.params.min_edge_bps)).min(1.0)
+        } else {
+            1.0
+        };
+
+        Signal::builder(
+            self.config.strategy_id.clone(),
+            self.config.strategy_type,
+            first.key().to_string(),
+        )
+        .direction(first.side)
+        .strength(1.0)
+        .confidence(confidence)
+        .urgency(Urgency::high()) // Edge is gone at the next book update
+        .prices(
+            trading_core::Price::from_f64(first_fill.vwap()),
+            trading_core::Price::from_f64(first_fill.vwap()),
+        )
+        .entry_price(trading_core::Price::from_f64(first_fill.worst_price))
+        .legs(legs)
+        .expected_edge_bps(net_edge_bps)
+        .ttl_ms(self.params.signal_ttl_ms)
+        .features(features)
+        .model_version("cycle_arb_v1.0")
+        .timestamp_ms(now_ns / 1_000_000)
+        .build()
+    }
+}
+
+impl SignalGeneratorPort for CycleArbitrage {
+    fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+        &mut self,
+        market_data: &M,
+        clock: &C,
+    ) -> Vec<Signal> {
+        self.tick_count += 1;
+        let now_ns = clock.now_ns();
+        self.observe_books(market_data, now_ns);
+
+        // Clone cycles to avoid borrow issues
+        let cycles = self.params.cycles.clone();
+        cycles
+            .iter()
+            .filter(|cycle| !cycle.legs.is_empty())
+            .filter_map(|cycle| self.evaluate(cycle, market_data, now_ns))
+            .collect()
+    }
+
+    fn update_params(&mut self, params: &serde_json::Value) -> Result<(), ParamsError> {
+        let params = CycleArbitrageConfig::deserialize(params)
+            .map_err(|e| ParamsError::new(format!("invalid cycle arbitrage params: {}", e)))?;
+        self.set_params(params);
+        Ok(())
+    }
+
+    fn on_start(&mut self) {
+        for leg in self.params.cycles.iter().flat_map(|c| &c.legs) {
+            if !self.config.symbols.contains(&leg.key()) {
+                tracing::warn!(
+                    "CycleArbitrage strategy '{}' trades {} but does not watch it",
+                    self.config.strategy_id,
+                    leg.key()
+                );
+            }
+        }
+        tracing::info!(
+            "CycleArbitrage strategy '{}' starting with {} cycles",
+            self.config.strategy_id,
+            self.params.cycles.len()
+        );
+    }
+
+    fn on_stop(&mut self) {
+        tracing::info!(
+            "CycleArbitrage strategy '{}' stopping after {} ticks",
+            self.config.strategy_id,
+            self.tick_count
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrderBookManager;
+    use crate::infrastructure::{EventClock, MarketDataAdapter};
+    use std::time::Duration;
+    use trading_core::{CompactLevel, OrderBookSnapshot, Price, Quantity};
+
+    const NOW_NS: u64 = 1_700_000_000_000_000_000;
+
+    fn level(price: f64, size: f64) -> CompactLevel {
+        CompactLevel::from_types(Price::from_f64(price), Quantity::from_f64(size))
+    }
+
+    fn apply(
+        books: &OrderBookManager,
+        symbol: (&str, &str),
+        update_id: u64,
+        timestamp_ns: u64,
+        bids: Vec<CompactLevel>,
+        asks: Vec<CompactLevel>,
+    ) {
+        let mut snapshot = OrderBookSnapshot::new(symbol.0, symbol.1, update_id)
+            .with_bids(bids)
+            .with_asks(asks);
+        snapshot.timestamp_ns = timestamp_ns;
+        books.apply_snapshot(&snapshot);
+    }
+
+    fn triangle() -> ArbCycle {
+        ArbCycle {
+            name: "usdt-btc-eth".to_string(),
+            legs: vec![
+                CycleLeg::buy("binance", "BTCUSDT", 0.001),
+                CycleLeg::buy("binance", "ETHBTC", 0.01),
+                CycleLeg::sell("binance", "ETHUSDT", 0.01),
+            ],
+        }
+    }
+
+    fn strategy(cycles: Vec<ArbCycle>) -> CycleArbitrage {
+        let mut strategy = CycleArbitrage::with_cycles("arb", cycles);
+        strategy.set_params(CycleArbitrageConfig {
+            default_taker_fee_bps: 5.0,
+            max_start_amount: 100_000.0,
+            size_steps: 100,
+            ..strategy.params().clone()
+        });
+        strategy
+    }
+
+    /// BTCUSDT * ETHBTC implies ETHUSDT at 50_000 * 0.05 = 2_500
+    fn triangle_books(books: &OrderBookManager, eth_bid: f64, update_id: u64, timestamp_ns: u64) {
+        apply(
+            books,
+            ("binance", "BTCUSDT"),
+            update_id,
+            timestamp_ns,
+            vec![level(49_990.0, 5.0)],
+            vec![level(50_000.0, 0.5), level(50_100.0, 1.0)],
+        );
+        apply(
+            books,
+            ("binance", "ETHBTC"),
+            update_id,
+            timestamp_ns,
+            vec![level(0.0499, 100.0)],
+            vec![level(0.05, 100.0)],
+        );
+        apply(
+            books,
+            ("binance", "ETHUSDT"),
+            update_id,
+            timestamp_ns,
+            vec![level(eth_bid, 8.0), level(eth_bid - 5.0, 100.0)],
+            vec![level(eth_bid + 1.0, 100.0)],
+        );
+    }
+
+    #[test]
+    fn test_triangular_opportunity() {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let clock = EventClock::new(NOW_NS);
+        // 2_510 vs 2_500 implied: 40 bps gross, 25 bps net of three 5 bps fees
+        triangle_books(&books, 2_510.0, 1, NOW_NS);
+
+        let mut strategy = strategy(vec![triangle()]);
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals.len(), 1);
+        let signal = &signals[0];
+
+        assert_eq!(signal.symbol, "binance:BTCUSDT");
+        assert_eq!(signal.direction, SignalDirection::Buy);
+        assert_eq!(signal.urgency, Urgency::high());
+        assert_eq!(signal.ttl_ms, Some(100));
+        assert_eq!(signal.legs.len(), 3);
+        assert_eq!(signal.legs[2].direction, SignalDirection::Sell);
+
+        // Depth caps the size: only the 0.5 BTC at 50_000 is worth taking
+        let btc = signal.legs[0].ratio_f64();
+        let eth = signal.legs[1].ratio_f64();
+        assert!((btc - 0.5).abs() < 0.01, "BTC {}", btc);
+        // The ETH fee comes off what is sold, and lot sizes hold
+        let sold = signal.legs[2].ratio_f64();
+        assert!(sold < eth && sold > eth - 0.02, "ETH {} -> {}", eth, sold);
+        for qty in [btc, eth, sold] {
+            assert!(((qty / 0.001).round() * 0.001 - qty).abs() < 1e-6);
+        }
+
+        let net = signal.expected_edge_bps.unwrap();
+        // Less than 25 bps: ETH dust left by fees and lot rounding counts as lost
+        assert!(net > 5.0 && net < 25.0, "net edge {}", net);
+        assert!(signal.features["gross_edge_bps"] > net);
+
+        // Same books, no repeat
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+    }
+
+    #[test]
+    fn test_fees_remove_thin_edge() {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        // 2_503 vs 2_500: 12 bps gross, less than 15 bps of fees
+        triangle_books(&books, 2_503.0, 1, NOW_NS);
+
+        let mut strategy = strategy(vec![triangle()]);
+        assert!(
+            strategy
+                .on_tick(&market_data, &EventClock::new(NOW_NS))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cross_venue_with_venue_fees() {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        apply(
+            &books,
+            ("binance", "BTCUSDT"),
+            1,
+            NOW_NS,
+            vec![level(49_990.0, 1.0)],
+            vec![level(50_000.0, 1.0)],
+        );
+        apply(
+            &books,
+            ("kraken", "BTCUSDT"),
+            1,
+            NOW_NS,
+            vec![level(50_100.0, 0.3)],
+            vec![level(50_110.0, 1.0)],
+        );
+        let cycle = ArbCycle {
+            name: "btc-binance-kraken".to_string(),
+            legs: vec![
+                CycleLeg::buy("binance", "BTCUSDT", 0.0001),
+                CycleLeg::sell("kraken", "BTCUSDT", 0.0001),
+            ],
+        };
+
+        // 20 bps gross; kraken's 16 bps and binance's 4 bps take all of it
+        let mut strategy = strategy(vec![cycle]);
+        let mut params = strategy.params().clone();
+        params.taker_fee_bps =
+            HashMap::from([("binance".to_string(), 4.0), ("kraken".to_string(), 16.0)]);
+        strategy.set_params(params.clone());
+        let clock = EventClock::new(NOW_NS);
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+
+        params.taker_fee_bps.insert("kraken".to_string(), 2.0);
+        strategy.set_params(params);
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].legs[1].venue, "kraken");
+        // Kraken's bid depth limits the size
+        assert!((signals[0].legs[0].ratio_f64() - 0.3).abs() < 1e-6);
+        let net = signals[0].expected_edge_bps.unwrap();
+        // 14 bps less the BTC dust the buy fee leaves unsold
+        assert!(net > 10.0 && net < 14.0, "net edge {}", net);
+    }
+
+    #[test]
+    fn test_stale_books_are_skipped() {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let mut strategy = strategy(vec![triangle()]);
+
+        // Exchange timestamps older than the limit
+        triangle_books(&books, 2_510.0, 1, NOW_NS);
+        let clock = EventClock::new(NOW_NS + 501_000_000);
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+
+        // Without timestamps, a book ages from its last new update id
+        triangle_books(&books, 2_510.0, 2, 0);
+        assert_eq!(strategy.on_tick(&market_data, &clock).len(), 1);
+        clock.advance(Duration::from_millis(501));
+        apply(
+            &books,
+            ("binance", "ETHUSDT"),
+            3,
+            0,
+            vec![level(2_510.0, 9.0), level(2_505.0, 100.0)],
+            vec![level(2_511.0, 100.0)],
+        );
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+
+        triangle_books(&books, 2_510.0, 4, 0);
+        assert_eq!(strategy.on_tick(&market_data, &clock).len(), 1);
+    }
+}
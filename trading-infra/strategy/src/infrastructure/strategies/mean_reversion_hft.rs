@@ -16,16 +16,19 @@
 //! 5. Confidence based on imbalance strength
 
 use crate::application::ports::{
-    GeneratorConfig, MarketDataPort, OrderBookReader, SignalGeneratorPort, SymbolKey,
+    Clock, GeneratorConfig, MarketDataPort, OrderBookReader, ParamsError, SignalGeneratorPort,
+    SymbolKey,
 };
 use crate::domain::{Signal, SignalDirection, StrategyId, StrategyType, Urgency};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
 use trading_core::{Price, RollingStats};
 
 /// Configuration for the Mean Reversion HFT strategy
+///
+/// Fields left out of a config take their defaults.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeanReversionConfig {
     /// Minimum microprice skew (in bps) to generate signal
     pub min_skew_bps: f64,
@@ -65,8 +68,8 @@ pub struct MeanReversionHFT {
     params: MeanReversionConfig,
     /// Rolling statistics for each symbol's mid price
     price_stats: HashMap<String, RollingStats>,
-    /// Last signal time per symbol (for cooldown)
-    last_signal_time: HashMap<String, Instant>,
+    /// Last signal time per symbol in clock nanoseconds (for cooldown)
+    last_signal_ns: HashMap<String, u64>,
     /// Tick counter for statistics
     tick_count: u64,
 }
@@ -78,7 +81,7 @@ impl MeanReversionHFT {
             config,
             params,
             price_stats: HashMap::new(),
-            last_signal_time: HashMap::new(),
+            last_signal_ns: HashMap::new(),
             tick_count: 0,
         }
     }
@@ -93,11 +96,34 @@ impl MeanReversionHFT {
         Self::new(config, MeanReversionConfig::default())
     }
 
+    /// Current strategy parameters
+    pub fn params(&self) -> &MeanReversionConfig {
+        &self.params
+    }
+
+    /// Replace parameters, keeping rolling statistics and cooldowns
+    ///
+    /// A new `rolling_window` keeps the most recent prices that fit in it.
+    pub fn set_params(&mut self, params: MeanReversionConfig) {
+        if params.rolling_window != self.params.rolling_window {
+            for stats in self.price_stats.values_mut() {
+                let mut resized = RollingStats::new(params.rolling_window, stats.decimals());
+                let values = stats.values();
+                let keep = values.len().min(resized.window_size());
+                for &value in &values[values.len() - keep..] {
+                    resized.push(value);
+                }
+                *stats = resized;
+            }
+        }
+        self.params = params;
+    }
+
     /// Check if cooldown period has elapsed for a symbol
-    fn is_cooldown_elapsed(&self, symbol: &str) -> bool {
-        match self.last_signal_time.get(symbol) {
-            Some(last_time) => {
-                last_time.elapsed().as_millis() as u64 >= self.params.signal_cooldown_ms
+    fn is_cooldown_elapsed(&self, symbol: &str, now_ns: u64) -> bool {
+        match self.last_signal_ns.get(symbol) {
+            Some(&last_ns) => {
+                now_ns.saturating_sub(last_ns) >= self.params.signal_cooldown_ms * 1_000_000
             }
             None => true,
         }
@@ -146,6 +172,7 @@ impl MeanReversionHFT {
         &mut self,
         symbol_key: &SymbolKey,
         market_data: &M,
+        now_ns: u64,
     ) -> Option<Signal> {
         let book = market_data.book(symbol_key);
 
@@ -202,7 +229,7 @@ impl MeanReversionHFT {
         }
 
         // Check cooldown
-        if !self.is_cooldown_elapsed(&symbol_key.symbol) {
+        if !self.is_cooldown_elapsed(&symbol_key.symbol, now_ns) {
             return None;
         }
 
@@ -254,8 +281,8 @@ impl MeanReversionHFT {
         }
 
         // Record signal time
-        self.last_signal_time
-            .insert(symbol_key.symbol.clone(), Instant::now());
+        self.last_signal_ns
+            .insert(symbol_key.symbol.clone(), now_ns);
 
         // Build features map
         let mut features = HashMap::new();
@@ -283,6 +310,7 @@ impl MeanReversionHFT {
             .expected_edge_bps(abs_skew)
             .features(features)
             .model_version("mean_reversion_hft_v1.0")
+            .timestamp_ms(now_ns / 1_000_000)
             .build(),
         )
     }
@@ -293,8 +321,13 @@ impl SignalGeneratorPort for MeanReversionHFT {
         &self.config
     }
 
-    fn on_tick<M: MarketDataPort>(&mut self, market_data: &M) -> Vec<Signal> {
+    fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+        &mut self,
+        market_data: &M,
+        clock: &C,
+    ) -> Vec<Signal> {
         self.tick_count += 1;
+        let now_ns = clock.now_ns();
 
         let mut signals = Vec::new();
 
@@ -302,7 +335,7 @@ impl SignalGeneratorPort for MeanReversionHFT {
         let symbols: Vec<SymbolKey> = self.config.symbols.clone();
 
         for symbol_key in &symbols {
-            if let Some(signal) = self.generate_signal_for_symbol(symbol_key, market_data) {
+            if let Some(signal) = self.generate_signal_for_symbol(symbol_key, market_data, now_ns) {
                 signals.push(signal);
             }
         }
@@ -310,6 +343,13 @@ impl SignalGeneratorPort for MeanReversionHFT {
         signals
     }
 
+    fn update_params(&mut self, params: &serde_json::Value) -> Result<(), ParamsError> {
+        let params = MeanReversionConfig::deserialize(params)
+            .map_err(|e| ParamsError::new(format!("invalid mean reversion params: {}", e)))?;
+        self.set_params(params);
+        Ok(())
+    }
+
     fn on_start(&mut self) {
         tracing::info!(
             "MeanReversionHFT strategy '{}' starting with {} symbols",
@@ -331,7 +371,9 @@ impl SignalGeneratorPort for MeanReversionHFT {
 mod tests {
     use super::*;
     use crate::application::ports::{BookLevel, OrderBookReader};
+    use crate::infrastructure::EventClock;
     use std::sync::Arc;
+    use std::time::Duration;
     use trading_core::Quantity;
 
     /// Mock order book for testing
@@ -448,7 +490,7 @@ mod tests {
         );
         let market_data = MockMarketData { books };
 
-        let signals = strategy.on_tick(&market_data);
+        let signals = strategy.on_tick(&market_data, &EventClock::new(0));
         assert!(
             signals.is_empty(),
             "Should not generate signal with balanced book"
@@ -474,7 +516,7 @@ mod tests {
         );
         let market_data = MockMarketData { books };
 
-        let signals = strategy.on_tick(&market_data);
+        let signals = strategy.on_tick(&market_data, &EventClock::new(0));
 
         // Signal should be generated if conditions are met
         // The skew might be below threshold in this example, so let's check
@@ -497,15 +539,60 @@ mod tests {
         );
         let market_data = MockMarketData { books };
 
-        // First tick might generate a signal
-        let _signals1 = strategy.on_tick(&market_data);
+        let clock = EventClock::new(1_000_000_000);
+        let signals1 = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals1.len(), 1);
+        assert_eq!(signals1[0].timestamp_ms, 1_000);
 
         // Immediate second tick should be blocked by cooldown
-        let signals2 = strategy.on_tick(&market_data);
+        let signals2 = strategy.on_tick(&market_data, &clock);
         assert!(
             signals2.is_empty(),
             "Cooldown should prevent immediate second signal"
         );
+
+        // The cooldown runs on the clock, not on system time
+        clock.advance(Duration::from_millis(99));
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+        clock.advance(Duration::from_millis(1));
+        let signals3 = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals3.len(), 1);
+        assert_eq!(signals3[0].timestamp_ms, 1_100);
+    }
+
+    #[test]
+    fn test_update_params_keeps_rolling_state() {
+        let mut strategy =
+            MeanReversionHFT::with_defaults("test_mr", vec![SymbolKey::new("binance", "BTCUSDT")]);
+        for price in 100..110 {
+            strategy.update_stats("BTCUSDT", Price::from_int(price));
+        }
+
+        let params = serde_json::json!({
+            "min_skew_bps": 5.0,
+            "max_skew_bps": 40.0,
+            "min_imbalance": 0.2,
+            "z_score_threshold": 2.0,
+            "half_life_seconds": 1.0,
+            "signal_cooldown_ms": 250,
+            "rolling_window": 4
+        });
+        strategy.update_params(&params).unwrap();
+
+        assert_eq!(strategy.params().min_skew_bps, 5.0);
+        assert_eq!(strategy.params().signal_cooldown_ms, 250);
+        let stats = &strategy.price_stats["BTCUSDT"];
+        assert_eq!(stats.window_size(), 4);
+        assert_eq!(stats.last(), Some(Price::from_int(109).raw()));
+        assert_eq!(stats.first(), Some(Price::from_int(106).raw()));
+
+        // A bad update leaves the running parameters alone
+        assert!(
+            strategy
+                .update_params(&serde_json::json!({ "min_skew_bps": "x" }))
+                .is_err()
+        );
+        assert_eq!(strategy.params().min_skew_bps, 5.0);
     }
 
     #[test]
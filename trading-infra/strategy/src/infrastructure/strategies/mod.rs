@@ -7,7 +7,23 @@
 //!
 //! - `MeanReversionHFT`: High-frequency mean reversion strategy based on
 //!   microprice deviation and order book imbalance.
+//! - `CycleArbitrage`: Triangular and cross-venue arbitrage walking book
+//!   depth, net of per-venue taker fees.
+//! - `ModelStrategy`: Trades the score of a trained model loaded from a file,
+//!   hot-swapped when the file changes.
+//! - `OrderFlowStrategy`: Short-horizon direction from multi-level order
+//!   flow imbalance, queue depletion and trade flow through a linear model.
+//! - `PairsTrading`: Statistical arbitrage on a cointegrated pair with a
+//!   Kalman-filter hedge ratio.
 
+mod cycle_arbitrage;
 mod mean_reversion_hft;
+mod model_strategy;
+mod order_flow;
+mod pairs_trading;
 
+pub use cycle_arbitrage::{ArbCycle, CycleArbitrage, CycleArbitrageConfig, CycleLeg};
 pub use mean_reversion_hft::{MeanReversionConfig, MeanReversionHFT};
+pub use model_strategy::{ModelStrategy, ModelStrategyConfig};
+pub use order_flow::{LinearReturnModel, OrderFlowConfig, OrderFlowStrategy};
+pub use pairs_trading::{PairsTrading, PairsTradingConfig};
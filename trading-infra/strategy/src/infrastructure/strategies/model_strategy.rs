@@ -0,0 +1,510 @@
+//! Model Strategy
+//!
+//! Trades the score of a trained model loaded from `model_path`. Each tick
+//! the symbol's book, and trades when available, go through the feature
+//! extractors and the model; signals carry the model's version.
+//!
+//! # Strategy Logic
+//!
+//! 1. Features: `OrderBookExtractor` over `depth_levels`, plus
+//!    `TradeFlowExtractor` over `trade_window_ms` when `use_trades` is set
+//! 2. Score with the current model:
+//!    - `return_bps` models: expected edge is the score, confidence assumes
+//!      normal residuals of `residual_std_bps`
+//!    - `up_probability` models: expected edge is
+//!      `(2p - 1) * expected_move_bps`, confidence is |2p - 1|
+//! 3. Signal in the edge's direction when it clears `min_edge_bps` and
+//!    confidence clears `min_confidence`, at most once per
+//!    `signal_interval_ms` per symbol
+//!
+//! The model file is checked for changes every `reload_interval_ms` and
+//! re-read on every parameter reload; a model needing features the
+//! extractors don't produce is refused and the old model kept. With
+//! `training_log_path` set, features and the mid return over `horizon_ms`
+//! are appended there as JSON lines.
+
+use crate::application::ports::{
+    Clock, FeatureExtractionPipeline, GeneratorConfig, MarketDataPort, OrderBookReader,
+    ParamsError, SignalGeneratorPort, SymbolKey, TradeFeatureExtractorPort,
+};
+use crate::application::services::{ModelScore, ModelScorer};
+use crate::domain::{
+    ModelError, ModelTarget, Signal, SignalDirection, StrategyId, StrategyType, Urgency,
+    sign_confidence,
+};
+use crate::infrastructure::adapters::{JsonlTrainingLog, ModelFileWatcher};
+use crate::infrastructure::extractors::{OrderBookExtractor, TradeFlowExtractor};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use trading_core::Price;
+
+/// Configuration for the model strategy
+///
+/// Fields left out of a config take their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelStrategyConfig {
+    /// Model file to load
+    pub model_path: String,
+    /// How often to check the model file for changes (milliseconds, 0 = never)
+    pub reload_interval_ms: u64,
+    /// Book levels the order book features cover
+    pub depth_levels: usize,
+    /// Add trade flow features
+    pub use_trades: bool,
+    /// Trade flow lookback (milliseconds)
+    pub trade_window_ms: u64,
+    /// Horizon the model predicts over (milliseconds)
+    pub horizon_ms: u64,
+    /// Residual standard deviation of `return_bps` models (bps)
+    pub residual_std_bps: f64,
+    /// Move a certain `up_probability` model expects (bps)
+    pub expected_move_bps: f64,
+    /// Minimum expected edge to signal (bps)
+    pub min_edge_bps: f64,
+    /// Minimum confidence to signal
+    pub min_confidence: f64,
+    /// Minimum time between signals per symbol (milliseconds)
+    pub signal_interval_ms: u64,
+    /// JSON lines file to record features and outcomes to
+    pub training_log_path: Option<String>,
+}
+
+impl Default for ModelStrategyConfig {
+    fn default() -> Self {
+        Self {
+            model_path: String::new(),
+            reload_interval_ms: 1_000, // Check the file every second
+            depth_levels: 5,           // Top 5 levels
+            use_trades: true,          // Book and trade features
+            trade_window_ms: 60_000,   // 1 minute of trades
+            horizon_ms: 1_000,         // 1s ahead
+            residual_std_bps: 2.0,     // Typical 1s return noise
+            expected_move_bps: 2.0,    // Typical 1s move
+            min_edge_bps: 1.0,         // 1 bps expected edge
+            min_confidence: 0.3,       // ~65% right sign
+            signal_interval_ms: 500,   // 2 signals/sec per symbol
+            training_log_path: None,   // No training capture
+        }
+    }
+}
+
+impl ModelStrategyConfig {
+    /// Whether two configs extract the same features and log to the same place
+    fn same_scorer(&self, other: &Self) -> bool {
+        self.depth_levels == other.depth_levels
+            && self.use_trades == other.use_trades
+            && self.trade_window_ms == other.trade_window_ms
+            && self.horizon_ms == other.horizon_ms
+            && self.training_log_path == other.training_log_path
+    }
+}
+
+/// Model Strategy Implementation
+///
+/// Implements `SignalGeneratorPort` for use with the strategy engine.
+pub struct ModelStrategy {
+    config: GeneratorConfig,
+    params: ModelStrategyConfig,
+    scorer: ModelScorer,
+    watcher: ModelFileWatcher,
+    last_poll_ns: u64,
+    last_signal_ns: HashMap<SymbolKey, u64>,
+    /// Tick counter for statistics
+    tick_count: u64,
+}
+
+impl ModelStrategy {
+    /// Create a model strategy, loading its model file
+    pub fn new(config: GeneratorConfig, params: ModelStrategyConfig) -> Result<Self, ModelError> {
+        let mut watcher = ModelFileWatcher::new(&params.model_path);
+        let scorer = Self::build_scorer(&params, &mut watcher)?;
+        Ok(Self {
+            config,
+            params,
+            scorer,
+            watcher,
+            last_poll_ns: 0,
+            last_signal_ns: HashMap::new(),
+            tick_count: 0,
+        })
+    }
+
+    /// Create with default parameters for a model file
+    pub fn with_model_file(
+        strategy_id: impl Into<String>,
+        symbols: Vec<SymbolKey>,
+        model_path: impl Into<String>,
+    ) -> Result<Self, ModelError> {
+        let config =
+            GeneratorConfig::new(StrategyId::new(strategy_id), StrategyType::Model, symbols);
+        Self::new(
+            config,
+            ModelStrategyConfig {
+                model_path: model_path.into(),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Current strategy parameters
+    pub fn params(&self) -> &ModelStrategyConfig {
+        &self.params
+    }
+
+    /// Version of the model in use
+    pub fn model_version(&self) -> String {
+        self.scorer.model().load().version().to_string()
+    }
+
+    fn build_scorer(
+        params: &ModelStrategyConfig,
+        watcher: &mut ModelFileWatcher,
+    ) -> Result<ModelScorer, ModelError> {
+        let pipeline = FeatureExtractionPipeline::new().with_extractor(Arc::new(
+            OrderBookExtractor::with_depth(params.depth_levels),
+        ));
+        let trades = params.use_trades.then(|| {
+            Arc::new(TradeFlowExtractor::with_window(
+                params.trade_window_ms * 1_000_000,
+                1_000_000_000,
+            )) as Arc<dyn TradeFeatureExtractorPort>
+        });
+        let scorer = ModelScorer::with_extractors(pipeline, trades, watcher.load()?)?;
+
+        Ok(match &params.training_log_path {
+            Some(path) => {
+                let log = JsonlTrainingLog::open(path).map_err(|source| ModelError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                scorer.with_training_log(Box::new(log), params.horizon_ms * 1_000_000)
+            }
+            None => scorer,
+        })
+    }
+
+    /// Replace parameters and re-read the model file
+    ///
+    /// On error nothing changes, pending training samples included.
+    pub fn set_params(&mut self, params: ModelStrategyConfig) -> Result<(), ModelError> {
+        let mut watcher = ModelFileWatcher::new(&params.model_path);
+        if params.same_scorer(&self.params) {
+            // Keep pending training samples; only the model changes
+            self.scorer.swap_model(watcher.load()?)?;
+        } else {
+            let scorer = Self::build_scorer(&params, &mut watcher)?;
+            self.scorer.flush();
+            self.scorer = scorer;
+        }
+        self.watcher = watcher;
+        self.params = params;
+        Ok(())
+    }
+
+    /// Swap in the model file if it changed
+    fn poll_model(&mut self, now_ns: u64) {
+        let interval_ns = self.params.reload_interval_ms * 1_000_000;
+        if interval_ns == 0 || now_ns.saturating_sub(self.last_poll_ns) < interval_ns {
+            return;
+        }
+        self.last_poll_ns = now_ns;
+
+        let swapped = self
+            .watcher
+            .poll()
+            .and_then(|model| model.map(|m| self.scorer.swap_model(m)).transpose());
+        match swapped {
+            Ok(Some(old)) => tracing::info!(
+                "Model strategy '{}' swapped model {} for {}",
+                self.config.strategy_id,
+                old.version(),
+                self.model_version()
+            ),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Model strategy '{}' keeping model {}: {}",
+                self.config.strategy_id,
+                self.model_version(),
+                e
+            ),
+        }
+    }
+
+    /// Expected edge (bps) and confidence of a score
+    fn edge(&self, score: &ModelScore) -> (f64, f64) {
+        match score.target {
+            ModelTarget::ReturnBps => (
+                score.value,
+                sign_confidence(score.value, self.params.residual_std_bps),
+            ),
+            ModelTarget::UpProbability => {
+                let conviction = (2.0 * score.value - 1.0).clamp(-1.0, 1.0);
+                (conviction * self.params.expected_move_bps, conviction.abs())
+            }
+        }
+    }
+
+    /// Check if a symbol is out of its signal cooldown
+    fn can_signal(&self, key: &SymbolKey, now_ns: u64) -> bool {
+        match self.last_signal_ns.get(key) {
+            Some(&last_ns) => {
+                now_ns.saturating_sub(last_ns) >= self.params.signal_interval_ms * 1_000_000
+            }
+            None => true,
+        }
+    }
+
+    /// Generate a signal for a single symbol
+    fn generate_signal<M: MarketDataPort>(
+        &mut self,
+        key: &SymbolKey,
+        market_data: &M,
+        now_ns: u64,
+    ) -> Option<Signal> {
+        let book = market_data.book(key);
+        if !book.is_initialized() {
+            return None;
+        }
+        let trades = market_data.trades(key);
+        let score = self
+            .scorer
+            .score(key, book.as_ref(), trades.as_deref(), now_ns)?;
+
+        let (edge_bps, confidence) = self.edge(&score);
+        if edge_bps.abs() < self.params.min_edge_bps
+            || confidence < self.params.min_confidence
+            || !self.can_signal(key, now_ns)
+        {
+            return None;
+        }
+        self.last_signal_ns.insert(key.clone(), now_ns);
+
+        let direction = if edge_bps > 0.0 {
+            SignalDirection::Buy
+        } else {
+            SignalDirection::Sell
+        };
+        let mid = book.mid_price()?;
+        let fair_value = Price::from_f64(mid.to_f64() * (1.0 + edge_bps / 10_000.0));
+        let strength =
+            (edge_bps.abs() / (2.0 * self.params.min_edge_bps.max(f64::EPSILON))).min(1.0);
+
+        let mut features = score.features;
+        features.insert("model_score".to_string(), score.value);
+
+        let mut builder = Signal::builder(
+            self.config.strategy_id.clone(),
+            self.config.strategy_type,
+            key.to_string(),
+        )
+        .direction(direction)
+        .strength(strength)
+        .confidence(confidence)
+        .urgency(Urgency::medium())
+        .prices(mid, fair_value)
+        .expected_edge_bps(edge_bps.abs())
+        .half_life_seconds(self.params.horizon_ms as f64 / 1_000.0)
+        .ttl_ms(self.params.horizon_ms)
+        .features(features)
+        .model_version(score.model_version)
+        .timestamp_ms(now_ns / 1_000_000);
+        if score.target == ModelTarget::ReturnBps {
+            builder = builder.model_variance(self.params.residual_std_bps.powi(2));
+        }
+        Some(builder.build())
+    }
+}
+
+impl SignalGeneratorPort for ModelStrategy {
+    fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+        &mut self,
+        market_data: &M,
+        clock: &C,
+    ) -> Vec<Signal> {
+        self.tick_count += 1;
+        let now_ns = clock.now_ns();
+        self.poll_model(now_ns);
+
+        // Clone symbols to avoid borrow issues
+        let symbols: Vec<SymbolKey> = self.config.symbols.clone();
+        symbols
+            .iter()
+            .filter_map(|key| self.generate_signal(key, market_data, now_ns))
+            .collect()
+    }
+
+    fn update_params(&mut self, params: &serde_json::Value) -> Result<(), ParamsError> {
+        let params = ModelStrategyConfig::deserialize(params)
+            .map_err(|e| ParamsError::new(format!("invalid model strategy params: {}", e)))?;
+        self.set_params(params)
+            .map_err(|e| ParamsError::new(e.to_string()))
+    }
+
+    fn on_start(&mut self) {
+        tracing::info!(
+            "Model strategy '{}' starting with model {} from {}",
+            self.config.strategy_id,
+            self.model_version(),
+            self.watcher.path().display()
+        );
+    }
+
+    fn on_stop(&mut self) {
+        self.scorer.flush();
+        tracing::info!(
+            "Model strategy '{}' stopping after {} ticks",
+            self.config.strategy_id,
+            self.tick_count
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrderBookManager;
+    use crate::application::ports::TrainingSample;
+    use crate::infrastructure::{EventClock, MarketDataAdapter};
+    use std::path::Path;
+    use std::time::{Duration, SystemTime};
+    use trading_core::{CompactLevel, OrderBookSnapshot, Quantity};
+
+    const NOW_NS: u64 = 1_700_000_000_000_000_000;
+
+    fn key() -> SymbolKey {
+        SymbolKey::new("binance", "BTCUSDT")
+    }
+
+    fn book(books: &OrderBookManager, update_id: u64, bid: f64, bid_size: f64, ask_size: f64) {
+        let level = |price: f64, size: f64| {
+            CompactLevel::from_types(Price::from_f64(price), Quantity::from_f64(size))
+        };
+        books.apply_snapshot(
+            &OrderBookSnapshot::new("binance", "BTCUSDT", update_id)
+                .with_bids(vec![level(bid, bid_size)])
+                .with_asks(vec![level(bid + 1.0, ask_size)]),
+        );
+    }
+
+    /// Linear model on `imbalance`, written with a distinct mtime
+    fn write_model(path: &Path, version: &str, feature: &str, weight: f64, age: u64) {
+        std::fs::write(
+            path,
+            format!(
+                r#"{{ "version": "{}", "features": ["{}"],
+                      "model": {{ "type": "linear", "coefficients": {{ "{}": {} }} }} }}"#,
+                version, feature, feature, weight
+            ),
+        )
+        .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + age))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_scores_and_hot_swaps_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let model_path = dir.path().join("model.json");
+        write_model(&model_path, "imb_v1", "imbalance", 4.0, 0);
+
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let clock = EventClock::new(NOW_NS);
+        let mut strategy =
+            ModelStrategy::with_model_file("model", vec![key()], model_path.display().to_string())
+                .unwrap();
+
+        // Imbalance (30 - 10) / 40 = 0.5 scores 2 bps
+        book(&books, 1, 100.0, 30.0, 10.0);
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].direction, SignalDirection::Buy);
+        assert_eq!(signals[0].model_version, "imb_v1");
+        assert_eq!(signals[0].strategy_type, StrategyType::Model);
+        assert!((signals[0].expected_edge_bps.unwrap() - 2.0).abs() < 1e-9);
+        assert!((signals[0].confidence - 0.6827).abs() < 1e-3);
+        assert_eq!(signals[0].features["model_score"], 2.0);
+
+        // New file picked up on the next poll, with the sign flipped
+        write_model(&model_path, "imb_v2", "imbalance", -4.0, 1);
+        clock.advance(Duration::from_secs(1));
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals[0].direction, SignalDirection::Sell);
+        assert_eq!(signals[0].model_version, "imb_v2");
+
+        // A model the extractors can't feed is refused
+        write_model(&model_path, "bad_v3", "sentiment", 1.0, 2);
+        clock.advance(Duration::from_secs(1));
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals[0].model_version, "imb_v2");
+
+        // A parameter reload re-reads the file and reports the mismatch
+        let params = serde_json::json!({ "model_path": model_path });
+        let err = strategy.update_params(&params).unwrap_err();
+        assert!(err.message.contains("sentiment"));
+        write_model(&model_path, "imb_v4", "imbalance", 4.0, 3);
+        strategy.update_params(&params).unwrap();
+        assert_eq!(strategy.model_version(), "imb_v4");
+    }
+
+    #[test]
+    fn test_training_log_records_outcomes() {
+        let dir = tempfile::tempdir().unwrap();
+        let model_path = dir.path().join("model.json");
+        let log_path = dir.path().join("samples.jsonl");
+        write_model(&model_path, "imb_v1", "imbalance", 4.0, 0);
+
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let clock = EventClock::new(NOW_NS);
+        let config =
+            GeneratorConfig::new(StrategyId::new("model"), StrategyType::Model, vec![key()]);
+        let mut strategy = ModelStrategy::new(
+            config,
+            ModelStrategyConfig {
+                model_path: model_path.display().to_string(),
+                training_log_path: Some(log_path.display().to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        book(&books, 1, 100.0, 30.0, 10.0);
+        strategy.on_tick(&market_data, &clock);
+
+        // A rebuild that fails keeps the scorer and its pending sample
+        let params = ModelStrategyConfig {
+            model_path: dir.path().join("missing.json").display().to_string(),
+            depth_levels: 10,
+            ..strategy.params().clone()
+        };
+        assert!(strategy.set_params(params).is_err());
+        assert_eq!(strategy.params().depth_levels, 5);
+
+        clock.advance(Duration::from_secs(1));
+        book(&books, 2, 101.0, 10.0, 10.0);
+        strategy.on_tick(&market_data, &clock);
+        strategy.on_stop();
+
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        let samples: Vec<TrainingSample> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].model_version, "imb_v1");
+        assert_eq!(samples[0].features["imbalance"], 0.5);
+        // Mid 100.5 -> 101.5
+        assert!((samples[0].outcome_bps - 1.0 / 100.5 * 10_000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,674 @@
+//! Order Flow Strategy
+//!
+//! Short-horizon directional signals from order flow. The order book
+//! records the order flow imbalance and queue depletion of every applied
+//! delta; on each tick these are summed over a rolling window, combined
+//! with the trade tape's aggressor flow and fed to a linear model of the
+//! return over the next `horizon_ms`.
+//!
+//! # Features
+//!
+//! - `ofi_l1` .. `ofi_lN`: Order flow imbalance at each level over the
+//!   window (Cont, Kukanov and Stoikov), in units of the level's mean size
+//! - `ofi`: Mean of the per-level imbalances
+//! - `bid_depletion_rate`, `ask_depletion_rate`: Share of the best queue
+//!   taken off per second over the window
+//! - `depletion_imbalance`: Ask less bid depletion rate
+//! - `trade_imbalance`: (buy - sell) / total aggressor volume over
+//!   `trade_window_ms`
+//! - `vpin`: VPIN over the last `vpin_buckets` buckets of
+//!   `vpin_bucket_volume` traded
+//! - `spread_bps`: Current quoted spread
+//!
+//! # Strategy Logic
+//!
+//! 1. Expected return (bps) = `intercept + sum(weight * feature)`, with
+//!    missing features counted as zero
+//! 2. Confidence = probability the return has the predicted sign, taking
+//!    the model's residuals as normal with `residual_std_bps`
+//! 3. Signal in the predicted direction when |expected return| clears
+//!    `min_edge_bps` and confidence clears `min_confidence`, at most once
+//!    per `signal_interval_ms` per symbol
+
+use crate::application::ports::{
+    Clock, GeneratorConfig, MarketDataPort, OrderBookReader, ParamsError, SignalGeneratorPort,
+    SymbolKey, TradeReader,
+};
+use crate::domain::{
+    AggressorSide, Signal, SignalDirection, StrategyId, StrategyType, Urgency, sign_confidence,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use trading_core::{Price, Quantity, Vpin};
+
+/// Features the strategy computes besides the per-level `ofi_l{n}`
+const FEATURES: [&str; 7] = [
+    "ofi",
+    "bid_depletion_rate",
+    "ask_depletion_rate",
+    "depletion_imbalance",
+    "trade_imbalance",
+    "vpin",
+    "spread_bps",
+];
+
+/// Linear model of the short-horizon return in basis points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinearReturnModel {
+    /// Expected return with all features at zero (bps)
+    pub intercept: f64,
+    /// Weight per feature name (bps per unit of feature)
+    pub weights: HashMap<String, f64>,
+    /// Standard deviation of the model's residuals (bps)
+    pub residual_std_bps: f64,
+    /// Stamped on signals as their model version
+    pub version: String,
+}
+
+impl Default for LinearReturnModel {
+    fn default() -> Self {
+        Self {
+            intercept: 0.0,
+            weights: HashMap::from([
+                ("ofi".to_string(), 1.5),
+                ("depletion_imbalance".to_string(), 0.5),
+                ("trade_imbalance".to_string(), 1.0),
+            ]),
+            residual_std_bps: 2.0,
+            version: "ofi_linear_v1.0".to_string(),
+        }
+    }
+}
+
+impl LinearReturnModel {
+    /// Expected return for a set of features (bps)
+    pub fn predict(&self, features: &HashMap<String, f64>) -> f64 {
+        self.intercept
+            + self
+                .weights
+                .iter()
+                .map(|(name, weight)| weight * features.get(name).copied().unwrap_or(0.0))
+                .sum::<f64>()
+    }
+
+    /// Probability that the realised return has the sign of `expected_bps`
+    /// rescaled to 0-1, i.e. `2 * P(correct sign) - 1`
+    pub fn confidence(&self, expected_bps: f64) -> f64 {
+        sign_confidence(expected_bps, self.residual_std_bps)
+    }
+}
+
+/// Configuration for the order flow strategy
+///
+/// Fields left out of a config take their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderFlowConfig {
+    /// Book levels order flow imbalance is measured on, up to the levels
+    /// the order book records
+    pub depth_levels: usize,
+    /// Window book changes are accumulated over (milliseconds)
+    pub window_ms: u64,
+    /// Window for trade-sign imbalance (milliseconds)
+    pub trade_window_ms: u64,
+    /// Traded volume that closes a VPIN bucket (base asset units)
+    pub vpin_bucket_volume: f64,
+    /// VPIN buckets averaged over
+    pub vpin_buckets: u64,
+    /// Horizon the model predicts over (milliseconds)
+    pub horizon_ms: u64,
+    /// Return model
+    pub model: LinearReturnModel,
+    /// Minimum |expected return| to signal (bps)
+    pub min_edge_bps: f64,
+    /// Minimum confidence to signal
+    pub min_confidence: f64,
+    /// Minimum time between signals per symbol (milliseconds)
+    pub signal_interval_ms: u64,
+}
+
+impl Default for OrderFlowConfig {
+    fn default() -> Self {
+        Self {
+            depth_levels: 5,                     // Top 5 levels
+            window_ms: 1_000,                    // 1s of book changes
+            trade_window_ms: 5_000,              // 5s of trades
+            vpin_bucket_volume: 10.0,            // 10 units per bucket
+            vpin_buckets: 50,                    // 500 units of trading
+            horizon_ms: 1_000,                   // 1s ahead
+            model: LinearReturnModel::default(), // OFI, depletion and trade flow
+            min_edge_bps: 1.0,                   // 1 bps expected return
+            min_confidence: 0.3,                 // ~65% right sign
+            signal_interval_ms: 500,             // 2 signals/sec per symbol
+        }
+    }
+}
+
+impl OrderFlowConfig {
+    /// Check the model only weights features the strategy computes
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        let unknown: Vec<&str> = self
+            .model
+            .weights
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !self.is_feature(name))
+            .collect();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(ParamsError::new(format!(
+                "order flow model weights unknown features: {}",
+                unknown.join(", ")
+            )))
+        }
+    }
+
+    fn is_feature(&self, name: &str) -> bool {
+        FEATURES.contains(&name)
+            || name
+                .strip_prefix("ofi_l")
+                .and_then(|level| level.parse::<usize>().ok())
+                .is_some_and(|level| (1..=self.depth_levels).contains(&level))
+    }
+}
+
+/// Trade flow state of one symbol
+#[derive(Debug, Default)]
+struct SymbolFlow {
+    /// Built from the tape on first use and after a bucket change
+    vpin: Option<Vpin>,
+    /// (timestamp, trade id) of the last print fed to `vpin`
+    last_trade: Option<(u64, u64)>,
+    last_signal_ns: Option<u64>,
+}
+
+/// Order Flow Strategy Implementation
+///
+/// Implements `SignalGeneratorPort` for use with the strategy engine.
+pub struct OrderFlowStrategy {
+    config: GeneratorConfig,
+    params: OrderFlowConfig,
+    flows: HashMap<SymbolKey, SymbolFlow>,
+    /// Tick counter for statistics
+    tick_count: u64,
+}
+
+impl OrderFlowStrategy {
+    /// Create a new order flow strategy
+    pub fn new(config: GeneratorConfig, params: OrderFlowConfig) -> Self {
+        Self {
+            config,
+            params,
+            flows: HashMap::new(),
+            tick_count: 0,
+        }
+    }
+
+    /// Create with default parameters
+    pub fn with_defaults(strategy_id: impl Into<String>, symbols: Vec<SymbolKey>) -> Self {
+        let config = GeneratorConfig::new(
+            StrategyId::new(strategy_id),
+            StrategyType::OrderFlow,
+            symbols,
+        );
+        Self::new(config, OrderFlowConfig::default())
+    }
+
+    /// Current strategy parameters
+    pub fn params(&self) -> &OrderFlowConfig {
+        &self.params
+    }
+
+    /// Replace parameters, keeping the order flow seen so far
+    ///
+    /// A new VPIN bucketing is rebuilt from the prints still on the tape.
+    pub fn set_params(&mut self, params: OrderFlowConfig) {
+        if params.vpin_bucket_volume != self.params.vpin_bucket_volume
+            || params.vpin_buckets != self.params.vpin_buckets
+        {
+            for flow in self.flows.values_mut() {
+                flow.vpin = None;
+                flow.last_trade = None;
+            }
+        }
+        self.params = params;
+    }
+
+    /// Feed the prints since the last tick to the symbol's VPIN
+    fn observe_trades(&mut self, key: &SymbolKey, trades: &dyn TradeReader) {
+        let bucket_volume = Quantity::from_f64(self.params.vpin_bucket_volume).raw();
+        let buckets = self.params.vpin_buckets as usize;
+        let flow = self.flows.entry(key.clone()).or_default();
+        let vpin = flow
+            .vpin
+            .get_or_insert_with(|| Vpin::new(bucket_volume, buckets));
+
+        let since_ns = flow.last_trade.map_or(0, |(timestamp_ns, _)| timestamp_ns);
+        for print in trades.trades_since(since_ns) {
+            let id = Some((print.timestamp_ns, print.trade_id));
+            if id <= flow.last_trade {
+                continue;
+            }
+            vpin.record(print.quantity.raw(), print.aggressor == AggressorSide::Buy);
+            flow.last_trade = id;
+        }
+    }
+
+    /// Model features for a symbol, `None` without a book change in the window
+    fn features<M: MarketDataPort>(
+        &self,
+        key: &SymbolKey,
+        book: &M::BookReader,
+        market_data: &M,
+        now_ns: u64,
+    ) -> Option<HashMap<String, f64>> {
+        let window_ns = self.params.window_ms * 1_000_000;
+        let flow = book.flow_since(now_ns.saturating_sub(window_ns))?;
+        if flow.changes == 0 {
+            return None;
+        }
+        let mut features = HashMap::new();
+
+        let levels = self.params.depth_levels;
+        let ofi: Vec<f64> = (0..levels)
+            .map(|m| flow.ofi.get(m).copied().unwrap_or(0.0))
+            .collect();
+        for (m, value) in ofi.iter().enumerate() {
+            features.insert(format!("ofi_l{}", m + 1), *value);
+        }
+        let depth = book
+            .bid_levels(levels)
+            .len()
+            .min(book.ask_levels(levels).len());
+        let used = depth.min(levels).max(1);
+        features.insert(
+            "ofi".to_string(),
+            ofi[..used.min(levels)].iter().sum::<f64>() / used as f64,
+        );
+
+        let window_secs = (self.params.window_ms as f64 / 1_000.0).max(f64::EPSILON);
+        let bid_rate = flow.bid_depletion / window_secs;
+        let ask_rate = flow.ask_depletion / window_secs;
+        features.insert("bid_depletion_rate".to_string(), bid_rate);
+        features.insert("ask_depletion_rate".to_string(), ask_rate);
+        features.insert("depletion_imbalance".to_string(), ask_rate - bid_rate);
+
+        if let Some(trades) = market_data.trades(key) {
+            let since_ns = now_ns.saturating_sub(self.params.trade_window_ms * 1_000_000);
+            let imbalance = trades.aggressor_volume_since(since_ns).imbalance();
+            features.insert("trade_imbalance".to_string(), imbalance.unwrap_or(0.0));
+        }
+        if let Some(vpin) = self
+            .flows
+            .get(key)
+            .and_then(|f| f.vpin.as_ref())
+            .and_then(Vpin::value)
+        {
+            features.insert("vpin".to_string(), vpin);
+        }
+
+        if let (Some(bid), Some(ask), Some(mid)) =
+            (book.best_bid(), book.best_ask(), book.mid_price())
+            && mid.raw() > 0
+        {
+            features.insert(
+                "spread_bps".to_string(),
+                (ask.price.to_f64() - bid.price.to_f64()) / mid.to_f64() * 10_000.0,
+            );
+        }
+
+        Some(features)
+    }
+
+    /// Check if a symbol is out of its signal cooldown
+    fn can_signal(&self, key: &SymbolKey, now_ns: u64) -> bool {
+        match self.flows.get(key).and_then(|f| f.last_signal_ns) {
+            Some(last_ns) => {
+                now_ns.saturating_sub(last_ns) >= self.params.signal_interval_ms * 1_000_000
+            }
+            None => true,
+        }
+    }
+
+    /// Generate a signal for a single symbol
+    fn generate_signal<M: MarketDataPort>(
+        &mut self,
+        key: &SymbolKey,
+        market_data: &M,
+        now_ns: u64,
+    ) -> Option<Signal> {
+        let book = market_data.book(key);
+        if !book.is_initialized() {
+            return None;
+        }
+        if let Some(trades) = market_data.trades(key) {
+            self.observe_trades(key, trades.as_ref());
+        }
+        if !self.can_signal(key, now_ns) {
+            return None;
+        }
+
+        let features = self.features(key, book.as_ref(), market_data, now_ns)?;
+        let model = &self.params.model;
+        let expected_bps = model.predict(&features);
+        let confidence = model.confidence(expected_bps);
+        if expected_bps.abs() < self.params.min_edge_bps || confidence < self.params.min_confidence
+        {
+            return None;
+        }
+
+        let direction = if expected_bps > 0.0 {
+            SignalDirection::Buy
+        } else {
+            SignalDirection::Sell
+        };
+        let mid = book.mid_price()?;
+        let fair_value = Price::from_f64(mid.to_f64() * (1.0 + expected_bps / 10_000.0));
+        let strength =
+            (expected_bps.abs() / (2.0 * self.params.min_edge_bps.max(f64::EPSILON))).min(1.0);
+
+        self.flows.entry(key.clone()).or_default().last_signal_ns = Some(now_ns);
+
+        Some(
+            Signal::builder(
+                self.config.strategy_id.clone(),
+                self.config.strategy_type,
+                key.to_string(),
+            )
+            .direction(direction)
+            .strength(strength)
+            .confidence(confidence)
+            .urgency(Urgency::new(0.8)) // Flow edge lasts about the horizon
+            .prices(mid, fair_value)
+            .expected_edge_bps(expected_bps.abs())
+            .half_life_seconds(self.params.horizon_ms as f64 / 1_000.0)
+            .model_variance(model.residual_std_bps.powi(2))
+            .ttl_ms(self.params.horizon_ms)
+            .features(features)
+            .model_version(model.version.clone())
+            .timestamp_ms(now_ns / 1_000_000)
+            .build(),
+        )
+    }
+}
+
+impl SignalGeneratorPort for OrderFlowStrategy {
+    fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+        &mut self,
+        market_data: &M,
+        clock: &C,
+    ) -> Vec<Signal> {
+        self.tick_count += 1;
+        let now_ns = clock.now_ns();
+
+        // Clone symbols to avoid borrow issues
+        let symbols: Vec<SymbolKey> = self.config.symbols.clone();
+        symbols
+            .iter()
+            .filter_map(|key| self.generate_signal(key, market_data, now_ns))
+            .collect()
+    }
+
+    fn update_params(&mut self, params: &serde_json::Value) -> Result<(), ParamsError> {
+        let params = OrderFlowConfig::deserialize(params)
+            .map_err(|e| ParamsError::new(format!("invalid order flow params: {}", e)))?;
+        params.validate()?;
+        self.set_params(params);
+        Ok(())
+    }
+
+    fn on_start(&mut self) {
+        if let Err(e) = self.params.validate() {
+            tracing::warn!(
+                "OrderFlow strategy '{}': {}; those features read as zero",
+                self.config.strategy_id,
+                e.message
+            );
+        }
+        tracing::info!(
+            "OrderFlow strategy '{}' starting with {} symbols, model {}",
+            self.config.strategy_id,
+            self.config.symbols.len(),
+            self.params.model.version
+        );
+    }
+
+    fn on_stop(&mut self) {
+        tracing::info!(
+            "OrderFlow strategy '{}' stopping after {} ticks",
+            self.config.strategy_id,
+            self.tick_count
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{EventClock, MarketDataAdapter};
+    use crate::{OrderBookManager, TradeTapeManager};
+    use std::time::Duration;
+    use trading_core::{CompactLevel, DepthUpdate, OrderBookSnapshot, TradeUpdate};
+
+    const NOW_NS: u64 = 1_700_000_000_000_000_000;
+
+    fn key() -> SymbolKey {
+        SymbolKey::new("binance", "BTCUSDT")
+    }
+
+    fn level(price: f64, size: f64) -> CompactLevel {
+        CompactLevel::from_types(Price::from_f64(price), Quantity::from_f64(size))
+    }
+
+    /// Two-level book around 100 / 101, stamped at the clock's time
+    fn book(
+        books: &OrderBookManager,
+        clock: &EventClock,
+        update_id: u64,
+        bid_size: f64,
+        ask_size: f64,
+    ) {
+        let mut snapshot = OrderBookSnapshot::new("binance", "BTCUSDT", update_id)
+            .with_bids(vec![level(100.0, bid_size), level(99.0, 10.0)])
+            .with_asks(vec![level(101.0, ask_size), level(102.0, 10.0)]);
+        snapshot.timestamp_ns = clock.now_ns();
+        books.apply_snapshot(&snapshot);
+    }
+
+    fn strategy(model: LinearReturnModel) -> OrderFlowStrategy {
+        let mut strategy = OrderFlowStrategy::with_defaults("of", vec![key()]);
+        strategy.set_params(OrderFlowConfig {
+            depth_levels: 2,
+            model,
+            ..Default::default()
+        });
+        strategy
+    }
+
+    fn model(weights: &[(&str, f64)]) -> LinearReturnModel {
+        LinearReturnModel {
+            weights: weights
+                .iter()
+                .map(|(name, w)| (name.to_string(), *w))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bid_building_signals_buy() {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let clock = EventClock::new(NOW_NS);
+        let mut strategy = strategy(model(&[("ofi_l1", 2.0)]));
+
+        book(&books, &clock, 1, 10.0, 10.0);
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+
+        // Bid queue grows by 10 against a mean level-1 size of 12.5
+        clock.advance(Duration::from_millis(100));
+        book(&books, &clock, 2, 20.0, 10.0);
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals.len(), 1);
+        let signal = &signals[0];
+
+        assert_eq!(signal.direction, SignalDirection::Buy);
+        let ofi = signal.features["ofi_l1"];
+        assert!((ofi - 0.8).abs() < 1e-9, "ofi {}", ofi);
+        assert_eq!(signal.features["ofi_l2"], 0.0);
+        assert!((signal.expected_edge_bps.unwrap() - 1.6).abs() < 1e-9);
+        // 1.6 bps against 2 bps residuals: erf(0.566)
+        assert!((signal.confidence - 0.576).abs() < 0.01);
+        assert_eq!(signal.model_version, "ofi_linear_v1.0");
+        assert_eq!(signal.ttl_ms, Some(1_000));
+
+        // Cooldown, then the change falls out of the window
+        clock.advance(Duration::from_millis(100));
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+        clock.advance(Duration::from_millis(1_000));
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+    }
+
+    #[test]
+    fn test_trade_flow_and_depletion() {
+        let books = OrderBookManager::new();
+        let tapes = TradeTapeManager::new();
+        let market_data = MarketDataAdapter::new(books.clone()).with_trades(tapes.clone());
+        let clock = EventClock::new(NOW_NS);
+        let mut strategy = strategy(model(&[
+            ("trade_imbalance", 1.0),
+            ("depletion_imbalance", 2.0),
+        ]));
+        strategy.set_params(OrderFlowConfig {
+            vpin_bucket_volume: 2.0,
+            ..strategy.params().clone()
+        });
+
+        book(&books, &clock, 1, 10.0, 10.0);
+        strategy.on_tick(&market_data, &clock);
+
+        // Sellers hit the bid: 6 of 10 taken off the queue
+        for id in 1..=3 {
+            let mut trade = TradeUpdate::new(
+                "binance",
+                "BTCUSDT",
+                id,
+                Price::from_f64(100.0),
+                Quantity::from_f64(2.0),
+                true,
+            );
+            trade.timestamp_ns = NOW_NS + 50_000_000;
+            tapes.record(&trade);
+        }
+        clock.advance(Duration::from_millis(100));
+        book(&books, &clock, 2, 4.0, 10.0);
+
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals.len(), 1);
+        let signal = &signals[0];
+        assert_eq!(signal.direction, SignalDirection::Sell);
+        assert_eq!(signal.features["trade_imbalance"], -1.0);
+        assert_eq!(signal.features["vpin"], 1.0);
+        assert!((signal.features["bid_depletion_rate"] - 0.6).abs() < 1e-9);
+        // -1 from trades, -1.2 from depletion
+        assert!((signal.expected_edge_bps.unwrap() - 2.2).abs() < 1e-9);
+        assert!(signal.fair_value < signal.current_price);
+    }
+
+    #[test]
+    fn test_changes_between_ticks_count() {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let clock = EventClock::new(NOW_NS);
+        let mut strategy = strategy(model(&[("depletion_imbalance", 4.0)]));
+
+        book(&books, &clock, 1, 10.0, 10.0);
+        assert!(strategy.on_tick(&market_data, &clock).is_empty());
+
+        // The bid doubles and is hit back to 10 before the next tick
+        for (id, size) in [(2, 20.0), (3, 10.0)] {
+            clock.advance(Duration::from_millis(50));
+            let mut update =
+                DepthUpdate::new("binance", "BTCUSDT", id, id).with_bids(vec![level(100.0, size)]);
+            update.timestamp_ns = clock.now_ns();
+            assert!(books.apply_delta(&update));
+        }
+
+        let signals = strategy.on_tick(&market_data, &clock);
+        assert_eq!(signals.len(), 1);
+        let signal = &signals[0];
+        assert_eq!(signal.direction, SignalDirection::Sell);
+        assert!(signal.features["ofi_l1"].abs() < 1e-9);
+        assert!((signal.features["bid_depletion_rate"] - 0.5).abs() < 1e-9);
+        assert!((signal.expected_edge_bps.unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vpin_buckets_by_volume() {
+        let tapes = TradeTapeManager::new();
+        let market_data =
+            MarketDataAdapter::new(OrderBookManager::new()).with_trades(tapes.clone());
+        let mut strategy = strategy(LinearReturnModel::default());
+        strategy.set_params(OrderFlowConfig {
+            vpin_bucket_volume: 2.0,
+            vpin_buckets: 2,
+            ..strategy.params().clone()
+        });
+        let trade = |id: u64, quantity: f64, buyer_is_maker: bool| {
+            let mut trade = TradeUpdate::new(
+                "binance",
+                "BTCUSDT",
+                id,
+                Price::from_f64(100.0),
+                Quantity::from_f64(quantity),
+                buyer_is_maker,
+            );
+            trade.timestamp_ns = NOW_NS;
+            trade
+        };
+        let vpin = |strategy: &OrderFlowStrategy| {
+            strategy.flows[&key()].vpin.as_ref().and_then(Vpin::value)
+        };
+
+        // One print fills a bucket and a half, all bought
+        tapes.record(&trade(1, 3.0, false));
+        strategy.observe_trades(&key(), market_data.trades(&key()).unwrap().as_ref());
+        assert_eq!(vpin(&strategy), Some(1.0));
+
+        // Prints at the same time are fed once each; the half bucket
+        // closes balanced and the next is all sold
+        tapes.record(&trade(2, 1.0, true));
+        tapes.record(&trade(3, 2.0, true));
+        strategy.observe_trades(&key(), market_data.trades(&key()).unwrap().as_ref());
+        strategy.observe_trades(&key(), market_data.trades(&key()).unwrap().as_ref());
+        assert_eq!(vpin(&strategy), Some(0.5));
+    }
+
+    #[test]
+    fn test_update_params_validates_model() {
+        let mut strategy = strategy(LinearReturnModel::default());
+
+        let params = serde_json::json!({
+            "depth_levels": 3,
+            "model": { "intercept": 0.1, "weights": { "ofi_l3": 1.0, "vpin": -2.0 } }
+        });
+        strategy.update_params(&params).unwrap();
+        assert_eq!(strategy.params().depth_levels, 3);
+        assert_eq!(strategy.params().model.residual_std_bps, 2.0);
+
+        let params = serde_json::json!({
+            "depth_levels": 3,
+            "model": { "weights": { "ofi_l4": 1.0, "momentum": 1.0 } }
+        });
+        let err = strategy.update_params(&params).unwrap_err();
+        assert!(err.message.contains("ofi_l4"));
+        assert!(err.message.contains("momentum"));
+        assert_eq!(strategy.params().model.intercept, 0.1);
+    }
+}
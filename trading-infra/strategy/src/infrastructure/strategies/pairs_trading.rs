@@ -0,0 +1,580 @@
+//! Pairs Trading Strategy
+//!
+//! Statistical arbitrage between two cointegrated instruments. The first
+//! configured symbol is `y`, the second `x`; the strategy trades the spread
+//! `ln y - beta * ln x - alpha`, with both log prices measured from their
+//! first sample.
+//!
+//! # Strategy Logic
+//!
+//! 1. Sample both mids every `sample_interval_ms`
+//! 2. Update a Kalman filter estimate of `beta` and `alpha` on log prices
+//! 3. Z-score is the filter's forecast error over its standard deviation
+//! 4. Over the last `window` spreads, require a rolling ADF statistic below
+//!    the critical value and an Ornstein-Uhlenbeck half-life within bounds
+//! 5. Enter when |z| crosses `entry_z`:
+//!    - z < 0 (y cheap) → buy y, sell x
+//!    - z > 0 (y rich) → sell y, buy x
+//! 6. Exit when |z| falls inside `exit_z` or the pair stops cointegrating
+//!
+//! Log prices keep the filter's noise settings independent of price level;
+//! measuring them from the first sample keeps the hedge ratio's state noise
+//! from being amplified by the price level.
+//! Legs carry quantity ratios: `beta * y / x` of x per unit of y.
+
+use crate::application::ports::{
+    Clock, GeneratorConfig, MarketDataPort, OrderBookReader, ParamsError, SignalGeneratorPort,
+    SymbolKey,
+};
+use crate::domain::{
+    KalmanHedge, KalmanUpdate, Leg, Signal, SignalDirection, StrategyId, StrategyType, Urgency,
+    adf_statistic, ou_half_life,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use trading_core::Price;
+
+/// Configuration for the pairs trading strategy
+///
+/// Fields left out of a config take their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PairsTradingConfig {
+    /// Time between spread samples (milliseconds)
+    pub sample_interval_ms: u64,
+    /// Spread samples the ADF test and half-life are fitted on
+    pub window: usize,
+    /// Kalman state noise; larger lets the hedge ratio move faster
+    pub kalman_delta: f64,
+    /// Kalman observation noise variance, in squared log price
+    pub observation_variance: f64,
+    /// |z| at which a position is opened
+    pub entry_z: f64,
+    /// |z| under which a position is closed
+    pub exit_z: f64,
+    /// Lagged differences in the ADF regression
+    pub adf_lags: usize,
+    /// ADF statistic the spread must be below to trade
+    pub adf_critical_value: f64,
+    /// Shortest half-life traded (seconds)
+    pub min_half_life_seconds: f64,
+    /// Longest half-life traded (seconds)
+    pub max_half_life_seconds: f64,
+}
+
+impl Default for PairsTradingConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval_ms: 1_000,      // 1s samples
+            window: 300,                    // 5 minutes of spreads
+            kalman_delta: 1e-8,             // Slowly moving hedge ratio
+            observation_variance: 1e-6,     // ~10 bps spread noise
+            entry_z: 2.0,                   // 2 sigma entry
+            exit_z: 0.5,                    // 0.5 sigma exit
+            adf_lags: 1,                    // One lagged difference
+            adf_critical_value: -2.86,      // 5% with a constant
+            min_half_life_seconds: 1.0,     // Faster is likely noise
+            max_half_life_seconds: 3_600.0, // Slower ties up capital
+        }
+    }
+}
+
+/// Position the strategy has signalled into
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpreadPosition {
+    Flat,
+    /// Long y, short x
+    Long {
+        ratio: f64,
+        strength: f64,
+    },
+    /// Short y, long x
+    Short {
+        ratio: f64,
+        strength: f64,
+    },
+}
+
+/// Cointegration checks over the spread window
+#[derive(Debug, Clone, Copy)]
+struct Cointegration {
+    adf: f64,
+    half_life_seconds: Option<f64>,
+}
+
+/// Pairs Trading Strategy Implementation
+///
+/// Implements `SignalGeneratorPort` for use with the strategy engine.
+pub struct PairsTrading {
+    config: GeneratorConfig,
+    params: PairsTradingConfig,
+    kalman: KalmanHedge,
+    /// First sampled `(ln x, ln y)`, which log prices are measured from
+    origin: Option<(f64, f64)>,
+    /// Recent spreads and the clock times they were sampled at
+    spreads: VecDeque<(u64, f64)>,
+    last_sample_ns: Option<u64>,
+    position: SpreadPosition,
+    /// Tick counter for statistics
+    tick_count: u64,
+}
+
+impl PairsTrading {
+    /// Create a new pairs trading strategy
+    ///
+    /// `config.symbols` must hold exactly two symbols, `y` then `x`.
+    pub fn new(config: GeneratorConfig, params: PairsTradingConfig) -> Self {
+        Self {
+            config,
+            kalman: KalmanHedge::new(params.kalman_delta, params.observation_variance),
+            params,
+            origin: None,
+            spreads: VecDeque::new(),
+            last_sample_ns: None,
+            position: SpreadPosition::Flat,
+            tick_count: 0,
+        }
+    }
+
+    /// Create with default parameters
+    pub fn with_defaults(strategy_id: impl Into<String>, y: SymbolKey, x: SymbolKey) -> Self {
+        let config = GeneratorConfig::new(
+            StrategyId::new(strategy_id),
+            StrategyType::StatArb,
+            vec![y, x],
+        );
+        Self::new(config, PairsTradingConfig::default())
+    }
+
+    /// Current strategy parameters
+    pub fn params(&self) -> &PairsTradingConfig {
+        &self.params
+    }
+
+    /// Replace parameters, keeping the hedge estimate, spreads and position
+    ///
+    /// A smaller `window` keeps the most recent spreads that fit in it.
+    pub fn set_params(&mut self, params: PairsTradingConfig) {
+        self.kalman
+            .set_noise(params.kalman_delta, params.observation_variance);
+        while self.spreads.len() > params.window {
+            self.spreads.pop_front();
+        }
+        self.params = params;
+    }
+
+    /// Current hedge ratio of `ln y` on `ln x`
+    pub fn hedge_ratio(&self) -> f64 {
+        self.kalman.hedge_ratio()
+    }
+
+    /// Whether a position is open
+    pub fn in_position(&self) -> bool {
+        self.position != SpreadPosition::Flat
+    }
+
+    /// Check if a new sample is due
+    fn is_sample_due(&self, now_ns: u64) -> bool {
+        match self.last_sample_ns {
+            Some(last_ns) => {
+                now_ns.saturating_sub(last_ns) >= self.params.sample_interval_ms * 1_000_000
+            }
+            None => true,
+        }
+    }
+
+    /// Record the spread at the current estimate
+    fn push_spread(&mut self, now_ns: u64, spread: f64) {
+        self.spreads.push_back((now_ns, spread));
+        while self.spreads.len() > self.params.window {
+            self.spreads.pop_front();
+        }
+    }
+
+    /// ADF statistic and half-life of the spread window
+    fn cointegration(&self) -> Option<Cointegration> {
+        let spreads: Vec<f64> = self.spreads.iter().map(|(_, s)| *s).collect();
+        let adf = adf_statistic(&spreads, self.params.adf_lags)?;
+
+        // Convert samples to seconds at the window's average sample spacing
+        let (first_ns, _) = self.spreads.front()?;
+        let (last_ns, _) = self.spreads.back()?;
+        let spacing_secs =
+            (last_ns - first_ns) as f64 / 1e9 / (self.spreads.len() - 1).max(1) as f64;
+        let half_life_seconds = ou_half_life(&spreads).map(|samples| samples * spacing_secs);
+
+        Some(Cointegration {
+            adf,
+            half_life_seconds,
+        })
+    }
+
+    /// Whether the spread is cointegrated enough to open a position
+    fn is_tradeable(&self, stats: &Cointegration) -> bool {
+        stats.adf <= self.params.adf_critical_value
+            && stats.half_life_seconds.is_some_and(|h| {
+                (self.params.min_half_life_seconds..=self.params.max_half_life_seconds).contains(&h)
+            })
+    }
+
+    /// Calculate confidence from how far the ADF statistic clears its
+    /// critical value (0-1)
+    fn calculate_confidence(&self, adf: f64) -> f64 {
+        let critical = self.params.adf_critical_value;
+        if adf > critical {
+            return 0.0;
+        }
+        (0.5 + 0.5 * (critical - adf) / critical.abs().max(f64::EPSILON)).min(1.0)
+    }
+
+    /// Sample the pair and decide whether to enter or exit
+    fn generate_signal<M: MarketDataPort>(
+        &mut self,
+        y_key: &SymbolKey,
+        x_key: &SymbolKey,
+        market_data: &M,
+        now_ns: u64,
+    ) -> Option<Signal> {
+        if !self.is_sample_due(now_ns) {
+            return None;
+        }
+        let y_mid = market_data.book(y_key).mid_price()?;
+        let x_mid = market_data.book(x_key).mid_price()?;
+        if y_mid.raw() <= 0 || x_mid.raw() <= 0 {
+            return None;
+        }
+        self.last_sample_ns = Some(now_ns);
+
+        let (ln_x, ln_y) = (x_mid.to_f64().ln(), y_mid.to_f64().ln());
+        let (x0, y0) = *self.origin.get_or_insert((ln_x, ln_y));
+        let (ln_x, ln_y) = (ln_x - x0, ln_y - y0);
+        let update = self.kalman.update(ln_x, ln_y);
+        self.push_spread(now_ns, self.kalman.spread(ln_x, ln_y));
+
+        // Wait for a full window before trusting the estimate
+        if self.spreads.len() < self.params.window {
+            return None;
+        }
+        let stats = self.cointegration();
+        let z = update.z_score();
+
+        let (direction, ratio, strength) = match self.position {
+            SpreadPosition::Flat => {
+                let stats = stats.filter(|s| self.is_tradeable(s))?;
+                if z.abs() < self.params.entry_z {
+                    return None;
+                }
+                let ratio = (update.hedge_ratio * y_mid.to_f64() / x_mid.to_f64()).abs();
+                let strength = (z.abs() / (2.0 * self.params.entry_z)).min(1.0);
+                let confidence = self.calculate_confidence(stats.adf);
+                let direction = if z < 0.0 {
+                    self.position = SpreadPosition::Long { ratio, strength };
+                    SignalDirection::Buy
+                } else {
+                    self.position = SpreadPosition::Short { ratio, strength };
+                    SignalDirection::Sell
+                };
+                return Some(
+                    self.build_signal(
+                        (y_key, x_key),
+                        (direction, ratio, strength),
+                        (y_mid, &update, z),
+                        Some(stats),
+                    )
+                    .confidence(confidence)
+                    .expected_edge_bps(update.forecast_error.abs() * 10_000.0)
+                    .build(),
+                );
+            }
+            SpreadPosition::Long { ratio, strength } => {
+                let broken = !stats.is_some_and(|s| s.adf <= self.params.adf_critical_value);
+                if z < -self.params.exit_z && !broken {
+                    return None;
+                }
+                (SignalDirection::Sell, ratio, strength)
+            }
+            SpreadPosition::Short { ratio, strength } => {
+                let broken = !stats.is_some_and(|s| s.adf <= self.params.adf_critical_value);
+                if z > self.params.exit_z && !broken {
+                    return None;
+                }
+                (SignalDirection::Buy, ratio, strength)
+            }
+        };
+
+        // Unwind the position at the size and ratio it was opened with
+        self.position = SpreadPosition::Flat;
+        Some(
+            self.build_signal(
+                (y_key, x_key),
+                (direction, ratio, strength),
+                (y_mid, &update, z),
+                stats,
+            )
+            .confidence(1.0)
+            .build(),
+        )
+    }
+
+    /// Two-leg signal common to entries and exits
+    fn build_signal(
+        &self,
+        (y_key, x_key): (&SymbolKey, &SymbolKey),
+        (direction, ratio, strength): (SignalDirection, f64, f64),
+        (y_mid, update, z): (Price, &KalmanUpdate, f64),
+        stats: Option<Cointegration>,
+    ) -> crate::domain::SignalBuilder {
+        // Fair value of y implied by x at the current estimate
+        let fair_value = Price::from_f64((y_mid.to_f64().ln() - update.forecast_error).exp());
+
+        let mut features = HashMap::new();
+        features.insert("hedge_ratio".to_string(), update.hedge_ratio);
+        features.insert("intercept".to_string(), update.intercept);
+        features.insert("quantity_ratio".to_string(), ratio);
+        features.insert("z_score".to_string(), z);
+        features.insert("spread".to_string(), update.forecast_error);
+
+        let mut builder = Signal::builder(
+            self.config.strategy_id.clone(),
+            self.config.strategy_type,
+            y_key.to_string(),
+        )
+        .direction(direction)
+        .strength(strength)
+        .urgency(Urgency::low()) // Cointegration edge is persistent
+        .prices(y_mid, fair_value)
+        .leg(Leg::new(&y_key.symbol, direction, 1.0, &y_key.exchange))
+        .leg(Leg::new(
+            &x_key.symbol,
+            direction.opposite(),
+            ratio,
+            &x_key.exchange,
+        ))
+        .model_variance(update.forecast_variance);
+
+        if let Some(stats) = stats {
+            features.insert("adf_statistic".to_string(), stats.adf);
+            if let Some(half_life) = stats.half_life_seconds {
+                features.insert("half_life_seconds".to_string(), half_life);
+                builder = builder.half_life_seconds(half_life);
+            }
+        }
+
+        builder
+            .features(features)
+            .model_version("pairs_kalman_v1.0")
+            .timestamp_ms(self.last_sample_ns.unwrap_or(0) / 1_000_000)
+    }
+}
+
+impl SignalGeneratorPort for PairsTrading {
+    fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    fn on_tick<M: MarketDataPort, C: Clock + ?Sized>(
+        &mut self,
+        market_data: &M,
+        clock: &C,
+    ) -> Vec<Signal> {
+        self.tick_count += 1;
+        let now_ns = clock.now_ns();
+
+        let [y_key, x_key] = match self.config.symbols.as_slice() {
+            [y, x] => [y.clone(), x.clone()],
+            _ => return Vec::new(),
+        };
+
+        self.generate_signal(&y_key, &x_key, market_data, now_ns)
+            .into_iter()
+            .collect()
+    }
+
+    fn update_params(&mut self, params: &serde_json::Value) -> Result<(), ParamsError> {
+        let params = PairsTradingConfig::deserialize(params)
+            .map_err(|e| ParamsError::new(format!("invalid pairs trading params: {}", e)))?;
+        self.set_params(params);
+        Ok(())
+    }
+
+    fn on_start(&mut self) {
+        if self.config.symbols.len() != 2 {
+            tracing::error!(
+                "PairsTrading strategy '{}' needs exactly 2 symbols, has {}; it will not trade",
+                self.config.strategy_id,
+                self.config.symbols.len()
+            );
+            return;
+        }
+        tracing::info!(
+            "PairsTrading strategy '{}' starting on {} / {}",
+            self.config.strategy_id,
+            self.config.symbols[0],
+            self.config.symbols[1]
+        );
+    }
+
+    fn on_stop(&mut self) {
+        tracing::info!(
+            "PairsTrading strategy '{}' stopping after {} ticks",
+            self.config.strategy_id,
+            self.tick_count
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrderBookManager;
+    use crate::infrastructure::{EventClock, MarketDataAdapter};
+    use std::time::Duration;
+    use trading_core::{CompactLevel, OrderBookSnapshot, Quantity};
+
+    /// Deterministic noise in [-1, 1)
+    struct Noise(u64);
+
+    impl Noise {
+        fn next(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+        }
+    }
+
+    fn apply(books: &OrderBookManager, symbol: &str, update_id: u64, mid: f64) {
+        let half_tick = mid * 0.00005;
+        books.apply_snapshot(
+            &OrderBookSnapshot::new("binance", symbol, update_id)
+                .with_bids(vec![CompactLevel::from_types(
+                    Price::from_f64(mid - half_tick),
+                    Quantity::from_int(10),
+                )])
+                .with_asks(vec![CompactLevel::from_types(
+                    Price::from_f64(mid + half_tick),
+                    Quantity::from_int(10),
+                )]),
+        );
+    }
+
+    fn strategy(window: usize) -> PairsTrading {
+        let mut strategy = PairsTrading::with_defaults(
+            "pairs",
+            SymbolKey::new("binance", "ETHUSDT"),
+            SymbolKey::new("binance", "BTCUSDT"),
+        );
+        strategy.set_params(PairsTradingConfig {
+            window,
+            ..Default::default()
+        });
+        strategy
+    }
+
+    /// Run on a pair where ln y = 1.2 ln x - 4 plus a spread reverting at
+    /// 0.8 per second
+    fn run(strategy: &mut PairsTrading, seconds: u64) -> Vec<Signal> {
+        let books = OrderBookManager::new();
+        let market_data = MarketDataAdapter::new(books.clone());
+        let clock = EventClock::new(1_000_000_000_000);
+        let mut noise = Noise(17);
+        let (mut ln_x, mut spread) = (50_000f64.ln(), 0.0);
+
+        let mut signals = Vec::new();
+        for step in 1..=seconds {
+            ln_x += 0.01 * noise.next();
+            spread = 0.8 * spread + 0.002 * noise.next();
+            apply(&books, "BTCUSDT", step, ln_x.exp());
+            apply(&books, "ETHUSDT", step, (1.2 * ln_x - 4.0 + spread).exp());
+
+            signals.extend(strategy.on_tick(&market_data, &clock));
+            // Ticks between samples are ignored
+            clock.advance(Duration::from_millis(500));
+            assert!(strategy.on_tick(&market_data, &clock).is_empty());
+            clock.advance(Duration::from_millis(500));
+        }
+        signals
+    }
+
+    #[test]
+    fn test_strategy_creation() {
+        let strategy = strategy(100);
+        assert_eq!(strategy.config().strategy_type, StrategyType::StatArb);
+        assert_eq!(strategy.symbols().len(), 2);
+        assert!(!strategy.in_position());
+    }
+
+    #[test]
+    fn test_entries_and_exits_alternate() {
+        let mut strategy = strategy(100);
+        let signals = run(&mut strategy, 600);
+        assert!(signals.len() >= 2, "only {} signals", signals.len());
+
+        // Nothing before the window fills
+        assert!(signals[0].timestamp_ms >= 1_000_000 + 99_000);
+        assert!((strategy.hedge_ratio() - 1.2).abs() < 0.1);
+
+        for (i, signal) in signals.iter().enumerate() {
+            assert!(signal.is_multi_leg());
+            assert_eq!(signal.legs.len(), 2);
+            assert_eq!(signal.symbol, "binance:ETHUSDT");
+            assert_eq!(signal.legs[0].direction, signal.direction);
+            assert_eq!(signal.legs[1].direction, signal.direction.opposite());
+            assert_eq!(signal.legs[1].symbol, "BTCUSDT");
+            assert!(signal.half_life_seconds.is_some());
+
+            if i % 2 == 0 {
+                // Entry: ~1.2 * 8k / 50k of BTC per ETH
+                let ratio = signal.legs[1].ratio_f64();
+                assert!((ratio - 0.19).abs() < 0.03, "ratio {}", ratio);
+                assert!(signal.expected_edge_bps.is_some());
+                assert!(signal.features["z_score"].abs() >= 2.0);
+            } else {
+                // Exit unwinds the entry before it
+                let entry = &signals[i - 1];
+                assert_eq!(signal.direction, entry.direction.opposite());
+                assert_eq!(signal.legs[1].ratio, entry.legs[1].ratio);
+                assert_eq!(signal.strength, entry.strength);
+            }
+        }
+        let half_life = signals[0].half_life_seconds.unwrap();
+        assert!((1.0..20.0).contains(&half_life), "half-life {}", half_life);
+    }
+
+    #[test]
+    fn test_update_params_keeps_state() {
+        let mut strategy = strategy(100);
+        run(&mut strategy, 150);
+        let hedge_ratio = strategy.hedge_ratio();
+
+        strategy
+            .update_params(&serde_json::json!({ "window": 50, "entry_z": 3.0 }))
+            .unwrap();
+        assert_eq!(strategy.params().entry_z, 3.0);
+        assert_eq!(strategy.spreads.len(), 50);
+        assert_eq!(strategy.hedge_ratio(), hedge_ratio);
+
+        assert!(
+            strategy
+                .update_params(&serde_json::json!({ "entry_z": "x" }))
+                .is_err()
+        );
+        assert_eq!(strategy.params().entry_z, 3.0);
+    }
+
+    #[test]
+    fn test_needs_two_symbols() {
+        let config = GeneratorConfig::new(
+            StrategyId::new("pairs"),
+            StrategyType::StatArb,
+            vec![SymbolKey::new("binance", "BTCUSDT")],
+        );
+        let mut strategy = PairsTrading::new(config, PairsTradingConfig::default());
+        let market_data = MarketDataAdapter::new(OrderBookManager::new());
+        assert!(
+            strategy
+                .on_tick(&market_data, &EventClock::new(0))
+                .is_empty()
+        );
+    }
+}
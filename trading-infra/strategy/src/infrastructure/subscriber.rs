@@ -1,24 +1,35 @@
 //! Market Data Subscriber
 //!
-//! Subscribes to market data from gateway, builds local order books,
-//! and requests snapshots when sequence gaps are detected.
+//! Subscribes to market data from gateway, builds local order books and
+//! trade tapes, and requests snapshots when sequence gaps are detected.
+//!
+//! Transport gaps are first requested for retransmission on the snapshot
+//! request channel; when the gateway can no longer fill them every book is
+//! resynchronized from a snapshot.
 
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use trading_core::{DepthUpdate, OrderBookSnapshot, SnapshotRequest};
+use trading_core::{DepthUpdate, OrderBookSnapshot, SnapshotRequest, TradeUpdate};
 use transport::{
-    MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, Publisher, Subscriber, TransportError, WireMessage,
+    Codec, CodecKind, MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, MSG_TRADE, Publisher,
+    SequenceEvent, SequenceStats, SequencedSubscriber, SequencingConfig, Subscriber,
+    TransportError, WireMessage, parse_market_data_topic,
 };
 
 use crate::domain::order_book::OrderBookManager;
+use crate::domain::trade_tape::TradeTapeManager;
+use crate::infrastructure::clock::EventClock;
 
 /// Subscriber for market data updates from gateway
 pub struct MarketDataSubscriber {
-    transport: Box<dyn Subscriber>,
-    snapshot_requester: Box<dyn Publisher>,
+    transport: SequencedSubscriber,
+    snapshot_requester: Arc<dyn Publisher>,
     books: OrderBookManager,
+    trades: TradeTapeManager,
     last_received_sequence: AtomicU64,
+    event_clock: Option<EventClock>,
 }
 
 impl MarketDataSubscriber {
@@ -28,65 +39,157 @@ impl MarketDataSubscriber {
         snapshot_requester: Box<dyn Publisher>,
         books: OrderBookManager,
     ) -> Self {
+        Self::with_sequencing(
+            transport,
+            snapshot_requester,
+            books,
+            SequencingConfig::default(),
+        )
+    }
+
+    /// Create a subscriber with explicit gap recovery settings
+    pub fn with_sequencing(
+        transport: Box<dyn Subscriber>,
+        snapshot_requester: Box<dyn Publisher>,
+        books: OrderBookManager,
+        sequencing: SequencingConfig,
+    ) -> Self {
+        let snapshot_requester: Arc<dyn Publisher> = Arc::from(snapshot_requester);
+        let transport = SequencedSubscriber::new(transport, sequencing)
+            .with_retransmit_requests(Box::new(Arc::clone(&snapshot_requester)));
         MarketDataSubscriber {
             transport,
             snapshot_requester,
             books,
+            trades: TradeTapeManager::new(),
             last_received_sequence: AtomicU64::new(0),
+            event_clock: None,
         }
     }
 
+    /// Record trade prints into `trades` instead of a private set of tapes
+    pub fn with_trades(mut self, trades: TradeTapeManager) -> Self {
+        self.trades = trades;
+        self
+    }
+
+    /// Advance `clock` to the timestamp of every received message
+    ///
+    /// Strategies on `clock` then run on the gateway's (or a capture's)
+    /// time rather than the local system time.
+    pub fn with_event_clock(mut self, clock: EventClock) -> Self {
+        self.event_clock = Some(clock);
+        self
+    }
+
     /// Poll for incoming messages and process them
     /// Returns number of messages processed
     pub fn poll(&self) -> Result<usize, TransportError> {
         let books = &self.books;
+        let trades = &self.trades;
         let snapshot_req = &self.snapshot_requester;
         let last_seq = &self.last_received_sequence;
-
-        self.transport.poll(&mut |data| {
-            if let Ok(msg) = bincode::deserialize::<WireMessage>(data) {
-                // Track sequence
-                last_seq.store(msg.sequence, Ordering::Relaxed);
-
-                match msg.msg_type {
-                    MSG_ORDER_BOOK_SNAPSHOT => {
-                        if let Ok(snapshot) =
-                            bincode::deserialize::<OrderBookSnapshot>(&msg.payload)
-                        {
-                            books.apply_snapshot(&snapshot);
-                            tracing::debug!(
-                                "Applied snapshot for {}:{} at {}",
-                                snapshot.exchange,
-                                snapshot.symbol,
-                                snapshot.last_update_id
-                            );
-                        }
-                    }
-                    MSG_DEPTH_UPDATE => {
-                        if let Ok(update) = bincode::deserialize::<DepthUpdate>(&msg.payload)
-                            && !books.apply_delta(&update)
-                        {
-                            // Sequence gap detected - request snapshot
-                            tracing::warn!(
-                                "Sequence gap for {}:{}, requesting snapshot",
-                                update.exchange,
-                                update.symbol
-                            );
+        let event_clock = &self.event_clock;
+
+        self.transport.poll_events(&mut |event| {
+            let data = match event {
+                SequenceEvent::Message(data) => data,
+                SequenceEvent::Lost {
+                    source,
+                    topic,
+                    from,
+                    to,
+                } => {
+                    tracing::warn!(
+                        "Lost {} {} messages {}..={}, requesting snapshots",
+                        source,
+                        topic,
+                        from,
+                        to
+                    );
+                    // A symbol's topic only affects its book; otherwise any book may be stale
+                    if let Some((exchange, symbol, _)) = parse_market_data_topic(topic) {
+                        let _ = Self::request_snapshot_internal(
+                            snapshot_req.as_ref(),
+                            exchange,
+                            symbol,
+                        );
+                    } else {
+                        for key in books.symbols() {
                             let _ = Self::request_snapshot_internal(
                                 snapshot_req.as_ref(),
-                                &update.exchange,
-                                &update.symbol,
+                                key.exchange.as_str(),
+                                &key.symbol,
                             );
                         }
                     }
-                    _ => {
-                        tracing::trace!("Unknown message type: {}", msg.msg_type);
+                    return;
+                }
+                event => {
+                    tracing::debug!("{:?}", event);
+                    return;
+                }
+            };
+            let Ok(header) = WireMessage::header(data) else {
+                return;
+            };
+            // Track sequence
+            last_seq.store(header.sequence, Ordering::Relaxed);
+            if let Some(clock) = event_clock {
+                clock.advance_to(header.timestamp_ns);
+            }
+
+            // The gateway may frame with either codec
+            let codec = CodecKind::detect(data);
+            match header.msg_type {
+                MSG_ORDER_BOOK_SNAPSHOT => {
+                    if let Ok(snapshot) = codec.decode::<OrderBookSnapshot>(data) {
+                        books.apply_snapshot(&snapshot);
+                        tracing::debug!(
+                            "Applied snapshot for {}:{} at {}",
+                            snapshot.exchange,
+                            snapshot.symbol,
+                            snapshot.last_update_id
+                        );
                     }
                 }
+                MSG_DEPTH_UPDATE => {
+                    if let Ok(update) = codec.decode::<DepthUpdate>(data)
+                        && !books.apply_delta(&update)
+                    {
+                        // Sequence gap detected - request snapshot
+                        tracing::warn!(
+                            "Sequence gap for {}:{}, requesting snapshot",
+                            update.exchange,
+                            update.symbol
+                        );
+                        let _ = Self::request_snapshot_internal(
+                            snapshot_req.as_ref(),
+                            &update.exchange,
+                            &update.symbol,
+                        );
+                    }
+                }
+                MSG_TRADE => {
+                    if let Ok(trade) = codec.decode::<TradeUpdate>(data) {
+                        trades.record(&trade);
+                    }
+                }
+                _ => {
+                    tracing::trace!("Unknown message type: {}", header.msg_type);
+                }
             }
         })
     }
 
+    /// Only receive market data on topics matching `pattern`
+    ///
+    /// e.g. `md.binance.BTCUSDT.>` or `md.*.ETHUSDT.depth`. Without any
+    /// subscription the full stream is received.
+    pub fn subscribe(&self, pattern: &str) -> Result<(), TransportError> {
+        self.transport.subscribe(pattern)
+    }
+
     /// Request a snapshot for a specific symbol
     pub fn request_snapshot(&self, exchange: &str, symbol: &str) -> Result<(), TransportError> {
         Self::request_snapshot_internal(self.snapshot_requester.as_ref(), exchange, symbol)
@@ -109,6 +212,7 @@ impl MarketDataSubscriber {
                 .map(|d| d.as_nanos() as u64)
                 .unwrap_or(0),
             source: "strategy".to_string(),
+            topic: String::new(),
             payload,
         };
 
@@ -123,10 +227,20 @@ impl MarketDataSubscriber {
         &self.books
     }
 
+    /// Get the trade tapes built from received trade prints
+    pub fn trades(&self) -> &TradeTapeManager {
+        &self.trades
+    }
+
     /// Get the last received sequence number
     pub fn last_sequence(&self) -> u64 {
         self.last_received_sequence.load(Ordering::Relaxed)
     }
+
+    /// Transport gap and duplicate counters
+    pub fn sequence_stats(&self) -> SequenceStats {
+        self.transport.stats()
+    }
 }
 
 #[cfg(test)]
@@ -160,6 +274,7 @@ mod tests {
             sequence: 1,
             timestamp_ns: 0,
             source: "gateway".to_string(),
+            topic: String::new(),
             payload,
         };
         let data = bincode::serialize(&msg).unwrap();
@@ -199,6 +314,7 @@ mod tests {
             sequence: 1,
             timestamp_ns: 0,
             source: "gateway".to_string(),
+            topic: String::new(),
             payload,
         };
         md_pub.publish(&bincode::serialize(&msg).unwrap()).unwrap();
@@ -220,6 +336,7 @@ mod tests {
             sequence: 2,
             timestamp_ns: 0,
             source: "gateway".to_string(),
+            topic: String::new(),
             payload,
         };
         md_pub.publish(&bincode::serialize(&msg).unwrap()).unwrap();
@@ -231,6 +348,77 @@ mod tests {
         assert_eq!(book.best_bid().unwrap().quantity.raw(), 2_00000000);
     }
 
+    #[test]
+    fn test_receive_fixed_layout() {
+        let (md_pub, md_sub) = channel_pair(100);
+        let (snap_req_pub, _snap_req_sub) = channel_pair(100);
+        let subscriber = MarketDataSubscriber::new(
+            Box::new(md_sub),
+            Box::new(snap_req_pub),
+            OrderBookManager::new(),
+        );
+
+        let snapshot = OrderBookSnapshot {
+            exchange: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            last_update_id: 100,
+            timestamp_ns: 0,
+            bids: vec![CompactLevel::new(50000_00000000, 1_00000000)],
+            asks: vec![],
+        };
+        let update = DepthUpdate::new("binance", "BTCUSDT", 101, 101)
+            .with_bids(vec![CompactLevel::new(50000_00000000, 3_00000000)]);
+        let codec = CodecKind::Fixed;
+        md_pub
+            .publish(&codec.encode(1, "gateway", "", &snapshot).unwrap())
+            .unwrap();
+        md_pub
+            .publish(&codec.encode(2, "gateway", "", &update).unwrap())
+            .unwrap();
+
+        assert_eq!(subscriber.poll().unwrap(), 2);
+        assert_eq!(subscriber.last_sequence(), 2);
+        let book = subscriber.books().book("binance", "BTCUSDT");
+        assert_eq!(book.last_update_id(), 101);
+        assert_eq!(book.best_bid().unwrap().quantity.raw(), 3_00000000);
+    }
+
+    #[test]
+    fn test_receive_trades() {
+        let (md_pub, md_sub) = channel_pair(100);
+        let (snap_req_pub, _snap_req_sub) = channel_pair(100);
+        let trades = TradeTapeManager::new();
+        let subscriber = MarketDataSubscriber::new(
+            Box::new(md_sub),
+            Box::new(snap_req_pub),
+            OrderBookManager::new(),
+        )
+        .with_trades(trades.clone());
+
+        for (sequence, codec, buyer_is_maker) in
+            [(1, CodecKind::Bincode, false), (2, CodecKind::Fixed, true)]
+        {
+            let trade = TradeUpdate::new(
+                "binance",
+                "BTCUSDT",
+                sequence,
+                trading_core::Price::from_int(50000),
+                trading_core::Quantity::from_int(1),
+                buyer_is_maker,
+            );
+            md_pub
+                .publish(&codec.encode(sequence, "gateway", "", &trade).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(subscriber.poll().unwrap(), 2);
+        let tape = trades.tape("binance", "BTCUSDT");
+        assert_eq!(tape.len(), 2);
+        assert_eq!(tape.last_trade().unwrap().trade_id, 2);
+        let volume = tape.aggressor_volume_since(0);
+        assert_eq!(volume.buy, volume.sell);
+    }
+
     #[test]
     fn test_sequence_gap_triggers_snapshot_request() {
         let (md_pub, md_sub) = channel_pair(100);
@@ -255,6 +443,7 @@ mod tests {
             sequence: 1,
             timestamp_ns: 0,
             source: "gateway".to_string(),
+            topic: String::new(),
             payload,
         };
         md_pub.publish(&bincode::serialize(&msg).unwrap()).unwrap();
@@ -276,6 +465,7 @@ mod tests {
             sequence: 2,
             timestamp_ns: 0,
             source: "gateway".to_string(),
+            topic: String::new(),
             payload,
         };
         md_pub.publish(&bincode::serialize(&msg).unwrap()).unwrap();
@@ -301,4 +491,77 @@ mod tests {
         let book = subscriber.books().book("binance", "BTCUSDT");
         assert_eq!(book.last_update_id(), 100);
     }
+
+    #[test]
+    fn test_lost_messages_trigger_snapshot_requests() {
+        let (md_pub, md_sub) = channel_pair(100);
+        let (snap_req_pub, snap_req_sub) = channel_pair(100);
+
+        let books = OrderBookManager::new();
+        let subscriber =
+            MarketDataSubscriber::new(Box::new(md_sub), Box::new(snap_req_pub), books.clone());
+
+        let snapshot = OrderBookSnapshot {
+            exchange: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            last_update_id: 100,
+            timestamp_ns: 0,
+            bids: vec![CompactLevel::new(50000_00000000, 1_00000000)],
+            asks: vec![],
+        };
+        let msg = WireMessage::new(
+            transport::MessageType::OrderBookSnapshot,
+            1,
+            "gateway",
+            &snapshot,
+        )
+        .unwrap();
+        md_pub.publish(&msg.serialize().unwrap()).unwrap();
+        subscriber.poll().unwrap();
+
+        // Transport sequences 2..=4 never arrive
+        let update = DepthUpdate::new("binance", "BTCUSDT", 101, 101);
+        let msg =
+            WireMessage::new(transport::MessageType::DepthUpdate, 5, "gateway", &update).unwrap();
+        md_pub.publish(&msg.serialize().unwrap()).unwrap();
+        assert_eq!(subscriber.poll().unwrap(), 0);
+
+        let mut retransmit = None;
+        snap_req_sub
+            .poll(&mut |data| {
+                let msg = WireMessage::deserialize(data).unwrap();
+                assert_eq!(msg.msg_type, transport::MSG_RETRANSMIT_REQUEST);
+                retransmit = Some(
+                    msg.decode_payload::<transport::RetransmitRequest>()
+                        .unwrap(),
+                );
+            })
+            .unwrap();
+        let retransmit = retransmit.unwrap();
+        assert_eq!((retransmit.from_sequence, retransmit.to_sequence), (2, 4));
+
+        // The gateway no longer has them
+        let reject = WireMessage::new(
+            transport::MessageType::RetransmitReject,
+            0,
+            "gateway",
+            &retransmit,
+        )
+        .unwrap();
+        md_pub.publish(&reject.serialize().unwrap()).unwrap();
+        assert_eq!(subscriber.poll().unwrap(), 1);
+        assert_eq!(subscriber.last_sequence(), 5);
+        assert_eq!(subscriber.sequence_stats().lost, 1);
+
+        let mut requested = Vec::new();
+        snap_req_sub
+            .poll(&mut |data| {
+                let msg = WireMessage::deserialize(data).unwrap();
+                assert_eq!(msg.msg_type, transport::MSG_SNAPSHOT_REQUEST);
+                let req: SnapshotRequest = msg.decode_payload().unwrap();
+                requested.push(format!("{}:{}", req.exchange, req.symbol));
+            })
+            .unwrap();
+        assert_eq!(requested, vec!["binance:BTCUSDT"]);
+    }
 }
@@ -6,7 +6,7 @@
 //!
 //! The strategy process:
 //! - Receives order book deltas from gateway via transport
-//! - Builds and maintains local order book state
+//! - Builds and maintains local order book state and trade tapes
 //! - Extracts features from order book data
 //! - Generates trading signals
 //! - Publishes signals to execution layer
@@ -59,8 +59,15 @@ pub mod application;
 pub mod domain;
 pub mod infrastructure;
 
+// Process configuration
+pub mod config;
+
 // Re-export key types from domain
 pub use domain::order_book::{OrderBookManager, SharedOrderBook};
+pub use domain::trade_tape::{SharedTradeTape, TradeTapeManager};
 
 // Re-export key types from infrastructure
 pub use infrastructure::subscriber::MarketDataSubscriber;
+
+// Re-export configuration loading
+pub use config::{StrategyConfigFile, load_config, load_config_from_str, load_default_config};
@@ -1,14 +1,381 @@
 //! Strategy Binary
 //!
-//! Runs the strategy process for signal generation.
-
-fn main() {
-    println!("Strategy starting...");
-    // TODO: Implement strategy main loop
-    // 1. Load configuration
-    // 2. Create transport subscriber (for market data)
-    // 3. Create transport publisher (for signals)
-    // 4. Initialize local order book manager
-    // 5. Load and start strategy engines
-    // 6. Poll for market data, generate signals
+//! Runs the strategy process: builds local books and trade tapes from the
+//! gateway's market data, runs every configured strategy on its own thread
+//! and publishes their signals to execution. SIGHUP re-reads the config and
+//! applies new strategy parameters without restarting the strategies.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+
+use serde::Deserialize;
+use strategy::application::ports::SignalGeneratorPort;
+use strategy::application::services::{EngineService, ParamUpdates};
+use strategy::config::{StrategyInstanceConfig, StrategyKind};
+use strategy::infrastructure::{
+    CycleArbitrage, CycleArbitrageConfig, MarketDataAdapter, MeanReversionConfig, MeanReversionHFT,
+    ModelStrategy, ModelStrategyConfig, OrderFlowConfig, OrderFlowStrategy, PairsTrading,
+    PairsTradingConfig, TransportSignalPublisher, WallClock,
+};
+use strategy::{
+    MarketDataSubscriber, OrderBookManager, StrategyConfigFile, TradeTapeManager, load_config,
+    load_default_config,
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use transport::{Publisher, TransportFactory, TransportType, market_data_topic};
+
+fn print_help() {
+    eprintln!(
+        r#"Strategy - signal generation from gateway market data
+
+USAGE:
+    strategy [OPTIONS]
+
+OPTIONS:
+    --config <PATH>     Load configuration from JSON file (default: embedded config)
+    --help              Print this help message
+
+SIGNALS:
+    SIGHUP              Re-read the config file and apply strategy parameters
+    SIGINT, SIGTERM     Stop all strategies and exit
+
+ENVIRONMENT VARIABLES:
+    RUST_LOG            Log level filter
+
+EXAMPLES:
+    # Run the strategies in strategy_config.json
+    strategy --config strategy_config.json
+"#
+    );
+}
+
+/// A started strategy and the handle its parameters are reloaded through
+struct RunningStrategy {
+    config: StrategyInstanceConfig,
+    updates: ParamUpdates,
+    thread: JoinHandle<()>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "strategy=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // Parse command line arguments
+    let args: Vec<String> = std::env::args().collect();
+    let mut config_path: Option<String> = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--help" | "-h" => {
+                print_help();
+                return Ok(());
+            }
+            "--config" | "-c" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --config requires a path argument");
+                    std::process::exit(1);
+                }
+                config_path = Some(args[i].clone());
+            }
+            arg => {
+                eprintln!("Unknown argument: {}", arg);
+                print_help();
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let config = match &config_path {
+        Some(path) => {
+            tracing::info!("Loading configuration from: {}", path);
+            load_config(path)?
+        }
+        None => {
+            tracing::info!("Using embedded default configuration");
+            load_default_config()?
+        }
+    };
+    config.validate()?;
+
+    // Market data from the gateway
+    let transport = &config.transport;
+    if transport.market_data.transport_type == TransportType::Channel {
+        tracing::warn!(
+            "Market data transport is an in-process channel; no gateway process can reach it"
+        );
+    }
+    let books = OrderBookManager::new();
+    let trades = TradeTapeManager::new();
+    let subscriber = MarketDataSubscriber::with_sequencing(
+        TransportFactory::create_subscriber(&transport.market_data)?,
+        TransportFactory::create_publisher(&transport.snapshot_requests)?,
+        books.clone(),
+        transport.sequencing.clone(),
+    )
+    .with_trades(trades.clone());
+
+    let symbols: BTreeSet<_> = config
+        .enabled_strategies()
+        .iter()
+        .flat_map(|s| s.symbols.iter())
+        .map(|key| (key.exchange.clone(), key.symbol.clone()))
+        .collect();
+    for (exchange, symbol) in &symbols {
+        subscriber.subscribe(&market_data_topic(exchange, symbol, ">"))?;
+        // Deltas resync the book once the gateway is reachable
+        if let Err(e) = subscriber.request_snapshot(exchange, symbol) {
+            tracing::warn!("Snapshot request for {}:{} failed: {}", exchange, symbol, e);
+        }
+    }
+    tracing::info!("Subscribed to {} symbols", symbols.len());
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let poller = {
+        let shutdown = Arc::clone(&shutdown);
+        let idle = transport.poll_interval();
+        std::thread::Builder::new()
+            .name("market-data".to_string())
+            .spawn(move || {
+                while !shutdown.load(Ordering::Relaxed) {
+                    match subscriber.poll() {
+                        Ok(0) => std::thread::sleep(idle),
+                        Ok(_) => {}
+                        Err(e) => {
+                            tracing::warn!("Market data poll failed: {}", e);
+                            std::thread::sleep(idle);
+                        }
+                    }
+                }
+                let stats = subscriber.sequence_stats();
+                tracing::info!(
+                    "Market data stopped at sequence {}, {} gaps",
+                    subscriber.last_sequence(),
+                    stats.gaps
+                );
+            })?
+    };
+
+    // One thread per strategy, sharing the books and the signal transport
+    let market_data = Arc::new(MarketDataAdapter::new(books).with_trades(trades));
+    let signals: Arc<dyn Publisher> =
+        Arc::from(TransportFactory::create_publisher(&transport.signals)?);
+    let mut running = HashMap::new();
+    for instance in config.enabled_strategies() {
+        let updates = ParamUpdates::new();
+        let thread = spawn_strategy(
+            &config,
+            instance,
+            Arc::clone(&market_data),
+            Arc::clone(&signals),
+            Arc::clone(&shutdown),
+            updates.clone(),
+        )?;
+        running.insert(
+            instance.id.clone(),
+            RunningStrategy {
+                config: instance.clone(),
+                updates,
+                thread,
+            },
+        );
+    }
+
+    let (reload_tx, mut reload_rx) = tokio::sync::mpsc::unbounded_channel();
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut hangup = signal(SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                if reload_tx.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    #[cfg(not(unix))]
+    let _reload_tx = reload_tx;
+
+    let shutdown_requested = shutdown_signal();
+    tokio::pin!(shutdown_requested);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown_requested => break,
+            Some(()) = reload_rx.recv() => {
+                tracing::info!("Received SIGHUP");
+                reload(config_path.as_deref(), &mut running);
+            }
+        }
+    }
+
+    shutdown.store(true, Ordering::Relaxed);
+    for (id, strategy) in running {
+        if strategy.thread.join().is_err() {
+            tracing::error!("Strategy '{}' panicked", id);
+        }
+    }
+    if poller.join().is_err() {
+        tracing::error!("Market data thread panicked");
+    }
+    signals.flush()?;
+    Ok(())
+}
+
+/// Start a strategy instance on its own thread
+fn spawn_strategy(
+    config: &StrategyConfigFile,
+    instance: &StrategyInstanceConfig,
+    market_data: Arc<MarketDataAdapter>,
+    signals: Arc<dyn Publisher>,
+    shutdown: Arc<AtomicBool>,
+    updates: ParamUpdates,
+) -> Result<JoinHandle<()>, Box<dyn std::error::Error>> {
+    let publisher = TransportSignalPublisher::new(signals, config.source.clone(), &instance.id)
+        .with_codec(config.transport.codec);
+    tracing::info!(
+        "Starting {:?} strategy '{}' publishing on {}",
+        instance.kind,
+        instance.id,
+        publisher.topic()
+    );
+
+    match instance.kind {
+        StrategyKind::MeanReversionHft => {
+            let params = MeanReversionConfig::deserialize(&instance.params)?;
+            let generator = MeanReversionHFT::new(instance.generator_config(), params);
+            run_on_thread(generator, market_data, publisher, config, shutdown, updates)
+        }
+        StrategyKind::PairsTrading => {
+            let params = PairsTradingConfig::deserialize(&instance.params)?;
+            let generator = PairsTrading::new(instance.generator_config(), params);
+            run_on_thread(generator, market_data, publisher, config, shutdown, updates)
+        }
+        StrategyKind::CycleArbitrage => {
+            let params = CycleArbitrageConfig::deserialize(&instance.params)?;
+            let generator = CycleArbitrage::new(instance.generator_config(), params);
+            run_on_thread(generator, market_data, publisher, config, shutdown, updates)
+        }
+        StrategyKind::OrderFlow => {
+            let params = OrderFlowConfig::deserialize(&instance.params)?;
+            params.validate()?;
+            let generator = OrderFlowStrategy::new(instance.generator_config(), params);
+            run_on_thread(generator, market_data, publisher, config, shutdown, updates)
+        }
+        StrategyKind::Model => {
+            let params = ModelStrategyConfig::deserialize(&instance.params)?;
+            let generator = ModelStrategy::new(instance.generator_config(), params)?;
+            run_on_thread(generator, market_data, publisher, config, shutdown, updates)
+        }
+    }
+}
+
+fn run_on_thread<G: SignalGeneratorPort>(
+    generator: G,
+    market_data: Arc<MarketDataAdapter>,
+    publisher: TransportSignalPublisher,
+    config: &StrategyConfigFile,
+    shutdown: Arc<AtomicBool>,
+    updates: ParamUpdates,
+) -> Result<JoinHandle<()>, Box<dyn std::error::Error>> {
+    let engine = config.engine.clone();
+    let thread = std::thread::Builder::new()
+        .name(format!("strategy-{}", generator.name()))
+        .spawn(move || {
+            EngineService::run_generator_with_updates(
+                generator,
+                market_data,
+                publisher,
+                WallClock::new(),
+                engine,
+                shutdown,
+                updates,
+            )
+        })?;
+    Ok(thread)
+}
+
+/// Re-read the config and hand changed parameters to running strategies
+///
+/// Only parameters are applied; adding, removing or re-pointing a strategy
+/// needs a restart.
+fn reload(config_path: Option<&str>, running: &mut HashMap<String, RunningStrategy>) {
+    let Some(path) = config_path else {
+        tracing::warn!("Running the embedded configuration; nothing to reload");
+        return;
+    };
+    let config = match load_config(path).and_then(|c| c.validate().map(|_| c)) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Keeping current configuration, reload failed: {}", e);
+            return;
+        }
+    };
+
+    for instance in config.enabled_strategies() {
+        let Some(strategy) = running.get_mut(&instance.id) else {
+            tracing::warn!("Strategy '{}' is new; restart to start it", instance.id);
+            continue;
+        };
+        if instance.kind != strategy.config.kind || instance.symbols != strategy.config.symbols {
+            tracing::warn!(
+                "Strategy '{}' changed type or symbols; restart to apply",
+                instance.id
+            );
+        }
+        // Only parameters the engine accepted are current; rejected ones
+        // are pushed again so the rejection is reported on every reload
+        if let Some(applied) = strategy.updates.applied() {
+            strategy.config.params = (*applied).clone();
+        }
+        if instance.params != strategy.config.params {
+            tracing::info!("Reloading parameters for strategy '{}'", instance.id);
+            strategy.updates.push(instance.params.clone());
+        }
+    }
+    for id in running.keys() {
+        if !config.get_strategy(id).is_some_and(|s| s.enabled) {
+            tracing::warn!("Strategy '{}' was removed; restart to stop it", id);
+        }
+    }
+}
+
+/// Resolves on SIGINT or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received SIGINT"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
 }
@@ -0,0 +1,158 @@
+//! Captured session replay into the strategy
+//!
+//! Records a short gateway session, replays it from disk onto a transport
+//! and runs `MeanReversionHFT` on the books and trade tapes the subscriber
+//! rebuilds, on the capture's event time.
+
+use strategy::application::ports::{Clock, SignalGeneratorPort, SymbolKey};
+use strategy::domain::SignalDirection;
+use strategy::infrastructure::{EventClock, MarketDataAdapter, MeanReversionHFT};
+use strategy::{MarketDataSubscriber, OrderBookManager};
+use trading_core::{CompactLevel, DepthUpdate, OrderBookSnapshot, Price, Quantity, TradeUpdate};
+use transport::{
+    CaptureConfig, CaptureReader, CaptureWriter, MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT,
+    MSG_TRADE, MessageType, Publisher, RecordingPublisher, ReplaySpeed, Replayer, WireMessage,
+    channel_pair,
+};
+
+const PRICE: i64 = 100_000_000;
+
+fn snapshot(symbol: &str, bid_qty: i64, ask_qty: i64) -> OrderBookSnapshot {
+    OrderBookSnapshot {
+        exchange: "binance".to_string(),
+        symbol: symbol.to_string(),
+        last_update_id: 10,
+        timestamp_ns: 0,
+        bids: vec![CompactLevel::new(100 * PRICE, bid_qty * PRICE)],
+        asks: vec![CompactLevel::new(101 * PRICE, ask_qty * PRICE)],
+    }
+}
+
+fn encode<T: serde::Serialize>(
+    msg_type: MessageType,
+    sequence: u64,
+    ts_ms: u64,
+    payload: &T,
+) -> Vec<u8> {
+    let mut msg = WireMessage::new(msg_type, sequence, "gateway", payload).unwrap();
+    msg.timestamp_ns = 1_700_000_000_000_000_000 + ts_ms * 1_000_000;
+    msg.serialize().unwrap()
+}
+
+/// Symbol of a gateway market data message
+fn symbol_of(data: &[u8]) -> Option<String> {
+    let msg = WireMessage::deserialize(data).ok()?;
+    match msg.msg_type {
+        MSG_DEPTH_UPDATE => msg.decode_payload::<DepthUpdate>().ok().map(|u| u.symbol),
+        MSG_ORDER_BOOK_SNAPSHOT => msg
+            .decode_payload::<OrderBookSnapshot>()
+            .ok()
+            .map(|s| s.symbol),
+        MSG_TRADE => msg.decode_payload::<TradeUpdate>().ok().map(|t| t.symbol),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_strategy_runs_on_replayed_capture() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // Live session: gateway publishes through a recording transport
+    {
+        let (live, _live_sub) = channel_pair(100);
+        let writer = CaptureWriter::create(CaptureConfig {
+            chunk_messages: 2,
+            ..CaptureConfig::new(dir.path())
+        })
+        .unwrap();
+        let gateway = RecordingPublisher::new(Box::new(live), writer);
+
+        gateway
+            .publish(&encode(
+                MessageType::OrderBookSnapshot,
+                0,
+                0,
+                &snapshot("BTCUSDT", 10, 10),
+            ))
+            .unwrap();
+        gateway
+            .publish(&encode(
+                MessageType::OrderBookSnapshot,
+                1,
+                1,
+                &snapshot("ETHUSDT", 10, 10),
+            ))
+            .unwrap();
+        // Bids build up: 20 vs 5 skews the microprice toward the ask
+        let update = DepthUpdate::new("binance", "BTCUSDT", 11, 11)
+            .with_bids(vec![CompactLevel::new(100 * PRICE, 20 * PRICE)])
+            .with_asks(vec![CompactLevel::new(101 * PRICE, 5 * PRICE)]);
+        gateway
+            .publish(&encode(MessageType::DepthUpdate, 2, 2, &update))
+            .unwrap();
+        for (sequence, symbol) in [(3, "BTCUSDT"), (4, "ETHUSDT")] {
+            let trade = TradeUpdate::new(
+                "binance",
+                symbol,
+                sequence,
+                Price::from_int(101),
+                Quantity::from_int(2),
+                false,
+            );
+            gateway
+                .publish(&encode(MessageType::Trade, sequence, sequence, &trade))
+                .unwrap();
+        }
+        gateway.flush().unwrap();
+    }
+
+    // Later, without a venue: replay the BTCUSDT stream into a strategy
+    let reader = CaptureReader::open(dir.path()).unwrap();
+    assert_eq!(reader.len(), 5);
+
+    let (replay_pub, replay_sub) = channel_pair(100);
+    let stats = Replayer::new(reader)
+        .with_speed(ReplaySpeed::AsFastAsPossible)
+        .with_filter(|_, data| symbol_of(data).as_deref() == Some("BTCUSDT"))
+        .run(&replay_pub)
+        .unwrap();
+    assert_eq!(stats.published, 3);
+    assert_eq!(stats.filtered, 2);
+
+    let (requests, _requests_sub) = channel_pair(10);
+    let books = OrderBookManager::new();
+    let clock = EventClock::default();
+    let subscriber =
+        MarketDataSubscriber::new(Box::new(replay_sub), Box::new(requests), books.clone())
+            .with_event_clock(clock.clone());
+    // Filtering renumbers the BTCUSDT stream, so no transport gap is seen
+    assert_eq!(subscriber.poll().unwrap(), 3);
+    assert_eq!(subscriber.last_sequence(), 2);
+    assert_eq!(subscriber.sequence_stats().gaps, 0);
+    // Time is the captured time of the last message, not the replay time
+    assert_eq!(clock.now_ms(), 1_700_000_000_003);
+    assert_eq!(
+        subscriber
+            .books()
+            .book("binance", "BTCUSDT")
+            .last_update_id(),
+        11
+    );
+    assert!(
+        !subscriber
+            .books()
+            .book("binance", "ETHUSDT")
+            .is_initialized()
+    );
+    let tape = subscriber.trades().tape("binance", "BTCUSDT");
+    assert_eq!(tape.len(), 1);
+    assert_eq!(tape.last_price(), Some(Price::from_int(101)));
+    assert!(subscriber.trades().tape("binance", "ETHUSDT").is_empty());
+
+    let mut strategy =
+        MeanReversionHFT::with_defaults("replay", vec![SymbolKey::new("binance", "BTCUSDT")]);
+    let signals = strategy.on_tick(&MarketDataAdapter::new(books), &clock);
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].direction, SignalDirection::Buy);
+    assert_eq!(signals[0].timestamp_ms, 1_700_000_000_003);
+}
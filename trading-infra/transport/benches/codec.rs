@@ -0,0 +1,136 @@
+//! Bincode vs fixed-layout encoding of market data frames
+//!
+//! Run with `cargo bench -p transport --bench codec`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use trading_core::{CompactLevel, DepthUpdate, OrderBookSnapshot, TradeUpdate};
+use transport::{BincodeCodec, Codec, CodecKind, FixedCodec, FrameView, WireMessage};
+
+const TOPIC: &str = "md.binance.BTCUSDT.depth";
+const PRICE: i64 = 100_000_000;
+
+fn levels(count: usize, start: i64, step: i64) -> Vec<CompactLevel> {
+    (0..count as i64)
+        .map(|i| CompactLevel::new(start + i * step, PRICE + i))
+        .collect()
+}
+
+fn depth_update(levels_per_side: usize) -> DepthUpdate {
+    DepthUpdate::new("binance", "BTCUSDT", 1_000, 1_010)
+        .with_bids(levels(levels_per_side, 50_000 * PRICE, -1_000_000))
+        .with_asks(levels(levels_per_side, 50_001 * PRICE, 1_000_000))
+}
+
+fn snapshot(levels_per_side: usize) -> OrderBookSnapshot {
+    OrderBookSnapshot::new("binance", "BTCUSDT", 1_000)
+        .with_bids(levels(levels_per_side, 50_000 * PRICE, -1_000_000))
+        .with_asks(levels(levels_per_side, 50_001 * PRICE, 1_000_000))
+}
+
+fn trade() -> TradeUpdate {
+    TradeUpdate {
+        exchange: "binance".to_string(),
+        symbol: "BTCUSDT".to_string(),
+        trade_id: 42,
+        price_raw: 50_000 * PRICE,
+        quantity_raw: 25_000_000,
+        buyer_is_maker: true,
+        timestamp_ns: 0,
+    }
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_depth_update");
+    for levels in [1, 10, 50] {
+        let update = depth_update(levels);
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::new("bincode", levels), &update, |b, u| {
+            b.iter(|| {
+                BincodeCodec
+                    .encode(1, "gateway", TOPIC, black_box(u))
+                    .unwrap()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("fixed", levels), &update, |b, u| {
+            b.iter(|| {
+                FixedCodec
+                    .encode(1, "gateway", TOPIC, black_box(u))
+                    .unwrap()
+            })
+        });
+        // Reusing the buffer leaves the fixed codec allocation-free
+        let mut buf = Vec::new();
+        group.bench_with_input(BenchmarkId::new("fixed_reused", levels), &update, |b, u| {
+            b.iter(|| {
+                buf.clear();
+                FixedCodec
+                    .encode_into(1, "gateway", TOPIC, black_box(u), &mut buf)
+                    .unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_depth_update");
+    for levels in [1, 10, 50] {
+        let update = depth_update(levels);
+        let bincode = BincodeCodec.encode(1, "gateway", TOPIC, &update).unwrap();
+        let fixed = FixedCodec.encode(1, "gateway", TOPIC, &update).unwrap();
+        group.throughput(Throughput::Elements(1));
+
+        // What a subscriber does today: envelope, then payload
+        group.bench_with_input(BenchmarkId::new("bincode", levels), &bincode, |b, data| {
+            b.iter(|| {
+                let msg = WireMessage::deserialize(black_box(data)).unwrap();
+                msg.decode_payload::<DepthUpdate>().unwrap()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("fixed_owned", levels),
+            &fixed,
+            |b, data| b.iter(|| FixedCodec.decode::<DepthUpdate>(black_box(data)).unwrap()),
+        );
+        // Read every level in place without materializing the update
+        group.bench_with_input(BenchmarkId::new("fixed_view", levels), &fixed, |b, data| {
+            b.iter(|| {
+                let frame = FrameView::new(black_box(data)).unwrap();
+                let view = frame.message::<DepthUpdate>().unwrap();
+                view.bids()
+                    .iter()
+                    .chain(view.asks().iter())
+                    .map(|level| level.quantity_raw)
+                    .sum::<i64>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_other_messages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("roundtrip");
+    let snapshot = snapshot(100);
+    let trade = trade();
+    for kind in [CodecKind::Bincode, CodecKind::Fixed] {
+        let name = format!("{:?}", kind).to_lowercase();
+        group.bench_function(BenchmarkId::new("snapshot_100", &name), |b| {
+            b.iter(|| {
+                let data = kind
+                    .encode(1, "gateway", TOPIC, black_box(&snapshot))
+                    .unwrap();
+                kind.decode::<OrderBookSnapshot>(&data).unwrap()
+            })
+        });
+        group.bench_function(BenchmarkId::new("trade", &name), |b| {
+            b.iter(|| {
+                let data = kind.encode(1, "gateway", TOPIC, black_box(&trade)).unwrap();
+                kind.decode::<TradeUpdate>(&data).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_decode, bench_other_messages);
+criterion_main!(benches);
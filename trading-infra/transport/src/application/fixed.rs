@@ -0,0 +1,879 @@
+//! Fixed-Layout Wire Encoding
+//!
+//! Schema-driven binary layout for market data and signals, read in place
+//! from the transport buffer. All integers are little-endian and every field
+//! sits at a fixed offset, so decoding a message is a bounds check followed
+//! by direct reads; nothing is copied or allocated.
+//!
+//! # Frame
+//!
+//! ```text
+//! offset  size  field
+//!      0     1  magic (0xA7, never a MessageType value)
+//!      1     1  schema version
+//!      2     1  message type
+//!      3     1  source length
+//!      4     2  topic length
+//!      6     2  reserved
+//!      8     8  sequence
+//!     16     8  timestamp_ns
+//!     24     -  source bytes, topic bytes, message body
+//! ```
+//!
+//! A body is a root block (`u16` block length, then fixed fields), zero or
+//! more repeating groups (`u16` entry length, `u16` count, then entries) and
+//! trailing variable-length strings (`u8` length, then UTF-8 bytes).
+//!
+//! Block and entry lengths are written out so a later schema version can
+//! append fields: older readers skip what they don't know.
+
+use trading_core::{CompactLevel, DepthUpdate, OrderBookSnapshot, SignalMessage, TradeUpdate};
+
+use super::error::TransportError;
+use super::message::{MessageType, WireHeader};
+
+/// First byte of every fixed-layout frame
+pub const FRAME_MAGIC: u8 = 0xA7;
+/// Schema version written by this build
+pub const SCHEMA_VERSION: u8 = 1;
+/// Bytes before the source and topic
+pub const FRAME_HEADER_LEN: usize = 24;
+
+const LEVEL_LEN: usize = 16;
+const DEPTH_BLOCK_LEN: usize = 24;
+const SNAPSHOT_BLOCK_LEN: usize = 16;
+const TRADE_BLOCK_LEN: usize = 33;
+const SIGNAL_BLOCK_LEN: usize = 62;
+
+/// Whether `data` is a fixed-layout frame rather than a bincode envelope
+pub fn is_fixed_frame(data: &[u8]) -> bool {
+    data.first() == Some(&FRAME_MAGIC)
+}
+
+fn malformed(what: &str) -> TransportError {
+    TransportError::Serialization(format!("malformed fixed-layout frame: {}", what))
+}
+
+fn u16_at(data: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([data[at], data[at + 1]])
+}
+
+fn u64_at(data: &[u8], at: usize) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&data[at..at + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn i64_at(data: &[u8], at: usize) -> i64 {
+    u64_at(data, at) as i64
+}
+
+fn u32_at(data: &[u8], at: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&data[at..at + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+/// Bounds-checked cursor over a message body
+struct BodyReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BodyReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize, what: &str) -> Result<&'a [u8], TransportError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| malformed(what))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Root block, at least `min_len` bytes
+    fn block(&mut self, min_len: usize) -> Result<&'a [u8], TransportError> {
+        let len = u16_at(self.take(2, "block length")?, 0) as usize;
+        if len < min_len {
+            return Err(malformed("root block too short"));
+        }
+        self.take(len, "root block")
+    }
+
+    fn levels(&mut self) -> Result<LevelsView<'a>, TransportError> {
+        let header = self.take(4, "group header")?;
+        let stride = u16_at(header, 0) as usize;
+        let count = u16_at(header, 2) as usize;
+        if stride < LEVEL_LEN {
+            return Err(malformed("level entry too short"));
+        }
+        Ok(LevelsView {
+            data: self.take(stride * count, "levels")?,
+            stride,
+            count,
+        })
+    }
+
+    fn str(&mut self) -> Result<&'a str, TransportError> {
+        let len = self.take(1, "string length")?[0] as usize;
+        std::str::from_utf8(self.take(len, "string")?).map_err(|_| malformed("invalid UTF-8"))
+    }
+}
+
+fn put_u16(buf: &mut Vec<u8>, value: usize, what: &str) -> Result<(), TransportError> {
+    let value = u16::try_from(value)
+        .map_err(|_| TransportError::Serialization(format!("{} exceeds {}", what, u16::MAX)))?;
+    buf.extend_from_slice(&value.to_le_bytes());
+    Ok(())
+}
+
+fn put_str(buf: &mut Vec<u8>, value: &str) -> Result<(), TransportError> {
+    let len = u8::try_from(value.len()).map_err(|_| {
+        TransportError::Serialization(format!("string longer than 255 bytes: {}", value))
+    })?;
+    buf.push(len);
+    buf.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+fn put_levels(buf: &mut Vec<u8>, levels: &[CompactLevel]) -> Result<(), TransportError> {
+    put_u16(buf, LEVEL_LEN, "level length")?;
+    put_u16(buf, levels.len(), "level count")?;
+    for level in levels {
+        buf.extend_from_slice(&level.price_raw.to_le_bytes());
+        buf.extend_from_slice(&level.quantity_raw.to_le_bytes());
+    }
+    Ok(())
+}
+
+/// Append a frame header, source and topic
+pub(crate) fn encode_header(
+    buf: &mut Vec<u8>,
+    msg_type: MessageType,
+    sequence: u64,
+    timestamp_ns: u64,
+    source: &str,
+    topic: &str,
+) -> Result<(), TransportError> {
+    let source_len = u8::try_from(source.len())
+        .map_err(|_| TransportError::Serialization(format!("source too long: {}", source)))?;
+    buf.extend_from_slice(&[FRAME_MAGIC, SCHEMA_VERSION, msg_type.into(), source_len]);
+    put_u16(buf, topic.len(), "topic length")?;
+    buf.extend_from_slice(&[0, 0]);
+    buf.extend_from_slice(&sequence.to_le_bytes());
+    buf.extend_from_slice(&timestamp_ns.to_le_bytes());
+    buf.extend_from_slice(source.as_bytes());
+    buf.extend_from_slice(topic.as_bytes());
+    Ok(())
+}
+
+/// Overwrite the sequence of an encoded frame in place
+pub fn set_frame_sequence(data: &mut [u8], sequence: u64) -> Result<(), TransportError> {
+    FrameView::new(data)?;
+    data[8..16].copy_from_slice(&sequence.to_le_bytes());
+    Ok(())
+}
+
+/// A message with a fixed-layout schema
+pub trait FixedLayout: Sized {
+    /// Message type written in the frame header
+    const MESSAGE_TYPE: MessageType;
+
+    /// Zero-copy reader over an encoded body
+    type View<'a>;
+
+    /// Encoded body size, for reserving the buffer up front
+    fn encoded_len(&self) -> usize;
+
+    /// Append the body
+    fn encode_body(&self, buf: &mut Vec<u8>) -> Result<(), TransportError>;
+
+    /// Validate a body and wrap it for in-place reads
+    fn view(body: &[u8]) -> Result<Self::View<'_>, TransportError>;
+
+    /// Copy a view out into the owned message
+    fn from_view(view: &Self::View<'_>) -> Self;
+}
+
+/// A fixed-layout frame read in place
+#[derive(Debug, Clone, Copy)]
+pub struct FrameView<'a> {
+    data: &'a [u8],
+    source: &'a str,
+    topic: &'a str,
+    body: &'a [u8],
+}
+
+impl<'a> FrameView<'a> {
+    /// Validate the header of a frame
+    pub fn new(data: &'a [u8]) -> Result<Self, TransportError> {
+        if data.len() < FRAME_HEADER_LEN || !is_fixed_frame(data) {
+            return Err(malformed("bad header"));
+        }
+        // Later versions only append, so any of them can be read
+        if data[1] == 0 {
+            return Err(malformed("schema version 0"));
+        }
+        let mut reader = BodyReader {
+            data,
+            pos: FRAME_HEADER_LEN,
+        };
+        let source = reader.take(data[3] as usize, "source")?;
+        let topic = reader.take(u16_at(data, 4) as usize, "topic")?;
+        Ok(Self {
+            data,
+            source: std::str::from_utf8(source).map_err(|_| malformed("invalid UTF-8"))?,
+            topic: std::str::from_utf8(topic).map_err(|_| malformed("invalid UTF-8"))?,
+            body: &data[reader.pos..],
+        })
+    }
+
+    pub fn version(&self) -> u8 {
+        self.data[1]
+    }
+
+    pub fn msg_type(&self) -> u8 {
+        self.data[2]
+    }
+
+    pub fn sequence(&self) -> u64 {
+        u64_at(self.data, 8)
+    }
+
+    pub fn timestamp_ns(&self) -> u64 {
+        u64_at(self.data, 16)
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn topic(&self) -> &'a str {
+        self.topic
+    }
+
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    /// Owned copy of the header fields
+    pub fn header(&self) -> WireHeader {
+        WireHeader {
+            msg_type: self.msg_type(),
+            sequence: self.sequence(),
+            timestamp_ns: self.timestamp_ns(),
+            source: self.source.to_string(),
+            topic: self.topic.to_string(),
+        }
+    }
+
+    /// View the body as `M`, failing if the frame carries another type
+    pub fn message<M: FixedLayout>(&self) -> Result<M::View<'a>, TransportError> {
+        if self.msg_type() != u8::from(M::MESSAGE_TYPE) {
+            return Err(TransportError::Serialization(format!(
+                "expected message type {}, frame has {}",
+                u8::from(M::MESSAGE_TYPE),
+                self.msg_type()
+            )));
+        }
+        M::view(self.body)
+    }
+}
+
+/// Price levels of a repeating group, read in place
+#[derive(Debug, Clone, Copy)]
+pub struct LevelsView<'a> {
+    data: &'a [u8],
+    stride: usize,
+    count: usize,
+}
+
+impl<'a> LevelsView<'a> {
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<CompactLevel> {
+        (index < self.count).then(|| {
+            let at = index * self.stride;
+            CompactLevel::new(i64_at(self.data, at), i64_at(self.data, at + 8))
+        })
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = CompactLevel> + 'a {
+        let view = *self;
+        (0..view.count).map(move |i| view.get(i).unwrap())
+    }
+
+    pub fn to_vec(&self) -> Vec<CompactLevel> {
+        self.iter().collect()
+    }
+}
+
+/// `DepthUpdate` read in place
+#[derive(Debug, Clone, Copy)]
+pub struct DepthUpdateView<'a> {
+    block: &'a [u8],
+    bids: LevelsView<'a>,
+    asks: LevelsView<'a>,
+    exchange: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> DepthUpdateView<'a> {
+    pub fn first_update_id(&self) -> u64 {
+        u64_at(self.block, 0)
+    }
+
+    pub fn final_update_id(&self) -> u64 {
+        u64_at(self.block, 8)
+    }
+
+    pub fn timestamp_ns(&self) -> u64 {
+        u64_at(self.block, 16)
+    }
+
+    pub fn bids(&self) -> LevelsView<'a> {
+        self.bids
+    }
+
+    pub fn asks(&self) -> LevelsView<'a> {
+        self.asks
+    }
+
+    pub fn exchange(&self) -> &'a str {
+        self.exchange
+    }
+
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+}
+
+impl FixedLayout for DepthUpdate {
+    const MESSAGE_TYPE: MessageType = MessageType::DepthUpdate;
+    type View<'a> = DepthUpdateView<'a>;
+
+    fn encoded_len(&self) -> usize {
+        2 + DEPTH_BLOCK_LEN
+            + 8
+            + (self.bids.len() + self.asks.len()) * LEVEL_LEN
+            + 2
+            + self.exchange.len()
+            + self.symbol.len()
+    }
+
+    fn encode_body(&self, buf: &mut Vec<u8>) -> Result<(), TransportError> {
+        put_u16(buf, DEPTH_BLOCK_LEN, "block length")?;
+        buf.extend_from_slice(&self.first_update_id.to_le_bytes());
+        buf.extend_from_slice(&self.final_update_id.to_le_bytes());
+        buf.extend_from_slice(&self.timestamp_ns.to_le_bytes());
+        put_levels(buf, &self.bids)?;
+        put_levels(buf, &self.asks)?;
+        put_str(buf, &self.exchange)?;
+        put_str(buf, &self.symbol)
+    }
+
+    fn view(body: &[u8]) -> Result<DepthUpdateView<'_>, TransportError> {
+        let mut reader = BodyReader::new(body);
+        Ok(DepthUpdateView {
+            block: reader.block(DEPTH_BLOCK_LEN)?,
+            bids: reader.levels()?,
+            asks: reader.levels()?,
+            exchange: reader.str()?,
+            symbol: reader.str()?,
+        })
+    }
+
+    fn from_view(view: &DepthUpdateView<'_>) -> Self {
+        DepthUpdate {
+            exchange: view.exchange().to_string(),
+            symbol: view.symbol().to_string(),
+            first_update_id: view.first_update_id(),
+            final_update_id: view.final_update_id(),
+            timestamp_ns: view.timestamp_ns(),
+            bids: view.bids().to_vec(),
+            asks: view.asks().to_vec(),
+        }
+    }
+}
+
+/// `OrderBookSnapshot` read in place
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotView<'a> {
+    block: &'a [u8],
+    bids: LevelsView<'a>,
+    asks: LevelsView<'a>,
+    exchange: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> SnapshotView<'a> {
+    pub fn last_update_id(&self) -> u64 {
+        u64_at(self.block, 0)
+    }
+
+    pub fn timestamp_ns(&self) -> u64 {
+        u64_at(self.block, 8)
+    }
+
+    pub fn bids(&self) -> LevelsView<'a> {
+        self.bids
+    }
+
+    pub fn asks(&self) -> LevelsView<'a> {
+        self.asks
+    }
+
+    pub fn exchange(&self) -> &'a str {
+        self.exchange
+    }
+
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+}
+
+impl FixedLayout for OrderBookSnapshot {
+    const MESSAGE_TYPE: MessageType = MessageType::OrderBookSnapshot;
+    type View<'a> = SnapshotView<'a>;
+
+    fn encoded_len(&self) -> usize {
+        2 + SNAPSHOT_BLOCK_LEN
+            + 8
+            + (self.bids.len() + self.asks.len()) * LEVEL_LEN
+            + 2
+            + self.exchange.len()
+            + self.symbol.len()
+    }
+
+    fn encode_body(&self, buf: &mut Vec<u8>) -> Result<(), TransportError> {
+        put_u16(buf, SNAPSHOT_BLOCK_LEN, "block length")?;
+        buf.extend_from_slice(&self.last_update_id.to_le_bytes());
+        buf.extend_from_slice(&self.timestamp_ns.to_le_bytes());
+        put_levels(buf, &self.bids)?;
+        put_levels(buf, &self.asks)?;
+        put_str(buf, &self.exchange)?;
+        put_str(buf, &self.symbol)
+    }
+
+    fn view(body: &[u8]) -> Result<SnapshotView<'_>, TransportError> {
+        let mut reader = BodyReader::new(body);
+        Ok(SnapshotView {
+            block: reader.block(SNAPSHOT_BLOCK_LEN)?,
+            bids: reader.levels()?,
+            asks: reader.levels()?,
+            exchange: reader.str()?,
+            symbol: reader.str()?,
+        })
+    }
+
+    fn from_view(view: &SnapshotView<'_>) -> Self {
+        OrderBookSnapshot {
+            exchange: view.exchange().to_string(),
+            symbol: view.symbol().to_string(),
+            last_update_id: view.last_update_id(),
+            timestamp_ns: view.timestamp_ns(),
+            bids: view.bids().to_vec(),
+            asks: view.asks().to_vec(),
+        }
+    }
+}
+
+/// `TradeUpdate` read in place
+#[derive(Debug, Clone, Copy)]
+pub struct TradeView<'a> {
+    block: &'a [u8],
+    exchange: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> TradeView<'a> {
+    pub fn trade_id(&self) -> u64 {
+        u64_at(self.block, 0)
+    }
+
+    pub fn price_raw(&self) -> i64 {
+        i64_at(self.block, 8)
+    }
+
+    pub fn quantity_raw(&self) -> i64 {
+        i64_at(self.block, 16)
+    }
+
+    pub fn timestamp_ns(&self) -> u64 {
+        u64_at(self.block, 24)
+    }
+
+    pub fn buyer_is_maker(&self) -> bool {
+        self.block[32] != 0
+    }
+
+    pub fn exchange(&self) -> &'a str {
+        self.exchange
+    }
+
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+}
+
+impl FixedLayout for TradeUpdate {
+    const MESSAGE_TYPE: MessageType = MessageType::Trade;
+    type View<'a> = TradeView<'a>;
+
+    fn encoded_len(&self) -> usize {
+        2 + TRADE_BLOCK_LEN + 2 + self.exchange.len() + self.symbol.len()
+    }
+
+    fn encode_body(&self, buf: &mut Vec<u8>) -> Result<(), TransportError> {
+        put_u16(buf, TRADE_BLOCK_LEN, "block length")?;
+        buf.extend_from_slice(&self.trade_id.to_le_bytes());
+        buf.extend_from_slice(&self.price_raw.to_le_bytes());
+        buf.extend_from_slice(&self.quantity_raw.to_le_bytes());
+        buf.extend_from_slice(&self.timestamp_ns.to_le_bytes());
+        buf.push(self.buyer_is_maker as u8);
+        put_str(buf, &self.exchange)?;
+        put_str(buf, &self.symbol)
+    }
+
+    fn view(body: &[u8]) -> Result<TradeView<'_>, TransportError> {
+        let mut reader = BodyReader::new(body);
+        Ok(TradeView {
+            block: reader.block(TRADE_BLOCK_LEN)?,
+            exchange: reader.str()?,
+            symbol: reader.str()?,
+        })
+    }
+
+    fn from_view(view: &TradeView<'_>) -> Self {
+        TradeUpdate {
+            exchange: view.exchange().to_string(),
+            symbol: view.symbol().to_string(),
+            trade_id: view.trade_id(),
+            price_raw: view.price_raw(),
+            quantity_raw: view.quantity_raw(),
+            buyer_is_maker: view.buyer_is_maker(),
+            timestamp_ns: view.timestamp_ns(),
+        }
+    }
+}
+
+/// `SignalMessage` read in place
+#[derive(Debug, Clone, Copy)]
+pub struct SignalView<'a> {
+    block: &'a [u8],
+    signal_id: &'a str,
+    strategy_id: &'a str,
+    exchange: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> SignalView<'a> {
+    pub fn current_price_raw(&self) -> i64 {
+        i64_at(self.block, 0)
+    }
+
+    pub fn fair_value_raw(&self) -> i64 {
+        i64_at(self.block, 8)
+    }
+
+    pub fn entry_price_raw(&self) -> i64 {
+        i64_at(self.block, 16)
+    }
+
+    pub fn target_price_raw(&self) -> i64 {
+        i64_at(self.block, 24)
+    }
+
+    pub fn stop_price_raw(&self) -> i64 {
+        i64_at(self.block, 32)
+    }
+
+    pub fn timestamp_ms(&self) -> u64 {
+        u64_at(self.block, 40)
+    }
+
+    pub fn strength(&self) -> f32 {
+        f32::from_bits(u32_at(self.block, 48))
+    }
+
+    pub fn confidence(&self) -> f32 {
+        f32::from_bits(u32_at(self.block, 52))
+    }
+
+    pub fn expected_edge_bps(&self) -> i32 {
+        u32_at(self.block, 56) as i32
+    }
+
+    pub fn strategy_type(&self) -> u8 {
+        self.block[60]
+    }
+
+    pub fn direction(&self) -> i8 {
+        self.block[61] as i8
+    }
+
+    pub fn signal_id(&self) -> &'a str {
+        self.signal_id
+    }
+
+    pub fn strategy_id(&self) -> &'a str {
+        self.strategy_id
+    }
+
+    pub fn exchange(&self) -> &'a str {
+        self.exchange
+    }
+
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+}
+
+impl FixedLayout for SignalMessage {
+    const MESSAGE_TYPE: MessageType = MessageType::Signal;
+    type View<'a> = SignalView<'a>;
+
+    fn encoded_len(&self) -> usize {
+        2 + SIGNAL_BLOCK_LEN
+            + 4
+            + self.signal_id.len()
+            + self.strategy_id.len()
+            + self.exchange.len()
+            + self.symbol.len()
+    }
+
+    fn encode_body(&self, buf: &mut Vec<u8>) -> Result<(), TransportError> {
+        put_u16(buf, SIGNAL_BLOCK_LEN, "block length")?;
+        for raw in [
+            self.current_price_raw,
+            self.fair_value_raw,
+            self.entry_price_raw,
+            self.target_price_raw,
+            self.stop_price_raw,
+        ] {
+            buf.extend_from_slice(&raw.to_le_bytes());
+        }
+        buf.extend_from_slice(&self.timestamp_ms.to_le_bytes());
+        buf.extend_from_slice(&self.strength.to_bits().to_le_bytes());
+        buf.extend_from_slice(&self.confidence.to_bits().to_le_bytes());
+        buf.extend_from_slice(&self.expected_edge_bps.to_le_bytes());
+        buf.push(self.strategy_type);
+        buf.push(self.direction as u8);
+        put_str(buf, &self.signal_id)?;
+        put_str(buf, &self.strategy_id)?;
+        put_str(buf, &self.exchange)?;
+        put_str(buf, &self.symbol)
+    }
+
+    fn view(body: &[u8]) -> Result<SignalView<'_>, TransportError> {
+        let mut reader = BodyReader::new(body);
+        Ok(SignalView {
+            block: reader.block(SIGNAL_BLOCK_LEN)?,
+            signal_id: reader.str()?,
+            strategy_id: reader.str()?,
+            exchange: reader.str()?,
+            symbol: reader.str()?,
+        })
+    }
+
+    fn from_view(view: &SignalView<'_>) -> Self {
+        SignalMessage {
+            signal_id: view.signal_id().to_string(),
+            strategy_id: view.strategy_id().to_string(),
+            strategy_type: view.strategy_type(),
+            exchange: view.exchange().to_string(),
+            symbol: view.symbol().to_string(),
+            direction: view.direction(),
+            strength: view.strength(),
+            confidence: view.confidence(),
+            current_price_raw: view.current_price_raw(),
+            fair_value_raw: view.fair_value_raw(),
+            entry_price_raw: view.entry_price_raw(),
+            target_price_raw: view.target_price_raw(),
+            stop_price_raw: view.stop_price_raw(),
+            expected_edge_bps: view.expected_edge_bps(),
+            timestamp_ms: view.timestamp_ms(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRICE: i64 = 100_000_000;
+
+    fn frame<M: FixedLayout>(message: &M, sequence: u64) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_header(
+            &mut buf,
+            M::MESSAGE_TYPE,
+            sequence,
+            42,
+            "gateway",
+            "md.binance.BTCUSDT.depth",
+        )
+        .unwrap();
+        message.encode_body(&mut buf).unwrap();
+        buf
+    }
+
+    fn depth_update() -> DepthUpdate {
+        DepthUpdate {
+            exchange: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 100,
+            final_update_id: 105,
+            timestamp_ns: 7,
+            bids: vec![
+                CompactLevel::new(50_000 * PRICE, PRICE),
+                CompactLevel::new(49_999 * PRICE, 0),
+            ],
+            asks: vec![CompactLevel::new(50_001 * PRICE, -3)],
+        }
+    }
+
+    #[test]
+    fn test_depth_update_view() {
+        let update = depth_update();
+        let data = frame(&update, 9);
+        assert_eq!(data.len(), FRAME_HEADER_LEN + 31 + update.encoded_len());
+
+        let frame = FrameView::new(&data).unwrap();
+        assert_eq!(frame.version(), SCHEMA_VERSION);
+        assert_eq!(frame.sequence(), 9);
+        assert_eq!(frame.timestamp_ns(), 42);
+        assert_eq!(frame.source(), "gateway");
+        assert_eq!(frame.topic(), "md.binance.BTCUSDT.depth");
+
+        let view = frame.message::<DepthUpdate>().unwrap();
+        assert_eq!(view.first_update_id(), 100);
+        assert_eq!(view.final_update_id(), 105);
+        assert_eq!(view.symbol(), "BTCUSDT");
+        assert_eq!(view.bids().len(), 2);
+        assert_eq!(view.asks().get(0), Some(update.asks[0]));
+        assert_eq!(view.asks().get(1), None);
+
+        let decoded = DepthUpdate::from_view(&view);
+        assert_eq!(decoded.bids, update.bids);
+        assert_eq!(decoded.asks, update.asks);
+        assert_eq!(decoded.timestamp_ns, 7);
+    }
+
+    #[test]
+    fn test_snapshot_trade_and_signal_roundtrip() {
+        let snapshot = OrderBookSnapshot {
+            exchange: "kraken".to_string(),
+            symbol: "ETHUSD".to_string(),
+            last_update_id: 12,
+            timestamp_ns: 3,
+            bids: vec![],
+            asks: vec![CompactLevel::new(3_000 * PRICE, 5)],
+        };
+        let data = frame(&snapshot, 0);
+        let view = FrameView::new(&data)
+            .unwrap()
+            .message::<OrderBookSnapshot>()
+            .unwrap();
+        let decoded = OrderBookSnapshot::from_view(&view);
+        assert_eq!(decoded.last_update_id, 12);
+        assert!(decoded.bids.is_empty());
+        assert_eq!(decoded.asks, snapshot.asks);
+
+        let mut trade = TradeUpdate::new(
+            "binance",
+            "BTCUSDT",
+            77,
+            trading_core::Price::from_int(50_000),
+            trading_core::Quantity::from_f64(0.25),
+            true,
+        );
+        trade.timestamp_ns = 11;
+        let data = frame(&trade, 1);
+        let view = FrameView::new(&data)
+            .unwrap()
+            .message::<TradeUpdate>()
+            .unwrap();
+        assert_eq!(view.trade_id(), 77);
+        assert!(view.buyer_is_maker());
+        let decoded = TradeUpdate::from_view(&view);
+        assert_eq!(decoded.price_raw, trade.price_raw);
+        assert_eq!(decoded.quantity_raw, trade.quantity_raw);
+        assert_eq!(decoded.timestamp_ns, 11);
+
+        let mut signal = SignalMessage::new("sig-1", "pairs", "binance", "BTCUSDT")
+            .with_strength(-0.5)
+            .with_confidence(0.75)
+            .with_prices(1, 2, 3, 4, -5)
+            .with_edge_bps(-12);
+        signal.strategy_type = 3;
+        signal.direction = -1;
+        let data = frame(&signal, 2);
+        let view = FrameView::new(&data)
+            .unwrap()
+            .message::<SignalMessage>()
+            .unwrap();
+        assert_eq!(view.direction(), -1);
+        assert_eq!(view.stop_price_raw(), -5);
+        let decoded = SignalMessage::from_view(&view);
+        assert_eq!(decoded.signal_id, "sig-1");
+        assert_eq!(decoded.strategy_type, 3);
+        assert_eq!(decoded.strength, -0.5);
+        assert_eq!(decoded.confidence, 0.75);
+        assert_eq!(decoded.expected_edge_bps, -12);
+        assert_eq!(decoded.timestamp_ms, signal.timestamp_ms);
+    }
+
+    #[test]
+    fn test_older_reader_skips_appended_fields() {
+        let update = depth_update();
+        let mut body = Vec::new();
+        update.encode_body(&mut body).unwrap();
+
+        let mut data = frame(&update, 1);
+        data[1] = SCHEMA_VERSION + 1;
+        assert_eq!(FrameView::new(&data).unwrap().version(), SCHEMA_VERSION + 1);
+
+        // A newer writer with a 4-byte longer root block
+        let mut extended = ((DEPTH_BLOCK_LEN + 4) as u16).to_le_bytes().to_vec();
+        extended.extend_from_slice(&body[2..2 + DEPTH_BLOCK_LEN]);
+        extended.extend_from_slice(&[0xFF; 4]);
+        extended.extend_from_slice(&body[2 + DEPTH_BLOCK_LEN..]);
+
+        let view = DepthUpdate::view(&extended).unwrap();
+        assert_eq!(view.final_update_id(), 105);
+        assert_eq!(view.bids().to_vec(), update.bids);
+        assert_eq!(view.symbol(), "BTCUSDT");
+    }
+
+    #[test]
+    fn test_rejects_malformed_frames() {
+        let data = frame(&depth_update(), 1);
+
+        // Every truncation fails cleanly instead of panicking
+        for len in 0..data.len() {
+            let truncated = &data[..len];
+            let result = FrameView::new(truncated).and_then(|f| f.message::<DepthUpdate>());
+            assert!(result.is_err(), "accepted {} of {} bytes", len, data.len());
+        }
+
+        let mut unversioned = data.clone();
+        unversioned[1] = 0;
+        assert!(FrameView::new(&unversioned).is_err());
+
+        let frame = FrameView::new(&data).unwrap();
+        assert!(frame.message::<TradeUpdate>().is_err());
+        assert!(!is_fixed_frame(b"\x02bincode"));
+    }
+}
@@ -10,6 +10,8 @@ pub const MSG_DEPTH_UPDATE: u8 = 2;
 pub const MSG_TRADE: u8 = 3;
 pub const MSG_SIGNAL: u8 = 4;
 pub const MSG_SNAPSHOT_REQUEST: u8 = 5;
+pub const MSG_RETRANSMIT_REQUEST: u8 = 6;
+pub const MSG_RETRANSMIT_REJECT: u8 = 7;
 
 /// Message type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -20,6 +22,8 @@ pub enum MessageType {
     Trade = 3,
     Signal = 4,
     SnapshotRequest = 5,
+    RetransmitRequest = 6,
+    RetransmitReject = 7,
 }
 
 impl From<u8> for MessageType {
@@ -30,6 +34,8 @@ impl From<u8> for MessageType {
             3 => MessageType::Trade,
             4 => MessageType::Signal,
             5 => MessageType::SnapshotRequest,
+            6 => MessageType::RetransmitRequest,
+            7 => MessageType::RetransmitReject,
             _ => MessageType::DepthUpdate, // Default fallback
         }
     }
@@ -55,10 +61,40 @@ pub struct WireMessage {
     pub timestamp_ns: u64,
     /// Source identifier (e.g., "gateway-1", "binance")
     pub source: String,
+    /// Routing topic (e.g., "md.binance.BTCUSDT.depth"); empty when unrouted
+    pub topic: String,
     /// Serialized payload
     pub payload: Vec<u8>,
 }
 
+/// Leading fields of a serialized `WireMessage`
+///
+/// Lets recorders and routers read ordering data without copying the payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WireHeader {
+    pub msg_type: u8,
+    pub sequence: u64,
+    pub timestamp_ns: u64,
+    pub source: String,
+    pub topic: String,
+}
+
+/// Ask a publisher to resend a range of its messages
+///
+/// Payload of `MSG_RETRANSMIT_REQUEST`; echoed back as the payload of
+/// `MSG_RETRANSMIT_REJECT` when the range is no longer buffered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetransmitRequest {
+    /// Publisher source whose messages are missing
+    pub source: String,
+    /// Topic of the stream with the gap; sequences are per source and topic
+    pub topic: String,
+    /// First missing sequence
+    pub from_sequence: u64,
+    /// Last missing sequence, inclusive
+    pub to_sequence: u64,
+}
+
 impl WireMessage {
     /// Create a new wire message
     pub fn new<T: Serialize>(
@@ -72,6 +108,7 @@ impl WireMessage {
             sequence,
             timestamp_ns: current_timestamp_ns(),
             source: source.to_string(),
+            topic: String::new(),
             payload: bincode::serialize(payload)?,
         })
     }
@@ -88,10 +125,17 @@ impl WireMessage {
             sequence,
             timestamp_ns: current_timestamp_ns(),
             source: source.to_string(),
+            topic: String::new(),
             payload,
         }
     }
 
+    /// Route the message on a topic
+    pub fn with_topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = topic.into();
+        self
+    }
+
     /// Decode the payload into a typed message
     pub fn decode_payload<T: serde::de::DeserializeOwned>(&self) -> Result<T, bincode::Error> {
         bincode::deserialize(&self.payload)
@@ -111,6 +155,19 @@ impl WireMessage {
     pub fn deserialize(data: &[u8]) -> Result<Self, bincode::Error> {
         bincode::deserialize(data)
     }
+
+    /// Read only the header of a serialized wire message
+    ///
+    /// Also reads fixed-layout frames, so routing and sequencing don't care
+    /// which codec produced the message.
+    pub fn header(data: &[u8]) -> Result<WireHeader, bincode::Error> {
+        if super::fixed::is_fixed_frame(data) {
+            return super::fixed::FrameView::new(data)
+                .map(|frame| frame.header())
+                .map_err(|e| Box::new(bincode::ErrorKind::Custom(e.to_string())));
+        }
+        bincode::deserialize(data)
+    }
 }
 
 /// Get current timestamp in nanoseconds
@@ -155,6 +212,19 @@ mod tests {
         assert_eq!(decoded, payload);
     }
 
+    #[test]
+    fn test_header_without_payload_copy() {
+        let msg = WireMessage::with_raw_payload(MessageType::Trade, 7, "gw", vec![1, 2, 3])
+            .with_topic("md.binance.BTCUSDT.trades");
+        let header = WireMessage::header(&msg.serialize().unwrap()).unwrap();
+
+        assert_eq!(header.msg_type, MSG_TRADE);
+        assert_eq!(header.sequence, 7);
+        assert_eq!(header.timestamp_ns, msg.timestamp_ns);
+        assert_eq!(header.source, "gw");
+        assert_eq!(header.topic, "md.binance.BTCUSDT.trades");
+    }
+
     #[test]
     fn test_message_type_conversion() {
         assert_eq!(MessageType::from(1), MessageType::OrderBookSnapshot);
@@ -162,6 +232,7 @@ mod tests {
         assert_eq!(MessageType::from(3), MessageType::Trade);
         assert_eq!(MessageType::from(4), MessageType::Signal);
         assert_eq!(MessageType::from(5), MessageType::SnapshotRequest);
+        assert_eq!(MessageType::from(7), MessageType::RetransmitReject);
 
         assert_eq!(u8::from(MessageType::OrderBookSnapshot), 1);
         assert_eq!(u8::from(MessageType::Signal), 4);
@@ -3,13 +3,25 @@
 //! Contains the abstract traits and types that define the transport interface.
 //! Other crates depend on these abstractions, not concrete implementations.
 
+pub mod codec;
 pub mod error;
+pub mod fixed;
 pub mod message;
+pub mod topic;
 pub mod traits;
 
+pub use codec::{BincodeCodec, Codec, CodecKind, CodecMessage, FixedCodec};
 pub use error::TransportError;
+pub use fixed::{
+    DepthUpdateView, FRAME_MAGIC, FixedLayout, FrameView, LevelsView, SCHEMA_VERSION, SignalView,
+    SnapshotView, TradeView, is_fixed_frame,
+};
 pub use message::{
-    MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, MSG_SIGNAL, MSG_SNAPSHOT_REQUEST, MSG_TRADE,
-    MessageType, WireMessage,
+    MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, MSG_RETRANSMIT_REJECT, MSG_RETRANSMIT_REQUEST,
+    MSG_SIGNAL, MSG_SNAPSHOT_REQUEST, MSG_TRADE, MessageType, RetransmitRequest, WireHeader,
+    WireMessage,
+};
+pub use topic::{
+    TOPIC_SEPARATOR, TopicPattern, market_data_topic, parse_market_data_topic, signal_topic,
 };
 pub use traits::{Publisher, Subscriber};
@@ -0,0 +1,175 @@
+//! Topics
+//!
+//! Hierarchical, dot-separated routing keys such as
+//! `md.binance.BTCUSDT.depth` or `signals.mean-reversion`, and the wildcard
+//! patterns subscribers select them with:
+//!
+//! - `*` matches exactly one segment (`md.*.BTCUSDT.depth`)
+//! - `>` as the last segment matches one or more trailing segments
+//!   (`md.binance.>`)
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::TransportError;
+
+/// Topic segment separator
+pub const TOPIC_SEPARATOR: char = '.';
+
+/// Topic of an exchange/symbol market data stream, e.g. `md.binance.BTCUSDT.depth`
+pub fn market_data_topic(exchange: &str, symbol: &str, stream: &str) -> String {
+    format!("md.{}.{}.{}", exchange, symbol, stream)
+}
+
+/// Exchange, symbol and stream of a market data topic
+pub fn parse_market_data_topic(topic: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = topic.split(TOPIC_SEPARATOR);
+    match (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) {
+        (Some("md"), Some(exchange), Some(symbol), Some(stream), None) => {
+            Some((exchange, symbol, stream))
+        }
+        _ => None,
+    }
+}
+
+/// Topic a strategy publishes its signals on
+pub fn signal_topic(strategy: &str) -> String {
+    format!("signals.{}", strategy)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    /// `*`
+    Any,
+    /// `>`
+    Rest,
+}
+
+/// Wildcard subscription pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicPattern {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl TopicPattern {
+    pub fn parse(pattern: &str) -> Result<Self, TransportError> {
+        let invalid = |reason: &str| {
+            TransportError::Config(format!("invalid topic pattern {:?}: {}", pattern, reason))
+        };
+        if pattern.is_empty() {
+            return Err(invalid("empty"));
+        }
+
+        let parts: Vec<&str> = pattern.split(TOPIC_SEPARATOR).collect();
+        let mut segments = Vec::with_capacity(parts.len());
+        for (i, part) in parts.iter().enumerate() {
+            segments.push(match *part {
+                "" => return Err(invalid("empty segment")),
+                "*" => Segment::Any,
+                ">" if i + 1 == parts.len() => Segment::Rest,
+                ">" => return Err(invalid("'>' must be the last segment")),
+                literal => Segment::Literal(literal.to_string()),
+            });
+        }
+        Ok(Self {
+            pattern: pattern.to_string(),
+            segments,
+        })
+    }
+
+    /// Whether `topic` is selected by this pattern
+    pub fn matches(&self, topic: &str) -> bool {
+        let mut parts = topic.split(TOPIC_SEPARATOR);
+        for segment in &self.segments {
+            match (segment, parts.next()) {
+                (_, None) => return false,
+                (Segment::Rest, Some(_)) => return true,
+                (Segment::Any, Some(_)) => {}
+                (Segment::Literal(literal), Some(part)) if literal == part => {}
+                (Segment::Literal(_), Some(_)) => return false,
+            }
+        }
+        parts.next().is_none()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl FromStr for TopicPattern {
+    type Err = TransportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for TopicPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, topic: &str) -> bool {
+        TopicPattern::parse(pattern).unwrap().matches(topic)
+    }
+
+    #[test]
+    fn test_literal_and_single_segment_wildcard() {
+        assert!(matches(
+            "md.binance.BTCUSDT.depth",
+            "md.binance.BTCUSDT.depth"
+        ));
+        assert!(!matches(
+            "md.binance.BTCUSDT.depth",
+            "md.binance.ETHUSDT.depth"
+        ));
+        assert!(matches("md.*.BTCUSDT.depth", "md.kraken.BTCUSDT.depth"));
+        assert!(!matches("md.*.depth", "md.binance.BTCUSDT.depth"));
+        assert!(!matches("md.binance.*", "md.binance"));
+    }
+
+    #[test]
+    fn test_trailing_wildcard() {
+        assert!(matches("md.binance.>", "md.binance.BTCUSDT.depth"));
+        assert!(matches("md.binance.>", "md.binance.BTCUSDT"));
+        assert!(!matches("md.binance.>", "md.binance"));
+        assert!(matches(">", "signals.pairs"));
+        assert!(!matches("signals.>", "md.binance.BTCUSDT.depth"));
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(TopicPattern::parse("").is_err());
+        assert!(TopicPattern::parse("md..depth").is_err());
+        assert!(TopicPattern::parse("md.>.depth").is_err());
+    }
+
+    #[test]
+    fn test_topic_helpers() {
+        assert_eq!(
+            market_data_topic("binance", "BTCUSDT", "depth"),
+            "md.binance.BTCUSDT.depth"
+        );
+        assert_eq!(signal_topic("pairs"), "signals.pairs");
+        assert_eq!(
+            parse_market_data_topic("md.binance.BTCUSDT.depth"),
+            Some(("binance", "BTCUSDT", "depth"))
+        );
+        assert_eq!(parse_market_data_topic("signals.pairs"), None);
+        assert_eq!(parse_market_data_topic("md.binance.BTCUSDT"), None);
+    }
+}
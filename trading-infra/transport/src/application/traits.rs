@@ -2,6 +2,8 @@
 //!
 //! Core abstractions for message publishing and subscribing.
 
+use std::sync::Arc;
+
 use super::error::TransportError;
 
 /// Message publisher interface
@@ -35,6 +37,26 @@ pub trait Publisher: Send + Sync {
     }
 }
 
+/// Shared publishers, e.g. one handle for the data path and one for a
+/// request handler
+impl<P: Publisher + ?Sized> Publisher for Arc<P> {
+    fn publish(&self, data: &[u8]) -> Result<(), TransportError> {
+        (**self).publish(data)
+    }
+
+    fn publish_to(&self, topic: &str, data: &[u8]) -> Result<(), TransportError> {
+        (**self).publish_to(topic, data)
+    }
+
+    fn flush(&self) -> Result<(), TransportError> {
+        (**self).flush()
+    }
+
+    fn is_active(&self) -> bool {
+        (**self).is_active()
+    }
+}
+
 /// Message subscriber interface
 ///
 /// Implementations receive serialized data from publishers.
@@ -71,7 +93,7 @@ pub type BoxSubscriber = Box<dyn Subscriber>;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::{Arc, Mutex};
+    use std::sync::Mutex;
 
     struct MockPublisher {
         messages: Arc<Mutex<Vec<Vec<u8>>>>,
@@ -0,0 +1,515 @@
+//! Aeron Transport Implementation
+//!
+//! `Publisher`/`Subscriber` over Aeron publications and subscriptions.
+//!
+//! The Aeron client itself sits behind the `AeronClient`, `Publication` and
+//! `Subscription` traits, which mirror the calls of the Aeron C/Java client
+//! API; `RusteronClient` implements them over the C client. This module owns what is independent of the binding:
+//!
+//! - mapping `offer` result codes to `TransportError`
+//! - retrying admin actions and riding out brief back-pressure
+//! - reassembling fragmented messages per publisher session
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::config::{AeronConfig, TransportType};
+use crate::application::error::TransportError;
+use crate::application::traits::{Publisher, Subscriber};
+
+/// `offer` result: no subscriber connected
+pub const NOT_CONNECTED: i64 = -1;
+/// `offer` result: subscribers are behind; retry later
+pub const BACK_PRESSURED: i64 = -2;
+/// `offer` result: log rotation in progress; retry immediately
+pub const ADMIN_ACTION: i64 = -3;
+/// `offer` result: publication closed
+pub const CLOSED: i64 = -4;
+/// `offer` result: publication reached its maximum position
+pub const MAX_POSITION_EXCEEDED: i64 = -5;
+
+/// Fragment header flag: first fragment of a message
+pub const BEGIN_FRAG_FLAG: u8 = 0x80;
+/// Fragment header flag: last fragment of a message
+pub const END_FRAG_FLAG: u8 = 0x40;
+/// Fragment header flags for a message carried in one fragment
+pub const UNFRAGMENTED: u8 = BEGIN_FRAG_FLAG | END_FRAG_FLAG;
+
+/// Fragment metadata passed to subscription handlers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentHeader {
+    /// Publisher session the fragment belongs to
+    pub session_id: i32,
+    /// BEGIN/END fragment flags
+    pub flags: u8,
+}
+
+/// Aeron publication, as exposed by a client binding
+pub trait Publication: Send + Sync {
+    /// Offer a message; returns the new stream position or a negative code
+    fn offer(&self, data: &[u8]) -> i64;
+
+    /// Whether at least one subscriber is connected
+    fn is_connected(&self) -> bool;
+
+    fn is_closed(&self) -> bool;
+}
+
+/// Aeron subscription, as exposed by a client binding
+pub trait Subscription: Send + Sync {
+    /// Deliver up to `fragment_limit` fragments; returns the count delivered
+    fn poll(
+        &self,
+        handler: &mut dyn FnMut(&[u8], FragmentHeader),
+        fragment_limit: usize,
+    ) -> Result<usize, TransportError>;
+
+    /// Whether at least one publication image is available
+    fn is_connected(&self) -> bool;
+}
+
+/// Aeron client connected to a media driver
+pub trait AeronClient: Send + Sync {
+    fn add_publication(
+        &self,
+        channel: &str,
+        stream_id: i32,
+    ) -> Result<Box<dyn Publication>, TransportError>;
+
+    fn add_subscription(
+        &self,
+        channel: &str,
+        stream_id: i32,
+    ) -> Result<Box<dyn Subscription>, TransportError>;
+}
+
+/// Map an `offer` result to a transport result
+pub fn offer_result(result: i64) -> Result<(), TransportError> {
+    match result {
+        position if position >= 0 => Ok(()),
+        NOT_CONNECTED => Err(TransportError::ConnectionFailed(
+            "no Aeron subscriber connected".to_string(),
+        )),
+        BACK_PRESSURED | ADMIN_ACTION => Err(TransportError::Full),
+        CLOSED => Err(TransportError::ChannelClosed),
+        MAX_POSITION_EXCEEDED => Err(TransportError::Io(
+            "Aeron publication reached max position".to_string(),
+        )),
+        code => Err(TransportError::Io(format!("Aeron offer failed: {}", code))),
+    }
+}
+
+/// Check the channel URI matches the transport type
+pub fn validate_channel(
+    transport_type: &TransportType,
+    config: &AeronConfig,
+) -> Result<(), TransportError> {
+    let expected = match transport_type {
+        TransportType::AeronIpc => "aeron:ipc",
+        TransportType::AeronUdp => "aeron:udp",
+        other => {
+            return Err(TransportError::Config(format!(
+                "{:?} is not an Aeron transport",
+                other
+            )));
+        }
+    };
+    if config.channel == expected || config.channel.starts_with(&format!("{}?", expected)) {
+        Ok(())
+    } else {
+        Err(TransportError::Config(format!(
+            "channel {} does not match {:?}",
+            config.channel, transport_type
+        )))
+    }
+}
+
+/// Aeron-backed publisher
+pub struct AeronPublisher {
+    publication: Box<dyn Publication>,
+    /// How long to keep retrying while back-pressured
+    back_pressure_timeout: Duration,
+}
+
+impl AeronPublisher {
+    pub fn new(publication: Box<dyn Publication>, config: &AeronConfig) -> Self {
+        Self {
+            publication,
+            back_pressure_timeout: Duration::from_micros(config.back_pressure_timeout_us),
+        }
+    }
+
+    /// Add a publication on the configured channel and stream
+    pub fn connect(
+        client: &dyn AeronClient,
+        transport_type: &TransportType,
+        config: &AeronConfig,
+    ) -> Result<Self, TransportError> {
+        validate_channel(transport_type, config)?;
+        let publication = client.add_publication(&config.channel, config.stream_id)?;
+        Ok(Self::new(publication, config))
+    }
+}
+
+impl Publisher for AeronPublisher {
+    fn publish(&self, data: &[u8]) -> Result<(), TransportError> {
+        let mut deadline = None;
+        loop {
+            let result = self.publication.offer(data);
+            match result {
+                ADMIN_ACTION => continue,
+                BACK_PRESSURED => {
+                    let deadline = *deadline
+                        .get_or_insert_with(|| Instant::now() + self.back_pressure_timeout);
+                    if Instant::now() >= deadline {
+                        return offer_result(result);
+                    }
+                    std::hint::spin_loop();
+                }
+                _ => return offer_result(result),
+            }
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        !self.publication.is_closed() && self.publication.is_connected()
+    }
+}
+
+/// Rebuilds messages from BEGIN/middle/END fragments, per session
+#[derive(Debug, Default)]
+pub struct FragmentAssembler {
+    partial: HashMap<i32, Vec<u8>>,
+}
+
+impl FragmentAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one fragment; calls `handler` when a message is complete
+    ///
+    /// Returns true if a message was delivered. Fragments arriving without
+    /// a BEGIN are dropped.
+    pub fn on_fragment(
+        &mut self,
+        data: &[u8],
+        header: FragmentHeader,
+        handler: &mut dyn FnMut(&[u8]),
+    ) -> bool {
+        if header.flags & UNFRAGMENTED == UNFRAGMENTED {
+            self.partial.remove(&header.session_id);
+            handler(data);
+            return true;
+        }
+
+        if header.flags & BEGIN_FRAG_FLAG != 0 {
+            let buffer = self.partial.entry(header.session_id).or_default();
+            buffer.clear();
+            buffer.extend_from_slice(data);
+            return false;
+        }
+
+        let Some(buffer) = self.partial.get_mut(&header.session_id) else {
+            return false;
+        };
+        buffer.extend_from_slice(data);
+        if header.flags & END_FRAG_FLAG != 0 {
+            let message = self.partial.remove(&header.session_id).unwrap_or_default();
+            handler(&message);
+            return true;
+        }
+        false
+    }
+
+    /// Sessions with a message in progress
+    pub fn pending_sessions(&self) -> usize {
+        self.partial.len()
+    }
+}
+
+/// Aeron-backed subscriber
+pub struct AeronSubscriber {
+    subscription: Box<dyn Subscription>,
+    assembler: Mutex<FragmentAssembler>,
+    fragment_limit: usize,
+}
+
+impl AeronSubscriber {
+    pub fn new(subscription: Box<dyn Subscription>, config: &AeronConfig) -> Self {
+        Self {
+            subscription,
+            assembler: Mutex::new(FragmentAssembler::new()),
+            fragment_limit: config.fragment_limit,
+        }
+    }
+
+    /// Add a subscription on the configured channel and stream
+    pub fn connect(
+        client: &dyn AeronClient,
+        transport_type: &TransportType,
+        config: &AeronConfig,
+    ) -> Result<Self, TransportError> {
+        validate_channel(transport_type, config)?;
+        let subscription = client.add_subscription(&config.channel, config.stream_id)?;
+        Ok(Self::new(subscription, config))
+    }
+}
+
+impl Subscriber for AeronSubscriber {
+    fn poll(&self, handler: &mut dyn FnMut(&[u8])) -> Result<usize, TransportError> {
+        let mut assembler = self.assembler.lock().unwrap_or_else(|e| e.into_inner());
+        let mut messages = 0;
+        self.subscription.poll(
+            &mut |data, header| {
+                if assembler.on_fragment(data, header, handler) {
+                    messages += 1;
+                }
+            },
+            self.fragment_limit,
+        )?;
+        Ok(messages)
+    }
+
+    fn has_messages(&self) -> bool {
+        self.subscription.is_connected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+
+    /// In-memory log standing in for a media driver
+    #[derive(Default)]
+    struct Log {
+        fragments: Mutex<VecDeque<(Vec<u8>, FragmentHeader)>>,
+        results: Mutex<VecDeque<i64>>,
+    }
+
+    struct TestPublication {
+        log: Arc<Log>,
+        session_id: i32,
+        mtu: usize,
+    }
+
+    impl Publication for TestPublication {
+        fn offer(&self, data: &[u8]) -> i64 {
+            if let Some(result) = self.log.results.lock().unwrap().pop_front() {
+                return result;
+            }
+            let mut fragments = self.log.fragments.lock().unwrap();
+            let chunks: Vec<&[u8]> = data.chunks(self.mtu).collect();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let mut flags = 0;
+                if i == 0 {
+                    flags |= BEGIN_FRAG_FLAG;
+                }
+                if i == chunks.len() - 1 {
+                    flags |= END_FRAG_FLAG;
+                }
+                let header = FragmentHeader {
+                    session_id: self.session_id,
+                    flags,
+                };
+                fragments.push_back((chunk.to_vec(), header));
+            }
+            data.len() as i64
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn is_closed(&self) -> bool {
+            false
+        }
+    }
+
+    struct TestSubscription {
+        log: Arc<Log>,
+    }
+
+    impl Subscription for TestSubscription {
+        fn poll(
+            &self,
+            handler: &mut dyn FnMut(&[u8], FragmentHeader),
+            fragment_limit: usize,
+        ) -> Result<usize, TransportError> {
+            let mut fragments = self.log.fragments.lock().unwrap();
+            let mut count = 0;
+            while count < fragment_limit {
+                let Some((data, header)) = fragments.pop_front() else {
+                    break;
+                };
+                handler(&data, header);
+                count += 1;
+            }
+            Ok(count)
+        }
+
+        fn is_connected(&self) -> bool {
+            !self.log.fragments.lock().unwrap().is_empty()
+        }
+    }
+
+    fn pair(mtu: usize) -> (Arc<Log>, AeronPublisher, AeronSubscriber) {
+        let log = Arc::new(Log::default());
+        let config = AeronConfig::default();
+        let publication = TestPublication {
+            log: Arc::clone(&log),
+            session_id: 7,
+            mtu,
+        };
+        let subscription = TestSubscription {
+            log: Arc::clone(&log),
+        };
+        (
+            log,
+            AeronPublisher::new(Box::new(publication), &config),
+            AeronSubscriber::new(Box::new(subscription), &config),
+        )
+    }
+
+    fn drain(subscriber: &AeronSubscriber) -> Vec<Vec<u8>> {
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| received.push(data.to_vec()))
+            .unwrap();
+        received
+    }
+
+    #[test]
+    fn test_offer_result_mapping() {
+        assert!(offer_result(1024).is_ok());
+        assert!(matches!(
+            offer_result(NOT_CONNECTED),
+            Err(TransportError::ConnectionFailed(_))
+        ));
+        assert!(matches!(
+            offer_result(BACK_PRESSURED),
+            Err(TransportError::Full)
+        ));
+        assert!(matches!(
+            offer_result(CLOSED),
+            Err(TransportError::ChannelClosed)
+        ));
+        assert!(matches!(
+            offer_result(MAX_POSITION_EXCEEDED),
+            Err(TransportError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_publish_and_reassemble_fragments() {
+        let (_log, publisher, subscriber) = pair(4);
+        let large: Vec<u8> = (0..=25).collect();
+
+        publisher.publish(b"hi").unwrap();
+        publisher.publish(&large).unwrap();
+
+        assert_eq!(drain(&subscriber), vec![b"hi".to_vec(), large]);
+    }
+
+    #[test]
+    fn test_message_split_across_polls() {
+        let log = Arc::new(Log::default());
+        let config = AeronConfig {
+            fragment_limit: 2,
+            ..Default::default()
+        };
+        let publisher = AeronPublisher::new(
+            Box::new(TestPublication {
+                log: Arc::clone(&log),
+                session_id: 1,
+                mtu: 2,
+            }),
+            &config,
+        );
+        let subscriber = AeronSubscriber::new(
+            Box::new(TestSubscription {
+                log: Arc::clone(&log),
+            }),
+            &config,
+        );
+
+        publisher.publish(b"abcdef").unwrap();
+        assert!(drain(&subscriber).is_empty());
+        assert_eq!(drain(&subscriber), vec![b"abcdef".to_vec()]);
+    }
+
+    #[test]
+    fn test_interleaved_sessions() {
+        let mut assembler = FragmentAssembler::new();
+        let mut received = Vec::new();
+        let a = |flags| FragmentHeader {
+            session_id: 1,
+            flags,
+        };
+        let b = |flags| FragmentHeader {
+            session_id: 2,
+            flags,
+        };
+
+        let mut handler = |data: &[u8]| received.push(data.to_vec());
+        assembler.on_fragment(b"he", a(BEGIN_FRAG_FLAG), &mut handler);
+        assembler.on_fragment(b"wo", b(BEGIN_FRAG_FLAG), &mut handler);
+        assembler.on_fragment(b"llo", a(END_FRAG_FLAG), &mut handler);
+        // Middle fragment without a BEGIN is dropped
+        assembler.on_fragment(
+            b"xx",
+            FragmentHeader {
+                session_id: 3,
+                flags: 0,
+            },
+            &mut handler,
+        );
+        assembler.on_fragment(b"rld", b(END_FRAG_FLAG), &mut handler);
+
+        assert_eq!(received, vec![b"hello".to_vec(), b"world".to_vec()]);
+        assert_eq!(assembler.pending_sessions(), 0);
+    }
+
+    #[test]
+    fn test_admin_action_retried_and_back_pressure_surfaced() {
+        let (log, publisher, subscriber) = pair(64);
+
+        log.results.lock().unwrap().push_back(ADMIN_ACTION);
+        publisher.publish(b"one").unwrap();
+        assert_eq!(drain(&subscriber), vec![b"one".to_vec()]);
+
+        // Back-pressured for longer than the timeout
+        log.results
+            .lock()
+            .unwrap()
+            .extend(std::iter::repeat_n(BACK_PRESSURED, 100_000));
+        assert!(matches!(
+            publisher.publish(b"two"),
+            Err(TransportError::Full)
+        ));
+
+        log.results.lock().unwrap().clear();
+        log.results.lock().unwrap().push_back(NOT_CONNECTED);
+        assert!(matches!(
+            publisher.publish(b"three"),
+            Err(TransportError::ConnectionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_channel() {
+        let ipc = AeronConfig::default();
+        assert!(validate_channel(&TransportType::AeronIpc, &ipc).is_ok());
+        assert!(validate_channel(&TransportType::AeronUdp, &ipc).is_err());
+
+        let udp = AeronConfig {
+            channel: "aeron:udp?endpoint=localhost:40123".to_string(),
+            ..Default::default()
+        };
+        assert!(validate_channel(&TransportType::AeronUdp, &udp).is_ok());
+        assert!(validate_channel(&TransportType::Channel, &udp).is_err());
+    }
+}
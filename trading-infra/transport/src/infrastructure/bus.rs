@@ -0,0 +1,432 @@
+//! Topic Bus
+//!
+//! In-process publish/subscribe with topic routing. Every subscriber owns a
+//! bounded queue and receives its own copy of each message it subscribed
+//! to, so one gateway can feed many strategies that each want a different
+//! subset of symbols. What happens when a queue is full is decided per
+//! topic by a `BackpressurePolicy`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, SendTimeoutError, Sender, TrySendError, bounded};
+use serde::{Deserialize, Serialize};
+
+use super::filter::selected;
+use crate::application::error::TransportError;
+use crate::application::message::WireMessage;
+use crate::application::topic::TopicPattern;
+use crate::application::traits::{Publisher, Subscriber};
+
+/// What a publisher does when a subscriber's queue is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BackpressurePolicy {
+    /// Evict the subscriber's oldest queued message; publishing never fails
+    #[default]
+    DropOldest,
+    /// Wait up to `timeout_ms` for room, then fail the publish with `Full`
+    Block { timeout_ms: u64 },
+}
+
+/// Back-pressure policy for topics matching a pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicPolicy {
+    pub pattern: String,
+    pub policy: BackpressurePolicy,
+}
+
+/// Topic bus configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicBusConfig {
+    /// Messages queued per subscriber
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// Policy for topics no entry in `policies` matches
+    #[serde(default)]
+    pub default_policy: BackpressurePolicy,
+    /// Per-topic policies; the first matching pattern wins
+    #[serde(default)]
+    pub policies: Vec<TopicPolicy>,
+}
+
+fn default_queue_capacity() -> usize {
+    10_000
+}
+
+impl Default for TopicBusConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: default_queue_capacity(),
+            default_policy: BackpressurePolicy::default(),
+            policies: Vec::new(),
+        }
+    }
+}
+
+impl TopicBusConfig {
+    pub fn with_policy(mut self, pattern: impl Into<String>, policy: BackpressurePolicy) -> Self {
+        self.policies.push(TopicPolicy {
+            pattern: pattern.into(),
+            policy,
+        });
+        self
+    }
+}
+
+/// One subscriber's queue and subscriptions
+struct Queue {
+    id: u64,
+    tx: Sender<Arc<[u8]>>,
+    /// Publisher-side handle used to evict under `DropOldest`
+    rx: Receiver<Arc<[u8]>>,
+    patterns: RwLock<Vec<TopicPattern>>,
+    dropped: AtomicU64,
+}
+
+struct BusInner {
+    queue_capacity: usize,
+    default_policy: BackpressurePolicy,
+    policies: Vec<(TopicPattern, BackpressurePolicy)>,
+    queues: RwLock<Vec<Arc<Queue>>>,
+    next_id: AtomicU64,
+}
+
+impl BusInner {
+    fn policy_for(&self, topic: &str) -> BackpressurePolicy {
+        self.policies
+            .iter()
+            .find(|(pattern, _)| pattern.matches(topic))
+            .map_or(self.default_policy, |(_, policy)| *policy)
+    }
+
+    fn deliver(&self, topic: &str, data: &[u8]) -> Result<(), TransportError> {
+        let policy = self.policy_for(topic);
+        let data: Arc<[u8]> = Arc::from(data);
+        let mut blocked = false;
+
+        for queue in self.queues.read().unwrap().iter() {
+            if !selected(&queue.patterns.read().unwrap(), topic) {
+                continue;
+            }
+            match policy {
+                BackpressurePolicy::DropOldest => {
+                    while let Err(TrySendError::Full(_)) = queue.tx.try_send(Arc::clone(&data)) {
+                        if queue.rx.try_recv().is_ok() {
+                            queue.dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                BackpressurePolicy::Block { timeout_ms } => {
+                    let timeout = Duration::from_millis(timeout_ms);
+                    if let Err(SendTimeoutError::Timeout(_)) =
+                        queue.tx.send_timeout(Arc::clone(&data), timeout)
+                    {
+                        queue.dropped.fetch_add(1, Ordering::Relaxed);
+                        blocked = true;
+                    }
+                }
+            }
+        }
+
+        if blocked {
+            Err(TransportError::Full)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// In-process topic router with fan-out to independent subscribers
+#[derive(Clone)]
+pub struct TopicBus {
+    inner: Arc<BusInner>,
+}
+
+impl TopicBus {
+    pub fn new(config: TopicBusConfig) -> Result<Self, TransportError> {
+        let policies = config
+            .policies
+            .iter()
+            .map(|p| Ok((TopicPattern::parse(&p.pattern)?, p.policy)))
+            .collect::<Result<_, TransportError>>()?;
+        Ok(Self {
+            inner: Arc::new(BusInner {
+                queue_capacity: config.queue_capacity.max(1),
+                default_policy: config.default_policy,
+                policies,
+                queues: RwLock::new(Vec::new()),
+                next_id: AtomicU64::new(0),
+            }),
+        })
+    }
+
+    pub fn publisher(&self) -> TopicPublisher {
+        TopicPublisher {
+            bus: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Attach a new subscriber
+    ///
+    /// It receives every message until its first `subscribe`; subscribe
+    /// before publishing starts to avoid queueing unwanted topics.
+    pub fn subscriber(&self) -> TopicSubscriber {
+        let (tx, rx) = bounded(self.inner.queue_capacity);
+        let queue = Arc::new(Queue {
+            id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+            tx,
+            rx,
+            patterns: RwLock::new(Vec::new()),
+            dropped: AtomicU64::new(0),
+        });
+        self.inner.queues.write().unwrap().push(Arc::clone(&queue));
+        TopicSubscriber {
+            bus: Arc::clone(&self.inner),
+            queue,
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.queues.read().unwrap().len()
+    }
+
+    pub fn policy_for(&self, topic: &str) -> BackpressurePolicy {
+        self.inner.policy_for(topic)
+    }
+}
+
+/// Publishing handle of a `TopicBus`
+#[derive(Clone)]
+pub struct TopicPublisher {
+    bus: Arc<BusInner>,
+}
+
+impl Publisher for TopicPublisher {
+    /// Routes on the topic in the `WireMessage` header
+    fn publish(&self, data: &[u8]) -> Result<(), TransportError> {
+        let topic = WireMessage::header(data)
+            .map(|header| header.topic)
+            .unwrap_or_default();
+        self.bus.deliver(&topic, data)
+    }
+
+    fn publish_to(&self, topic: &str, data: &[u8]) -> Result<(), TransportError> {
+        self.bus.deliver(topic, data)
+    }
+}
+
+/// Subscriber attached to a `TopicBus`
+pub struct TopicSubscriber {
+    bus: Arc<BusInner>,
+    queue: Arc<Queue>,
+}
+
+impl TopicSubscriber {
+    /// Drop a subscription; returns false if it was not subscribed
+    pub fn unsubscribe(&self, pattern: &str) -> bool {
+        let mut patterns = self.queue.patterns.write().unwrap();
+        let before = patterns.len();
+        patterns.retain(|p| p.as_str() != pattern);
+        patterns.len() != before
+    }
+
+    /// Messages evicted or refused because this subscriber fell behind
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Subscriber for TopicSubscriber {
+    fn poll(&self, handler: &mut dyn FnMut(&[u8])) -> Result<usize, TransportError> {
+        let mut count = 0;
+        // Bounded so a fast publisher cannot keep this call from returning
+        for _ in 0..self.bus.queue_capacity {
+            let Ok(data) = self.queue.rx.try_recv() else {
+                break;
+            };
+            handler(&data);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn subscribe(&self, topic: &str) -> Result<(), TransportError> {
+        let pattern = TopicPattern::parse(topic)?;
+        let mut patterns = self.queue.patterns.write().unwrap();
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+        Ok(())
+    }
+
+    fn has_messages(&self) -> bool {
+        !self.queue.rx.is_empty()
+    }
+}
+
+impl Drop for TopicSubscriber {
+    fn drop(&mut self) {
+        let id = self.queue.id;
+        self.bus.queues.write().unwrap().retain(|q| q.id != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::message::MessageType;
+
+    fn message(sequence: u64, topic: &str) -> Vec<u8> {
+        WireMessage::with_raw_payload(MessageType::DepthUpdate, sequence, "gw", vec![])
+            .with_topic(topic)
+            .serialize()
+            .unwrap()
+    }
+
+    fn received(subscriber: &TopicSubscriber) -> Vec<(String, u64)> {
+        let mut messages = Vec::new();
+        subscriber
+            .poll(&mut |data| {
+                let header = WireMessage::header(data).unwrap();
+                messages.push((header.topic, header.sequence));
+            })
+            .unwrap();
+        messages
+    }
+
+    fn topics(messages: &[(String, u64)]) -> Vec<&str> {
+        messages.iter().map(|(topic, _)| topic.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fan_out_by_subscription() {
+        let bus = TopicBus::new(TopicBusConfig::default()).unwrap();
+        let btc = bus.subscriber();
+        btc.subscribe("md.*.BTCUSDT.>").unwrap();
+        let binance = bus.subscriber();
+        binance.subscribe("md.binance.>").unwrap();
+        let everything = bus.subscriber();
+
+        let publisher = bus.publisher();
+        for topic in [
+            "md.binance.BTCUSDT.depth",
+            "md.binance.ETHUSDT.depth",
+            "md.kraken.BTCUSDT.depth",
+            "signals.pairs",
+        ] {
+            publisher.publish(&message(0, topic)).unwrap();
+        }
+
+        assert_eq!(
+            topics(&received(&btc)),
+            vec!["md.binance.BTCUSDT.depth", "md.kraken.BTCUSDT.depth"]
+        );
+        assert_eq!(
+            topics(&received(&binance)),
+            vec!["md.binance.BTCUSDT.depth", "md.binance.ETHUSDT.depth"]
+        );
+        assert_eq!(received(&everything).len(), 4);
+    }
+
+    #[test]
+    fn test_publish_to_overrides_header_topic() {
+        let bus = TopicBus::new(TopicBusConfig::default()).unwrap();
+        let subscriber = bus.subscriber();
+        subscriber.subscribe("signals.>").unwrap();
+
+        bus.publisher()
+            .publish_to("signals.pairs", b"not a wire message")
+            .unwrap();
+        let mut count = 0;
+        subscriber.poll(&mut |_| count += 1).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_latest() {
+        let bus = TopicBus::new(TopicBusConfig {
+            queue_capacity: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let slow = bus.subscriber();
+        let publisher = bus.publisher();
+
+        for seq in 0..5 {
+            publisher
+                .publish(&message(seq, "md.binance.BTCUSDT.depth"))
+                .unwrap();
+        }
+
+        let sequences: Vec<u64> = received(&slow).into_iter().map(|(_, s)| s).collect();
+        assert_eq!(sequences, vec![3, 4]);
+        assert_eq!(slow.dropped(), 3);
+    }
+
+    #[test]
+    fn test_block_policy_fails_after_timeout() {
+        let config = TopicBusConfig {
+            queue_capacity: 1,
+            ..Default::default()
+        }
+        .with_policy("orders.>", BackpressurePolicy::Block { timeout_ms: 5 });
+        let bus = TopicBus::new(config).unwrap();
+        let slow = bus.subscriber();
+        let publisher = bus.publisher();
+
+        assert_eq!(
+            bus.policy_for("orders.binance"),
+            BackpressurePolicy::Block { timeout_ms: 5 }
+        );
+        publisher.publish(&message(0, "orders.binance")).unwrap();
+        assert!(matches!(
+            publisher.publish(&message(1, "orders.binance")),
+            Err(TransportError::Full)
+        ));
+        // The queued message was kept
+        assert_eq!(received(&slow), vec![("orders.binance".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_block_policy_waits_for_reader() {
+        let config = TopicBusConfig {
+            queue_capacity: 1,
+            default_policy: BackpressurePolicy::Block { timeout_ms: 1_000 },
+            ..Default::default()
+        };
+        let bus = TopicBus::new(config).unwrap();
+        let subscriber = bus.subscriber();
+        let publisher = bus.publisher();
+
+        let writer = std::thread::spawn(move || {
+            for seq in 0..20 {
+                publisher.publish(&message(seq, "signals.pairs")).unwrap();
+            }
+        });
+        let mut sequences = Vec::new();
+        while sequences.len() < 20 {
+            sequences.extend(received(&subscriber).into_iter().map(|(_, s)| s));
+        }
+        writer.join().unwrap();
+        assert_eq!(sequences, (0..20).collect::<Vec<_>>());
+        assert_eq!(subscriber.dropped(), 0);
+    }
+
+    #[test]
+    fn test_dropped_subscriber_detaches() {
+        let bus = TopicBus::new(TopicBusConfig::default()).unwrap();
+        let subscriber = bus.subscriber();
+        assert_eq!(bus.subscriber_count(), 1);
+        drop(subscriber);
+        assert_eq!(bus.subscriber_count(), 0);
+        bus.publisher().publish(&message(0, "md.x")).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_policy_pattern() {
+        let config = TopicBusConfig::default().with_policy("md..x", BackpressurePolicy::DropOldest);
+        assert!(TopicBus::new(config).is_err());
+    }
+}
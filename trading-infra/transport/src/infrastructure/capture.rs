@@ -0,0 +1,513 @@
+//! Market Data Capture
+//!
+//! Records published `WireMessage`s to disk so a session can be replayed
+//! later without a live venue.
+//!
+//! A capture is a directory of gzip-compressed chunk files plus an
+//! `index.jsonl` with one line per chunk giving its time and sequence range.
+//! Each chunk holds frames of `[timestamp_ns: u64][sequence: u64][len: u32]`
+//! (little endian) followed by the serialized message, so readers can seek
+//! by time from the index and skip frames without decoding them.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+
+use crate::application::error::TransportError;
+use crate::application::message::WireMessage;
+use crate::application::traits::{BoxPublisher, Publisher};
+
+/// Name of the chunk index inside a capture directory
+pub const CAPTURE_INDEX_FILE: &str = "index.jsonl";
+
+const FRAME_HEADER_LEN: usize = 8 + 8 + 4;
+
+/// Capture configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureConfig {
+    /// Directory receiving chunk files and the index
+    pub dir: PathBuf,
+    /// Close a chunk after this many messages
+    #[serde(default = "default_chunk_messages")]
+    pub chunk_messages: usize,
+    /// Close a chunk once this many uncompressed bytes are buffered
+    #[serde(default = "default_chunk_bytes")]
+    pub chunk_bytes: usize,
+    /// Gzip level, 0 (none) to 9 (best)
+    #[serde(default = "default_compression_level")]
+    pub compression_level: u32,
+}
+
+fn default_chunk_messages() -> usize {
+    50_000
+}
+
+fn default_chunk_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
+fn default_compression_level() -> u32 {
+    6
+}
+
+impl CaptureConfig {
+    /// Capture into `dir` with default chunking
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            chunk_messages: default_chunk_messages(),
+            chunk_bytes: default_chunk_bytes(),
+            compression_level: default_compression_level(),
+        }
+    }
+}
+
+/// Index entry for one chunk file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    /// File name relative to the capture directory
+    pub file: String,
+    pub first_timestamp_ns: u64,
+    pub last_timestamp_ns: u64,
+    pub first_sequence: u64,
+    pub last_sequence: u64,
+    pub messages: usize,
+}
+
+/// One recorded message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedMessage {
+    pub timestamp_ns: u64,
+    pub sequence: u64,
+    /// Serialized `WireMessage`, exactly as published
+    pub data: Vec<u8>,
+}
+
+impl CapturedMessage {
+    /// Decode the wire message
+    pub fn message(&self) -> Result<WireMessage, TransportError> {
+        Ok(WireMessage::deserialize(&self.data)?)
+    }
+}
+
+/// Writes serialized wire messages into chunked, compressed files
+pub struct CaptureWriter {
+    config: CaptureConfig,
+    buffer: Vec<u8>,
+    pending: Option<ChunkInfo>,
+    next_chunk: usize,
+}
+
+impl CaptureWriter {
+    /// Open a capture directory, continuing after any chunks already in it
+    pub fn create(config: CaptureConfig) -> Result<Self, TransportError> {
+        fs::create_dir_all(&config.dir)?;
+        let next_chunk = read_index(&config.dir)?.len();
+        Ok(Self {
+            config,
+            buffer: Vec::new(),
+            pending: None,
+            next_chunk,
+        })
+    }
+
+    /// Capture directory
+    pub fn dir(&self) -> &Path {
+        &self.config.dir
+    }
+
+    /// Record one serialized `WireMessage`
+    pub fn record(&mut self, data: &[u8]) -> Result<(), TransportError> {
+        let header = WireMessage::header(data)?;
+        let len = u32::try_from(data.len())
+            .map_err(|_| TransportError::Serialization("message too large".to_string()))?;
+
+        self.buffer
+            .extend_from_slice(&header.timestamp_ns.to_le_bytes());
+        self.buffer
+            .extend_from_slice(&header.sequence.to_le_bytes());
+        self.buffer.extend_from_slice(&len.to_le_bytes());
+        self.buffer.extend_from_slice(data);
+
+        let chunk = self.pending.get_or_insert_with(|| ChunkInfo {
+            file: format!("chunk-{:06}.bin.gz", self.next_chunk),
+            first_timestamp_ns: header.timestamp_ns,
+            last_timestamp_ns: header.timestamp_ns,
+            first_sequence: header.sequence,
+            last_sequence: header.sequence,
+            messages: 0,
+        });
+        chunk.first_timestamp_ns = chunk.first_timestamp_ns.min(header.timestamp_ns);
+        chunk.last_timestamp_ns = chunk.last_timestamp_ns.max(header.timestamp_ns);
+        chunk.last_sequence = header.sequence;
+        chunk.messages += 1;
+
+        if chunk.messages >= self.config.chunk_messages
+            || self.buffer.len() >= self.config.chunk_bytes
+        {
+            self.finish_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Compress the open chunk to disk and add it to the index
+    pub fn finish_chunk(&mut self) -> Result<(), TransportError> {
+        let Some(chunk) = self.pending.take() else {
+            return Ok(());
+        };
+
+        // Frames of a chunk that fails to write are dropped with it
+        let frames = std::mem::take(&mut self.buffer);
+        let file = File::create(self.config.dir.join(&chunk.file))?;
+        let mut encoder = GzEncoder::new(file, Compression::new(self.config.compression_level));
+        encoder.write_all(&frames)?;
+        encoder.finish()?.sync_all()?;
+        self.buffer = frames;
+        self.buffer.clear();
+
+        let mut index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.config.dir.join(CAPTURE_INDEX_FILE))?;
+        let line = serde_json::to_string(&chunk)
+            .map_err(|e| TransportError::Serialization(e.to_string()))?;
+        writeln!(index, "{}", line)?;
+
+        self.next_chunk += 1;
+        Ok(())
+    }
+}
+
+impl Drop for CaptureWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish_chunk() {
+            tracing::error!("Failed to write final capture chunk: {}", e);
+        }
+    }
+}
+
+/// Publisher that records everything it forwards
+///
+/// Wraps the real transport; a failed write to disk is logged and never
+/// holds back live data.
+pub struct RecordingPublisher {
+    inner: BoxPublisher,
+    writer: Mutex<CaptureWriter>,
+}
+
+impl RecordingPublisher {
+    pub fn new(inner: BoxPublisher, writer: CaptureWriter) -> Self {
+        Self {
+            inner,
+            writer: Mutex::new(writer),
+        }
+    }
+
+    fn record(&self, data: &[u8]) {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writer.record(data) {
+            tracing::warn!("Failed to capture message: {}", e);
+        }
+    }
+}
+
+impl Publisher for RecordingPublisher {
+    fn publish(&self, data: &[u8]) -> Result<(), TransportError> {
+        self.record(data);
+        self.inner.publish(data)
+    }
+
+    fn publish_to(&self, topic: &str, data: &[u8]) -> Result<(), TransportError> {
+        self.record(data);
+        self.inner.publish_to(topic, data)
+    }
+
+    fn flush(&self) -> Result<(), TransportError> {
+        self.writer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .finish_chunk()?;
+        self.inner.flush()
+    }
+
+    fn is_active(&self) -> bool {
+        self.inner.is_active()
+    }
+}
+
+/// Reads a capture directory
+#[derive(Debug, Clone)]
+pub struct CaptureReader {
+    dir: PathBuf,
+    chunks: Vec<ChunkInfo>,
+}
+
+impl CaptureReader {
+    /// Open a capture directory by its index
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, TransportError> {
+        let dir = dir.into();
+        let chunks = read_index(&dir)?;
+        if chunks.is_empty() && !dir.join(CAPTURE_INDEX_FILE).exists() {
+            return Err(TransportError::Config(format!(
+                "no capture index in {}",
+                dir.display()
+            )));
+        }
+        Ok(Self { dir, chunks })
+    }
+
+    /// Chunks in recording order
+    pub fn chunks(&self) -> &[ChunkInfo] {
+        &self.chunks
+    }
+
+    /// Timestamp of the first recorded message
+    pub fn start_ns(&self) -> Option<u64> {
+        self.chunks.iter().map(|c| c.first_timestamp_ns).min()
+    }
+
+    /// Timestamp of the last recorded message
+    pub fn end_ns(&self) -> Option<u64> {
+        self.chunks.iter().map(|c| c.last_timestamp_ns).max()
+    }
+
+    /// Total recorded messages
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|c| c.messages).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Messages stamped at or after `from_ns`, in recording order
+    ///
+    /// Chunks that end before `from_ns` are skipped without being opened.
+    pub fn messages_from(&self, from_ns: u64) -> CaptureIter {
+        let chunks = self
+            .chunks
+            .iter()
+            .filter(|c| c.last_timestamp_ns >= from_ns)
+            .map(|c| self.dir.join(&c.file))
+            .collect();
+        CaptureIter {
+            chunks,
+            from_ns,
+            current: Vec::new(),
+            offset: 0,
+        }
+    }
+
+    /// All messages, in recording order
+    pub fn messages(&self) -> CaptureIter {
+        self.messages_from(0)
+    }
+}
+
+/// Iterator over captured messages, decompressing one chunk at a time
+pub struct CaptureIter {
+    chunks: VecDeque<PathBuf>,
+    from_ns: u64,
+    current: Vec<u8>,
+    offset: usize,
+}
+
+impl CaptureIter {
+    fn load_next_chunk(&mut self) -> Result<bool, TransportError> {
+        let Some(path) = self.chunks.pop_front() else {
+            return Ok(false);
+        };
+        self.current.clear();
+        self.offset = 0;
+        GzDecoder::new(File::open(&path)?).read_to_end(&mut self.current)?;
+        Ok(true)
+    }
+
+    fn next_frame(&mut self) -> Result<Option<CapturedMessage>, TransportError> {
+        loop {
+            if self.offset >= self.current.len() {
+                if !self.load_next_chunk()? {
+                    return Ok(None);
+                }
+                continue;
+            }
+
+            let rest = &self.current[self.offset..];
+            if rest.len() < FRAME_HEADER_LEN {
+                return Err(TransportError::Serialization(
+                    "truncated capture frame header".to_string(),
+                ));
+            }
+            let timestamp_ns = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+            let sequence = u64::from_le_bytes(rest[8..16].try_into().unwrap());
+            let len = u32::from_le_bytes(rest[16..20].try_into().unwrap()) as usize;
+            let end = FRAME_HEADER_LEN + len;
+            if rest.len() < end {
+                return Err(TransportError::Serialization(
+                    "truncated capture frame".to_string(),
+                ));
+            }
+            self.offset += end;
+
+            if timestamp_ns >= self.from_ns {
+                return Ok(Some(CapturedMessage {
+                    timestamp_ns,
+                    sequence,
+                    data: rest[FRAME_HEADER_LEN..end].to_vec(),
+                }));
+            }
+        }
+    }
+}
+
+impl Iterator for CaptureIter {
+    type Item = Result<CapturedMessage, TransportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_frame() {
+            Ok(frame) => frame.map(Ok),
+            Err(e) => {
+                // Stop after a corrupt chunk rather than yield garbage
+                self.chunks.clear();
+                self.current.clear();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn read_index(dir: &Path) -> Result<Vec<ChunkInfo>, TransportError> {
+    let path = dir.join(CAPTURE_INDEX_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut chunks = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        chunks.push(
+            serde_json::from_str(&line)
+                .map_err(|e| TransportError::Serialization(e.to_string()))?,
+        );
+    }
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::message::MessageType;
+
+    fn message(sequence: u64, timestamp_ns: u64) -> Vec<u8> {
+        let mut msg =
+            WireMessage::with_raw_payload(MessageType::DepthUpdate, sequence, "test", vec![0; 16]);
+        msg.timestamp_ns = timestamp_ns;
+        msg.serialize().unwrap()
+    }
+
+    fn config(dir: &Path, chunk_messages: usize) -> CaptureConfig {
+        CaptureConfig {
+            chunk_messages,
+            ..CaptureConfig::new(dir)
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_across_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = CaptureWriter::create(config(dir.path(), 4)).unwrap();
+        for seq in 1..=10 {
+            writer.record(&message(seq, seq * 1_000)).unwrap();
+        }
+        drop(writer);
+
+        let reader = CaptureReader::open(dir.path()).unwrap();
+        assert_eq!(reader.chunks().len(), 3);
+        assert_eq!(reader.len(), 10);
+        assert_eq!(reader.start_ns(), Some(1_000));
+        assert_eq!(reader.end_ns(), Some(10_000));
+        assert_eq!(reader.chunks()[1].first_sequence, 5);
+        assert_eq!(reader.chunks()[1].last_sequence, 8);
+
+        let sequences: Vec<u64> = reader.messages().map(|m| m.unwrap().sequence).collect();
+        assert_eq!(sequences, (1..=10).collect::<Vec<_>>());
+
+        let first = reader.messages().next().unwrap().unwrap();
+        assert_eq!(first.data, message(1, 1_000));
+        assert_eq!(first.message().unwrap().sequence, 1);
+    }
+
+    #[test]
+    fn test_seek_by_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = CaptureWriter::create(config(dir.path(), 3)).unwrap();
+        for seq in 1..=9 {
+            writer.record(&message(seq, seq * 1_000)).unwrap();
+        }
+        writer.finish_chunk().unwrap();
+
+        let reader = CaptureReader::open(dir.path()).unwrap();
+        let sequences: Vec<u64> = reader
+            .messages_from(5_500)
+            .map(|m| m.unwrap().sequence)
+            .collect();
+        assert_eq!(sequences, vec![6, 7, 8, 9]);
+        assert_eq!(reader.messages_from(20_000).count(), 0);
+    }
+
+    #[test]
+    fn test_writer_appends_to_existing_capture() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = CaptureWriter::create(config(dir.path(), 100)).unwrap();
+        writer.record(&message(1, 1_000)).unwrap();
+        drop(writer);
+
+        let mut writer = CaptureWriter::create(config(dir.path(), 100)).unwrap();
+        writer.record(&message(2, 2_000)).unwrap();
+        drop(writer);
+
+        let reader = CaptureReader::open(dir.path()).unwrap();
+        assert_eq!(reader.chunks().len(), 2);
+        assert_ne!(reader.chunks()[0].file, reader.chunks()[1].file);
+        assert_eq!(reader.messages().count(), 2);
+    }
+
+    #[cfg(feature = "channel")]
+    #[test]
+    fn test_recording_publisher_forwards_and_records() {
+        use crate::application::traits::Subscriber;
+        use crate::infrastructure::channel::channel_pair;
+
+        let dir = tempfile::tempdir().unwrap();
+        let (inner, subscriber) = channel_pair(10);
+        let writer = CaptureWriter::create(CaptureConfig::new(dir.path())).unwrap();
+        let publisher = RecordingPublisher::new(Box::new(inner), writer);
+
+        publisher.publish(&message(1, 1_000)).unwrap();
+        // Not a wire message: still forwarded, not recorded
+        publisher.publish(b"x").unwrap();
+        publisher.flush().unwrap();
+
+        assert_eq!(subscriber.poll(&mut |_| {}).unwrap(), 2);
+        let reader = CaptureReader::open(dir.path()).unwrap();
+        assert_eq!(reader.len(), 1);
+    }
+
+    #[test]
+    fn test_open_missing_capture() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            CaptureReader::open(dir.path().join("nope")),
+            Err(TransportError::Config(_))
+        ));
+    }
+}
@@ -3,6 +3,7 @@
 //! Configuration types for creating transports from config files.
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Transport type selector
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,6 +16,8 @@ pub enum TransportType {
     AeronIpc,
     /// Aeron UDP (reliable UDP across machines)
     AeronUdp,
+    /// Shared memory ring buffer (inter-process on same machine, no driver)
+    SharedMemory,
 }
 
 /// Channel-specific configuration
@@ -47,18 +50,118 @@ pub struct AeronConfig {
     /// Media driver directory
     #[serde(default = "default_media_driver_dir")]
     pub media_driver_dir: String,
+    /// Maximum fragments handled per subscriber poll
+    #[serde(default = "default_fragment_limit")]
+    pub fragment_limit: usize,
+    /// How long a publish retries while back-pressured before returning `Full`
+    #[serde(default = "default_back_pressure_timeout_us")]
+    pub back_pressure_timeout_us: u64,
+    /// How long connecting waits for the media driver to respond
+    #[serde(default = "default_driver_timeout_ms")]
+    pub driver_timeout_ms: u64,
 }
 
 fn default_media_driver_dir() -> String {
     "/dev/shm/aeron".to_string()
 }
 
+fn default_fragment_limit() -> usize {
+    256
+}
+
+fn default_back_pressure_timeout_us() -> u64 {
+    100
+}
+
+fn default_driver_timeout_ms() -> u64 {
+    10_000
+}
+
 impl Default for AeronConfig {
     fn default() -> Self {
         Self {
             channel: "aeron:ipc".to_string(),
             stream_id: 1001,
             media_driver_dir: default_media_driver_dir(),
+            fragment_limit: default_fragment_limit(),
+            back_pressure_timeout_us: default_back_pressure_timeout_us(),
+            driver_timeout_ms: default_driver_timeout_ms(),
+        }
+    }
+}
+
+/// What the publisher does when it would overwrite a consumer's next message
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowConsumerPolicy {
+    /// Keep publishing; the lapped consumer skips ahead
+    #[default]
+    Overwrite,
+    /// Return `TransportError::Full` until the consumer catches up
+    Backpressure,
+}
+
+/// Shared memory ring configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedMemoryConfig {
+    /// Backing file; /dev/shm keeps it in memory
+    #[serde(default = "default_shm_path")]
+    pub path: PathBuf,
+    /// Bytes per slot including the 16-byte slot header; multiple of 64
+    #[serde(default = "default_slot_size")]
+    pub slot_size: usize,
+    /// Number of slots; power of two
+    #[serde(default = "default_slot_count")]
+    pub slot_count: usize,
+    #[serde(default)]
+    pub slow_consumer_policy: SlowConsumerPolicy,
+    /// A consumer this far behind is reported as slow, in slots
+    #[serde(default = "default_slow_consumer_lag")]
+    pub slow_consumer_lag: usize,
+    /// Consumers that have not polled for this long are ignored
+    #[serde(default = "default_consumer_timeout_ms")]
+    pub consumer_timeout_ms: u64,
+}
+
+fn default_shm_path() -> PathBuf {
+    PathBuf::from("/dev/shm/athena-market-data")
+}
+
+fn default_slot_size() -> usize {
+    16 * 1024
+}
+
+fn default_slot_count() -> usize {
+    4096
+}
+
+fn default_slow_consumer_lag() -> usize {
+    default_slot_count() / 2
+}
+
+fn default_consumer_timeout_ms() -> u64 {
+    5_000
+}
+
+impl Default for SharedMemoryConfig {
+    fn default() -> Self {
+        Self {
+            path: default_shm_path(),
+            slot_size: default_slot_size(),
+            slot_count: default_slot_count(),
+            slow_consumer_policy: SlowConsumerPolicy::default(),
+            slow_consumer_lag: default_slow_consumer_lag(),
+            consumer_timeout_ms: default_consumer_timeout_ms(),
+        }
+    }
+}
+
+impl SharedMemoryConfig {
+    /// Ring at `path` with default geometry
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
         }
     }
 }
@@ -77,6 +180,10 @@ pub struct TransportConfig {
     /// Aeron configuration (when type = "aeron_ipc" or "aeron_udp")
     #[serde(default)]
     pub aeron: AeronConfig,
+
+    /// Shared memory configuration (when type = "shared_memory")
+    #[serde(default)]
+    pub shm: SharedMemoryConfig,
 }
 
 impl Default for TransportConfig {
@@ -85,6 +192,7 @@ impl Default for TransportConfig {
             transport_type: TransportType::Channel,
             channel: ChannelConfig::default(),
             aeron: AeronConfig::default(),
+            shm: SharedMemoryConfig::default(),
         }
     }
 }
@@ -124,6 +232,15 @@ impl TransportConfig {
             ..Default::default()
         }
     }
+
+    /// Create a shared memory ring config (inter-process on same machine)
+    pub fn shared_memory(path: impl Into<PathBuf>) -> Self {
+        Self {
+            transport_type: TransportType::SharedMemory,
+            shm: SharedMemoryConfig::new(path),
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -160,6 +277,17 @@ mod tests {
         assert_eq!(config.aeron.stream_id, 1002);
     }
 
+    #[test]
+    fn test_shared_memory_config() {
+        let json =
+            r#"{"type": "shared_memory", "shm": {"path": "/dev/shm/md", "slot_count": 1024}}"#;
+        let config: TransportConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.transport_type, TransportType::SharedMemory);
+        assert_eq!(config.shm.path, std::path::PathBuf::from("/dev/shm/md"));
+        assert_eq!(config.shm.slot_count, 1024);
+        assert_eq!(config.shm.slot_size, 16 * 1024);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = TransportConfig::channel(10_000);
@@ -1,6 +1,9 @@
 //! Transport Factory
 //!
 //! Creates Publisher/Subscriber pairs from configuration.
+//!
+//! Aeron transports connect a `RusteronClient` to the media driver in
+//! `AeronConfig::media_driver_dir`; the driver must already be running.
 
 use super::config::{TransportConfig, TransportType};
 use crate::application::error::TransportError;
@@ -20,10 +23,29 @@ impl TransportFactory {
                 let (pub_, _sub) = super::channel::channel_pair(config.channel.capacity);
                 Ok(Box::new(pub_))
             }
+            TransportType::SharedMemory => {
+                #[cfg(feature = "shm")]
+                {
+                    Ok(Box::new(super::shm::ShmPublisher::create(
+                        config.shm.clone(),
+                    )?))
+                }
+                #[cfg(not(feature = "shm"))]
+                {
+                    Err(TransportError::Config(
+                        "Shared memory transport not enabled. Enable 'shm' feature.".to_string(),
+                    ))
+                }
+            }
             TransportType::AeronIpc | TransportType::AeronUdp => {
                 #[cfg(feature = "aeron")]
                 {
-                    todo!("Aeron publisher not yet implemented")
+                    let client = super::rusteron::RusteronClient::connect(&config.aeron)?;
+                    Ok(Box::new(super::aeron::AeronPublisher::connect(
+                        &client,
+                        &config.transport_type,
+                        &config.aeron,
+                    )?))
                 }
                 #[cfg(not(feature = "aeron"))]
                 {
@@ -43,12 +65,33 @@ impl TransportFactory {
                 // For subscriber-only, we create a channel pair and return the subscriber
                 // The publisher is dropped (no messages will be received)
                 let (_pub, sub) = super::channel::channel_pair(config.channel.capacity);
-                Ok(Box::new(sub))
+                Ok(Self::filtered(Box::new(sub)))
+            }
+            TransportType::SharedMemory => {
+                #[cfg(feature = "shm")]
+                {
+                    Ok(Self::filtered(Box::new(super::shm::ShmSubscriber::open(
+                        &config.shm,
+                    )?)))
+                }
+                #[cfg(not(feature = "shm"))]
+                {
+                    Err(TransportError::Config(
+                        "Shared memory transport not enabled. Enable 'shm' feature.".to_string(),
+                    ))
+                }
             }
             TransportType::AeronIpc | TransportType::AeronUdp => {
                 #[cfg(feature = "aeron")]
                 {
-                    todo!("Aeron subscriber not yet implemented")
+                    let client = super::rusteron::RusteronClient::connect(&config.aeron)?;
+                    Ok(Self::filtered(Box::new(
+                        super::aeron::AeronSubscriber::connect(
+                            &client,
+                            &config.transport_type,
+                            &config.aeron,
+                        )?,
+                    )))
                 }
                 #[cfg(not(feature = "aeron"))]
                 {
@@ -70,12 +113,37 @@ impl TransportFactory {
         match config.transport_type {
             TransportType::Channel => {
                 let (pub_, sub) = super::channel::channel_pair(config.channel.capacity);
-                Ok((Box::new(pub_), Box::new(sub)))
+                Ok((Box::new(pub_), Self::filtered(Box::new(sub))))
+            }
+            TransportType::SharedMemory => {
+                #[cfg(feature = "shm")]
+                {
+                    let publisher = super::shm::ShmPublisher::create(config.shm.clone())?;
+                    let subscriber = super::shm::ShmSubscriber::open(&config.shm)?;
+                    Ok((Box::new(publisher), Self::filtered(Box::new(subscriber))))
+                }
+                #[cfg(not(feature = "shm"))]
+                {
+                    Err(TransportError::Config(
+                        "Shared memory transport not enabled. Enable 'shm' feature.".to_string(),
+                    ))
+                }
             }
             TransportType::AeronIpc | TransportType::AeronUdp => {
                 #[cfg(feature = "aeron")]
                 {
-                    todo!("Aeron pair not yet implemented")
+                    let client = super::rusteron::RusteronClient::connect(&config.aeron)?;
+                    let subscriber = super::aeron::AeronSubscriber::connect(
+                        &client,
+                        &config.transport_type,
+                        &config.aeron,
+                    )?;
+                    let publisher = super::aeron::AeronPublisher::connect(
+                        &client,
+                        &config.transport_type,
+                        &config.aeron,
+                    )?;
+                    Ok((Box::new(publisher), Self::filtered(Box::new(subscriber))))
                 }
                 #[cfg(not(feature = "aeron"))]
                 {
@@ -87,6 +155,12 @@ impl TransportFactory {
         }
     }
 
+    /// Subscribers from the factory honour `subscribe` on every transport
+    #[cfg(feature = "channel")]
+    fn filtered(subscriber: BoxSubscriber) -> BoxSubscriber {
+        Box::new(super::filter::TopicFilter::new(subscriber))
+    }
+
     /// Create a channel pair directly (convenience method)
     #[cfg(feature = "channel")]
     pub fn channel_pair(capacity: usize) -> (impl Publisher + Clone, impl Subscriber) {
@@ -149,4 +223,33 @@ mod tests {
         let result = TransportFactory::create_pair(&config);
         assert!(result.is_err());
     }
+
+    #[test]
+    #[cfg(feature = "aeron")]
+    fn test_aeron_requires_media_driver() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = TransportConfig::aeron_ipc(1001);
+        config.aeron.media_driver_dir = dir.path().join("aeron").display().to_string();
+        config.aeron.driver_timeout_ms = 100;
+        let result = TransportFactory::create_pair(&config);
+        assert!(matches!(result, Err(TransportError::ConnectionFailed(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "shm")]
+    fn test_create_shared_memory_pair() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TransportConfig::shared_memory(dir.path().join("ring"));
+        let (publisher, subscriber) = TransportFactory::create_pair(&config).unwrap();
+
+        publisher.publish(b"test").unwrap();
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| received.push(data.to_vec()))
+            .unwrap();
+        assert_eq!(received, vec![b"test".to_vec()]);
+
+        // A second process would attach by path
+        assert!(TransportFactory::create_subscriber(&config).is_ok());
+    }
 }
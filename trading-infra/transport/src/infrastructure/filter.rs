@@ -0,0 +1,158 @@
+//! Topic Filtering
+//!
+//! Gives any `Subscriber` wildcard subscriptions by matching the topic in
+//! each `WireMessage` header. Transports that deliver the full stream to
+//! every reader (shared memory, Aeron) are filtered on the receiving side.
+
+use std::sync::RwLock;
+
+use crate::application::error::TransportError;
+use crate::application::message::WireMessage;
+use crate::application::topic::TopicPattern;
+use crate::application::traits::{BoxSubscriber, Subscriber};
+
+/// Subscriber delivering only messages on subscribed topics
+///
+/// With no subscriptions every message is delivered. Messages without a
+/// topic (control messages such as retransmit rejects) and data that is not
+/// a `WireMessage` always pass.
+pub struct TopicFilter {
+    inner: BoxSubscriber,
+    patterns: RwLock<Vec<TopicPattern>>,
+}
+
+impl TopicFilter {
+    pub fn new(inner: BoxSubscriber) -> Self {
+        Self {
+            inner,
+            patterns: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Drop a subscription; returns false if it was not subscribed
+    pub fn unsubscribe(&self, pattern: &str) -> bool {
+        let mut patterns = self.patterns.write().unwrap();
+        let before = patterns.len();
+        patterns.retain(|p| p.as_str() != pattern);
+        patterns.len() != before
+    }
+
+    pub fn subscriptions(&self) -> Vec<String> {
+        let patterns = self.patterns.read().unwrap();
+        patterns.iter().map(|p| p.to_string()).collect()
+    }
+}
+
+/// Whether a message on `topic` reaches a reader subscribed to `patterns`
+pub(crate) fn selected(patterns: &[TopicPattern], topic: &str) -> bool {
+    patterns.is_empty() || topic.is_empty() || patterns.iter().any(|p| p.matches(topic))
+}
+
+impl Subscriber for TopicFilter {
+    fn poll(&self, handler: &mut dyn FnMut(&[u8])) -> Result<usize, TransportError> {
+        let patterns = self.patterns.read().unwrap();
+        if patterns.is_empty() {
+            return self.inner.poll(handler);
+        }
+
+        let mut delivered = 0;
+        self.inner.poll(&mut |data| {
+            let pass = WireMessage::header(data)
+                .map(|header| selected(&patterns, &header.topic))
+                .unwrap_or(true);
+            if pass {
+                handler(data);
+                delivered += 1;
+            }
+        })?;
+        Ok(delivered)
+    }
+
+    fn subscribe(&self, topic: &str) -> Result<(), TransportError> {
+        let pattern = TopicPattern::parse(topic)?;
+        self.inner.subscribe(topic)?;
+        let mut patterns = self.patterns.write().unwrap();
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+        Ok(())
+    }
+
+    fn has_messages(&self) -> bool {
+        self.inner.has_messages()
+    }
+}
+
+#[cfg(all(test, feature = "channel"))]
+mod tests {
+    use super::*;
+    use crate::application::message::MessageType;
+    use crate::application::traits::Publisher;
+    use crate::infrastructure::channel::channel_pair;
+
+    fn message(topic: &str) -> Vec<u8> {
+        WireMessage::with_raw_payload(MessageType::DepthUpdate, 0, "gw", vec![])
+            .with_topic(topic)
+            .serialize()
+            .unwrap()
+    }
+
+    fn received(filter: &TopicFilter) -> Vec<String> {
+        let mut topics = Vec::new();
+        filter
+            .poll(&mut |data| topics.push(WireMessage::header(data).unwrap().topic))
+            .unwrap();
+        topics
+    }
+
+    #[test]
+    fn test_filters_by_subscription() {
+        let (publisher, subscriber) = channel_pair(100);
+        let filter = TopicFilter::new(Box::new(subscriber));
+        filter.subscribe("md.*.BTCUSDT.>").unwrap();
+        filter.subscribe("signals.pairs").unwrap();
+
+        for topic in [
+            "md.binance.BTCUSDT.depth",
+            "md.binance.ETHUSDT.depth",
+            "signals.pairs",
+            "",
+            "md.kraken.BTCUSDT.trades",
+        ] {
+            publisher.publish(&message(topic)).unwrap();
+        }
+
+        assert_eq!(
+            received(&filter),
+            vec![
+                "md.binance.BTCUSDT.depth",
+                "signals.pairs",
+                "",
+                "md.kraken.BTCUSDT.trades"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsubscribed_filter_passes_everything() {
+        let (publisher, subscriber) = channel_pair(100);
+        let filter = TopicFilter::new(Box::new(subscriber));
+        filter.subscribe("signals.>").unwrap();
+        assert!(filter.unsubscribe("signals.>"));
+        assert!(filter.subscriptions().is_empty());
+
+        publisher
+            .publish(&message("md.binance.BTCUSDT.depth"))
+            .unwrap();
+        publisher.publish(b"raw").unwrap();
+        assert_eq!(filter.poll(&mut |_| {}).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_rejects_invalid_pattern() {
+        let (_publisher, subscriber) = channel_pair(1);
+        let filter = TopicFilter::new(Box::new(subscriber));
+        assert!(filter.subscribe("md..depth").is_err());
+        assert!(filter.subscriptions().is_empty());
+    }
+}
@@ -2,14 +2,56 @@
 //!
 //! Contains concrete transport implementations and configuration.
 //! - Channel: In-process communication via crossbeam channels
-//! - Aeron: Ultra-low latency reliable messaging (feature-gated)
+//! - Aeron: Ultra-low latency reliable messaging over the C client (feature-gated)
+//! - Shared memory: Ring buffer in a memory-mapped file between co-located processes
+//! - Capture/Replay: Recording published messages to disk and playing them back
+//! - Sequencing: Gap detection and retransmit on top of any of the above
+//! - Topics: In-process topic bus with fan-out, and topic filtering for any subscriber
 
+#[cfg(feature = "aeron")]
+pub mod aeron;
+#[cfg(feature = "channel")]
+pub mod bus;
+pub mod capture;
 #[cfg(feature = "channel")]
 pub mod channel;
 pub mod config;
 pub mod factory;
+pub mod filter;
+pub mod replay;
+#[cfg(feature = "aeron")]
+pub mod rusteron;
+pub mod sequencing;
+#[cfg(feature = "shm")]
+pub mod shm;
 
+#[cfg(feature = "aeron")]
+pub use aeron::{
+    AeronClient, AeronPublisher, AeronSubscriber, FragmentAssembler, FragmentHeader, Publication,
+    Subscription,
+};
+#[cfg(feature = "channel")]
+pub use bus::{
+    BackpressurePolicy, TopicBus, TopicBusConfig, TopicPolicy, TopicPublisher, TopicSubscriber,
+};
+pub use capture::{
+    CaptureConfig, CaptureIter, CaptureReader, CaptureWriter, CapturedMessage, ChunkInfo,
+    RecordingPublisher,
+};
 #[cfg(feature = "channel")]
 pub use channel::{ChannelPublisher, ChannelSubscriber, channel_pair};
-pub use config::{AeronConfig, ChannelConfig, TransportConfig, TransportType};
+pub use config::{
+    AeronConfig, ChannelConfig, SharedMemoryConfig, SlowConsumerPolicy, TransportConfig,
+    TransportType,
+};
 pub use factory::TransportFactory;
+pub use filter::TopicFilter;
+pub use replay::{ReplaySpeed, ReplayStats, Replayer};
+#[cfg(feature = "aeron")]
+pub use rusteron::RusteronClient;
+pub use sequencing::{
+    RetransmitOutcome, SequenceEvent, SequenceStats, SequencedPublisher, SequencedSubscriber,
+    SequencingConfig,
+};
+#[cfg(feature = "shm")]
+pub use shm::{ConsumerStatus, ShmPublisher, ShmSubscriber};
@@ -0,0 +1,427 @@
+//! Capture Replay
+//!
+//! Publishes a recorded capture back onto any `Publisher`, paced by the
+//! recorded timestamps.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use super::capture::CaptureReader;
+use crate::application::codec::CodecKind;
+use crate::application::error::TransportError;
+use crate::application::fixed::set_frame_sequence;
+use crate::application::message::{WireHeader, WireMessage};
+use crate::application::traits::Publisher;
+
+/// How long to keep retrying a full transport before giving up
+const FULL_RETRY_LIMIT: Duration = Duration::from_secs(1);
+
+/// Replay pacing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Original inter-message timing
+    RealTime,
+    /// Original timing divided by the factor
+    Multiplier(f64),
+    /// No pacing
+    AsFastAsPossible,
+}
+
+impl ReplaySpeed {
+    /// Recorded time elapsed mapped to wall time, `None` when unpaced
+    fn wall_time(&self, recorded_ns: u64) -> Option<Duration> {
+        match self {
+            ReplaySpeed::RealTime => Some(Duration::from_nanos(recorded_ns)),
+            ReplaySpeed::Multiplier(factor) => {
+                Some(Duration::from_secs_f64(recorded_ns as f64 / 1e9 / factor))
+            }
+            ReplaySpeed::AsFastAsPossible => None,
+        }
+    }
+}
+
+impl FromStr for ReplaySpeed {
+    type Err = TransportError;
+
+    /// Parses `1x`, `10x`, `0.5x` or `max`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        if s == "max" {
+            return Ok(ReplaySpeed::AsFastAsPossible);
+        }
+        let factor: f64 = s
+            .strip_suffix('x')
+            .unwrap_or(&s)
+            .parse()
+            .map_err(|_| TransportError::Config(format!("invalid replay speed: {}", s)))?;
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(TransportError::Config(format!(
+                "replay speed must be positive: {}",
+                s
+            )));
+        }
+        Ok(if factor == 1.0 {
+            ReplaySpeed::RealTime
+        } else {
+            ReplaySpeed::Multiplier(factor)
+        })
+    }
+}
+
+impl fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplaySpeed::RealTime => write!(f, "1x"),
+            ReplaySpeed::Multiplier(factor) => write!(f, "{}x", factor),
+            ReplaySpeed::AsFastAsPossible => write!(f, "max"),
+        }
+    }
+}
+
+/// Replay results
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Messages published
+    pub published: usize,
+    /// Messages dropped by the filter
+    pub filtered: usize,
+    pub first_timestamp_ns: Option<u64>,
+    pub last_timestamp_ns: Option<u64>,
+}
+
+type MessageFilter = Box<dyn Fn(&WireHeader, &[u8]) -> bool + Send + Sync>;
+
+/// Replays a capture onto a publisher
+///
+/// Messages are published byte-for-byte as recorded, so subscribers see the
+/// original sequence numbers and timestamps, routed on their recorded
+/// topics. A filtered replay renumbers each stream to stay contiguous, so
+/// sequence-checking subscribers don't treat filtered-out messages as gaps.
+pub struct Replayer {
+    reader: CaptureReader,
+    speed: ReplaySpeed,
+    from_ns: u64,
+    until_ns: u64,
+    filter: Option<MessageFilter>,
+}
+
+impl Replayer {
+    pub fn new(reader: CaptureReader) -> Self {
+        Self {
+            reader,
+            speed: ReplaySpeed::RealTime,
+            from_ns: 0,
+            until_ns: u64::MAX,
+            filter: None,
+        }
+    }
+
+    pub fn with_speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Start at the first message stamped at or after `timestamp_ns`
+    pub fn seek(mut self, timestamp_ns: u64) -> Self {
+        self.from_ns = timestamp_ns;
+        self
+    }
+
+    /// Stop after the last message stamped at or before `timestamp_ns`
+    pub fn until(mut self, timestamp_ns: u64) -> Self {
+        self.until_ns = timestamp_ns;
+        self
+    }
+
+    /// Only publish messages the filter accepts
+    ///
+    /// The filter gets each message's header and its frame as recorded, in
+    /// either codec; callers decode the frame to filter by symbol. Published
+    /// sequences are renumbered per source and topic from the first one
+    /// replayed.
+    pub fn with_filter(
+        mut self,
+        filter: impl Fn(&WireHeader, &[u8]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    pub fn reader(&self) -> &CaptureReader {
+        &self.reader
+    }
+
+    /// Publish the selected range, blocking until done
+    pub fn run(&self, publisher: &dyn Publisher) -> Result<ReplayStats, TransportError> {
+        let mut stats = ReplayStats::default();
+        let mut origin: Option<(u64, Instant)> = None;
+        let mut next_sequence: HashMap<(String, String), u64> = HashMap::new();
+
+        for captured in self.reader.messages_from(self.from_ns) {
+            let captured = captured?;
+            if captured.timestamp_ns > self.until_ns {
+                break;
+            }
+            let mut data = captured.data;
+            let header = WireMessage::header(&data)?;
+            if let Some(filter) = &self.filter {
+                if !filter(&header, &data) {
+                    stats.filtered += 1;
+                    continue;
+                }
+                let expected = next_sequence
+                    .entry((header.source.clone(), header.topic.clone()))
+                    .or_insert(header.sequence);
+                // Sequence 0 is a stream restart; keep it
+                if header.sequence == 0 {
+                    *expected = 0;
+                }
+                if header.sequence != *expected {
+                    renumber(&mut data, *expected)?;
+                }
+                *expected += 1;
+            }
+
+            let (start_ns, started) =
+                *origin.get_or_insert((captured.timestamp_ns, Instant::now()));
+            if let Some(offset) = self
+                .speed
+                .wall_time(captured.timestamp_ns.saturating_sub(start_ns))
+            {
+                let due = started + offset;
+                let now = Instant::now();
+                if due > now {
+                    std::thread::sleep(due - now);
+                }
+            }
+
+            publish_with_backpressure(publisher, &header.topic, &data)?;
+            stats.published += 1;
+            stats
+                .first_timestamp_ns
+                .get_or_insert(captured.timestamp_ns);
+            stats.last_timestamp_ns = Some(captured.timestamp_ns);
+        }
+
+        publisher.flush()?;
+        Ok(stats)
+    }
+}
+
+/// Rewrite a recorded frame's sequence, keeping its codec
+fn renumber(data: &mut Vec<u8>, sequence: u64) -> Result<(), TransportError> {
+    match CodecKind::detect(data) {
+        CodecKind::Fixed => set_frame_sequence(data, sequence),
+        CodecKind::Bincode => {
+            let mut msg = WireMessage::deserialize(data)?;
+            msg.sequence = sequence;
+            *data = msg.serialize()?;
+            Ok(())
+        }
+    }
+}
+
+/// Wait out a full transport instead of dropping recorded data
+fn publish_with_backpressure(
+    publisher: &dyn Publisher,
+    topic: &str,
+    data: &[u8],
+) -> Result<(), TransportError> {
+    let deadline = Instant::now() + FULL_RETRY_LIMIT;
+    loop {
+        let result = if topic.is_empty() {
+            publisher.publish(data)
+        } else {
+            publisher.publish_to(topic, data)
+        };
+        match result {
+            Err(TransportError::Full) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_micros(50));
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "channel"))]
+mod tests {
+    use super::*;
+    use crate::application::codec::{Codec, FixedCodec};
+    use crate::application::fixed::is_fixed_frame;
+    use crate::application::message::MessageType;
+    use crate::application::traits::Subscriber;
+    use crate::infrastructure::capture::{CaptureConfig, CaptureWriter};
+    use crate::infrastructure::channel::channel_pair;
+    use trading_core::DepthUpdate;
+
+    const MS: u64 = 1_000_000;
+
+    fn capture(dir: &std::path::Path, timestamps_ms: &[u64]) -> CaptureReader {
+        let mut writer = CaptureWriter::create(CaptureConfig {
+            chunk_messages: 2,
+            ..CaptureConfig::new(dir)
+        })
+        .unwrap();
+        for (i, ts) in timestamps_ms.iter().enumerate() {
+            let source = if i % 2 == 0 { "even" } else { "odd" };
+            let mut msg = WireMessage::with_raw_payload(
+                MessageType::DepthUpdate,
+                i as u64 + 1,
+                source,
+                vec![],
+            );
+            msg.timestamp_ns = ts * MS;
+            writer.record(&msg.serialize().unwrap()).unwrap();
+        }
+        drop(writer);
+        CaptureReader::open(dir).unwrap()
+    }
+
+    fn received(subscriber: &dyn Subscriber) -> Vec<u64> {
+        let mut sequences = Vec::new();
+        subscriber
+            .poll(&mut |data| sequences.push(WireMessage::deserialize(data).unwrap().sequence))
+            .unwrap();
+        sequences
+    }
+
+    #[test]
+    fn test_parse_speed() {
+        assert_eq!("1x".parse::<ReplaySpeed>().unwrap(), ReplaySpeed::RealTime);
+        assert_eq!(
+            "10X".parse::<ReplaySpeed>().unwrap(),
+            ReplaySpeed::Multiplier(10.0)
+        );
+        assert_eq!(
+            "max".parse::<ReplaySpeed>().unwrap(),
+            ReplaySpeed::AsFastAsPossible
+        );
+        assert!("0x".parse::<ReplaySpeed>().is_err());
+        assert!("fast".parse::<ReplaySpeed>().is_err());
+    }
+
+    #[test]
+    fn test_replay_all_as_fast_as_possible() {
+        let dir = tempfile::tempdir().unwrap();
+        let reader = capture(dir.path(), &[0, 1_000, 2_000, 3_000, 4_000]);
+        let (publisher, subscriber) = channel_pair(100);
+
+        let started = Instant::now();
+        let stats = Replayer::new(reader)
+            .with_speed(ReplaySpeed::AsFastAsPossible)
+            .run(&publisher)
+            .unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(stats.published, 5);
+        assert_eq!(stats.first_timestamp_ns, Some(0));
+        assert_eq!(stats.last_timestamp_ns, Some(4_000 * MS));
+        assert_eq!(received(&subscriber), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_replay_paced_by_multiplier() {
+        let dir = tempfile::tempdir().unwrap();
+        let reader = capture(dir.path(), &[0, 100, 200]);
+        let (publisher, subscriber) = channel_pair(100);
+
+        let started = Instant::now();
+        Replayer::new(reader)
+            .with_speed(ReplaySpeed::Multiplier(4.0))
+            .run(&publisher)
+            .unwrap();
+
+        // 200ms recorded at 4x
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(50), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(200), "{:?}", elapsed);
+        assert_eq!(received(&subscriber), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_replay_seek_until_and_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let reader = capture(dir.path(), &[10, 20, 30, 40, 50, 60]);
+        let (publisher, subscriber) = channel_pair(100);
+
+        let stats = Replayer::new(reader)
+            .with_speed(ReplaySpeed::AsFastAsPossible)
+            .seek(25 * MS)
+            .until(55 * MS)
+            .with_filter(|header, _| header.source == "even")
+            .run(&publisher)
+            .unwrap();
+
+        // Sequences 3..=5 are in range; 4 is "odd", so 5 goes out as 4
+        assert_eq!(received(&subscriber), vec![3, 4]);
+        assert_eq!(stats.published, 2);
+        assert_eq!(stats.filtered, 1);
+    }
+
+    #[test]
+    fn test_filtered_replay_keeps_fixed_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = CaptureWriter::create(CaptureConfig::new(dir.path())).unwrap();
+        for (i, symbol) in ["BTCUSDT", "ETHUSDT", "BTCUSDT"].into_iter().enumerate() {
+            let sequence = i as u64 + 1;
+            let update = DepthUpdate::new("binance", symbol, sequence, sequence);
+            let frame = FixedCodec
+                .encode(sequence, "gateway", "md.binance.depth", &update)
+                .unwrap();
+            writer.record(&frame).unwrap();
+        }
+        drop(writer);
+        let (publisher, subscriber) = channel_pair(100);
+
+        let stats = Replayer::new(CaptureReader::open(dir.path()).unwrap())
+            .with_speed(ReplaySpeed::AsFastAsPossible)
+            .with_filter(|_, data| {
+                CodecKind::detect(data)
+                    .decode::<DepthUpdate>(data)
+                    .is_ok_and(|update| update.symbol == "BTCUSDT")
+            })
+            .run(&publisher)
+            .unwrap();
+        assert_eq!(stats.published, 2);
+        assert_eq!(stats.filtered, 1);
+
+        // Still fixed frames; the second BTCUSDT update goes out as 2
+        let mut replayed = Vec::new();
+        subscriber
+            .poll(&mut |data| {
+                assert!(is_fixed_frame(data));
+                let update: DepthUpdate = FixedCodec.decode(data).unwrap();
+                let sequence = WireMessage::header(data).unwrap().sequence;
+                replayed.push((sequence, update.symbol, update.final_update_id));
+            })
+            .unwrap();
+        assert_eq!(
+            replayed,
+            vec![(1, "BTCUSDT".to_string(), 1), (2, "BTCUSDT".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_replay_waits_for_slow_subscriber() {
+        let dir = tempfile::tempdir().unwrap();
+        let reader = capture(dir.path(), &[0, 1, 2, 3]);
+        let (publisher, subscriber) = channel_pair(1);
+
+        let drain = std::thread::spawn(move || {
+            let mut sequences = Vec::new();
+            while sequences.len() < 4 {
+                sequences.extend(received(&subscriber));
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            sequences
+        });
+
+        Replayer::new(reader)
+            .with_speed(ReplaySpeed::AsFastAsPossible)
+            .run(&publisher)
+            .unwrap();
+        assert_eq!(drain.join().unwrap(), vec![1, 2, 3, 4]);
+    }
+}
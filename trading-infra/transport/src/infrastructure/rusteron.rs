@@ -0,0 +1,161 @@
+//! Aeron client binding over rusteron
+//!
+//! Implements `AeronClient`, `Publication` and `Subscription` on the Aeron C
+//! client, so `AeronPublisher`/`AeronSubscriber` run against a real media
+//! driver. Fragments are handed over raw; reassembly stays in
+//! `FragmentAssembler`.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rusteron_client::{
+    Aeron, AeronContext, AeronFragmentHandlerCallback, AeronHeader, AeronPublication,
+    AeronReservedValueSupplierLogger, AeronSubscription, Handler, Handlers, IntoCString,
+};
+
+use super::aeron::{AeronClient, FragmentHeader, Publication, Subscription};
+use super::config::AeronConfig;
+use crate::application::error::TransportError;
+
+/// How long adding a publication or subscription waits for the driver
+const REGISTRATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn driver_error(context: &str, error: impl std::fmt::Display) -> TransportError {
+    TransportError::ConnectionFailed(format!("{}: {}", context, error))
+}
+
+/// Aeron client connected to a media driver through its directory
+pub struct RusteronClient {
+    aeron: Aeron,
+}
+
+impl RusteronClient {
+    /// Connect to the media driver in `config.media_driver_dir`
+    ///
+    /// Fails once `driver_timeout_ms` passes without a running driver.
+    pub fn connect(config: &AeronConfig) -> Result<Self, TransportError> {
+        let context = AeronContext::new().map_err(|e| driver_error("Aeron context", e))?;
+        context
+            .set_dir(&config.media_driver_dir.as_str().into_c_string())
+            .and_then(|_| context.set_driver_timeout_ms(config.driver_timeout_ms))
+            .map_err(|e| driver_error("Aeron context", e))?;
+        let aeron = Aeron::new(&context).map_err(|e| driver_error("Aeron client", e))?;
+        aeron.start().map_err(|e| {
+            driver_error(
+                &format!("no media driver at {}", config.media_driver_dir),
+                e,
+            )
+        })?;
+        Ok(Self { aeron })
+    }
+}
+
+impl AeronClient for RusteronClient {
+    fn add_publication(
+        &self,
+        channel: &str,
+        stream_id: i32,
+    ) -> Result<Box<dyn Publication>, TransportError> {
+        let publication = self
+            .aeron
+            .add_publication(&channel.into_c_string(), stream_id, REGISTRATION_TIMEOUT)
+            .map_err(|e| driver_error(&format!("publication {} {}", channel, stream_id), e))?;
+        Ok(Box::new(RusteronPublication {
+            publication,
+            _aeron: self.aeron.clone(),
+        }))
+    }
+
+    fn add_subscription(
+        &self,
+        channel: &str,
+        stream_id: i32,
+    ) -> Result<Box<dyn Subscription>, TransportError> {
+        let subscription = self
+            .aeron
+            .add_subscription(
+                &channel.into_c_string(),
+                stream_id,
+                Handlers::NONE,
+                Handlers::NONE,
+                REGISTRATION_TIMEOUT,
+            )
+            .map_err(|e| driver_error(&format!("subscription {} {}", channel, stream_id), e))?;
+        Ok(Box::new(RusteronSubscription {
+            subscription: Mutex::new(subscription),
+            _aeron: self.aeron.clone(),
+        }))
+    }
+}
+
+/// Publications and subscriptions keep their client alive
+struct RusteronPublication {
+    publication: AeronPublication,
+    _aeron: Aeron,
+}
+
+impl Publication for RusteronPublication {
+    fn offer(&self, data: &[u8]) -> i64 {
+        self.publication
+            .offer_raw::<AeronReservedValueSupplierLogger>(data, None)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.publication.is_connected()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publication.is_closed()
+    }
+}
+
+/// Aeron subscriptions must not be polled from two threads at once
+struct RusteronSubscription {
+    subscription: Mutex<AeronSubscription>,
+    _aeron: Aeron,
+}
+
+/// Forwards raw fragments with their BEGIN/END flags
+struct Fragments<'a> {
+    handler: &'a mut dyn FnMut(&[u8], FragmentHeader),
+}
+
+impl AeronFragmentHandlerCallback for Fragments<'_> {
+    fn handle_aeron_fragment_handler(&mut self, buffer: &[u8], header: AeronHeader) {
+        // Only fails on a null header; a panic here would abort across the C frame
+        let Ok(values) = header.get_values() else {
+            tracing::warn!("Dropping Aeron fragment without header values");
+            return;
+        };
+        let frame = values.frame();
+        (self.handler)(
+            buffer,
+            FragmentHeader {
+                session_id: frame.session_id(),
+                flags: frame.flags(),
+            },
+        );
+    }
+}
+
+impl Subscription for RusteronSubscription {
+    fn poll(
+        &self,
+        handler: &mut dyn FnMut(&[u8], FragmentHeader),
+        fragment_limit: usize,
+    ) -> Result<usize, TransportError> {
+        let subscription = self.subscription.lock().unwrap_or_else(|e| e.into_inner());
+        let fragments = Handler::new(Fragments { handler });
+        let count = subscription
+            .poll(Some(&fragments), fragment_limit)
+            .map_err(|e| TransportError::Io(format!("Aeron poll failed: {}", e)))?;
+        Ok(count as usize)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.subscription
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_connected()
+    }
+}
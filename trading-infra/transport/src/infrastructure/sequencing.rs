@@ -0,0 +1,795 @@
+//! Reliable Sequencing
+//!
+//! Decorators that add gap detection and recovery to any transport.
+//!
+//! `SequencedPublisher` keeps the last N published messages so they can be
+//! resent on request. `SequencedSubscriber` tracks the `WireMessage`
+//! sequence of every stream (source and topic), drops duplicates, holds back out-of-order
+//! messages and asks the publisher to retransmit what is missing. A gap the
+//! publisher can no longer fill (rejected, timed out or too large) is
+//! reported as lost, and the owner falls back to a snapshot.
+//!
+//! Sequence 0 marks a stream (re)start and resets tracking for it.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::error::TransportError;
+use crate::application::message::{
+    MSG_RETRANSMIT_REJECT, MSG_RETRANSMIT_REQUEST, MessageType, RetransmitRequest, WireMessage,
+};
+use crate::application::traits::{BoxPublisher, BoxSubscriber, Publisher, Subscriber};
+
+/// Gap detection and retransmit settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencingConfig {
+    /// Messages a publisher keeps for retransmission
+    #[serde(default = "default_retransmit_buffer")]
+    pub retransmit_buffer: usize,
+    /// Out-of-order messages a subscriber holds per source before giving up
+    /// on the gap in front of them
+    #[serde(default = "default_max_pending")]
+    pub max_pending: usize,
+    /// How long a subscriber waits for a retransmit before giving up
+    #[serde(default = "default_retransmit_timeout_ms")]
+    pub retransmit_timeout_ms: u64,
+}
+
+fn default_retransmit_buffer() -> usize {
+    65_536
+}
+
+fn default_max_pending() -> usize {
+    10_000
+}
+
+fn default_retransmit_timeout_ms() -> u64 {
+    250
+}
+
+impl Default for SequencingConfig {
+    fn default() -> Self {
+        Self {
+            retransmit_buffer: default_retransmit_buffer(),
+            max_pending: default_max_pending(),
+            retransmit_timeout_ms: default_retransmit_timeout_ms(),
+        }
+    }
+}
+
+/// Result of handling a retransmit request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetransmitOutcome {
+    /// This many buffered messages were published again
+    Resent(usize),
+    /// The range starts before the buffer; a reject was published
+    Rejected,
+    /// The request names another source
+    NotOurs,
+}
+
+/// A message kept for retransmission
+struct Buffered {
+    topic: String,
+    sequence: u64,
+    data: Vec<u8>,
+}
+
+/// Publisher that keeps a bounded retransmit buffer
+///
+/// Messages are buffered by the topic and sequence in their `WireMessage`
+/// header even when the inner publish fails, so a message dropped on a full
+/// transport can still be recovered.
+pub struct SequencedPublisher {
+    inner: BoxPublisher,
+    source: String,
+    capacity: usize,
+    buffer: Mutex<VecDeque<Buffered>>,
+}
+
+impl SequencedPublisher {
+    /// Wrap a publisher whose messages carry `source`
+    pub fn new(inner: BoxPublisher, source: impl Into<String>, config: &SequencingConfig) -> Self {
+        Self {
+            inner,
+            source: source.into(),
+            capacity: config.retransmit_buffer.max(1),
+            buffer: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Oldest and newest buffered sequence of a topic's stream
+    pub fn buffered_range(&self, topic: &str) -> Option<(u64, u64)> {
+        let buffer = self.buffer.lock().unwrap();
+        let mut stream = buffer.iter().filter(|m| m.topic == topic);
+        let first = stream.next()?.sequence;
+        Some((first, stream.next_back().map_or(first, |m| m.sequence)))
+    }
+
+    /// Publish the requested range again, or a reject if it is too old
+    pub fn retransmit(
+        &self,
+        request: &RetransmitRequest,
+    ) -> Result<RetransmitOutcome, TransportError> {
+        if request.source != self.source {
+            return Ok(RetransmitOutcome::NotOurs);
+        }
+
+        let messages: Option<Vec<Vec<u8>>> = {
+            let buffer = self.buffer.lock().unwrap();
+            let mut stream = buffer
+                .iter()
+                .filter(|m| m.topic == request.topic)
+                .peekable();
+            match stream.peek() {
+                Some(oldest) if oldest.sequence <= request.from_sequence => Some(
+                    stream
+                        .filter(|m| {
+                            (request.from_sequence..=request.to_sequence).contains(&m.sequence)
+                        })
+                        .map(|m| m.data.clone())
+                        .collect(),
+                ),
+                _ => None,
+            }
+        };
+
+        match messages {
+            Some(messages) => {
+                for data in &messages {
+                    self.send(&request.topic, data)?;
+                }
+                Ok(RetransmitOutcome::Resent(messages.len()))
+            }
+            None => {
+                let reject =
+                    WireMessage::new(MessageType::RetransmitReject, 0, &self.source, request)?
+                        .with_topic(&request.topic);
+                self.send(&request.topic, &reject.serialize()?)?;
+                Ok(RetransmitOutcome::Rejected)
+            }
+        }
+    }
+
+    /// Handle a serialized `MSG_RETRANSMIT_REQUEST`; other messages are ignored
+    pub fn handle_request(&self, data: &[u8]) -> Result<Option<RetransmitOutcome>, TransportError> {
+        let msg = WireMessage::deserialize(data)?;
+        if msg.msg_type != MSG_RETRANSMIT_REQUEST {
+            return Ok(None);
+        }
+        let request: RetransmitRequest = msg.decode_payload()?;
+        self.retransmit(&request).map(Some)
+    }
+
+    fn send(&self, topic: &str, data: &[u8]) -> Result<(), TransportError> {
+        if topic.is_empty() {
+            self.inner.publish(data)
+        } else {
+            self.inner.publish_to(topic, data)
+        }
+    }
+
+    fn remember(&self, data: &[u8]) {
+        let Ok(header) = WireMessage::header(data) else {
+            return;
+        };
+        let mut buffer = self.buffer.lock().unwrap();
+        // A restart renumbers the stream from zero; its older entries would be ambiguous
+        if header.sequence == 0 {
+            buffer.retain(|m| m.topic != header.topic);
+        }
+        if buffer.len() == self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(Buffered {
+            topic: header.topic,
+            sequence: header.sequence,
+            data: data.to_vec(),
+        });
+    }
+}
+
+impl Publisher for SequencedPublisher {
+    fn publish(&self, data: &[u8]) -> Result<(), TransportError> {
+        self.remember(data);
+        self.inner.publish(data)
+    }
+
+    fn publish_to(&self, topic: &str, data: &[u8]) -> Result<(), TransportError> {
+        self.remember(data);
+        self.inner.publish_to(topic, data)
+    }
+
+    fn flush(&self) -> Result<(), TransportError> {
+        self.inner.flush()
+    }
+
+    fn is_active(&self) -> bool {
+        self.inner.is_active()
+    }
+}
+
+/// What a `SequencedSubscriber` observed
+///
+/// Sequences are tracked per stream, a stream being a source and topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceEvent<'a> {
+    /// Next in-order message (or an undecodable one, passed through)
+    Message(&'a [u8]),
+    /// Already delivered or already held; dropped
+    Duplicate {
+        source: &'a str,
+        topic: &'a str,
+        sequence: u64,
+    },
+    /// Missing range detected; a retransmit was requested
+    Gap {
+        source: &'a str,
+        topic: &'a str,
+        from: u64,
+        to: u64,
+    },
+    /// Missing range given up on; state derived from this stream is stale
+    Lost {
+        source: &'a str,
+        topic: &'a str,
+        from: u64,
+        to: u64,
+    },
+    /// Stream restarted at sequence 0
+    Reset { source: &'a str, topic: &'a str },
+}
+
+/// Counters across all streams
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SequenceStats {
+    pub delivered: u64,
+    pub duplicates: u64,
+    pub gaps: u64,
+    pub lost: u64,
+}
+
+/// Source and topic
+type StreamKey = (String, String);
+
+struct StreamState {
+    /// Next sequence to deliver
+    next: u64,
+    /// One past the highest sequence seen
+    high: u64,
+    /// Out-of-order messages waiting for the gap in front of them
+    pending: BTreeMap<u64, Vec<u8>>,
+    /// When the current gap was detected or last made progress
+    gap_since: Option<Instant>,
+}
+
+impl StreamState {
+    fn starting_after(sequence: u64) -> Self {
+        Self {
+            next: sequence + 1,
+            high: sequence + 1,
+            pending: BTreeMap::new(),
+            gap_since: None,
+        }
+    }
+}
+
+type EventHandler<'h> = dyn FnMut(SequenceEvent<'_>) + 'h;
+
+/// Subscriber that delivers each stream's messages once and in order
+pub struct SequencedSubscriber {
+    inner: BoxSubscriber,
+    requester: Option<BoxPublisher>,
+    config: SequencingConfig,
+    streams: Mutex<HashMap<StreamKey, StreamState>>,
+    stats: Mutex<SequenceStats>,
+}
+
+impl SequencedSubscriber {
+    pub fn new(inner: BoxSubscriber, config: SequencingConfig) -> Self {
+        Self {
+            inner,
+            requester: None,
+            config,
+            streams: Mutex::new(HashMap::new()),
+            stats: Mutex::new(SequenceStats::default()),
+        }
+    }
+
+    /// Send `MSG_RETRANSMIT_REQUEST`s for gaps through this publisher
+    ///
+    /// Without one, gaps are only filled by late arrivals before the timeout.
+    pub fn with_retransmit_requests(mut self, requester: BoxPublisher) -> Self {
+        self.requester = Some(requester);
+        self
+    }
+
+    pub fn stats(&self) -> SequenceStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Next sequence expected on a stream
+    pub fn next_expected(&self, source: &str, topic: &str) -> Option<u64> {
+        let streams = self.streams.lock().unwrap();
+        streams
+            .get(&(source.to_string(), topic.to_string()))
+            .map(|s| s.next)
+    }
+
+    /// Poll the inner transport, reporting every sequencing event
+    ///
+    /// Returns the number of messages delivered.
+    pub fn poll_events(&self, handler: &mut EventHandler<'_>) -> Result<usize, TransportError> {
+        let mut streams = self.streams.lock().unwrap();
+        let mut stats = self.stats.lock().unwrap();
+        let delivered_before = stats.delivered;
+
+        let mut handler = |event: SequenceEvent<'_>| {
+            match &event {
+                SequenceEvent::Message(_) => stats.delivered += 1,
+                SequenceEvent::Duplicate { .. } => stats.duplicates += 1,
+                SequenceEvent::Gap { .. } => stats.gaps += 1,
+                SequenceEvent::Lost { .. } => stats.lost += 1,
+                SequenceEvent::Reset { .. } => {}
+            }
+            handler(event);
+        };
+
+        self.inner
+            .poll(&mut |data| self.on_message(&mut streams, data, &mut handler))?;
+
+        let timeout = Duration::from_millis(self.config.retransmit_timeout_ms);
+        for (key, state) in streams.iter_mut() {
+            if state
+                .gap_since
+                .is_some_and(|since| since.elapsed() >= timeout)
+            {
+                give_up(key, state, &mut handler);
+            }
+        }
+
+        Ok((stats.delivered - delivered_before) as usize)
+    }
+
+    fn on_message(
+        &self,
+        streams: &mut HashMap<StreamKey, StreamState>,
+        data: &[u8],
+        handler: &mut EventHandler<'_>,
+    ) {
+        let Ok(header) = WireMessage::header(data) else {
+            handler(SequenceEvent::Message(data));
+            return;
+        };
+
+        if header.msg_type == MSG_RETRANSMIT_REJECT {
+            let request = WireMessage::deserialize(data)
+                .and_then(|msg| msg.decode_payload::<RetransmitRequest>());
+            if let Ok(request) = request {
+                let key = (request.source, request.topic);
+                if let Some(state) = streams.get_mut(&key)
+                    && request.to_sequence >= state.next
+                {
+                    give_up(&key, state, handler);
+                }
+            }
+            return;
+        }
+
+        let sequence = header.sequence;
+        let key = (header.source, header.topic);
+        let Some(state) = streams.get_mut(&key) else {
+            streams.insert(key, StreamState::starting_after(sequence));
+            handler(SequenceEvent::Message(data));
+            return;
+        };
+        let (source, topic) = (key.0.as_str(), key.1.as_str());
+
+        if sequence == 0 && state.next > 1 {
+            *state = StreamState::starting_after(0);
+            handler(SequenceEvent::Reset { source, topic });
+            handler(SequenceEvent::Message(data));
+            return;
+        }
+
+        if sequence < state.next || state.pending.contains_key(&sequence) {
+            handler(SequenceEvent::Duplicate {
+                source,
+                topic,
+                sequence,
+            });
+            return;
+        }
+
+        if sequence == state.next {
+            handler(SequenceEvent::Message(data));
+            state.next += 1;
+            state.high = state.high.max(state.next);
+            drain(state, handler);
+            return;
+        }
+
+        // Ahead of the next expected: hold it and request what is newly missing
+        if sequence > state.high {
+            let (from, to) = (state.high, sequence - 1);
+            handler(SequenceEvent::Gap {
+                source,
+                topic,
+                from,
+                to,
+            });
+            self.request_retransmit(&key, from, to);
+            state.gap_since.get_or_insert_with(Instant::now);
+        }
+        state.high = state.high.max(sequence + 1);
+        state.pending.insert(sequence, data.to_vec());
+        if state.pending.len() > self.config.max_pending {
+            give_up(&key, state, handler);
+        }
+    }
+
+    fn request_retransmit(&self, (source, topic): &StreamKey, from: u64, to: u64) {
+        let Some(requester) = &self.requester else {
+            return;
+        };
+        let request = RetransmitRequest {
+            source: source.clone(),
+            topic: topic.clone(),
+            from_sequence: from,
+            to_sequence: to,
+        };
+        let result = WireMessage::new(MessageType::RetransmitRequest, 0, "subscriber", &request)
+            .and_then(|msg| msg.serialize())
+            .map_err(TransportError::from)
+            .and_then(|data| requester.publish(&data));
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to request retransmit of {} {} {}..={}: {}",
+                source,
+                topic,
+                from,
+                to,
+                e
+            );
+        }
+    }
+}
+
+/// Deliver held messages that are now in order
+fn drain(state: &mut StreamState, handler: &mut EventHandler<'_>) {
+    let mut progressed = false;
+    while let Some(data) = state.pending.remove(&state.next) {
+        handler(SequenceEvent::Message(&data));
+        state.next += 1;
+        progressed = true;
+    }
+    if state.pending.is_empty() {
+        state.gap_since = None;
+    } else if progressed {
+        state.gap_since = Some(Instant::now());
+    }
+}
+
+/// Skip the gap in front of the oldest held message
+fn give_up((source, topic): &StreamKey, state: &mut StreamState, handler: &mut EventHandler<'_>) {
+    let Some(&first_held) = state.pending.keys().next() else {
+        state.gap_since = None;
+        return;
+    };
+    handler(SequenceEvent::Lost {
+        source,
+        topic,
+        from: state.next,
+        to: first_held - 1,
+    });
+    state.next = first_held;
+    drain(state, handler);
+}
+
+impl Subscriber for SequencedSubscriber {
+    fn poll(&self, handler: &mut dyn FnMut(&[u8])) -> Result<usize, TransportError> {
+        self.poll_events(&mut |event| match event {
+            SequenceEvent::Message(data) => handler(data),
+            SequenceEvent::Lost {
+                source,
+                topic,
+                from,
+                to,
+            } => {
+                tracing::warn!("Lost {} {} messages {}..={}", source, topic, from, to);
+            }
+            event => tracing::debug!("{:?}", event),
+        })
+    }
+
+    fn subscribe(&self, topic: &str) -> Result<(), TransportError> {
+        self.inner.subscribe(topic)
+    }
+
+    fn has_messages(&self) -> bool {
+        self.inner.has_messages()
+    }
+}
+
+#[cfg(all(test, feature = "channel"))]
+mod tests {
+    use super::*;
+    use crate::infrastructure::channel::channel_pair;
+
+    fn message(sequence: u64, source: &str) -> Vec<u8> {
+        on_topic(sequence, source, "")
+    }
+
+    fn on_topic(sequence: u64, source: &str, topic: &str) -> Vec<u8> {
+        WireMessage::with_raw_payload(MessageType::DepthUpdate, sequence, source, vec![])
+            .with_topic(topic)
+            .serialize()
+            .unwrap()
+    }
+
+    /// Buffer a message without it reaching the wire
+    fn lose(publisher: &SequencedPublisher, sequence: u64) {
+        publisher.buffer.lock().unwrap().push_back(Buffered {
+            topic: String::new(),
+            sequence,
+            data: message(sequence, "gw"),
+        });
+    }
+
+    fn sequence_of(data: &[u8]) -> u64 {
+        WireMessage::header(data).unwrap().sequence
+    }
+
+    fn fast_config() -> SequencingConfig {
+        SequencingConfig {
+            retransmit_buffer: 4,
+            max_pending: 8,
+            retransmit_timeout_ms: 20,
+        }
+    }
+
+    /// Poll once, returning delivered sequences and non-message events
+    fn collect(subscriber: &SequencedSubscriber) -> (Vec<u64>, Vec<String>) {
+        let mut delivered = Vec::new();
+        let mut events = Vec::new();
+        subscriber
+            .poll_events(&mut |event| match event {
+                SequenceEvent::Message(data) => delivered.push(sequence_of(data)),
+                event => events.push(format!("{:?}", event)),
+            })
+            .unwrap();
+        (delivered, events)
+    }
+
+    #[test]
+    fn test_in_order_and_duplicates() {
+        let (publisher, subscriber) = channel_pair(100);
+        let subscriber = SequencedSubscriber::new(Box::new(subscriber), fast_config());
+
+        for seq in [5, 6, 6, 7, 5] {
+            publisher.publish(&message(seq, "gw")).unwrap();
+        }
+        let (delivered, _) = collect(&subscriber);
+
+        assert_eq!(delivered, vec![5, 6, 7]);
+        assert_eq!(subscriber.stats().duplicates, 2);
+        assert_eq!(subscriber.next_expected("gw", ""), Some(8));
+    }
+
+    #[test]
+    fn test_streams_tracked_independently() {
+        let (publisher, subscriber) = channel_pair(100);
+        let subscriber = SequencedSubscriber::new(Box::new(subscriber), fast_config());
+
+        for (seq, source, topic) in [
+            (1, "a", "md.x"),
+            (100, "b", "md.x"),
+            (0, "a", "md.y"),
+            (2, "a", "md.x"),
+            (101, "b", "md.x"),
+            (1, "a", "md.y"),
+        ] {
+            publisher.publish(&on_topic(seq, source, topic)).unwrap();
+        }
+        let (delivered, events) = collect(&subscriber);
+
+        assert_eq!(delivered, vec![1, 100, 0, 2, 101, 1]);
+        assert!(events.is_empty(), "{:?}", events);
+    }
+
+    #[test]
+    fn test_reorders_late_arrival() {
+        let (publisher, subscriber) = channel_pair(100);
+        let subscriber = SequencedSubscriber::new(Box::new(subscriber), fast_config());
+
+        for seq in [1, 3, 4, 2] {
+            publisher.publish(&message(seq, "gw")).unwrap();
+        }
+        let (delivered, events) = collect(&subscriber);
+
+        assert_eq!(delivered, vec![1, 2, 3, 4]);
+        assert_eq!(
+            events,
+            vec!["Gap { source: \"gw\", topic: \"\", from: 2, to: 2 }"]
+        );
+    }
+
+    #[test]
+    fn test_gap_recovered_by_retransmit() {
+        let (data_pub, data_sub) = channel_pair(100);
+        let (request_pub, request_sub) = channel_pair(100);
+        let publisher = SequencedPublisher::new(Box::new(data_pub), "gw", &fast_config());
+        let subscriber = SequencedSubscriber::new(Box::new(data_sub), fast_config())
+            .with_retransmit_requests(Box::new(request_pub));
+
+        publisher.publish(&message(1, "gw")).unwrap();
+        // Message 2 reaches the buffer but not the wire
+        lose(&publisher, 2);
+        publisher.publish(&message(3, "gw")).unwrap();
+
+        let (delivered, events) = collect(&subscriber);
+        assert_eq!(delivered, vec![1]);
+        assert_eq!(
+            events,
+            vec!["Gap { source: \"gw\", topic: \"\", from: 2, to: 2 }"]
+        );
+
+        let mut outcomes = Vec::new();
+        request_sub
+            .poll(&mut |data| outcomes.push(publisher.handle_request(data).unwrap()))
+            .unwrap();
+        assert_eq!(outcomes, vec![Some(RetransmitOutcome::Resent(1))]);
+
+        let (delivered, _) = collect(&subscriber);
+        assert_eq!(delivered, vec![2, 3]);
+        assert_eq!(subscriber.stats().lost, 0);
+    }
+
+    #[test]
+    fn test_too_old_gap_is_rejected_and_lost() {
+        let (data_pub, data_sub) = channel_pair(100);
+        let (request_pub, request_sub) = channel_pair(100);
+        let publisher = SequencedPublisher::new(Box::new(data_pub), "gw", &fast_config());
+        let subscriber = SequencedSubscriber::new(Box::new(data_sub), fast_config())
+            .with_retransmit_requests(Box::new(request_pub));
+
+        publisher.publish(&message(1, "gw")).unwrap();
+        collect(&subscriber);
+        // 2..=6 go missing; the buffer of 4 only keeps 4..=7
+        for seq in 2..=6 {
+            lose(&publisher, seq);
+        }
+        publisher.buffer.lock().unwrap().drain(..3);
+        publisher.publish(&message(7, "gw")).unwrap();
+        assert_eq!(publisher.buffered_range(""), Some((4, 7)));
+
+        collect(&subscriber);
+        request_sub
+            .poll(&mut |data| {
+                assert_eq!(
+                    publisher.handle_request(data).unwrap(),
+                    Some(RetransmitOutcome::Rejected)
+                );
+            })
+            .unwrap();
+
+        let (delivered, events) = collect(&subscriber);
+        assert_eq!(delivered, vec![7]);
+        assert_eq!(
+            events,
+            vec!["Lost { source: \"gw\", topic: \"\", from: 2, to: 6 }"]
+        );
+    }
+
+    #[test]
+    fn test_gap_times_out_without_requester() {
+        let (publisher, subscriber) = channel_pair(100);
+        let subscriber = SequencedSubscriber::new(Box::new(subscriber), fast_config());
+
+        publisher.publish(&message(1, "gw")).unwrap();
+        publisher.publish(&message(3, "gw")).unwrap();
+        assert_eq!(collect(&subscriber).0, vec![1]);
+
+        std::thread::sleep(Duration::from_millis(30));
+        let (delivered, events) = collect(&subscriber);
+        assert_eq!(delivered, vec![3]);
+        assert_eq!(
+            events,
+            vec!["Lost { source: \"gw\", topic: \"\", from: 2, to: 2 }"]
+        );
+    }
+
+    #[test]
+    fn test_pending_overflow_gives_up() {
+        let (publisher, subscriber) = channel_pair(100);
+        let subscriber = SequencedSubscriber::new(Box::new(subscriber), fast_config());
+
+        publisher.publish(&message(1, "gw")).unwrap();
+        for seq in 10..=18 {
+            publisher.publish(&message(seq, "gw")).unwrap();
+        }
+        let (delivered, events) = collect(&subscriber);
+
+        assert_eq!(
+            delivered,
+            (std::iter::once(1).chain(10..=18)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            events.last().unwrap(),
+            "Lost { source: \"gw\", topic: \"\", from: 2, to: 9 }"
+        );
+    }
+
+    #[test]
+    fn test_restart_resets_source() {
+        let (publisher, subscriber) = channel_pair(100);
+        let subscriber = SequencedSubscriber::new(Box::new(subscriber), fast_config());
+
+        for seq in [0, 1, 2, 0, 1] {
+            publisher.publish(&message(seq, "gw")).unwrap();
+        }
+        let (delivered, events) = collect(&subscriber);
+
+        assert_eq!(delivered, vec![0, 1, 2, 0, 1]);
+        assert_eq!(events, vec!["Reset { source: \"gw\", topic: \"\" }"]);
+    }
+
+    #[test]
+    fn test_retransmit_ignores_other_sources() {
+        let (data_pub, _data_sub) = channel_pair(100);
+        let publisher = SequencedPublisher::new(Box::new(data_pub), "gw", &fast_config());
+        let request = RetransmitRequest {
+            source: "other".to_string(),
+            topic: String::new(),
+            from_sequence: 1,
+            to_sequence: 2,
+        };
+        assert_eq!(
+            publisher.retransmit(&request).unwrap(),
+            RetransmitOutcome::NotOurs
+        );
+    }
+
+    #[test]
+    fn test_retransmit_is_per_topic() {
+        let (data_pub, data_sub) = channel_pair(100);
+        let publisher = SequencedPublisher::new(Box::new(data_pub), "gw", &fast_config());
+
+        for seq in 0..3 {
+            publisher.publish(&on_topic(seq, "gw", "md.a")).unwrap();
+            publisher.publish(&on_topic(seq, "gw", "md.b")).unwrap();
+        }
+        // Capacity 4 evicted md.a 0 and md.b 0
+        assert_eq!(publisher.buffered_range("md.a"), Some((1, 2)));
+        data_sub.poll(&mut |_| {}).unwrap();
+
+        let request = RetransmitRequest {
+            source: "gw".to_string(),
+            topic: "md.b".to_string(),
+            from_sequence: 1,
+            to_sequence: 2,
+        };
+        assert_eq!(
+            publisher.retransmit(&request).unwrap(),
+            RetransmitOutcome::Resent(2)
+        );
+        let mut resent = Vec::new();
+        data_sub
+            .poll(&mut |data| {
+                let header = WireMessage::header(data).unwrap();
+                resent.push((header.topic, header.sequence));
+            })
+            .unwrap();
+        assert_eq!(
+            resent,
+            vec![("md.b".to_string(), 1), ("md.b".to_string(), 2)]
+        );
+    }
+}
@@ -0,0 +1,730 @@
+//! Shared Memory Transport Implementation
+//!
+//! Single-producer, multi-consumer ring buffer in a memory-mapped file,
+//! normally under /dev/shm. Co-located processes exchange messages without
+//! a broker or media driver.
+//!
+//! # Layout
+//!
+//! ```text
+//! [header: 4 KiB]
+//!   magic, version, slot size, slot count
+//!   write sequence              (own cache line)
+//!   consumer table              (position + heartbeat per consumer)
+//! [slot 0][slot 1]...[slot N-1]
+//!   each slot: [sequence + 1: u64][len: u32][pad: u32][data]
+//! ```
+//!
+//! The publisher writes message `s` into slot `s % N` seqlock-style: it
+//! marks the slot as being written, copies the data, then stamps `s + 1`.
+//! Consumers keep their own cursor and validate the stamp before and after
+//! copying, so a consumer that the publisher has lapped notices and skips
+//! ahead instead of reading torn data.
+//!
+//! Consumers publish their position in the header. The publisher reports
+//! consumers that fall behind and, with `SlowConsumerPolicy::Backpressure`,
+//! refuses to lap them.
+
+use std::fs::{File, OpenOptions};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering, fence};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use memmap2::MmapRaw;
+
+use super::config::{SharedMemoryConfig, SlowConsumerPolicy};
+use crate::application::error::TransportError;
+use crate::application::traits::{Publisher, Subscriber};
+
+const MAGIC: u64 = u64::from_le_bytes(*b"ATHNSHM1");
+const VERSION: u32 = 1;
+
+const HEADER_SIZE: usize = 4096;
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 8;
+const SLOT_SIZE_OFFSET: usize = 12;
+const SLOT_COUNT_OFFSET: usize = 16;
+const WRITE_SEQUENCE_OFFSET: usize = 64;
+const CONSUMERS_OFFSET: usize = 128;
+
+/// Consumers that can attach to one ring
+pub const MAX_CONSUMERS: usize = 32;
+const CONSUMER_STRIDE: usize = 64;
+
+const SLOT_HEADER_SIZE: usize = 16;
+/// Slot stamp while the publisher is writing it
+const WRITING: u64 = u64::MAX;
+
+/// Consumer entry state when unclaimed; otherwise it holds the owner's token
+const CONSUMER_FREE: u64 = 0;
+
+/// A consumer attached to the ring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsumerStatus {
+    /// Consumer table index
+    pub id: usize,
+    /// Next sequence the consumer will read
+    pub position: u64,
+    /// Messages published but not yet read
+    pub lag: u64,
+    /// No poll within the consumer timeout
+    pub stale: bool,
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Mapped ring shared by the publisher and subscriber handles
+struct Ring {
+    map: MmapRaw,
+    slot_size: usize,
+    slot_count: u64,
+}
+
+impl Ring {
+    fn atomic_u64(&self, offset: usize) -> &AtomicU64 {
+        debug_assert!(offset.is_multiple_of(8) && offset + 8 <= self.map.len());
+        // SAFETY: in bounds, 8-byte aligned (the mapping is page aligned) and
+        // only ever accessed atomically by every process sharing the file.
+        unsafe { &*(self.map.as_mut_ptr().add(offset) as *const AtomicU64) }
+    }
+
+    fn atomic_u32(&self, offset: usize) -> &AtomicU32 {
+        debug_assert!(offset.is_multiple_of(4) && offset + 4 <= self.map.len());
+        // SAFETY: as for `atomic_u64`
+        unsafe { &*(self.map.as_mut_ptr().add(offset) as *const AtomicU32) }
+    }
+
+    fn write_sequence(&self) -> &AtomicU64 {
+        self.atomic_u64(WRITE_SEQUENCE_OFFSET)
+    }
+
+    fn consumer_state(&self, id: usize) -> &AtomicU64 {
+        self.atomic_u64(CONSUMERS_OFFSET + id * CONSUMER_STRIDE)
+    }
+
+    fn consumer_position(&self, id: usize) -> &AtomicU64 {
+        self.atomic_u64(CONSUMERS_OFFSET + id * CONSUMER_STRIDE + 8)
+    }
+
+    fn consumer_heartbeat(&self, id: usize) -> &AtomicU64 {
+        self.atomic_u64(CONSUMERS_OFFSET + id * CONSUMER_STRIDE + 16)
+    }
+
+    fn slot_offset(&self, sequence: u64) -> usize {
+        HEADER_SIZE + (sequence & (self.slot_count - 1)) as usize * self.slot_size
+    }
+
+    fn slot_stamp(&self, sequence: u64) -> &AtomicU64 {
+        self.atomic_u64(self.slot_offset(sequence))
+    }
+
+    fn slot_len(&self, sequence: u64) -> &AtomicU32 {
+        self.atomic_u32(self.slot_offset(sequence) + 8)
+    }
+
+    fn slot_data(&self, sequence: u64) -> *mut u8 {
+        // SAFETY: slot_offset is within the mapping
+        unsafe {
+            self.map
+                .as_mut_ptr()
+                .add(self.slot_offset(sequence) + SLOT_HEADER_SIZE)
+        }
+    }
+
+    fn max_message_len(&self) -> usize {
+        self.slot_size - SLOT_HEADER_SIZE
+    }
+
+    fn consumers(&self, timeout_ms: u64) -> Vec<ConsumerStatus> {
+        let written = self.write_sequence().load(Ordering::Acquire);
+        let now = now_ns();
+        (0..MAX_CONSUMERS)
+            .filter(|&id| self.consumer_state(id).load(Ordering::Acquire) != CONSUMER_FREE)
+            .map(|id| {
+                let position = self.consumer_position(id).load(Ordering::Acquire);
+                let heartbeat = self.consumer_heartbeat(id).load(Ordering::Relaxed);
+                ConsumerStatus {
+                    id,
+                    position,
+                    lag: written.saturating_sub(position),
+                    stale: now.saturating_sub(heartbeat) > timeout_ms * 1_000_000,
+                }
+            })
+            .collect()
+    }
+}
+
+fn validate(config: &SharedMemoryConfig) -> Result<(), TransportError> {
+    if config.slot_size <= SLOT_HEADER_SIZE || !config.slot_size.is_multiple_of(64) {
+        return Err(TransportError::Config(format!(
+            "slot_size {} must be a multiple of 64 above {}",
+            config.slot_size, SLOT_HEADER_SIZE
+        )));
+    }
+    if !config.slot_count.is_power_of_two() || config.slot_size > u32::MAX as usize {
+        return Err(TransportError::Config(format!(
+            "slot_count {} must be a power of two",
+            config.slot_count
+        )));
+    }
+    Ok(())
+}
+
+fn file_len(config: &SharedMemoryConfig) -> usize {
+    HEADER_SIZE + config.slot_size * config.slot_count
+}
+
+fn map_file(file: &File) -> Result<MmapRaw, TransportError> {
+    MmapRaw::map_raw(file).map_err(TransportError::from)
+}
+
+/// Shared memory ring publisher
+///
+/// There must be one publisher per ring; it is safe to share between
+/// threads of that process.
+pub struct ShmPublisher {
+    ring: Ring,
+    config: SharedMemoryConfig,
+    /// Serializes writers within this process
+    next_sequence: Mutex<u64>,
+}
+
+impl ShmPublisher {
+    /// Create the ring, or resume one with the same geometry
+    ///
+    /// Resuming keeps the write sequence, so attached consumers carry on.
+    pub fn create(config: SharedMemoryConfig) -> Result<Self, TransportError> {
+        validate(&config)?;
+        if let Some(parent) = config.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&config.path)?;
+
+        let len = file_len(&config) as u64;
+        let resumable = file.metadata()?.len() == len;
+        if !resumable {
+            file.set_len(0)?;
+            file.set_len(len)?;
+        }
+
+        let ring = Ring {
+            map: map_file(&file)?,
+            slot_size: config.slot_size,
+            slot_count: config.slot_count as u64,
+        };
+        let resumed = resumable
+            && ring.atomic_u64(MAGIC_OFFSET).load(Ordering::Acquire) == MAGIC
+            && ring.atomic_u32(VERSION_OFFSET).load(Ordering::Acquire) == VERSION
+            && ring.atomic_u32(SLOT_SIZE_OFFSET).load(Ordering::Acquire) as usize
+                == config.slot_size
+            && ring.atomic_u64(SLOT_COUNT_OFFSET).load(Ordering::Acquire) == ring.slot_count;
+
+        let next_sequence = if resumed {
+            ring.write_sequence().load(Ordering::Acquire)
+        } else {
+            ring.atomic_u32(VERSION_OFFSET)
+                .store(VERSION, Ordering::Relaxed);
+            ring.atomic_u32(SLOT_SIZE_OFFSET)
+                .store(config.slot_size as u32, Ordering::Relaxed);
+            ring.atomic_u64(SLOT_COUNT_OFFSET)
+                .store(ring.slot_count, Ordering::Relaxed);
+            ring.write_sequence().store(0, Ordering::Relaxed);
+            ring.atomic_u64(MAGIC_OFFSET)
+                .store(MAGIC, Ordering::Release);
+            0
+        };
+
+        Ok(Self {
+            ring,
+            config,
+            next_sequence: Mutex::new(next_sequence),
+        })
+    }
+
+    /// Sequence the next message will get
+    pub fn sequence(&self) -> u64 {
+        self.ring.write_sequence().load(Ordering::Acquire)
+    }
+
+    /// Attached consumers
+    pub fn consumers(&self) -> Vec<ConsumerStatus> {
+        self.ring.consumers(self.config.consumer_timeout_ms)
+    }
+
+    /// Live consumers lagging by `slow_consumer_lag` or more
+    pub fn slow_consumers(&self) -> Vec<ConsumerStatus> {
+        self.consumers()
+            .into_iter()
+            .filter(|c| !c.stale && c.lag >= self.config.slow_consumer_lag as u64)
+            .collect()
+    }
+
+    /// Would writing `sequence` overwrite a live consumer's unread message?
+    fn laps_live_consumer(&self, sequence: u64) -> bool {
+        self.consumers()
+            .iter()
+            .any(|c| !c.stale && sequence >= c.position + self.ring.slot_count)
+    }
+}
+
+impl Publisher for ShmPublisher {
+    fn publish(&self, data: &[u8]) -> Result<(), TransportError> {
+        if data.len() > self.ring.max_message_len() {
+            return Err(TransportError::Serialization(format!(
+                "message of {} bytes exceeds slot capacity {}",
+                data.len(),
+                self.ring.max_message_len()
+            )));
+        }
+
+        let mut next = self.next_sequence.lock().unwrap_or_else(|e| e.into_inner());
+        let sequence = *next;
+        if self.config.slow_consumer_policy == SlowConsumerPolicy::Backpressure
+            && sequence >= self.ring.slot_count
+            && self.laps_live_consumer(sequence)
+        {
+            return Err(TransportError::Full);
+        }
+
+        let stamp = self.ring.slot_stamp(sequence);
+        stamp.store(WRITING, Ordering::Relaxed);
+        fence(Ordering::Release);
+        // SAFETY: the slot holds at least max_message_len bytes; consumers
+        // reading concurrently discard the copy when the stamp changes.
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), self.ring.slot_data(sequence), data.len());
+        }
+        self.ring
+            .slot_len(sequence)
+            .store(data.len() as u32, Ordering::Relaxed);
+        stamp.store(sequence + 1, Ordering::Release);
+        self.ring
+            .write_sequence()
+            .store(sequence + 1, Ordering::Release);
+
+        *next = sequence + 1;
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+}
+
+/// Per-subscriber read state
+struct Cursor {
+    next: u64,
+    buffer: Vec<u8>,
+    lapped: u64,
+}
+
+/// Shared memory ring subscriber
+///
+/// Starts at the publisher's current position; earlier messages are not
+/// delivered.
+pub struct ShmSubscriber {
+    ring: Ring,
+    id: usize,
+    token: u64,
+    cursor: Mutex<Cursor>,
+}
+
+impl ShmSubscriber {
+    /// Attach to an existing ring
+    pub fn open(config: &SharedMemoryConfig) -> Result<Self, TransportError> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&config.path)
+            .map_err(|e| {
+                TransportError::ConnectionFailed(format!("{}: {}", config.path.display(), e))
+            })?;
+        if (file.metadata()?.len() as usize) < HEADER_SIZE {
+            return Err(TransportError::ConnectionFailed(format!(
+                "{} is not initialized",
+                config.path.display()
+            )));
+        }
+
+        let mut ring = Ring {
+            map: map_file(&file)?,
+            slot_size: 0,
+            slot_count: 0,
+        };
+        if ring.atomic_u64(MAGIC_OFFSET).load(Ordering::Acquire) != MAGIC
+            || ring.atomic_u32(VERSION_OFFSET).load(Ordering::Acquire) != VERSION
+        {
+            return Err(TransportError::ConnectionFailed(format!(
+                "{} is not a ring buffer",
+                config.path.display()
+            )));
+        }
+        ring.slot_size = ring.atomic_u32(SLOT_SIZE_OFFSET).load(Ordering::Acquire) as usize;
+        ring.slot_count = ring.atomic_u64(SLOT_COUNT_OFFSET).load(Ordering::Acquire);
+        if ring.map.len() < HEADER_SIZE + ring.slot_size * ring.slot_count as usize {
+            return Err(TransportError::ConnectionFailed(format!(
+                "{} is truncated",
+                config.path.display()
+            )));
+        }
+
+        // Until the heartbeat below lands the entry looks stale, so the
+        // publisher ignores its position
+        let (id, token) = Self::claim_consumer(&ring, config.consumer_timeout_ms)?;
+        let next = ring.write_sequence().load(Ordering::Acquire);
+        ring.consumer_position(id).store(next, Ordering::Release);
+        ring.consumer_heartbeat(id)
+            .store(now_ns(), Ordering::Release);
+
+        Ok(Self {
+            ring,
+            id,
+            token,
+            cursor: Mutex::new(Cursor {
+                next,
+                buffer: Vec::new(),
+                lapped: 0,
+            }),
+        })
+    }
+
+    /// Take a free consumer entry, or one whose owner stopped polling
+    ///
+    /// Entries hold their owner's token, so two processes reclaiming the same
+    /// stale entry cannot both win.
+    fn claim_consumer(ring: &Ring, timeout_ms: u64) -> Result<(usize, u64), TransportError> {
+        static NEXT_TOKEN: AtomicU32 = AtomicU32::new(1);
+        let token = (u64::from(std::process::id()) << 32)
+            | u64::from(NEXT_TOKEN.fetch_add(1, Ordering::Relaxed));
+
+        let stale: Vec<usize> = ring
+            .consumers(timeout_ms)
+            .iter()
+            .filter(|c| c.stale)
+            .map(|c| c.id)
+            .collect();
+        for id in 0..MAX_CONSUMERS {
+            let state = ring.consumer_state(id);
+            let current = state.load(Ordering::Acquire);
+            if current != CONSUMER_FREE && !stale.contains(&id) {
+                continue;
+            }
+            if state
+                .compare_exchange(current, token, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return Ok((id, token));
+            }
+        }
+        Err(TransportError::ConnectionFailed(format!(
+            "all {} consumer entries in use",
+            MAX_CONSUMERS
+        )))
+    }
+
+    /// Consumer table index
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Messages lost to being lapped by the publisher
+    pub fn lapped(&self) -> u64 {
+        self.cursor.lock().unwrap_or_else(|e| e.into_inner()).lapped
+    }
+}
+
+impl Subscriber for ShmSubscriber {
+    fn poll(&self, handler: &mut dyn FnMut(&[u8])) -> Result<usize, TransportError> {
+        let ring = &self.ring;
+        let mut cursor = self.cursor.lock().unwrap_or_else(|e| e.into_inner());
+        let cursor = &mut *cursor;
+        ring.consumer_heartbeat(self.id)
+            .store(now_ns(), Ordering::Release);
+
+        let mut count = 0;
+        loop {
+            let written = ring.write_sequence().load(Ordering::Acquire);
+            if cursor.next >= written {
+                break;
+            }
+
+            let sequence = cursor.next;
+            let expected = sequence + 1;
+            let stamp = ring.slot_stamp(sequence);
+            let mut valid =
+                written - sequence <= ring.slot_count && stamp.load(Ordering::Acquire) == expected;
+            if valid {
+                let len = (ring.slot_len(sequence).load(Ordering::Relaxed) as usize)
+                    .min(ring.max_message_len());
+                cursor.buffer.resize(len, 0);
+                // SAFETY: len is clamped to the slot; a torn copy is caught
+                // by re-checking the stamp below.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        ring.slot_data(sequence),
+                        cursor.buffer.as_mut_ptr(),
+                        len,
+                    );
+                }
+                fence(Ordering::Acquire);
+                valid = stamp.load(Ordering::Relaxed) == expected;
+            }
+
+            if !valid {
+                // Lapped: resume at the oldest slot the publisher cannot be writing
+                let written = ring.write_sequence().load(Ordering::Acquire);
+                let resume = (written + 1)
+                    .saturating_sub(ring.slot_count)
+                    .max(sequence + 1);
+                tracing::warn!(
+                    "Shared memory consumer {} lapped; skipped {} messages",
+                    self.id,
+                    resume - sequence
+                );
+                cursor.lapped += resume - sequence;
+                cursor.next = resume;
+                continue;
+            }
+
+            handler(&cursor.buffer);
+            cursor.next += 1;
+            count += 1;
+        }
+
+        ring.consumer_position(self.id)
+            .store(cursor.next, Ordering::Release);
+        Ok(count)
+    }
+
+    fn has_messages(&self) -> bool {
+        let next = self.cursor.lock().unwrap_or_else(|e| e.into_inner()).next;
+        self.ring.write_sequence().load(Ordering::Acquire) > next
+    }
+}
+
+impl Drop for ShmSubscriber {
+    fn drop(&mut self) {
+        // Leave the entry alone if another consumer reclaimed it
+        let _ = self.ring.consumer_state(self.id).compare_exchange(
+            self.token,
+            CONSUMER_FREE,
+            Ordering::AcqRel,
+            Ordering::Relaxed,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dir: &tempfile::TempDir, slot_count: usize) -> SharedMemoryConfig {
+        SharedMemoryConfig {
+            slot_size: 128,
+            slot_count,
+            slow_consumer_lag: slot_count / 2,
+            ..SharedMemoryConfig::new(dir.path().join("ring"))
+        }
+    }
+
+    fn drain(subscriber: &ShmSubscriber) -> Vec<Vec<u8>> {
+        let mut received = Vec::new();
+        subscriber
+            .poll(&mut |data| received.push(data.to_vec()))
+            .unwrap();
+        received
+    }
+
+    #[test]
+    fn test_publish_to_multiple_consumers() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(&dir, 8);
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        let a = ShmSubscriber::open(&config).unwrap();
+        let b = ShmSubscriber::open(&config).unwrap();
+        assert_ne!(a.id(), b.id());
+
+        publisher.publish(b"hello").unwrap();
+        publisher.publish(b"world").unwrap();
+
+        assert!(a.has_messages());
+        assert_eq!(drain(&a), vec![b"hello".to_vec(), b"world".to_vec()]);
+        assert!(!a.has_messages());
+        assert_eq!(drain(&b), vec![b"hello".to_vec(), b"world".to_vec()]);
+        assert_eq!(publisher.consumers().len(), 2);
+    }
+
+    #[test]
+    fn test_subscriber_starts_at_live_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(&dir, 8);
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        publisher.publish(b"before").unwrap();
+
+        let subscriber = ShmSubscriber::open(&config).unwrap();
+        publisher.publish(b"after").unwrap();
+        assert_eq!(drain(&subscriber), vec![b"after".to_vec()]);
+    }
+
+    #[test]
+    fn test_lapped_consumer_skips_ahead() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(&dir, 4);
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        let subscriber = ShmSubscriber::open(&config).unwrap();
+
+        for i in 0..10u8 {
+            publisher.publish(&[i]).unwrap();
+        }
+
+        // Only the newest slots minus the one that may be rewritten survive
+        let received = drain(&subscriber);
+        assert_eq!(received, vec![vec![7], vec![8], vec![9]]);
+        assert_eq!(subscriber.lapped(), 7);
+    }
+
+    #[test]
+    fn test_backpressure_policy_protects_slow_consumer() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SharedMemoryConfig {
+            slow_consumer_policy: SlowConsumerPolicy::Backpressure,
+            ..config(&dir, 4)
+        };
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        let subscriber = ShmSubscriber::open(&config).unwrap();
+
+        for i in 0..4u8 {
+            publisher.publish(&[i]).unwrap();
+        }
+        assert_eq!(publisher.slow_consumers().len(), 1);
+        assert_eq!(publisher.slow_consumers()[0].lag, 4);
+        assert!(matches!(publisher.publish(&[4]), Err(TransportError::Full)));
+
+        assert_eq!(drain(&subscriber).len(), 4);
+        assert!(publisher.slow_consumers().is_empty());
+        publisher.publish(&[4]).unwrap();
+        assert_eq!(drain(&subscriber), vec![vec![4]]);
+        assert_eq!(subscriber.lapped(), 0);
+    }
+
+    #[test]
+    fn test_stale_consumer_ignored_and_reclaimed() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SharedMemoryConfig {
+            slow_consumer_policy: SlowConsumerPolicy::Backpressure,
+            consumer_timeout_ms: 0,
+            ..config(&dir, 4)
+        };
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        let abandoned = ShmSubscriber::open(&config).unwrap();
+        let id = abandoned.id();
+        // Simulate a crashed process: entry stays claimed, heartbeat stops
+        std::mem::forget(abandoned);
+        std::thread::sleep(std::time::Duration::from_millis(2));
+
+        for i in 0..8u8 {
+            publisher.publish(&[i]).unwrap();
+        }
+        assert!(publisher.consumers()[0].stale);
+
+        let subscriber = ShmSubscriber::open(&config).unwrap();
+        assert_eq!(subscriber.id(), id);
+    }
+
+    #[test]
+    fn test_oversized_message_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let publisher = ShmPublisher::create(config(&dir, 4)).unwrap();
+        assert!(publisher.publish(&[0; 112]).is_ok());
+        assert!(matches!(
+            publisher.publish(&[0; 113]),
+            Err(TransportError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_publisher_restart_resumes_sequence() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(&dir, 8);
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        let subscriber = ShmSubscriber::open(&config).unwrap();
+        publisher.publish(b"one").unwrap();
+        drop(publisher);
+
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        assert_eq!(publisher.sequence(), 1);
+        publisher.publish(b"two").unwrap();
+        assert_eq!(drain(&subscriber), vec![b"one".to_vec(), b"two".to_vec()]);
+    }
+
+    #[test]
+    fn test_open_missing_or_invalid_ring() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(&dir, 4);
+        assert!(matches!(
+            ShmSubscriber::open(&config),
+            Err(TransportError::ConnectionFailed(_))
+        ));
+
+        std::fs::write(&config.path, vec![0u8; HEADER_SIZE]).unwrap();
+        assert!(matches!(
+            ShmSubscriber::open(&config),
+            Err(TransportError::ConnectionFailed(_))
+        ));
+
+        let bad = SharedMemoryConfig {
+            slot_count: 3,
+            ..config
+        };
+        assert!(matches!(
+            ShmPublisher::create(bad),
+            Err(TransportError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_reader_sees_ordered_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SharedMemoryConfig {
+            slow_consumer_policy: SlowConsumerPolicy::Backpressure,
+            ..config(&dir, 64)
+        };
+        let publisher = ShmPublisher::create(config.clone()).unwrap();
+        let subscriber = ShmSubscriber::open(&config).unwrap();
+        const COUNT: u64 = 10_000;
+
+        let reader = std::thread::spawn(move || {
+            let mut expected = 0u64;
+            while expected < COUNT {
+                subscriber
+                    .poll(&mut |data| {
+                        let value = u64::from_le_bytes(data.try_into().unwrap());
+                        assert_eq!(value, expected);
+                        expected += 1;
+                    })
+                    .unwrap();
+            }
+            subscriber.lapped()
+        });
+
+        let mut i = 0u64;
+        while i < COUNT {
+            match publisher.publish(&i.to_le_bytes()) {
+                Ok(()) => i += 1,
+                Err(TransportError::Full) => std::thread::yield_now(),
+                Err(e) => panic!("{}", e),
+            }
+        }
+        assert_eq!(reader.join().unwrap(), 0);
+    }
+}
@@ -5,6 +5,21 @@
 //!
 //! - **Channel** (default): In-process communication via crossbeam channels
 //! - **Aeron**: Ultra-low latency reliable messaging via Aeron IPC/UDP (feature-gated)
+//! - **Shared memory**: SPMC ring buffer in a memory-mapped file, no media driver (`shm` feature)
+//!
+//! `RecordingPublisher` captures published messages to compressed chunk files
+//! and `Replayer` publishes a capture back onto any `Publisher`.
+//!
+//! `SequencedPublisher` and `SequencedSubscriber` wrap any transport with
+//! per-stream gap detection, duplicate suppression and retransmission.
+//!
+//! Typed messages are framed by a `Codec`: bincode (`WireMessage`) or a
+//! fixed-layout format read in place through `FrameView` without allocating.
+//!
+//! Messages carry a hierarchical topic (`md.binance.BTCUSDT.depth`).
+//! `TopicBus` routes them in-process to any number of subscribers, each
+//! with wildcard subscriptions and its own queue; `TopicFilter` gives the
+//! same subscriptions to transports that deliver the full stream.
 //!
 //! # Architecture
 //!
@@ -49,13 +64,37 @@ pub mod infrastructure;
 
 // Re-export application layer types (ports/abstractions)
 pub use application::{
-    MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT, MSG_SIGNAL, MSG_SNAPSHOT_REQUEST, MSG_TRADE,
-    MessageType, Publisher, Subscriber, TransportError, WireMessage,
+    BincodeCodec, Codec, CodecKind, CodecMessage, DepthUpdateView, FRAME_MAGIC, FixedCodec,
+    FixedLayout, FrameView, LevelsView, MSG_DEPTH_UPDATE, MSG_ORDER_BOOK_SNAPSHOT,
+    MSG_RETRANSMIT_REJECT, MSG_RETRANSMIT_REQUEST, MSG_SIGNAL, MSG_SNAPSHOT_REQUEST, MSG_TRADE,
+    MessageType, Publisher, RetransmitRequest, SCHEMA_VERSION, SignalView, SnapshotView,
+    Subscriber, TOPIC_SEPARATOR, TopicPattern, TradeView, TransportError, WireHeader, WireMessage,
+    is_fixed_frame, market_data_topic, parse_market_data_topic, signal_topic,
 };
 
 // Re-export infrastructure layer types (implementations)
-pub use infrastructure::{ChannelConfig, TransportConfig, TransportFactory, TransportType};
+pub use infrastructure::{
+    CaptureConfig, CaptureIter, CaptureReader, CaptureWriter, CapturedMessage, ChannelConfig,
+    ChunkInfo, RecordingPublisher, ReplaySpeed, ReplayStats, Replayer, RetransmitOutcome,
+    SequenceEvent, SequenceStats, SequencedPublisher, SequencedSubscriber, SequencingConfig,
+    SharedMemoryConfig, SlowConsumerPolicy, TopicFilter, TransportConfig, TransportFactory,
+    TransportType,
+};
+
+// Re-export Aeron types when feature is enabled
+#[cfg(feature = "aeron")]
+pub use infrastructure::{
+    AeronClient, AeronPublisher, AeronSubscriber, FragmentAssembler, FragmentHeader, Publication,
+    RusteronClient, Subscription,
+};
+
+// Re-export shared memory types when feature is enabled
+#[cfg(feature = "shm")]
+pub use infrastructure::{ConsumerStatus, ShmPublisher, ShmSubscriber};
 
 // Re-export channel types when feature is enabled
 #[cfg(feature = "channel")]
-pub use infrastructure::{ChannelPublisher, ChannelSubscriber, channel_pair};
+pub use infrastructure::{
+    BackpressurePolicy, ChannelPublisher, ChannelSubscriber, TopicBus, TopicBusConfig, TopicPolicy,
+    TopicPublisher, TopicSubscriber, channel_pair,
+};
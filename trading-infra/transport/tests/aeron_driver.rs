@@ -0,0 +1,104 @@
+//! Aeron transport against an embedded media driver
+//!
+//! Runs `TransportFactory` endpoints through a real driver: ordered
+//! delivery, messages larger than the MTU reassembled from fragments, and
+//! back-pressure surfacing as `Full` until the subscriber catches up.
+
+#![cfg(feature = "aeron-driver")]
+
+use rusteron_media_driver::testing::EmbeddedDriver;
+use std::time::{Duration, Instant};
+use transport::{Publisher, Subscriber, TransportConfig, TransportError, TransportFactory};
+
+fn pair(driver: &EmbeddedDriver, channel: &str) -> (Box<dyn Publisher>, Box<dyn Subscriber>) {
+    let mut config = TransportConfig::aeron_ipc(1001);
+    config.aeron.channel = channel.to_string();
+    config.aeron.media_driver_dir = driver.dir().to_string();
+    let (publisher, subscriber) = TransportFactory::create_pair(&config).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !publisher.is_active() {
+        assert!(Instant::now() < deadline, "publication never connected");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    (publisher, subscriber)
+}
+
+/// Poll until `count` messages arrived
+fn receive(subscriber: &dyn Subscriber, count: usize) -> Vec<Vec<u8>> {
+    let mut received = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while received.len() < count {
+        assert!(
+            Instant::now() < deadline,
+            "received {} of {} messages",
+            received.len(),
+            count
+        );
+        subscriber
+            .poll(&mut |data| received.push(data.to_vec()))
+            .unwrap();
+    }
+    received
+}
+
+#[test]
+fn test_messages_arrive_in_order() {
+    let driver = EmbeddedDriver::launch().unwrap();
+    let (publisher, subscriber) = pair(&driver, "aeron:ipc");
+
+    for i in 0..100u32 {
+        publisher.publish(&i.to_le_bytes()).unwrap();
+    }
+    let received = receive(subscriber.as_ref(), 100);
+    for (i, message) in received.iter().enumerate() {
+        assert_eq!(message.as_slice(), (i as u32).to_le_bytes());
+    }
+}
+
+#[test]
+fn test_messages_larger_than_mtu_are_reassembled() {
+    let driver = EmbeddedDriver::launch().unwrap();
+    let (publisher, subscriber) = pair(&driver, "aeron:ipc?mtu=4096");
+
+    // Interleave small messages with ones spanning many fragments
+    let messages: Vec<Vec<u8>> = (0..10u8)
+        .map(|i| {
+            let len = if i % 2 == 0 { 100_000 + i as usize } else { 10 };
+            (0..len).map(|b| (b as u8).wrapping_add(i)).collect()
+        })
+        .collect();
+    for message in &messages {
+        publisher.publish(message).unwrap();
+    }
+    assert_eq!(receive(subscriber.as_ref(), messages.len()), messages);
+}
+
+#[test]
+fn test_back_pressure_until_subscriber_polls() {
+    let driver = EmbeddedDriver::launch().unwrap();
+    // A 64 KiB term lets the publisher run 32 KiB ahead of the subscriber
+    let (publisher, subscriber) = pair(&driver, "aeron:ipc?term-length=65536");
+
+    let message = [7u8; 1024];
+    let mut published = 0;
+    let result = loop {
+        match publisher.publish(&message) {
+            Ok(()) => published += 1,
+            Err(e) => break e,
+        }
+        assert!(published < 1_000, "publisher was never back-pressured");
+    };
+    assert!(matches!(result, TransportError::Full), "{:?}", result);
+    assert!(published > 0);
+
+    // Draining the subscriber frees the window once the driver sees it
+    assert_eq!(receive(subscriber.as_ref(), published).len(), published);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while let Err(e) = publisher.publish(&message) {
+        assert!(matches!(e, TransportError::Full), "{:?}", e);
+        assert!(Instant::now() < deadline, "window never reopened");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(receive(subscriber.as_ref(), 1), vec![message.to_vec()]);
+}